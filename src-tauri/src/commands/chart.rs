@@ -3,14 +3,42 @@
 // ============================================================================
 
 use crate::AppState;
-use crate::models::chart::{ChartConfig, ChartData, ChartType};
+use crate::commands::data_export::{ExportSource, record_export};
+use crate::commands::operations::create_history_entry;
+use crate::commands::settings::current_chart_defaults;
+use crate::data::chart_spec::build_chart_spec;
+use crate::data::decimal::{decimal_to_f64, is_numeric_dtype};
+use crate::data::export::{check_overwrite, write_atomic_checked};
+use crate::data::period::{DEFAULT_AGGREGATION, aggregation_expr};
+use crate::models::chart::{
+    AxisHint, AxisRange, AxisRangeOptions, ChartAxis, ChartConfig, ChartData, ChartDefaults, ChartFacet, ChartIssue,
+    ChartIssueLevel, ChartSmoothing, ChartSuggestion, ChartType, ChartValidation, DuplicateXAggregation,
+    EmptyBucketFill, LogTransform, NonPositivePolicy, QuantileBands, SeriesNormalization,
+};
+use crate::models::history::{OperationType, PeriodBucket, RollingFunction};
+use chrono::{Datelike, Months, NaiveDate};
 use polars::prelude::*;
 use serde_json::Value as JsonValue;
-use tauri::State;
+use std::io::Write;
+use tauri::{AppHandle, State};
 
 /// 生成图表数据（ECharts dataset 格式）
+///
+/// 指定 facet_column 时按分面列的每个值分别生成 dataset（小多图），
+/// 只返回行数最多的 facet_limit 个分面，空值行不参与分面
+///
+/// 未指定的直方图分箱数量、日期分箱粒度和分面数量使用图表默认设置（save_chart_defaults）
+///
+/// 指定 axis_range 时同时返回按分位数建议的 Y 轴范围（axis_ranges），
+/// 个别极端值不会把其余数据压成一条线
 #[tauri::command]
-pub async fn generate_chart_data(config: ChartConfig, state: State<'_, AppState>) -> Result<ChartData, String> {
+pub async fn generate_chart_data(
+    mut config: ChartConfig,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<ChartData, String> {
+    current_chart_defaults(&app)?.apply(&mut config);
+
     // 获取当前数据集
     let current_df = {
         let store = state
@@ -20,22 +48,410 @@ pub async fn generate_chart_data(config: ChartConfig, state: State<'_, AppState>
         store.get_current().ok_or("没有数据")?.clone()
     };
 
-    // 根据图表类型构建 dataset
-    let (dataset, data_count) = match config.chart_type {
-        ChartType::Line | ChartType::Bar | ChartType::Scatter => build_xy_dataset(&current_df, &config)?,
-        ChartType::Pie => build_pie_dataset(&current_df, &config)?,
-        ChartType::Histogram => build_histogram_dataset(&current_df, &config)?,
-    };
+    compute_chart_data(&current_df, &config)
+}
+
+/// 按图表配置生成图表数据（generate_chart_data 和 export_chart_spec 共用）
+fn compute_chart_data(df: &DataFrame, config: &ChartConfig) -> Result<ChartData, String> {
+    let axis_hints = build_axis_hints(df, config);
+
+    if let Some(facet_column) = &config.facet_column {
+        let (facets, omitted_facets) = build_facets(df, config, facet_column)?;
+        return Ok(ChartData {
+            chart_type: config.chart_type.clone(),
+            dataset: Vec::new(),
+            data_count: facets.iter().map(|facet| facet.data_count).sum(),
+            facets: Some(facets),
+            omitted_facets,
+            axis_hints,
+            axis_ranges: Vec::new(),
+        });
+    }
+
+    let (dataset, data_count, axis_ranges) = build_dataset(df, config)?;
 
     Ok(ChartData {
-        chart_type: config.chart_type,
+        chart_type: config.chart_type.clone(),
         dataset,
         data_count,
+        facets: None,
+        omitted_facets: 0,
+        axis_hints,
+        axis_ranges,
     })
 }
 
-/// 构建 X-Y 轴图表的 dataset（折线图、柱状图、散点图）
-fn build_xy_dataset(df: &DataFrame, config: &ChartConfig) -> Result<(Vec<Vec<JsonValue>>, usize), String> {
+// ============================================================================
+// 导出独立图表规格
+// ============================================================================
+/// 按图表配置生成图表数据，并写出独立的 JSON 图表规格（ECharts option + 内嵌 dataset）
+///
+/// 写出的文件可以嵌入其他工具（echarts.setOption(spec.option)），或在无界面环境中渲染；
+/// 分面图表在 facets 中为每个分面写出一个 option
+/// 与其他导出相同，先写临时文件再重命名，并记录到导出记录中
+///
+/// 参数：
+/// - config: 图表配置（未指定的选项使用图表默认设置）
+/// - output_path: 输出文件的完整路径（.json）
+/// - overwrite: 目标文件已存在时是否覆盖（可选，默认 true；为 false 且文件已存在时返回错误）
+///
+/// 前端调用示例：
+/// ```typescript
+/// await invoke('export_chart_spec', {
+///   config: { chart_type: 'line', x_column: 'date', y_columns: ['sales'] },
+///   outputPath: '/path/to/sales_chart.json'
+/// });
+/// ```
+#[tauri::command]
+pub async fn export_chart_spec(
+    mut config: ChartConfig,
+    output_path: String,
+    overwrite: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let overwrite = overwrite.unwrap_or(true);
+    check_overwrite(&output_path, overwrite).map_err(|e| e.to_string())?;
+    current_chart_defaults(&app)?.apply(&mut config);
+
+    let (current_df, source) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        (
+            store.get_current().ok_or("没有数据")?.clone(),
+            ExportSource::current(&store),
+        )
+    };
+
+    let shape = current_df.shape();
+    let path_clone = output_path.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let data = compute_chart_data(&current_df, &config)?;
+        let spec = build_chart_spec(&config, &data);
+        let content = serde_json::to_string_pretty(&spec).map_err(|e| e.to_string())?;
+        write_atomic_checked(&path_clone, overwrite, |file| {
+            file.write_all(content.as_bytes())?;
+            Ok(())
+        })
+        .map_err(|e| format!("写出图表规格失败: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    record_export(&state, source, "export_chart_spec", &output_path, shape)?;
+    Ok(output_path)
+}
+
+// ============================================================================
+// 图表汇总结果转为数据集
+// ============================================================================
+/// 把图表的汇总结果作为新的历史步骤（替换当前数据，可以撤销）
+///
+/// 适用于图表的汇总表本身就是需要继续处理的数据的情况，例如按月汇总的销售额
+///
+/// 支持的图表：
+/// - 折线图 / 柱状图 / 散点图：需要设置 x_bucket（按时间段汇总）或 x_duplicates（合并重复 X 值），
+///   平滑、对数变换、标准化等选项会一并应用
+/// - 热力图：两个分类列交叉汇总的结果
+/// - 直方图：分箱标签（bin）和计数（count）
+///
+/// 饼图不汇总数据，分面图表会产生多个结果，都不支持
+///
+/// 前端调用示例：
+/// ```typescript
+/// await invoke('materialize_chart_data', {
+///   config: { chart_type: 'line', x_column: 'date', y_columns: ['sales'], x_bucket: 'month' }
+/// });
+/// ```
+#[tauri::command]
+pub async fn materialize_chart_data(
+    mut config: ChartConfig,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    current_chart_defaults(&app)?.apply(&mut config);
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.ensure_unlocked().map_err(|e| e.to_string())?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let config_clone = config.clone();
+    let result_df = tauri::async_runtime::spawn_blocking(move || build_chart_frame(&current_df, &config_clone))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::MaterializeChart {
+        config: Box::new(config),
+    };
+    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// 按图表配置生成汇总后的 DataFrame（列顺序与图表 dataset 一致）
+fn build_chart_frame(df: &DataFrame, config: &ChartConfig) -> Result<DataFrame, String> {
+    if config.facet_column.is_some() {
+        return Err("分面图表不能转换为数据集，请先按分面列筛选后再转换".to_string());
+    }
+
+    match config.chart_type {
+        ChartType::Line | ChartType::Bar | ChartType::Scatter => {
+            if config.x_bucket.is_none() && config.x_duplicates.is_none() {
+                return Err(
+                    "图表没有汇总数据，请设置 x_bucket（按时间段汇总）或 x_duplicates（合并重复 X 值）".to_string(),
+                );
+            }
+            let (frame, columns) = build_xy_frame(df, config)?;
+            frame.select(&columns).map_err(|e| e.to_string())
+        }
+        ChartType::Heatmap => {
+            let (frame, columns) = build_heatmap_frame(df, config)?;
+            frame.select(&columns).map_err(|e| e.to_string())
+        }
+        ChartType::Histogram => {
+            let (dataset, _) = build_histogram_dataset(df, config)?;
+            let rows = dataset.get(1..).unwrap_or_default();
+            let bins: Vec<Option<&str>> = rows.iter().map(|row| row.first().and_then(|v| v.as_str())).collect();
+            let counts: Vec<Option<u64>> = rows.iter().map(|row| row.get(1).and_then(|v| v.as_u64())).collect();
+            DataFrame::new(vec![
+                Series::new("bin".into(), bins).into(),
+                Series::new("count".into(), counts).into(),
+            ])
+            .map_err(|e| e.to_string())
+        }
+        ChartType::Pie => Err("饼图不汇总数据，请改用柱状图并设置 x_duplicates".to_string()),
+    }
+}
+
+/// 根据对数变换选项生成坐标轴提示（没有变换的坐标轴不返回）
+fn build_axis_hints(df: &DataFrame, config: &ChartConfig) -> Vec<AxisHint> {
+    let Some(transform) = &config.log_transform else {
+        return Vec::new();
+    };
+
+    match config.chart_type {
+        ChartType::Line | ChartType::Bar | ChartType::Scatter => {
+            let right = config.y_axis_right_columns.clone().unwrap_or_default();
+            let (right_columns, left_columns): (Vec<String>, Vec<String>) = log_columns(config, transform)
+                .into_iter()
+                .partition(|name| right.contains(name));
+            [(ChartAxis::Y, left_columns), (ChartAxis::YRight, right_columns)]
+                .into_iter()
+                .filter(|(_, columns)| !columns.is_empty())
+                .map(|(axis, columns)| AxisHint {
+                    axis,
+                    log_base: transform.base,
+                    columns,
+                })
+                .collect()
+        }
+        ChartType::Histogram => config
+            .histogram_column
+            .iter()
+            .filter(|name| {
+                df.column(name)
+                    .map(|column| is_numeric_dtype(column.dtype()))
+                    .unwrap_or(false)
+            })
+            .map(|name| AxisHint {
+                axis: ChartAxis::X,
+                log_base: transform.base,
+                columns: vec![name.clone()],
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// 需要取对数的 Y 轴列（未指定时为所有 Y 轴列）
+fn log_columns(config: &ChartConfig, transform: &LogTransform) -> Vec<String> {
+    let y_columns = config.y_columns.clone().unwrap_or_default();
+    match &transform.columns {
+        Some(columns) => columns
+            .iter()
+            .filter(|name| y_columns.contains(name))
+            .cloned()
+            .collect(),
+        None => y_columns,
+    }
+}
+
+/// build_dataset 的结果：(dataset, 数据行数, 建议的 Y 轴范围)
+type BuiltDataset = (Vec<Vec<JsonValue>>, usize, Vec<AxisRange>);
+
+/// 根据图表类型构建 dataset，返回 (dataset, 数据行数, 建议的 Y 轴范围)
+fn build_dataset(df: &DataFrame, config: &ChartConfig) -> Result<BuiltDataset, String> {
+    let (dataset, data_count) = match config.chart_type {
+        ChartType::Line | ChartType::Bar | ChartType::Scatter => {
+            let (frame, columns) = build_xy_frame(df, config)?;
+            let axis_ranges = match &config.axis_range {
+                Some(options) => suggest_axis_ranges(&frame, &columns, config, options)?,
+                None => Vec::new(),
+            };
+            let (dataset, data_count) = frame_to_dataset(&frame, &columns)?;
+            return Ok((dataset, data_count, axis_ranges));
+        }
+        ChartType::Pie => build_pie_dataset(df, config)?,
+        ChartType::Histogram => build_histogram_dataset(df, config)?,
+        ChartType::Heatmap => build_heatmap_dataset(df, config)?,
+    };
+    Ok((dataset, data_count, Vec::new()))
+}
+
+/// 按分位数计算左右 Y 轴的建议范围（使用处理后的图表数据，包括分位带列）
+///
+/// 分位带列跟随所属的列放在同一坐标轴上；没有有效数值的坐标轴不返回
+fn suggest_axis_ranges(
+    frame: &DataFrame,
+    columns: &[String],
+    config: &ChartConfig,
+    options: &AxisRangeOptions,
+) -> Result<Vec<AxisRange>, String> {
+    if !(0.0..=1.0).contains(&options.lower) || !(0.0..=1.0).contains(&options.upper) {
+        return Err("坐标轴范围的分位数必须在 0 ~ 1 之间".to_string());
+    }
+    if options.lower >= options.upper {
+        return Err("坐标轴范围的下限分位数必须小于上限分位数".to_string());
+    }
+
+    let right = config.y_axis_right_columns.clone().unwrap_or_default();
+    let axis_of = |name: &String| {
+        let owner = match &config.quantile_bands {
+            Some(spec) if !config.y_columns.iter().flatten().any(|y| y == name) => &spec.column,
+            _ => name,
+        };
+        if right.contains(owner) {
+            ChartAxis::YRight
+        } else {
+            ChartAxis::Y
+        }
+    };
+
+    let mut ranges = Vec::new();
+    for axis in [ChartAxis::Y, ChartAxis::YRight] {
+        let mut values: Vec<f64> = Vec::new();
+        for name in columns[1..].iter().filter(|name| axis_of(name) == axis) {
+            let series = frame
+                .column(name)
+                .map_err(|e| e.to_string())?
+                .as_materialized_series()
+                .cast(&DataType::Float64)
+                .map_err(|e| format!("列 '{}' 无法转换为数值: {}", name, e))?;
+            let series = series.f64().map_err(|e| e.to_string())?;
+            values.extend(series.into_iter().flatten().filter(|value| value.is_finite()));
+        }
+        if values.is_empty() {
+            continue;
+        }
+
+        values.sort_by(f64::total_cmp);
+        let min = sorted_quantile(&values, options.lower);
+        let max = sorted_quantile(&values, options.upper);
+        let clipped_count = values.iter().filter(|value| **value < min || **value > max).count();
+        ranges.push(AxisRange {
+            axis,
+            min,
+            max,
+            data_min: values[0],
+            data_max: values[values.len() - 1],
+            clipped_count,
+        });
+    }
+
+    Ok(ranges)
+}
+
+/// 已排序数组的分位数（线性插值）
+fn sorted_quantile(values: &[f64], q: f64) -> f64 {
+    let position = q * (values.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    values[lower] + (values[upper] - values[lower]) * (position - lower as f64)
+}
+
+/// 按分面列拆分数据，分别构建 dataset
+///
+/// 分面按行数降序（行数相同时按值升序）排列，返回 (分面列表, 被省略的分面数量)
+fn build_facets(df: &DataFrame, config: &ChartConfig, facet_column: &str) -> Result<(Vec<ChartFacet>, usize), String> {
+    let limit = config
+        .facet_limit
+        .unwrap_or_else(|| ChartDefaults::default().facet_limit);
+    if limit == 0 {
+        return Err("facet_limit 必须大于 0".to_string());
+    }
+
+    // 分面值统一转换为文本（数值、日期等也可以作为分面列）
+    let labels = df
+        .column(facet_column)
+        .map_err(|_| format!("分面列 '{}' 不存在", facet_column))?
+        .as_materialized_series()
+        .cast(&DataType::String)
+        .map_err(|e| format!("分面列 '{}' 无法转换为文本: {}", facet_column, e))?;
+    let labels = labels.str().map_err(|e| e.to_string())?;
+
+    let counts = DataFrame::new(vec![labels.clone().into_series().with_name("facet".into()).into()])
+        .map_err(|e| e.to_string())?
+        .lazy()
+        .filter(col("facet").is_not_null())
+        .group_by([col("facet")])
+        .agg([len().alias("rows")])
+        .sort_by_exprs(
+            [col("rows"), col("facet")],
+            SortMultipleOptions::default().with_order_descending_multi([true, false]),
+        )
+        .collect()
+        .map_err(|e| format!("分面统计失败: {}", e))?;
+
+    let omitted_facets = counts.height().saturating_sub(limit);
+    let facet_values = counts
+        .column("facet")
+        .map_err(|e| e.to_string())?
+        .str()
+        .map_err(|e| e.to_string())?
+        .clone();
+    let row_counts = counts
+        .column("rows")
+        .map_err(|e| e.to_string())?
+        .idx()
+        .map_err(|e| e.to_string())?
+        .clone();
+
+    let mut facets = Vec::new();
+    for (value, row_count) in facet_values.into_iter().zip(&row_counts).take(limit) {
+        let (Some(value), Some(row_count)) = (value, row_count) else {
+            continue;
+        };
+        let mask = labels.equal(value);
+        let subset = df.filter(&mask).map_err(|e| e.to_string())?;
+        let (dataset, data_count, axis_ranges) =
+            build_dataset(&subset, config).map_err(|e| format!("分面 '{}': {}", value, e))?;
+        facets.push(ChartFacet {
+            value: value.to_string(),
+            row_count: row_count as usize,
+            dataset,
+            data_count,
+            axis_ranges,
+        });
+    }
+
+    Ok((facets, omitted_facets))
+}
+
+/// 按图表选项处理 X-Y 数据（汇总 → 平滑 → 对数变换 → 标准化），返回处理后的数据和输出列顺序
+fn build_xy_frame(df: &DataFrame, config: &ChartConfig) -> Result<(DataFrame, Vec<String>), String> {
     let x_column = config
         .x_column
         .as_ref()
@@ -62,9 +478,153 @@ fn build_xy_dataset(df: &DataFrame, config: &ChartConfig) -> Result<(Vec<Vec<Jso
         }
     }
 
+    // 分位带：(分位数, 输出列名)
+    let bands = match &config.quantile_bands {
+        Some(bands) => band_columns(bands, &columns)?,
+        None => Vec::new(),
+    };
+    if !bands.is_empty() && config.normalize.is_some() {
+        return Err("分位带不能与标准化同时使用".to_string());
+    }
+
     // 选择指定的列
-    let selected_df = df.select(&columns).map_err(|e| format!("选择列失败: {}", e))?;
+    let mut selected_df = df.select(&columns).map_err(|e| format!("选择列失败: {}", e))?;
+    if let Some(bucket) = config.x_bucket {
+        let aggregation = config.bucket_aggregation.as_deref().unwrap_or(DEFAULT_AGGREGATION);
+        // 每个时间段内原始值的分位数
+        let band_aggs = match &config.quantile_bands {
+            Some(spec) => bands
+                .iter()
+                .map(|(q, name)| {
+                    col(spec.column.as_str())
+                        .cast(DataType::Float64)
+                        .quantile(lit(*q), QuantileMethod::Linear)
+                        .alias(name.as_str())
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        selected_df = bucket_xy_data(
+            selected_df,
+            x_column,
+            y_columns,
+            band_aggs,
+            bucket,
+            aggregation,
+            config.fill_empty_buckets,
+        )?;
+    } else {
+        if let Some(duplicates) = config.x_duplicates {
+            selected_df = merge_duplicate_x(selected_df, x_column, y_columns, duplicates)?;
+        }
+        if let Some(spec) = &config.quantile_bands {
+            selected_df = rolling_bands(selected_df, spec, &bands)?;
+        }
+    }
+    if let Some(smoothing) = &config.smoothing {
+        selected_df = smooth_series(selected_df, y_columns, smoothing)?;
+    }
+    if let Some(transform) = &config.log_transform {
+        if let Some(name) = transform
+            .columns
+            .iter()
+            .flatten()
+            .find(|name| !y_columns.contains(name))
+        {
+            return Err(format!("对数变换的列 '{}' 不是 Y 轴列", name));
+        }
+        for name in log_columns(config, transform) {
+            let series = selected_df
+                .column(&name)
+                .map_err(|e| e.to_string())?
+                .as_materialized_series()
+                .clone();
+            selected_df
+                .with_column(log_transform_series(&series, transform)?)
+                .map_err(|e| e.to_string())?;
+        }
+        // 分位带与所属的列使用相同的刻度
+        let log_band = config
+            .quantile_bands
+            .as_ref()
+            .is_some_and(|spec| log_columns(config, transform).contains(&spec.column));
+        for (_, name) in bands.iter().filter(|_| log_band) {
+            let series = selected_df
+                .column(name)
+                .map_err(|e| e.to_string())?
+                .as_materialized_series()
+                .clone();
+            selected_df
+                .with_column(log_transform_series(&series, transform)?)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    if let Some(normalization) = config.normalize {
+        selected_df = normalize_series(selected_df, y_columns, normalization)?;
+    }
+
+    columns.extend(bands.into_iter().map(|(_, name)| name));
+    Ok((selected_df, columns))
+}
+
+/// 检查分位带选项，返回 (分位数, 输出列名)
+fn band_columns(spec: &QuantileBands, columns: &[String]) -> Result<Vec<(f64, String)>, String> {
+    if !columns[1..].contains(&spec.column) {
+        return Err(format!("分位带的列 '{}' 不是 Y 轴列", spec.column));
+    }
+    let quantiles = spec.quantiles();
+    if quantiles.is_empty() {
+        return Err("分位带至少需要一个分位数".to_string());
+    }
+
+    let mut bands: Vec<(f64, String)> = Vec::with_capacity(quantiles.len());
+    for q in quantiles {
+        if !(0.0..=1.0).contains(&q) {
+            return Err(format!("分位数必须在 0 ~ 1 之间，当前为 {}", q));
+        }
+        let name = spec.column_name(q);
+        if columns.contains(&name) || bands.iter().any(|(_, existing)| *existing == name) {
+            return Err(format!("分位带列名 '{}' 与已有列重复", name));
+        }
+        bands.push((q, name));
+    }
+    Ok(bands)
+}
+
+/// 按移动窗口计算分位带（窗口为当前行及之前的行）
+fn rolling_bands(df: DataFrame, spec: &QuantileBands, bands: &[(f64, String)]) -> Result<DataFrame, String> {
+    let window_size = spec
+        .window_size
+        .ok_or("未设置 x_bucket 时，分位带需要指定 window_size（移动窗口行数）")?;
+    if window_size == 0 {
+        return Err("分位带窗口大小必须大于 0".to_string());
+    }
+
+    let options = RollingOptionsFixedWindow {
+        window_size,
+        min_periods: 1,
+        weights: None,
+        center: false,
+        fn_params: None,
+    };
+    let exprs: Vec<Expr> = bands
+        .iter()
+        .map(|(q, name)| {
+            col(spec.column.as_str())
+                .cast(DataType::Float64)
+                .rolling_quantile(QuantileMethod::Linear, *q, options.clone())
+                .alias(name.as_str())
+        })
+        .collect();
+
+    df.lazy()
+        .with_columns(exprs)
+        .collect()
+        .map_err(|e| format!("计算分位带失败: {}", e))
+}
 
+/// 把 DataFrame 的指定列转换为 ECharts dataset（第一行是列名），返回 (dataset, 数据行数)
+fn frame_to_dataset(df: &DataFrame, columns: &[String]) -> Result<(Vec<Vec<JsonValue>>, usize), String> {
     // 构建 dataset 二维数组
     let mut dataset: Vec<Vec<JsonValue>> = Vec::new();
 
@@ -73,12 +633,12 @@ fn build_xy_dataset(df: &DataFrame, config: &ChartConfig) -> Result<(Vec<Vec<Jso
     dataset.push(header);
 
     // 数据行
-    let row_count = selected_df.height();
+    let row_count = df.height();
     for row_idx in 0..row_count {
         let mut row: Vec<JsonValue> = Vec::new();
 
-        for col_name in &columns {
-            let column = selected_df
+        for col_name in columns {
+            let column = df
                 .column(col_name)
                 .map_err(|e| format!("获取列 '{}' 失败: {}", col_name, e))?;
 
@@ -93,6 +653,256 @@ fn build_xy_dataset(df: &DataFrame, config: &ChartConfig) -> Result<(Vec<Vec<Jso
     Ok((dataset, row_count))
 }
 
+/// 合并重复的 X 值：每个 X 值只保留一行，Y 值按指定方式合并，保持 X 值首次出现的顺序
+fn merge_duplicate_x(
+    df: DataFrame,
+    x_column: &str,
+    y_columns: &[String],
+    duplicates: DuplicateXAggregation,
+) -> Result<DataFrame, String> {
+    let mut aggs = Vec::with_capacity(y_columns.len());
+    for name in y_columns {
+        aggs.push(
+            aggregation_expr(name, duplicates.as_str())
+                .map_err(|e| e.to_string())?
+                .alias(name.as_str()),
+        );
+    }
+    df.lazy()
+        .group_by_stable([col(x_column)])
+        .agg(aggs)
+        .collect()
+        .map_err(|e| format!("合并重复 X 值失败: {}", e))
+}
+
+/// 按时间段汇总 X-Y 数据（X 轴为日期 / 日期时间列，日期时间只看日期部分）
+///
+/// X 轴替换为时间段标签（例如 2024-03、2024-Q1），按时间排序；
+/// 指定 fill 时补齐最早和最晚时间段之间没有数据的时间段，Y 值为 0 或空值
+/// （extra_aggs 是额外的汇总列，例如分位带，空时间段始终为空值）
+fn bucket_xy_data(
+    df: DataFrame,
+    x_column: &str,
+    y_columns: &[String],
+    extra_aggs: Vec<Expr>,
+    bucket: PeriodBucket,
+    aggregation: &str,
+    fill: Option<EmptyBucketFill>,
+) -> Result<DataFrame, String> {
+    let x_dtype = df.column(x_column).map_err(|e| e.to_string())?.dtype().clone();
+    if !matches!(x_dtype, DataType::Date | DataType::Datetime(_, _)) {
+        return Err(format!("按时间段汇总需要 X 轴 '{}' 为日期或日期时间列", x_column));
+    }
+
+    let mut aggs = Vec::with_capacity(y_columns.len());
+    for name in y_columns {
+        aggs.push(
+            aggregation_expr(name, aggregation)
+                .map_err(|e| e.to_string())?
+                .alias(name.as_str()),
+        );
+    }
+    aggs.extend(extra_aggs);
+    let aggregated = df
+        .lazy()
+        .filter(col(x_column).is_not_null())
+        .group_by([col(x_column)
+            .cast(DataType::Date)
+            .dt()
+            .truncate(lit(bucket.every()))
+            .alias(x_column)])
+        .agg(aggs)
+        .collect()
+        .map_err(|e| format!("按时间段汇总失败: {}", e))?;
+
+    // 汇总后的时间段起点
+    let mut starts: Vec<NaiveDate> = aggregated
+        .column(x_column)
+        .map_err(|e| e.to_string())?
+        .as_materialized_series()
+        .to_physical_repr()
+        .i32()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .flatten()
+        .filter_map(|days| NaiveDate::from_num_days_from_ce_opt(days + 719_163))
+        .collect();
+    starts.sort();
+
+    // 补齐时列出最早到最晚之间的所有时间段
+    let range = starts.first().copied().zip(starts.last().copied());
+    let bins = match (fill, range) {
+        (Some(_), Some((first, last))) => {
+            let mut bins = Vec::new();
+            let mut current = first;
+            while current <= last {
+                if bins.len() >= MAX_TEMPORAL_BINS {
+                    return Err(format!("时间段数量超过 {}，请选择更粗的时间粒度", MAX_TEMPORAL_BINS));
+                }
+                bins.push(current);
+                current = next_bin_start(current, bucket).ok_or("日期超出范围")?;
+            }
+            bins
+        }
+        _ => starts,
+    };
+
+    let bin_days: Vec<i32> = bins.iter().map(|date| date.num_days_from_ce() - 719_163).collect();
+    let bin_df = DataFrame::new(vec![
+        Series::new(x_column.into(), bin_days)
+            .cast(&DataType::Date)
+            .map_err(|e| e.to_string())?
+            .into(),
+    ])
+    .map_err(|e| e.to_string())?;
+
+    let mut result = bin_df
+        .lazy()
+        .join(
+            aggregated.lazy(),
+            [col(x_column)],
+            [col(x_column)],
+            JoinArgs::new(JoinType::Left),
+        )
+        .sort([x_column], SortMultipleOptions::default());
+    if fill == Some(EmptyBucketFill::Zero) {
+        let filled: Vec<Expr> = y_columns
+            .iter()
+            .map(|name| col(name.as_str()).fill_null(lit(0)))
+            .collect();
+        result = result.with_columns(filled);
+    }
+    let mut result = result.collect().map_err(|e| format!("按时间段汇总失败: {}", e))?;
+
+    // X 轴显示为时间段标签
+    let labels: Vec<String> = bins.iter().map(|start| format_bin_date(*start, bucket)).collect();
+    result
+        .with_column(Series::new(x_column.into(), labels))
+        .map_err(|e| e.to_string())?;
+    Ok(result)
+}
+
+/// 按行顺序对每个 Y 轴系列计算移动窗口（窗口开头不足 window_size 行时按已有的行计算）
+fn smooth_series(df: DataFrame, y_columns: &[String], smoothing: &ChartSmoothing) -> Result<DataFrame, String> {
+    if smoothing.window_size == 0 {
+        return Err("平滑窗口大小必须大于 0".to_string());
+    }
+
+    let options = RollingOptionsFixedWindow {
+        window_size: smoothing.window_size,
+        min_periods: 1,
+        weights: None,
+        center: smoothing.center,
+        fn_params: None,
+    };
+
+    let mut exprs = Vec::with_capacity(y_columns.len());
+    for name in y_columns {
+        let dtype = df.column(name).map_err(|e| e.to_string())?.dtype().clone();
+        if !is_numeric_dtype(&dtype) {
+            return Err(format!("列 '{}' 不是数值列，无法平滑", name));
+        }
+
+        let value = col(name.as_str()).cast(DataType::Float64);
+        let expr = match smoothing.function {
+            RollingFunction::Mean => value.rolling_mean(options.clone()),
+            RollingFunction::Median => value.rolling_median(options.clone()),
+            RollingFunction::Sum => value.rolling_sum(options.clone()),
+            RollingFunction::Min => value.rolling_min(options.clone()),
+            RollingFunction::Max => value.rolling_max(options.clone()),
+            RollingFunction::Std => value.rolling_std(options.clone()),
+            RollingFunction::Var => value.rolling_var(options.clone()),
+            RollingFunction::Quantile => return Err("图表平滑不支持 quantile".to_string()),
+        };
+        exprs.push(expr.alias(name.as_str()));
+    }
+
+    df.lazy()
+        .with_columns(exprs)
+        .collect()
+        .map_err(|e| format!("平滑失败: {}", e))
+}
+
+/// 对一列取对数（结果为 Float64，空值保持为空值）
+fn log_transform_series(series: &Series, transform: &LogTransform) -> Result<Series, String> {
+    if !is_numeric_dtype(series.dtype()) {
+        return Err(format!("列 '{}' 不是数值列，无法取对数", series.name()));
+    }
+
+    let values = series.cast(&DataType::Float64).map_err(|e| e.to_string())?;
+    let values = values.f64().map_err(|e| e.to_string())?;
+
+    let offset = match transform.non_positive {
+        NonPositivePolicy::Null => 0.0,
+        NonPositivePolicy::Shift => match values.min() {
+            Some(min) if min <= 0.0 => 1.0 - min,
+            _ => 0.0,
+        },
+        NonPositivePolicy::Error => {
+            let count = values.into_iter().flatten().filter(|value| *value <= 0.0).count();
+            if count > 0 {
+                return Err(format!(
+                    "列 '{}' 有 {} 个小于等于 0 的值，无法取对数",
+                    series.name(),
+                    count
+                ));
+            }
+            0.0
+        }
+    };
+
+    let transformed: Float64Chunked = values
+        .into_iter()
+        .map(|value| {
+            value
+                .map(|value| value + offset)
+                .filter(|value| *value > 0.0)
+                .map(|value| transform.base.apply(value))
+        })
+        .collect();
+    Ok(transformed.into_series().with_name(series.name().clone()))
+}
+
+/// 按行顺序对每个 Y 轴系列单独标准化（结果为 Float64）
+fn normalize_series(
+    df: DataFrame,
+    y_columns: &[String],
+    normalization: SeriesNormalization,
+) -> Result<DataFrame, String> {
+    let mut exprs = Vec::with_capacity(y_columns.len());
+    for name in y_columns {
+        let dtype = df.column(name).map_err(|e| e.to_string())?.dtype().clone();
+        if !is_numeric_dtype(&dtype) {
+            return Err(format!("列 '{}' 不是数值列，无法标准化", name));
+        }
+
+        let value = col(name.as_str()).cast(DataType::Float64);
+        let expr = match normalization {
+            SeriesNormalization::Index => {
+                let base = value
+                    .clone()
+                    .filter(value.clone().is_not_null().and(value.clone().neq(lit(0.0))))
+                    .first();
+                value / base * lit(100.0)
+            }
+            SeriesNormalization::MinMax => {
+                let min = value.clone().min();
+                let range = value.clone().max() - min.clone();
+                // 所有值相同时 range 为 0：非空值为 0，空值保持为空
+                when(range.clone().gt(lit(0.0)))
+                    .then((value.clone() - min) / range)
+                    .otherwise(value * lit(0.0))
+            }
+        };
+        exprs.push(expr.alias(name.as_str()));
+    }
+
+    df.lazy()
+        .with_columns(exprs)
+        .collect()
+        .map_err(|e| format!("标准化失败: {}", e))
+}
+
 /// 构建饼图的 dataset
 fn build_pie_dataset(df: &DataFrame, config: &ChartConfig) -> Result<(Vec<Vec<JsonValue>>, usize), String> {
     let category_column = config.category_column.as_ref().ok_or("饼图需要指定 category_column")?;
@@ -140,14 +950,65 @@ fn build_pie_dataset(df: &DataFrame, config: &ChartConfig) -> Result<(Vec<Vec<Js
     Ok((dataset, row_count))
 }
 
+/// 构建热力图的 dataset
+///
+/// 两个分类列交叉统计：指定 value_column 时对值求和，否则计数
+/// 输出列：[x_column, y_column, value_column 或 "count"]
+fn build_heatmap_dataset(df: &DataFrame, config: &ChartConfig) -> Result<(Vec<Vec<JsonValue>>, usize), String> {
+    let (grouped, output_columns) = build_heatmap_frame(df, config)?;
+    frame_to_dataset(&grouped, &output_columns)
+}
+
+/// 按两个分类列交叉汇总，返回汇总结果和输出列顺序
+fn build_heatmap_frame(df: &DataFrame, config: &ChartConfig) -> Result<(DataFrame, Vec<String>), String> {
+    let x_column = config.x_column.as_ref().ok_or("热力图需要指定 x_column")?;
+    let y_column = config
+        .y_columns
+        .as_ref()
+        .and_then(|columns| columns.first())
+        .ok_or("热力图需要指定 y_columns（使用第一列作为 Y 轴分类）")?;
+
+    let mut columns = vec![x_column.clone(), y_column.clone()];
+    columns.extend(config.value_column.clone());
+
+    // 验证列是否存在
+    let column_names = df.get_column_names();
+    for col_name in &columns {
+        if !column_names.iter().any(|name| name.as_str() == col_name.as_str()) {
+            return Err(format!("列 '{}' 不存在", col_name));
+        }
+    }
+
+    let (value_name, value_expr) = match &config.value_column {
+        Some(value_column) => (value_column.clone(), col(value_column.as_str()).sum()),
+        None => ("count".to_string(), len()),
+    };
+
+    let grouped = df
+        .clone()
+        .lazy()
+        .group_by([col(x_column.as_str()), col(y_column.as_str())])
+        .agg([value_expr.alias(value_name.as_str())])
+        .sort([x_column.as_str(), y_column.as_str()], SortMultipleOptions::default())
+        .collect()
+        .map_err(|e| format!("热力图聚合失败: {}", e))?;
+
+    let output_columns = vec![x_column.clone(), y_column.clone(), value_name];
+    Ok((grouped, output_columns))
+}
+
 /// 构建直方图的 dataset
+///
+/// 数值列按 histogram_bins 等宽分箱；日期 / 日期时间列按 histogram_interval 的时间单位分箱
 fn build_histogram_dataset(df: &DataFrame, config: &ChartConfig) -> Result<(Vec<Vec<JsonValue>>, usize), String> {
     let column_name = config
         .histogram_column
         .as_ref()
         .ok_or("直方图需要指定 histogram_column")?;
 
-    let bins = config.histogram_bins.unwrap_or(10);
+    let bins = config
+        .histogram_bins
+        .unwrap_or_else(|| ChartDefaults::default().histogram_bins);
     if bins == 0 {
         return Err("直方图分箱数量必须大于 0".to_string());
     }
@@ -163,6 +1024,16 @@ fn build_histogram_dataset(df: &DataFrame, config: &ChartConfig) -> Result<(Vec<
         .as_materialized_series()
         .clone();
 
+    if matches!(series.dtype(), DataType::Date | DataType::Datetime(_, _)) {
+        return build_temporal_histogram(&series, config.histogram_interval);
+    }
+
+    // 偏态分布：先取对数再分箱
+    let series = match &config.log_transform {
+        Some(transform) if is_numeric_dtype(series.dtype()) => log_transform_series(&series, transform)?,
+        _ => series,
+    };
+
     let dtype = series.dtype();
     let is_numeric = matches!(
         dtype,
@@ -180,7 +1051,7 @@ fn build_histogram_dataset(df: &DataFrame, config: &ChartConfig) -> Result<(Vec<
     );
 
     if !is_numeric {
-        return Err("直方图仅支持数值列".to_string());
+        return Err("直方图仅支持数值、日期和日期时间列".to_string());
     }
 
     let mut values: Vec<f64> = Vec::new();
@@ -232,6 +1103,107 @@ fn build_histogram_dataset(df: &DataFrame, config: &ChartConfig) -> Result<(Vec<
     Ok((dataset, values.len()))
 }
 
+/// 时间直方图最多的分箱数量
+const MAX_TEMPORAL_BINS: usize = 10_000;
+
+/// 构建日期 / 日期时间列的直方图（按日 / 周 / 月 / 季度 / 年计数）
+///
+/// 日期时间列只看日期部分；没有数据的时间段也会输出（计数为 0），保证横轴连续
+fn build_temporal_histogram(
+    series: &Series,
+    interval: Option<PeriodBucket>,
+) -> Result<(Vec<Vec<JsonValue>>, usize), String> {
+    let days = series
+        .cast(&DataType::Date)
+        .and_then(|s| s.to_physical_repr().cast(&DataType::Int32))
+        .map_err(|e| format!("日期转换失败: {}", e))?;
+    let dates: Vec<NaiveDate> = days
+        .i32()
+        .map_err(|e| format!("日期转换失败: {}", e))?
+        .into_iter()
+        .flatten()
+        .filter_map(|days| NaiveDate::from_num_days_from_ce_opt(days + 719_163))
+        .collect();
+
+    let (Some(min), Some(max)) = (dates.iter().min().copied(), dates.iter().max().copied()) else {
+        return Err("直方图列无可用日期".to_string());
+    };
+
+    // 未指定粒度时按时间跨度选择：3 个月以内按日，2 年以内按周，否则按月
+    let interval = interval.unwrap_or_else(|| match (max - min).num_days() {
+        0..=90 => PeriodBucket::Day,
+        91..=730 => PeriodBucket::Week,
+        _ => PeriodBucket::Month,
+    });
+
+    let mut bins: Vec<NaiveDate> = Vec::new();
+    let mut current = bin_start(min, interval);
+    while current <= max {
+        if bins.len() >= MAX_TEMPORAL_BINS {
+            return Err(format!("分箱数量超过 {}，请选择更粗的时间粒度", MAX_TEMPORAL_BINS));
+        }
+        bins.push(current);
+        current = next_bin_start(current, interval).ok_or("日期超出范围")?;
+    }
+
+    let mut counts = vec![0usize; bins.len()];
+    for date in &dates {
+        let start = bin_start(*date, interval);
+        if let Ok(idx) = bins.binary_search(&start) {
+            counts[idx] += 1;
+        }
+    }
+
+    let mut dataset: Vec<Vec<JsonValue>> = Vec::new();
+    dataset.push(vec![
+        JsonValue::String("bin".to_string()),
+        JsonValue::String("count".to_string()),
+    ]);
+
+    for (start, count) in bins.iter().zip(counts) {
+        dataset.push(vec![
+            JsonValue::String(format_bin_date(*start, interval)),
+            JsonValue::Number(count.into()),
+        ]);
+    }
+
+    Ok((dataset, dates.len()))
+}
+
+/// 日期所在时间段的第一天（周从周一开始）
+fn bin_start(date: NaiveDate, interval: PeriodBucket) -> NaiveDate {
+    let first_of_month = |month: u32| NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap_or(date);
+
+    match interval {
+        PeriodBucket::Day => date,
+        PeriodBucket::Week => date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64),
+        PeriodBucket::Month => first_of_month(date.month()),
+        PeriodBucket::Quarter => first_of_month((date.month() - 1) / 3 * 3 + 1),
+        PeriodBucket::Year => first_of_month(1),
+    }
+}
+
+/// 下一个时间段的第一天
+fn next_bin_start(start: NaiveDate, interval: PeriodBucket) -> Option<NaiveDate> {
+    match interval {
+        PeriodBucket::Day => start.succ_opt(),
+        PeriodBucket::Week => start.checked_add_days(chrono::Days::new(7)),
+        PeriodBucket::Month => start.checked_add_months(Months::new(1)),
+        PeriodBucket::Quarter => start.checked_add_months(Months::new(3)),
+        PeriodBucket::Year => start.checked_add_months(Months::new(12)),
+    }
+}
+
+/// 时间分箱的标签，例如 2024-03-05、2024-03、2024-Q1、2024
+fn format_bin_date(start: NaiveDate, interval: PeriodBucket) -> String {
+    match interval {
+        PeriodBucket::Day | PeriodBucket::Week => start.format("%Y-%m-%d").to_string(),
+        PeriodBucket::Month => start.format("%Y-%m").to_string(),
+        PeriodBucket::Quarter => format!("{}-Q{}", start.year(), (start.month() - 1) / 3 + 1),
+        PeriodBucket::Year => start.year().to_string(),
+    }
+}
+
 fn any_value_to_f64(value: AnyValue) -> Option<f64> {
     match value {
         AnyValue::Null => None,
@@ -257,10 +1229,7 @@ fn any_value_to_f64(value: AnyValue) -> Option<f64> {
                 None
             }
         }
-        AnyValue::Decimal(v, scale, _) => {
-            let divisor = 10f64.powi(scale as i32);
-            Some(v as f64 / divisor)
-        }
+        AnyValue::Decimal(v, _, scale) => Some(decimal_to_f64(v, scale)),
         _ => None,
     }
 }
@@ -331,3 +1300,562 @@ fn series_value_to_json(series: &Series, idx: usize) -> Result<JsonValue, String
 
     Ok(json_value)
 }
+
+// ============================================================================
+// 图表配置检查
+// ============================================================================
+/// 饼图扇区数量超过该值时提示（扇区过多时标签重叠，无法阅读）
+const MAX_PIE_CATEGORIES_HINT: usize = 20;
+
+/// 柱状图 / 热力图分类数量超过该值时提示
+const MAX_AXIS_CATEGORIES_HINT: usize = 200;
+
+/// 在生成图表前检查图表配置
+///
+/// 检查内容：
+/// - 列是否存在、列类型是否适合该图表类型（例如 Y 轴须为数值列）
+/// - 选项组合是否有效（例如 x_bucket 需要日期列、对数变换的列须为 Y 轴列）
+/// - 分类数量和行数是否过多（例如 5000 个扇区的饼图；行数上限为图表默认设置的 max_points）
+///
+/// Error 级别的问题会导致 generate_chart_data 失败，Warning 级别的问题仍可生成图表
+///
+/// 前端调用示例：
+/// ```typescript
+/// const result = await invoke('validate_chart_config', { config });
+/// if (!result.valid) showIssues(result.issues);
+/// ```
+#[tauri::command]
+pub async fn validate_chart_config(
+    mut config: ChartConfig,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<ChartValidation, String> {
+    let defaults = current_chart_defaults(&app)?;
+    defaults.apply(&mut config);
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut checker = ChartConfigChecker::new(&current_df, defaults.max_points);
+        checker.check(&config);
+        Ok(checker.finish())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 收集图表配置问题
+struct ChartConfigChecker<'a> {
+    df: &'a DataFrame,
+    max_points: usize,
+    issues: Vec<ChartIssue>,
+}
+
+impl<'a> ChartConfigChecker<'a> {
+    fn new(df: &'a DataFrame, max_points: usize) -> Self {
+        Self {
+            df,
+            max_points,
+            issues: Vec::new(),
+        }
+    }
+
+    fn error(&mut self, field: &str, message: String) {
+        self.push(ChartIssueLevel::Error, field, message);
+    }
+
+    fn warning(&mut self, field: &str, message: String) {
+        self.push(ChartIssueLevel::Warning, field, message);
+    }
+
+    fn push(&mut self, level: ChartIssueLevel, field: &str, message: String) {
+        self.issues.push(ChartIssue {
+            level,
+            field: Some(field.to_string()),
+            message,
+        });
+    }
+
+    /// 检查必填列：未指定或不存在时记录错误，存在时返回列
+    fn require(&mut self, field: &str, name: Option<&String>) -> Option<&'a Column> {
+        let Some(name) = name else {
+            self.error(field, format!("需要指定 {}", field));
+            return None;
+        };
+        self.column(field, name)
+    }
+
+    /// 检查列是否存在
+    fn column(&mut self, field: &str, name: &str) -> Option<&'a Column> {
+        let df = self.df;
+        match df.column(name) {
+            Ok(column) => Some(column),
+            Err(_) => {
+                self.error(field, format!("列 '{}' 不存在", name));
+                None
+            }
+        }
+    }
+
+    /// 检查数值列
+    fn require_numeric(&mut self, field: &str, column: &Column) {
+        if !is_numeric_dtype(column.dtype()) {
+            self.error(
+                field,
+                format!(
+                    "列 '{}' 是 {} 类型，请选择数值列或先用 cast_types 转换类型",
+                    column.name(),
+                    column.dtype()
+                ),
+            );
+        }
+    }
+
+    /// 不同值数量（空值不计入）
+    fn distinct_count(column: &Column) -> usize {
+        column.as_materialized_series().drop_nulls().n_unique().unwrap_or(0)
+    }
+
+    fn check(&mut self, config: &ChartConfig) {
+        match config.chart_type {
+            ChartType::Line | ChartType::Bar | ChartType::Scatter => self.check_xy(config),
+            ChartType::Pie => self.check_pie(config),
+            ChartType::Histogram => self.check_histogram(config),
+            ChartType::Heatmap => self.check_heatmap(config),
+        }
+
+        if let Some(facet_column) = &config.facet_column {
+            let limit = config.facet_limit.unwrap_or(0);
+            if limit == 0 {
+                self.error("facet_limit", "facet_limit 必须大于 0".to_string());
+            }
+            if let Some(column) = self.column("facet_column", facet_column) {
+                let facets = Self::distinct_count(column);
+                if limit > 0 && facets > limit {
+                    self.warning(
+                        "facet_column",
+                        format!(
+                            "分面列 '{}' 有 {} 个不同值，只会显示行数最多的 {} 个",
+                            facet_column, facets, limit
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_xy(&mut self, config: &ChartConfig) {
+        let x = self.require("x_column", config.x_column.as_ref());
+
+        let y_columns = config.y_columns.clone().unwrap_or_default();
+        if y_columns.is_empty() {
+            self.error("y_columns", "至少需要选择一个 Y 轴列".to_string());
+        }
+        for name in &y_columns {
+            if let Some(column) = self.column("y_columns", name) {
+                self.require_numeric("y_columns", column);
+            }
+        }
+        for name in config.y_axis_right_columns.iter().flatten() {
+            if !y_columns.contains(name) {
+                self.warning(
+                    "y_axis_right_columns",
+                    format!("右侧 Y 轴的列 '{}' 不在 y_columns 中，将被忽略", name),
+                );
+            }
+        }
+
+        if let Some(transform) = &config.log_transform {
+            for name in transform.columns.iter().flatten() {
+                if !y_columns.contains(name) {
+                    self.error("log_transform", format!("对数变换的列 '{}' 不是 Y 轴列", name));
+                }
+            }
+        }
+        if let Some(smoothing) = &config.smoothing {
+            if smoothing.window_size == 0 {
+                self.error("smoothing", "平滑窗口大小必须大于 0".to_string());
+            }
+            if smoothing.function == RollingFunction::Quantile {
+                self.error("smoothing", "图表平滑不支持 quantile，请选择其他函数".to_string());
+            }
+        }
+        if let Some(options) = &config.axis_range {
+            if !(0.0..=1.0).contains(&options.lower) || !(0.0..=1.0).contains(&options.upper) {
+                self.error("axis_range", "坐标轴范围的分位数必须在 0 ~ 1 之间".to_string());
+            } else if options.lower >= options.upper {
+                self.error("axis_range", "坐标轴范围的下限分位数必须小于上限分位数".to_string());
+            }
+        }
+        if let Some(bands) = &config.quantile_bands {
+            if !y_columns.contains(&bands.column) {
+                self.error("quantile_bands", format!("分位带的列 '{}' 不是 Y 轴列", bands.column));
+            }
+            if let Some(q) = bands.quantiles().into_iter().find(|q| !(0.0..=1.0).contains(q)) {
+                self.error("quantile_bands", format!("分位数必须在 0 ~ 1 之间，当前为 {}", q));
+            }
+            if config.x_bucket.is_none() && bands.window_size.unwrap_or(0) == 0 {
+                self.error(
+                    "quantile_bands",
+                    "未设置 x_bucket 时，分位带需要指定大于 0 的 window_size".to_string(),
+                );
+            }
+            if config.normalize.is_some() {
+                self.error("quantile_bands", "分位带不能与标准化同时使用".to_string());
+            }
+        }
+
+        let Some(x) = x else {
+            return;
+        };
+        let x_is_temporal = matches!(x.dtype(), DataType::Date | DataType::Datetime(_, _));
+
+        if config.x_bucket.is_some() {
+            if !x_is_temporal {
+                self.error(
+                    "x_bucket",
+                    format!("按时间段汇总需要 X 轴 '{}' 为日期或日期时间列", x.name()),
+                );
+            }
+            if let Some(aggregation) = &config.bucket_aggregation
+                && let Err(e) = aggregation_expr(x.name(), aggregation)
+            {
+                self.error("bucket_aggregation", e.to_string());
+            }
+            return;
+        }
+
+        if matches!(config.chart_type, ChartType::Scatter) && !is_numeric_dtype(x.dtype()) && !x_is_temporal {
+            self.warning(
+                "x_column",
+                format!("散点图的 X 轴 '{}' 不是数值或日期列，点会按分类排列", x.name()),
+            );
+        }
+
+        let rows = self.df.height();
+        let distinct = Self::distinct_count(x);
+        if !matches!(config.chart_type, ChartType::Scatter)
+            && config.x_duplicates.is_none()
+            && distinct < x.len() - x.null_count()
+        {
+            self.warning(
+                "x_duplicates",
+                format!(
+                    "X 轴 '{}' 有重复值，每个值会绘制多个点，可以设置 x_duplicates 合并或设置 x_bucket 按时间段汇总",
+                    x.name()
+                ),
+            );
+        }
+        if matches!(config.chart_type, ChartType::Bar) && distinct > MAX_AXIS_CATEGORIES_HINT {
+            self.warning(
+                "x_column",
+                format!(
+                    "X 轴 '{}' 有 {} 个不同值，柱子会过于密集，建议先筛选或汇总",
+                    x.name(),
+                    distinct
+                ),
+            );
+        }
+        if !matches!(config.chart_type, ChartType::Bar) && config.x_duplicates.is_none() && rows > self.max_points {
+            self.warning(
+                "x_column",
+                format!(
+                    "将绘制 {} 行数据，渲染可能较慢，建议设置 x_bucket 按时间段汇总或先抽样",
+                    rows
+                ),
+            );
+        }
+    }
+
+    fn check_pie(&mut self, config: &ChartConfig) {
+        let category = self.require("category_column", config.category_column.as_ref());
+        if let Some(value) = self.require("value_column", config.value_column.as_ref()) {
+            self.require_numeric("value_column", value);
+            if is_numeric_dtype(value.dtype()) {
+                let negative = value
+                    .as_materialized_series()
+                    .cast(&DataType::Float64)
+                    .ok()
+                    .and_then(|series| series.f64().ok().map(|values| values.lt(0.0).sum().unwrap_or(0)))
+                    .unwrap_or(0);
+                if negative > 0 {
+                    self.warning(
+                        "value_column",
+                        format!("列 '{}' 有 {} 个负数，饼图无法显示负数扇区", value.name(), negative),
+                    );
+                }
+            }
+        }
+        if let Some(category) = category {
+            let distinct = Self::distinct_count(category);
+            if distinct > MAX_PIE_CATEGORIES_HINT {
+                self.warning(
+                    "category_column",
+                    format!(
+                        "分类列 '{}' 有 {} 个不同值，饼图扇区过多无法阅读，建议改用柱状图或只保留前几个分类",
+                        category.name(),
+                        distinct
+                    ),
+                );
+            }
+        }
+    }
+
+    fn check_histogram(&mut self, config: &ChartConfig) {
+        if config.histogram_bins == Some(0) {
+            self.error("histogram_bins", "直方图分箱数量必须大于 0".to_string());
+        }
+        let Some(column) = self.require("histogram_column", config.histogram_column.as_ref()) else {
+            return;
+        };
+        let is_temporal = matches!(column.dtype(), DataType::Date | DataType::Datetime(_, _));
+        if !is_temporal && !is_numeric_dtype(column.dtype()) {
+            self.error(
+                "histogram_column",
+                format!(
+                    "列 '{}' 是 {} 类型，直方图仅支持数值、日期和日期时间列",
+                    column.name(),
+                    column.dtype()
+                ),
+            );
+        } else if column.null_count() == column.len() {
+            self.error("histogram_column", format!("列 '{}' 没有可用的值", column.name()));
+        }
+    }
+
+    fn check_heatmap(&mut self, config: &ChartConfig) {
+        let x = self.require("x_column", config.x_column.as_ref());
+        let y = self.require(
+            "y_columns",
+            config.y_columns.as_ref().and_then(|columns| columns.first()),
+        );
+        if let Some(value_column) = &config.value_column
+            && let Some(value) = self.column("value_column", value_column)
+        {
+            self.require_numeric("value_column", value);
+        }
+        for (field, column) in [("x_column", x), ("y_columns", y)] {
+            let Some(column) = column else {
+                continue;
+            };
+            let distinct = Self::distinct_count(column);
+            if distinct > MAX_AXIS_CATEGORIES_HINT {
+                self.warning(
+                    field,
+                    format!(
+                        "列 '{}' 有 {} 个不同值，热力图格子会过于密集，建议先筛选或分桶",
+                        column.name(),
+                        distinct
+                    ),
+                );
+            }
+        }
+    }
+
+    fn finish(mut self) -> ChartValidation {
+        self.issues.sort_by_key(|issue| issue.level != ChartIssueLevel::Error);
+        ChartValidation {
+            valid: self.issues.iter().all(|issue| issue.level != ChartIssueLevel::Error),
+            issues: self.issues,
+        }
+    }
+}
+
+// ============================================================================
+// 图表推荐
+// ============================================================================
+/// 根据当前数据集的列类型推荐图表
+///
+/// 规则：
+/// - 日期 / 日期时间列 + 数值列 → 折线图
+/// - 分类列 + 数值列 → 柱状图；分类较少时 → 饼图
+/// - 两个分类列 → 热力图（交叉计数）
+/// - 两个数值列 → 散点图；单个数值列 → 直方图
+///
+/// 分类列为字符串、分类或布尔列，且不同值数量在 2 ~ max_categories 之间；
+/// 折线图系列数、饼图扇区数等上限和折线图的时间粒度来自图表默认设置；
+/// 每行都不同的整数列（通常是 ID）不作为数值列推荐
+///
+/// 参数：
+/// - limit: 最多返回的推荐数量（可选，默认 10）
+///
+/// 返回：
+/// - Result<Vec<ChartSuggestion>, String>: 按分数降序排列的推荐
+#[tauri::command]
+pub async fn suggest_charts(
+    limit: Option<usize>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<ChartSuggestion>, String> {
+    let defaults = current_chart_defaults(&app)?;
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut suggestions = build_chart_suggestions(&current_df, &defaults)?;
+        suggestions.sort_by(|a, b| b.score.total_cmp(&a.score));
+        suggestions.truncate(limit.unwrap_or(10));
+        Ok(suggestions)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 空白图表配置（推荐时只填写需要的字段）
+fn empty_chart_config(chart_type: ChartType) -> ChartConfig {
+    ChartConfig {
+        chart_type,
+        x_column: None,
+        y_columns: None,
+        category_column: None,
+        value_column: None,
+        title: None,
+        line_style: None,
+        y_axis_right_columns: None,
+        normalize: None,
+        log_transform: None,
+        smoothing: None,
+        x_bucket: None,
+        bucket_aggregation: None,
+        fill_empty_buckets: None,
+        x_duplicates: None,
+        quantile_bands: None,
+        axis_range: None,
+        histogram_column: None,
+        histogram_bins: None,
+        histogram_interval: None,
+        facet_column: None,
+        facet_limit: None,
+    }
+}
+
+/// 按列类型生成所有候选推荐（未排序）
+fn build_chart_suggestions(df: &DataFrame, defaults: &ChartDefaults) -> Result<Vec<ChartSuggestion>, String> {
+    let mut temporal: Vec<String> = Vec::new();
+    let mut numeric: Vec<String> = Vec::new();
+    // (列名, 不同值数量)
+    let mut categorical: Vec<(String, usize)> = Vec::new();
+
+    for column in df.get_columns() {
+        let name = column.name().to_string();
+        let dtype = column.dtype();
+        if matches!(dtype, DataType::Date | DataType::Datetime(_, _)) {
+            temporal.push(name);
+        } else if is_numeric_dtype(dtype) {
+            let unique = column
+                .n_unique()
+                .map_err(|e| format!("统计列 '{}' 失败: {}", name, e))?;
+            let is_identifier = dtype.is_integer() && df.height() > 1 && unique == df.height();
+            if !is_identifier {
+                numeric.push(name);
+            }
+        } else if dtype.is_string() || dtype.is_categorical() || dtype.is_bool() {
+            let unique = column
+                .n_unique()
+                .map_err(|e| format!("统计列 '{}' 失败: {}", name, e))?;
+            if (2..=defaults.max_categories).contains(&unique) {
+                categorical.push((name, unique));
+            }
+        }
+    }
+
+    let mut suggestions = Vec::new();
+
+    // 折线图：时间 + 数值（多个数值列作为多系列）
+    if let (Some(x), false) = (temporal.first(), numeric.is_empty()) {
+        let y_columns: Vec<String> = numeric.iter().take(defaults.max_series).cloned().collect();
+        let mut config = empty_chart_config(ChartType::Line);
+        config.title = Some(format!("{} 随 {} 变化", y_columns.join("、"), x));
+        config.x_column = Some(x.clone());
+        config.y_columns = Some(y_columns);
+        config.x_bucket = defaults.date_bucket;
+        suggestions.push(ChartSuggestion {
+            config,
+            score: 0.95,
+            reason: format!("'{}' 是时间列，适合用折线图观察趋势", x),
+        });
+    }
+
+    // 柱状图 / 饼图：分类 + 数值（分类越少越清晰）
+    if let Some(value) = numeric.first() {
+        for (category, unique) in &categorical {
+            let clarity = 1.0 - *unique as f64 / (defaults.max_categories as f64 * 2.0);
+
+            let mut config = empty_chart_config(ChartType::Bar);
+            config.title = Some(format!("各{}的{}", category, value));
+            config.x_column = Some(category.clone());
+            config.y_columns = Some(vec![value.clone()]);
+            suggestions.push(ChartSuggestion {
+                config,
+                score: 0.85 * clarity,
+                reason: format!("'{}' 有 {} 个分类，适合用柱状图比较 '{}'", category, unique, value),
+            });
+
+            if *unique <= defaults.max_pie_slices {
+                let mut config = empty_chart_config(ChartType::Pie);
+                config.title = Some(format!("{}占比（按{}）", value, category));
+                config.category_column = Some(category.clone());
+                config.value_column = Some(value.clone());
+                suggestions.push(ChartSuggestion {
+                    config,
+                    score: 0.75 * clarity,
+                    reason: format!("'{}' 只有 {} 个分类，适合用饼图查看占比", category, unique),
+                });
+            }
+        }
+    }
+
+    // 热力图：两个分类列交叉计数
+    for (i, (x, x_unique)) in categorical.iter().enumerate() {
+        for (y, y_unique) in categorical.iter().skip(i + 1) {
+            let cells = (x_unique * y_unique) as f64;
+            let mut config = empty_chart_config(ChartType::Heatmap);
+            config.title = Some(format!("{} × {}", x, y));
+            config.x_column = Some(x.clone());
+            config.y_columns = Some(vec![y.clone()]);
+            suggestions.push(ChartSuggestion {
+                config,
+                score: 0.7 * (1.0 - cells / (defaults.max_categories * defaults.max_categories) as f64 / 2.0),
+                reason: format!("'{}' 和 '{}' 都是分类列，适合用热力图查看组合分布", x, y),
+            });
+        }
+    }
+
+    // 散点图：前两个数值列
+    if let [x, y, ..] = numeric.as_slice() {
+        let mut config = empty_chart_config(ChartType::Scatter);
+        config.title = Some(format!("{} 与 {}", y, x));
+        config.x_column = Some(x.clone());
+        config.y_columns = Some(vec![y.clone()]);
+        suggestions.push(ChartSuggestion {
+            config,
+            score: 0.6,
+            reason: format!("'{}' 和 '{}' 都是数值列，适合用散点图查看相关性", x, y),
+        });
+    }
+
+    // 直方图：每个数值列的分布
+    for column in &numeric {
+        let mut config = empty_chart_config(ChartType::Histogram);
+        config.title = Some(format!("{} 分布", column));
+        config.histogram_column = Some(column.clone());
+        suggestions.push(ChartSuggestion {
+            config,
+            score: 0.5,
+            reason: format!("查看 '{}' 的数值分布", column),
+        });
+    }
+
+    Ok(suggestions)
+}
@@ -0,0 +1,827 @@
+// ============================================================================
+// commands/cleaning.rs - 数据清洗命令
+// ============================================================================
+// 这个文件实现了针对常见语义类型的清洗操作（均记录到历史，可撤销）：
+// - normalize_emails: 邮箱规范化
+// - normalize_phones: 电话号码规范化为 E.164
+// - parse_urls: 拆分 URL 为域名和路径列
+// - parse_ip_addresses: 识别 IP 版本，可选 GeoIP 国家
+// - parse_user_agents: 解析 User-Agent 为浏览器和操作系统列
+// - parse_currency: 金额字符串转为数值
+// - parse_percentages / format_percentages: 百分比字符串与小数互相转换
+// - normalize_boolean: 真值/假值标记转换为布尔列
+// - clean_whitespace: 清理空白和不可见字符
+// - replace_non_finite: 把浮点列中的 NaN / 无穷大替换为空值或指定值
+
+use crate::AppState;
+use crate::commands::operations::create_history_entry;
+use crate::data::column_names::OutputColumns;
+use crate::data::decimal::is_numeric_dtype;
+use crate::data::text::{
+    DEFAULT_FALSE_TOKENS, DEFAULT_TRUE_TOKENS, clean_whitespace_value, format_percentage_value, ip_version,
+    map_boolean_column, map_string_column, map_string_to_f64, normalize_email, normalize_phone_e164,
+    parse_currency_value, parse_percentage_value, parse_url, user_agent_browser, user_agent_os,
+};
+use crate::models::history::ColumnConflictPolicy;
+use crate::models::{OperationType, UnmatchedValuesReport};
+use polars::prelude::*;
+use tauri::State;
+
+// ============================================================================
+// 1. 邮箱规范化（Normalize Emails）
+// ============================================================================
+/// 规范化邮箱列：去除首尾空白并转为小写（原地替换）
+///
+/// 参数：
+/// - columns: 要处理的列名列表
+#[tauri::command]
+pub async fn normalize_emails(columns: Vec<String>, state: State<'_, AppState>) -> Result<(), String> {
+    if columns.is_empty() {
+        return Err("至少需要选择一列".to_string());
+    }
+
+    let cols_clone = columns.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let result_df = tauri::async_runtime::spawn_blocking(move || {
+        let mut df = current_df;
+        for column in &cols_clone {
+            let series = map_string_column(&df, column, column, normalize_email).map_err(|e| e.to_string())?;
+            df.with_column(series)
+                .map_err(|e| format!("更新列 {} 失败: {}", column, e))?;
+        }
+        Ok::<DataFrame, String>(df)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::NormalizeEmails { columns };
+    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 2. 电话号码规范化（Normalize Phones）
+// ============================================================================
+/// 将电话号码规范化为 E.164 格式，结果写入新列 "{column}_e164"
+///
+/// 无法识别的号码结果为空值
+///
+/// 参数：
+/// - column: 电话号码列
+/// - default_country_code: 默认国家码（不含 "+"，例如 "86"），用于没有国家码的本地号码
+#[tauri::command]
+pub async fn normalize_phones(
+    column: String,
+    default_country_code: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if let Some(code) = &default_country_code
+        && (code.is_empty() || !code.chars().all(|c| c.is_ascii_digit()))
+    {
+        return Err(format!("无效的国家码: {}", code));
+    }
+
+    let column_clone = column.clone();
+    let country_code = default_country_code.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let result_df = tauri::async_runtime::spawn_blocking(move || {
+        let output_name = format!("{}_e164", column_clone);
+        let series = map_string_column(&current_df, &column_clone, &output_name, |value| {
+            normalize_phone_e164(value, country_code.as_deref())
+        })
+        .map_err(|e| e.to_string())?;
+
+        let mut df = current_df;
+        df.with_column(series)
+            .map_err(|e| format!("添加列 {} 失败: {}", output_name, e))?;
+        Ok::<DataFrame, String>(df)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::NormalizePhones {
+        column,
+        default_country_code,
+    };
+    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 3. URL 解析（Parse URLs）
+// ============================================================================
+/// 将 URL 列拆分为域名列 "{column}_domain" 和路径列 "{column}_path"
+///
+/// 参数：
+/// - column: URL 列
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error）
+#[tauri::command]
+pub async fn parse_urls(
+    column: String,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let column_clone = column.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let domain_name = outputs
+            .resolve(&format!("{}_domain", column_clone))
+            .map_err(|e| e.to_string())?;
+        let path_name = outputs
+            .resolve(&format!("{}_path", column_clone))
+            .map_err(|e| e.to_string())?;
+
+        let domain = map_string_column(&current_df, &column_clone, &domain_name, |value| {
+            parse_url(value).map(|(domain, _)| domain)
+        })
+        .map_err(|e| e.to_string())?;
+        let path = map_string_column(&current_df, &column_clone, &path_name, |value| {
+            parse_url(value).map(|(_, path)| path)
+        })
+        .map_err(|e| e.to_string())?;
+
+        let mut df = current_df;
+        df.with_column(domain)
+            .map_err(|e| format!("添加列 {} 失败: {}", domain_name, e))?;
+        df.with_column(path)
+            .map_err(|e| format!("添加列 {} 失败: {}", path_name, e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::ParseUrls { column };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 4. IP 地址解析（Parse IP Addresses）
+// ============================================================================
+/// 解析 IP 地址列
+///
+/// 新增列：
+/// - "{column}_ip_version": "v4" / "v6"，无效地址为空值
+/// - "{column}_ip_valid": 是否为有效 IP 地址
+/// - "{column}_country": 国家 ISO 代码（仅提供 mmdb_path 时，查不到为空值）
+///
+/// 参数：
+/// - column: IP 地址列
+/// - mmdb_path: MaxMind GeoIP2/GeoLite2 Country 或 City 数据库文件路径（可选）
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error）
+#[tauri::command]
+pub async fn parse_ip_addresses(
+    column: String,
+    mmdb_path: Option<String>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let column_clone = column.clone();
+    let mmdb_clone = mmdb_path.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let version_name = outputs
+            .resolve(&format!("{}_ip_version", column_clone))
+            .map_err(|e| e.to_string())?;
+        let valid_name = outputs
+            .resolve(&format!("{}_ip_valid", column_clone))
+            .map_err(|e| e.to_string())?;
+
+        let version =
+            map_string_column(&current_df, &column_clone, &version_name, ip_version).map_err(|e| e.to_string())?;
+        let valid = version
+            .is_not_null()
+            .with_name(valid_name.as_str().into())
+            .into_series();
+
+        let mut df = current_df;
+
+        if let Some(path) = &mmdb_clone {
+            let reader = maxminddb::Reader::open_readfile(path).map_err(|e| format!("打开 GeoIP 数据库失败: {}", e))?;
+            let country_name = outputs
+                .resolve(&format!("{}_country", column_clone))
+                .map_err(|e| e.to_string())?;
+            let country = map_string_column(&df, &column_clone, &country_name, |value| {
+                let ip = value.trim().parse::<std::net::IpAddr>().ok()?;
+                let record = reader.lookup::<maxminddb::geoip2::Country>(ip).ok()?;
+                record.country?.iso_code.map(|code| code.to_string())
+            })
+            .map_err(|e| e.to_string())?;
+            df.with_column(country)
+                .map_err(|e| format!("添加列 {} 失败: {}", country_name, e))?;
+        }
+
+        df.with_column(version)
+            .map_err(|e| format!("添加列 {} 失败: {}", version_name, e))?;
+        df.with_column(valid)
+            .map_err(|e| format!("添加列 {} 失败: {}", valid_name, e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::ParseIpAddresses { column, mmdb_path };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 5. User-Agent 解析（Parse User Agents）
+// ============================================================================
+/// 将 User-Agent 列解析为浏览器列 "{column}_browser" 和操作系统列 "{column}_os"
+///
+/// 无法识别时结果为空值
+///
+/// 参数：
+/// - column: User-Agent 列
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error）
+#[tauri::command]
+pub async fn parse_user_agents(
+    column: String,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let column_clone = column.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let browser_name = outputs
+            .resolve(&format!("{}_browser", column_clone))
+            .map_err(|e| e.to_string())?;
+        let os_name = outputs
+            .resolve(&format!("{}_os", column_clone))
+            .map_err(|e| e.to_string())?;
+
+        let browser = map_string_column(&current_df, &column_clone, &browser_name, user_agent_browser)
+            .map_err(|e| e.to_string())?;
+        let os = map_string_column(&current_df, &column_clone, &os_name, user_agent_os).map_err(|e| e.to_string())?;
+
+        let mut df = current_df;
+        df.with_column(browser)
+            .map_err(|e| format!("添加列 {} 失败: {}", browser_name, e))?;
+        df.with_column(os)
+            .map_err(|e| format!("添加列 {} 失败: {}", os_name, e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::ParseUserAgents { column };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 6. 金额解析（Parse Currency）
+// ============================================================================
+/// 将带货币格式的字符串解析为 Float64
+///
+/// 去除货币符号和千分位，括号表示负数："($1,234.56)" → -1234.56
+/// 无法解析的值结果为空值
+///
+/// 参数：
+/// - column: 金额列
+/// - decimal_separator: 小数点字符，"." 或 ","（默认 "."）
+/// - output_column: 新列名（默认原地替换）
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error；覆盖原列时不检查）
+#[tauri::command]
+pub async fn parse_currency(
+    column: String,
+    decimal_separator: Option<String>,
+    output_column: Option<String>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let decimal_char = match decimal_separator.as_deref().unwrap_or(".") {
+        "." => '.',
+        "," => ',',
+        other => return Err(format!("不支持的小数点字符: {}", other)),
+    };
+
+    let column_clone = column.clone();
+    let output_name = output_column.clone().unwrap_or_else(|| column.clone());
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let output_name = if output_name == column_clone {
+            output_name
+        } else {
+            outputs.resolve(&output_name).map_err(|e| e.to_string())?
+        };
+
+        let series = map_string_to_f64(&current_df, &column_clone, &output_name, |value| {
+            parse_currency_value(value, decimal_char)
+        })
+        .map_err(|e| e.to_string())?;
+
+        let mut df = current_df;
+        df.with_column(series)
+            .map_err(|e| format!("更新列 {} 失败: {}", output_name, e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::ParseCurrency {
+        column,
+        decimal_separator: decimal_char.to_string(),
+        output_column,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 7. 百分比解析（Parse Percentages）
+// ============================================================================
+/// 将百分比字符串转换为小数（原地替换为 Float64）："12.5%" → 0.125
+///
+/// 整列都按百分数解释（百分号可省略）；无法解析的值结果为空值
+///
+/// 参数：
+/// - columns: 要处理的列名列表
+#[tauri::command]
+pub async fn parse_percentages(columns: Vec<String>, state: State<'_, AppState>) -> Result<(), String> {
+    if columns.is_empty() {
+        return Err("至少需要选择一列".to_string());
+    }
+
+    let cols_clone = columns.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let result_df = tauri::async_runtime::spawn_blocking(move || {
+        let mut df = current_df;
+        for column in &cols_clone {
+            let series = map_string_to_f64(&df, column, column, parse_percentage_value).map_err(|e| e.to_string())?;
+            df.with_column(series)
+                .map_err(|e| format!("更新列 {} 失败: {}", column, e))?;
+        }
+        Ok::<DataFrame, String>(df)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::ParsePercentages { columns };
+    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 8. 百分比格式化（Format Percentages）
+// ============================================================================
+/// 将小数列转换为百分比字符串（原地替换为 String）：0.125 → "12.5%"
+///
+/// 用于导出需要百分比文本的场景；只改变显示时请使用列显示格式（set_column_format）
+///
+/// 参数：
+/// - columns: 要处理的数值列
+/// - decimal_places: 保留的小数位数（默认最多 6 位并去掉末尾的 0）
+#[tauri::command]
+pub async fn format_percentages(
+    columns: Vec<String>,
+    decimal_places: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if columns.is_empty() {
+        return Err("至少需要选择一列".to_string());
+    }
+
+    let cols_clone = columns.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let result_df = tauri::async_runtime::spawn_blocking(move || {
+        let mut df = current_df;
+        for column in &cols_clone {
+            let source = df.column(column).map_err(|_| format!("列 '{}' 不存在", column))?;
+            if !is_numeric_dtype(source.dtype()) {
+                return Err(format!("列 '{}' 不是数值列", column));
+            }
+
+            let values = source
+                .cast(&DataType::Float64)
+                .map_err(|e| format!("转换列 {} 失败: {}", column, e))?;
+            let formatted: StringChunked = values
+                .f64()
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .map(|value| value.map(|v| format_percentage_value(v, decimal_places)))
+                .collect();
+
+            df.with_column(formatted.with_name(column.as_str().into()).into_series())
+                .map_err(|e| format!("更新列 {} 失败: {}", column, e))?;
+        }
+        Ok::<DataFrame, String>(df)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::FormatPercentages {
+        columns,
+        decimal_places,
+    };
+    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 9. 布尔值规范化（Normalize Boolean）
+// ============================================================================
+/// 将 "Y/N"、"yes/no"、"1/0"、"true/false"、"是/否" 等标记转换为布尔列
+///
+/// 比较时忽略大小写和首尾空白；无法识别的值转换为空值并在返回的报告中列出
+///
+/// 参数：
+/// - column: 源列
+/// - true_values: 真值标记（默认 true/t/yes/y/1/on/是/对/真）
+/// - false_values: 假值标记（默认 false/f/no/n/0/off/否/错/假）
+/// - output_column: 新列名（默认原地替换）
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error；覆盖原列时不检查）
+///
+/// 返回：
+/// - Result<UnmatchedValuesReport, String>: 无法识别的值
+#[tauri::command]
+pub async fn normalize_boolean(
+    column: String,
+    true_values: Option<Vec<String>>,
+    false_values: Option<Vec<String>>,
+    output_column: Option<String>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<UnmatchedValuesReport, String> {
+    let to_tokens = |tokens: &[&str]| tokens.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+    let true_values = true_values.unwrap_or_else(|| to_tokens(DEFAULT_TRUE_TOKENS));
+    let false_values = false_values.unwrap_or_else(|| to_tokens(DEFAULT_FALSE_TOKENS));
+
+    let column_clone = column.clone();
+    let true_clone = true_values.clone();
+    let false_clone = false_values.clone();
+    let output_name = output_column.clone().unwrap_or_else(|| column.clone());
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, report, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let output_name = if output_name == column_clone {
+            output_name
+        } else {
+            outputs.resolve(&output_name).map_err(|e| e.to_string())?
+        };
+
+        let (series, report) = map_boolean_column(&current_df, &column_clone, &output_name, &true_clone, &false_clone)
+            .map_err(|e| e.to_string())?;
+
+        let mut df = current_df;
+        df.with_column(series)
+            .map_err(|e| format!("更新列 {} 失败: {}", output_name, e))?;
+        Ok::<_, String>((df, report, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::NormalizeBoolean {
+        column,
+        true_values,
+        false_values,
+        output_column,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(report)
+}
+
+// ============================================================================
+// 10. 空白清理（Clean Whitespace）
+// ============================================================================
+/// 清理字符串列中的空白和不可见字符（原地替换）
+///
+/// 去除首尾空白、合并连续空白、删除零宽字符、把不换行空格替换为普通空格
+///
+/// 参数：
+/// - columns: 要处理的列名列表（None 表示所有字符串列）
+#[tauri::command]
+pub async fn clean_whitespace(columns: Option<Vec<String>>, state: State<'_, AppState>) -> Result<(), String> {
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let target_columns: Vec<String> = match &columns {
+        Some(cols) => {
+            for column in cols {
+                let dtype = current_df
+                    .column(column)
+                    .map_err(|_| format!("列 '{}' 不存在", column))?
+                    .dtype();
+                if dtype != &DataType::String {
+                    return Err(format!("列 '{}' 不是字符串列", column));
+                }
+            }
+            cols.clone()
+        }
+        None => current_df
+            .get_columns()
+            .iter()
+            .filter(|c| c.dtype() == &DataType::String)
+            .map(|c| c.name().to_string())
+            .collect(),
+    };
+
+    if target_columns.is_empty() {
+        return Err("没有需要处理的字符串列".to_string());
+    }
+
+    let result_df = tauri::async_runtime::spawn_blocking(move || {
+        let mut df = current_df;
+        for column in &target_columns {
+            let series = map_string_column(&df, column, column, |value| Some(clean_whitespace_value(value)))
+                .map_err(|e| e.to_string())?;
+            df.with_column(series)
+                .map_err(|e| format!("更新列 {} 失败: {}", column, e))?;
+        }
+        Ok::<DataFrame, String>(df)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::CleanWhitespace { columns };
+    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 11. 替换 NaN / 无穷大（Replace Non-Finite）
+// ============================================================================
+/// 把浮点列中的 NaN、正负无穷大替换为空值或指定值（原地替换）
+///
+/// 表格默认把这些值显示为空（见 set_float_precision 的 non_finite），
+/// 替换后它们成为真正的空值或普通数值，可以被筛选、填充空值等操作处理
+///
+/// 参数：
+/// - columns: 要处理的浮点列（None 表示所有浮点列）
+/// - value: 替换值（可选，必须是有限数；None 表示替换为空值）
+///
+/// 返回：
+/// - Result<usize, String>: 被替换的单元格数量；所选列中没有 NaN / 无穷大时返回错误
+#[tauri::command]
+pub async fn replace_non_finite(
+    columns: Option<Vec<String>>,
+    value: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    if value.is_some_and(|v| !v.is_finite()) {
+        return Err("替换值必须是有限数".to_string());
+    }
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let target_columns: Vec<String> = match &columns {
+        Some(cols) => {
+            for column in cols {
+                let dtype = current_df
+                    .column(column)
+                    .map_err(|_| format!("列 '{}' 不存在", column))?
+                    .dtype();
+                if !dtype.is_float() {
+                    return Err(format!("列 '{}' 不是浮点列", column));
+                }
+            }
+            cols.clone()
+        }
+        None => current_df
+            .get_columns()
+            .iter()
+            .filter(|c| c.dtype().is_float())
+            .map(|c| c.name().to_string())
+            .collect(),
+    };
+
+    if target_columns.is_empty() {
+        return Err("没有需要处理的浮点列".to_string());
+    }
+
+    let (result_df, replaced) = tauri::async_runtime::spawn_blocking(move || {
+        // NaN / 无穷大为 true，空值为 false
+        let non_finite = |name: &str| col(name).is_finite().not().fill_null(lit(false));
+
+        let counts = current_df
+            .clone()
+            .lazy()
+            .select(
+                target_columns
+                    .iter()
+                    .map(|name| non_finite(name).cast(DataType::UInt64).sum().alias(name.as_str()))
+                    .collect::<Vec<_>>(),
+            )
+            .collect()
+            .map_err(|e| format!("统计 NaN / 无穷大失败: {}", e))?;
+        let replaced: usize = counts
+            .get_columns()
+            .iter()
+            .filter_map(|c| c.get(0).ok().and_then(|v| v.extract::<u64>()))
+            .sum::<u64>() as usize;
+        if replaced == 0 {
+            return Err("所选列中没有 NaN 或无穷大".to_string());
+        }
+
+        let exprs: Vec<Expr> = target_columns
+            .iter()
+            .map(|name| {
+                let dtype = current_df
+                    .column(name)
+                    .map(|c| c.dtype().clone())
+                    .unwrap_or(DataType::Float64);
+                let replacement = match value {
+                    Some(v) => lit(v),
+                    None => lit(NULL),
+                };
+                when(non_finite(name))
+                    .then(replacement.cast(dtype))
+                    .otherwise(col(name.as_str()))
+                    .alias(name.as_str())
+            })
+            .collect();
+        let df = current_df
+            .lazy()
+            .with_columns(exprs)
+            .collect()
+            .map_err(|e| format!("替换 NaN / 无穷大失败: {}", e))?;
+        Ok::<(DataFrame, usize), String>((df, replaced))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::ReplaceNonFinite { columns, value };
+    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(replaced)
+}
@@ -8,39 +8,278 @@
 // 导入依赖
 // ============================================================================
 use crate::AppState;
+use crate::commands::data_query::{build_scalar_agg, compute_column_stats};
+use crate::commands::operations::compute_pivot;
+use crate::commands::settings::{export_profiles_path, stable_ordering};
+use crate::commands::tasks::emit_task_progress;
+use crate::data::clipboard::render_clipboard_text;
+use crate::data::crypto::{encrypt_columns, validate_encryption, write_encrypted_zip, zip_entry_name};
+use crate::data::excel::{write_table, write_workbook};
+use crate::data::export::{
+    check_overwrite, create_new_output, parquet_compression, write_atomic_checked, write_csv_chunks, write_ipc_chunks,
+    write_ipc_stream, write_parquet_chunks,
+};
+use crate::data::export_profiles::find_profile;
+use crate::data::nested::nested_columns_to_json;
+use crate::data::partition::partition_dataframe;
+use crate::data::profile_report::{
+    DEFAULT_TOP_VALUES, build_profile_report, infer_profile_format, write_profile_report,
+};
+use crate::data::sqlite::write_sqlite_table;
+use crate::data::{
+    DataStore, TaskHandle, apply_column_formats, report_path_for, select_export_subset, validate_dataframe,
+    write_validation_report,
+};
+use crate::models::{
+    AggregationSpec, ClipboardFormat, ColumnStats, CsvExportProfile, ExcelReportOptions, ExportEncryption,
+    ExportRecord, ExportSubset, ParquetExportCompression, ProfileReportFormat, SqliteIfExists, ValidationExportOptions,
+};
 use polars::prelude::*;
+use std::collections::HashSet;
+use std::path::Path;
+use tauri::AppHandle;
+use tauri::ipc::Response;
+
+// ============================================================================
+// 辅助函数：导出前校验
+// ============================================================================
+/// 按校验选项检查数据，并在导出文件旁边写出校验报告
+///
+/// 存在违规且 refuse_on_violations 为 true 时返回错误（报告仍会写出）
+fn run_export_validation(df: &DataFrame, output_path: &str, options: &ValidationExportOptions) -> Result<(), String> {
+    let report = validate_dataframe(df, &options.rules).map_err(|e| e.to_string())?;
+
+    let report_path = report_path_for(output_path, options.report_format);
+    if options.write_report {
+        write_validation_report(&report, &report_path, options.report_format)
+            .map_err(|e| format!("写入校验报告失败: {}", e))?;
+    }
+
+    if options.refuse_on_violations && !report.is_clean() {
+        let mut message = format!("数据存在 {} 处校验违规，已拒绝导出", report.violation_count);
+        if options.write_report {
+            message.push_str(&format!("（详见 {}）", report_path.display()));
+        }
+        return Err(message);
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// 辅助函数：列级加密
+// ============================================================================
+/// 按加密选项加密敏感列（选择 zip 加密或未加密时原样返回）
+fn encrypt_export_columns(df: DataFrame, encryption: &Option<ExportEncryption>) -> Result<DataFrame, String> {
+    match encryption {
+        Some(ExportEncryption::Columns { columns, passphrase }) => {
+            encrypt_columns(&df, columns, passphrase).map_err(|e| e.to_string())
+        }
+        _ => Ok(df),
+    }
+}
+
+// ============================================================================
+// 辅助函数：导出子集
+// ============================================================================
+/// 按子集选项取出要导出的列和行（未指定时原样返回）
+///
+/// 只影响导出文件，当前数据集和历史都不会被修改
+fn select_subset(df: DataFrame, subset: &Option<ExportSubset>) -> Result<DataFrame, String> {
+    match subset {
+        Some(subset) => select_export_subset(&df, subset).map_err(|e| format!("选择导出范围失败: {}", e)),
+        None => Ok(df),
+    }
+}
+
+// ============================================================================
+// 辅助函数：导出记录
+// ============================================================================
+/// ExportSource - 导出的数据所在的历史条目
+///
+/// 在取出数据时记录（导出期间数据集可能继续被修改或撤销）
+#[derive(Clone)]
+pub(crate) struct ExportSource {
+    entry_id: Option<String>,
+    description: Option<String>,
+}
+
+impl ExportSource {
+    /// 当前历史条目
+    pub(crate) fn current(store: &DataStore) -> Self {
+        let entry = store.get_current_entry();
+        Self {
+            entry_id: entry.map(|entry| entry.id.clone()),
+            description: entry.map(|entry| entry.description.clone()),
+        }
+    }
+}
+
+/// 导出成功后写入导出记录（get_export_log 返回）
+///
+/// 参数：
+/// - shape: 导出的（行数, 列数）
+pub(crate) fn record_export(
+    state: &AppState,
+    source: ExportSource,
+    kind: &str,
+    output_path: &str,
+    shape: (usize, usize),
+) -> Result<(), String> {
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+    store.record_export(ExportRecord {
+        path: output_path.to_string(),
+        kind: kind.to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        history_entry_id: source.entry_id,
+        history_description: source.description,
+        rows: shape.0,
+        columns: shape.1,
+    });
+    Ok(())
+}
+
+// ============================================================================
+// 辅助函数：后台导出
+// ============================================================================
+/// 将导出注册为后台任务，在阻塞线程中执行 write，并在结束时推送最终状态
+///
+/// write 接收任务句柄和进度回调；任务 ID 由前端传入时，可以在导出结束前调用 cancel_task 取消
+async fn run_export_task<F>(
+    app: AppHandle,
+    state: &AppState,
+    task_id: Option<String>,
+    kind: &str,
+    total_rows: usize,
+    write: F,
+) -> Result<(), String>
+where
+    F: FnOnce(&TaskHandle, &dyn Fn(&TaskHandle)) -> Result<(), String> + Send + 'static,
+{
+    let task = state
+        .tasks
+        .start(task_id, kind, Some(total_rows))
+        .map_err(|e| e.to_string())?;
+    emit_task_progress(&app, &task);
+
+    let task_clone = task.clone();
+    let app_clone = app.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        write(&task_clone, &|t: &TaskHandle| emit_task_progress(&app_clone, t))
+    })
+    .await
+    .unwrap_or_else(|e| Err(e.to_string()));
+
+    task.finish(&result);
+    emit_task_progress(&app, &task);
+    result
+}
 
 // ============================================================================
 // 导出当前数据集为 CSV 文件
 // ============================================================================
 /// 导出当前数据集为 CSV 文件
 ///
+/// 导出作为后台任务运行：
+/// - 按批次写出，通过 "task://progress" 事件推送已写行数
+/// - 可以用 cancel_task 取消
+/// - 先写入临时文件，成功后再重命名，失败或取消时不会留下不完整的文件
+///
 /// 参数：
-/// - output_path: 输出文件的完整路径
+/// - output_path: 输出文件的完整路径（使用 zip 加密时应为 .zip）
+/// - apply_formats: 是否按列显示格式输出（默认 false，即输出原始值）
+/// - validation: 校验选项（可选），在导出文件旁边写出校验报告，并可在有违规时拒绝导出
+/// - encryption: 加密选项（可选），加密指定的敏感列，或打包为带密码的 zip
+/// - profile: 导出预设名称（可选，例如 "Excel-DE"），决定分隔符、编码、引号、日期格式等
+/// - subset: 导出范围（可选），只导出指定的列和满足 SQL 条件的行，不修改数据集和历史
+/// - overwrite: 目标文件已存在时是否覆盖（可选，默认 true；为 false 且文件已存在时返回错误，不写出任何内容）
+/// - task_id: 任务 ID（可选），用于查询进度和取消
+/// - app: 应用句柄（自动注入，用于推送进度事件）
 /// - state: 应用状态（自动注入）
 ///
 /// 返回：
 /// - Result<String, String>: 成功返回文件路径，失败返回错误
 #[tauri::command]
-pub async fn export_csv(output_path: String, state: tauri::State<'_, AppState>) -> Result<String, String> {
-    // 获取当前 DataFrame
-    let store = state
-        .data_store
-        .lock()
-        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+#[allow(clippy::too_many_arguments)]
+pub async fn export_csv(
+    output_path: String,
+    apply_formats: Option<bool>,
+    validation: Option<ValidationExportOptions>,
+    encryption: Option<ExportEncryption>,
+    profile: Option<String>,
+    subset: Option<ExportSubset>,
+    overwrite: Option<bool>,
+    task_id: Option<String>,
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let overwrite = overwrite.unwrap_or(true);
+    check_overwrite(&output_path, overwrite).map_err(|e| e.to_string())?;
+    // 获取当前 DataFrame 和显示设置（不在导出期间持有锁）
+    let (current_df, display, source) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        let df = store.get_current().ok_or("没有数据")?.clone();
+        (df, store.get_current_display().cloned(), ExportSource::current(&store))
+    };
+    let current_df = select_subset(current_df, &subset)?;
 
-    let df = store.get_current().ok_or("没有数据")?;
+    if let Some(encryption) = &encryption {
+        validate_encryption(&current_df, encryption).map_err(|e| e.to_string())?;
+    }
 
-    // 创建输出文件
-    let mut file = std::fs::File::create(&output_path).map_err(|e| format!("Failed to create file: {}", e))?;
+    let profile = match profile {
+        Some(name) => find_profile(&export_profiles_path(&app)?, &name).map_err(|e| e.to_string())?,
+        None => CsvExportProfile::default(),
+    };
 
-    // 写入 CSV 数据
-    CsvWriter::new(&mut file)
-        .include_header(true)
-        .with_separator(b',')
-        .finish(&mut df.clone())
-        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+    let shape = current_df.shape();
+    let total_rows = current_df.height();
+    let path_clone = output_path.clone();
+    run_export_task(
+        app,
+        &state,
+        task_id,
+        "export_csv",
+        total_rows,
+        move |task, on_progress| {
+            // 校验原始数据（格式化之前）
+            if let Some(options) = &validation {
+                run_export_validation(&current_df, &path_clone, options)?;
+            }
 
+            // 按列显示格式输出（只影响导出文件，不修改数据）
+            let df = match display {
+                Some(display) if apply_formats.unwrap_or(false) && !display.column_formats.is_empty() => {
+                    apply_column_formats(&current_df, &display.column_formats)
+                        .map_err(|e| format!("应用列格式失败: {}", e))?
+                }
+                _ => current_df,
+            };
+            // 列表、结构体列写为 JSON 文本（CSV 不支持嵌套类型）
+            let df = nested_columns_to_json(&df).map_err(|e| format!("转换嵌套列失败: {}", e))?;
+            let df = encrypt_export_columns(df, &encryption)?;
+
+            write_atomic_checked(&path_clone, overwrite, |file| match &encryption {
+                Some(ExportEncryption::Zip { password }) => {
+                    let entry_name = zip_entry_name(&path_clone, "csv");
+                    write_encrypted_zip(file, &entry_name, password, |zip| {
+                        write_csv_chunks(zip, &df, &profile, task, on_progress)
+                    })
+                }
+                _ => write_csv_chunks(file, &df, &profile, task, on_progress),
+            })
+            .map_err(|e| format!("Failed to write CSV: {}", e))
+        },
+    )
+    .await?;
+
+    record_export(&state, source, "export_csv", &output_path, shape)?;
     Ok(output_path)
 }
 
@@ -49,30 +288,905 @@ pub async fn export_csv(output_path: String, state: tauri::State<'_, AppState>)
 // ============================================================================
 /// 导出当前数据集为 Parquet 文件
 ///
+/// 与 export_csv 相同，作为后台任务运行（进度事件、可取消、原子重命名）
+///
+/// 参数：
+/// - output_path: 输出文件的完整路径（使用 zip 加密时应为 .zip）
+/// - validation: 校验选项（可选），在导出文件旁边写出校验报告，并可在有违规时拒绝导出
+/// - encryption: 加密选项（可选），加密指定的敏感列，或打包为带密码的 zip
+/// - subset: 导出范围（可选），只导出指定的列和满足 SQL 条件的行，不修改数据集和历史
+/// - compression: 压缩方式（可选，snappy / zstd / gzip / uncompressed，zstd 和 gzip 可指定级别；默认 zstd）
+/// - row_group_size: 每个 row group 的行数（可选，默认 50000）
+/// - overwrite: 目标文件已存在时是否覆盖（可选，默认 true；为 false 且文件已存在时返回错误，不写出任何内容）
+/// - task_id: 任务 ID（可选），用于查询进度和取消
+/// - app: 应用句柄（自动注入，用于推送进度事件）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<String, String>: 成功返回文件路径，失败返回错误
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn export_parquet(
+    output_path: String,
+    validation: Option<ValidationExportOptions>,
+    encryption: Option<ExportEncryption>,
+    subset: Option<ExportSubset>,
+    compression: Option<ParquetExportCompression>,
+    row_group_size: Option<usize>,
+    overwrite: Option<bool>,
+    task_id: Option<String>,
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let overwrite = overwrite.unwrap_or(true);
+    check_overwrite(&output_path, overwrite).map_err(|e| e.to_string())?;
+    // 获取当前 DataFrame（不在导出期间持有锁）
+    let (current_df, source) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        (
+            store.get_current().ok_or("没有数据")?.clone(),
+            ExportSource::current(&store),
+        )
+    };
+    let current_df = select_subset(current_df, &subset)?;
+
+    if let Some(encryption) = &encryption {
+        validate_encryption(&current_df, encryption).map_err(|e| e.to_string())?;
+    }
+
+    let compression = parquet_compression(compression).map_err(|e| e.to_string())?;
+    if row_group_size == Some(0) {
+        return Err("row group 行数必须大于 0".to_string());
+    }
+
+    let shape = current_df.shape();
+    let total_rows = current_df.height();
+    let path_clone = output_path.clone();
+    run_export_task(
+        app,
+        &state,
+        task_id,
+        "export_parquet",
+        total_rows,
+        move |task, on_progress| {
+            if let Some(options) = &validation {
+                run_export_validation(&current_df, &path_clone, options)?;
+            }
+            let df = encrypt_export_columns(current_df, &encryption)?;
+
+            write_atomic_checked(&path_clone, overwrite, |file| match &encryption {
+                Some(ExportEncryption::Zip { password }) => {
+                    let entry_name = zip_entry_name(&path_clone, "parquet");
+                    write_encrypted_zip(file, &entry_name, password, |zip| {
+                        write_parquet_chunks(zip, &df, compression, row_group_size, task, on_progress)
+                    })
+                }
+                _ => write_parquet_chunks(file, &df, compression, row_group_size, task, on_progress),
+            })
+            .map_err(|e| format!("Failed to write Parquet: {}", e))
+        },
+    )
+    .await?;
+
+    record_export(&state, source, "export_parquet", &output_path, shape)?;
+    Ok(output_path)
+}
+
+// ============================================================================
+// 导出当前数据集为 Arrow IPC 文件
+// ============================================================================
+/// 导出当前数据集为 Arrow IPC（Feather v2）文件
+///
+/// 数据类型完整保留，pandas（pd.read_feather）、polars、R arrow 可以直接读取
+/// 与 export_parquet 相同，作为后台任务运行（进度事件、可取消、原子重命名）
+///
+/// 参数：
+/// - output_path: 输出文件的完整路径（.arrow / .feather；使用 zip 加密时应为 .zip）
+/// - validation: 校验选项（可选），在导出文件旁边写出校验报告，并可在有违规时拒绝导出
+/// - encryption: 加密选项（可选），加密指定的敏感列，或打包为带密码的 zip
+/// - subset: 导出范围（可选），只导出指定的列和满足 SQL 条件的行，不修改数据集和历史
+/// - overwrite: 目标文件已存在时是否覆盖（可选，默认 true；为 false 且文件已存在时返回错误，不写出任何内容）
+/// - task_id: 任务 ID（可选），用于查询进度和取消
+/// - app: 应用句柄（自动注入，用于推送进度事件）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<String, String>: 成功返回文件路径，失败返回错误
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn export_arrow(
+    output_path: String,
+    validation: Option<ValidationExportOptions>,
+    encryption: Option<ExportEncryption>,
+    subset: Option<ExportSubset>,
+    overwrite: Option<bool>,
+    task_id: Option<String>,
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let overwrite = overwrite.unwrap_or(true);
+    check_overwrite(&output_path, overwrite).map_err(|e| e.to_string())?;
+    // 获取当前 DataFrame（不在导出期间持有锁）
+    let (current_df, source) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        (
+            store.get_current().ok_or("没有数据")?.clone(),
+            ExportSource::current(&store),
+        )
+    };
+    let current_df = select_subset(current_df, &subset)?;
+
+    if let Some(encryption) = &encryption {
+        validate_encryption(&current_df, encryption).map_err(|e| e.to_string())?;
+    }
+
+    let shape = current_df.shape();
+    let total_rows = current_df.height();
+    let path_clone = output_path.clone();
+    run_export_task(
+        app,
+        &state,
+        task_id,
+        "export_arrow",
+        total_rows,
+        move |task, on_progress| {
+            if let Some(options) = &validation {
+                run_export_validation(&current_df, &path_clone, options)?;
+            }
+            let df = encrypt_export_columns(current_df, &encryption)?;
+
+            write_atomic_checked(&path_clone, overwrite, |file| match &encryption {
+                Some(ExportEncryption::Zip { password }) => {
+                    let entry_name = zip_entry_name(&path_clone, "arrow");
+                    write_encrypted_zip(file, &entry_name, password, |zip| {
+                        write_ipc_chunks(zip, &df, task, on_progress)
+                    })
+                }
+                _ => write_ipc_chunks(file, &df, task, on_progress),
+            })
+            .map_err(|e| format!("Failed to write Arrow IPC: {}", e))
+        },
+    )
+    .await?;
+
+    record_export(&state, source, "export_arrow", &output_path, shape)?;
+    Ok(output_path)
+}
+
+// ============================================================================
+// 导出当前数据集为 Arrow IPC 流
+// ============================================================================
+/// 将当前数据集序列化为 Arrow IPC 流，直接返回字节或写入命名管道
+///
+/// 用于把数据交给本地 Python / R 进程（不需要先写临时文件）：
+/// - 未指定 pipe_path：以二进制响应返回 IPC 流字节（前端收到 ArrayBuffer）
+/// - 指定 pipe_path：写入该路径（命名管道 / FIFO 会等待读取端打开），返回空响应
+///
+/// 参数：
+/// - pipe_path: 命名管道路径（可选，也可以是普通文件路径）
+/// - subset: 导出范围（可选），只导出指定的列和满足 SQL 条件的行，不修改数据集和历史
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<Response, String>: IPC 流字节（写入管道时为空），失败返回错误
+///
+/// 读取示例（Python）：
+/// ```python
+/// import pyarrow as pa
+/// table = pa.ipc.open_stream(open("/tmp/datavis.pipe", "rb")).read_all()
+/// ```
+#[tauri::command]
+pub async fn export_arrow_stream(
+    pipe_path: Option<String>,
+    subset: Option<ExportSubset>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Response, String> {
+    let (current_df, source) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        (
+            store.get_current().ok_or("没有数据")?.clone(),
+            ExportSource::current(&store),
+        )
+    };
+    let current_df = select_subset(current_df, &subset)?;
+
+    let shape = current_df.shape();
+    let path_clone = pipe_path.clone();
+    let bytes = tauri::async_runtime::spawn_blocking(move || match &path_clone {
+        Some(path) => {
+            let mut pipe = std::fs::OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(path)
+                .map_err(|e| format!("打开管道 {} 失败: {}", path, e))?;
+            write_ipc_stream(&mut pipe, &current_df).map_err(|e| format!("写入 Arrow IPC 流失败: {}", e))?;
+            Ok(Vec::new())
+        }
+        None => {
+            let mut buffer = Vec::new();
+            write_ipc_stream(&mut buffer, &current_df).map_err(|e| format!("写入 Arrow IPC 流失败: {}", e))?;
+            Ok::<Vec<u8>, String>(buffer)
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    if let Some(path) = &pipe_path {
+        record_export(&state, source, "export_arrow_stream", path, shape)?;
+    }
+    Ok(Response::new(bytes))
+}
+
+// ============================================================================
+// 导出分组汇总
+// ============================================================================
+/// 按分组列聚合当前数据集，并直接将汇总结果写入文件
+///
+/// 当前数据集和历史都不会被修改，适合"只要汇总数"的场景
+/// 输出格式由文件扩展名决定：.csv / .parquet / .arrow（.feather, .ipc）/ .xlsx
+/// 汇总结果按分组列排序；group_by 为空时输出一行总计
+///
 /// 参数：
 /// - output_path: 输出文件的完整路径
+/// - group_by: 分组列（可以为空）
+/// - aggregations: 聚合列表，每个聚合生成一列
+/// - overwrite: 目标文件已存在时是否覆盖（可选，默认 true；为 false 且文件已存在时返回错误，不写出任何内容）
+/// - task_id: 任务 ID（可选），用于查询进度和取消
+/// - app: 应用句柄（自动注入，用于推送进度事件）
 /// - state: 应用状态（自动注入）
 ///
 /// 返回：
 /// - Result<String, String>: 成功返回文件路径，失败返回错误
 #[tauri::command]
-pub async fn export_parquet(output_path: String, state: tauri::State<'_, AppState>) -> Result<String, String> {
-    // 获取当前 DataFrame
-    let store = state
-        .data_store
-        .lock()
-        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+pub async fn export_aggregated(
+    output_path: String,
+    group_by: Vec<String>,
+    aggregations: Vec<AggregationSpec>,
+    overwrite: Option<bool>,
+    task_id: Option<String>,
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let overwrite = overwrite.unwrap_or(true);
+    check_overwrite(&output_path, overwrite).map_err(|e| e.to_string())?;
+    if aggregations.is_empty() {
+        return Err("至少需要指定一个聚合".to_string());
+    }
+
+    let extension = Path::new(&output_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    if !matches!(
+        extension.as_str(),
+        "csv" | "parquet" | "arrow" | "feather" | "ipc" | "xlsx"
+    ) {
+        return Err(format!(
+            "不支持的导出格式 '.{}'，请使用 .csv、.parquet、.arrow 或 .xlsx",
+            extension
+        ));
+    }
+
+    let (current_df, source) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        (
+            store.get_current().ok_or("没有数据")?.clone(),
+            ExportSource::current(&store),
+        )
+    };
+
+    let summary =
+        tauri::async_runtime::spawn_blocking(move || aggregate_for_export(current_df, &group_by, &aggregations))
+            .await
+            .map_err(|e| e.to_string())??;
+
+    let shape = summary.shape();
+    let total_rows = summary.height();
+    let path_clone = output_path.clone();
+    run_export_task(
+        app,
+        &state,
+        task_id,
+        "export_aggregated",
+        total_rows,
+        move |task, on_progress| {
+            let result = match extension.as_str() {
+                "csv" => {
+                    let df = nested_columns_to_json(&summary).map_err(|e| format!("转换嵌套列失败: {}", e))?;
+                    let profile = CsvExportProfile::default();
+                    write_atomic_checked(&path_clone, overwrite, |file| {
+                        write_csv_chunks(file, &df, &profile, task, on_progress)
+                    })
+                }
+                "parquet" => write_atomic_checked(&path_clone, overwrite, |file| {
+                    write_parquet_chunks(file, &summary, ParquetCompression::default(), None, task, on_progress)
+                }),
+                "xlsx" => write_workbook(&path_clone, &[("汇总".to_string(), summary)], overwrite),
+                _ => write_atomic_checked(&path_clone, overwrite, |file| {
+                    write_ipc_chunks(file, &summary, task, on_progress)
+                }),
+            };
+            result.map_err(|e| format!("写出汇总文件失败: {}", e))
+        },
+    )
+    .await?;
+
+    record_export(&state, source, "export_aggregated", &output_path, shape)?;
+    Ok(output_path)
+}
+
+/// 计算分组汇总（不修改数据）
+fn aggregate_for_export(
+    df: DataFrame,
+    group_by: &[String],
+    aggregations: &[AggregationSpec],
+) -> Result<DataFrame, String> {
+    for column in group_by {
+        if df.get_column_index(column).is_none() {
+            return Err(format!("列 '{}' 不存在", column));
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut agg_exprs = Vec::with_capacity(aggregations.len());
+    for spec in aggregations {
+        let name = spec.output_name();
+        if group_by.contains(&name) || !seen.insert(name.clone()) {
+            return Err(format!("汇总结果中的列名 '{}' 重复，请指定 alias", name));
+        }
+        let expr = build_scalar_agg(&df, &spec.column, &spec.function, spec.weight_column.as_deref())?;
+        agg_exprs.push(expr.alias(name));
+    }
+
+    let summary = if group_by.is_empty() {
+        df.lazy().select(agg_exprs)
+    } else {
+        let keys: Vec<Expr> = group_by.iter().map(|name| col(name.as_str())).collect();
+        df.lazy()
+            .group_by(keys)
+            .agg(agg_exprs)
+            .sort(group_by.to_vec(), SortMultipleOptions::default().with_nulls_last(true))
+    };
+
+    summary.collect().map_err(|e| format!("分组汇总失败: {}", e))
+}
+
+// ============================================================================
+// 按列值拆分导出
+// ============================================================================
+/// 按某一列的取值把当前数据集拆分为多个文件（每个取值一个文件，例如每个地区一个 CSV）
+///
+/// 文件名为取值本身（非法字符替换为 "_"，空值为 "__null__"），写入 output_dir
+/// 目录不存在时自动创建；任何目标文件已存在时不写出任何文件，
+/// 写出期间才出现的同名文件也不会被覆盖（返回错误）
+/// 与 export_csv 相同，作为后台任务运行（进度事件、可取消），每个文件都原子写入
+///
+/// 参数：
+/// - output_dir: 输出目录
+/// - partition_column: 分区列
+/// - format: 文件格式（"csv" / "parquet" / "arrow"）
+/// - task_id: 任务 ID（可选），用于查询进度和取消
+/// - app: 应用句柄（自动注入，用于推送进度事件）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<Vec<String>, String>: 成功返回写出的文件路径（按取值首次出现的顺序），失败返回错误
+#[tauri::command]
+pub async fn export_partitioned(
+    output_dir: String,
+    partition_column: String,
+    format: String,
+    task_id: Option<String>,
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let extension = format.trim().to_ascii_lowercase();
+    if !matches!(extension.as_str(), "csv" | "parquet" | "arrow") {
+        return Err(format!("不支持的导出格式 '{}'，请使用 csv、parquet 或 arrow", format));
+    }
+
+    let (current_df, source) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        (
+            store.get_current().ok_or("没有数据")?.clone(),
+            ExportSource::current(&store),
+        )
+    };
+
+    let extension_clone = extension.clone();
+    let partitions = tauri::async_runtime::spawn_blocking(move || {
+        partition_dataframe(&current_df, &partition_column, &extension_clone)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    let dir = Path::new(&output_dir);
+    std::fs::create_dir_all(dir).map_err(|e| format!("创建输出目录失败: {}", e))?;
+    let targets: Vec<(String, (usize, usize))> = partitions
+        .iter()
+        .map(|partition| {
+            (
+                dir.join(&partition.file_name).to_string_lossy().to_string(),
+                partition.df.shape(),
+            )
+        })
+        .collect();
+    if let Some((existing, _)) = targets.iter().find(|(path, _)| Path::new(path).exists()) {
+        return Err(format!("文件已存在: {}", existing));
+    }
+
+    let total_rows = partitions.iter().map(|partition| partition.df.height()).sum();
+    let paths: Vec<String> = targets.iter().map(|(path, _)| path.clone()).collect();
+    run_export_task(
+        app,
+        &state,
+        task_id,
+        "export_partitioned",
+        total_rows,
+        move |task, on_progress| {
+            let profile = CsvExportProfile::default();
+            let mut written = 0;
+            for (partition, path) in partitions.iter().zip(&paths) {
+                // 单个文件内的进度不推送，每写完一个文件推送累计行数
+                let no_progress = |_: &TaskHandle| {};
+                let result = match extension.as_str() {
+                    "csv" => {
+                        let df = nested_columns_to_json(&partition.df).map_err(|e| format!("转换嵌套列失败: {}", e))?;
+                        write_atomic_checked(path, false, |file| {
+                            write_csv_chunks(file, &df, &profile, task, no_progress)
+                        })
+                    }
+                    "parquet" => write_atomic_checked(path, false, |file| {
+                        write_parquet_chunks(
+                            file,
+                            &partition.df,
+                            ParquetCompression::default(),
+                            None,
+                            task,
+                            no_progress,
+                        )
+                    }),
+                    _ => write_atomic_checked(path, false, |file| {
+                        write_ipc_chunks(file, &partition.df, task, no_progress)
+                    }),
+                };
+                result.map_err(|e| format!("写出 {} 失败: {}", path, e))?;
+
+                written += partition.df.height();
+                task.set_processed(written);
+                on_progress(task);
+            }
+            Ok(())
+        },
+    )
+    .await?;
+
+    for (path, shape) in &targets {
+        record_export(&state, source.clone(), "export_partitioned", path, *shape)?;
+    }
+    Ok(targets.into_iter().map(|(path, _)| path).collect())
+}
+
+// ============================================================================
+// 导出当前数据集到 SQLite 数据库
+// ============================================================================
+/// 将当前数据集写入 SQLite 数据库中的表
+///
+/// 数据库文件不存在时自动创建，库中的其他表保持不变。
+/// 与 export_csv 相同，作为后台任务运行（进度事件、可取消）：
+/// 所有写入在一个事务中完成，失败或取消时回滚，数据库保持原样
+///
+/// 参数：
+/// - output_path: 数据库文件的完整路径（.sqlite / .db）
+/// - table_name: 表名
+/// - if_exists: 表已存在时的处理方式（可选，"fail" / "replace" / "append"，默认 "fail"）
+/// - subset: 导出范围（可选），只导出指定的列和满足 SQL 条件的行，不修改数据集和历史
+/// - overwrite: 数据库文件已存在时是否写入（可选，默认 true；为 false 且文件已存在时返回错误，不写出任何内容；
+///   写入已有数据库时只按 if_exists 处理同名表，不删除其他表）
+/// - task_id: 任务 ID（可选），用于查询进度和取消
+/// - app: 应用句柄（自动注入，用于推送进度事件）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<String, String>: 成功返回文件路径，失败返回错误
+///
+/// 前端调用示例：
+/// ```typescript
+/// await invoke('export_sqlite', {
+///   outputPath: '/path/to/analytics.sqlite',
+///   tableName: 'orders',
+///   ifExists: 'replace'
+/// });
+/// ```
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn export_sqlite(
+    output_path: String,
+    table_name: String,
+    if_exists: Option<SqliteIfExists>,
+    subset: Option<ExportSubset>,
+    overwrite: Option<bool>,
+    task_id: Option<String>,
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let overwrite = overwrite.unwrap_or(true);
+    check_overwrite(&output_path, overwrite).map_err(|e| e.to_string())?;
+    // 获取当前 DataFrame（不在导出期间持有锁）
+    let (current_df, source) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        (
+            store.get_current().ok_or("没有数据")?.clone(),
+            ExportSource::current(&store),
+        )
+    };
+    let current_df = select_subset(current_df, &subset)?;
+
+    let shape = current_df.shape();
+    let total_rows = current_df.height();
+    let path_clone = output_path.clone();
+    let if_exists = if_exists.unwrap_or_default();
+    run_export_task(
+        app,
+        &state,
+        task_id,
+        "export_sqlite",
+        total_rows,
+        move |task, on_progress| {
+            // 不覆盖时先创建空文件占用路径（SQLite 把空文件当作新数据库），
+            // 避免写入在检查之后被其他程序创建的数据库；写入失败时删除这个空文件
+            if !overwrite {
+                create_new_output(&path_clone).map_err(|e| e.to_string())?;
+            }
+            let result = tauri::async_runtime::block_on(write_sqlite_table(
+                &path_clone,
+                &table_name,
+                &current_df,
+                if_exists,
+                task,
+                on_progress,
+            ));
+            if result.is_err() && !overwrite {
+                let _ = std::fs::remove_file(&path_clone);
+            }
+            result.map_err(|e| e.to_string())
+        },
+    )
+    .await?;
+
+    record_export(&state, source, "export_sqlite", &output_path, shape)?;
+    Ok(output_path)
+}
+
+// ============================================================================
+// 导出当前数据集为 Excel 文件
+// ============================================================================
+/// 未指定工作表名称时使用的名称
+const DEFAULT_EXCEL_SHEET_NAME: &str = "数据";
+
+/// 导出当前数据集为 Excel 文件（.xlsx，单个工作表）
+///
+/// - 单元格保留类型：数值为数字，布尔值为布尔，日期 / 日期时间为 Excel 日期，空值留空
+/// - 表头行加粗、添加自动筛选并冻结
+/// - 超过 Excel 限制（1,048,576 行 × 16,384 列）时返回错误，请改用 CSV / Parquet
+///
+/// 需要描述统计、值计数、透视表等汇总工作表时使用 export_excel_report
+///
+/// 参数：
+/// - output_path: 输出文件的完整路径（.xlsx）
+/// - sheet_name: 工作表名称（可选，默认 "数据"；不允许的字符会被替换，超过 31 个字符会被截断）
+/// - subset: 导出范围（可选），只导出指定的列和满足 SQL 条件的行，不修改数据集和历史
+/// - overwrite: 目标文件已存在时是否覆盖（可选，默认 true；为 false 且文件已存在时返回错误，不写出任何内容）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<String, String>: 成功返回文件路径，失败返回错误
+///
+/// 前端调用示例：
+/// ```typescript
+/// await invoke('export_excel', { outputPath: '/path/to/sales.xlsx', sheetName: '销售明细' });
+/// ```
+#[tauri::command]
+pub async fn export_excel(
+    output_path: String,
+    sheet_name: Option<String>,
+    subset: Option<ExportSubset>,
+    overwrite: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let overwrite = overwrite.unwrap_or(true);
+    check_overwrite(&output_path, overwrite).map_err(|e| e.to_string())?;
+    let (current_df, source) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        (
+            store.get_current().ok_or("没有数据")?.clone(),
+            ExportSource::current(&store),
+        )
+    };
+    let current_df = select_subset(current_df, &subset)?;
+
+    let sheet_name = sheet_name.unwrap_or_else(|| DEFAULT_EXCEL_SHEET_NAME.to_string());
+    let shape = current_df.shape();
+    let output_path = tauri::async_runtime::spawn_blocking(move || {
+        write_table(&output_path, &sheet_name, &current_df, overwrite)
+            .map_err(|e| format!("Failed to write Excel: {}", e))?;
+        Ok::<_, String>(output_path)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    record_export(&state, source, "export_excel", &output_path, shape)?;
+    Ok(output_path)
+}
+
+// ============================================================================
+// 复制到剪贴板
+// ============================================================================
+/// 将当前数据集（或前 max_rows 行）渲染为文本，由前端写入剪贴板
+///
+/// 适合把较小的结果粘贴到邮件或 Excel；不写文件，导出记录中的路径为 "clipboard"
+///
+/// 参数：
+/// - max_rows: 最多包含的行数（可选，默认全部行）
+/// - format: 文本格式（可选，"tsv" / "csv" / "markdown"，默认 "tsv"，粘贴到 Excel 时自动分列）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<String, String>: 成功返回文本（包含表头），失败返回错误
+///
+/// 前端调用示例：
+/// ```typescript
+/// const text = await invoke<string>('export_clipboard', { maxRows: 100, format: 'markdown' });
+/// await navigator.clipboard.writeText(text);
+/// ```
+#[tauri::command]
+pub async fn export_clipboard(
+    max_rows: Option<usize>,
+    format: Option<ClipboardFormat>,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let (current_df, source) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        let df = store.get_current().ok_or("没有数据")?;
+        let df = match max_rows {
+            Some(rows) => df.head(Some(rows)),
+            None => df.clone(),
+        };
+        (df, ExportSource::current(&store))
+    };
+
+    let format = format.unwrap_or_default();
+    let shape = current_df.shape();
+    let text = tauri::async_runtime::spawn_blocking(move || {
+        render_clipboard_text(&current_df, format).map_err(|e| format!("生成剪贴板文本失败: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    record_export(&state, source, "export_clipboard", "clipboard", shape)?;
+    Ok(text)
+}
+
+// ============================================================================
+// 导出 Excel 报告
+// ============================================================================
+/// 导出包含数据和汇总工作表的 Excel 报告
+///
+/// 工作表：
+/// 1. 数据：当前数据集
+/// 2. 描述统计：每列的统计信息（与 describe_dataset 相同）
+/// 3. 值计数：每个指定分类列一个工作表
+/// 4. 透视表：按 PivotSpec 生成（与 pivot_data 相同）
+///
+/// 参数：
+/// - output_path: 输出文件的完整路径（.xlsx）
+/// - options: 报告选项（默认只包含数据和描述统计）
+/// - overwrite: 目标文件已存在时是否覆盖（可选，默认 true；为 false 且文件已存在时返回错误，不写出任何内容）
+/// - app: 应用句柄（自动注入，用于读取行顺序设置）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<String, String>: 成功返回文件路径，失败返回错误
+#[tauri::command]
+pub async fn export_excel_report(
+    output_path: String,
+    options: Option<ExcelReportOptions>,
+    overwrite: Option<bool>,
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let overwrite = overwrite.unwrap_or(true);
+    check_overwrite(&output_path, overwrite).map_err(|e| e.to_string())?;
+    let options = options.unwrap_or_default();
+    let stable = stable_ordering(&app)?;
+
+    let (current_df, source) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        (
+            store.get_current().ok_or("没有数据")?.clone(),
+            ExportSource::current(&store),
+        )
+    };
+
+    let shape = current_df.shape();
+    let output_path = tauri::async_runtime::spawn_blocking(move || {
+        let mut sheets = Vec::new();
+
+        if options.include_summary {
+            let stats = current_df
+                .get_column_names()
+                .iter()
+                .map(|name| compute_column_stats(&current_df, name))
+                .collect::<Result<Vec<_>, String>>()?;
+            sheets.push(("描述统计".to_string(), stats_to_dataframe(&stats)?));
+        }
+
+        for column in &options.value_count_columns {
+            let counts = value_counts(&current_df, column, options.value_count_limit)?;
+            sheets.push((format!("计数_{}", column), counts));
+        }
+
+        if let Some(pivot) = &options.pivot {
+            let aggregate = pivot.aggregate.as_deref().unwrap_or("first");
+            let pivoted = compute_pivot(
+                &current_df,
+                &pivot.index,
+                &pivot.columns,
+                &pivot.values,
+                aggregate,
+                stable,
+            )?;
+            sheets.push(("透视表".to_string(), pivoted));
+        }
+
+        sheets.insert(0, ("数据".to_string(), current_df));
+
+        write_workbook(&output_path, &sheets, overwrite).map_err(|e| e.to_string())?;
+        Ok::<_, String>(output_path)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    record_export(&state, source, "export_excel_report", &output_path, shape)?;
+    Ok(output_path)
+}
+
+/// 将列统计信息转换为 DataFrame（每列一行）
+fn stats_to_dataframe(stats: &[ColumnStats]) -> Result<DataFrame, String> {
+    let text = |f: fn(&ColumnStats) -> String| stats.iter().map(f).collect::<Vec<_>>();
+    let count = |f: fn(&ColumnStats) -> usize| stats.iter().map(|s| f(s) as u64).collect::<Vec<_>>();
+    let number = |f: fn(&ColumnStats) -> Option<f64>| stats.iter().map(f).collect::<Vec<_>>();
+
+    DataFrame::new(vec![
+        Column::new("列名".into(), text(|s| s.name.clone())),
+        Column::new("类型".into(), text(|s| s.dtype.clone())),
+        Column::new("总数".into(), count(|s| s.total_count)),
+        Column::new("缺失".into(), count(|s| s.null_count)),
+        Column::new("唯一值".into(), count(|s| s.unique_count)),
+        Column::new("最小值".into(), number(|s| s.min)),
+        Column::new("最大值".into(), number(|s| s.max)),
+        Column::new("平均值".into(), number(|s| s.mean)),
+        Column::new("标准差".into(), number(|s| s.std)),
+        Column::new("25%".into(), number(|s| s.q25)),
+        Column::new("50%".into(), number(|s| s.q50)),
+        Column::new("75%".into(), number(|s| s.q75)),
+    ])
+    .map_err(|e| format!("生成描述统计失败: {}", e))
+}
+
+/// 计算单列的值计数（按次数降序）
+fn value_counts(df: &DataFrame, column: &str, limit: Option<usize>) -> Result<DataFrame, String> {
+    if df.get_column_index(column).is_none() {
+        return Err(format!("列 '{}' 不存在", column));
+    }
+
+    let mut counts = df
+        .clone()
+        .lazy()
+        .group_by([col(column)])
+        .agg([len().alias("次数")])
+        .sort(["次数"], SortMultipleOptions::default().with_order_descending(true));
+
+    if let Some(limit) = limit {
+        counts = counts.limit(limit as IdxSize);
+    }
+
+    counts.collect().map_err(|e| format!("计算值计数失败: {}", e))
+}
+
+// ============================================================================
+// 导出数据质量报告
+// ============================================================================
+/// 计算每一列的统计信息，写出数据质量报告（JSON 或单文件 HTML），方便分享数据质量快照
+///
+/// 报告内容：每列的缺失数、唯一值数、最小 / 最大值、分位数、均值、标准差（与 describe_dataset 相同），
+/// 以及最常见的值和次数
+///
+/// 参数：
+/// - output_path: 输出文件的完整路径
+/// - format: 报告格式（可选，默认按扩展名推断：.html / .htm 为 HTML，其余为 JSON）
+/// - top_n: 每列保留的最常见值数量（可选，默认 10）
+/// - overwrite: 目标文件已存在时是否覆盖（可选，默认 true；为 false 且文件已存在时返回错误，不写出任何内容）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<String, String>: 成功返回文件路径，失败返回错误
+#[tauri::command]
+pub async fn export_profile_report(
+    output_path: String,
+    format: Option<ProfileReportFormat>,
+    top_n: Option<usize>,
+    overwrite: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let overwrite = overwrite.unwrap_or(true);
+    check_overwrite(&output_path, overwrite).map_err(|e| e.to_string())?;
+    let format = format.unwrap_or_else(|| infer_profile_format(&output_path));
+    let top_n = top_n.unwrap_or(DEFAULT_TOP_VALUES);
 
-    let df = store.get_current().ok_or("没有数据")?;
+    let (current_df, dataset_name, source) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        (
+            store.get_current().ok_or("没有数据")?.clone(),
+            store
+                .get_current_entry()
+                .map(|entry| entry.metadata.name.clone())
+                .unwrap_or_default(),
+            ExportSource::current(&store),
+        )
+    };
 
-    // 创建输出文件
-    let mut file = std::fs::File::create(&output_path).map_err(|e| format!("Failed to create file: {}", e))?;
+    let shape = current_df.shape();
+    let output_path = tauri::async_runtime::spawn_blocking(move || {
+        let stats = current_df
+            .get_column_names()
+            .iter()
+            .map(|name| compute_column_stats(&current_df, name))
+            .collect::<Result<Vec<_>, String>>()?;
 
-    // 写入 Parquet 数据
-    ParquetWriter::new(&mut file)
-        .finish(&mut df.clone())
-        .map_err(|e| format!("Failed to write Parquet: {}", e))?;
+        let report = build_profile_report(&dataset_name, &current_df, stats, top_n)
+            .map_err(|e| format!("生成数据质量报告失败: {}", e))?;
+        write_profile_report(&report, &output_path, format, overwrite)
+            .map_err(|e| format!("写入数据质量报告失败: {}", e))?;
+        Ok::<_, String>(output_path)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
 
+    record_export(&state, source, "export_profile_report", &output_path, shape)?;
     Ok(output_path)
 }
 
@@ -89,7 +1203,7 @@ pub async fn clear_data(state: tauri::State<'_, AppState>) -> Result<(), String>
         .lock()
         .map_err(|e| format!("Failed to lock data store: {}", e))?;
 
-    store.clear();
+    store.clear().map_err(|e| e.to_string())?;
     Ok(())
 }
 
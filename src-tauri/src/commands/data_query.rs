@@ -4,13 +4,26 @@
 // 这个文件定义了数据查询相关的 Tauri 命令
 // 新架构：只有一个当前数据集，不再有多数据集列表
 
-use crate::data::dataframe_to_json_rows;
-use crate::models::{ColumnStats, DatasetData, DatasetInfo};
+use crate::commands::settings::column_summaries_enabled;
+use crate::data::column_summary::{fill_column_summaries, has_column_summaries};
+use crate::data::decimal::is_numeric_dtype;
+use crate::data::gaps::{detect_sequence_gaps, write_gap_report};
+use crate::data::nested::{compute_list_stats, nested_unique_count};
+use crate::data::{
+    build_sql_predicate, cluster_similar_values, compute_cell_styles, compute_fingerprint, dataframe_to_json_rows,
+};
+use crate::models::{
+    ColumnFormat, ColumnStats, DatasetData, DatasetFingerprint, DatasetInfo, FloatPrecision, GapReport, KpiMetric,
+    KpiSummary, KpiSummarySpec, KpiValue, ReportFormat, SimilarCluster, ViewState,
+};
 // ============================================================================
 // 导入依赖
 // ============================================================================
 use crate::AppState;
 use polars::prelude::*;
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::AppHandle;
 
 // ============================================================================
 // 获取当前数据集的元信息
@@ -19,7 +32,11 @@ use polars::prelude::*;
 ///
 /// 返回当前正在查看的数据集的元信息
 ///
+/// 开启列摘要设置时，每一列附带最小值、最大值和不同值数量：
+/// 每个历史状态第一次请求时计算，结果缓存在该历史条目的元信息中
+///
 /// 参数：
+/// - app: 应用句柄（自动注入，用于读取列摘要设置）
 /// - state: 应用状态（自动注入）
 ///
 /// 返回：
@@ -27,13 +44,41 @@ use polars::prelude::*;
 ///   - Some(info): 有数据时返回元信息
 ///   - None: 没有数据
 #[tauri::command]
-pub async fn get_current_info(state: tauri::State<'_, AppState>) -> Result<Option<DatasetInfo>, String> {
-    let store = state
+pub async fn get_current_info(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<DatasetInfo>, String> {
+    let summaries = column_summaries_enabled(&app)?;
+
+    let (entry_id, mut info, df) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        let Some(entry) = store.get_current_entry() else {
+            return Ok(None);
+        };
+        if !summaries || has_column_summaries(&entry.metadata, &entry.dataframe) {
+            return Ok(Some(entry.metadata.clone()));
+        }
+        (entry.id.clone(), entry.metadata.clone(), entry.dataframe.clone())
+    };
+
+    // 在锁外计算列摘要，然后缓存到对应的历史条目
+    let info = tauri::async_runtime::spawn_blocking(move || {
+        fill_column_summaries(&mut info, &df).map_err(|e| format!("计算列摘要失败: {}", e))?;
+        Ok::<DatasetInfo, String>(info)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
         .data_store
         .lock()
         .map_err(|e| format!("Failed to lock data store: {}", e))?;
+    store.set_entry_metadata(&entry_id, info.clone());
 
-    Ok(store.get_current_info().cloned())
+    Ok(Some(info))
 }
 
 // ============================================================================
@@ -46,16 +91,18 @@ pub async fn get_current_info(state: tauri::State<'_, AppState>) -> Result<Optio
 /// 参数：
 /// - offset: 起始行索引（从 0 开始）
 /// - limit: 要获取的行数
+/// - include_styles: 是否计算条件格式的单元格样式标签（默认 false）
 /// - state: 应用状态（自动注入）
 ///
 /// 返回：
 /// - Result<DatasetData, String>:
-///   - 成功：返回 DatasetData（包含列名、数据行、总行数）
+///   - 成功：返回 DatasetData（包含列名、数据行、总行数，以及可选的单元格样式）
 ///   - 失败：返回错误消息
 #[tauri::command]
 pub async fn get_current_data(
     offset: usize,
     limit: usize,
+    include_styles: Option<bool>,
     state: tauri::State<'_, AppState>,
 ) -> Result<DatasetData, String> {
     let store = state
@@ -64,7 +111,8 @@ pub async fn get_current_data(
         .map_err(|e| format!("Failed to lock data store: {}", e))?;
 
     // 获取当前 DataFrame
-    let df = store.get_current().ok_or("没有数据")?;
+    let entry = store.get_current_entry().ok_or("没有数据")?;
+    let df = &entry.dataframe;
 
     // 获取总行数
     let total_rows = df.height();
@@ -79,16 +127,584 @@ pub async fn get_current_data(
     // 提取列名
     let columns: Vec<String> = sliced_df.get_column_names().iter().map(|s| s.to_string()).collect();
 
-    // 转换为 JSON 行
-    let rows = dataframe_to_json_rows(&sliced_df);
+    // 转换为 JSON 行（应用列显示格式）
+    let display = store.get_current_display().cloned().unwrap_or_default();
+    let rows = dataframe_to_json_rows(&sliced_df, &display.column_formats, &display.float_precision)
+        .map_err(|e| e.to_string())?;
+
+    // 计算条件格式（离群值等统计量基于完整数据，按历史条目缓存）
+    let cell_styles = if include_styles.unwrap_or(false) {
+        Some(
+            store
+                .outlier_moments()
+                .with_entry(&entry.id, |moments| {
+                    compute_cell_styles(df, &sliced_df, &display.conditional_formats, moments)
+                })
+                .map_err(|e| format!("计算条件格式失败: {}", e))?,
+        )
+    } else {
+        None
+    };
 
     Ok(DatasetData {
         columns,
         rows,
         total_rows,
+        cell_styles,
     })
 }
 
+// ============================================================================
+// 获取列显示格式
+// ============================================================================
+/// 获取当前状态下所有列的显示格式
+///
+/// 返回：
+/// - Result<HashMap<String, ColumnFormat>, String>: 列名 -> 显示格式（没有数据时为空）
+#[tauri::command]
+pub async fn get_column_formats(state: tauri::State<'_, AppState>) -> Result<HashMap<String, ColumnFormat>, String> {
+    let store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    Ok(store
+        .get_current_display()
+        .map(|display| display.column_formats.clone())
+        .unwrap_or_default())
+}
+
+// ============================================================================
+// 获取表格布局
+// ============================================================================
+/// 获取当前状态下的表格布局（隐藏列、固定列、列宽）
+///
+/// 返回：
+/// - Result<ViewState, String>: 表格布局（没有数据时为默认布局）
+#[tauri::command]
+pub async fn get_view_state(state: tauri::State<'_, AppState>) -> Result<ViewState, String> {
+    let store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    Ok(store
+        .get_current_display()
+        .map(|display| display.view_state.clone())
+        .unwrap_or_default())
+}
+
+// ============================================================================
+// 聚合为单个值
+// ============================================================================
+/// 将当前数据集聚合为单个值（用于表格上方的 KPI 卡片）
+///
+/// 两种用法（二选一）：
+/// 1. column + function：对单列应用内置聚合函数
+///    - sum, mean, median, min, max, std, var, first, last
+///    - count（非空数量）, null_count（空值数量）, n_unique（去重计数）
+///    - weighted_mean（加权平均，需要 weight_column）
+/// 2. expression：任意 SQL 聚合表达式，例如 "SUM(amount) / COUNT(*)"
+///
+/// 不修改数据，也不产生历史记录
+///
+/// 返回：
+/// - Result<serde_json::Value, String>: 聚合结果（数字、字符串或 null）
+#[tauri::command]
+pub async fn aggregate_scalar(
+    column: Option<String>,
+    function: Option<String>,
+    expression: Option<String>,
+    weight_column: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<serde_json::Value, String> {
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        compute_scalar(
+            current_df,
+            column.as_deref(),
+            function.as_deref(),
+            expression.as_deref(),
+            weight_column.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 计算单个聚合值（aggregate_scalar 和 get_kpi_summary 共用）
+fn compute_scalar(
+    df: DataFrame,
+    column: Option<&str>,
+    function: Option<&str>,
+    expression: Option<&str>,
+    weight_column: Option<&str>,
+) -> Result<serde_json::Value, String> {
+    let result_df = match (expression, column, function) {
+        (Some(expression), _, _) => {
+            let sql_query = format!("SELECT {} AS value FROM self", expression);
+            let mut ctx = polars::sql::SQLContext::new();
+            ctx.register("self", df.lazy());
+            ctx.execute(&sql_query)
+                .and_then(|lf| lf.collect())
+                .map_err(|e| format!("SQL 聚合执行失败: {}", e))?
+        }
+        (None, Some(column), Some(function)) => {
+            let agg_expr = build_scalar_agg(&df, column, function, weight_column)?;
+            df.lazy()
+                .select([agg_expr.alias("value")])
+                .collect()
+                .map_err(|e| format!("聚合计算失败: {}", e))?
+        }
+        _ => return Err("需要指定 expression，或者同时指定 column 和 function".to_string()),
+    };
+
+    if result_df.width() != 1 || result_df.height() != 1 {
+        return Err(format!(
+            "聚合结果必须是单个值，实际为 {} 行 {} 列",
+            result_df.height(),
+            result_df.width()
+        ));
+    }
+
+    let rows =
+        dataframe_to_json_rows(&result_df, &HashMap::new(), &FloatPrecision::default()).map_err(|e| e.to_string())?;
+    Ok(rows[0][0].clone())
+}
+
+// ============================================================================
+// KPI 汇总
+// ============================================================================
+/// 一次计算仪表盘顶部的一组 KPI（替代前端每次刷新多次调用 aggregate_scalar）
+///
+/// 每个指标的写法与 aggregate_scalar 相同（column + function 或 expression）。
+/// 指定 comparison 时，按日期列把数据切成当前周期和上一周期分别计算，
+/// 并给出变化量和变化率；参照日期默认为日期列中的最晚日期
+///
+/// 不修改数据，也不产生历史记录
+///
+/// 参数：
+/// - spec: 指标定义
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<KpiSummary, String>: 各指标的值（顺序与请求一致）
+///
+/// 前端调用示例：
+/// ```typescript
+/// const summary = await invoke('get_kpi_summary', {
+///   spec: {
+///     metrics: [{ name: '销售额', column: 'amount', function: 'sum' }],
+///     comparison: { date_column: 'order_date', period: 'mom' }
+///   }
+/// });
+/// ```
+#[tauri::command]
+pub async fn get_kpi_summary(spec: KpiSummarySpec, state: tauri::State<'_, AppState>) -> Result<KpiSummary, String> {
+    if spec.metrics.is_empty() {
+        return Err("至少需要一个指标".to_string());
+    }
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(comparison) = spec.comparison else {
+            let metrics = spec
+                .metrics
+                .iter()
+                .map(|metric| {
+                    Ok(KpiValue {
+                        name: metric.name.clone(),
+                        value: compute_metric(current_df.clone(), metric)?,
+                        previous: None,
+                        delta: None,
+                        change_ratio: None,
+                    })
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            return Ok(KpiSummary {
+                metrics,
+                current_period: None,
+                previous_period: None,
+            });
+        };
+
+        // 日期列转换为天数（1970-01-01 起），日期时间按日期部分计算
+        let days = current_df
+            .column(&comparison.date_column)
+            .map_err(|_| format!("列 '{}' 不存在", comparison.date_column))?
+            .as_materialized_series()
+            .cast(&DataType::Date)
+            .and_then(|s| s.cast(&DataType::Int32))
+            .map_err(|e| format!("列 '{}' 无法转换为日期: {}", comparison.date_column, e))?;
+        let days = days.i32().map_err(|e| e.to_string())?;
+
+        let epoch = chrono::NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid date");
+        let reference = match &comparison.reference_date {
+            Some(text) => chrono::NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d")
+                .map_err(|_| format!("无效的参照日期 '{}'，格式应为 YYYY-MM-DD", text))?,
+            None => {
+                let max_day = days.max().ok_or("日期列没有有效值")?;
+                epoch + chrono::Duration::days(max_day as i64)
+            }
+        };
+        let current_start = comparison.period.shift_back(reference).ok_or("参照日期超出范围")?;
+        let previous_start = comparison.period.shift_back(current_start).ok_or("参照日期超出范围")?;
+
+        // 周期为左开右闭区间 (start, end]
+        let to_day = |date: chrono::NaiveDate| (date - epoch).num_days() as i32;
+        let window = |start: chrono::NaiveDate, end: chrono::NaiveDate| -> Result<DataFrame, String> {
+            let (start, end) = (to_day(start), to_day(end));
+            let mask: BooleanChunked = days
+                .into_iter()
+                .map(|day| day.map(|day| day > start && day <= end))
+                .collect();
+            current_df.filter(&mask).map_err(|e| e.to_string())
+        };
+        let current_window = window(current_start, reference)?;
+        let previous_window = window(previous_start, current_start)?;
+
+        let metrics = spec
+            .metrics
+            .iter()
+            .map(|metric| {
+                let value = compute_metric(current_window.clone(), metric)?;
+                let previous = compute_metric(previous_window.clone(), metric)?;
+                let (delta, change_ratio) = match (value.as_f64(), previous.as_f64()) {
+                    (Some(value), Some(previous)) => {
+                        let delta = value - previous;
+                        let ratio = if previous != 0.0 {
+                            Some(delta / previous.abs())
+                        } else {
+                            None
+                        };
+                        (Some(delta), ratio)
+                    }
+                    _ => (None, None),
+                };
+                Ok(KpiValue {
+                    name: metric.name.clone(),
+                    value,
+                    previous: Some(previous),
+                    delta,
+                    change_ratio,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let format_date = |date: chrono::NaiveDate| date.format("%Y-%m-%d").to_string();
+        Ok(KpiSummary {
+            metrics,
+            current_period: Some((format_date(current_start), format_date(reference))),
+            previous_period: Some((format_date(previous_start), format_date(current_start))),
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 计算单个 KPI 指标（错误信息带上指标名称）
+fn compute_metric(df: DataFrame, metric: &KpiMetric) -> Result<serde_json::Value, String> {
+    compute_scalar(
+        df,
+        metric.column.as_deref(),
+        metric.function.as_deref(),
+        metric.expression.as_deref(),
+        metric.weight_column.as_deref(),
+    )
+    .map_err(|e| format!("指标 '{}': {}", metric.name, e))
+}
+
+/// 构建单列聚合表达式（aggregate_scalar、get_kpi_summary 和 export_aggregated 共用）
+pub(crate) fn build_scalar_agg(
+    df: &DataFrame,
+    column: &str,
+    function: &str,
+    weight_column: Option<&str>,
+) -> Result<Expr, String> {
+    if df.get_column_index(column).is_none() {
+        return Err(format!("列 '{}' 不存在", column));
+    }
+
+    let c = col(column);
+    let expr = match function {
+        "sum" => c.sum(),
+        "mean" => c.mean(),
+        "median" => c.median(),
+        "min" => c.min(),
+        "max" => c.max(),
+        "std" => c.std(1),
+        "var" => c.var(1),
+        "first" => c.first(),
+        "last" => c.last(),
+        "count" => c.count(),
+        "null_count" => c.null_count(),
+        "n_unique" => c.n_unique(),
+        "weighted_mean" => {
+            let weight = weight_column.ok_or("加权平均需要指定 weight_column")?;
+            if df.get_column_index(weight).is_none() {
+                return Err(format!("列 '{}' 不存在", weight));
+            }
+            let value = c.cast(DataType::Float64);
+            let weight_expr = col(weight).cast(DataType::Float64);
+            // 只统计值和权重都不为空的行
+            let valid = value.clone().is_not_null().and(weight_expr.clone().is_not_null());
+            (value * weight_expr.clone()).filter(valid.clone()).sum() / weight_expr.filter(valid).sum()
+        }
+        _ => return Err(format!("不支持的聚合函数: {}", function)),
+    };
+
+    Ok(expr)
+}
+
+// ============================================================================
+// 汇总行
+// ============================================================================
+/// 计算表格底部固定汇总行的值
+///
+/// 基于完整数据计算（而不是当前已加载的分页），结果按列显示格式格式化
+/// 计数类聚合（count, null_count, n_unique）不应用列格式
+///
+/// 参数：
+/// - columns: 要汇总的列
+/// - aggregations: 聚合函数（与 aggregate_scalar 相同，不含 weighted_mean）
+///   - 只有一个时应用到所有列
+///   - 否则必须与 columns 一一对应
+///
+/// 返回：
+/// - Result<HashMap<String, serde_json::Value>, String>: 列名 -> 汇总值
+#[tauri::command]
+pub async fn get_column_totals(
+    columns: Vec<String>,
+    aggregations: Vec<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<HashMap<String, serde_json::Value>, String> {
+    if columns.is_empty() {
+        return Ok(HashMap::new());
+    }
+    if aggregations.len() != 1 && aggregations.len() != columns.len() {
+        return Err(format!(
+            "聚合函数数量（{}）必须为 1 或与列数量（{}）相同",
+            aggregations.len(),
+            columns.len()
+        ));
+    }
+
+    let (current_df, column_formats, precision) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        let df = store.get_current().ok_or("没有数据")?.clone();
+        let display = store.get_current_display().cloned().unwrap_or_default();
+        (df, display.column_formats, display.float_precision)
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut exprs = Vec::with_capacity(columns.len());
+        let mut formats = HashMap::new();
+
+        for (idx, column) in columns.iter().enumerate() {
+            let function = aggregations.get(idx).unwrap_or(&aggregations[0]);
+            if function == "weighted_mean" {
+                return Err("汇总行不支持加权平均".to_string());
+            }
+
+            exprs.push(build_scalar_agg(&current_df, column, function, None)?.alias(column.as_str()));
+
+            if !matches!(function.as_str(), "count" | "null_count" | "n_unique")
+                && let Some(format) = column_formats.get(column)
+            {
+                formats.insert(column.clone(), format.clone());
+            }
+        }
+
+        let result_df = current_df
+            .lazy()
+            .select(exprs)
+            .collect()
+            .map_err(|e| format!("汇总计算失败: {}", e))?;
+
+        let values = dataframe_to_json_rows(&result_df, &formats, &precision)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+
+        Ok(columns.into_iter().zip(values).collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// ============================================================================
+// 数据集指纹
+// ============================================================================
+/// 计算当前数据集的内容指纹
+///
+/// 返回每列的哈希和整体摘要，用于判断数据在两次会话之间是否真正发生了变化
+/// （例如撤销后回到相同数据，指纹也相同）
+///
+/// 返回：
+/// - Result<DatasetFingerprint, String>: 整体摘要、行数和每列哈希
+#[tauri::command]
+pub async fn get_dataset_fingerprint(state: tauri::State<'_, AppState>) -> Result<DatasetFingerprint, String> {
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        compute_fingerprint(&current_df).map_err(|e| format!("计算数据指纹失败: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// ============================================================================
+// 近似重复值报告
+// ============================================================================
+/// 参与相似度比较的最大唯一值数量（两两比较，复杂度为平方级）
+const MAX_SIMILARITY_DISTINCT: usize = 5000;
+
+/// 查找列中近似重复的字符串值
+///
+/// 忽略大小写、标点和多余空白后，使用归一化 Levenshtein 相似度聚类
+/// 例如 "Acme Inc"、"ACME, Inc."、"Acme Inc" 会被归为一组
+///
+/// 参数：
+/// - column: 列名（非字符串列会先转换为字符串）
+/// - threshold: 相似度阈值（0.0 ~ 1.0，默认 0.85）
+///
+/// 返回：
+/// - Result<Vec<SimilarCluster>, String>: 包含两个及以上不同值的簇，按总出现次数降序
+#[tauri::command]
+pub async fn find_similar_values(
+    column: String,
+    threshold: Option<f64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<SimilarCluster>, String> {
+    let threshold = threshold.unwrap_or(0.85);
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err("相似度阈值必须在 0.0 到 1.0 之间".to_string());
+    }
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        if current_df.get_column_index(&column).is_none() {
+            return Err(format!("列 '{}' 不存在", column));
+        }
+
+        // 统计每个唯一值的出现次数
+        let counts_df = current_df
+            .lazy()
+            .select([col(column.as_str()).cast(DataType::String).alias("value")])
+            .filter(col("value").is_not_null())
+            .group_by([col("value")])
+            .agg([len().alias("count")])
+            .collect()
+            .map_err(|e| format!("统计唯一值失败: {}", e))?;
+
+        if counts_df.height() > MAX_SIMILARITY_DISTINCT {
+            return Err(format!(
+                "唯一值过多（{} 个），最多支持 {} 个",
+                counts_df.height(),
+                MAX_SIMILARITY_DISTINCT
+            ));
+        }
+
+        let values = counts_df
+            .column("value")
+            .and_then(|c| c.str().cloned())
+            .map_err(|e| e.to_string())?;
+        let counts = counts_df
+            .column("count")
+            .and_then(|c| c.cast(&DataType::UInt64))
+            .map_err(|e| e.to_string())?;
+        let counts = counts.u64().map_err(|e| e.to_string())?;
+
+        let value_counts: Vec<(String, usize)> = values
+            .into_iter()
+            .zip(counts)
+            .filter_map(|(value, count)| Some((value?.to_string(), count? as usize)))
+            .collect();
+
+        Ok(cluster_similar_values(value_counts, threshold))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// ============================================================================
+// 序列缺口检测
+// ============================================================================
+/// 检测整数或日期序列中缺失的值（例如缺失的发票号、缺失的日期）
+///
+/// 参数：
+/// - column: 序列列（整数、日期或日期时间）
+/// - group_by: 分组列（可选），每个分组分别检查
+/// - step: 期望的步长（整数列默认 1；日期 / 日期时间列为时间间隔文本，默认 "1d"）
+/// - report_path: 缺口报告的输出路径（可选）
+/// - report_format: 报告格式（默认 CSV，即缺口明细表）
+///
+/// 返回：
+/// - Result<GapReport, String>: 缺口汇总和明细（明细最多 10000 条）
+#[tauri::command]
+pub async fn detect_gaps(
+    column: String,
+    group_by: Option<Vec<String>>,
+    step: Option<String>,
+    report_path: Option<String>,
+    report_format: Option<ReportFormat>,
+    state: tauri::State<'_, AppState>,
+) -> Result<GapReport, String> {
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let group_by = group_by.unwrap_or_default();
+        let report = detect_sequence_gaps(&current_df, &column, &group_by, step.as_deref())
+            .map_err(|e| format!("检测序列缺口失败: {}", e))?;
+
+        if let Some(path) = report_path {
+            write_gap_report(&report, Path::new(&path), report_format.unwrap_or(ReportFormat::Csv))
+                .map_err(|e| format!("写入缺口报告失败: {}", e))?;
+        }
+
+        Ok(report)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 // ============================================================================
 // 获取列统计信息
 // ============================================================================
@@ -96,12 +712,17 @@ pub async fn get_current_data(
 ///
 /// 参数：
 /// - column_name: 列名
+/// - filter: 可选的 SQL WHERE 条件，只统计满足条件的行（不修改数据，不产生历史记录）
 /// - state: 应用状态
 ///
 /// 返回：
 /// - Result<ColumnStats, String>: 列统计信息
 #[tauri::command]
-pub async fn get_column_stats(column_name: String, state: tauri::State<'_, AppState>) -> Result<ColumnStats, String> {
+pub async fn get_column_stats(
+    column_name: String,
+    filter: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<ColumnStats, String> {
     let store = state
         .data_store
         .lock()
@@ -109,17 +730,79 @@ pub async fn get_column_stats(column_name: String, state: tauri::State<'_, AppSt
 
     let df = store.get_current().ok_or("没有数据")?;
 
+    match filter {
+        Some(expression) => compute_column_stats(&filter_subset(df, &expression)?, &column_name),
+        None => compute_column_stats(df, &column_name),
+    }
+}
+
+// ============================================================================
+// 获取所有列的统计信息
+// ============================================================================
+/// 获取当前数据集所有列的统计信息
+///
+/// 参数：
+/// - filter: 可选的 SQL WHERE 条件，只统计满足条件的行（不修改数据，不产生历史记录）
+///
+/// 返回：
+/// - Result<Vec<ColumnStats>, String>: 按列顺序排列的统计信息
+#[tauri::command]
+pub async fn describe_dataset(
+    filter: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ColumnStats>, String> {
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let df = match filter {
+            Some(expression) => filter_subset(&current_df, &expression)?,
+            None => current_df,
+        };
+
+        df.get_column_names()
+            .iter()
+            .map(|name| compute_column_stats(&df, name.as_str()))
+            .collect()
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 按 SQL WHERE 条件惰性筛选出子集（用于统计，不影响当前数据）
+fn filter_subset(df: &DataFrame, expression: &str) -> Result<DataFrame, String> {
+    let predicate = build_sql_predicate(expression).map_err(|e| e.to_string())?;
+
+    df.clone()
+        .lazy()
+        .filter(predicate)
+        .collect()
+        .map_err(|e| format!("筛选失败: {}", e))
+}
+
+/// 计算单列的统计信息
+pub(crate) fn compute_column_stats(df: &DataFrame, column_name: &str) -> Result<ColumnStats, String> {
     let series = df
-        .column(&column_name)
+        .column(column_name)
         .map_err(|e| format!("找不到列 {}: {}", column_name, e))?;
 
     // 基础统计
     let total_count = series.len();
     let null_count = series.null_count();
-    let unique_count = series.n_unique().map_err(|e| format!("计算唯一值数量失败: {}", e))?;
+    let unique_count = if series.dtype().is_nested() {
+        // 列表、结构体列按 JSON 文本比较
+        nested_unique_count(series.as_materialized_series())
+    } else {
+        series.n_unique().map_err(|e| format!("计算唯一值数量失败: {}", e))?
+    };
 
     // 数值统计
-    let (max, min, mean, std, q25, q50, q75) = if series.dtype().is_numeric() {
+    let (max, min, mean, std, q25, q50, q75) = if is_numeric_dtype(series.dtype()) {
         // 转换为 Float64 进行统计
         match series.cast(&DataType::Float64) {
             Ok(float_series) => match float_series.f64() {
@@ -155,6 +838,12 @@ pub async fn get_column_stats(column_name: String, state: tauri::State<'_, AppSt
         (None, None)
     };
 
+    // 列表统计
+    let list_stats = match series.list() {
+        Ok(ca) => Some(compute_list_stats(ca).map_err(|e| format!("计算列表统计失败: {}", e))?),
+        Err(_) => None,
+    };
+
     // 日期时间统计
     let (min_datetime, max_datetime, datetime_range_days) = match series.dtype() {
         DataType::Date => {
@@ -224,7 +913,7 @@ pub async fn get_column_stats(column_name: String, state: tauri::State<'_, AppSt
     };
 
     Ok(ColumnStats {
-        name: column_name,
+        name: column_name.to_string(),
         dtype: format!("{:?}", series.dtype()),
         total_count,
         null_count,
@@ -241,5 +930,6 @@ pub async fn get_column_stats(column_name: String, state: tauri::State<'_, AppSt
         datetime_range_days,
         true_count,
         false_count,
+        list_stats,
     })
 }
@@ -0,0 +1,120 @@
+// ============================================================================
+// commands/database.rs - 数据库导入命令
+// ============================================================================
+// 这个文件实现了从 PostgreSQL / MySQL 导入数据的 Tauri 命令：
+// - connect_database: 测试连接，返回服务器版本
+// - list_tables: 列出表和视图
+// - import_query: 导入整张表或 SQL 查询结果，替换当前数据集
+//
+// 连接参数每次调用时传入，命令结束后关闭连接，不会保存连接信息或密码
+
+use crate::AppState;
+use crate::commands::file_import::{create_import_entry, replace_with_import_entry};
+use crate::data::db::DbPool;
+use crate::models::{DatabaseConnection, DatabaseTable, DatasetInfo};
+
+// ============================================================================
+// 1. 测试连接
+// ============================================================================
+/// 测试数据库连接
+///
+/// 参数：
+/// - connection: 连接参数
+///
+/// 返回：
+/// - Result<String, String>: 成功返回服务器版本，失败返回错误
+///
+/// 前端调用示例：
+/// ```typescript
+/// const version = await invoke('connect_database', {
+///   connection: { kind: 'postgres', host: 'localhost', database: 'analytics', username: 'reader', password: '...' }
+/// });
+/// ```
+#[tauri::command]
+pub async fn connect_database(connection: DatabaseConnection) -> Result<String, String> {
+    let pool = DbPool::connect(&connection).await.map_err(|e| e.to_string())?;
+    let version = pool.server_version().await;
+    pool.close().await;
+    version.map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// 2. 列出表
+// ============================================================================
+/// 列出数据库中的表和视图
+///
+/// PostgreSQL 列出所有用户模式中的表，MySQL 列出当前数据库中的表
+///
+/// 参数：
+/// - connection: 连接参数
+///
+/// 返回：
+/// - Result<Vec<DatabaseTable>, String>: 表列表（按模式、表名排序）
+#[tauri::command]
+pub async fn list_tables(connection: DatabaseConnection) -> Result<Vec<DatabaseTable>, String> {
+    let pool = DbPool::connect(&connection).await.map_err(|e| e.to_string())?;
+    let tables = pool.list_tables().await;
+    pool.close().await;
+    tables.map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// 3. 导入表或查询结果
+// ============================================================================
+/// 导入整张表或 SQL 查询结果，替换当前数据集
+///
+/// table 和 query 二选一：
+/// - table（可选 schema）：读取整张表
+/// - query：执行任意 SELECT 查询
+///
+/// 参数：
+/// - connection: 连接参数
+/// - table: 表名（可选）
+/// - schema: 模式名（可选，只在指定 table 时使用）
+/// - query: SQL 查询（可选）
+/// - limit: 最多导入的行数（可选，默认不限制）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<DatasetInfo, String>: 成功返回元信息，失败返回错误
+///
+/// 前端调用示例：
+/// ```typescript
+/// const info = await invoke('import_query', {
+///   connection,
+///   query: 'SELECT region, SUM(amount) AS total FROM orders GROUP BY region'
+/// });
+/// ```
+#[tauri::command]
+pub async fn import_query(
+    connection: DatabaseConnection,
+    table: Option<String>,
+    schema: Option<String>,
+    query: Option<String>,
+    limit: Option<usize>,
+    state: tauri::State<'_, AppState>,
+) -> Result<DatasetInfo, String> {
+    let _session = state.imports.begin("数据库").map_err(|e| e.to_string())?;
+    let pool = DbPool::connect(&connection).await.map_err(|e| e.to_string())?;
+
+    let (sql, name) = match (&table, &query) {
+        (Some(table), None) => (pool.table_query(schema.as_deref(), table), table.clone()),
+        (None, Some(query)) => (query.clone(), "query".to_string()),
+        _ => {
+            pool.close().await;
+            return Err("需要指定 table 或 query（二选一）".to_string());
+        }
+    };
+
+    let df = pool.query_to_dataframe(&sql, limit).await;
+    pool.close().await;
+    let df = df.map_err(|e| e.to_string())?;
+
+    // 数据来源记录为 "postgres://user@host:port/database/table"（不包含密码）
+    let source = format!("{}/{}", connection.source(), name);
+    let mut entry = create_import_entry(source, None, df);
+    entry.metadata.name = name;
+
+    // 清空历史并添加新条目
+    replace_with_import_entry(&state, entry)
+}
@@ -2,20 +2,47 @@
 // commands/file_import.rs - 文件导入命令
 // ============================================================================
 // 这个文件定义了文件导入相关的 Tauri 命令
-// 前端可以调用这些命令来导入 CSV 和 Excel 文件
+// 前端可以调用这些命令来导入 CSV、Excel、Parquet、JSON、Arrow IPC、Avro 和 ORC 文件，
+// 或者从 HTTP(S) 地址下载后导入，也可以生成演示数据
 
 // 应用状态
-use crate::data::{create_dataset_info, load_csv, load_excel, load_parquet};
+use crate::commands::operations::create_history_entry;
+use crate::commands::tasks::emit_task_progress;
+use crate::data::demo::{DEFAULT_DEMO_ROWS, DEMO_SOURCE_PREFIX, demo_source, generate_demo_dataframe};
+use crate::data::download::{
+    DEFAULT_DOWNLOAD_TIMEOUT_SECS, DEFAULT_MAX_DOWNLOAD_BYTES, RemoteFormat, download_to_temp,
+};
+use crate::data::parquet_meta::read_parquet_metadata;
+use crate::data::recipe::{REPLAYABLE_OPERATIONS, replay_recipe_step, unsupported_recipe_steps};
+use crate::data::snapshot::read_snapshot_bundle;
+use crate::data::{
+    CsvProgress, create_dataset_info, list_excel_sheets, load_arrow, load_avro, load_csv, load_csv_with_progress,
+    load_excel, load_excel_all_sheets, load_json, load_orc, load_parquet, load_parquet_dir, load_sample,
+};
+use crate::error::DataAnalystError;
 // 数据加载函数
-use crate::models::{DatasetInfo, HistoryEntry, OperationType};
+use crate::models::{
+    CsvImportOptions, DatasetInfo, DemoPreset, DisplaySettings, ExcelSheetInfo, HistoryEntry, HistoryEntryInfo,
+    ImportOptions, ImportProgress, OperationType, ParquetMetadata, SampleStrategy,
+};
 // ============================================================================
 // 导入依赖
 // ============================================================================
 use crate::AppState;
 // 数据集元信息类型
+use polars::prelude::DataFrame;
+use std::path::Path;
+use std::time::Duration;
+use tauri::Emitter;
 use uuid::Uuid;
 // UUID 生成器
 
+/// 导入进度事件名称（载荷为 ImportProgress）
+pub const IMPORT_PROGRESS_EVENT: &str = "import://progress";
+
+/// 导入任务的类型前缀（cancel_import 只取消这类任务）
+const IMPORT_TASK_PREFIX: &str = "import_";
+
 // ============================================================================
 // CSV 文件导入命令
 // ============================================================================
@@ -25,14 +52,28 @@ use uuid::Uuid;
 ///
 /// 工作流程：
 /// 1. 接收文件路径
-/// 2. 使用 Polars 读取 CSV 文件
+/// 2. 使用 Polars 读取 CSV 文件（.csv.gz、.csv.zst、单文件 .zip 会先自动解压）
 /// 3. 生成唯一的数据集 ID（UUID）
 /// 4. 提取数据集元信息
 /// 5. 将 DataFrame 和元信息存储到 DataStore
 /// 6. 返回元信息给前端
 ///
+/// 读取过程中通过 "import://progress" 事件推送进度（ImportProgress）：
+/// 开始解析前推送一次；超过 32 MB 的文件分块读取，每块推送一次；小文件读取完成后推送一次
+///
+/// 导入同时注册为后台任务（类型 "import_csv"），可以用 cancel_import 取消：
+/// 取消在开始解析前或当前块解析完成后生效，已读取的数据会被丢弃，当前数据集保持不变
+///
+/// 同一时间只允许一个导入（包括其他格式的导入和 reload_from_source），
+/// 另一个导入进行中时返回 "Import in progress: ..." 错误；
+/// 新数据完整读取后才一次性替换当前数据集，读取失败时当前数据集保持不变
+///
 /// 参数：
 /// - file_path: CSV 文件的完整路径（由前端传入）
+/// - options: 可选的导入选项（分隔符、表头、引号、编码、跳过行数、空值文本、列类型覆盖），
+///   自动检测不准确时用于覆盖
+/// - job_id: 导入任务 ID（可选，前端传入以便取消；None 时自动生成）
+/// - app: 应用句柄（自动注入，用于推送进度事件）
 /// - state: 应用状态（Tauri 自动注入，包含 DataStore）
 ///
 /// 返回：
@@ -46,71 +87,120 @@ use uuid::Uuid;
 ///   filePath: '/path/to/data.csv'
 /// });
 /// console.log(`导入成功，数据集 ID: ${info.id}`);
+///
+/// // GBK 编码、分号分隔，"NA" 视为空值
+/// const info = await invoke('import_csv', {
+///   filePath: '/path/to/export.csv',
+///   options: { separator: ';', encoding: 'gbk', null_values: ['NA'] }
+/// });
+///
+/// // 邮编保留前导零（不推断为整数）
+/// const info = await invoke('import_csv', {
+///   filePath: '/path/to/customers.csv',
+///   options: { dtype_overrides: { zip_code: 'String' } }
+/// });
+///
+/// // 显示进度条，点击"取消"时中止导入
+/// const unlisten = await listen('import://progress', (event) => {
+///   setProgress(event.payload.bytes_read / event.payload.total_bytes);
+/// });
+/// const importing = invoke('import_csv', { filePath, jobId: 'import-1' });
+/// await invoke('cancel_import', { jobId: 'import-1' });
 /// ```
 #[tauri::command] // 这个宏将函数注册为 Tauri 命令
 pub async fn import_csv(
     file_path: String,                 // 文件路径（前端传入）
+    options: Option<CsvImportOptions>, // 导入选项（可选）
+    job_id: Option<String>,            // 导入任务 ID（可选）
+    app: tauri::AppHandle,             // 应用句柄（自动注入）
     state: tauri::State<'_, AppState>, // 应用状态（自动注入）
 ) -> Result<DatasetInfo, String> {
+    let _session = state.imports.begin(&file_path).map_err(|e| e.to_string())?;
     // 返回类型
     // ------------------------------------------------------------------------
     // 步骤 1: 读取 CSV 文件
     // ------------------------------------------------------------------------
-    // load_csv: 使用 Polars 读取 CSV 文件，返回 DataFrame
+    // load_csv_with_progress: 使用 Polars 读取 CSV 文件，返回 DataFrame，并推送读取进度
+    // spawn_blocking: 在后台线程读取，避免大文件阻塞异步运行时
     // map_err: 将错误转换为字符串（Tauri 要求错误类型是 String）
     // format!: 格式化错误消息
     // ?: 如果失败，立即返回错误
-    let df = load_csv(&file_path).map_err(|e| format!("Failed to load CSV: {}", e))?;
-
-    // ------------------------------------------------------------------------
-    // 步骤 2: 生成唯一 ID 和时间戳
-    // ------------------------------------------------------------------------
-    let id = Uuid::new_v4().to_string();
-    let timestamp = chrono::Utc::now().to_rfc3339();
-
-    // ------------------------------------------------------------------------
-    // 步骤 3: 创建数据集元信息
-    // ------------------------------------------------------------------------
-    let info = create_dataset_info(&id, &file_path, &df);
-
-    // ------------------------------------------------------------------------
-    // 步骤 4: 创建操作类型和描述
-    // ------------------------------------------------------------------------
-    let operation = OperationType::Import {
-        file_path: file_path.clone(),
+    let options = options.unwrap_or_default();
+    let import_options = ImportOptions::Csv {
+        options: options.clone(),
     };
-    let description = operation.description();
+    let task = state
+        .tasks
+        .start(job_id, "import_csv", None)
+        .map_err(|e| e.to_string())?;
+    emit_task_progress(&app, &task);
 
-    // ------------------------------------------------------------------------
-    // 步骤 5: 创建历史条目
-    // ------------------------------------------------------------------------
-    let entry = HistoryEntry {
-        id: id.clone(),
-        operation,
-        dataframe: df,
-        metadata: info.clone(),
-        timestamp,
-        description,
+    let result = {
+        let file_path = file_path.clone();
+        let task = task.clone();
+        let app = app.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let on_progress = |progress: CsvProgress| {
+                task.set_processed(progress.rows_parsed);
+                let _ = app.emit(
+                    IMPORT_PROGRESS_EVENT,
+                    ImportProgress {
+                        job_id: task.id().to_string(),
+                        file_path: file_path.clone(),
+                        bytes_read: progress.bytes_read,
+                        total_bytes: progress.total_bytes,
+                        rows_parsed: progress.rows_parsed,
+                        done: progress.bytes_read >= progress.total_bytes,
+                    },
+                );
+                // 已请求取消时返回 Cancelled 错误，中止读取
+                task.check_cancelled()
+            };
+            load_csv_with_progress(&file_path, &options, &on_progress)
+        })
+        .await
+        .unwrap_or_else(|e| Err(DataAnalystError::InvalidOperation(e.to_string())))
     };
+    task.finish(&result);
+    emit_task_progress(&app, &task);
+    let df = result.map_err(|e| format!("Failed to load CSV: {}", e))?;
 
     // ------------------------------------------------------------------------
-    // 步骤 6: 清空历史并添加新条目
+    // 步骤 2: 创建导入记录（生成 ID、时间戳和数据集元信息）
     // ------------------------------------------------------------------------
-    let mut store = state
-        .data_store
-        .lock()
-        .map_err(|e| format!("Failed to lock data store: {}", e))?;
-
-    // 清空旧历史（导入新文件时重置）
-    store.clear();
-
-    // 添加导入操作到历史栈
-    store.push_operation(entry);
+    let entry = create_import_entry(file_path, Some(import_options), df);
 
     // ------------------------------------------------------------------------
-    // 步骤 7: 返回元信息
+    // 步骤 3: 清空历史并添加新条目，返回元信息
     // ------------------------------------------------------------------------
-    Ok(info)
+    replace_with_import_entry(&state, entry)
+}
+
+// ============================================================================
+// 取消导入
+// ============================================================================
+/// 请求取消正在进行的导入
+///
+/// 取消是协作式的：CSV 导入在解析完当前块后停止，其他格式在文件读取完成后丢弃结果，
+/// 导入命令返回"操作已取消"错误，当前数据集和历史记录保持不变；导入已结束时不做任何事
+///
+/// 只能取消导入任务（类型以 "import_" 开头），导出等其他后台任务请用 cancel_task
+///
+/// 参数：
+/// - job_id: 导入任务 ID（调用导入命令时传入的 job_id，或进度事件中的 job_id）
+/// - state: 应用状态（自动注入）
+#[tauri::command]
+pub async fn cancel_import(job_id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    match state.tasks.get(&job_id) {
+        Some(task) if task.kind.starts_with(IMPORT_TASK_PREFIX) => {
+            state.tasks.cancel(&job_id).map_err(|e| e.to_string())
+        }
+        Some(task) => Err(format!(
+            "任务 '{}' 不是导入任务（类型 {}），请使用 cancel_task",
+            job_id, task.kind
+        )),
+        None => Err(format!("导入任务 '{}' 不存在", job_id)),
+    }
 }
 
 // ============================================================================
@@ -133,6 +223,9 @@ pub async fn import_csv(
 /// - sheet_name: 可选的工作表名称
 ///   - Some("Sheet1"): 读取指定的工作表
 ///   - None: 读取第一个工作表
+/// - header_row: 可选的表头行（从 0 开始，相对于读取范围），表头之前的标题行会被忽略
+/// - skip_rows: 可选，表头之后、数据之前要跳过的行数
+/// - cell_range: 可选的 A1 格式读取范围（例如 "B3:H200"），用于去掉标题和页脚
 /// - state: 应用状态（自动注入）
 ///
 /// 返回：
@@ -150,49 +243,127 @@ pub async fn import_csv(
 ///   filePath: '/path/to/data.xlsx',
 ///   sheetName: '销售数据'
 /// });
+///
+/// // 报表：只读取 B3:H200，跳过表头下面的单位行
+/// const info = await invoke('import_excel', {
+///   filePath: '/path/to/report.xlsx',
+///   cellRange: 'B3:H200',
+///   skipRows: 1
+/// });
 /// ```
 #[tauri::command]
 pub async fn import_excel(
     file_path: String,                 // 文件路径
     sheet_name: Option<String>,        // 可选的工作表名称
+    header_row: Option<usize>,         // 可选的表头行
+    skip_rows: Option<usize>,          // 可选的表头后跳过行数
+    cell_range: Option<String>,        // 可选的单元格范围
     state: tauri::State<'_, AppState>, // 应用状态
 ) -> Result<DatasetInfo, String> {
+    let _session = state.imports.begin(&file_path).map_err(|e| e.to_string())?;
     // ------------------------------------------------------------------------
     // 步骤 1: 读取 Excel 文件
     // ------------------------------------------------------------------------
     // load_excel: 使用 Calamine 读取 Excel 文件
     // sheet_name: 传递工作表名称（可能是 None）
-    let df = load_excel(&file_path, sheet_name).map_err(|e| format!("Failed to load Excel: {}", e))?;
+    let df = load_excel(
+        &file_path,
+        sheet_name.clone(),
+        header_row,
+        skip_rows,
+        cell_range.as_deref(),
+    )
+    .map_err(|e| format!("Failed to load Excel: {}", e))?;
 
     // ------------------------------------------------------------------------
     // 步骤 2-7: 与 CSV 导入相同
     // ------------------------------------------------------------------------
-    let id = Uuid::new_v4().to_string();
-    let timestamp = chrono::Utc::now().to_rfc3339();
-    let info = create_dataset_info(&id, &file_path, &df);
+    let entry = create_import_entry(
+        file_path,
+        Some(ImportOptions::Excel {
+            sheet_name,
+            header_row,
+            skip_rows,
+            cell_range,
+        }),
+        df,
+    );
+    replace_with_import_entry(&state, entry)
+}
 
-    let operation = OperationType::Import {
-        file_path: file_path.clone(),
-    };
-    let description = operation.description();
+/// 合并所有工作表时，默认记录来源工作表的列名
+const DEFAULT_SHEET_COLUMN: &str = "sheet";
 
-    let entry = HistoryEntry {
-        id: id.clone(),
-        operation,
-        dataframe: df,
-        metadata: info.clone(),
-        timestamp,
-        description,
+/// 导入工作簿中的所有工作表，纵向合并为一个数据集
+///
+/// 适用于每月 / 每个地区一个工作表的报表。合并后的第一列记录每行来自哪个工作表，
+/// 各工作表的列按列名对齐，某个工作表没有的列填空值。每个工作表的第一行作为表头
+///
+/// 参数：
+/// - file_path: Excel 文件的完整路径
+/// - sheet_column: 记录来源工作表的列名（可选，默认 "sheet"）
+/// - include_hidden: 是否包含隐藏的工作表（可选，默认 false）
+/// - state: 应用状态（自动注入）
+///
+/// 前端调用示例：
+/// ```typescript
+/// const info = await invoke('import_excel_all_sheets', { filePath: '/path/to/monthly.xlsx' });
+/// ```
+#[tauri::command]
+pub async fn import_excel_all_sheets(
+    file_path: String,
+    sheet_column: Option<String>,
+    include_hidden: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<DatasetInfo, String> {
+    let _session = state.imports.begin(&file_path).map_err(|e| e.to_string())?;
+    let sheet_column = sheet_column.unwrap_or_else(|| DEFAULT_SHEET_COLUMN.to_string());
+    if sheet_column.trim().is_empty() {
+        return Err("工作表列名不能为空".to_string());
+    }
+
+    let include_hidden = include_hidden.unwrap_or(false);
+    let import_options = ImportOptions::ExcelAllSheets {
+        sheet_column: sheet_column.clone(),
+        include_hidden,
     };
 
-    let mut store = state
-        .data_store
-        .lock()
-        .map_err(|e| format!("Failed to lock data store: {}", e))?;
-    store.clear();
-    store.push_operation(entry);
+    let path_clone = file_path.clone();
+    let df =
+        tauri::async_runtime::spawn_blocking(move || load_excel_all_sheets(&path_clone, &sheet_column, include_hidden))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| format!("Failed to load Excel: {}", e))?;
 
-    Ok(info)
+    let entry = create_import_entry(file_path, Some(import_options), df);
+    replace_with_import_entry(&state, entry)
+}
+
+// ============================================================================
+// Excel 工作表列表
+// ============================================================================
+/// 列出 Excel 工作簿中的工作表，用于在导入前让用户选择工作表
+///
+/// 参数：
+/// - file_path: Excel 文件的完整路径
+///
+/// 返回：
+/// - Result<Vec<ExcelSheetInfo>, String>: 工作表名称、行数（不含表头）、列数、是否隐藏
+///
+/// 前端调用示例：
+/// ```typescript
+/// const sheets = await invoke('get_excel_sheets', { filePath: '/path/to/data.xlsx' });
+/// // 用户选择后
+/// await invoke('import_excel', { filePath: '/path/to/data.xlsx', sheetName: sheets[1].name });
+/// ```
+#[tauri::command]
+pub async fn get_excel_sheets(file_path: String) -> Result<Vec<ExcelSheetInfo>, String> {
+    // 需要读取每个工作表的范围，大文件可能较慢，在后台线程中执行
+    tauri::async_runtime::spawn_blocking(move || {
+        list_excel_sheets(&file_path).map_err(|e| format!("读取 Excel 工作表失败 ({}): {}", file_path, e))
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
 // ============================================================================
@@ -207,8 +378,13 @@ pub async fn import_excel(
 /// - 文件体积小（压缩率高）
 /// - 保留完整的数据类型信息（不需要类型推断）
 ///
+/// 大文件可以只导入部分列和前 N 行：未选择的列和不需要的行组不会被解码，
+/// 可以先用 get_parquet_metadata 查看列名和行数
+///
 /// 参数：
 /// - file_path: Parquet 文件的完整路径
+/// - columns: 只导入这些列（可选，默认所有列）
+/// - n_rows: 最多导入的行数（可选，默认全部）
 /// - state: 应用状态（自动注入）
 ///
 /// 返回：
@@ -219,45 +395,658 @@ pub async fn import_excel(
 /// const info = await invoke('import_parquet', {
 ///   filePath: '/path/to/data.parquet'
 /// });
+///
+/// // 只导入两列的前 10 万行
+/// const partial = await invoke('import_parquet', {
+///   filePath: '/path/to/huge.parquet',
+///   columns: ['date', 'amount'],
+///   nRows: 100000
+/// });
+/// ```
+#[tauri::command]
+pub async fn import_parquet(
+    file_path: String,
+    columns: Option<Vec<String>>,
+    n_rows: Option<usize>,
+    state: tauri::State<'_, AppState>,
+) -> Result<DatasetInfo, String> {
+    let _session = state.imports.begin(&file_path).map_err(|e| e.to_string())?;
+    // 记录选择的列和行数，从源文件刷新时读取同样的部分
+    let import_options = ImportOptions::Parquet {
+        columns: columns.clone(),
+        n_rows,
+    };
+    // 读取 Parquet 文件（在后台线程中解码，避免阻塞）
+    let path_clone = file_path.clone();
+    let df = tauri::async_runtime::spawn_blocking(move || load_parquet(&path_clone, columns.as_deref(), n_rows))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("Failed to load Parquet: {}", e))?;
+
+    let entry = create_import_entry(file_path, Some(import_options), df);
+    replace_with_import_entry(&state, entry)
+}
+
+/// 导入 Hive 分区的 Parquet 目录
+///
+/// 读取目录下（含子目录）所有 .parquet 文件并合并为一个数据集，
+/// 路径中的分区（例如 `year=2024/month=01`）作为列追加在末尾
+///
+/// 参数：
+/// - dir_path: 分区目录的根路径
+/// - job_id: 导入任务 ID（可选，用于 cancel_import；None 时自动生成）
+/// - app: 应用句柄（自动注入，用于推送任务进度）
+/// - state: 应用状态（自动注入）
+///
+/// 前端调用示例：
+/// ```typescript
+/// const info = await invoke('import_parquet_dir', { dirPath: '/path/to/data' });
+/// ```
+#[tauri::command]
+pub async fn import_parquet_dir(
+    dir_path: String,
+    job_id: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<DatasetInfo, String> {
+    import_with_loader(
+        dir_path,
+        job_id,
+        app,
+        state,
+        "import_parquet_dir",
+        "Parquet directory",
+        load_parquet_dir,
+    )
+    .await
+}
+
+// ============================================================================
+// JSON / NDJSON 文件导入命令
+// ============================================================================
+/// 导入 JSON 文件
+///
+/// 支持标准 JSON（对象数组）和 NDJSON / JSONL（每行一个对象），
+/// 常见于 API 导出的数据，不需要先转换为 CSV
+///
+/// 参数：
+/// - file_path: JSON 文件的完整路径（.json / .jsonl / .ndjson）
+/// - job_id: 导入任务 ID（可选，用于 cancel_import；None 时自动生成）
+/// - app: 应用句柄（自动注入，用于推送任务进度）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<DatasetInfo, String>: 成功返回元信息，失败返回错误
+///
+/// 前端调用示例：
+/// ```typescript
+/// const info = await invoke('import_json', {
+///   filePath: '/path/to/events.jsonl'
+/// });
+/// ```
+#[tauri::command]
+pub async fn import_json(
+    file_path: String,
+    job_id: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<DatasetInfo, String> {
+    import_with_loader(file_path, job_id, app, state, "import_json", "JSON", load_json).await
+}
+
+// ============================================================================
+// Arrow IPC 文件导入命令
+// ============================================================================
+/// 导入 Arrow IPC 文件
+///
+/// 支持 .arrow / .feather（Feather v2）文件，常用于与 pandas / polars 笔记本交换数据，
+/// 列类型完整保留
+///
+/// 参数：
+/// - file_path: Arrow IPC 文件的完整路径（.arrow / .feather）
+/// - job_id: 导入任务 ID（可选，用于 cancel_import；None 时自动生成）
+/// - app: 应用句柄（自动注入，用于推送任务进度）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<DatasetInfo, String>: 成功返回元信息，失败返回错误
+///
+/// 前端调用示例：
+/// ```typescript
+/// const info = await invoke('import_arrow', {
+///   filePath: '/path/to/data.feather'
+/// });
+/// ```
+#[tauri::command]
+pub async fn import_arrow(
+    file_path: String,
+    job_id: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<DatasetInfo, String> {
+    import_with_loader(file_path, job_id, app, state, "import_arrow", "Arrow IPC", load_arrow).await
+}
+
+/// 导入 Avro 容器文件
+///
+/// 适用于 Kafka / Hadoop 导出的 .avro 文件，文件自带 schema，列类型完整保留
+///
+/// 参数：
+/// - file_path: Avro 文件的完整路径
+/// - job_id: 导入任务 ID（可选，用于 cancel_import；None 时自动生成）
+/// - app: 应用句柄（自动注入，用于推送任务进度）
+/// - state: 应用状态（自动注入）
+///
+/// 前端调用示例：
+/// ```typescript
+/// const info = await invoke('import_avro', { filePath: '/path/to/events.avro' });
+/// ```
+#[tauri::command]
+pub async fn import_avro(
+    file_path: String,
+    job_id: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<DatasetInfo, String> {
+    import_with_loader(file_path, job_id, app, state, "import_avro", "Avro", load_avro).await
+}
+
+/// 导入 ORC 文件
+///
+/// 适用于 Hive / Spark 数据湖导出的 .orc 文件
+///
+/// 参数：
+/// - file_path: ORC 文件的完整路径
+/// - job_id: 导入任务 ID（可选，用于 cancel_import；None 时自动生成）
+/// - app: 应用句柄（自动注入，用于推送任务进度）
+/// - state: 应用状态（自动注入）
+///
+/// 前端调用示例：
+/// ```typescript
+/// const info = await invoke('import_orc', { filePath: '/path/to/part-00000.orc' });
+/// ```
+#[tauri::command]
+pub async fn import_orc(
+    file_path: String,
+    job_id: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<DatasetInfo, String> {
+    import_with_loader(file_path, job_id, app, state, "import_orc", "ORC", load_orc).await
+}
+
+/// 在后台线程中用给定的加载函数读取文件，并作为新数据集替换当前历史
+///
+/// 读取注册为后台任务（类型为 kind），可以用 cancel_import 取消：
+/// 加载函数不能中途停止，读取完成后发现已请求取消时丢弃结果，当前数据集保持不变
+async fn import_with_loader(
+    file_path: String,
+    job_id: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    kind: &str,
+    label: &str,
+    loader: fn(&str) -> crate::error::Result<DataFrame>,
+) -> Result<DatasetInfo, String> {
+    let _session = state.imports.begin(&file_path).map_err(|e| e.to_string())?;
+    let task = state.tasks.start(job_id, kind, None).map_err(|e| e.to_string())?;
+    emit_task_progress(&app, &task);
+
+    let path_clone = file_path.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || loader(&path_clone))
+        .await
+        .unwrap_or_else(|e| Err(DataAnalystError::InvalidOperation(e.to_string())))
+        .and_then(|df| {
+            task.check_cancelled()?;
+            task.set_processed(df.height());
+            Ok(df)
+        });
+    task.finish(&result);
+    emit_task_progress(&app, &task);
+    let df = result.map_err(|e| format!("Failed to load {}: {}", label, e))?;
+
+    let entry = create_import_entry(file_path, None, df);
+    replace_with_import_entry(&state, entry)
+}
+
+// ============================================================================
+// 抽样导入命令
+// ============================================================================
+/// 只导入文件的一部分行（前 N 行或随机 N 行）
+///
+/// 适用于超大文件：先在抽样数据上查看结构、搭建处理流程，
+/// 确认后用 reload_from_source 读取完整文件（导入记录的是原文件路径）
+///
+/// 支持 CSV（含压缩文件）、Parquet、Arrow IPC、JSON、Avro、ORC 和 Excel，按扩展名判断格式
+///
+/// 参数：
+/// - file_path: 文件的完整路径
+/// - sample_rows: 抽样行数
+/// - strategy: 抽样方式（可选，"head" 或 "random"，默认 "head"）
+/// - options: CSV 导入选项（可选，只用于 CSV，从源文件刷新时沿用）
+/// - state: 应用状态（自动注入）
+///
+/// 前端调用示例：
+/// ```typescript
+/// const info = await invoke('import_sample', {
+///   filePath: '/path/to/huge.csv',
+///   sampleRows: 10000,
+///   strategy: 'random'
+/// });
+/// ```
+#[tauri::command]
+pub async fn import_sample(
+    file_path: String,
+    sample_rows: usize,
+    strategy: Option<SampleStrategy>,
+    options: Option<CsvImportOptions>,
+    state: tauri::State<'_, AppState>,
+) -> Result<DatasetInfo, String> {
+    let _session = state.imports.begin(&file_path).map_err(|e| e.to_string())?;
+    let options = options.unwrap_or_default();
+    // 只有按 CSV 读取时才记录 CSV 选项；其他格式刷新时按扩展名读取完整文件，不需要选项
+    let import_options = reads_as_csv(&file_path).then(|| ImportOptions::Csv {
+        options: options.clone(),
+    });
+    let path_clone = file_path.clone();
+    let df = tauri::async_runtime::spawn_blocking(move || {
+        load_sample(&path_clone, sample_rows, strategy.unwrap_or_default(), &options)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| format!("抽样导入失败: {}", e))?;
+
+    let mut entry = create_import_entry(file_path, import_options, df);
+    entry.description = format!("{} (抽样 {} 行)", entry.description, entry.dataframe.height());
+    replace_with_import_entry(&state, entry)
+}
+
+// ============================================================================
+// URL 导入命令
+// ============================================================================
+/// 从 HTTP(S) 地址下载并导入 CSV、JSON 或 Parquet 文件
+///
+/// 适用于开放数据平台和共享链接，不需要先手动下载
+/// 文件先下载到系统临时目录，读取后删除
+///
+/// 格式判断顺序：format 参数 → URL 扩展名 → 响应的 Content-Type
+/// CSV 使用自动检测（压缩的 .csv.gz / .csv.zst / .zip 会自动解压）
+///
+/// 参数：
+/// - url: http / https 地址
+/// - format: 文件格式（可选，"csv"、"json"、"parquet"）
+/// - timeout_secs: 超时时间（可选，默认 60 秒）
+/// - max_bytes: 最大下载大小（可选，默认 500 MB）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<DatasetInfo, String>: 成功返回元信息，失败返回错误
+///
+/// 前端调用示例：
+/// ```typescript
+/// const info = await invoke('import_url', {
+///   url: 'https://example.org/open-data/population.csv',
+///   timeoutSecs: 120
+/// });
 /// ```
 #[tauri::command]
-pub async fn import_parquet(file_path: String, state: tauri::State<'_, AppState>) -> Result<DatasetInfo, String> {
-    // 读取 Parquet 文件
-    let df = load_parquet(&file_path).map_err(|e| format!("Failed to load Parquet: {}", e))?;
+pub async fn import_url(
+    url: String,
+    format: Option<String>,
+    timeout_secs: Option<u64>,
+    max_bytes: Option<u64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<DatasetInfo, String> {
+    let _session = state.imports.begin(&url).map_err(|e| e.to_string())?;
+    let requested = format
+        .as_deref()
+        .map(RemoteFormat::parse)
+        .transpose()
+        .map_err(|e| e.to_string())?;
+
+    let timeout = Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_DOWNLOAD_TIMEOUT_SECS));
+    let (df, file_name) = fetch_remote_dataframe(&url, requested, timeout, max_bytes).await?;
+
+    // 数据集名称使用 URL 中的文件名
+    let mut entry = create_import_entry(url, None, df);
+    entry.metadata.name = file_name;
+    replace_with_import_entry(&state, entry)
+}
+
+/// 下载 URL 指向的文件并解析为 DataFrame（import_url 和 reload_from_source 共用）
+///
+/// 返回 (DataFrame, URL 中的文件名)
+async fn fetch_remote_dataframe(
+    url: &str,
+    requested: Option<RemoteFormat>,
+    timeout: Duration,
+    max_bytes: Option<u64>,
+) -> Result<(DataFrame, String), String> {
+    let downloaded = download_to_temp(url, timeout, max_bytes.unwrap_or(DEFAULT_MAX_DOWNLOAD_BYTES))
+        .await
+        .map_err(|e| e.to_string())?;
 
-    // 生成 UUID 和时间戳
+    let format = requested
+        .or_else(|| RemoteFormat::from_file_name(&downloaded.file_name))
+        .or_else(|| {
+            downloaded
+                .content_type
+                .as_deref()
+                .and_then(RemoteFormat::from_content_type)
+        })
+        .ok_or_else(|| format!("无法判断 '{}' 的文件格式，请指定 format（csv、json、parquet）", url))?;
+
+    // 在后台线程中解析（下载的临时文件随 downloaded 一起删除）
+    let file_name = downloaded.file_name.clone();
+    let df = tauri::async_runtime::spawn_blocking(move || {
+        let path = downloaded.path.to_string_lossy().to_string();
+        match format {
+            RemoteFormat::Csv => load_csv(&path, &CsvImportOptions::default()),
+            RemoteFormat::Json => load_json(&path),
+            RemoteFormat::Parquet => load_parquet(&path, None, None),
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| format!("Failed to load {}: {}", url, e))?;
+
+    Ok((df, file_name))
+}
+
+// ============================================================================
+// 从源文件刷新
+// ============================================================================
+/// 重新读取当前数据集的源文件（导入时的文件路径或 URL），替换初始数据
+///
+/// 适用于每天更新的源文件：一键刷新，不需要重新选择文件
+///
+/// 行为：
+/// - 源文件路径和导入选项取自历史中的第一条导入记录，按扩展名选择读取方式
+///   （CSV 沿用导入时的选项，可以通过 options 覆盖；Excel 沿用导入时的工作表、表头行和单元格范围；
+///   Parquet 沿用导入时选择的列和行数；快照包重新读取包中的数据）
+/// - 刷新后历史重置为一条新的导入记录；replay 为 true 时，在新数据上重新执行导入之后到当前状态的操作，
+///   每一步重新记入历史（支持的操作见 data::recipe::REPLAYABLE_OPERATIONS，有不支持的步骤时不刷新）
+/// - 当前的显示设置（列格式、条件格式、表格布局）保留在仍然存在的列上
+/// - 从数据库导入的数据需要重新连接，不能用这个命令刷新
+///
+/// 参数：
+/// - options: CSV 导入选项（可选，只对 CSV 源文件生效，覆盖导入时的选项）
+/// - replay: 是否重新执行导入之后的操作（可选，默认 false）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<DatasetInfo, String>: 成功返回新的元信息，失败返回错误（当前数据保持不变）
+///
+/// 前端调用示例：
+/// ```typescript
+/// const info = await invoke('reload_from_source', { replay: true });
+/// ```
+#[tauri::command]
+pub async fn reload_from_source(
+    options: Option<CsvImportOptions>,
+    replay: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<DatasetInfo, String> {
+    let (source, import_options, steps, display) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.ensure_unlocked().map_err(|e| e.to_string())?;
+        let (source, recorded) = match store.entries().first().map(|entry| &entry.operation) {
+            Some(OperationType::Import { file_path, options }) => (file_path.clone(), options.clone()),
+            Some(_) => return Err("当前数据集不是从文件导入的，无法刷新".to_string()),
+            None => return Err("没有数据".to_string()),
+        };
+        // 传入的 CSV 选项只替换 CSV 选项，不影响记录的 Excel 工作表设置
+        let import_options = match (options, recorded) {
+            (Some(options), None | Some(ImportOptions::Csv { .. })) => Some(ImportOptions::Csv { options }),
+            (_, recorded) => recorded,
+        };
+        // 导入记录 + 之后到当前状态的操作（可以重做的条目不包括在内）
+        let steps: Vec<HistoryEntryInfo> = if replay.unwrap_or(false) {
+            let current = store.get_current_index().unwrap_or(0);
+            store.entries()[..=current].iter().map(|entry| entry.into()).collect()
+        } else {
+            Vec::new()
+        };
+        let unsupported = unsupported_recipe_steps(&steps);
+        if !unsupported.is_empty() {
+            return Err(format!(
+                "以下步骤不支持重新执行: {}（只支持{}）",
+                unsupported.join("；"),
+                REPLAYABLE_OPERATIONS
+            ));
+        }
+        (
+            source,
+            import_options,
+            steps,
+            store.get_current_display().cloned().unwrap_or_default(),
+        )
+    };
+    let _session = state.imports.begin(&source).map_err(|e| e.to_string())?;
+
+    let lower = source.to_ascii_lowercase();
+    let (df, name) = if lower.starts_with("http://") || lower.starts_with("https://") {
+        let timeout = Duration::from_secs(DEFAULT_DOWNLOAD_TIMEOUT_SECS);
+        fetch_remote_dataframe(&source, None, timeout, None).await?
+    } else if lower.starts_with("postgres://") || lower.starts_with("mysql://") {
+        return Err("从数据库导入的数据需要重新连接导入（import_query），不能从源文件刷新".to_string());
+    } else if lower.starts_with(DEMO_SOURCE_PREFIX) {
+        return Err("演示数据没有源文件，请重新生成（generate_demo_data）".to_string());
+    } else {
+        if !Path::new(&source).is_file() {
+            return Err(format!("源文件不存在: {}", source));
+        }
+        let path = source.clone();
+        let options = import_options.clone();
+        let df = tauri::async_runtime::spawn_blocking(move || load_local_source(&path, options.as_ref()))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| format!("Failed to load {}: {}", source, e))?;
+        let name = Path::new(&source)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| source.clone());
+        (df, name)
+    };
+
+    // 在新数据上重新执行之后的操作（在修改历史之前完成，失败时当前数据保持不变）
+    let replayed = {
+        let df = df.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let mut df = df;
+            let mut entries = Vec::with_capacity(steps.len().saturating_sub(1));
+            for (index, step) in steps
+                .iter()
+                .enumerate()
+                .skip(1)
+                .filter(|(_, step)| !step.operation.is_display_only())
+            {
+                df = replay_recipe_step(df, index, step).map_err(|e| e.to_string())?;
+                entries.push(create_history_entry(df.clone(), step.operation.clone()).map_err(|e| e.to_string())?);
+            }
+            Ok::<_, String>(entries)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("重新执行操作失败: {}", e))?
+    };
+
+    let mut entry = create_import_entry(source, import_options, df);
+    entry.metadata.name = name;
+    entry.display = display.carry_over(&entry.dataframe);
+    let info = entry.metadata.clone();
+
+    // 清空历史并添加新条目
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+    store.replace_with_import(entry).map_err(|e| e.to_string())?;
+    for entry in replayed {
+        store.push_operation(entry).map_err(|e| e.to_string())?;
+    }
+
+    Ok(info)
+}
+
+/// 按扩展名读取本地源文件（无法识别的扩展名按 CSV 读取，压缩的 CSV 会自动解压；目录按分区 Parquet 读取）
+///
+/// options 为导入时记录的选项：CSV 选项只用于 CSV，工作表设置只用于 Excel，
+/// 列和行数只用于 Parquet；没有时使用默认设置。
+/// 快照包（.zip）与压缩的 CSV 扩展名相同，按记录的选项区分，只读取包中的数据
+pub(crate) fn load_local_source(path: &str, options: Option<&ImportOptions>) -> crate::error::Result<DataFrame> {
+    if Path::new(path).is_dir() {
+        return load_parquet_dir(path);
+    }
+    if let Some(ImportOptions::Snapshot) = options {
+        return read_snapshot_bundle(path).map(|(_, df, _)| df);
+    }
+
+    match source_extension(path).as_str() {
+        "xlsx" | "xlsm" => match options {
+            Some(ImportOptions::Excel {
+                sheet_name,
+                header_row,
+                skip_rows,
+                cell_range,
+            }) => load_excel(path, sheet_name.clone(), *header_row, *skip_rows, cell_range.as_deref()),
+            Some(ImportOptions::ExcelAllSheets {
+                sheet_column,
+                include_hidden,
+            }) => load_excel_all_sheets(path, sheet_column, *include_hidden),
+            _ => load_excel(path, None, None, None, None),
+        },
+        "parquet" => match options {
+            Some(ImportOptions::Parquet { columns, n_rows }) => load_parquet(path, columns.as_deref(), *n_rows),
+            _ => load_parquet(path, None, None),
+        },
+        "arrow" | "feather" | "ipc" => load_arrow(path),
+        "avro" => load_avro(path),
+        "orc" => load_orc(path),
+        "json" | "jsonl" | "ndjson" => load_json(path),
+        _ => match options {
+            Some(ImportOptions::Csv { options }) => load_csv(path, options),
+            _ => load_csv(path, &CsvImportOptions::default()),
+        },
+    }
+}
+
+/// 不按 CSV 读取的扩展名（load_local_source 和 load_sample 按扩展名选择对应的读取方式）
+const NON_CSV_EXTENSIONS: &[&str] = &[
+    "xlsx", "xlsm", "parquet", "arrow", "feather", "ipc", "avro", "orc", "json", "jsonl", "ndjson",
+];
+
+/// 文件扩展名（小写；没有扩展名时为空字符串）
+fn source_extension(path: &str) -> String {
+    Path::new(path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+/// 本地文件是否按 CSV 读取（无法识别的扩展名都按 CSV 读取）
+fn reads_as_csv(path: &str) -> bool {
+    !NON_CSV_EXTENSIONS.contains(&source_extension(path).as_str())
+}
+
+// ============================================================================
+// 导入记录
+// ============================================================================
+/// 从导入的数据创建导入记录（新数据集的第一条历史条目）
+///
+/// 数据集名称取自来源的文件名；需要其他名称或显示设置时，由调用方修改返回的条目
+pub(crate) fn create_import_entry(source: String, options: Option<ImportOptions>, df: DataFrame) -> HistoryEntry {
     let id = Uuid::new_v4().to_string();
     let timestamp = chrono::Utc::now().to_rfc3339();
+    let metadata = create_dataset_info(&id, &source, &df);
 
-    // 创建元信息
-    let info = create_dataset_info(&id, &file_path, &df);
-
-    // 创建操作和历史条目
     let operation = OperationType::Import {
-        file_path: file_path.clone(),
+        file_path: source,
+        options,
     };
     let description = operation.description();
 
-    let entry = HistoryEntry {
-        id: id.clone(),
+    HistoryEntry {
+        id,
         operation,
         dataframe: df,
-        metadata: info.clone(),
+        metadata,
         timestamp,
         description,
-    };
+        display: DisplaySettings::default(),
+        removed_rows: None,
+        renamed_columns: Vec::new(),
+    }
+}
 
-    // 清空历史并添加新条目
+/// 清空历史并以导入记录作为新数据集的第一条，返回新数据集的元信息
+pub(crate) fn replace_with_import_entry(state: &AppState, entry: HistoryEntry) -> Result<DatasetInfo, String> {
+    let info = entry.metadata.clone();
     let mut store = state
         .data_store
         .lock()
         .map_err(|e| format!("Failed to lock data store: {}", e))?;
-    store.clear();
-    store.push_operation(entry);
-
+    store.replace_with_import(entry).map_err(|e| e.to_string())?;
     Ok(info)
 }
 
+// ============================================================================
+// Parquet 文件元数据
+// ============================================================================
+/// 获取 Parquet 文件的元数据（行组、压缩方式、列编码、最小 / 最大值统计）
+///
+/// 只读取文件末尾的元数据，不加载数据，用于排查文件读取慢或体积大的原因
+///
+/// 参数：
+/// - file_path: Parquet 文件路径（None 表示当前数据集导入时使用的文件，必须是本地 .parquet 文件）
+///
+/// 前端调用示例：
+/// ```typescript
+/// const meta = await invoke('get_parquet_metadata', { filePath: '/path/to/data.parquet' });
+/// ```
+#[tauri::command]
+pub async fn get_parquet_metadata(
+    file_path: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<ParquetMetadata, String> {
+    let file_path = match file_path {
+        Some(path) => path,
+        None => {
+            let store = state
+                .data_store
+                .lock()
+                .map_err(|e| format!("Failed to lock data store: {}", e))?;
+            let source = store
+                .get_history()
+                .into_iter()
+                .find_map(|entry| match entry.operation {
+                    OperationType::Import { file_path, .. } => Some(file_path),
+                    _ => None,
+                })
+                .ok_or("没有找到导入的文件，请指定 Parquet 文件路径")?;
+            let is_parquet = std::path::Path::new(&source)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("parquet"));
+            if source.contains("://") || !is_parquet {
+                return Err(format!(
+                    "当前数据不是从本地 Parquet 文件导入的（来源: {}），请指定 Parquet 文件路径",
+                    source
+                ));
+            }
+            source
+        }
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        read_parquet_metadata(&file_path).map_err(|e| format!("读取 Parquet 元数据失败 ({}): {}", file_path, e))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 // ============================================================================
 // 错误处理说明
 // ============================================================================
@@ -363,3 +1152,45 @@ pub async fn import_parquet(file_path: String, state: tauri::State<'_, AppState>
 // ```
 //
 // ============================================================================
+
+// ============================================================================
+// 演示数据命令
+// ============================================================================
+/// 生成演示数据集并作为导入记录加入历史（替换当前数据集）
+///
+/// 让新用户不用找文件就能试用所有功能。数据由固定种子生成，
+/// 同样的预设和行数总是得到同样的数据
+///
+/// 参数：
+/// - rows: 行数（可选，默认 1000，最多 1,000,000）
+/// - schema_preset: 结构预设（可选，"sales" / "timeseries" / "iris"，默认 "sales"）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Ok(DatasetInfo): 演示数据集元信息（来源为 "demo://<预设>"）
+/// - Err(String): 行数超出范围或数据集被锁定
+///
+/// 前端调用示例：
+/// ```typescript
+/// const info = await invoke('generate_demo_data', {
+///   rows: 5000,
+///   schemaPreset: 'timeseries'
+/// });
+/// ```
+#[tauri::command]
+pub async fn generate_demo_data(
+    rows: Option<usize>,
+    schema_preset: Option<DemoPreset>,
+    state: tauri::State<'_, AppState>,
+) -> Result<DatasetInfo, String> {
+    let _session = state.imports.begin("演示数据").map_err(|e| e.to_string())?;
+    let preset = schema_preset.unwrap_or_default();
+    let rows = rows.unwrap_or(DEFAULT_DEMO_ROWS);
+    let df = tauri::async_runtime::spawn_blocking(move || generate_demo_dataframe(preset, rows))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("生成演示数据失败: {}", e))?;
+
+    let entry = create_import_entry(demo_source(preset), None, df);
+    replace_with_import_entry(&state, entry)
+}
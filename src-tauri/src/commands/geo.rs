@@ -0,0 +1,217 @@
+// ============================================================================
+// commands/geo.rs - 地理坐标命令
+// ============================================================================
+// 这个文件实现了经纬度相关的 Tauri 命令：
+// - validate_coordinates: 校验经纬度列（只读，不产生历史记录）
+// - haversine_distance: 计算两组坐标之间的球面距离（记录到历史）
+// - geo_bucket: 将坐标分桶为网格或 Geohash 列（记录到历史）
+
+use crate::AppState;
+use crate::commands::operations::create_history_entry;
+use crate::data::column_names::OutputColumns;
+use crate::data::geo::{
+    coordinate_column, geo_bucket as bucket_of, haversine_km, is_valid_coordinate, validate_bucket_method,
+    validate_coordinates as coordinate_report,
+};
+use crate::models::history::ColumnConflictPolicy;
+use crate::models::{CoordinateReport, GeoBucketMethod, OperationType};
+use polars::prelude::*;
+use tauri::State;
+
+// ============================================================================
+// 1. 坐标校验（Validate Coordinates）
+// ============================================================================
+/// 校验经纬度列：统计有效、空值、超出范围以及疑似经纬度颠倒的数量
+///
+/// 参数：
+/// - lat_column: 纬度列
+/// - lon_column: 经度列
+///
+/// 返回：
+/// - Result<CoordinateReport, String>: 校验结果
+#[tauri::command]
+pub async fn validate_coordinates(
+    lat_column: String,
+    lon_column: String,
+    state: State<'_, AppState>,
+) -> Result<CoordinateReport, String> {
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        coordinate_report(&current_df, &lat_column, &lon_column).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// ============================================================================
+// 2. 球面距离（Haversine Distance）
+// ============================================================================
+/// 计算两组坐标之间的 Haversine 球面距离（千米），结果写入新列
+///
+/// 任一坐标为空或无效时结果为空值
+///
+/// 参数：
+/// - lat1 / lon1: 起点纬度、经度列
+/// - lat2 / lon2: 终点纬度、经度列
+/// - output_column: 新列名（默认 "distance_km"）
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error）
+#[tauri::command]
+pub async fn haversine_distance(
+    lat1: String,
+    lon1: String,
+    lat2: String,
+    lon2: String,
+    output_column: Option<String>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let output_column = output_column.unwrap_or_else(|| "distance_km".to_string());
+    let (lat1_clone, lon1_clone, lat2_clone, lon2_clone) = (lat1.clone(), lon1.clone(), lat2.clone(), lon2.clone());
+    let output_clone = output_column.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let output_clone = outputs.resolve(&output_clone).map_err(|e| e.to_string())?;
+
+        let lat1 = coordinate_column(&current_df, &lat1_clone).map_err(|e| e.to_string())?;
+        let lon1 = coordinate_column(&current_df, &lon1_clone).map_err(|e| e.to_string())?;
+        let lat2 = coordinate_column(&current_df, &lat2_clone).map_err(|e| e.to_string())?;
+        let lon2 = coordinate_column(&current_df, &lon2_clone).map_err(|e| e.to_string())?;
+
+        let distance: Float64Chunked = lat1
+            .into_iter()
+            .zip(&lon1)
+            .zip(lat2.into_iter().zip(&lon2))
+            .map(|((lat1, lon1), (lat2, lon2))| match (lat1, lon1, lat2, lon2) {
+                (Some(lat1), Some(lon1), Some(lat2), Some(lon2))
+                    if is_valid_coordinate(lat1, lon1) && is_valid_coordinate(lat2, lon2) =>
+                {
+                    Some(haversine_km(lat1, lon1, lat2, lon2))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut df = current_df;
+        df.with_column(distance.with_name(output_clone.as_str().into()).into_series())
+            .map_err(|e| format!("添加列 {} 失败: {}", output_clone, e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::HaversineDistance {
+        lat1,
+        lon1,
+        lat2,
+        lon2,
+        output_column,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 3. 坐标分桶（Geo Bucket）
+// ============================================================================
+/// 将坐标分桶为网格或 Geohash 字符串列，便于按桶分组聚合（例如密度图）
+///
+/// 坐标为空或无效时结果为空值
+///
+/// 参数：
+/// - lat_column: 纬度列
+/// - lon_column: 经度列
+/// - method: 分桶方式（网格边长或 Geohash 精度）
+/// - output_column: 新列名（默认 "geo_bucket"）
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error）
+#[tauri::command]
+pub async fn geo_bucket(
+    lat_column: String,
+    lon_column: String,
+    method: GeoBucketMethod,
+    output_column: Option<String>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    validate_bucket_method(&method).map_err(|e| e.to_string())?;
+
+    let output_column = output_column.unwrap_or_else(|| "geo_bucket".to_string());
+    let lat_clone = lat_column.clone();
+    let lon_clone = lon_column.clone();
+    let method_clone = method.clone();
+    let output_clone = output_column.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let output_clone = outputs.resolve(&output_clone).map_err(|e| e.to_string())?;
+
+        let lat = coordinate_column(&current_df, &lat_clone).map_err(|e| e.to_string())?;
+        let lon = coordinate_column(&current_df, &lon_clone).map_err(|e| e.to_string())?;
+
+        let buckets: StringChunked = lat
+            .into_iter()
+            .zip(&lon)
+            .map(|(lat, lon)| match (lat, lon) {
+                (Some(lat), Some(lon)) => bucket_of(lat, lon, &method_clone),
+                _ => None,
+            })
+            .collect();
+
+        let mut df = current_df;
+        df.with_column(buckets.with_name(output_clone.as_str().into()).into_series())
+            .map_err(|e| format!("添加列 {} 失败: {}", output_clone, e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::GeoBucket {
+        lat_column,
+        lon_column,
+        method,
+        output_column,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -2,10 +2,18 @@
 // commands/history.rs - 历史管理命令
 // ============================================================================
 // 这个文件实现了操作历史相关的 Tauri 命令
-// 包括获取历史列表、undo、redo、跳转到指定历史等功能
+// 包括获取历史列表、undo、redo、跳转到指定历史、查看被删除的行、指标历史、导出记录、
+// 与初始导入的差异等功能
 
 use crate::AppState;
-use crate::models::HistoryEntryInfo;
+use crate::data::dataframe_to_json_rows;
+use crate::data::decimal::is_numeric_dtype;
+use crate::models::{
+    ColumnMetric, DatasetData, DatasetDiff, DtypeChange, ExportRecord, HistoryEntryInfo, MetricSnapshot, OperationType,
+    RenamedColumn, StateRecoveryReport,
+};
+use polars::prelude::*;
+use std::collections::{HashMap, HashSet};
 use tauri::State;
 
 // ============================================================================
@@ -115,6 +123,62 @@ pub async fn jump_to_history(entry_id: String, state: State<'_, AppState>) -> Re
     Ok(())
 }
 
+// ============================================================================
+// 查看被删除的行
+// ============================================================================
+/// 获取某个历史操作删除的行（分页）
+///
+/// 只有在筛选 / 删除空值时传入 capture_removed = true 才会记录被删除的行
+/// 历史条目信息中的 removed_row_count 不为空时表示可以查看
+///
+/// 参数：
+/// - entry_id: 历史条目的 ID
+/// - offset: 起始行索引（从 0 开始）
+/// - limit: 要获取的行数
+///
+/// 返回：
+/// - Result<DatasetData, String>: 被删除的行（不包含单元格样式）
+#[tauri::command]
+pub async fn get_removed_rows(
+    entry_id: String,
+    offset: usize,
+    limit: usize,
+    state: State<'_, AppState>,
+) -> Result<DatasetData, String> {
+    let store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let entry = store
+        .get_entry(&entry_id)
+        .ok_or_else(|| format!("找不到历史节点: {}", entry_id))?;
+    let removed = entry
+        .removed_rows
+        .as_ref()
+        .ok_or_else(|| format!("操作 '{}' 没有记录被删除的行", entry.description))?;
+
+    let total_rows = removed.height();
+    let start = offset.min(total_rows);
+    let end = offset.saturating_add(limit).min(total_rows);
+    let sliced_df = removed.slice(start as i64, end - start);
+
+    let columns: Vec<String> = sliced_df.get_column_names().iter().map(|s| s.to_string()).collect();
+    let rows = dataframe_to_json_rows(
+        &sliced_df,
+        &entry.display.column_formats,
+        &entry.display.float_precision,
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(DatasetData {
+        columns,
+        rows,
+        total_rows,
+        cell_styles: None,
+    })
+}
+
 // ============================================================================
 // 检查是否可以撤销/重做
 // ============================================================================
@@ -165,6 +229,115 @@ pub async fn reset_to_initial(state: State<'_, AppState>) -> Result<(), String>
     Ok(())
 }
 
+// ============================================================================
+// 只读锁定
+// ============================================================================
+/// 锁定当前数据集（只读）
+///
+/// 锁定后，所有修改数据或历史的命令（数据操作、undo/redo、跳转、重置、导入、清空）
+/// 都会返回"数据集已锁定"错误；查询、统计、图表和导出不受影响
+///
+/// 返回：
+/// - Result<(), String>: 成功或错误消息（没有数据时无法锁定）
+#[tauri::command]
+pub async fn lock_dataset(state: State<'_, AppState>) -> Result<(), String> {
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    store.lock().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// 解除当前数据集的只读锁定
+///
+/// 返回：
+/// - Result<(), String>: 成功或错误消息
+#[tauri::command]
+pub async fn unlock_dataset(state: State<'_, AppState>) -> Result<(), String> {
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    store.unlock();
+
+    Ok(())
+}
+
+/// 检查当前数据集是否处于只读锁定状态
+///
+/// 返回：
+/// - Result<bool, String>: 是否已锁定
+#[tauri::command]
+pub async fn is_dataset_locked(state: State<'_, AppState>) -> Result<bool, String> {
+    let store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    Ok(store.is_locked())
+}
+
+// ============================================================================
+// 恢复状态
+// ============================================================================
+/// 检查并修复数据存储的状态
+///
+/// 某个命令在修改数据时 panic，数据存储的锁会"中毒"。加锁时会自动恢复并修复历史索引，
+/// 之后的命令可以继续使用；这个命令用于在出错后主动检查一次，并告诉前端是否发生过恢复，
+/// 前端可以据此重新加载历史列表和当前数据
+///
+/// 返回：
+/// - Result<StateRecoveryReport, String>: 恢复次数、是否修复了历史索引、当前历史位置
+///
+/// 前端调用示例：
+/// ```typescript
+/// const report = await invoke('recover_state');
+/// if (report.lock_recoveries > 0 || report.repaired) {
+///   await refreshHistory();
+/// }
+/// ```
+#[tauri::command]
+pub async fn recover_state(state: State<'_, AppState>) -> Result<StateRecoveryReport, String> {
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let repaired = store.repair();
+    Ok(StateRecoveryReport {
+        lock_recoveries: state.data_store.recovery_count(),
+        repaired,
+        history_len: store.history_len(),
+        current_index: store.get_current_index(),
+    })
+}
+
+// ============================================================================
+// 导出记录
+// ============================================================================
+/// 获取本次会话中成功的导出记录（按时间顺序）
+///
+/// 每条记录包含文件路径、导出命令、完成时间，以及导出数据所在的历史条目 ID 和操作描述，
+/// 用于追溯已交付的文件来自哪个数据状态（条目之后被撤销或裁剪时记录仍然保留）
+///
+/// 前端调用示例：
+/// ```typescript
+/// const log = await invoke<ExportRecord[]>('get_export_log');
+/// ```
+#[tauri::command]
+pub async fn get_export_log(state: State<'_, AppState>) -> Result<Vec<ExportRecord>, String> {
+    let store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    Ok(store.export_log().to_vec())
+}
+
 // ============================================================================
 // 使用说明
 // ============================================================================
@@ -192,6 +365,234 @@ pub async fn reset_to_initial(state: State<'_, AppState>) -> Result<(), String>
 // // 6. 检查是否可以撤销/重做
 // const canUndo = await invoke<boolean>('can_undo');
 // const canRedo = await invoke<boolean>('can_redo');
+//
+// // 7. 只读锁定
+// await invoke('lock_dataset');
+// const locked = await invoke<boolean>('is_dataset_locked');
+// await invoke('unlock_dataset');
+//
+// // 8. 导出记录
+// const exports = await invoke<ExportRecord[]>('get_export_log');
+//
+// // 9. 与初始导入的差异
+// const diff = await invoke<DatasetDiff>('diff_from_initial');
 // ```
 //
 // ============================================================================
+
+// ============================================================================
+// 指标历史
+// ============================================================================
+/// 计算每个历史节点上的数据指标，查看各步清洗操作如何改变数据分布
+///
+/// 每个节点包含行数、列数，以及所选列的空值数量、空值比例和均值（仅数值列）
+/// 只包含数据操作节点（列格式、条件格式、视图状态等显示设置节点不改变数据，会被跳过）；
+/// 可以重做的节点也会包含在内，is_current 标记当前所在节点
+///
+/// 参数：
+/// - columns: 要跟踪的列（可选，默认为当前数据集的全部列）
+/// - state: 应用状态
+///
+/// 返回：
+/// - Result<Vec<MetricSnapshot>, String>: 按时间顺序的指标列表
+#[tauri::command]
+pub async fn get_metric_history(
+    columns: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<Vec<MetricSnapshot>, String> {
+    // 只在锁内克隆 DataFrame（共享底层数据，开销很小），指标在后台线程计算
+    let (entries, columns) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+        let columns = match columns {
+            Some(columns) => columns,
+            None => store
+                .get_current()
+                .ok_or("没有数据")?
+                .get_column_names()
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+        };
+
+        let current_index = store.get_current_index();
+        let entries: Vec<_> = store
+            .entries()
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| !entry.operation.is_display_only())
+            .map(|(index, entry)| {
+                (
+                    entry.id.clone(),
+                    entry.description.clone(),
+                    entry.timestamp.clone(),
+                    Some(index) == current_index,
+                    entry.dataframe.clone(),
+                )
+            })
+            .collect();
+        (entries, columns)
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        entries
+            .into_iter()
+            .map(|(entry_id, description, timestamp, is_current, df)| {
+                let columns = columns
+                    .iter()
+                    .map(|column| column_metric(&df, column))
+                    .collect::<Result<Vec<_>, String>>()?;
+                Ok(MetricSnapshot {
+                    entry_id,
+                    description,
+                    timestamp,
+                    is_current,
+                    row_count: df.height(),
+                    column_count: df.width(),
+                    columns,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 计算单列指标（列不存在时 present 为 false）
+fn column_metric(df: &DataFrame, column: &str) -> Result<ColumnMetric, String> {
+    let Ok(series) = df.column(column) else {
+        return Ok(ColumnMetric {
+            column: column.to_string(),
+            present: false,
+            dtype: None,
+            null_count: None,
+            null_ratio: None,
+            mean: None,
+        });
+    };
+
+    let null_count = series.null_count();
+    let null_ratio = (df.height() > 0).then(|| null_count as f64 / df.height() as f64);
+    let mean = if is_numeric_dtype(series.dtype()) {
+        series
+            .cast(&DataType::Float64)
+            .map_err(|e| format!("列 '{}' 转换为数值失败: {}", column, e))?
+            .f64()
+            .map_err(|e| e.to_string())?
+            .mean()
+    } else {
+        None
+    };
+
+    Ok(ColumnMetric {
+        column: column.to_string(),
+        present: true,
+        dtype: Some(format!("{:?}", series.dtype())),
+        null_count: Some(null_count),
+        null_ratio,
+        mean,
+    })
+}
+
+// ============================================================================
+// 与初始导入的差异
+// ============================================================================
+/// 汇总从初始导入到当前状态的累计变化
+///
+/// 对比历史中第一个导入节点（历史被截断时为最早的节点）和当前节点：
+/// - 行数变化
+/// - 新增、删除的列
+/// - 重命名的列（按重命名操作追踪，多次重命名合并为 初始列名 → 当前列名）
+/// - 类型变化的列
+///
+/// 参数：
+/// - state: 应用状态
+///
+/// 返回：
+/// - Result<DatasetDiff, String>: 累计变化
+#[tauri::command]
+pub async fn diff_from_initial(state: State<'_, AppState>) -> Result<DatasetDiff, String> {
+    let store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let current_index = store.get_current_index().ok_or("没有数据")?;
+    let entries = &store.entries()[..=current_index];
+    let baseline_index = entries
+        .iter()
+        .position(|entry| matches!(entry.operation, OperationType::Import { .. }))
+        .unwrap_or(0);
+    let baseline = &entries[baseline_index];
+    let current = &entries[current_index];
+
+    // 追踪重命名：当前列名 -> 初始列名
+    let mut origins: HashMap<String, String> = HashMap::new();
+    let mut operations = Vec::new();
+    for entry in &entries[baseline_index + 1..] {
+        if let OperationType::RenameColumns { mapping } = &entry.operation {
+            let renamed: Vec<(String, String)> = mapping
+                .iter()
+                .map(|(old, new)| (new.clone(), origins.remove(old).unwrap_or_else(|| old.clone())))
+                .collect();
+            origins.extend(renamed);
+        }
+        if !entry.operation.is_display_only() {
+            operations.push(entry.description.clone());
+        }
+    }
+
+    let before = &baseline.dataframe;
+    let after = &current.dataframe;
+
+    let mut columns_added = Vec::new();
+    let mut columns_renamed = Vec::new();
+    let mut dtype_changes = Vec::new();
+    let mut kept = HashSet::new();
+    for column in after.get_columns() {
+        let name = column.name().to_string();
+        let origin = origins.get(&name).cloned().unwrap_or_else(|| name.clone());
+        let Ok(initial) = before.column(&origin) else {
+            columns_added.push(name);
+            continue;
+        };
+        if origin != name {
+            columns_renamed.push(RenamedColumn {
+                from: origin.clone(),
+                to: name.clone(),
+            });
+        }
+        if initial.dtype() != column.dtype() {
+            dtype_changes.push(DtypeChange {
+                column: name,
+                from: format!("{:?}", initial.dtype()),
+                to: format!("{:?}", column.dtype()),
+            });
+        }
+        kept.insert(origin);
+    }
+    let columns_dropped = before
+        .get_column_names()
+        .into_iter()
+        .filter(|name| !kept.contains(name.as_str()))
+        .map(|name| name.to_string())
+        .collect();
+
+    Ok(DatasetDiff {
+        baseline_entry_id: baseline.id.clone(),
+        baseline_description: baseline.description.clone(),
+        current_entry_id: current.id.clone(),
+        operations,
+        rows_before: before.height(),
+        rows_after: after.height(),
+        rows_removed: before.height().saturating_sub(after.height()),
+        rows_added: after.height().saturating_sub(before.height()),
+        columns_added,
+        columns_dropped,
+        columns_renamed,
+        dtype_changes,
+    })
+}
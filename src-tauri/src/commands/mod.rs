@@ -22,7 +22,8 @@
 // - pub mod file_import → 查找 commands/file_import.rs
 
 /// 数据导出和清空命令模块
-/// 包含：export_csv, clear_dataset, clear_all_datasets
+/// 包含：export_csv, export_parquet, export_arrow, export_aggregated, export_sqlite, export_excel,
+/// export_excel_report, export_clipboard, export_partitioned, export_arrow_stream, export_profile_report, clear_data
 pub mod data_export;
 
 /// 数据查询命令模块
@@ -30,22 +31,67 @@ pub mod data_export;
 pub mod data_query;
 
 /// 文件导入命令模块
-/// 包含：import_csv, import_excel
+/// 包含：import_csv, cancel_import, import_excel, import_excel_all_sheets, get_excel_sheets, import_parquet,
+/// import_parquet_dir, import_json, import_arrow, import_avro, import_orc, import_sample, import_url,
+/// reload_from_source, get_parquet_metadata, generate_demo_data
 pub mod file_import;
 
 /// 历史管理命令模块
-/// 包含：get_history, undo_operation, redo_operation, jump_to_history, can_undo, can_redo
+/// 包含：get_history, undo_operation, redo_operation, jump_to_history, can_undo, can_redo, get_removed_rows,
+/// get_metric_history, recover_state, get_export_log, diff_from_initial
 pub mod history;
 
 /// 数据操作命令模块
 /// 包含：unpivot_data, drop_nulls, drop_all_nulls, select_columns, drop_columns,
-///       rename_columns, cast_types, fill_null, rolling_average, rolling_median
+///       rename_columns, cast_types, cast_types_by_pattern, fill_null, rolling_average, rolling_median
 pub mod operations;
 
 /// 图表数据生成命令模块
-/// 包含：generate_chart_data
+/// 包含：generate_chart_data, suggest_charts, validate_chart_config, materialize_chart_data, export_chart_spec
 pub mod chart;
 
+/// 数据清洗命令模块
+/// 包含：normalize_emails, normalize_phones, parse_urls, parse_ip_addresses, parse_user_agents,
+/// parse_currency, parse_percentages, format_percentages, normalize_boolean, clean_whitespace,
+/// replace_non_finite
+pub mod cleaning;
+
+/// 地理坐标命令模块
+/// 包含：validate_coordinates, haversine_distance, geo_bucket
+pub mod geo;
+
+/// 时间间隔命令模块
+/// 包含：duration_between, shift_by_duration, duration_to_number, period_comparison, cohort_table,
+/// extract_date_parts, business_days_between, add_business_days, compute_age
+pub mod temporal;
+
+/// 设置命令模块
+/// 包含：list_export_profiles, save_export_profile, delete_export_profile, get_chart_defaults, save_chart_defaults,
+/// get_row_guardrails, save_row_guardrails, get_row_ordering, save_row_ordering, list_cast_profiles,
+/// save_cast_profile, delete_cast_profile, get_column_summaries, save_column_summaries
+pub mod settings;
+
+/// 后台任务命令模块
+/// 包含：get_task, list_tasks, cancel_task
+pub mod tasks;
+
+/// 快照分享包命令模块
+/// 包含：export_snapshot_bundle, import_snapshot_bundle, export_history_bundle, check_recipe_compatibility,
+/// resolve_recipe, apply_recipe
+pub mod snapshot;
+
+/// 数据库导入命令模块
+/// 包含：connect_database, list_tables, import_query
+pub mod database;
+
+/// 计划任务命令模块
+/// 包含：list_schedules, save_schedule, delete_schedule, run_schedule_now
+pub mod schedule;
+
+/// 源文件监视命令模块
+/// 包含：enable_file_watch, disable_file_watch
+pub mod watch;
+
 // ============================================================================
 // 重新导出（Re-exports）
 // ============================================================================
@@ -57,29 +103,82 @@ pub mod chart;
 // - 没有 pub use，需要写：use crate::commands::data_export::export_csv;
 
 /// 这些命令用于将数据导出为 CSV/Parquet 文件，以及清空数据
-pub use data_export::{clear_data, export_csv, export_parquet};
+pub use data_export::{
+    clear_data, export_aggregated, export_arrow, export_arrow_stream, export_clipboard, export_csv, export_excel,
+    export_excel_report, export_parquet, export_partitioned, export_profile_report, export_sqlite,
+};
 
 /// 这些命令用于查询数据集列表、元信息和实际数据
-pub use data_query::{get_column_stats, get_current_data, get_current_info};
+pub use data_query::{
+    aggregate_scalar, describe_dataset, detect_gaps, find_similar_values, get_column_formats, get_column_stats,
+    get_column_totals, get_current_data, get_current_info, get_dataset_fingerprint, get_kpi_summary, get_view_state,
+};
 
-/// 这些命令用于导入 CSV、Excel 和 Parquet 文件
-pub use file_import::{import_csv, import_excel, import_parquet};
+/// 这些命令用于导入 CSV、Excel、Parquet、JSON、Arrow、Avro 和 ORC 文件，以及查看 Parquet 文件元数据
+pub use file_import::{
+    cancel_import, generate_demo_data, get_excel_sheets, get_parquet_metadata, import_arrow, import_avro, import_csv,
+    import_excel, import_excel_all_sheets, import_json, import_orc, import_parquet, import_parquet_dir, import_sample,
+    import_url, reload_from_source,
+};
 
-/// 这些命令用于管理操作历史（undo/redo）
+/// 这些命令用于管理操作历史（undo/redo）、只读锁定、出错后的状态恢复和导出记录
 pub use history::{
-    can_redo, can_undo, get_current_index, get_history, jump_to_history, redo_operation, reset_to_initial,
-    undo_operation,
+    can_redo, can_undo, diff_from_initial, get_current_index, get_export_log, get_history, get_metric_history,
+    get_removed_rows, is_dataset_locked, jump_to_history, lock_dataset, recover_state, redo_operation,
+    reset_to_initial, undo_operation, unlock_dataset,
 };
 
 /// 这些命令用于数据操作（Phase 1: 基础操作）
 pub use operations::{
-    cast_types, drop_all_nulls, drop_columns, drop_nulls, fill_null, filter_data, pivot_data, rename_columns,
-    rolling_average, rolling_max, rolling_median, rolling_min, rolling_quantile, rolling_std, rolling_sum, rolling_var,
-    select_columns, sort_data, unpivot_data,
+    cast_types, cast_types_by_pattern, drop_all_nulls, drop_columns, drop_duplicates, drop_nulls, explode_list,
+    fill_null, filter_data, filter_rows, join_file, join_list, pivot_data, rename_columns, rolling_average,
+    rolling_batch, rolling_max, rolling_median, rolling_min, rolling_quantile, rolling_std, rolling_sum, rolling_var,
+    select_columns, set_column_format, set_conditional_formats, set_float_precision, set_view_state, sort_data,
+    unnest_struct, unpivot_data,
 };
 
 /// 这些命令用于图表数据生成
-pub use chart::generate_chart_data;
+pub use chart::{
+    export_chart_spec, generate_chart_data, materialize_chart_data, suggest_charts, validate_chart_config,
+};
+
+/// 这些命令用于常见语义类型的数据清洗
+pub use cleaning::{
+    clean_whitespace, format_percentages, normalize_boolean, normalize_emails, normalize_phones, parse_currency,
+    parse_ip_addresses, parse_percentages, parse_urls, parse_user_agents, replace_non_finite,
+};
+
+/// 这些命令用于经纬度校验、距离计算和坐标分桶
+pub use geo::{geo_bucket, haversine_distance, validate_coordinates};
+
+/// 这些命令用于管理 CSV 导出预设和图表默认设置
+pub use settings::{
+    delete_cast_profile, delete_export_profile, get_chart_defaults, get_column_summaries, get_row_guardrails,
+    get_row_ordering, list_cast_profiles, list_export_profiles, save_cast_profile, save_chart_defaults,
+    save_column_summaries, save_export_profile, save_row_guardrails, save_row_ordering,
+};
+
+pub use tasks::{cancel_task, get_task, list_tasks};
+
+/// 这些命令用于导出和导入快照分享包，以及检查和替换操作记录的参数与兼容性
+pub use snapshot::{
+    apply_recipe, check_recipe_compatibility, export_history_bundle, export_snapshot_bundle, import_snapshot_bundle,
+    resolve_recipe,
+};
+
+/// 这些命令用于从 PostgreSQL / MySQL 导入表或查询结果
+pub use database::{connect_database, import_query, list_tables};
+
+/// 这些命令用于管理和运行计划任务（定时重新导入、执行操作记录、导出）
+pub use schedule::{delete_schedule, list_schedules, run_schedule_now, save_schedule};
+
+/// 这些命令用于监视源文件变化
+pub use watch::{disable_file_watch, enable_file_watch};
+
+pub use temporal::{
+    add_business_days, business_days_between, cohort_table, compute_age, duration_between, duration_to_number,
+    extract_date_parts, period_comparison, shift_by_duration,
+};
 
 // ============================================================================
 // 使用示例
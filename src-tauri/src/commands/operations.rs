@@ -6,12 +6,31 @@
 // Phase 2: 实现复杂操作（unpivot, pivot, rolling）
 
 use crate::AppState;
+use crate::commands::file_import::load_local_source;
+use crate::commands::settings::cast_profiles_path;
+use crate::commands::settings::{current_row_guardrails, stable_ordering};
+use crate::data::cast_profiles::{cast_column, find_cast_profile, resolve_cast_rules, validate_cast_rules};
+use crate::data::column_names::OutputColumns;
+use crate::data::decimal::parse_dtype_name;
+use crate::data::guardrails::exceeds_row_guardrails;
+use crate::data::keys::{drop_duplicate_rows, join_on_keys};
+use crate::data::nested::{join_list_values, list_column, unnest_struct_column};
+use crate::data::ordering::unpivot_in_original_order;
+use crate::data::{
+    apply_null_policy, build_condition_predicate, build_sql_predicate, removed_by_predicate, rows_with_nulls,
+    validate_conditional_rules, validate_date_format,
+};
 use crate::error::DataAnalystError;
-use crate::models::history::FillStrategy;
-use crate::models::{ColumnInfo, DatasetInfo, HistoryEntry, OperationType};
+use crate::models::history::{
+    ColumnConflictPolicy, ColumnRename, DuplicateKeep, FillStrategy, JoinHow, RollingFunction,
+};
+use crate::models::{
+    CastRule, ColumnFormat, ColumnInfo, ConditionalFormatRule, DatasetInfo, DisplaySettings, FilterCondition,
+    FloatPrecision, FloatRounding, HistoryEntry, NullPolicy, OperationOutcome, OperationType, ViewState,
+};
 use polars::prelude::*;
-use std::collections::HashMap;
-use tauri::State;
+use std::collections::{HashMap, HashSet};
+use tauri::{AppHandle, State};
 
 // Use polars-ops directly for stable pivot implementation
 // Function called via fully qualified path in pivot_data
@@ -20,7 +39,7 @@ use tauri::State;
 // 辅助函数：创建历史条目
 // ============================================================================
 /// 从 DataFrame 和操作类型创建历史条目
-fn create_history_entry(df: DataFrame, operation: OperationType) -> Result<HistoryEntry, DataAnalystError> {
+pub(crate) fn create_history_entry(df: DataFrame, operation: OperationType) -> Result<HistoryEntry, DataAnalystError> {
     // 生成唯一 ID
     let id = uuid::Uuid::new_v4().to_string();
 
@@ -40,6 +59,9 @@ fn create_history_entry(df: DataFrame, operation: OperationType) -> Result<Histo
         metadata,
         timestamp,
         description,
+        display: DisplaySettings::default(),
+        removed_rows: None,
+        renamed_columns: Vec::new(),
     })
 }
 
@@ -57,6 +79,7 @@ fn create_dataset_info(df: &DataFrame, id: &str, name: &str) -> Result<DatasetIn
                 name: series.name().to_string(),
                 dtype: format!("{:?}", series.dtype()),
                 null_count,
+                summary: None,
             }
         })
         .collect();
@@ -71,6 +94,25 @@ fn create_dataset_info(df: &DataFrame, id: &str, name: &str) -> Result<DatasetIn
     })
 }
 
+// ============================================================================
+// 辅助函数：行数保护
+// ============================================================================
+/// 检查操作删除的行数是否超过行数保护阈值（force 为 true 时不检查）
+///
+/// 返回 Some 表示需要确认，命令应直接返回该结果，不修改数据和历史
+fn guard_row_removal(
+    app: &AppHandle,
+    rows_before: usize,
+    rows_after: usize,
+    force: bool,
+) -> Result<Option<OperationOutcome>, String> {
+    if force {
+        return Ok(None);
+    }
+    let guardrails = current_row_guardrails(app)?;
+    Ok(exceeds_row_guardrails(&guardrails, rows_before, rows_after).map(OperationOutcome::needs_confirmation))
+}
+
 // ============================================================================
 // 1. 删除空值行（Drop Nulls）
 // ============================================================================
@@ -78,8 +120,18 @@ fn create_dataset_info(df: &DataFrame, id: &str, name: &str) -> Result<DatasetIn
 ///
 /// 参数：
 /// - subset: 要检查的列（None 表示检查所有列）
+/// - capture_removed: 是否记录被删除的行（默认 false，可通过 get_removed_rows 查看）
+/// - force: 删除的行数超过行数保护阈值时仍然执行（默认 false，否则返回预计影响等待确认）
 #[tauri::command]
-pub async fn drop_nulls(subset: Option<Vec<String>>, state: State<'_, AppState>) -> Result<(), String> {
+pub async fn drop_nulls(
+    subset: Option<Vec<String>>,
+    capture_removed: Option<bool>,
+    force: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<OperationOutcome, String> {
+    let capture_removed = capture_removed.unwrap_or(false);
+
     let (current_df, subset_clone) = {
         let store = state
             .data_store
@@ -88,40 +140,68 @@ pub async fn drop_nulls(subset: Option<Vec<String>>, state: State<'_, AppState>)
         let df = store.get_current().ok_or("没有数据")?.clone();
         (df, subset.clone())
     };
+    let rows_before = current_df.height();
 
-    let result_df = tauri::async_runtime::spawn_blocking(move || {
-        if let Some(cols) = &subset_clone {
+    let (result_df, removed_rows) = tauri::async_runtime::spawn_blocking(move || {
+        let result_df = if let Some(cols) = &subset_clone {
             current_df
                 .drop_nulls::<String>(Some(cols.as_slice()))
-                .map_err(|e| format!("删除空值行失败: {}", e))
+                .map_err(|e| format!("删除空值行失败: {}", e))?
         } else {
             current_df
                 .drop_nulls::<String>(None)
-                .map_err(|e| format!("删除空值行失败: {}", e))
-        }
+                .map_err(|e| format!("删除空值行失败: {}", e))?
+        };
+
+        let removed_rows = if capture_removed {
+            Some(
+                rows_with_nulls(&current_df, subset_clone.as_deref())
+                    .map_err(|e| format!("记录被删除的行失败: {}", e))?,
+            )
+        } else {
+            None
+        };
+
+        Ok::<_, String>((result_df, removed_rows))
     })
     .await
     .map_err(|e| e.to_string())??;
 
+    if let Some(outcome) = guard_row_removal(&app, rows_before, result_df.height(), force.unwrap_or(false))? {
+        return Ok(outcome);
+    }
+
     let mut store = state
         .data_store
         .lock()
         .map_err(|e| format!("Failed to lock data store: {}", e))?;
 
     let operation = OperationType::DropNulls { subset };
-    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.removed_rows = removed_rows;
 
-    store.push_operation(entry);
+    store.push_operation(entry).map_err(|e| e.to_string())?;
 
-    Ok(())
+    Ok(OperationOutcome::applied())
 }
 
 // ============================================================================
 // 2. 删除全空行（Drop All Nulls）
 // ============================================================================
 /// 删除所有列都为空的行
+///
+/// 参数：
+/// - capture_removed: 是否记录被删除的行（默认 false，可通过 get_removed_rows 查看）
+/// - force: 删除的行数超过行数保护阈值时仍然执行（默认 false，否则返回预计影响等待确认）
 #[tauri::command]
-pub async fn drop_all_nulls(state: State<'_, AppState>) -> Result<(), String> {
+pub async fn drop_all_nulls(
+    capture_removed: Option<bool>,
+    force: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<OperationOutcome, String> {
+    let capture_removed = capture_removed.unwrap_or(false);
+
     let current_df = {
         let store = state
             .data_store
@@ -129,8 +209,9 @@ pub async fn drop_all_nulls(state: State<'_, AppState>) -> Result<(), String> {
             .map_err(|e| format!("Failed to lock data store: {}", e))?;
         store.get_current().ok_or("没有数据")?.clone()
     };
+    let rows_before = current_df.height();
 
-    let result_df = tauri::async_runtime::spawn_blocking(move || {
+    let (result_df, removed_rows) = tauri::async_runtime::spawn_blocking(move || {
         let mut mask: Option<BooleanChunked> = None;
 
         for series in current_df.get_columns() {
@@ -142,40 +223,78 @@ pub async fn drop_all_nulls(state: State<'_, AppState>) -> Result<(), String> {
         }
 
         if let Some(mask) = mask {
+            let removed_rows = if capture_removed {
+                Some(
+                    current_df
+                        .filter(&mask)
+                        .map_err(|e| format!("记录被删除的行失败: {}", e))?,
+                )
+            } else {
+                None
+            };
+
             let keep_mask = !mask;
-            current_df
+            let result_df = current_df
                 .filter(&keep_mask)
-                .map_err(|e| format!("删除全空行失败: {}", e))
+                .map_err(|e| format!("删除全空行失败: {}", e))?;
+            Ok::<_, String>((result_df, removed_rows))
         } else {
-            Ok::<DataFrame, String>(current_df)
+            let removed_rows = capture_removed.then(|| current_df.clear());
+            Ok::<_, String>((current_df, removed_rows))
         }
     })
     .await
     .map_err(|e| e.to_string())??;
 
+    if let Some(outcome) = guard_row_removal(&app, rows_before, result_df.height(), force.unwrap_or(false))? {
+        return Ok(outcome);
+    }
+
     let mut store = state
         .data_store
         .lock()
         .map_err(|e| format!("Failed to lock data store: {}", e))?;
 
     let operation = OperationType::DropAllNulls;
-    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.removed_rows = removed_rows;
 
-    store.push_operation(entry);
+    store.push_operation(entry).map_err(|e| e.to_string())?;
 
-    Ok(())
+    Ok(OperationOutcome::applied())
 }
 
 // ============================================================================
 // 3. 选择列（Select Columns）
 // ============================================================================
-/// 保留指定的列
+/// 保留指定的列，结果按给定列表的顺序排列（与 SQL SELECT 相同）
 ///
 /// 参数：
-/// - columns: 要保留的列名列表
+/// - columns: 要保留的列名列表（不能重复）
+/// - keep_remaining: 是否保留其余列（可选，默认 false）；为 true 时其余列按原顺序排在列表之后，
+///   相当于把指定的列移到最前面
+///
+/// 前端调用示例：
+/// ```typescript
+/// // 只保留三列，按 name、amount、date 的顺序
+/// await invoke('select_columns', { columns: ['name', 'amount', 'date'] });
+/// // 把 id 和 name 移到最前面，其余列保持原顺序
+/// await invoke('select_columns', { columns: ['id', 'name'], keepRemaining: true });
+/// ```
 #[tauri::command]
-pub async fn select_columns(columns: Vec<String>, state: State<'_, AppState>) -> Result<(), String> {
-    let cols_clone = columns.clone();
+pub async fn select_columns(
+    columns: Vec<String>,
+    keep_remaining: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if columns.is_empty() {
+        return Err("至少需要选择一列".to_string());
+    }
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = columns.iter().find(|name| !seen.insert(name.as_str())) {
+        return Err(format!("列 '{}' 重复", duplicate));
+    }
+    let keep_remaining = keep_remaining.unwrap_or(false);
 
     let current_df = {
         let store = state
@@ -185,9 +304,24 @@ pub async fn select_columns(columns: Vec<String>, state: State<'_, AppState>) ->
         store.get_current().ok_or("没有数据")?.clone()
     };
 
+    if let Some(missing) = columns.iter().find(|name| current_df.get_column_index(name).is_none()) {
+        return Err(format!("列 '{}' 不存在", missing));
+    }
+
+    let mut order = columns.clone();
+    if keep_remaining {
+        order.extend(
+            current_df
+                .get_column_names()
+                .iter()
+                .map(|name| name.to_string())
+                .filter(|name| !columns.contains(name)),
+        );
+    }
+
     let result_df = tauri::async_runtime::spawn_blocking(move || {
         current_df
-            .select(cols_clone.iter().map(|s| s.as_str()))
+            .select(order.iter().map(|s| s.as_str()))
             .map_err(|e| format!("选择列失败: {}", e))
     })
     .await
@@ -198,10 +332,13 @@ pub async fn select_columns(columns: Vec<String>, state: State<'_, AppState>) ->
         .lock()
         .map_err(|e| format!("Failed to lock data store: {}", e))?;
 
-    let operation = OperationType::SelectColumns { columns };
+    let operation = OperationType::SelectColumns {
+        columns,
+        keep_remaining,
+    };
     let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
 
-    store.push_operation(entry);
+    store.push_operation(entry).map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -239,7 +376,7 @@ pub async fn drop_columns(columns: Vec<String>, state: State<'_, AppState>) -> R
     let operation = OperationType::DropColumns { columns };
     let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
 
-    store.push_operation(entry);
+    store.push_operation(entry).map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -284,7 +421,7 @@ pub async fn rename_columns(mapping: HashMap<String, String>, state: State<'_, A
     let operation = OperationType::RenameColumns { mapping };
     let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
 
-    store.push_operation(entry);
+    store.push_operation(entry).map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -296,7 +433,7 @@ pub async fn rename_columns(mapping: HashMap<String, String>, state: State<'_, A
 ///
 /// 参数：
 /// - mapping: 列名 -> 目标类型的映射
-///   支持所有 Polars 基础类型
+///   支持所有 Polars 基础类型，以及 "Decimal"、"Decimal(精度, 小数位)"
 #[tauri::command]
 pub async fn cast_types(mapping: HashMap<String, String>, state: State<'_, AppState>) -> Result<(), String> {
     let mapping_clone = mapping.clone();
@@ -317,28 +454,10 @@ pub async fn cast_types(mapping: HashMap<String, String>, state: State<'_, AppSt
                 .map_err(|e| format!("找不到列 {}: {}", col_name, e))?
                 .clone();
 
-            let data_type = match target_type.as_str() {
-                "Int8" => DataType::Int8,
-                "Int16" => DataType::Int16,
-                "Int32" => DataType::Int32,
-                "Int64" => DataType::Int64,
-                "UInt8" => DataType::UInt8,
-                "UInt16" => DataType::UInt16,
-                "UInt32" => DataType::UInt32,
-                "UInt64" => DataType::UInt64,
-                "Float32" => DataType::Float32,
-                "Float64" => DataType::Float64,
-                "String" => DataType::String,
-                "Boolean" => DataType::Boolean,
-                "Date" => DataType::Date,
-                "Datetime" => DataType::Datetime(TimeUnit::Microseconds, None),
-                "Time" => DataType::Time,
-                "Duration" => DataType::Duration(TimeUnit::Microseconds),
-                _ => return Err(format!("不支持的类型: {}", target_type)),
-            };
+            let data_type = parse_dtype_name(target_type)?;
 
-            let casted_series = series
-                .cast(&data_type)
+            // 字符串 → Duration / Time 按文本解析（例如 "2d 03:15:00"、"08:30:00"）
+            let casted_series = cast_column(&series, &data_type, None)
                 .map_err(|e| format!("转换列 {} 到 {} 失败: {}", col_name, target_type, e))?;
 
             df = df
@@ -359,11 +478,89 @@ pub async fn cast_types(mapping: HashMap<String, String>, state: State<'_, AppSt
     let operation = OperationType::CastTypes { mapping };
     let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
 
-    store.push_operation(entry);
+    store.push_operation(entry).map_err(|e| e.to_string())?;
 
     Ok(())
 }
 
+/// 按列名模式批量转换列类型
+///
+/// 规则按顺序匹配，一列只使用第一条匹配的规则；rules 和 profile 二选一
+///
+/// 参数：
+/// - rules: 转换规则（例如 "*_date" → Date，格式 "%d.%m.%Y"；"*_id" → String）
+/// - profile: 已保存的转换预设名称
+///
+/// 返回：实际转换的列和目标类型（按列顺序）
+#[tauri::command]
+pub async fn cast_types_by_pattern(
+    rules: Option<Vec<CastRule>>,
+    profile: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<(String, String)>, String> {
+    let rules = match (rules, &profile) {
+        (Some(_), Some(_)) => return Err("转换规则和转换预设只能指定一个".to_string()),
+        (Some(rules), None) => rules,
+        (None, Some(name)) => {
+            find_cast_profile(&cast_profiles_path(&app)?, name)
+                .map_err(|e| e.to_string())?
+                .rules
+        }
+        (None, None) => return Err("请指定转换规则或转换预设".to_string()),
+    };
+    validate_cast_rules(&rules).map_err(|e| e.to_string())?;
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let rules_clone = rules.clone();
+    let (result_df, converted) = tauri::async_runtime::spawn_blocking(move || {
+        let resolved = resolve_cast_rules(&current_df, &rules_clone).map_err(|e| e.to_string())?;
+
+        let mut df = current_df;
+        let mut converted = Vec::with_capacity(resolved.len());
+        for (col_name, rule) in resolved {
+            let data_type = parse_dtype_name(&rule.target_type)?;
+            let series = df
+                .column(&col_name)
+                .map_err(|e| format!("找不到列 {}: {}", col_name, e))?
+                .clone();
+
+            let casted_series = cast_column(&series, &data_type, rule.format.as_deref())
+                .map_err(|e| format!("转换列 {} 到 {} 失败: {}", col_name, rule.target_type, e))?;
+
+            df.with_column(casted_series)
+                .map_err(|e| format!("更新列 {} 失败: {}", col_name, e))?;
+            converted.push((col_name, rule.target_type.clone()));
+        }
+        Ok::<_, String>((df, converted))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::CastTypesByPattern {
+        rules,
+        profile,
+        columns: converted.iter().map(|(name, _)| name.clone()).collect(),
+    };
+    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(converted)
+}
+
 // ============================================================================
 // 7. 排序（Sort）
 // ============================================================================
@@ -372,8 +569,10 @@ pub async fn sort_data(
     column: String,
     descending: bool,
     nulls_last: bool,
+    app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    let stable = stable_ordering(&app)?;
     let column_clone = column.clone();
     let current_df = {
         let store = state
@@ -394,7 +593,8 @@ pub async fn sort_data(
                 [column_clone.as_str()],
                 SortMultipleOptions::new()
                     .with_order_descending(descending)
-                    .with_nulls_last(nulls_last),
+                    .with_nulls_last(nulls_last)
+                    .with_maintain_order(stable),
             )
             .map_err(|e| format!("排序失败: {}", e))
     })
@@ -412,7 +612,7 @@ pub async fn sort_data(
         nulls_last,
     };
     let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
-    store.push_operation(entry);
+    store.push_operation(entry).map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -422,11 +622,26 @@ pub async fn sort_data(
 // ============================================================================
 /// 使用 SQL WHERE 子句筛选数据
 ///
+/// 空值判断使用 IS NULL / IS NOT NULL（不能写 = NULL），集合和模式匹配使用
+/// IN (...)、NOT IN (...)、BETWEEN 下限 AND 上限、LIKE / ILIKE '模式'
+///
 /// 参数：
 /// - expression: SQL WHERE 条件（不需要 "WHERE" 关键字）
+/// - null_policy: 条件结果为 null 时的处理策略（默认视为 false，与 SQL 一致）
+/// - capture_removed: 是否记录被筛掉的行（默认 false，可通过 get_removed_rows 查看）
+/// - force: 删除的行数超过行数保护阈值时仍然执行（默认 false，否则返回预计影响等待确认）
 #[tauri::command]
-pub async fn filter_data(expression: String, state: State<'_, AppState>) -> Result<(), String> {
+pub async fn filter_data(
+    expression: String,
+    null_policy: Option<NullPolicy>,
+    capture_removed: Option<bool>,
+    force: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<OperationOutcome, String> {
     let expr_clone = expression.clone();
+    let null_policy = null_policy.unwrap_or_default();
+    let capture_removed = capture_removed.unwrap_or(false);
 
     let current_df = {
         let store = state
@@ -435,32 +650,142 @@ pub async fn filter_data(expression: String, state: State<'_, AppState>) -> Resu
             .map_err(|e| format!("Failed to lock data store: {}", e))?;
         store.get_current().ok_or("没有数据")?.clone()
     };
+    let rows_before = current_df.height();
+
+    let (result_df, removed_rows) = tauri::async_runtime::spawn_blocking(move || {
+        let predicate = apply_null_policy(
+            build_sql_predicate(&expr_clone).map_err(|e| e.to_string())?,
+            null_policy,
+        );
+
+        let removed_rows = if capture_removed {
+            Some(
+                removed_by_predicate(&current_df, predicate.clone())
+                    .map_err(|e| format!("记录被删除的行失败: {}", e))?,
+            )
+        } else {
+            None
+        };
 
-    let result_df = tauri::async_runtime::spawn_blocking(move || {
-        let sql_query = format!("SELECT * FROM self WHERE {}", expr_clone);
-        let mut ctx = polars::sql::SQLContext::new();
-        ctx.register("self", current_df.lazy());
-
-        let result_lf = ctx
-            .execute(&sql_query)
+        let result_df = current_df
+            .lazy()
+            .filter(predicate)
+            .collect()
             .map_err(|e| format!("SQL 查询执行失败: {}", e))?;
+        Ok::<_, String>((result_df, removed_rows))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    if let Some(outcome) = guard_row_removal(&app, rows_before, result_df.height(), force.unwrap_or(false))? {
+        return Ok(outcome);
+    }
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::Filter {
+        expression,
+        null_policy,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.removed_rows = removed_rows;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(OperationOutcome::applied())
+}
+
+// ============================================================================
+// 8.1 结构化筛选（Filter Rows）
+// ============================================================================
+/// 使用结构化条件筛选数据
+///
+/// 支持比较、包含、为空 / 不为空、IN / NOT IN、BETWEEN、LIKE / ILIKE 等运算符，无需编写 SQL
+///
+/// 参数：
+/// - conditions: 筛选条件列表
+/// - match_all: 是否要求所有条件都满足（默认 true；false 表示任一条件满足）
+/// - null_policy: 条件结果为 null 时的处理策略（默认视为 false）
+/// - capture_removed: 是否记录被筛掉的行（默认 false，可通过 get_removed_rows 查看）
+/// - force: 删除的行数超过行数保护阈值时仍然执行（默认 false，否则返回预计影响等待确认）
+#[tauri::command]
+pub async fn filter_rows(
+    conditions: Vec<FilterCondition>,
+    match_all: Option<bool>,
+    null_policy: Option<NullPolicy>,
+    capture_removed: Option<bool>,
+    force: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<OperationOutcome, String> {
+    let conditions_clone = conditions.clone();
+    let match_all = match_all.unwrap_or(true);
+    let null_policy = null_policy.unwrap_or_default();
+    let capture_removed = capture_removed.unwrap_or(false);
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+    let rows_before = current_df.height();
+
+    let (result_df, removed_rows) = tauri::async_runtime::spawn_blocking(move || {
+        for condition in &conditions_clone {
+            if current_df.get_column_index(&condition.column).is_none() {
+                return Err(format!("列 '{}' 不存在", condition.column));
+            }
+        }
+
+        let predicate = apply_null_policy(
+            build_condition_predicate(&conditions_clone, match_all).map_err(|e| e.to_string())?,
+            null_policy,
+        );
+
+        let removed_rows = if capture_removed {
+            Some(
+                removed_by_predicate(&current_df, predicate.clone())
+                    .map_err(|e| format!("记录被删除的行失败: {}", e))?,
+            )
+        } else {
+            None
+        };
 
-        result_lf.collect().map_err(|e| format!("收集查询结果失败: {}", e))
+        let result_df = current_df
+            .lazy()
+            .filter(predicate)
+            .collect()
+            .map_err(|e| format!("筛选失败: {}", e))?;
+        Ok((result_df, removed_rows))
     })
     .await
     .map_err(|e| e.to_string())??;
 
+    if let Some(outcome) = guard_row_removal(&app, rows_before, result_df.height(), force.unwrap_or(false))? {
+        return Ok(outcome);
+    }
+
     let mut store = state
         .data_store
         .lock()
         .map_err(|e| format!("Failed to lock data store: {}", e))?;
 
-    let operation = OperationType::Filter { expression };
-    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    let operation = OperationType::FilterRows {
+        conditions,
+        match_all,
+        null_policy,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.removed_rows = removed_rows;
 
-    store.push_operation(entry);
+    store.push_operation(entry).map_err(|e| e.to_string())?;
 
-    Ok(())
+    Ok(OperationOutcome::applied())
 }
 
 // ============================================================================
@@ -561,7 +886,7 @@ pub async fn fill_null(strategy: serde_json::Value, state: State<'_, AppState>)
     };
     let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
 
-    store.push_operation(entry);
+    store.push_operation(entry).map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -576,8 +901,10 @@ pub async fn unpivot_data(
     variable_name: Option<String>,
     value_name: Option<String>,
     sort_column: Option<String>,
+    app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    let stable = stable_ordering(&app)?;
     let var_name = variable_name.unwrap_or_else(|| "variable".to_string());
     let val_name = value_name.unwrap_or_else(|| "value".to_string());
 
@@ -600,9 +927,14 @@ pub async fn unpivot_data(
         // Polars 0.52+ unpivot: (args, index)
         // With 'pivot' feature enabled, 'unpivot' method should be available on DataFrame
         // If unpivot is not available, we use melt (alias usually)
-        let df = current_df
-            .unpivot(&value_vars_clone, &id_vars_clone)
-            .map_err(|e| format!("Unpivot 操作失败: {}", e))?;
+        let df = if stable {
+            unpivot_in_original_order(&current_df, &value_vars_clone, &id_vars_clone).map_err(|e| e.to_string())
+        } else {
+            current_df
+                .unpivot(&value_vars_clone, &id_vars_clone)
+                .map_err(|e| e.to_string())
+        }
+        .map_err(|e| format!("Unpivot 操作失败: {}", e))?;
 
         let mut df = df;
         if var_name_clone != "variable" {
@@ -620,7 +952,10 @@ pub async fn unpivot_data(
 
         if let Some(sort_col) = sort_col_clone {
             df = df
-                .sort([sort_col.as_str()], SortMultipleOptions::default())
+                .sort(
+                    [sort_col.as_str()],
+                    SortMultipleOptions::default().with_maintain_order(stable),
+                )
                 .map_err(|e| format!("排序失败: {}", e))?;
         }
 
@@ -643,7 +978,7 @@ pub async fn unpivot_data(
     };
     let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
 
-    store.push_operation(entry);
+    store.push_operation(entry).map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -651,14 +986,154 @@ pub async fn unpivot_data(
 // ============================================================================
 // 10. 横表转纵表（Pivot）
 // ============================================================================
+/// 计算透视表（不修改数据，供 pivot_data 和 Excel 报告共用）
+///
+/// 参数：
+/// - index: 行索引列
+/// - columns: 展开为列头的列
+/// - values: 值列
+/// - aggregate: 聚合函数（first, last, sum, mean, min, max, count, median）
+pub(crate) fn compute_pivot(
+    df: &DataFrame,
+    index: &[String],
+    columns: &str,
+    values: &str,
+    aggregate: &str,
+    stable: bool,
+) -> Result<DataFrame, String> {
+    // 使用 LazyFrame 进行聚合和重塑，避免 Eager Pivot 类型问题
+    let agg_expr = match aggregate {
+        "first" => col(values).first(),
+        "last" => col(values).last(),
+        "sum" => col(values).sum(),
+        "mean" => col(values).mean(),
+        "min" => col(values).min(),
+        "max" => col(values).max(),
+        "count" => col(values).count(),
+        "median" => col(values).median(),
+        _ => return Err(format!("不支持的聚合函数: {}", aggregate)),
+    };
+
+    // 1. GroupBy + Aggregate (预聚合)
+    let mut group_cols = index.to_vec();
+    group_cols.push(columns.to_string());
+
+    // 稳定顺序下按首次出现的顺序分组，透视后的行和新列顺序与原始数据一致
+    let keys = group_cols.iter().map(col).collect::<Vec<_>>();
+    let lazy = df.clone().lazy();
+    let grouped = if stable {
+        lazy.group_by_stable(keys)
+    } else {
+        lazy.group_by(keys)
+    };
+    let grouped = grouped
+        .agg([agg_expr.alias(values)])
+        .collect()
+        .map_err(|e| format!("分组聚合失败: {}", e))?;
+
+    // 2. Pivot (重塑)
+    // 使用 polars-ops 直接调用 pivot_stable 函数
+    // pivot_stable 签名: (df, on, index, values, sort, agg_fn, separator)
+    // on: columns that will become headers (columns)
+    // index: columns to keep as rows (index)
+    // values: value columns (values)
+    // agg_fn: None (since we pre-aggregated)
+
+    polars_ops::frame::pivot::pivot_stable(
+        &grouped,
+        [columns],            // on
+        Some(index.to_vec()), // index
+        Some([values]),       // values
+        false,                // sort
+        None,                 // agg_fn
+        None,                 // separator
+    )
+    .map_err(|e| format!("透视表操作失败: {}", e))
+}
+
+/// 透视表的新列（columns 列的不同值）与索引列同名时按冲突策略处理
+///
+/// 覆盖索引列会丢失行标签，因此 overwrite 与 error 一样返回错误
+fn resolve_pivot_headers(
+    df: &DataFrame,
+    index: &[String],
+    columns: &str,
+    policy: ColumnConflictPolicy,
+) -> Result<(DataFrame, Vec<ColumnRename>), String> {
+    let headers = df
+        .column(columns)
+        .map_err(|_| format!("列 '{}' 不存在", columns))?
+        .cast(&DataType::String)
+        .map_err(|e| e.to_string())?;
+    let unique: Vec<String> = headers
+        .as_materialized_series()
+        .unique()
+        .map_err(|e| e.to_string())?
+        .str()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .flatten()
+        .map(|value| value.to_string())
+        .collect();
+
+    let policy = match policy {
+        ColumnConflictPolicy::Overwrite => ColumnConflictPolicy::Error,
+        other => other,
+    };
+    let index_df = df.select(index.iter().cloned()).map_err(|e| e.to_string())?;
+    let mut outputs = OutputColumns::new(&index_df, policy);
+
+    // 先登记不冲突的列名，避免自动后缀与其他新列重名
+    let (conflicting, free): (Vec<String>, Vec<String>) = unique.into_iter().partition(|value| index.contains(value));
+    for value in &free {
+        outputs.resolve(value).map_err(|e| e.to_string())?;
+    }
+    for value in &conflicting {
+        outputs.resolve(value).map_err(|e| e.to_string())?;
+    }
+    let renamed = outputs.into_renamed();
+    if renamed.is_empty() {
+        return Ok((df.clone(), renamed));
+    }
+
+    let mapping: HashMap<&str, &str> = renamed
+        .iter()
+        .map(|rename| (rename.requested.as_str(), rename.actual.as_str()))
+        .collect();
+    let replaced: StringChunked = headers
+        .str()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|value| value.map(|v| mapping.get(v).copied().unwrap_or(v)))
+        .collect();
+
+    let mut df = df.clone();
+    df.with_column(replaced.with_name(columns.into()).into_series())
+        .map_err(|e| e.to_string())?;
+    Ok((df, renamed))
+}
+
+/// 透视表（长表转宽表）
+///
+/// 参数：
+/// - index: 保留为行的列
+/// - columns: 其不同值成为新列的列
+/// - values: 填入新列的值列
+/// - aggregate: 聚合函数（默认 "first"）
+/// - on_conflict: 新列与索引列同名时的处理方式（默认 error，不支持 overwrite）
+///
+/// 启用稳定顺序时，行和新列按首次出现的顺序排列
 #[tauri::command]
 pub async fn pivot_data(
     index: Vec<String>,
     columns: String,
     values: String,
     aggregate: Option<String>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    let stable = stable_ordering(&app)?;
     // Clone for closure
     let index_clone = index.clone();
     let columns_clone = columns.clone();
@@ -673,49 +1148,22 @@ pub async fn pivot_data(
         store.get_current().ok_or("没有数据")?.clone()
     };
 
-    let result_df = tauri::async_runtime::spawn_blocking(move || {
-        // 使用 LazyFrame 进行聚合和重塑，避免 Eager Pivot 类型问题
-        let agg_expr = match agg_str.as_str() {
-            "first" => col(&values_clone).first(),
-            "last" => col(&values_clone).last(),
-            "sum" => col(&values_clone).sum(),
-            "mean" => col(&values_clone).mean(),
-            "min" => col(&values_clone).min(),
-            "max" => col(&values_clone).max(),
-            "count" => col(&values_clone).count(),
-            "median" => col(&values_clone).median(),
-            _ => return Err(format!("不支持的聚合函数: {}", agg_str)),
-        };
-
-        // 1. GroupBy + Aggregate (预聚合)
-        let mut group_cols = index_clone.clone();
-        group_cols.push(columns_clone.clone());
-
-        let grouped = current_df
-            .lazy()
-            .group_by(group_cols.iter().map(|s| col(s)).collect::<Vec<_>>())
-            .agg([agg_expr.alias(&values_clone)])
-            .collect()
-            .map_err(|e| format!("分组聚合失败: {}", e))?;
-
-        // 2. Pivot (重塑)
-        // 使用 polars-ops 直接调用 pivot_stable 函数
-        // pivot_stable 签名: (df, on, index, values, sort, agg_fn, separator)
-        // on: columns that will become headers (columns_clone)
-        // index: columns to keep as rows (index_clone)
-        // values: value columns (values_clone)
-        // agg_fn: None (since we pre-aggregated)
-
-        polars_ops::frame::pivot::pivot_stable(
-            &grouped,
-            [columns_clone],      // on
-            Some(index_clone),    // index
-            Some([values_clone]), // values
-            false,                // sort
-            None,                 // agg_fn
-            None,                 // separator
-        )
-        .map_err(|e| format!("透视表操作失败: {}", e))
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let (source_df, renamed) = resolve_pivot_headers(
+            &current_df,
+            &index_clone,
+            &columns_clone,
+            on_conflict.unwrap_or_default(),
+        )?;
+        let df = compute_pivot(
+            &source_df,
+            &index_clone,
+            &columns_clone,
+            &values_clone,
+            &agg_str,
+            stable,
+        )?;
+        Ok::<_, String>((df, renamed))
     })
     .await
     .map_err(|e| e.to_string())??;
@@ -730,9 +1178,10 @@ pub async fn pivot_data(
         columns,
         values: values.to_string(),
     };
-    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
 
-    store.push_operation(entry);
+    store.push_operation(entry).map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -746,6 +1195,7 @@ pub async fn rolling_average(
     window_size: usize,
     center: bool,
     min_periods: Option<usize>,
+    on_conflict: Option<ColumnConflictPolicy>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     if window_size < 1 {
@@ -762,8 +1212,11 @@ pub async fn rolling_average(
         store.get_current().ok_or("没有数据")?.clone()
     };
 
-    let result_df = tauri::async_runtime::spawn_blocking(move || {
-        let new_col_name = format!("{}_rolling_avg_{}", column_clone, window_size);
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let new_col_name = outputs
+            .resolve(&format!("{}_rolling_avg_{}", column_clone, window_size))
+            .map_err(|e| e.to_string())?;
 
         // Config options
         let options = RollingOptionsFixedWindow {
@@ -774,11 +1227,12 @@ pub async fn rolling_average(
             fn_params: None,
         };
 
-        current_df
+        let df = current_df
             .lazy()
             .with_column(col(&column_clone).rolling_mean(options).alias(&new_col_name))
             .collect()
-            .map_err(|e| format!("计算移动平均失败: {}", e))
+            .map_err(|e| format!("计算移动平均失败: {}", e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
     })
     .await
     .map_err(|e| e.to_string())??;
@@ -794,9 +1248,10 @@ pub async fn rolling_average(
         center,
         min_periods,
     };
-    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
 
-    store.push_operation(entry);
+    store.push_operation(entry).map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -810,6 +1265,7 @@ pub async fn rolling_median(
     window_size: usize,
     center: bool,
     min_periods: Option<usize>,
+    on_conflict: Option<ColumnConflictPolicy>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     if window_size < 1 {
@@ -826,8 +1282,11 @@ pub async fn rolling_median(
         store.get_current().ok_or("没有数据")?.clone()
     };
 
-    let result_df = tauri::async_runtime::spawn_blocking(move || {
-        let new_col_name = format!("{}_rolling_median_{}", column_clone, window_size);
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let new_col_name = outputs
+            .resolve(&format!("{}_rolling_median_{}", column_clone, window_size))
+            .map_err(|e| e.to_string())?;
 
         // Use rolling options
         let options = RollingOptionsFixedWindow {
@@ -838,11 +1297,12 @@ pub async fn rolling_median(
             fn_params: None,
         };
 
-        current_df
+        let df = current_df
             .lazy()
             .with_column(col(&column_clone).rolling_median(options).alias(&new_col_name))
             .collect()
-            .map_err(|e| format!("计算移动中位数失败: {}", e))
+            .map_err(|e| format!("计算移动中位数失败: {}", e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
     })
     .await
     .map_err(|e| e.to_string())??;
@@ -858,9 +1318,10 @@ pub async fn rolling_median(
         center,
         min_periods,
     };
-    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
 
-    store.push_operation(entry);
+    store.push_operation(entry).map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -874,6 +1335,7 @@ pub async fn rolling_sum(
     window_size: usize,
     center: bool,
     min_periods: Option<usize>,
+    on_conflict: Option<ColumnConflictPolicy>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     if window_size < 1 {
@@ -886,8 +1348,11 @@ pub async fn rolling_sum(
         store.get_current().ok_or("没有数据")?.clone()
     };
 
-    let result_df = tauri::async_runtime::spawn_blocking(move || {
-        let new_col_name = format!("{}_rolling_sum_{}", column_clone, window_size);
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let new_col_name = outputs
+            .resolve(&format!("{}_rolling_sum_{}", column_clone, window_size))
+            .map_err(|e| e.to_string())?;
         let options = RollingOptionsFixedWindow {
             window_size,
             min_periods: min_periods.unwrap_or(1),
@@ -896,11 +1361,12 @@ pub async fn rolling_sum(
             fn_params: None,
         };
 
-        current_df
+        let df = current_df
             .lazy()
             .with_column(col(&column_clone).rolling_sum(options).alias(&new_col_name))
             .collect()
-            .map_err(|e| format!("计算移动求和失败: {}", e))
+            .map_err(|e| format!("计算移动求和失败: {}", e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
     })
     .await
     .map_err(|e| e.to_string())??;
@@ -912,8 +1378,9 @@ pub async fn rolling_sum(
         center,
         min_periods,
     };
-    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
-    store.push_operation(entry);
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+    store.push_operation(entry).map_err(|e| e.to_string())?;
     Ok(())
 }
 
@@ -926,6 +1393,7 @@ pub async fn rolling_min(
     window_size: usize,
     center: bool,
     min_periods: Option<usize>,
+    on_conflict: Option<ColumnConflictPolicy>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     if window_size < 1 {
@@ -937,8 +1405,11 @@ pub async fn rolling_min(
         store.get_current().ok_or("没有数据")?.clone()
     };
 
-    let result_df = tauri::async_runtime::spawn_blocking(move || {
-        let new_col_name = format!("{}_rolling_min_{}", column_clone, window_size);
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let new_col_name = outputs
+            .resolve(&format!("{}_rolling_min_{}", column_clone, window_size))
+            .map_err(|e| e.to_string())?;
         let options = RollingOptionsFixedWindow {
             window_size,
             min_periods: min_periods.unwrap_or(1),
@@ -946,11 +1417,12 @@ pub async fn rolling_min(
             center,
             fn_params: None,
         };
-        current_df
+        let df = current_df
             .lazy()
             .with_column(col(&column_clone).rolling_min(options).alias(&new_col_name))
             .collect()
-            .map_err(|e| format!("计算移动最小值失败: {}", e))
+            .map_err(|e| format!("计算移动最小值失败: {}", e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
     })
     .await
     .map_err(|e| e.to_string())??;
@@ -962,8 +1434,9 @@ pub async fn rolling_min(
         center,
         min_periods,
     };
-    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
-    store.push_operation(entry);
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+    store.push_operation(entry).map_err(|e| e.to_string())?;
     Ok(())
 }
 
@@ -976,6 +1449,7 @@ pub async fn rolling_max(
     window_size: usize,
     center: bool,
     min_periods: Option<usize>,
+    on_conflict: Option<ColumnConflictPolicy>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     if window_size < 1 {
@@ -987,8 +1461,11 @@ pub async fn rolling_max(
         store.get_current().ok_or("没有数据")?.clone()
     };
 
-    let result_df = tauri::async_runtime::spawn_blocking(move || {
-        let new_col_name = format!("{}_rolling_max_{}", column_clone, window_size);
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let new_col_name = outputs
+            .resolve(&format!("{}_rolling_max_{}", column_clone, window_size))
+            .map_err(|e| e.to_string())?;
         let options = RollingOptionsFixedWindow {
             window_size,
             min_periods: min_periods.unwrap_or(1),
@@ -996,11 +1473,12 @@ pub async fn rolling_max(
             center,
             fn_params: None,
         };
-        current_df
+        let df = current_df
             .lazy()
             .with_column(col(&column_clone).rolling_max(options).alias(&new_col_name))
             .collect()
-            .map_err(|e| format!("计算移动最大值失败: {}", e))
+            .map_err(|e| format!("计算移动最大值失败: {}", e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
     })
     .await
     .map_err(|e| e.to_string())??;
@@ -1012,8 +1490,9 @@ pub async fn rolling_max(
         center,
         min_periods,
     };
-    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
-    store.push_operation(entry);
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+    store.push_operation(entry).map_err(|e| e.to_string())?;
     Ok(())
 }
 
@@ -1026,6 +1505,7 @@ pub async fn rolling_std(
     window_size: usize,
     center: bool,
     min_periods: Option<usize>,
+    on_conflict: Option<ColumnConflictPolicy>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     if window_size < 1 {
@@ -1037,8 +1517,11 @@ pub async fn rolling_std(
         store.get_current().ok_or("没有数据")?.clone()
     };
 
-    let result_df = tauri::async_runtime::spawn_blocking(move || {
-        let new_col_name = format!("{}_rolling_std_{}", column_clone, window_size);
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let new_col_name = outputs
+            .resolve(&format!("{}_rolling_std_{}", column_clone, window_size))
+            .map_err(|e| e.to_string())?;
         let options = RollingOptionsFixedWindow {
             window_size,
             min_periods: min_periods.unwrap_or(1),
@@ -1046,11 +1529,12 @@ pub async fn rolling_std(
             center,
             fn_params: None,
         };
-        current_df
+        let df = current_df
             .lazy()
             .with_column(col(&column_clone).rolling_std(options).alias(&new_col_name))
             .collect()
-            .map_err(|e| format!("计算移动标准差失败: {}", e))
+            .map_err(|e| format!("计算移动标准差失败: {}", e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
     })
     .await
     .map_err(|e| e.to_string())??;
@@ -1062,8 +1546,9 @@ pub async fn rolling_std(
         center,
         min_periods,
     };
-    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
-    store.push_operation(entry);
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+    store.push_operation(entry).map_err(|e| e.to_string())?;
     Ok(())
 }
 
@@ -1076,6 +1561,7 @@ pub async fn rolling_var(
     window_size: usize,
     center: bool,
     min_periods: Option<usize>,
+    on_conflict: Option<ColumnConflictPolicy>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     if window_size < 1 {
@@ -1087,8 +1573,11 @@ pub async fn rolling_var(
         store.get_current().ok_or("没有数据")?.clone()
     };
 
-    let result_df = tauri::async_runtime::spawn_blocking(move || {
-        let new_col_name = format!("{}_rolling_var_{}", column_clone, window_size);
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let new_col_name = outputs
+            .resolve(&format!("{}_rolling_var_{}", column_clone, window_size))
+            .map_err(|e| e.to_string())?;
         let options = RollingOptionsFixedWindow {
             window_size,
             min_periods: min_periods.unwrap_or(1),
@@ -1096,11 +1585,12 @@ pub async fn rolling_var(
             center,
             fn_params: None,
         };
-        current_df
+        let df = current_df
             .lazy()
             .with_column(col(&column_clone).rolling_var(options).alias(&new_col_name))
             .collect()
-            .map_err(|e| format!("计算移动方差失败: {}", e))
+            .map_err(|e| format!("计算移动方差失败: {}", e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
     })
     .await
     .map_err(|e| e.to_string())??;
@@ -1112,8 +1602,9 @@ pub async fn rolling_var(
         center,
         min_periods,
     };
-    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
-    store.push_operation(entry);
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+    store.push_operation(entry).map_err(|e| e.to_string())?;
     Ok(())
 }
 
@@ -1127,6 +1618,7 @@ pub async fn rolling_quantile(
     quantile: f64,
     center: bool,
     min_periods: Option<usize>,
+    on_conflict: Option<ColumnConflictPolicy>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     if window_size < 1 {
@@ -1142,8 +1634,14 @@ pub async fn rolling_quantile(
         store.get_current().ok_or("没有数据")?.clone()
     };
 
-    let result_df = tauri::async_runtime::spawn_blocking(move || {
-        let new_col_name = format!("{}_rolling_quantile_{}_{}", column_clone, window_size, quantile);
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let new_col_name = outputs
+            .resolve(&format!(
+                "{}_rolling_quantile_{}_{}",
+                column_clone, window_size, quantile
+            ))
+            .map_err(|e| e.to_string())?;
 
         let options = RollingOptionsFixedWindow {
             window_size,
@@ -1154,7 +1652,7 @@ pub async fn rolling_quantile(
         };
 
         // rolling_quantile(method, quantile, options)
-        current_df
+        let df = current_df
             .lazy()
             .with_column(
                 col(&column_clone)
@@ -1162,7 +1660,8 @@ pub async fn rolling_quantile(
                     .alias(&new_col_name),
             )
             .collect()
-            .map_err(|e| format!("计算移动分位数失败: {}", e))
+            .map_err(|e| format!("计算移动分位数失败: {}", e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
     })
     .await
     .map_err(|e| e.to_string())??;
@@ -1175,7 +1674,662 @@ pub async fn rolling_quantile(
         center,
         min_periods,
     };
-    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
-    store.push_operation(entry);
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// ============================================================================
+// 19.1 批量移动窗口计算（Rolling Batch）
+// ============================================================================
+/// 对多个列同时应用一个或多个移动窗口函数
+///
+/// 所有新列在一次 LazyFrame 计算中生成，只产生一条历史记录
+/// 新列命名与单列命令一致，例如 "sales_rolling_avg_7"
+///
+/// 参数：
+/// - columns: 要计算的列
+/// - functions: 移动窗口函数列表
+/// - window_size: 窗口大小
+/// - quantile: 分位数（使用 Quantile 函数时必填，0.0 ~ 1.0）
+/// - center: 是否居中
+/// - min_periods: 最小样本数
+/// - on_conflict: 新列与已有列同名时的处理方式（默认 error）
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn rolling_batch(
+    columns: Vec<String>,
+    functions: Vec<RollingFunction>,
+    window_size: usize,
+    quantile: Option<f64>,
+    center: bool,
+    min_periods: Option<usize>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if window_size < 1 {
+        return Err("窗口大小必须至少为 1".to_string());
+    }
+    if columns.is_empty() {
+        return Err("至少需要选择一列".to_string());
+    }
+    if functions.is_empty() {
+        return Err("至少需要选择一个移动窗口函数".to_string());
+    }
+    if functions.contains(&RollingFunction::Quantile) {
+        match quantile {
+            Some(q) if (0.0..=1.0).contains(&q) => {}
+            Some(_) => return Err("分位数必须在 0.0 到 1.0 之间".to_string()),
+            None => return Err("移动分位数需要指定 quantile".to_string()),
+        }
+    }
+
+    let columns_clone = columns.clone();
+    let functions_clone = functions.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let options = RollingOptionsFixedWindow {
+            window_size,
+            min_periods: min_periods.unwrap_or(1),
+            weights: None,
+            center,
+            fn_params: None,
+        };
+
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let mut exprs = Vec::with_capacity(columns_clone.len() * functions_clone.len());
+        for column in &columns_clone {
+            if current_df.get_column_index(column).is_none() {
+                return Err(format!("列 '{}' 不存在", column));
+            }
+            for function in &functions_clone {
+                let name = outputs
+                    .resolve(&rolling_column_name(column, *function, window_size, quantile))
+                    .map_err(|e| e.to_string())?;
+                exprs.push(rolling_expr(column, *function, quantile, options.clone()).alias(name));
+            }
+        }
+
+        let df = current_df
+            .lazy()
+            .with_columns(exprs)
+            .collect()
+            .map_err(|e| format!("批量移动计算失败: {}", e))?;
+        Ok((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::RollingBatch {
+        columns,
+        functions,
+        window_size,
+        quantile,
+        center,
+        min_periods,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// 单个列 + 单个函数的移动窗口新列名（与单列命令一致）
+fn rolling_column_name(column: &str, function: RollingFunction, window_size: usize, quantile: Option<f64>) -> String {
+    match function {
+        RollingFunction::Quantile => format!(
+            "{}_rolling_quantile_{}_{}",
+            column,
+            window_size,
+            quantile.unwrap_or(0.5)
+        ),
+        _ => format!("{}_rolling_{}_{}", column, function.suffix(), window_size),
+    }
+}
+
+/// 构建单个列 + 单个函数的移动窗口表达式（不含别名）
+fn rolling_expr(
+    column: &str,
+    function: RollingFunction,
+    quantile: Option<f64>,
+    options: RollingOptionsFixedWindow,
+) -> Expr {
+    let base = col(column);
+    match function {
+        RollingFunction::Quantile => base.rolling_quantile(QuantileMethod::Linear, quantile.unwrap_or(0.5), options),
+        RollingFunction::Mean => base.rolling_mean(options),
+        RollingFunction::Median => base.rolling_median(options),
+        RollingFunction::Sum => base.rolling_sum(options),
+        RollingFunction::Min => base.rolling_min(options),
+        RollingFunction::Max => base.rolling_max(options),
+        RollingFunction::Std => base.rolling_std(options),
+        RollingFunction::Var => base.rolling_var(options),
+    }
+}
+
+// ============================================================================
+// 20. 设置列显示格式（Set Column Format）
+// ============================================================================
+/// 设置或清除列的显示格式
+///
+/// 只修改显示设置（千分位、小数位、日期格式、百分比），不改变数据类型
+/// 会产生新的历史条目，因此可以撤销
+///
+/// 参数：
+/// - column: 列名
+/// - format: 显示格式（None 表示清除该列的格式）
+#[tauri::command]
+pub async fn set_column_format(
+    column: String,
+    format: Option<ColumnFormat>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if let Some(date_format) = format.as_ref().and_then(|f| f.date_format.as_ref()) {
+        validate_date_format(date_format).map_err(|e| e.to_string())?;
+    }
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let (current_df, mut display) = {
+        let entry = store.get_current_entry().ok_or("没有数据")?;
+        (entry.dataframe.clone(), entry.display.clone())
+    };
+
+    if current_df.get_column_index(&column).is_none() {
+        return Err(format!("列 '{}' 不存在", column));
+    }
+
+    if let Some(f) = &format {
+        display.column_formats.insert(column.clone(), f.clone());
+    } else {
+        display.column_formats.remove(&column);
+    }
+
+    let operation = OperationType::SetColumnFormat { column, format };
+    let mut entry = create_history_entry(current_df, operation).map_err(|e| e.to_string())?;
+    entry.display = display;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 21. 设置条件格式（Set Conditional Formats）
+// ============================================================================
+/// 整体替换条件格式规则
+///
+/// 规则在获取分页数据时由后端计算，结果以单元格样式标签的形式返回
+/// 会产生新的历史条目，因此可以撤销
+///
+/// 参数：
+/// - rules: 条件格式规则列表（按顺序计算；为空表示清除所有条件格式）
+#[tauri::command]
+pub async fn set_conditional_formats(
+    rules: Vec<ConditionalFormatRule>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let (current_df, mut display) = {
+        let entry = store.get_current_entry().ok_or("没有数据")?;
+        (entry.dataframe.clone(), entry.display.clone())
+    };
+
+    validate_conditional_rules(&current_df, &rules).map_err(|e| e.to_string())?;
+
+    display.conditional_formats = rules.clone();
+
+    let operation = OperationType::SetConditionalFormats { rules };
+    let mut entry = create_history_entry(current_df, operation).map_err(|e| e.to_string())?;
+    entry.display = display;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 22. 删除重复行（Drop Duplicates）
+// ============================================================================
+/// 删除重复行，保持原有行顺序
+///
+/// normalize_keys 为 true 时，字符串键在比较前忽略大小写并去掉重音
+/// （"José" 与 "jose" 视为相同），输出中保留原始值
+///
+/// 参数：
+/// - subset: 判断重复的列（None 或空表示所有列）
+/// - keep: 保留策略（默认保留首行）
+/// - normalize_keys: 是否规范化字符串键（默认 false）
+/// - force: 删除的行数超过行数保护阈值时仍然执行（默认 false，否则返回预计影响等待确认）
+#[tauri::command]
+pub async fn drop_duplicates(
+    subset: Option<Vec<String>>,
+    keep: Option<DuplicateKeep>,
+    normalize_keys: Option<bool>,
+    force: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<OperationOutcome, String> {
+    let subset = subset.unwrap_or_default();
+    let keep = keep.unwrap_or_default();
+    let normalize_keys = normalize_keys.unwrap_or(false);
+    let subset_clone = subset.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+    let rows_before = current_df.height();
+
+    let result_df = tauri::async_runtime::spawn_blocking(move || {
+        drop_duplicate_rows(&current_df, &subset_clone, keep, normalize_keys)
+            .map_err(|e| format!("删除重复行失败: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    if let Some(outcome) = guard_row_removal(&app, rows_before, result_df.height(), force.unwrap_or(false))? {
+        return Ok(outcome);
+    }
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::DropDuplicates {
+        subset,
+        keep,
+        normalize_keys,
+    };
+    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(OperationOutcome::applied())
+}
+
+// ============================================================================
+// 22.1 连接文件（Join File）
+// ============================================================================
+/// 按键把另一个文件的列连接到当前数据集（保持当前数据集的行顺序）
+///
+/// normalize_keys 为 true 时，字符串键在匹配前忽略大小写并去掉重音
+/// （"José" 与 "jose" 匹配），输出中保留当前数据集键列的原始值
+///
+/// 参数：
+/// - file_path: 右表文件路径（按扩展名选择读取方式，与 reload_from_source 相同）
+/// - left_on: 当前数据集的键列
+/// - right_on: 右表的键列（可选，默认与 left_on 相同）
+/// - how: 连接方式（默认左连接）
+/// - normalize_keys: 是否规范化字符串键（默认 false）
+/// - on_conflict: 右表中与当前数据集重名的列的处理方式（默认 error；overwrite 不能覆盖键列）
+///
+/// 前端调用示例：
+/// ```typescript
+/// await invoke('join_file', {
+///   filePath: '/data/customers.csv',
+///   leftOn: ['customer_name'],
+///   rightOn: ['name'],
+///   normalizeKeys: true
+/// });
+/// ```
+#[tauri::command]
+pub async fn join_file(
+    file_path: String,
+    left_on: Vec<String>,
+    right_on: Option<Vec<String>>,
+    how: Option<JoinHow>,
+    normalize_keys: Option<bool>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let right_on = right_on.unwrap_or_else(|| left_on.clone());
+    let how = how.unwrap_or_default();
+    let normalize_keys = normalize_keys.unwrap_or(false);
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (path, left_clone, right_clone) = (file_path.clone(), left_on.clone(), right_on.clone());
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let right_df = load_local_source(&path, None).map_err(|e| format!("读取文件 {} 失败: {}", path, e))?;
+        join_on_keys(
+            &current_df,
+            &right_df,
+            &left_clone,
+            &right_clone,
+            how,
+            normalize_keys,
+            on_conflict.unwrap_or_default(),
+        )
+        .map_err(|e| format!("连接失败: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::JoinFile {
+        file_path,
+        left_on,
+        right_on,
+        how,
+        normalize_keys,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 23. 展开列表列（Explode List）
+// ============================================================================
+/// 展开列表列：每个元素占一行，其他列的值随之复制
+///
+/// 空列表和空值各保留一行（值为空）
+///
+/// 参数：
+/// - columns: 要展开的列表列（多列时同一行的列表长度必须相同）
+#[tauri::command]
+pub async fn explode_list(columns: Vec<String>, state: State<'_, AppState>) -> Result<(), String> {
+    if columns.is_empty() {
+        return Err("请至少选择一个列表列".to_string());
+    }
+    let columns_clone = columns.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let result_df = tauri::async_runtime::spawn_blocking(move || {
+        for column in &columns_clone {
+            list_column(&current_df, column).map_err(|e| e.to_string())?;
+        }
+
+        current_df
+            .explode(columns_clone.iter().map(|c| c.as_str()))
+            .map_err(|e| format!("展开列表失败: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::ExplodeList { columns };
+    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 24. 拼接列表列（Join List）
+// ============================================================================
+/// 将列表列的元素转为文本并用分隔符拼接
+///
+/// 空值元素被跳过，空列表得到空字符串，列表本身为空值时结果为空值
+///
+/// 参数：
+/// - column: 列表列
+/// - separator: 分隔符（默认 ", "）
+/// - output_column: 输出列（None 表示覆盖原列）
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error；覆盖原列时不检查）
+#[tauri::command]
+pub async fn join_list(
+    column: String,
+    separator: Option<String>,
+    output_column: Option<String>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let separator = separator.unwrap_or_else(|| ", ".to_string());
+    let column_clone = column.clone();
+    let separator_clone = separator.clone();
+    let target = output_column.clone().unwrap_or_else(|| column.clone());
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let target = if target == column_clone {
+            target
+        } else {
+            outputs.resolve(&target).map_err(|e| e.to_string())?
+        };
+
+        let lists = list_column(&current_df, &column_clone).map_err(|e| e.to_string())?;
+        let joined = join_list_values(&lists, &separator_clone).map_err(|e| format!("拼接列表失败: {}", e))?;
+
+        let mut df = current_df;
+        df.with_column(joined.with_name(target.as_str().into()).into_series())
+            .map_err(|e| format!("添加列 {} 失败: {}", target, e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::JoinList {
+        column,
+        separator,
+        output_column,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 25. 展开结构体列（Unnest Struct）
+// ============================================================================
+/// 将结构体列的字段展开为顶层列（插入在原列的位置，原列被移除）
+///
+/// 参数：
+/// - column: 结构体列
+/// - separator: 新列名前缀分隔符（可选，例如 "." 得到 "address.city"；不指定时直接使用字段名）
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error）
+#[tauri::command]
+pub async fn unnest_struct(
+    column: String,
+    separator: Option<String>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let column_clone = column.clone();
+    let separator_clone = separator.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let df = unnest_struct_column(&current_df, &column_clone, separator_clone.as_deref(), &mut outputs)
+            .map_err(|e| format!("展开结构体失败: {}", e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::UnnestStruct { column, separator };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 26. 设置表格布局（Set View State）
+// ============================================================================
+/// 整体替换表格布局（隐藏列、固定列、列宽）
+///
+/// 布局随历史条目一起保存，会产生新的历史条目，因此可以撤销
+/// 数据操作会继承布局，并自动丢弃已不存在的列
+///
+/// 参数：
+/// - view: 表格布局
+#[tauri::command]
+pub async fn set_view_state(view: ViewState, state: State<'_, AppState>) -> Result<(), String> {
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let (current_df, mut display) = {
+        let entry = store.get_current_entry().ok_or("没有数据")?;
+        (entry.dataframe.clone(), entry.display.clone())
+    };
+
+    if let Some(name) = view
+        .referenced_columns()
+        .find(|name| current_df.get_column_index(name).is_none())
+    {
+        return Err(format!("列 '{}' 不存在", name));
+    }
+    if let Some((name, _)) = view.column_widths.iter().find(|(_, width)| **width == 0) {
+        return Err(format!("列 '{}' 的宽度必须大于 0", name));
+    }
+
+    display.view_state = view.clone();
+
+    let operation = OperationType::SetViewState { view };
+    let mut entry = create_history_entry(current_df, operation).map_err(|e| e.to_string())?;
+    entry.display = display;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 27. 设置数值精度（Set Float Precision）
+// ============================================================================
+/// 整体替换浮点列的显示精度（全局舍入方式、按列覆盖、NaN / 无穷大的处理方式）
+///
+/// 只影响表格中显示的数值，避免 0.30000000000000004 这样的浮点噪声，不改变底层数据
+/// 需要真正替换 NaN / 无穷大时使用 replace_non_finite
+/// 设置随历史条目一起保存，会产生新的历史条目，因此可以撤销
+///
+/// 参数：
+/// - precision: 显示精度（默认值表示完整精度）
+///
+/// 前端调用示例：
+/// ```typescript
+/// await invoke('set_float_precision', {
+///   precision: {
+///     default: { type: 'significant_digits', digits: 12 },
+///     columns: { price: { type: 'decimal_places', places: 2 } },
+///     non_finite: 'text'
+///   }
+/// });
+/// ```
+#[tauri::command]
+pub async fn set_float_precision(precision: FloatPrecision, state: State<'_, AppState>) -> Result<(), String> {
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let (current_df, mut display) = {
+        let entry = store.get_current_entry().ok_or("没有数据")?;
+        (entry.dataframe.clone(), entry.display.clone())
+    };
+
+    if let Some(name) = precision
+        .columns
+        .keys()
+        .find(|name| current_df.get_column_index(name).is_none())
+    {
+        return Err(format!("列 '{}' 不存在", name));
+    }
+    for rounding in precision.default.iter().chain(precision.columns.values()) {
+        match rounding {
+            FloatRounding::SignificantDigits { digits } if !(1..=17).contains(digits) => {
+                return Err("有效数字位数必须在 1 到 17 之间".to_string());
+            }
+            FloatRounding::DecimalPlaces { places } if *places > 17 => {
+                return Err("小数位数不能超过 17".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    display.float_precision = precision.clone();
+
+    let operation = OperationType::SetFloatPrecision { precision };
+    let mut entry = create_history_entry(current_df, operation).map_err(|e| e.to_string())?;
+    entry.display = display;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
     Ok(())
 }
@@ -0,0 +1,287 @@
+// ============================================================================
+// commands/schedule.rs - 计划任务命令
+// ============================================================================
+// 这个文件实现了计划任务相关的 Tauri 命令：
+// - list_schedules: 列出计划任务及其运行状态
+// - save_schedule: 新建或更新计划任务
+// - delete_schedule: 删除计划任务
+// - run_schedule_now: 立即运行一次计划任务
+//
+// 以及应用启动后在后台运行的调度循环（run_scheduler）：
+// 每隔 SCHEDULER_TICK 读取 schedules.json，运行到期的任务：
+// 重新读取源文件 → 替换参数并检查兼容性 → 执行操作记录 → 导出（覆盖目标文件）
+//
+// 计划任务在独立的数据上运行，不影响当前打开的数据集和历史
+// 每次运行结束推送 "schedule://run" 事件（载荷为 ScheduledRunResult），前端可以显示成功 / 失败通知
+
+use crate::AppState;
+use crate::commands::file_import::load_local_source;
+use crate::data::export::write_atomic;
+use crate::data::recipe::{check_recipe_compatibility, replay_recipe, resolve_recipe_parameters};
+use crate::data::scheduler::{
+    SCHEDULER_TICK, SCHEDULES_FILE_NAME, load_schedules, next_run, save_schedules, validate_job,
+};
+use crate::models::{
+    ImportOptions, OperationType, ScheduledExportFormat, ScheduledJob, ScheduledJobInfo, ScheduledRunResult,
+};
+use chrono::Local;
+use polars::prelude::*;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager, State};
+use uuid::Uuid;
+
+/// 计划任务运行结束事件名称
+pub const SCHEDULE_RUN_EVENT: &str = "schedule://run";
+
+/// 计划任务文件路径
+pub(crate) fn schedules_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("无法获取应用配置目录: {}", e))?;
+    Ok(dir.join(SCHEDULES_FILE_NAME))
+}
+
+// ============================================================================
+// 1. 列出计划任务
+// ============================================================================
+/// 列出所有计划任务，以及下次运行时间、是否正在运行和上次运行结果
+///
+/// 上次运行结果只保存在内存中，应用重启后为空
+#[tauri::command]
+pub async fn list_schedules(app: AppHandle, state: State<'_, AppState>) -> Result<Vec<ScheduledJobInfo>, String> {
+    let path = schedules_path(&app)?;
+    let jobs = load_schedules(&path).map_err(|e| e.to_string())?;
+    let now = Local::now();
+
+    Ok(jobs
+        .into_iter()
+        .map(|job| {
+            let next = if job.enabled {
+                state
+                    .scheduler
+                    .next_due(&job.id)
+                    .or_else(|| next_run(&job.schedule, now))
+            } else {
+                None
+            };
+            ScheduledJobInfo {
+                next_run: next.map(|at| at.to_rfc3339()),
+                running: state.scheduler.is_running(&job.id),
+                last_run: state.scheduler.last_run(&job.id),
+                job,
+            }
+        })
+        .collect())
+}
+
+// ============================================================================
+// 2. 保存计划任务
+// ============================================================================
+/// 新建或更新计划任务（按 ID 覆盖；ID 为空时生成新 ID）
+///
+/// 保存前检查运行时间格式、操作记录中的步骤都能重新执行、参数声明有效
+/// 源文件此时不需要存在（例如每天由其他程序生成）
+///
+/// 操作记录只能包含以下操作：选择 / 删除 / 重命名列、类型转换、按模式转换类型、排序、筛选、
+/// 删除空值行、删除重复行、替换 NaN / 无穷大；只修改显示设置的步骤会被跳过
+/// 填充空值、生成新列（滚动计算、日期计算、解析等）等其他操作不支持，保存时返回错误并列出这些步骤
+///
+/// 参数：
+/// - job: 计划任务
+///
+/// 返回：
+/// - Result<ScheduledJob, String>: 保存后的任务（包含生成的 ID）
+///
+/// 前端调用示例：
+/// ```typescript
+/// await invoke('save_schedule', {
+///   job: {
+///     name: '每日销售汇总',
+///     source_path: '/data/daily_sales.csv',
+///     recipe: contents.recipe,
+///     output_path: '/data/out/daily_sales.parquet',
+///     format: 'parquet',
+///     schedule: { kind: 'daily', time: '07:00' }
+///   }
+/// });
+/// ```
+#[tauri::command]
+pub async fn save_schedule(mut job: ScheduledJob, app: AppHandle) -> Result<ScheduledJob, String> {
+    validate_job(&job).map_err(|e| e.to_string())?;
+    if job.id.trim().is_empty() {
+        job.id = Uuid::new_v4().to_string();
+    }
+
+    let path = schedules_path(&app)?;
+    let mut jobs = load_schedules(&path).map_err(|e| e.to_string())?;
+    match jobs.iter_mut().find(|existing| existing.id == job.id) {
+        Some(existing) => *existing = job.clone(),
+        None => jobs.push(job.clone()),
+    }
+
+    save_schedules(&path, &jobs).map_err(|e| format!("保存计划任务失败: {}", e))?;
+    Ok(job)
+}
+
+// ============================================================================
+// 3. 删除计划任务
+// ============================================================================
+/// 删除计划任务（正在进行的运行会继续完成）
+///
+/// 参数：
+/// - id: 任务 ID
+#[tauri::command]
+pub async fn delete_schedule(id: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let path = schedules_path(&app)?;
+    let mut jobs = load_schedules(&path).map_err(|e| e.to_string())?;
+    let before = jobs.len();
+    jobs.retain(|job| job.id != id);
+    if jobs.len() == before {
+        return Err(format!("计划任务 '{}' 不存在", id));
+    }
+
+    save_schedules(&path, &jobs).map_err(|e| format!("保存计划任务失败: {}", e))?;
+    state.scheduler.forget(&id);
+    Ok(())
+}
+
+// ============================================================================
+// 4. 立即运行
+// ============================================================================
+/// 立即运行一次计划任务（不改变下次运行时间，未启用的任务也可以运行）
+///
+/// 参数：
+/// - id: 任务 ID
+///
+/// 返回：
+/// - Result<ScheduledRunResult, String>: 运行结果（运行失败时 success 为 false，不返回错误）；
+///   任务不存在或正在运行时返回错误
+#[tauri::command]
+pub async fn run_schedule_now(id: String, app: AppHandle) -> Result<ScheduledRunResult, String> {
+    let path = schedules_path(&app)?;
+    let job = load_schedules(&path)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|job| job.id == id)
+        .ok_or_else(|| format!("计划任务 '{}' 不存在", id))?;
+
+    execute_job(&app, job).await
+}
+
+// ============================================================================
+// 调度循环
+// ============================================================================
+/// 后台调度循环（应用启动时开始，直到应用退出）
+///
+/// 每次检查都重新读取 schedules.json，保存或删除任务后不需要通知调度循环
+pub(crate) async fn run_scheduler(app: AppHandle) {
+    let mut interval = tokio::time::interval(SCHEDULER_TICK);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        // 配置目录不可用或文件格式错误时跳过这次检查（保存任务时会报告错误）
+        let Ok(jobs) = schedules_path(&app).and_then(|path| load_schedules(&path).map_err(|e| e.to_string())) else {
+            continue;
+        };
+
+        let scheduler = app.state::<AppState>().scheduler.clone();
+        for job in scheduler.take_due(&jobs, Local::now()) {
+            let app = app.clone();
+            // 上一次运行还没有结束时跳过这次运行
+            tauri::async_runtime::spawn(async move {
+                let _ = execute_job(&app, job).await;
+            });
+        }
+    }
+}
+
+/// 运行一次任务，记录结果并推送 "schedule://run" 事件
+async fn execute_job(app: &AppHandle, job: ScheduledJob) -> Result<ScheduledRunResult, String> {
+    let scheduler = app.state::<AppState>().scheduler.clone();
+    if !scheduler.begin_run(&job.id) {
+        return Err(format!("计划任务 '{}' 正在运行", job.name));
+    }
+
+    let started_at = chrono::Utc::now().to_rfc3339();
+    let (job_id, job_name, output_path) = (job.id.clone(), job.name.clone(), job.output_path.clone());
+    let outcome = tauri::async_runtime::spawn_blocking(move || run_job(&job))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|result| result);
+
+    let result = ScheduledRunResult {
+        job_id,
+        job_name,
+        success: outcome.is_ok(),
+        started_at,
+        finished_at: chrono::Utc::now().to_rfc3339(),
+        rows: outcome.as_ref().ok().copied(),
+        output_path,
+        error: outcome.err(),
+    };
+
+    scheduler.finish_run(result.clone());
+    let _ = app.emit(SCHEDULE_RUN_EVENT, &result);
+    Ok(result)
+}
+
+/// 重新读取源文件 → 替换参数并检查兼容性 → 执行操作记录 → 导出
+///
+/// 返回：
+/// - Result<usize, String>: 导出的行数
+fn run_job(job: &ScheduledJob) -> Result<usize, String> {
+    if !Path::new(&job.source_path).exists() {
+        return Err(format!("源文件不存在: {}", job.source_path));
+    }
+    // 没有指定 CSV 选项时沿用操作记录中导入步骤的选项（Excel 工作表等）
+    let options = match &job.csv_options {
+        Some(options) => Some(ImportOptions::Csv {
+            options: options.clone(),
+        }),
+        None => job.recipe.first().and_then(|step| match &step.operation {
+            OperationType::Import { options, .. } => options.clone(),
+            _ => None,
+        }),
+    };
+    let df = load_local_source(&job.source_path, options.as_ref())
+        .map_err(|e| format!("读取源文件 {} 失败: {}", job.source_path, e))?;
+
+    let mut df = if job.recipe.is_empty() {
+        df
+    } else {
+        let recipe = resolve_recipe_parameters(&job.recipe, &job.parameters, &job.values).map_err(|e| e.to_string())?;
+        let report = check_recipe_compatibility(&recipe, &df).map_err(|e| e.to_string())?;
+        if !report.compatible {
+            let columns: Vec<&str> = report
+                .missing_columns
+                .iter()
+                .chain(&report.type_mismatches)
+                .filter(|issue| !issue.used_by_steps.is_empty())
+                .map(|issue| issue.column.as_str())
+                .collect();
+            return Err(format!(
+                "源文件与操作记录不兼容（缺失或类型不一致的列: {}）",
+                columns.join(", ")
+            ));
+        }
+        replay_recipe(df, &recipe).map_err(|e| e.to_string())?
+    };
+
+    write_atomic(&job.output_path, |file| {
+        match job.format {
+            ScheduledExportFormat::Parquet => {
+                ParquetWriter::new(file).finish(&mut df)?;
+            }
+            ScheduledExportFormat::Csv => {
+                CsvWriter::new(file).include_header(true).finish(&mut df)?;
+            }
+        }
+        Ok(())
+    })
+    .map_err(|e| format!("导出到 {} 失败: {}", job.output_path, e))?;
+
+    Ok(df.height())
+}
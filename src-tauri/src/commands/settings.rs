@@ -0,0 +1,338 @@
+// ============================================================================
+// commands/settings.rs - 设置命令
+// ============================================================================
+// 这个文件实现了应用设置相关的 Tauri 命令：
+// - list_export_profiles: 列出 CSV 导出预设（内置 + 用户）
+// - save_export_profile: 新建或更新用户预设
+// - delete_export_profile: 删除用户预设
+// - get_chart_defaults: 读取图表默认设置
+// - save_chart_defaults: 更新图表默认设置
+// - get_row_guardrails: 读取行数保护设置
+// - save_row_guardrails: 更新行数保护设置
+// - get_row_ordering: 读取行顺序设置
+// - save_row_ordering: 更新行顺序设置（稳定顺序）
+// - list_cast_profiles: 列出类型转换预设
+// - save_cast_profile: 新建或更新类型转换预设
+// - delete_cast_profile: 删除类型转换预设
+// - get_column_summaries: 读取列摘要设置
+// - save_column_summaries: 更新列摘要设置
+//
+// 用户预设、图表默认设置、行数保护设置、行顺序设置、类型转换预设和列摘要设置保存在应用配置目录中，
+// 不随数据集或历史变化
+
+use crate::data::cast_profiles::{
+    CAST_PROFILES_FILE_NAME, load_cast_profiles, save_cast_profiles, validate_cast_profile,
+};
+use crate::data::chart_defaults::{
+    CHART_DEFAULTS_FILE_NAME, load_chart_defaults, save_chart_defaults as write_chart_defaults, validate_chart_defaults,
+};
+use crate::data::column_summary::{
+    COLUMN_SUMMARIES_FILE_NAME, load_column_summary_settings, save_column_summary_settings,
+};
+use crate::data::export_profiles::{
+    PROFILES_FILE_NAME, all_profiles, is_builtin_profile, load_user_profiles, save_user_profiles, validate_profile,
+};
+use crate::data::guardrails::{
+    ROW_GUARDRAILS_FILE_NAME, load_row_guardrails, save_row_guardrails as write_row_guardrails, validate_row_guardrails,
+};
+use crate::data::ordering::{ROW_ORDERING_FILE_NAME, load_row_ordering, save_row_ordering as write_row_ordering};
+use crate::models::chart::ChartDefaults;
+use crate::models::{CastProfile, ColumnSummarySettings, CsvExportProfile, RowGuardrails, RowOrdering};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// 用户导出预设文件路径
+pub(crate) fn export_profiles_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("无法获取应用配置目录: {}", e))?;
+    Ok(dir.join(PROFILES_FILE_NAME))
+}
+
+/// 图表默认设置文件路径
+pub(crate) fn chart_defaults_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("无法获取应用配置目录: {}", e))?;
+    Ok(dir.join(CHART_DEFAULTS_FILE_NAME))
+}
+
+/// 读取图表默认设置（供图表命令使用）
+pub(crate) fn current_chart_defaults(app: &AppHandle) -> Result<ChartDefaults, String> {
+    let path = chart_defaults_path(app)?;
+    load_chart_defaults(&path).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// 1. 列出导出预设
+// ============================================================================
+/// 列出所有 CSV 导出预设（内置预设在前，builtin 为 true）
+#[tauri::command]
+pub async fn list_export_profiles(app: AppHandle) -> Result<Vec<CsvExportProfile>, String> {
+    let path = export_profiles_path(&app)?;
+    all_profiles(&path).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// 2. 保存导出预设
+// ============================================================================
+/// 新建或更新用户导出预设（按名称匹配，同名预设会被覆盖）
+///
+/// 内置预设不能修改，可以用新名称另存一份
+///
+/// 参数：
+/// - profile: 导出预设
+#[tauri::command]
+pub async fn save_export_profile(profile: CsvExportProfile, app: AppHandle) -> Result<(), String> {
+    let profile = CsvExportProfile {
+        name: profile.name.trim().to_string(),
+        builtin: false,
+        ..profile
+    };
+
+    if is_builtin_profile(&profile.name) {
+        return Err(format!("内置预设 '{}' 不能修改，请使用其他名称另存", profile.name));
+    }
+    validate_profile(&profile).map_err(|e| e.to_string())?;
+
+    let path = export_profiles_path(&app)?;
+    let mut profiles = load_user_profiles(&path).map_err(|e| e.to_string())?;
+    match profiles.iter_mut().find(|p| p.name == profile.name) {
+        Some(existing) => *existing = profile,
+        None => profiles.push(profile),
+    }
+
+    save_user_profiles(&path, &profiles).map_err(|e| format!("保存导出预设失败: {}", e))
+}
+
+// ============================================================================
+// 3. 删除导出预设
+// ============================================================================
+/// 删除用户导出预设
+///
+/// 参数：
+/// - name: 预设名称
+#[tauri::command]
+pub async fn delete_export_profile(name: String, app: AppHandle) -> Result<(), String> {
+    if is_builtin_profile(&name) {
+        return Err(format!("内置预设 '{}' 不能删除", name));
+    }
+
+    let path = export_profiles_path(&app)?;
+    let mut profiles = load_user_profiles(&path).map_err(|e| e.to_string())?;
+    let before = profiles.len();
+    profiles.retain(|p| p.name != name);
+    if profiles.len() == before {
+        return Err(format!("导出预设 '{}' 不存在", name));
+    }
+
+    save_user_profiles(&path, &profiles).map_err(|e| format!("保存导出预设失败: {}", e))
+}
+
+/// 行数保护设置文件路径
+pub(crate) fn row_guardrails_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("无法获取应用配置目录: {}", e))?;
+    Ok(dir.join(ROW_GUARDRAILS_FILE_NAME))
+}
+
+/// 读取行数保护设置（供删除行的操作使用）
+pub(crate) fn current_row_guardrails(app: &AppHandle) -> Result<RowGuardrails, String> {
+    let path = row_guardrails_path(app)?;
+    load_row_guardrails(&path).map_err(|e| e.to_string())
+}
+
+/// 行顺序设置文件路径
+pub(crate) fn row_ordering_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("无法获取应用配置目录: {}", e))?;
+    Ok(dir.join(ROW_ORDERING_FILE_NAME))
+}
+
+/// 是否启用稳定顺序（供可能改变行顺序的操作使用）
+pub(crate) fn stable_ordering(app: &AppHandle) -> Result<bool, String> {
+    let path = row_ordering_path(app)?;
+    load_row_ordering(&path)
+        .map(|ordering| ordering.stable)
+        .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// 4. 图表默认设置
+// ============================================================================
+/// 读取图表默认设置（没有保存过时返回内置默认值）
+#[tauri::command]
+pub async fn get_chart_defaults(app: AppHandle) -> Result<ChartDefaults, String> {
+    current_chart_defaults(&app)
+}
+
+/// 更新图表默认设置，返回保存后的设置
+///
+/// 之后生成的图表中未指定的选项（直方图分箱数量、日期分箱粒度、分面数量）使用新的默认值
+///
+/// 参数：
+/// - defaults: 图表默认设置（缺少的字段使用内置默认值）
+#[tauri::command]
+pub async fn save_chart_defaults(defaults: ChartDefaults, app: AppHandle) -> Result<ChartDefaults, String> {
+    validate_chart_defaults(&defaults).map_err(|e| e.to_string())?;
+
+    let path = chart_defaults_path(&app)?;
+    write_chart_defaults(&path, &defaults).map_err(|e| format!("保存图表默认设置失败: {}", e))?;
+    Ok(defaults)
+}
+
+// ============================================================================
+// 5. 行数保护设置
+// ============================================================================
+/// 读取行数保护设置（没有保存过时返回内置默认值）
+#[tauri::command]
+pub async fn get_row_guardrails(app: AppHandle) -> Result<RowGuardrails, String> {
+    current_row_guardrails(&app)
+}
+
+/// 更新行数保护设置，返回保存后的设置
+///
+/// 之后的删除空值行、筛选、去重操作预计删除的行数超过阈值时需要确认（带 force = true 重新调用）
+///
+/// 参数：
+/// - guardrails: 行数保护设置（缺少的字段使用内置默认值）
+#[tauri::command]
+pub async fn save_row_guardrails(guardrails: RowGuardrails, app: AppHandle) -> Result<RowGuardrails, String> {
+    validate_row_guardrails(&guardrails).map_err(|e| e.to_string())?;
+
+    let path = row_guardrails_path(&app)?;
+    write_row_guardrails(&path, &guardrails).map_err(|e| format!("保存行数保护设置失败: {}", e))?;
+    Ok(guardrails)
+}
+
+// ============================================================================
+// 6. 行顺序设置
+// ============================================================================
+/// 读取行顺序设置（没有保存过时不启用稳定顺序）
+#[tauri::command]
+pub async fn get_row_ordering(app: AppHandle) -> Result<RowOrdering, String> {
+    let path = row_ordering_path(&app)?;
+    load_row_ordering(&path).map_err(|e| e.to_string())
+}
+
+/// 更新行顺序设置，返回保存后的设置
+///
+/// 启用稳定顺序后，之后的透视、排序、纵表转换保持原始行的相对顺序，
+/// 同样的数据和操作总是得到同样顺序的结果（便于与其他工具逐行比较）
+///
+/// 参数：
+/// - ordering: 行顺序设置（缺少的字段使用内置默认值）
+#[tauri::command]
+pub async fn save_row_ordering(ordering: RowOrdering, app: AppHandle) -> Result<RowOrdering, String> {
+    let path = row_ordering_path(&app)?;
+    write_row_ordering(&path, &ordering).map_err(|e| format!("保存行顺序设置失败: {}", e))?;
+    Ok(ordering)
+}
+
+/// 类型转换预设文件路径
+pub(crate) fn cast_profiles_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("无法获取应用配置目录: {}", e))?;
+    Ok(dir.join(CAST_PROFILES_FILE_NAME))
+}
+
+// ============================================================================
+// 7. 类型转换预设
+// ============================================================================
+/// 列出所有类型转换预设
+#[tauri::command]
+pub async fn list_cast_profiles(app: AppHandle) -> Result<Vec<CastProfile>, String> {
+    let path = cast_profiles_path(&app)?;
+    load_cast_profiles(&path).map_err(|e| e.to_string())
+}
+
+/// 新建或更新类型转换预设（按名称匹配，同名预设会被覆盖）
+///
+/// 参数：
+/// - profile: 转换预设
+#[tauri::command]
+pub async fn save_cast_profile(profile: CastProfile, app: AppHandle) -> Result<(), String> {
+    let profile = CastProfile {
+        name: profile.name.trim().to_string(),
+        ..profile
+    };
+    validate_cast_profile(&profile).map_err(|e| e.to_string())?;
+
+    let path = cast_profiles_path(&app)?;
+    let mut profiles = load_cast_profiles(&path).map_err(|e| e.to_string())?;
+    match profiles.iter_mut().find(|p| p.name == profile.name) {
+        Some(existing) => *existing = profile,
+        None => profiles.push(profile),
+    }
+
+    save_cast_profiles(&path, &profiles).map_err(|e| format!("保存转换预设失败: {}", e))
+}
+
+/// 删除类型转换预设
+///
+/// 参数：
+/// - name: 预设名称
+#[tauri::command]
+pub async fn delete_cast_profile(name: String, app: AppHandle) -> Result<(), String> {
+    let path = cast_profiles_path(&app)?;
+    let mut profiles = load_cast_profiles(&path).map_err(|e| e.to_string())?;
+    let before = profiles.len();
+    profiles.retain(|p| p.name != name);
+    if profiles.len() == before {
+        return Err(format!("转换预设 '{}' 不存在", name));
+    }
+
+    save_cast_profiles(&path, &profiles).map_err(|e| format!("保存转换预设失败: {}", e))
+}
+
+/// 列摘要设置文件路径
+pub(crate) fn column_summaries_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("无法获取应用配置目录: {}", e))?;
+    Ok(dir.join(COLUMN_SUMMARIES_FILE_NAME))
+}
+
+/// 是否计算列摘要（供返回元信息的命令使用）
+pub(crate) fn column_summaries_enabled(app: &AppHandle) -> Result<bool, String> {
+    let path = column_summaries_path(app)?;
+    load_column_summary_settings(&path)
+        .map(|settings| settings.enabled)
+        .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// 8. 列摘要设置
+// ============================================================================
+/// 读取列摘要设置（没有保存过时不计算列摘要）
+#[tauri::command]
+pub async fn get_column_summaries(app: AppHandle) -> Result<ColumnSummarySettings, String> {
+    let path = column_summaries_path(&app)?;
+    load_column_summary_settings(&path).map_err(|e| e.to_string())
+}
+
+/// 更新列摘要设置，返回保存后的设置
+///
+/// 开启后，get_current_info 返回的每一列附带最小值、最大值和不同值数量
+/// （每个历史状态只计算一次）
+///
+/// 参数：
+/// - settings: 列摘要设置（缺少的字段使用内置默认值）
+#[tauri::command]
+pub async fn save_column_summaries(
+    settings: ColumnSummarySettings,
+    app: AppHandle,
+) -> Result<ColumnSummarySettings, String> {
+    let path = column_summaries_path(&app)?;
+    save_column_summary_settings(&path, &settings).map_err(|e| format!("保存列摘要设置失败: {}", e))?;
+    Ok(settings)
+}
@@ -0,0 +1,420 @@
+// ============================================================================
+// commands/snapshot.rs - 快照分享包命令
+// ============================================================================
+// 这个文件实现了快照分享包相关的 Tauri 命令：
+// - export_snapshot_bundle: 将当前状态（数据、操作历史、描述统计、图表配置、显示设置）打包为 zip
+// - import_snapshot_bundle: 导入同事分享的快照包，还原数据和显示设置
+// - export_history_bundle: 把完整的操作历史（每一步的数据 + 操作清单）写入目录，用于归档和审计
+// - check_recipe_compatibility: 检查快照包中的操作记录能否用于当前数据集（缺失列、类型不一致、改名建议）
+// - resolve_recipe: 用参数值替换操作记录中声明为参数的位置（阈值、日期范围、列名等）
+// - apply_recipe: 替换参数、检查兼容性后，在当前数据集上重新执行操作记录（每一步记入历史）
+//
+// 快照包用于把"我现在看到的"原样交给同事，不需要对方重新执行清洗步骤
+
+use crate::AppState;
+use crate::commands::data_export::{ExportSource, record_export};
+use crate::commands::data_query::compute_column_stats;
+use crate::commands::file_import::{create_import_entry, replace_with_import_entry};
+use crate::commands::operations::create_history_entry;
+use crate::data::export::{check_overwrite, write_atomic_checked};
+use crate::data::history_bundle::{BundleEntry, write_history_bundle};
+use crate::data::recipe::{
+    REPLAYABLE_OPERATIONS, check_recipe_compatibility as check_compatibility, replay_recipe_step,
+    resolve_recipe_parameters, unsupported_recipe_steps, validate_recipe_parameters,
+};
+use crate::data::snapshot::{SNAPSHOT_FORMAT_VERSION, SnapshotParts, read_snapshot_bundle, write_snapshot_bundle};
+use crate::models::chart::ChartConfig;
+use crate::models::{
+    HistoryBundleManifest, HistoryEntryInfo, ImportOptions, RecipeCompatibilityReport, RecipeParameter,
+    SnapshotContents, SnapshotManifest,
+};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::path::Path;
+
+// ============================================================================
+// 1. 导出快照包
+// ============================================================================
+/// 将当前状态导出为快照分享包（.zip）
+///
+/// 包含：
+/// - data.parquet: 当前数据
+/// - recipe.json: 从导入到当前状态的操作历史（不包含已撤销的操作）
+/// - parameters.json: 操作记录的参数声明
+/// - profile.json: 各列的描述统计
+/// - charts.json: 前端传入的图表配置
+/// - display.json: 列格式、条件格式、视图状态
+///
+/// 参数：
+/// - output_path: 输出文件的完整路径（.zip）
+/// - charts: 图表配置（可选，由前端传入）
+/// - parameters: 操作记录的参数声明（可选，绑定的步骤序号与 recipe.json 中的位置一致）
+/// - overwrite: 目标文件已存在时是否覆盖（可选，默认 true；为 false 且文件已存在时返回错误，不写出任何内容）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<String, String>: 成功返回文件路径，失败返回错误
+///
+/// 前端调用示例：
+/// ```typescript
+/// await invoke('export_snapshot_bundle', {
+///   outputPath: '/path/to/sales_snapshot.zip',
+///   charts: [chartConfig]
+/// });
+/// ```
+#[tauri::command]
+pub async fn export_snapshot_bundle(
+    output_path: String,
+    charts: Option<Vec<ChartConfig>>,
+    parameters: Option<Vec<RecipeParameter>>,
+    overwrite: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let overwrite = overwrite.unwrap_or(true);
+    check_overwrite(&output_path, overwrite).map_err(|e| e.to_string())?;
+    // 获取当前数据、显示设置和操作历史（不在写出期间持有锁）
+    let (current_df, info, display, recipe, source) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        let df = store.get_current().ok_or("没有数据")?.clone();
+        let info = store.get_current_info().ok_or("没有数据")?.clone();
+        let display = store.get_current_display().cloned().unwrap_or_default();
+        let recipe = match store.get_current_index() {
+            Some(index) => store.get_history().into_iter().take(index + 1).collect(),
+            None => Vec::new(),
+        };
+        (df, info, display, recipe, ExportSource::current(&store))
+    };
+
+    let parameters = parameters.unwrap_or_default();
+    validate_recipe_parameters(&recipe, &parameters).map_err(|e| e.to_string())?;
+
+    let shape = current_df.shape();
+    let output_path = tauri::async_runtime::spawn_blocking(move || {
+        let profile = current_df
+            .get_column_names()
+            .iter()
+            .map(|name| compute_column_stats(&current_df, name))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let manifest = SnapshotManifest {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            dataset_name: info.name,
+            rows: current_df.height(),
+            columns: current_df.width(),
+        };
+        let parts = SnapshotParts {
+            recipe,
+            parameters,
+            profile,
+            charts: charts.unwrap_or_default(),
+            display,
+        };
+
+        write_atomic_checked(&output_path, overwrite, |file| {
+            write_snapshot_bundle(file, &manifest, &current_df, &parts)
+        })
+        .map_err(|e| format!("写出快照包失败: {}", e))?;
+        Ok::<_, String>(output_path)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    record_export(&state, source, "export_snapshot_bundle", &output_path, shape)?;
+    Ok(output_path)
+}
+
+// ============================================================================
+// 2. 导入快照包
+// ============================================================================
+/// 导入快照分享包，替换当前数据集
+///
+/// 数据和显示设置会恢复为快照时的状态（历史从这个状态重新开始），
+/// 操作历史、描述统计和图表配置返回给前端展示或恢复
+///
+/// 参数：
+/// - file_path: 快照包的完整路径（.zip）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<SnapshotContents, String>: 成功返回快照内容，失败返回错误
+#[tauri::command]
+pub async fn import_snapshot_bundle(
+    file_path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<SnapshotContents, String> {
+    let _session = state.imports.begin(&file_path).map_err(|e| e.to_string())?;
+    let path_clone = file_path.clone();
+    let (manifest, df, parts) = tauri::async_runtime::spawn_blocking(move || read_snapshot_bundle(&path_clone))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("导入快照包失败: {}", e))?;
+
+    // 记录为快照包导入，从源文件刷新时读取包中的数据，而不是按扩展名当作压缩的 CSV
+    let mut entry = create_import_entry(file_path, Some(ImportOptions::Snapshot), df);
+    // 数据集名称沿用快照时的原始文件名
+    entry.metadata.name = manifest.dataset_name.clone();
+    entry.display = parts.display;
+
+    // 清空历史并添加新条目
+    let info = replace_with_import_entry(&state, entry)?;
+
+    Ok(SnapshotContents {
+        manifest,
+        dataset: info,
+        recipe: parts.recipe,
+        parameters: parts.parameters,
+        profile: parts.profile,
+        charts: parts.charts,
+    })
+}
+
+// ============================================================================
+// 3. 导出历史归档
+// ============================================================================
+/// 将完整的操作历史写入归档目录
+///
+/// 与快照包不同，归档包含每个历史条目的数据（包括已撤销、可以重做的条目），
+/// 可以事后逐步核对每个操作前后的数据：
+/// - entry_000.parquet, entry_001.parquet, ...: 每个历史条目操作后的数据
+/// - entry_NNN_removed.parquet: 该操作删除的行（筛选、删除空值等记录了被删除行时）
+/// - manifest.json: 各条目的操作、时间、描述、行列数和显示设置，以及归档时的当前位置
+///
+/// 参数：
+/// - output_dir: 输出目录（不存在时自动创建；已存在时必须为空）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<HistoryBundleManifest, String>: 成功返回写出的清单，失败返回错误
+///   （导出记录中的行列数为当前状态的数据）
+///
+/// 前端调用示例：
+/// ```typescript
+/// const manifest = await invoke('export_history_bundle', { outputDir: '/path/to/sales_audit' });
+/// ```
+#[tauri::command]
+pub async fn export_history_bundle(
+    output_dir: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<HistoryBundleManifest, String> {
+    // 复制全部历史条目（DataFrame 的复制只增加引用计数，不在写出期间持有锁）
+    let (dataset_name, current_index, entries, source, shape) = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        let name = store.get_current_info().ok_or("没有数据")?.name.clone();
+        let shape = store.get_current().ok_or("没有数据")?.shape();
+        let entries: Vec<BundleEntry> = store
+            .entries()
+            .iter()
+            .map(|entry| BundleEntry {
+                info: entry.into(),
+                dataframe: entry.dataframe.clone(),
+                removed_rows: entry.removed_rows.clone(),
+                display: entry.display.clone(),
+            })
+            .collect();
+        (
+            name,
+            store.get_current_index(),
+            entries,
+            ExportSource::current(&store),
+            shape,
+        )
+    };
+
+    let dir = output_dir.clone();
+    let manifest = tauri::async_runtime::spawn_blocking(move || {
+        write_history_bundle(Path::new(&dir), &dataset_name, current_index, entries)
+            .map_err(|e| format!("写出历史归档失败: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    record_export(&state, source, "export_history_bundle", &output_dir, shape)?;
+    Ok(manifest)
+}
+
+// ============================================================================
+// 4. 检查操作记录兼容性
+// ============================================================================
+/// 检查操作记录（快照包中的 recipe）能否用于当前数据集
+///
+/// 以操作记录第一步（导入）时的列结构为准，一次性返回所有问题：
+/// - 缺失的列、类型不一致的列（附带用到该列的步骤）
+/// - 当前数据集中多出的列
+/// - 缺失列的改名建议（多出的列中名称相似的列）
+///
+/// 只有被步骤用到的缺失列或类型不一致的列才会使 compatible 为 false
+///
+/// 参数：
+/// - recipe: 操作记录（import_snapshot_bundle 返回的 recipe）
+/// - parameters: 操作记录的参数声明（可选，import_snapshot_bundle 返回的 parameters）
+/// - values: 参数值（可选，按参数名；先替换参数再检查，例如目标列名换成了新文件中的列）
+/// - state: 应用状态（自动注入）
+///
+/// 返回：
+/// - Result<RecipeCompatibilityReport, String>: 兼容性检查结果
+#[tauri::command]
+pub async fn check_recipe_compatibility(
+    recipe: Vec<HistoryEntryInfo>,
+    parameters: Option<Vec<RecipeParameter>>,
+    values: Option<HashMap<String, JsonValue>>,
+    state: tauri::State<'_, AppState>,
+) -> Result<RecipeCompatibilityReport, String> {
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let recipe = resolve_recipe_parameters(&recipe, &parameters.unwrap_or_default(), &values.unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+        check_compatibility(&recipe, &current_df).map_err(|e| format!("检查操作记录兼容性失败: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// ============================================================================
+// 5. 替换操作记录参数
+// ============================================================================
+/// 用参数值替换操作记录中声明为参数的位置，返回替换后的操作记录
+///
+/// 同一份操作记录可以用不同的阈值、日期范围、目标列名用于相似的文件，不需要手动修改 JSON
+/// 替换后的步骤会重新解析（值的类型不适用于该操作时返回错误），步骤描述按新参数重新生成
+///
+/// 参数值的优先级：values 中的值 → 参数默认值 → 操作记录中原来的值
+///
+/// 参数：
+/// - recipe: 操作记录（import_snapshot_bundle 返回的 recipe）
+/// - parameters: 操作记录的参数声明（import_snapshot_bundle 返回的 parameters）
+/// - values: 参数值（可选，按参数名）
+///
+/// 返回：
+/// - Result<Vec<HistoryEntryInfo>, String>: 替换后的操作记录
+///
+/// 前端调用示例：
+/// ```typescript
+/// const resolved = await invoke('resolve_recipe', {
+///   recipe: contents.recipe,
+///   parameters: contents.parameters,
+///   values: { threshold: 500, region_column: 'area' }
+/// });
+/// ```
+#[tauri::command]
+pub async fn resolve_recipe(
+    recipe: Vec<HistoryEntryInfo>,
+    parameters: Vec<RecipeParameter>,
+    values: Option<HashMap<String, JsonValue>>,
+) -> Result<Vec<HistoryEntryInfo>, String> {
+    resolve_recipe_parameters(&recipe, &parameters, &values.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// 6. 应用操作记录
+// ============================================================================
+/// 替换参数后，在当前数据集上按顺序重新执行操作记录（跳过第一条导入记录）
+///
+/// 执行前先检查兼容性（缺失列、类型不一致）和不能重新执行的步骤，有问题时不执行任何步骤
+/// 支持的操作见 data::recipe::REPLAYABLE_OPERATIONS；只修改显示设置的步骤会被跳过
+/// 每一步作为一条历史记录加入当前历史，可以逐步撤销；
+/// 某一步执行失败时返回错误，已执行的步骤也不会加入历史
+///
+/// 参数：
+/// - recipe: 操作记录（import_snapshot_bundle 返回的 recipe）
+/// - parameters: 操作记录的参数声明（可选）
+/// - values: 参数值（可选，按参数名）
+///
+/// 返回：
+/// - Result<usize, String>: 执行的步骤数
+///
+/// 前端调用示例：
+/// ```typescript
+/// const steps = await invoke('apply_recipe', {
+///   recipe: contents.recipe,
+///   parameters: contents.parameters,
+///   values: { threshold: 500 }
+/// });
+/// ```
+#[tauri::command]
+pub async fn apply_recipe(
+    recipe: Vec<HistoryEntryInfo>,
+    parameters: Option<Vec<RecipeParameter>>,
+    values: Option<HashMap<String, JsonValue>>,
+    state: tauri::State<'_, AppState>,
+) -> Result<usize, String> {
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.ensure_unlocked().map_err(|e| e.to_string())?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let entries = tauri::async_runtime::spawn_blocking(move || {
+        let recipe = resolve_recipe_parameters(&recipe, &parameters.unwrap_or_default(), &values.unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+
+        let report = check_compatibility(&recipe, &current_df).map_err(|e| format!("检查操作记录兼容性失败: {}", e))?;
+        if !report.compatible {
+            let columns: Vec<&str> = report
+                .missing_columns
+                .iter()
+                .chain(&report.type_mismatches)
+                .filter(|issue| !issue.used_by_steps.is_empty())
+                .map(|issue| issue.column.as_str())
+                .collect();
+            return Err(format!(
+                "当前数据与操作记录不兼容（缺失或类型不一致的列: {}）",
+                columns.join(", ")
+            ));
+        }
+
+        let unsupported = unsupported_recipe_steps(&recipe);
+        if !unsupported.is_empty() {
+            return Err(format!(
+                "以下步骤不支持重新执行: {}（只支持{}）",
+                unsupported.join("；"),
+                REPLAYABLE_OPERATIONS
+            ));
+        }
+
+        let mut df = current_df;
+        let mut entries = Vec::with_capacity(recipe.len().saturating_sub(1));
+        // 只修改显示设置的步骤不影响数据，跳过
+        for (index, step) in recipe
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, step)| !step.operation.is_display_only())
+        {
+            df = replay_recipe_step(df, index, step).map_err(|e| e.to_string())?;
+            entries.push(create_history_entry(df.clone(), step.operation.clone()).map_err(|e| e.to_string())?);
+        }
+        Ok::<_, String>(entries)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let steps = entries.len();
+    for entry in entries {
+        store.push_operation(entry).map_err(|e| e.to_string())?;
+    }
+
+    Ok(steps)
+}
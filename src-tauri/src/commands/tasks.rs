@@ -0,0 +1,57 @@
+// ============================================================================
+// commands/tasks.rs - 后台任务命令
+// ============================================================================
+// 这个文件实现了后台任务相关的 Tauri 命令：
+// - get_task / list_tasks: 查询任务进度
+// - cancel_task: 请求取消任务
+//
+// 任务运行期间，进度还会通过 "task://progress" 事件推送给前端（载荷为 TaskInfo）
+
+use crate::AppState;
+use crate::data::TaskHandle;
+use crate::models::TaskInfo;
+use tauri::{AppHandle, Emitter, State};
+
+/// 任务进度事件名称
+pub const TASK_PROGRESS_EVENT: &str = "task://progress";
+
+/// 推送任务进度事件（推送失败不影响任务本身）
+pub(crate) fn emit_task_progress(app: &AppHandle, task: &TaskHandle) {
+    let _ = app.emit(TASK_PROGRESS_EVENT, task.info());
+}
+
+// ============================================================================
+// 查询任务进度
+// ============================================================================
+/// 查询单个任务的进度
+///
+/// 参数：
+/// - task_id: 任务 ID
+///
+/// 返回：
+/// - Result<Option<TaskInfo>, String>: 任务进度，任务不存在时为 None
+#[tauri::command]
+pub async fn get_task(task_id: String, state: State<'_, AppState>) -> Result<Option<TaskInfo>, String> {
+    Ok(state.tasks.get(&task_id))
+}
+
+/// 列出所有任务（运行中的任务和最近结束的任务）
+#[tauri::command]
+pub async fn list_tasks(state: State<'_, AppState>) -> Result<Vec<TaskInfo>, String> {
+    Ok(state.tasks.list())
+}
+
+// ============================================================================
+// 取消任务
+// ============================================================================
+/// 请求取消任务
+///
+/// 取消是协作式的：任务在处理完当前批次后停止，状态变为 "cancelled"
+/// 任务已结束时不做任何事
+///
+/// 参数：
+/// - task_id: 任务 ID
+#[tauri::command]
+pub async fn cancel_task(task_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    state.tasks.cancel(&task_id).map_err(|e| e.to_string())
+}
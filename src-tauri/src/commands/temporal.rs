@@ -0,0 +1,734 @@
+// ============================================================================
+// commands/temporal.rs - 时间间隔与周期比较命令
+// ============================================================================
+// 这个文件实现了时间间隔（Duration）相关的 Tauri 命令：
+// - duration_between: 两个时间列相减，得到时间间隔列
+// - shift_by_duration: 时间列加上（或减去）一个时间间隔
+// - duration_to_number: 时间间隔转换为秒 / 分钟 / 小时 / 天数值
+// - period_comparison: 同比 / 环比（上期值、差值、变化率）
+// - cohort_table: 同期群留存表（同期群 × 偏移周期）
+// - extract_date_parts: 提取年、季度、月、ISO 周、财年、财季等日期部分
+// - business_days_between / add_business_days: 工作日差、加 N 个工作日（可指定节假日）
+// - compute_age: 按日历计算年龄 / 司龄（整年或整月）
+//
+// 所有命令都会记录到历史
+
+use crate::AppState;
+use crate::commands::operations::create_history_entry;
+use crate::data::calendar::{
+    BusinessCalendar, add_business_days as add_business_days_column,
+    business_days_between as business_days_between_columns, compute_age as compute_age_column,
+    extract_date_parts as extract_parts,
+};
+use crate::data::cohort::cohort_table as build_cohort_table;
+use crate::data::column_names::OutputColumns;
+use crate::data::duration::{
+    duration_between as duration_between_columns, duration_to_number as duration_to_number_column,
+    parse_duration_micros, shift_by_duration as shift_column,
+};
+use crate::data::period::{DEFAULT_AGGREGATION, period_comparison as compare_periods};
+use crate::models::OperationType;
+use crate::models::history::{
+    AgeUnit, CohortLayout, ColumnConflictPolicy, ComparisonPeriod, DatePart, DurationUnit, FiscalYearLabel,
+    PeriodBucket,
+};
+use chrono::{Local, NaiveDate};
+use tauri::State;
+
+// ============================================================================
+// 1. 时间差（Duration Between）
+// ============================================================================
+/// 计算两个时间列之差（end - start），结果写入时间间隔列
+///
+/// 日期列按当天 00:00:00 计算；任一值为空时结果为空值
+///
+/// 参数：
+/// - start_column: 开始时间列（Date / Datetime）
+/// - end_column: 结束时间列（Date / Datetime）
+/// - output_column: 新列名（默认 "duration"）
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error）
+#[tauri::command]
+pub async fn duration_between(
+    start_column: String,
+    end_column: String,
+    output_column: Option<String>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let output_column = output_column.unwrap_or_else(|| "duration".to_string());
+    let start_clone = start_column.clone();
+    let end_clone = end_column.clone();
+    let output_clone = output_column.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let output_clone = outputs.resolve(&output_clone).map_err(|e| e.to_string())?;
+
+        let duration = duration_between_columns(&current_df, &start_clone, &end_clone, &output_clone)
+            .map_err(|e| format!("计算时间差失败: {}", e))?;
+
+        let mut df = current_df;
+        df.with_column(duration)
+            .map_err(|e| format!("添加列 {} 失败: {}", output_clone, e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::DurationBetween {
+        start_column,
+        end_column,
+        output_column,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 2. 时间偏移（Shift By Duration）
+// ============================================================================
+/// 时间列加上一个时间间隔（间隔为负数时相当于减去）
+///
+/// 日期列的结果为日期时间列，日期时间列和时间间隔列保持原类型
+///
+/// 参数：
+/// - column: 日期、日期时间或时间间隔列
+/// - duration: 时间间隔文本，例如 "1d 2h 30m"、"2d 03:15:00"、"-00:30:00"
+/// - output_column: 输出列（None 表示覆盖原列）
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error；覆盖原列时不检查）
+#[tauri::command]
+pub async fn shift_by_duration(
+    column: String,
+    duration: String,
+    output_column: Option<String>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let offset = parse_duration_micros(&duration)
+        .ok_or_else(|| format!("无法解析时间间隔: {}（示例: \"1d 2h 30m\"、\"03:15:00\"）", duration))?;
+    let column_clone = column.clone();
+    let target = output_column.clone().unwrap_or_else(|| column.clone());
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let target = if target == column_clone {
+            target
+        } else {
+            outputs.resolve(&target).map_err(|e| e.to_string())?
+        };
+
+        let shifted =
+            shift_column(&current_df, &column_clone, offset, &target).map_err(|e| format!("时间偏移失败: {}", e))?;
+
+        let mut df = current_df;
+        df.with_column(shifted)
+            .map_err(|e| format!("添加列 {} 失败: {}", target, e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::ShiftByDuration {
+        column,
+        duration,
+        output_column,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 3. 时间间隔转数值（Duration To Number）
+// ============================================================================
+/// 时间间隔列转换为数值列（Float64），便于求平均、作图等
+///
+/// 参数：
+/// - column: 时间间隔列
+/// - unit: 数值单位（seconds / minutes / hours / days）
+/// - output_column: 输出列（None 表示覆盖原列）
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error；覆盖原列时不检查）
+#[tauri::command]
+pub async fn duration_to_number(
+    column: String,
+    unit: DurationUnit,
+    output_column: Option<String>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let column_clone = column.clone();
+    let target = output_column.clone().unwrap_or_else(|| column.clone());
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let target = if target == column_clone {
+            target
+        } else {
+            outputs.resolve(&target).map_err(|e| e.to_string())?
+        };
+
+        let values = duration_to_number_column(&current_df, &column_clone, unit, &target)
+            .map_err(|e| format!("时间间隔转数值失败: {}", e))?;
+
+        let mut df = current_df;
+        df.with_column(values)
+            .map_err(|e| format!("添加列 {} 失败: {}", target, e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::DurationToNumber {
+        column,
+        unit,
+        output_column,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 4. 同比 / 环比（Period Comparison）
+// ============================================================================
+/// 与上一周期比较，追加上期值、差值和变化率三列
+///
+/// 两种模式：
+/// - 逐行（不指定 bucket）：每行与同一分组内日期恰好早一个周期的行比较，行数不变
+/// - 汇总（指定 bucket）：先按粒度汇总数值列，再比较，结果替换为汇总表
+///
+/// 参数：
+/// - date_column: 日期或日期时间列
+/// - value_column: 数值列
+/// - period: 比较周期（wow / mom / yoy）
+/// - group_by: 分组列（可选，每组分别比较）
+/// - bucket: 汇总粒度（可选，day / week / month / quarter / year）
+/// - aggregation: 汇总函数（可选，默认 sum）
+/// - on_conflict: 结果列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error）
+///
+/// 前端调用示例：
+/// ```typescript
+/// await invoke('period_comparison', {
+///   dateColumn: 'order_date',
+///   valueColumn: 'revenue',
+///   period: 'yoy',
+///   bucket: 'month'
+/// });
+/// ```
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn period_comparison(
+    date_column: String,
+    value_column: String,
+    period: ComparisonPeriod,
+    group_by: Option<Vec<String>>,
+    bucket: Option<PeriodBucket>,
+    aggregation: Option<String>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let group_by = group_by.unwrap_or_default();
+    let aggregation = aggregation.unwrap_or_else(|| DEFAULT_AGGREGATION.to_string());
+    let date_clone = date_column.clone();
+    let value_clone = value_column.clone();
+    let group_clone = group_by.clone();
+    let aggregation_clone = aggregation.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let df = compare_periods(
+            &current_df,
+            &date_clone,
+            &value_clone,
+            period,
+            &group_clone,
+            bucket,
+            &aggregation_clone,
+            &mut outputs,
+        )
+        .map_err(|e| format!("同比 / 环比计算失败: {}", e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::PeriodComparison {
+        date_column,
+        value_column,
+        period,
+        group_by,
+        bucket,
+        aggregation,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 5. 同期群留存表（Cohort Table）
+// ============================================================================
+/// 生成同期群留存表，结果替换当前数据集（可以撤销回到原始数据）
+///
+/// 每个用户按最早的注册日期归入同期群，统计之后每个周期的去重活跃用户数和留存率
+///
+/// 参数：
+/// - user_column: 用户 ID 列
+/// - signup_column: 注册日期列（Date / Datetime）
+/// - activity_column: 活跃日期列（Date / Datetime）
+/// - period: 周期粒度（可选，day / week / month / quarter / year，默认 month）
+/// - layout: 结果布局（可选，long / wide，默认 long，可直接用于热力图）
+/// - max_offset: 最大偏移周期数（可选）
+///
+/// 前端调用示例：
+/// ```typescript
+/// await invoke('cohort_table', {
+///   userColumn: 'user_id',
+///   signupColumn: 'signup_date',
+///   activityColumn: 'event_date',
+///   period: 'week',
+///   maxOffset: 12
+/// });
+/// ```
+#[tauri::command]
+pub async fn cohort_table(
+    user_column: String,
+    signup_column: String,
+    activity_column: String,
+    period: Option<PeriodBucket>,
+    layout: Option<CohortLayout>,
+    max_offset: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let period = period.unwrap_or(PeriodBucket::Month);
+    let layout = layout.unwrap_or_default();
+    let user_clone = user_column.clone();
+    let signup_clone = signup_column.clone();
+    let activity_clone = activity_column.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let result_df = tauri::async_runtime::spawn_blocking(move || {
+        build_cohort_table(
+            &current_df,
+            &user_clone,
+            &signup_clone,
+            &activity_clone,
+            period,
+            layout,
+            max_offset,
+        )
+        .map_err(|e| format!("生成同期群留存表失败: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::CohortTable {
+        user_column,
+        signup_column,
+        activity_column,
+        period,
+        layout,
+        max_offset,
+    };
+    let entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 6. 提取日期部分（Extract Date Parts）
+// ============================================================================
+/// 从日期列提取年、季度、月、日、星期、ISO 周、ISO 年、财年、财季
+///
+/// 每个部分写入新列 {column}_{part}，例如 order_date_iso_week、order_date_fiscal_year
+///
+/// 参数：
+/// - column: 日期或日期时间列
+/// - parts: 要提取的部分（year / quarter / month / day / weekday / iso_week / iso_year /
+///   fiscal_year / fiscal_quarter）
+/// - fiscal_year_start: 财年起始月份（可选，1-12，默认 1）
+/// - fiscal_year_label: 财年命名方式（可选，end_year / start_year，默认 end_year）
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error）
+///
+/// 前端调用示例：
+/// ```typescript
+/// // 财年从 4 月开始
+/// await invoke('extract_date_parts', {
+///   column: 'order_date',
+///   parts: ['iso_week', 'fiscal_year', 'fiscal_quarter'],
+///   fiscalYearStart: 4
+/// });
+/// ```
+#[tauri::command]
+pub async fn extract_date_parts(
+    column: String,
+    parts: Vec<DatePart>,
+    fiscal_year_start: Option<u32>,
+    fiscal_year_label: Option<FiscalYearLabel>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let fiscal_year_start = fiscal_year_start.unwrap_or(1);
+    let fiscal_year_label = fiscal_year_label.unwrap_or_default();
+    let column_clone = column.clone();
+    let parts_clone = parts.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let df = extract_parts(
+            &current_df,
+            &column_clone,
+            &parts_clone,
+            fiscal_year_start,
+            fiscal_year_label,
+            &mut outputs,
+        )
+        .map_err(|e| format!("提取日期部分失败: {}", e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::ExtractDateParts {
+        column,
+        parts,
+        fiscal_year_start,
+        fiscal_year_label,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 7. 工作日差（Business Days Between）
+// ============================================================================
+/// 计算两个日期列之间的工作日数（end - start，不含结束日期），结果写入 Int64 列
+///
+/// 周六、周日和 holidays 中的日期不算工作日；结束日期早于开始日期时结果为负数
+/// 用于 SLA、交付周期等分析，例如周一下单、周二发货为 1 个工作日
+///
+/// 参数：
+/// - start_column: 开始日期列（Date / Datetime）
+/// - end_column: 结束日期列（Date / Datetime）
+/// - output_column: 新列名（默认 "business_days"）
+/// - holidays: 节假日列表（可选，格式 YYYY-MM-DD）
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error）
+#[tauri::command]
+pub async fn business_days_between(
+    start_column: String,
+    end_column: String,
+    output_column: Option<String>,
+    holidays: Option<Vec<String>>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let output_column = output_column.unwrap_or_else(|| "business_days".to_string());
+    let holidays = holidays.unwrap_or_default();
+    let calendar = BusinessCalendar::new(&holidays).map_err(|e| e.to_string())?;
+    let start_clone = start_column.clone();
+    let end_clone = end_column.clone();
+    let output_clone = output_column.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let output_clone = outputs.resolve(&output_clone).map_err(|e| e.to_string())?;
+
+        let counts = business_days_between_columns(&current_df, &start_clone, &end_clone, &calendar, &output_clone)
+            .map_err(|e| format!("计算工作日差失败: {}", e))?;
+
+        let mut df = current_df;
+        df.with_column(counts)
+            .map_err(|e| format!("添加列 {} 失败: {}", output_clone, e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::BusinessDaysBetween {
+        start_column,
+        end_column,
+        output_column,
+        holidays,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 8. 加工作日（Add Business Days）
+// ============================================================================
+/// 日期列加上 N 个工作日（N 为负数时向前数）
+///
+/// 起始日期本身不计入，例如周五加 1 个工作日为下周一；日期时间列保留原来的时刻
+///
+/// 参数：
+/// - column: 日期或日期时间列
+/// - days: 工作日数
+/// - output_column: 输出列（None 表示覆盖原列）
+/// - holidays: 节假日列表（可选，格式 YYYY-MM-DD）
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error；覆盖原列时不检查）
+#[tauri::command]
+pub async fn add_business_days(
+    column: String,
+    days: i64,
+    output_column: Option<String>,
+    holidays: Option<Vec<String>>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let holidays = holidays.unwrap_or_default();
+    let calendar = BusinessCalendar::new(&holidays).map_err(|e| e.to_string())?;
+    let column_clone = column.clone();
+    let target = output_column.clone().unwrap_or_else(|| column.clone());
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let target = if target == column_clone {
+            target
+        } else {
+            outputs.resolve(&target).map_err(|e| e.to_string())?
+        };
+
+        let shifted = add_business_days_column(&current_df, &column_clone, days, &calendar, &target)
+            .map_err(|e| format!("加工作日失败: {}", e))?;
+
+        let mut df = current_df;
+        df.with_column(shifted)
+            .map_err(|e| format!("添加列 {} 失败: {}", target, e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::AddBusinessDays {
+        column,
+        days,
+        output_column,
+        holidays,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================================================
+// 9. 年龄 / 司龄（Compute Age）
+// ============================================================================
+/// 计算从出生日期（或入职日期）到参照日期经过的整年数或整月数，结果写入 Int64 列
+///
+/// 按日历计算而不是按天数折算：生日 / 周年日当天才满一岁 / 一年，
+/// 月末日期对齐（1 月 31 日入职，2 月 28 日满 1 个月）
+/// 参照日期早于开始日期时结果为负数；任一日期为空时结果为空值
+///
+/// 参数：
+/// - column: 出生日期或开始日期列（Date / Datetime）
+/// - reference_column: 参照日期列（可选，例如离职日期）
+/// - reference_date: 参照日期（可选，格式 YYYY-MM-DD，默认今天；reference_column 优先）
+/// - unit: 单位（默认按年）
+/// - output_column: 新列名（默认 "{column}_age"）
+/// - on_conflict: 新列与已有列同名时的处理方式（可选，error / suffix / overwrite，默认 error）
+#[tauri::command]
+pub async fn compute_age(
+    column: String,
+    reference_column: Option<String>,
+    reference_date: Option<String>,
+    unit: Option<AgeUnit>,
+    output_column: Option<String>,
+    on_conflict: Option<ColumnConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let unit = unit.unwrap_or(AgeUnit::Years);
+    let output_column = output_column.unwrap_or_else(|| format!("{}_age", column));
+
+    // "今天" 在执行时确定，并记录到历史中，保证重放结果一致
+    let reference_date = match reference_column {
+        Some(_) => None,
+        None => Some(match reference_date {
+            Some(date) => NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                .map_err(|_| format!("无法解析参照日期 '{}'，请使用 YYYY-MM-DD 格式", date))?,
+            None => Local::now().date_naive(),
+        }),
+    };
+
+    let column_clone = column.clone();
+    let reference_clone = reference_column.clone();
+    let target = output_column.clone();
+
+    let current_df = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        store.get_current().ok_or("没有数据")?.clone()
+    };
+
+    let (result_df, renamed_columns) = tauri::async_runtime::spawn_blocking(move || {
+        let mut outputs = OutputColumns::new(&current_df, on_conflict.unwrap_or_default());
+        let target = outputs.resolve(&target).map_err(|e| e.to_string())?;
+
+        let ages = compute_age_column(
+            &current_df,
+            &column_clone,
+            reference_clone.as_deref(),
+            reference_date.unwrap_or_default(),
+            unit,
+            &target,
+        )
+        .map_err(|e| format!("计算年龄失败: {}", e))?;
+
+        let mut df = current_df;
+        df.with_column(ages)
+            .map_err(|e| format!("添加列 {} 失败: {}", target, e))?;
+        Ok::<_, String>((df, outputs.into_renamed()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut store = state
+        .data_store
+        .lock()
+        .map_err(|e| format!("Failed to lock data store: {}", e))?;
+
+    let operation = OperationType::ComputeAge {
+        column,
+        reference_column,
+        reference_date: reference_date.map(|date| date.format("%Y-%m-%d").to_string()),
+        unit,
+        output_column,
+    };
+    let mut entry = create_history_entry(result_df, operation).map_err(|e| e.to_string())?;
+    entry.renamed_columns = renamed_columns;
+
+    store.push_operation(entry).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
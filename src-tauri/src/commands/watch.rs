@@ -0,0 +1,86 @@
+// ============================================================================
+// commands/watch.rs - 源文件监视命令
+// ============================================================================
+// 这个文件实现了源文件监视相关的 Tauri 命令：
+// - enable_file_watch: 开始监视当前数据集的源文件
+// - disable_file_watch: 停止监视
+//
+// 源文件变化时推送 "source://changed" 事件（载荷为 SourceChangedEvent），
+// 前端可以提示"源文件已更新，是否刷新？"，确认后调用 reload_from_source
+
+use crate::AppState;
+use crate::models::{OperationType, SourceChangedEvent};
+use std::path::Path;
+use tauri::{AppHandle, Emitter, State};
+
+/// 源文件变化事件名称
+pub const SOURCE_CHANGED_EVENT: &str = "source://changed";
+
+// ============================================================================
+// 1. 开始监视
+// ============================================================================
+/// 开始监视当前数据集的源文件（历史中第一条导入记录的文件路径）
+///
+/// 同一时间只监视一个文件：再次调用会替换之前的监视（例如导入了新文件之后）
+/// 从 URL、数据库或快照包导入的数据没有可监视的本地文件，返回错误
+///
+/// 返回：
+/// - Result<String, String>: 成功返回被监视的文件路径
+///
+/// 前端调用示例：
+/// ```typescript
+/// await invoke('enable_file_watch');
+/// await listen('source://changed', (event) => {
+///   if (confirm('源文件已更新，是否刷新？')) invoke('reload_from_source');
+/// });
+/// ```
+#[tauri::command]
+pub async fn enable_file_watch(app: AppHandle, state: State<'_, AppState>) -> Result<String, String> {
+    let source = {
+        let store = state
+            .data_store
+            .lock()
+            .map_err(|e| format!("Failed to lock data store: {}", e))?;
+        match store.entries().first().map(|entry| &entry.operation) {
+            Some(OperationType::Import { file_path, .. }) => file_path.clone(),
+            Some(_) => return Err("当前数据集不是从文件导入的，无法监视".to_string()),
+            None => return Err("没有数据".to_string()),
+        }
+    };
+
+    if !Path::new(&source).is_file() {
+        return Err(format!("源文件不是本地文件或已不存在: {}", source));
+    }
+
+    let event_path = source.clone();
+    state
+        .file_watcher
+        .watch(Path::new(&source), move |kind| {
+            let _ = app.emit(
+                SOURCE_CHANGED_EVENT,
+                SourceChangedEvent {
+                    path: event_path.clone(),
+                    kind,
+                    detected_at: chrono::Utc::now().to_rfc3339(),
+                },
+            );
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(source)
+}
+
+// ============================================================================
+// 2. 停止监视
+// ============================================================================
+/// 停止监视源文件（没有在监视时不做任何事）
+///
+/// 返回：
+/// - Result<Option<String>, String>: 之前监视的文件路径
+#[tauri::command]
+pub async fn disable_file_watch(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    Ok(state
+        .file_watcher
+        .unwatch()
+        .map(|path| path.to_string_lossy().to_string()))
+}
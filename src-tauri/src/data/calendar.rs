@@ -0,0 +1,349 @@
+// ============================================================================
+// data/calendar.rs - 日历计算（日期部分 / ISO 周 / 财年 / 工作日）
+// ============================================================================
+// 这个文件负责与日历相关的计算：
+// 1. 从日期列中提取年、季度、月、星期、ISO 周和财年 / 财季
+//    - ISO 周：周一为一周的第一天，包含 1 月 4 日的那一周为第 1 周
+//    - 财年：按起始月份划分，例如起始月份为 4 时，4 月是财年的第一个月
+//    - 每个部分写入一个新列，列名为 {原列名}_{部分}，例如 order_date_fiscal_year
+// 2. 工作日计算：两个日期之间的工作日数、日期加上 N 个工作日
+//    - 周六、周日和用户提供的节假日不算工作日
+//    - 日期时间列按日期部分计算
+// 3. 年龄 / 司龄：从开始日期到参照日期经过的整年数或整月数
+
+use crate::data::column_names::OutputColumns;
+use crate::error::{DataAnalystError, Result};
+use crate::models::history::{AgeUnit, DatePart, FiscalYearLabel};
+use chrono::{Datelike, Months, NaiveDate};
+use polars::prelude::*;
+
+/// 1970-01-01 与公元元年 1 月 1 日之间的天数（NaiveDate 与 Polars Date 物理值的换算）
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// 输出列名
+pub fn date_part_column_name(column: &str, part: DatePart) -> String {
+    format!("{}_{}", column, part.suffix())
+}
+
+/// 构建单个日期部分的表达式
+///
+/// 财年计算：
+/// - 财年内的月份序号 = (月 - 起始月 + 12) % 12（0 表示财年第一个月）
+/// - 财季 = 月份序号 / 3 + 1
+/// - 财年（EndYear）= 起始月及之后的月份算作下一年；（StartYear）= 起始月之前的月份算作上一年
+fn date_part_expr(column: &str, part: DatePart, fiscal_start: u32, label: FiscalYearLabel) -> Expr {
+    let date = col(column);
+    let year = || date.clone().dt().year().cast(DataType::Int32);
+    let month = || date.clone().dt().month().cast(DataType::Int32);
+    let fiscal_month = || (month() - lit(fiscal_start as i32) + lit(12)) % lit(12);
+
+    match part {
+        DatePart::Year => year(),
+        DatePart::Quarter => date.dt().quarter().cast(DataType::Int32),
+        DatePart::Month => month(),
+        DatePart::Day => date.dt().day().cast(DataType::Int32),
+        DatePart::Weekday => date.dt().weekday().cast(DataType::Int32),
+        DatePart::IsoWeek => date.dt().week().cast(DataType::Int32),
+        DatePart::IsoYear => date.dt().iso_year().cast(DataType::Int32),
+        DatePart::FiscalQuarter => fiscal_month() / lit(3) + lit(1),
+        DatePart::FiscalYear if fiscal_start == 1 => year(),
+        DatePart::FiscalYear => {
+            let before_start = month().lt(lit(fiscal_start as i32));
+            match label {
+                FiscalYearLabel::EndYear => when(before_start).then(year()).otherwise(year() + lit(1)),
+                FiscalYearLabel::StartYear => when(before_start).then(year() - lit(1)).otherwise(year()),
+            }
+        }
+    }
+}
+
+// ============================================================================
+// 日期部分提取
+// ============================================================================
+/// 提取日期部分，结果以新列追加到 DataFrame（同名列会被覆盖）
+///
+/// 参数：
+/// - column: 日期或日期时间列
+/// - parts: 要提取的部分
+/// - fiscal_start: 财年起始月份（1-12，1 表示财年与自然年相同）
+/// - label: 财年的命名方式
+/// - outputs: 新列名的冲突处理（新列名为 {column}_{part}）
+///
+/// 示例：
+/// ```rust
+/// // 财年从 4 月开始：2024-05-10 → 财年 2025，财季 1
+/// let mut outputs = OutputColumns::new(&df, ColumnConflictPolicy::Error);
+/// let df = extract_date_parts(&df, "order_date", &[DatePart::FiscalYear, DatePart::FiscalQuarter],
+///     4, FiscalYearLabel::EndYear, &mut outputs)?;
+/// ```
+pub fn extract_date_parts(
+    df: &DataFrame,
+    column: &str,
+    parts: &[DatePart],
+    fiscal_start: u32,
+    label: FiscalYearLabel,
+    outputs: &mut OutputColumns,
+) -> Result<DataFrame> {
+    let invalid = |message: String| DataAnalystError::InvalidOperation(message);
+
+    let dtype = df
+        .column(column)
+        .map_err(|_| invalid(format!("列 '{}' 不存在", column)))?
+        .dtype();
+    if !matches!(dtype, DataType::Date | DataType::Datetime(_, _)) {
+        return Err(invalid(format!(
+            "列 '{}' 不是日期或日期时间类型（当前类型: {:?}）",
+            column, dtype
+        )));
+    }
+    if parts.is_empty() {
+        return Err(invalid("至少需要选择一个日期部分".to_string()));
+    }
+    if !(1..=12).contains(&fiscal_start) {
+        return Err(invalid(format!(
+            "财年起始月份必须在 1 到 12 之间，当前为 {}",
+            fiscal_start
+        )));
+    }
+
+    // 重复的部分只提取一次
+    let mut exprs: Vec<Expr> = Vec::with_capacity(parts.len());
+    for (i, part) in parts.iter().enumerate() {
+        if parts[..i].contains(part) {
+            continue;
+        }
+        let name = outputs.resolve(&date_part_column_name(column, *part))?;
+        exprs.push(date_part_expr(column, *part, fiscal_start, label).alias(name));
+    }
+
+    Ok(df.clone().lazy().with_columns(exprs).collect()?)
+}
+
+// ============================================================================
+// 工作日
+// ============================================================================
+/// 工作日日历：周一到周五，去掉节假日
+pub struct BusinessCalendar {
+    /// 落在周一到周五的节假日（1970-01-01 以来的天数，已排序、去重）
+    holidays: Vec<i32>,
+}
+
+impl BusinessCalendar {
+    /// 从节假日列表（"YYYY-MM-DD"）创建日历
+    pub fn new(holidays: &[String]) -> Result<Self> {
+        let mut days = Vec::with_capacity(holidays.len());
+        for text in holidays {
+            let date = NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").map_err(|_| {
+                DataAnalystError::InvalidOperation(format!("无法解析节假日日期: {}（格式: YYYY-MM-DD）", text))
+            })?;
+            let day = date.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE;
+            if is_weekday(day) {
+                days.push(day);
+            }
+        }
+        days.sort_unstable();
+        days.dedup();
+        Ok(Self { holidays: days })
+    }
+
+    /// 是否为工作日
+    pub fn is_business_day(&self, day: i32) -> bool {
+        is_weekday(day) && self.holidays.binary_search(&day).is_err()
+    }
+
+    /// [start, end) 之间的工作日数（不含结束日期，end 早于 start 时为负数）
+    ///
+    /// 例如周一到周二为 1，周五到下周一为 1
+    pub fn count_between(&self, start: i32, end: i32) -> i64 {
+        if end < start {
+            return -self.count_between(end, start);
+        }
+
+        // 整周直接按 5 天计算，剩余不足一周的天数逐天判断
+        let total = (end - start) as i64;
+        let mut count = total / 7 * 5;
+        for day in (start + (total / 7 * 7) as i32)..end {
+            if is_weekday(day) {
+                count += 1;
+            }
+        }
+
+        let from = self.holidays.partition_point(|&h| h < start);
+        let to = self.holidays.partition_point(|&h| h < end);
+        count - (to - from) as i64
+    }
+
+    /// 加上 n 个工作日（n 为负数时向前数）
+    ///
+    /// 起始日期本身不计入，例如周五加 1 个工作日为下周一，周六加 1 个工作日也是下周一
+    pub fn add_business_days(&self, start: i32, n: i64) -> i32 {
+        let step = if n < 0 { -1 } else { 1 };
+        let mut remaining = n.unsigned_abs();
+        let mut day = start;
+        while remaining > 0 {
+            day += step;
+            if self.is_business_day(day) {
+                remaining -= 1;
+            }
+        }
+        day
+    }
+}
+
+/// 是否为周一到周五（1970-01-01 是周四）
+fn is_weekday(day: i32) -> bool {
+    (day + 3).rem_euclid(7) < 5
+}
+
+/// 读取日期 / 日期时间列的日期部分（1970-01-01 以来的天数）
+fn day_numbers(df: &DataFrame, column: &str) -> Result<Int32Chunked> {
+    let series = df
+        .column(column)
+        .map_err(|_| DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", column)))?;
+
+    if !matches!(series.dtype(), DataType::Date | DataType::Datetime(_, _)) {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "列 '{}' 不是日期或日期时间类型（当前类型: {:?}）",
+            column,
+            series.dtype()
+        )));
+    }
+
+    let days = series
+        .as_materialized_series()
+        .cast(&DataType::Date)?
+        .to_physical_repr()
+        .into_owned();
+    Ok(days.i32()?.clone())
+}
+
+/// 两个日期列之间的工作日数（end - start，不含结束日期），结果为 Int64 列
+///
+/// 任一值为空时结果为空值
+pub fn business_days_between(
+    df: &DataFrame,
+    start: &str,
+    end: &str,
+    calendar: &BusinessCalendar,
+    name: &str,
+) -> Result<Series> {
+    let start = day_numbers(df, start)?;
+    let end = day_numbers(df, end)?;
+
+    let counts: Int64Chunked = start
+        .into_iter()
+        .zip(&end)
+        .map(|(s, e)| Some(calendar.count_between(s?, e?)))
+        .collect();
+
+    Ok(counts.with_name(name.into()).into_series())
+}
+
+/// 日期列加上 n 个工作日
+///
+/// 日期列的结果为日期列；日期时间列保留原来的时刻和类型
+pub fn add_business_days(
+    df: &DataFrame,
+    column: &str,
+    n: i64,
+    calendar: &BusinessCalendar,
+    name: &str,
+) -> Result<Series> {
+    let days = day_numbers(df, column)?;
+    let series = df.column(column)?.as_materialized_series();
+
+    // 每行移动的天数
+    let shifts: Vec<Option<i64>> = days
+        .into_iter()
+        .map(|day| day.map(|day| (calendar.add_business_days(day, n) - day) as i64))
+        .collect();
+
+    let ticks_per_day: i64 = match series.dtype() {
+        DataType::Datetime(TimeUnit::Nanoseconds, _) => 86_400_000_000_000,
+        DataType::Datetime(TimeUnit::Microseconds, _) => 86_400_000_000,
+        DataType::Datetime(TimeUnit::Milliseconds, _) => 86_400_000,
+        _ => 1,
+    };
+
+    let physical = series.to_physical_repr().cast(&DataType::Int64)?;
+    let shifted: Int64Chunked = physical
+        .i64()?
+        .into_iter()
+        .zip(shifts)
+        .map(|(value, shift)| Some(value? + shift? * ticks_per_day))
+        .collect();
+
+    // Date 的物理类型是 Int32，需要先转换回 Int32
+    let shifted = match series.dtype() {
+        DataType::Date => shifted.into_series().cast(&DataType::Int32)?,
+        _ => shifted.into_series(),
+    };
+    Ok(shifted.cast(series.dtype())?.with_name(name.into()))
+}
+
+// ============================================================================
+// 年龄 / 司龄
+// ============================================================================
+/// 从 start 到 reference 经过的完整单位数（按日历计算）
+///
+/// 定义为满足 start + n 个单位 <= reference 的最大 n，加月份时月末对齐
+/// （例如 1 月 31 日加 1 个月为 2 月 28 日 / 29 日；2 月 29 日出生的人在平年 2 月 28 日满岁）
+/// reference 早于 start 时结果为负数
+pub fn whole_units_between(start: NaiveDate, reference: NaiveDate, unit: AgeUnit) -> i64 {
+    if reference < start {
+        return -whole_units_between(reference, start, unit);
+    }
+
+    let step = unit.months();
+    let months = (reference.year() - start.year()) as i64 * 12 + reference.month() as i64 - start.month() as i64;
+    let mut n = months.max(0) as u32 / step;
+
+    // 估算值最多多 1（参照日期的日小于开始日期的日时）
+    while n > 0
+        && start
+            .checked_add_months(Months::new(n * step))
+            .is_none_or(|date| date > reference)
+    {
+        n -= 1;
+    }
+    n as i64
+}
+
+/// 计算年龄 / 司龄列（Int64）
+///
+/// 参数：
+/// - column: 出生日期或开始日期列
+/// - reference_column: 参照日期列（优先使用）
+/// - reference_date: 参照日期（reference_column 为 None 时使用）
+/// - unit: 年或月
+/// - name: 输出列名
+///
+/// 任一日期为空时结果为空值
+pub fn compute_age(
+    df: &DataFrame,
+    column: &str,
+    reference_column: Option<&str>,
+    reference_date: NaiveDate,
+    unit: AgeUnit,
+    name: &str,
+) -> Result<Series> {
+    let to_date = |day: i32| NaiveDate::from_num_days_from_ce_opt(day + UNIX_EPOCH_DAYS_FROM_CE);
+
+    let starts = day_numbers(df, column)?;
+    let ages: Int64Chunked = match reference_column {
+        Some(reference) => {
+            let references = day_numbers(df, reference)?;
+            starts
+                .into_iter()
+                .zip(&references)
+                .map(|(s, r)| Some(whole_units_between(to_date(s?)?, to_date(r?)?, unit)))
+                .collect()
+        }
+        None => starts
+            .into_iter()
+            .map(|s| Some(whole_units_between(to_date(s?)?, reference_date, unit)))
+            .collect(),
+    };
+
+    Ok(ages.with_name(name.into()).into_series())
+}
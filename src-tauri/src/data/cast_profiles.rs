@@ -0,0 +1,196 @@
+// ============================================================================
+// data/cast_profiles.rs - 类型转换预设
+// ============================================================================
+// 这个文件负责按列名模式批量转换类型：
+// - 转换预设：以 JSON 保存在应用配置目录下的 cast_profiles.json
+// - 校验规则，按列名模式把规则解析为 列名 -> 规则 的映射
+// - 转换单列类型（字符串 → Date / Datetime 时可以指定解析格式）
+
+use crate::data::decimal::parse_dtype_name;
+use crate::data::duration::{parse_duration_column, parse_time_column};
+use crate::data::export::write_atomic;
+use crate::data::format::validate_date_format;
+use crate::error::{DataAnalystError, Result};
+use crate::models::{CastProfile, CastRule};
+use polars::prelude::*;
+use std::io::Write;
+use std::path::Path;
+
+/// 转换预设文件名（位于应用配置目录）
+pub const CAST_PROFILES_FILE_NAME: &str = "cast_profiles.json";
+
+// ============================================================================
+// 校验
+// ============================================================================
+/// 检查转换规则是否有效
+pub fn validate_cast_rules(rules: &[CastRule]) -> Result<()> {
+    let invalid = |message: String| -> Result<()> { Err(DataAnalystError::InvalidOperation(message)) };
+
+    if rules.is_empty() {
+        return invalid("至少需要一条转换规则".to_string());
+    }
+
+    for rule in rules {
+        if rule.pattern.trim().is_empty() {
+            return invalid("列名模式不能为空".to_string());
+        }
+
+        let data_type = parse_dtype_name(&rule.target_type).map_err(DataAnalystError::InvalidOperation)?;
+        if let Some(format) = &rule.format {
+            if !matches!(data_type, DataType::Date | DataType::Datetime(_, _)) {
+                return invalid(format!(
+                    "规则 '{}' 的目标类型 {} 不支持解析格式（只有 Date / Datetime 可以指定格式）",
+                    rule.pattern, rule.target_type
+                ));
+            }
+            validate_date_format(format)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 检查转换预设是否有效
+pub fn validate_cast_profile(profile: &CastProfile) -> Result<()> {
+    if profile.name.trim().is_empty() {
+        return Err(DataAnalystError::InvalidOperation("预设名称不能为空".to_string()));
+    }
+    validate_cast_rules(&profile.rules)
+}
+
+// ============================================================================
+// 预设存储
+// ============================================================================
+/// 读取转换预设（文件不存在时返回空列表）
+pub fn load_cast_profiles(path: &Path) -> Result<Vec<CastProfile>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| DataAnalystError::InvalidDataFormat(format!("转换预设文件格式错误: {}", e)))
+}
+
+/// 保存转换预设（先写临时文件再替换）
+pub fn save_cast_profiles(path: &Path, profiles: &[CastProfile]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let content = serde_json::to_string_pretty(profiles)?;
+    write_atomic(&path.to_string_lossy(), |file| {
+        file.write_all(content.as_bytes())?;
+        Ok(())
+    })
+}
+
+/// 按名称查找转换预设
+pub fn find_cast_profile(path: &Path, name: &str) -> Result<CastProfile> {
+    load_cast_profiles(path)?
+        .into_iter()
+        .find(|profile| profile.name == name)
+        .ok_or_else(|| DataAnalystError::InvalidOperation(format!("转换预设 '{}' 不存在", name)))
+}
+
+// ============================================================================
+// 列名匹配
+// ============================================================================
+/// 列名是否匹配模式（* 匹配任意多个字符，? 匹配单个字符，不区分大小写）
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.trim().to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+
+    // 贪心匹配，遇到不匹配时回溯到上一个 * 多吞一个字符
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// 按规则找出要转换的列（按列顺序返回，一列只使用第一条匹配的规则）
+///
+/// 没有任何列匹配时返回错误
+pub fn resolve_cast_rules<'a>(df: &DataFrame, rules: &'a [CastRule]) -> Result<Vec<(String, &'a CastRule)>> {
+    let resolved: Vec<(String, &CastRule)> = df
+        .get_column_names()
+        .into_iter()
+        .filter_map(|name| {
+            rules
+                .iter()
+                .find(|rule| matches_pattern(&rule.pattern, name))
+                .map(|rule| (name.to_string(), rule))
+        })
+        .collect();
+
+    if resolved.is_empty() {
+        let patterns: Vec<&str> = rules.iter().map(|rule| rule.pattern.as_str()).collect();
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "没有列匹配转换规则: {}",
+            patterns.join(", ")
+        )));
+    }
+
+    Ok(resolved)
+}
+
+// ============================================================================
+// 转换单列
+// ============================================================================
+/// 转换单列类型
+///
+/// - 字符串 → Duration / Time 按文本解析（例如 "2d 03:15:00"、"08:30:00"）
+/// - 字符串 → Date / Datetime 且指定了格式时按格式解析（例如 "%d.%m.%Y"），无法解析的值报错
+/// - 其他情况使用 Polars 的类型转换
+pub fn cast_column(column: &Column, data_type: &DataType, format: Option<&str>) -> Result<Column> {
+    let casted = match (column.dtype(), data_type, format) {
+        (DataType::String, DataType::Date | DataType::Datetime(_, _), Some(format)) => {
+            let options = StrptimeOptions {
+                format: Some(format.into()),
+                strict: true,
+                exact: true,
+                cache: true,
+            };
+            let name = column.name().clone();
+            let expr = match data_type {
+                DataType::Datetime(time_unit, time_zone) => {
+                    col(name.clone())
+                        .str()
+                        .to_datetime(Some(*time_unit), time_zone.clone(), options, lit("raise"))
+                }
+                _ => col(name.clone()).str().to_date(options),
+            };
+            DataFrame::new(vec![column.clone()])?
+                .lazy()
+                .select([expr])
+                .collect()?
+                .column(&name)?
+                .clone()
+        }
+        (DataType::String, DataType::Duration(_), _) => {
+            Column::from(parse_duration_column(column.as_materialized_series())?)
+        }
+        (DataType::String, DataType::Time, _) => Column::from(parse_time_column(column.as_materialized_series())?),
+        _ => column.cast(data_type)?,
+    };
+    Ok(casted)
+}
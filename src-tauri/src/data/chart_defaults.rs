@@ -0,0 +1,56 @@
+// ============================================================================
+// data/chart_defaults.rs - 图表默认设置
+// ============================================================================
+// 这个文件负责图表默认设置的读写和校验
+// 默认设置以 JSON 保存在应用配置目录下的 chart_defaults.json，文件不存在时使用内置默认值
+
+use crate::data::export::write_atomic;
+use crate::error::{DataAnalystError, Result};
+use crate::models::chart::ChartDefaults;
+use std::io::Write;
+use std::path::Path;
+
+/// 图表默认设置文件名（位于应用配置目录）
+pub const CHART_DEFAULTS_FILE_NAME: &str = "chart_defaults.json";
+
+/// 检查默认设置是否有效（所有数量都必须大于 0）
+pub fn validate_chart_defaults(defaults: &ChartDefaults) -> Result<()> {
+    let limits = [
+        ("直方图分箱数量", defaults.histogram_bins),
+        ("最大绘制行数", defaults.max_points),
+        ("分面数量", defaults.facet_limit),
+        ("系列数量", defaults.max_series),
+        ("饼图扇区数量", defaults.max_pie_slices),
+        ("分类数量", defaults.max_categories),
+    ];
+    for (label, value) in limits {
+        if value == 0 {
+            return Err(DataAnalystError::InvalidOperation(format!("{}必须大于 0", label)));
+        }
+    }
+    Ok(())
+}
+
+/// 读取图表默认设置（文件不存在时返回内置默认值，缺少的字段使用内置默认值）
+pub fn load_chart_defaults(path: &Path) -> Result<ChartDefaults> {
+    if !path.exists() {
+        return Ok(ChartDefaults::default());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| DataAnalystError::InvalidDataFormat(format!("图表默认设置文件格式错误: {}", e)))
+}
+
+/// 保存图表默认设置（先写临时文件再替换）
+pub fn save_chart_defaults(path: &Path, defaults: &ChartDefaults) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let content = serde_json::to_string_pretty(defaults)?;
+    write_atomic(&path.to_string_lossy(), |file| {
+        file.write_all(content.as_bytes())?;
+        Ok(())
+    })
+}
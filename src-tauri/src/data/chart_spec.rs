@@ -0,0 +1,209 @@
+// ============================================================================
+// data/chart_spec.rs - 独立图表规格
+// ============================================================================
+// 这个文件负责把图表数据转换为独立的 ECharts option：
+// - 数据以 dataset.source 内嵌，文件本身就可以渲染，不依赖本应用
+// - 各图表类型的 option 与界面中的图表保持一致（坐标轴、图例、缩放条、系列）
+// - 指定 axis_range 时，建议的 Y 轴范围写入 yAxis 的 min / max
+
+use crate::models::chart::{AxisRange, ChartAxis, ChartConfig, ChartData, ChartFacetSpec, ChartSpec, ChartType};
+use serde_json::{Value as JsonValue, json};
+
+/// 图表规格格式版本
+pub const CHART_SPEC_FORMAT_VERSION: u32 = 1;
+
+/// 由图表配置和生成的图表数据构建独立图表规格
+pub fn build_chart_spec(config: &ChartConfig, data: &ChartData) -> ChartSpec {
+    let (option, facets) = match &data.facets {
+        Some(facets) => (
+            None,
+            facets
+                .iter()
+                .map(|facet| {
+                    let mut option = echarts_option(config, &facet.dataset, &facet.axis_ranges);
+                    option["title"] = json!({ "text": facet_title(config, &facet.value) });
+                    ChartFacetSpec {
+                        value: facet.value.clone(),
+                        row_count: facet.row_count,
+                        option,
+                    }
+                })
+                .collect(),
+        ),
+        None => (
+            Some(echarts_option(config, &data.dataset, &data.axis_ranges)),
+            Vec::new(),
+        ),
+    };
+
+    ChartSpec {
+        format_version: CHART_SPEC_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        config: config.clone(),
+        data_count: data.data_count,
+        option,
+        facets,
+    }
+}
+
+/// 分面图表的标题："{标题} - {分面值}"（没有标题时只显示分面值）
+fn facet_title(config: &ChartConfig, value: &str) -> String {
+    match &config.title {
+        Some(title) => format!("{} - {}", title, value),
+        None => value.to_string(),
+    }
+}
+
+/// 构建单个图表的 ECharts option（dataset 第一行是列名）
+fn echarts_option(config: &ChartConfig, dataset: &[Vec<JsonValue>], axis_ranges: &[AxisRange]) -> JsonValue {
+    let dimensions: Vec<String> = dataset
+        .first()
+        .map(|header| {
+            header
+                .iter()
+                .map(|name| match name {
+                    JsonValue::String(name) => name.clone(),
+                    other => other.to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let x = dimensions.first().cloned().unwrap_or_default();
+    let y_columns = dimensions.iter().skip(1);
+
+    let grid = json!({ "left": "3%", "right": "4%", "bottom": "15%", "top": "10%" });
+    let data_zoom = json!([{ "type": "slider", "start": 0, "end": 100 }, { "type": "inside" }]);
+
+    let mut option = match config.chart_type {
+        ChartType::Line => {
+            let line_style = config.line_style.as_deref().unwrap_or("line");
+            let right_columns = config.y_axis_right_columns.clone().unwrap_or_default();
+            let dual_axis = !right_columns.is_empty();
+            let series: Vec<JsonValue> = y_columns
+                .map(|name| {
+                    let mut series = json!({
+                        "name": name,
+                        "type": "line",
+                        "smooth": true,
+                        "encode": { "x": x, "y": name },
+                    });
+                    if dual_axis {
+                        series["yAxisIndex"] = json!(if right_columns.contains(name) { 1 } else { 0 });
+                    }
+                    if line_style == "area" || line_style == "stack_area" {
+                        series["areaStyle"] = json!({});
+                    }
+                    if line_style == "stack_area" {
+                        series["stack"] = json!("total");
+                    }
+                    series
+                })
+                .collect();
+            let y_axis = if dual_axis {
+                let mut right_axis = value_axis(axis_ranges, ChartAxis::YRight);
+                right_axis["position"] = json!("right");
+                json!([value_axis(axis_ranges, ChartAxis::Y), right_axis])
+            } else {
+                value_axis(axis_ranges, ChartAxis::Y)
+            };
+            json!({
+                "tooltip": { "trigger": "axis" },
+                "grid": grid,
+                "xAxis": { "type": "category" },
+                "yAxis": y_axis,
+                "dataZoom": data_zoom,
+                "series": series,
+            })
+        }
+        ChartType::Bar | ChartType::Scatter => {
+            let (series_type, x_axis_type) = match config.chart_type {
+                ChartType::Scatter => ("scatter", "value"),
+                _ => ("bar", "category"),
+            };
+            let series: Vec<JsonValue> = y_columns
+                .map(|name| json!({ "name": name, "type": series_type, "encode": { "x": x, "y": name } }))
+                .collect();
+            let data_zoom = if matches!(config.chart_type, ChartType::Scatter) {
+                json!([
+                    { "type": "slider", "xAxisIndex": 0, "start": 0, "end": 100 },
+                    { "type": "slider", "yAxisIndex": 0, "start": 0, "end": 100 },
+                    { "type": "inside" }
+                ])
+            } else {
+                data_zoom
+            };
+            json!({
+                "tooltip": { "trigger": "axis" },
+                "grid": grid,
+                "xAxis": { "type": x_axis_type },
+                "yAxis": value_axis(axis_ranges, ChartAxis::Y),
+                "dataZoom": data_zoom,
+                "series": series,
+            })
+        }
+        ChartType::Histogram => json!({
+            "tooltip": { "trigger": "axis" },
+            "grid": grid,
+            "xAxis": { "type": "category", "axisLabel": { "rotate": 30 } },
+            "yAxis": { "type": "value" },
+            "dataZoom": data_zoom,
+            "series": [{ "type": "bar", "encode": { "x": x, "y": dimensions.get(1) } }],
+        }),
+        ChartType::Pie => json!({
+            "tooltip": { "trigger": "item", "formatter": "{b}: {c} ({d}%)" },
+            "series": [{
+                "type": "pie",
+                "radius": "60%",
+                "center": ["50%", "50%"],
+                "encode": { "itemName": x, "value": dimensions.get(1) },
+                "emphasis": {
+                    "itemStyle": { "shadowBlur": 10, "shadowOffsetX": 0, "shadowColor": "rgba(0, 0, 0, 0.5)" }
+                },
+            }],
+        }),
+        ChartType::Heatmap => {
+            let values = dataset
+                .iter()
+                .skip(1)
+                .filter_map(|row| row.get(2).and_then(JsonValue::as_f64));
+            let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+                (min.min(v), max.max(v))
+            });
+            let (min, max) = if min.is_finite() { (min, max) } else { (0.0, 0.0) };
+            json!({
+                "tooltip": { "position": "top" },
+                "grid": grid,
+                "xAxis": { "type": "category" },
+                "yAxis": { "type": "category" },
+                "visualMap": {
+                    "min": min,
+                    "max": max,
+                    "calculable": true,
+                    "orient": "horizontal",
+                    "left": "center",
+                    "bottom": 0
+                },
+                "series": [{
+                    "type": "heatmap",
+                    "encode": { "x": x, "y": dimensions.get(1), "value": dimensions.get(2) },
+                }],
+            })
+        }
+    };
+
+    option["dataset"] = json!({ "source": dataset, "dimensions": dimensions });
+    option["legend"] = json!({});
+    if let Some(title) = &config.title {
+        option["title"] = json!({ "text": title });
+    }
+    option
+}
+
+/// 数值坐标轴，有建议范围时写入 min / max
+fn value_axis(axis_ranges: &[AxisRange], axis: ChartAxis) -> JsonValue {
+    match axis_ranges.iter().find(|range| range.axis == axis) {
+        Some(range) => json!({ "type": "value", "min": range.min, "max": range.max }),
+        None => json!({ "type": "value" }),
+    }
+}
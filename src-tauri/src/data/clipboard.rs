@@ -0,0 +1,74 @@
+// ============================================================================
+// data/clipboard.rs - 复制到剪贴板的文本
+// ============================================================================
+// 这个文件负责把 DataFrame 渲染为可以粘贴的文本：
+// - TSV: 制表符分隔，粘贴到 Excel 时自动分列
+// - CSV: 逗号分隔
+// - Markdown: 表格，粘贴到邮件、文档、聊天工具
+//
+// 列表、结构体列渲染为 JSON 文本；空值渲染为空字符串
+
+use crate::data::decimal::is_numeric_dtype;
+use crate::data::nested::nested_columns_to_json;
+use crate::error::{DataAnalystError, Result};
+use crate::models::ClipboardFormat;
+use polars::prelude::*;
+
+/// 将 DataFrame 渲染为剪贴板文本（包含表头）
+pub fn render_clipboard_text(df: &DataFrame, format: ClipboardFormat) -> Result<String> {
+    let mut df = nested_columns_to_json(df)?;
+    match format {
+        ClipboardFormat::Tsv => delimited_text(&mut df, b'\t'),
+        ClipboardFormat::Csv => delimited_text(&mut df, b','),
+        ClipboardFormat::Markdown => Ok(markdown_table(&df)),
+    }
+}
+
+/// 分隔符文本（值中含有分隔符、引号或换行时加引号）
+fn delimited_text(df: &mut DataFrame, separator: u8) -> Result<String> {
+    let mut buffer = Vec::new();
+    CsvWriter::new(&mut buffer)
+        .include_header(true)
+        .with_separator(separator)
+        .finish(df)?;
+    String::from_utf8(buffer).map_err(|e| DataAnalystError::InvalidDataFormat(format!("文本编码错误: {}", e)))
+}
+
+/// Markdown 表格（数值列右对齐）
+fn markdown_table(df: &DataFrame) -> String {
+    let columns = df.get_columns();
+    let header: Vec<String> = columns.iter().map(|column| markdown_cell(column.name())).collect();
+    let separator: Vec<&str> = columns
+        .iter()
+        .map(|column| {
+            if is_numeric_dtype(column.dtype()) {
+                "---:"
+            } else {
+                "---"
+            }
+        })
+        .collect();
+
+    let mut lines = vec![markdown_row(&header), markdown_row(&separator)];
+    for idx in 0..df.height() {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| match column.get(idx) {
+                Ok(AnyValue::Null) | Err(_) => String::new(),
+                Ok(value) => markdown_cell(&value.str_value()),
+            })
+            .collect();
+        lines.push(markdown_row(&cells));
+    }
+    lines.join("\n") + "\n"
+}
+
+/// 转义单元格中的竖线，换行替换为空格（Markdown 表格的一行不能跨行）
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace("\r\n", " ").replace(['\n', '\r'], " ")
+}
+
+fn markdown_row<S: AsRef<str>>(cells: &[S]) -> String {
+    let cells: Vec<&str> = cells.iter().map(|cell| cell.as_ref()).collect();
+    format!("| {} |", cells.join(" | "))
+}
@@ -0,0 +1,174 @@
+// ============================================================================
+// data/cohort.rs - 同期群留存表
+// ============================================================================
+// 这个文件负责生成经典的同期群留存矩阵（同期群 × 偏移周期）：
+// 1. 每个用户取最早的注册日期，按周期粒度截断，得到所属同期群
+// 2. 每条活跃记录按同样的粒度截断，计算与同期群相差的周期数（偏移）
+// 3. 统计每个（同期群, 偏移）的去重活跃用户数，除以同期群人数得到留存率
+//
+// 早于注册日期的活跃记录会被忽略；日期时间列按日期部分计算
+
+use crate::error::{DataAnalystError, Result};
+use crate::models::history::{CohortLayout, PeriodBucket};
+use polars::prelude::*;
+
+/// 长表的列名
+pub const COHORT_COLUMN: &str = "cohort";
+pub const OFFSET_COLUMN: &str = "period_offset";
+pub const COHORT_SIZE_COLUMN: &str = "cohort_size";
+pub const ACTIVE_USERS_COLUMN: &str = "active_users";
+pub const RETENTION_COLUMN: &str = "retention";
+
+/// 活跃周期与同期群之间相差的周期数（两者都已截断到周期起点）
+fn offset_expr(cohort: Expr, active: Expr, period: PeriodBucket) -> Expr {
+    let days = |e: Expr| e.cast(DataType::Int64);
+    let months = |e: Expr| e.clone().dt().year().cast(DataType::Int64) * lit(12) + e.dt().month().cast(DataType::Int64);
+
+    match period {
+        PeriodBucket::Day => days(active) - days(cohort),
+        PeriodBucket::Week => (days(active) - days(cohort)) / lit(7),
+        PeriodBucket::Month => months(active) - months(cohort),
+        PeriodBucket::Quarter => (months(active) - months(cohort)) / lit(3),
+        PeriodBucket::Year => active.dt().year().cast(DataType::Int64) - cohort.dt().year().cast(DataType::Int64),
+    }
+}
+
+// ============================================================================
+// 留存表计算
+// ============================================================================
+/// 生成同期群留存表
+///
+/// 参数：
+/// - user_column: 用户 ID 列
+/// - signup_column: 注册日期列（Date / Datetime）
+/// - activity_column: 活跃日期列（Date / Datetime）
+/// - period: 同期群和偏移的周期粒度
+/// - layout: 结果布局
+/// - max_offset: 最大偏移周期数（None 表示不限制）
+///
+/// 长表列：cohort, period_offset, cohort_size, active_users, retention
+/// 矩阵列：cohort, cohort_size, period_0, period_1, ...（值为留存率）
+///
+/// 示例：
+/// ```rust
+/// let df = cohort_table(&df, "user_id", "signup_date", "event_date",
+///     PeriodBucket::Month, CohortLayout::Wide, Some(12))?;
+/// ```
+pub fn cohort_table(
+    df: &DataFrame,
+    user_column: &str,
+    signup_column: &str,
+    activity_column: &str,
+    period: PeriodBucket,
+    layout: CohortLayout,
+    max_offset: Option<u32>,
+) -> Result<DataFrame> {
+    let invalid = |message: String| DataAnalystError::InvalidOperation(message);
+
+    if df.get_column_index(user_column).is_none() {
+        return Err(invalid(format!("列 '{}' 不存在", user_column)));
+    }
+    for name in [signup_column, activity_column] {
+        let dtype = df
+            .column(name)
+            .map_err(|_| invalid(format!("列 '{}' 不存在", name)))?
+            .dtype();
+        if !matches!(dtype, DataType::Date | DataType::Datetime(_, _)) {
+            return Err(invalid(format!(
+                "列 '{}' 不是日期或日期时间类型（当前类型: {:?}）",
+                name, dtype
+            )));
+        }
+    }
+
+    let every = period.every();
+    let base = df.clone().lazy().select([
+        col(user_column).alias("__user"),
+        col(signup_column).cast(DataType::Date).alias("__signup"),
+        col(activity_column).cast(DataType::Date).alias("__activity"),
+    ]);
+
+    // 每个用户所属的同期群（最早的注册日期）
+    let cohorts = base
+        .clone()
+        .filter(col("__user").is_not_null().and(col("__signup").is_not_null()))
+        .group_by([col("__user")])
+        .agg([col("__signup").min().dt().truncate(lit(every)).alias(COHORT_COLUMN)]);
+
+    let sizes = cohorts
+        .clone()
+        .group_by([col(COHORT_COLUMN)])
+        .agg([len().alias(COHORT_SIZE_COLUMN)]);
+
+    let mut activity = base
+        .filter(col("__activity").is_not_null())
+        .select([
+            col("__user"),
+            col("__activity").dt().truncate(lit(every)).alias("__active"),
+        ])
+        .join(
+            cohorts,
+            [col("__user")],
+            [col("__user")],
+            JoinArgs::new(JoinType::Inner),
+        )
+        .with_column(offset_expr(col(COHORT_COLUMN), col("__active"), period).alias(OFFSET_COLUMN))
+        .filter(col(OFFSET_COLUMN).gt_eq(lit(0)));
+    if let Some(max_offset) = max_offset {
+        activity = activity.filter(col(OFFSET_COLUMN).lt_eq(lit(max_offset as i64)));
+    }
+
+    let long = activity
+        .group_by([col(COHORT_COLUMN), col(OFFSET_COLUMN)])
+        .agg([col("__user").n_unique().alias(ACTIVE_USERS_COLUMN)])
+        .join(
+            sizes,
+            [col(COHORT_COLUMN)],
+            [col(COHORT_COLUMN)],
+            JoinArgs::new(JoinType::Inner),
+        )
+        .select([
+            col(COHORT_COLUMN),
+            col(OFFSET_COLUMN),
+            col(COHORT_SIZE_COLUMN),
+            col(ACTIVE_USERS_COLUMN),
+            (col(ACTIVE_USERS_COLUMN).cast(DataType::Float64) / col(COHORT_SIZE_COLUMN).cast(DataType::Float64))
+                .alias(RETENTION_COLUMN),
+        ])
+        .sort([COHORT_COLUMN, OFFSET_COLUMN], SortMultipleOptions::default())
+        .collect()?;
+
+    if long.height() == 0 {
+        return Err(invalid(
+            "没有可以计算留存的数据（用户、注册日期或活跃日期全部为空，或活跃日期都早于注册日期）".to_string(),
+        ));
+    }
+
+    match layout {
+        CohortLayout::Long => Ok(long),
+        CohortLayout::Wide => cohort_matrix(long),
+    }
+}
+
+/// 长表转换为矩阵：每个同期群一行，每个偏移一列（没有活跃用户的格子为空值）
+fn cohort_matrix(long: DataFrame) -> Result<DataFrame> {
+    let max_offset = long.column(OFFSET_COLUMN)?.i64()?.max().unwrap_or(0);
+
+    let offset_columns: Vec<Expr> = (0..=max_offset)
+        .map(|offset| {
+            col(RETENTION_COLUMN)
+                .filter(col(OFFSET_COLUMN).eq(lit(offset)))
+                .first()
+                .alias(format!("period_{}", offset))
+        })
+        .collect();
+
+    let wide = long
+        .lazy()
+        .group_by([col(COHORT_COLUMN), col(COHORT_SIZE_COLUMN)])
+        .agg(offset_columns)
+        .sort([COHORT_COLUMN], SortMultipleOptions::default())
+        .collect()?;
+
+    Ok(wide)
+}
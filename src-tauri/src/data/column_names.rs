@@ -0,0 +1,70 @@
+// ============================================================================
+// data/column_names.rs - 新列的列名冲突处理
+// ============================================================================
+// 这个文件负责按 ColumnConflictPolicy 决定操作生成的新列使用什么列名：
+// - error: 与已有列同名时返回 ColumnConflict 错误
+// - suffix: 自动加数字后缀（"revenue" → "revenue_1"、"revenue_2"），并记录改名
+// - overwrite: 直接使用原列名，覆盖已有列
+//
+// 同一次操作生成的多个新列之间也按同样的规则处理
+
+use crate::error::{DataAnalystError, Result};
+use crate::models::history::{ColumnConflictPolicy, ColumnRename};
+use polars::prelude::*;
+use std::collections::HashSet;
+
+/// OutputColumns - 一次操作中已占用的列名和被改名的新列
+pub struct OutputColumns {
+    policy: ColumnConflictPolicy,
+    taken: HashSet<String>,
+    renamed: Vec<ColumnRename>,
+}
+
+impl OutputColumns {
+    /// 以 DataFrame 现有的列名作为已占用列名
+    pub fn new(df: &DataFrame, policy: ColumnConflictPolicy) -> Self {
+        Self {
+            policy,
+            taken: df.get_column_names().iter().map(|name| name.to_string()).collect(),
+            renamed: Vec::new(),
+        }
+    }
+
+    /// 决定新列的实际列名
+    ///
+    /// 参数：
+    /// - requested: 操作原本要生成的列名
+    pub fn resolve(&mut self, requested: &str) -> Result<String> {
+        if !self.taken.contains(requested) {
+            self.taken.insert(requested.to_string());
+            return Ok(requested.to_string());
+        }
+
+        match self.policy {
+            ColumnConflictPolicy::Error => Err(DataAnalystError::ColumnConflict(requested.to_string())),
+            ColumnConflictPolicy::Overwrite => Ok(requested.to_string()),
+            ColumnConflictPolicy::Suffix => {
+                let actual = (1..)
+                    .map(|n| format!("{}_{}", requested, n))
+                    .find(|candidate| !self.taken.contains(candidate))
+                    .expect("列名后缀用尽");
+                self.taken.insert(actual.clone());
+                self.renamed.push(ColumnRename {
+                    requested: requested.to_string(),
+                    actual: actual.clone(),
+                });
+                Ok(actual)
+            }
+        }
+    }
+
+    /// 释放列名（该列会被新列替换，例如展开结构体时被移除的原列）
+    pub fn release(&mut self, name: &str) {
+        self.taken.remove(name);
+    }
+
+    /// 被自动改名的新列（记录到历史条目）
+    pub fn into_renamed(self) -> Vec<ColumnRename> {
+        self.renamed
+    }
+}
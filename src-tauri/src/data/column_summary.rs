@@ -0,0 +1,121 @@
+// ============================================================================
+// data/column_summary.rs - 列摘要
+// ============================================================================
+// 这个文件负责数据集元信息中的列摘要（表头提示使用）：
+// - 列摘要设置以 JSON 保存在应用配置目录下的 column_summaries.json，文件不存在时不计算
+// - 按列类型计算最小值、最大值，以及不同值数量
+//   （行数较多时按固定种子抽样，用 GEE 估计量估计不同值数量，避免每次操作后都完整去重）
+
+use crate::data::export::write_atomic;
+use crate::error::{DataAnalystError, Result};
+use crate::models::{ColumnSummary, ColumnSummarySettings, DatasetInfo};
+use polars::prelude::*;
+use std::io::Write;
+use std::path::Path;
+
+/// 列摘要设置文件名（位于应用配置目录）
+pub const COLUMN_SUMMARIES_FILE_NAME: &str = "column_summaries.json";
+
+/// 超过该行数时按抽样估计不同值数量
+const DISTINCT_SAMPLE_ROWS: usize = 100_000;
+
+/// 读取列摘要设置（文件不存在时返回内置默认值，即不计算）
+pub fn load_column_summary_settings(path: &Path) -> Result<ColumnSummarySettings> {
+    if !path.exists() {
+        return Ok(ColumnSummarySettings::default());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| DataAnalystError::InvalidDataFormat(format!("列摘要设置文件格式错误: {}", e)))
+}
+
+/// 保存列摘要设置（先写临时文件再替换）
+pub fn save_column_summary_settings(path: &Path, settings: &ColumnSummarySettings) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let content = serde_json::to_string_pretty(settings)?;
+    write_atomic(&path.to_string_lossy(), |file| {
+        file.write_all(content.as_bytes())?;
+        Ok(())
+    })
+}
+
+/// 元信息是否已经带有列摘要（列表、结构体列没有摘要，不计入）
+pub fn has_column_summaries(info: &DatasetInfo, df: &DataFrame) -> bool {
+    info.columns.iter().all(|column| {
+        column.summary.is_some() || df.column(&column.name).map(|c| c.dtype().is_nested()).unwrap_or(true)
+    })
+}
+
+/// 为元信息中的每一列计算列摘要
+pub fn fill_column_summaries(info: &mut DatasetInfo, df: &DataFrame) -> Result<()> {
+    for column in info.columns.iter_mut() {
+        if let Ok(series) = df.column(&column.name) {
+            column.summary = summarize_column(series)?;
+        }
+    }
+    Ok(())
+}
+
+/// 计算单列的摘要（列表、结构体列返回 None）
+pub fn summarize_column(column: &Column) -> Result<Option<ColumnSummary>> {
+    let dtype = column.dtype();
+    if dtype.is_nested() {
+        return Ok(None);
+    }
+
+    let series = column.as_materialized_series();
+    let (min, max) = if dtype.is_numeric() || dtype.is_temporal() || matches!(dtype, DataType::String) {
+        let text = |scalar: Scalar| -> Option<String> {
+            let value = scalar.value().clone();
+            (!value.is_null()).then(|| value.str_value().to_string())
+        };
+        (text(series.min_reduce()?), text(series.max_reduce()?))
+    } else {
+        (None, None)
+    };
+
+    let (distinct_count, distinct_exact) = estimate_distinct(series)?;
+
+    Ok(Some(ColumnSummary {
+        min,
+        max,
+        distinct_count,
+        distinct_exact,
+    }))
+}
+
+/// 不同值数量：行数不超过 DISTINCT_SAMPLE_ROWS 时精确计算，否则抽样估计
+///
+/// GEE 估计量：sqrt(N / n) × f1 + (d - f1)
+/// 其中 n 为样本行数，d 为样本中的不同值数量，f1 为样本中只出现一次的值的数量
+fn estimate_distinct(series: &Series) -> Result<(usize, bool)> {
+    let total = series.len();
+    if total <= DISTINCT_SAMPLE_ROWS {
+        return Ok((series.n_unique()?, true));
+    }
+
+    let sample = series
+        .sample_n(DISTINCT_SAMPLE_ROWS, false, false, Some(0))?
+        .with_name("value".into());
+    let counts = DataFrame::new(vec![sample.into_column()])?
+        .lazy()
+        .group_by([col("value")])
+        .agg([len().alias("count")])
+        .collect()?;
+
+    let distinct = counts.height();
+    let singletons = counts
+        .column("count")?
+        .as_materialized_series()
+        .equal(1)?
+        .sum()
+        .unwrap_or(0) as usize;
+
+    let scale = (total as f64 / DISTINCT_SAMPLE_ROWS as f64).sqrt();
+    let estimate = (scale * singletons as f64).round() as usize + (distinct - singletons);
+    Ok((estimate.clamp(distinct, total), false))
+}
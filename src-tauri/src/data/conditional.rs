@@ -0,0 +1,227 @@
+// ============================================================================
+// data/conditional.rs - 条件格式计算
+// ============================================================================
+// 这个文件负责：
+// 1. 校验条件格式规则（列是否存在、数值条件是否用于数值列）
+// 2. 将规则转换为 Polars 谓词表达式
+// 3. 在当前页数据上计算每个单元格的样式标签
+//
+// 离群值等依赖整列统计量的条件，先在完整数据上计算统计量，
+// 再以字面量的形式放入表达式，保证分页前后结果一致；
+// 统计量按历史条目缓存，翻页时不会重复扫描完整数据
+
+use crate::data::decimal::is_numeric_dtype;
+use crate::data::filter::build_sql_predicate;
+use crate::error::{DataAnalystError, Result};
+use crate::models::{CellStyle, ConditionalCondition, ConditionalFormatRule};
+use polars::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, PoisonError};
+
+// ============================================================================
+// 离群值统计量缓存
+// ============================================================================
+/// 离群值规则使用的列统计量（列名 -> 均值和标准差，None 表示无法计算）
+pub type ColumnMoments = HashMap<String, Option<(f64, f64)>>;
+
+/// OutlierMomentsCache - 条件格式离群值规则的列统计量缓存
+///
+/// 历史条目的数据不会改变，同一条目的统计量只需要计算一次；
+/// 只保留一个条目的统计量，切换到其他条目（操作、撤销、跳转）时清空
+#[derive(Default)]
+pub struct OutlierMomentsCache {
+    inner: Mutex<(String, ColumnMoments)>,
+}
+
+impl OutlierMomentsCache {
+    /// 使用指定历史条目的统计量缓存
+    pub fn with_entry<T>(&self, entry_id: &str, f: impl FnOnce(&mut ColumnMoments) -> T) -> T {
+        let mut cached = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        if cached.0 != entry_id {
+            *cached = (entry_id.to_string(), ColumnMoments::new());
+        }
+        f(&mut cached.1)
+    }
+}
+
+// ============================================================================
+// 规则校验
+// ============================================================================
+/// 校验条件格式规则
+///
+/// - 列必须存在
+/// - 标签不能为空
+/// - 数值条件只能用于数值列
+/// - SQL 条件必须能够解析，引用的列必须存在
+pub fn validate_conditional_rules(df: &DataFrame, rules: &[ConditionalFormatRule]) -> Result<()> {
+    for rule in rules {
+        let column = df
+            .column(&rule.column)
+            .map_err(|_| DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", rule.column)))?;
+
+        if rule.tag.trim().is_empty() {
+            return Err(DataAnalystError::InvalidOperation(format!(
+                "列 '{}' 的条件格式缺少样式标签",
+                rule.column
+            )));
+        }
+
+        if rule.condition.requires_numeric() && !is_numeric_dtype(column.dtype()) {
+            return Err(DataAnalystError::InvalidOperation(format!(
+                "列 '{}' 不是数值列，不能使用数值条件",
+                rule.column
+            )));
+        }
+
+        match &rule.condition {
+            ConditionalCondition::Between { min, max } if min > max => {
+                return Err(DataAnalystError::InvalidOperation(format!(
+                    "区间下限 {} 大于上限 {}",
+                    min, max
+                )));
+            }
+            ConditionalCondition::Outlier { z_score } if *z_score <= 0.0 => {
+                return Err(DataAnalystError::InvalidOperation("离群值阈值必须大于 0".to_string()));
+            }
+            ConditionalCondition::Expression { sql } => {
+                for name in build_sql_predicate(sql)?.meta().root_names() {
+                    if df.get_column_index(&name).is_none() {
+                        return Err(DataAnalystError::InvalidOperation(format!(
+                            "列 '{}' 的条件表达式引用的列 '{}' 不存在",
+                            rule.column, name
+                        )));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// 谓词构建
+// ============================================================================
+/// 将单条规则转换为谓词表达式
+///
+/// 参数：
+/// - full_df: 完整数据（用于计算离群值的均值和标准差）
+/// - rule: 条件格式规则
+/// - moments: full_df 的列统计量缓存
+///
+/// 返回 None 表示规则在当前数据上不会命中任何单元格（例如标准差为 0）
+fn build_rule_predicate(
+    full_df: &DataFrame,
+    rule: &ConditionalFormatRule,
+    moments: &mut ColumnMoments,
+) -> Result<Option<Expr>> {
+    let value = col(rule.column.as_str());
+
+    let predicate = match &rule.condition {
+        ConditionalCondition::Negative => value.lt(lit(0)),
+        ConditionalCondition::Positive => value.gt(lit(0)),
+        ConditionalCondition::IsNull => value.is_null(),
+        ConditionalCondition::AboveThreshold { value: threshold } => value.gt(lit(*threshold)),
+        ConditionalCondition::BelowThreshold { value: threshold } => value.lt(lit(*threshold)),
+        ConditionalCondition::Between { min, max } => value.clone().gt_eq(lit(*min)).and(value.lt_eq(lit(*max))),
+        ConditionalCondition::Outlier { z_score } => {
+            let column_moments = match moments.get(&rule.column) {
+                Some(cached) => *cached,
+                None => {
+                    let computed = compute_moments(full_df, &rule.column)?;
+                    moments.insert(rule.column.clone(), computed);
+                    computed
+                }
+            };
+            let Some((mean, std)) = column_moments else {
+                return Ok(None);
+            };
+
+            let lower = mean - z_score * std;
+            let upper = mean + z_score * std;
+            value.clone().lt(lit(lower)).or(value.gt(lit(upper)))
+        }
+        ConditionalCondition::Expression { sql } => build_sql_predicate(sql)?,
+    };
+
+    // 谓词结果为 null 时视为不命中
+    Ok(Some(predicate.fill_null(lit(false))))
+}
+
+/// 计算列的均值和标准差（样本标准差），无法计算或标准差为 0 时返回 None
+fn compute_moments(full_df: &DataFrame, column: &str) -> Result<Option<(f64, f64)>> {
+    let series = full_df.column(column)?.cast(&DataType::Float64)?;
+    let ca = series.f64()?;
+    Ok(match (ca.mean(), ca.std(1)) {
+        (Some(mean), Some(std)) if std != 0.0 => Some((mean, std)),
+        _ => None,
+    })
+}
+
+// ============================================================================
+// 单元格样式计算
+// ============================================================================
+/// 计算当前页每个单元格的样式标签
+///
+/// 参数：
+/// - full_df: 完整数据
+/// - page_df: 当前页数据（full_df 的切片）
+/// - rules: 条件格式规则（按顺序计算，一个单元格可以有多个标签）
+/// - moments: full_df 的列统计量缓存（见 OutlierMomentsCache）
+///
+/// 用到的列（包括 SQL 条件引用的列）不全存在的规则会被跳过
+///
+/// 返回：只包含至少有一个标签的单元格，按行、列顺序排列
+pub fn compute_cell_styles(
+    full_df: &DataFrame,
+    page_df: &DataFrame,
+    rules: &[ConditionalFormatRule],
+    moments: &mut ColumnMoments,
+) -> Result<Vec<CellStyle>> {
+    let mut predicates = Vec::new();
+    let mut applied_rules = Vec::new();
+
+    for rule in rules {
+        let Some(columns) = rule.referenced_columns() else {
+            continue;
+        };
+        if columns.iter().any(|name| page_df.get_column_index(name).is_none()) {
+            continue;
+        }
+        if let Some(predicate) = build_rule_predicate(full_df, rule, moments)? {
+            predicates.push(predicate.alias(format!("__rule_{}", applied_rules.len())));
+            applied_rules.push(rule);
+        }
+    }
+
+    if predicates.is_empty() || page_df.height() == 0 {
+        return Ok(Vec::new());
+    }
+
+    let matches = page_df.clone().lazy().select(predicates).collect()?;
+
+    // (行号, 列索引) -> 标签
+    let mut styles: BTreeMap<(usize, usize), Vec<String>> = BTreeMap::new();
+
+    for (idx, rule) in applied_rules.iter().enumerate() {
+        let column_idx = page_df.get_column_index(&rule.column).unwrap_or_default();
+        let hits = matches.column(&format!("__rule_{}", idx))?.bool()?.clone();
+
+        for (row, hit) in hits.into_iter().enumerate() {
+            if hit == Some(true) {
+                styles.entry((row, column_idx)).or_default().push(rule.tag.clone());
+            }
+        }
+    }
+
+    let columns = page_df.get_column_names();
+    Ok(styles
+        .into_iter()
+        .map(|((row, column_idx), tags)| CellStyle {
+            row,
+            column: columns[column_idx].to_string(),
+            tags,
+        })
+        .collect())
+}
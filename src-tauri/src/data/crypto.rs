@@ -0,0 +1,242 @@
+// ============================================================================
+// data/crypto.rs - 导出加密
+// ============================================================================
+// 这个文件负责导出时的加密：
+// - 列级加密：AES-256-GCM，密钥由口令经 PBKDF2-HMAC-SHA256 派生
+// - 带密码的 zip：导出文件打包为 WinZip AES-256 加密的 zip
+//
+// 列级密文格式："enc:v1:" + base64(salt(16) + nonce(12) + 密文)
+// 同一次导出共用一个 salt（只派生一次密钥），每个单元格使用独立的随机 nonce
+
+use crate::data::nested::nested_to_json_strings;
+use crate::error::{DataAnalystError, Result};
+use crate::models::ExportEncryption;
+use ::zip::write::SimpleFileOptions;
+use ::zip::{AesMode, CompressionMethod, ZipWriter};
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use polars::prelude::*;
+use sha2::Sha256;
+use std::io::{Seek, Write};
+
+/// 密文前缀（包含格式版本）
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// PBKDF2 迭代次数（测试中减少迭代，避免未优化构建下派生密钥过慢）
+const PBKDF2_ROUNDS: u32 = if cfg!(test) { 1_000 } else { 600_000 };
+
+/// 口令最小长度
+const MIN_SECRET_LEN: usize = 8;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+fn crypto_error(message: impl std::fmt::Display) -> DataAnalystError {
+    DataAnalystError::InvalidOperation(format!("加密失败: {}", message))
+}
+
+// ============================================================================
+// 参数检查
+// ============================================================================
+/// 检查加密选项：口令长度，以及要加密的列是否存在
+pub fn validate_encryption(df: &DataFrame, encryption: &ExportEncryption) -> Result<()> {
+    if encryption.secret().chars().count() < MIN_SECRET_LEN {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "口令至少需要 {} 个字符",
+            MIN_SECRET_LEN
+        )));
+    }
+
+    if let ExportEncryption::Columns { columns, .. } = encryption {
+        if columns.is_empty() {
+            return Err(DataAnalystError::InvalidOperation(
+                "请至少选择一个要加密的列".to_string(),
+            ));
+        }
+        for column in columns {
+            if df.column(column).is_err() {
+                return Err(DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", column)));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// 列级加密
+// ============================================================================
+/// 列加密器：派生一次密钥，逐个单元格加密
+struct ColumnCipher {
+    cipher: Aes256Gcm,
+    salt: [u8; SALT_LEN],
+}
+
+impl ColumnCipher {
+    fn new(passphrase: &str) -> Self {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+
+        let mut key = [0u8; 32];
+        pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), &salt, PBKDF2_ROUNDS, &mut key);
+
+        Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)),
+            salt,
+        }
+    }
+
+    fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+
+        let ciphertext = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+            .map_err(crypto_error)?;
+
+        let mut payload = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len());
+        payload.extend_from_slice(&self.salt);
+        payload.extend_from_slice(&nonce);
+        payload.extend_from_slice(&ciphertext);
+
+        Ok(format!("{}{}", ENCRYPTED_PREFIX, BASE64.encode(payload)))
+    }
+}
+
+/// 加密指定列：非空单元格转为文本后加密，结果列为字符串类型，空值保持为空
+pub fn encrypt_columns(df: &DataFrame, columns: &[String], passphrase: &str) -> Result<DataFrame> {
+    let cipher = ColumnCipher::new(passphrase);
+    let mut result = df.clone();
+
+    for name in columns {
+        let column = df.column(name)?;
+        // 嵌套列先转换为 JSON 文本
+        let values = if column.dtype().is_nested() {
+            nested_to_json_strings(column.as_materialized_series()).into_series()
+        } else {
+            column.as_materialized_series().cast(&DataType::String)?
+        };
+        let encrypted: StringChunked = values
+            .str()?
+            .into_iter()
+            .map(|value| value.map(|v| cipher.encrypt(v)).transpose())
+            .collect::<Result<_>>()?;
+
+        result.with_column(encrypted.with_name(name.as_str().into()).into_series())?;
+    }
+
+    Ok(result)
+}
+
+// ============================================================================
+// 带密码的 zip
+// ============================================================================
+/// zip 中条目的文件名：导出文件名去掉扩展名后加上 extension
+///
+/// 例如 "/path/salary.zip" + "csv" → "salary.csv"
+pub fn zip_entry_name(output_path: &str, extension: &str) -> String {
+    let stem = std::path::Path::new(output_path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "export".to_string());
+    format!("{}.{}", stem, extension)
+}
+
+/// 将内容写入带密码的 zip（单个条目，WinZip AES-256 加密）
+///
+/// write 负责写出条目内容（例如分批写出 CSV）
+pub fn write_encrypted_zip<W, F>(file: W, entry_name: &str, password: &str, write: F) -> Result<()>
+where
+    W: Write + Seek,
+    F: FnOnce(&mut ZipWriter<W>) -> Result<()>,
+{
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true)
+        .with_aes_encryption(AesMode::Aes256, password);
+
+    let mut zip = ZipWriter::new(file);
+    zip.start_file(entry_name, options).map_err(crypto_error)?;
+    write(&mut zip)?;
+    zip.finish().map_err(crypto_error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read};
+
+    /// 按密文格式解密单元格（salt + nonce + 密文）
+    fn decrypt(value: &str, passphrase: &str) -> Option<String> {
+        let payload = BASE64.decode(value.strip_prefix(ENCRYPTED_PREFIX)?).ok()?;
+        let (salt, rest) = payload.split_at(SALT_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+        let mut key = [0u8; 32];
+        pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+        let plaintext = cipher.decrypt(Nonce::from_slice(nonce), ciphertext).ok()?;
+        String::from_utf8(plaintext).ok()
+    }
+
+    #[test]
+    fn encrypted_columns_decrypt_to_original_text() {
+        let df = DataFrame::new(vec![
+            Column::new("name".into(), [Some("alice"), None, Some("bob")]),
+            Column::new("salary".into(), [1000i64, 2000, 3000]),
+        ])
+        .unwrap();
+        let columns = vec!["name".to_string(), "salary".to_string()];
+        let encrypted = encrypt_columns(&df, &columns, "correct horse").unwrap();
+
+        let names: Vec<Option<String>> = encrypted
+            .column("name")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .map(|value| value.map(|v| decrypt(v, "correct horse").unwrap()))
+            .collect();
+        assert_eq!(names, vec![Some("alice".to_string()), None, Some("bob".to_string())]);
+
+        let salaries: Vec<&str> = encrypted
+            .column("salary")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        let decrypted: Vec<String> = salaries.iter().map(|v| decrypt(v, "correct horse").unwrap()).collect();
+        assert_eq!(decrypted, vec!["1000", "2000", "3000"]);
+
+        // 每个单元格使用独立的 nonce，相同口令下密文也不同
+        assert_ne!(salaries[0], salaries[1]);
+        assert_eq!(decrypt(salaries[0], "wrong horse"), None);
+    }
+
+    #[test]
+    fn encrypted_zip_reads_back_only_with_password() {
+        let mut buffer = Cursor::new(Vec::new());
+        write_encrypted_zip(&mut buffer, "data.csv", "zip secret", |zip| {
+            zip.write_all(b"a,b\n1,2\n")?;
+            Ok(())
+        })
+        .unwrap();
+
+        let mut archive = ::zip::ZipArchive::new(Cursor::new(buffer.into_inner())).unwrap();
+        let mut content = String::new();
+        archive
+            .by_name_decrypt("data.csv", b"zip secret")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "a,b\n1,2\n");
+
+        assert!(archive.by_name_decrypt("data.csv", b"wrong").is_err());
+    }
+}
@@ -0,0 +1,366 @@
+// ============================================================================
+// data/db.rs - PostgreSQL / MySQL 数据库连接
+// ============================================================================
+// 这个文件负责从数据库读取数据：
+// 1. 按连接参数建立连接（每次调用单独连接，用完关闭，不保存连接信息）
+// 2. 列出当前数据库中的表和视图
+// 3. 执行查询，并按列的数据库类型转换为 Polars 列
+//
+// 类型对应：
+// - 布尔、整数、浮点数 → Boolean / Int64 / UInt64 / Float64
+// - NUMERIC / DECIMAL → Decimal（精确小数，小数位取整列最大的小数位）
+// - DATE → Date；TIMESTAMP / DATETIME → Datetime（微秒；带时区的时间转换为 UTC）
+// - TIME、UUID、JSON 以及其它文本类型 → String
+// 其它类型（二进制、几何、数组等）需要在 SQL 中转换为文本
+
+use crate::data::decimal::DECIMAL_MAX_PRECISION;
+use crate::error::{DataAnalystError, Result};
+use crate::models::{DatabaseConnection, DatabaseKind, DatabaseTable};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use polars::prelude::*;
+use sqlx::mysql::{MySqlConnectOptions, MySqlPool, MySqlPoolOptions, MySqlRow};
+use sqlx::postgres::{PgConnectOptions, PgPool, PgPoolOptions, PgRow};
+use sqlx::types::{Decimal, JsonValue, Uuid};
+use sqlx::{ColumnIndex, Decode, Executor, Row, Type, TypeInfo};
+use std::time::Duration;
+
+/// 默认连接超时（秒）
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+
+/// 1970-01-01 距离公元元年的天数（Polars Date 以 Unix 纪元为 0）
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+fn db_error(message: impl std::fmt::Display) -> DataAnalystError {
+    DataAnalystError::InvalidOperation(format!("数据库操作失败: {}", message))
+}
+
+// ============================================================================
+// 连接
+// ============================================================================
+/// DbPool - 单连接的连接池（只用于一次调用）
+pub enum DbPool {
+    Postgres(PgPool),
+    Mysql(MySqlPool),
+}
+
+impl DbPool {
+    /// 按连接参数建立连接（超时时间由 timeout_secs 决定）
+    pub async fn connect(connection: &DatabaseConnection) -> Result<Self> {
+        let timeout = Duration::from_secs(connection.timeout_secs.unwrap_or(DEFAULT_CONNECT_TIMEOUT_SECS));
+        let connect_error = |e: sqlx::Error| db_error(format!("无法连接到 {}: {}", connection.source(), e));
+
+        match connection.kind {
+            DatabaseKind::Postgres => {
+                let mut options = PgConnectOptions::new()
+                    .host(&connection.host)
+                    .port(connection.port())
+                    .username(&connection.username)
+                    .database(&connection.database);
+                if let Some(password) = &connection.password {
+                    options = options.password(password);
+                }
+                let pool = PgPoolOptions::new()
+                    .max_connections(1)
+                    .acquire_timeout(timeout)
+                    .connect_with(options)
+                    .await
+                    .map_err(connect_error)?;
+                Ok(DbPool::Postgres(pool))
+            }
+            DatabaseKind::Mysql => {
+                let mut options = MySqlConnectOptions::new()
+                    .host(&connection.host)
+                    .port(connection.port())
+                    .username(&connection.username)
+                    .database(&connection.database);
+                if let Some(password) = &connection.password {
+                    options = options.password(password);
+                }
+                let pool = MySqlPoolOptions::new()
+                    .max_connections(1)
+                    .acquire_timeout(timeout)
+                    .connect_with(options)
+                    .await
+                    .map_err(connect_error)?;
+                Ok(DbPool::Mysql(pool))
+            }
+        }
+    }
+
+    /// 关闭连接
+    pub async fn close(self) {
+        match self {
+            DbPool::Postgres(pool) => pool.close().await,
+            DbPool::Mysql(pool) => pool.close().await,
+        }
+    }
+
+    /// 数据库服务器版本
+    pub async fn server_version(&self) -> Result<String> {
+        let version: std::result::Result<String, sqlx::Error> = match self {
+            DbPool::Postgres(pool) => sqlx::query_scalar("SELECT version()").fetch_one(pool).await,
+            DbPool::Mysql(pool) => sqlx::query_scalar("SELECT VERSION()").fetch_one(pool).await,
+        };
+        version.map_err(db_error)
+    }
+
+    // ========================================================================
+    // 表列表
+    // ========================================================================
+    /// 列出表和视图
+    ///
+    /// PostgreSQL：所有用户模式（排除 pg_catalog、information_schema）
+    /// MySQL：当前数据库
+    pub async fn list_tables(&self) -> Result<Vec<DatabaseTable>> {
+        // information_schema 的列类型各不相同，统一转换为文本
+        let rows: Vec<(String, String, String)> = match self {
+            DbPool::Postgres(pool) => {
+                sqlx::query_as(
+                    "SELECT table_schema::text, table_name::text, table_type::text \
+                     FROM information_schema.tables \
+                     WHERE table_schema NOT IN ('pg_catalog', 'information_schema') \
+                     ORDER BY table_schema, table_name",
+                )
+                .fetch_all(pool)
+                .await
+            }
+            DbPool::Mysql(pool) => {
+                sqlx::query_as(
+                    "SELECT CAST(table_schema AS CHAR), CAST(table_name AS CHAR), CAST(table_type AS CHAR) \
+                     FROM information_schema.tables \
+                     WHERE table_schema = DATABASE() \
+                     ORDER BY table_name",
+                )
+                .fetch_all(pool)
+                .await
+            }
+        }
+        .map_err(db_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(schema, name, table_type)| DatabaseTable {
+                schema,
+                name,
+                is_view: table_type.to_ascii_uppercase().contains("VIEW"),
+            })
+            .collect())
+    }
+
+    // ========================================================================
+    // 查询
+    // ========================================================================
+    /// 生成读取整张表的查询（标识符按数据库的规则加引号）
+    pub fn table_query(&self, schema: Option<&str>, table: &str) -> String {
+        let quote = |name: &str| match self {
+            DbPool::Postgres(_) => format!("\"{}\"", name.replace('"', "\"\"")),
+            DbPool::Mysql(_) => format!("`{}`", name.replace('`', "``")),
+        };
+        match schema {
+            Some(schema) => format!("SELECT * FROM {}.{}", quote(schema), quote(table)),
+            None => format!("SELECT * FROM {}", quote(table)),
+        }
+    }
+
+    /// 执行查询并转换为 DataFrame
+    ///
+    /// limit 指定时把查询包装为子查询再限制行数（只适用于 SELECT 查询）
+    pub async fn query_to_dataframe(&self, sql: &str, limit: Option<usize>) -> Result<DataFrame> {
+        let sql = sql.trim().trim_end_matches(';').trim_end();
+        if sql.is_empty() {
+            return Err(db_error("查询语句为空"));
+        }
+        let sql = match limit {
+            Some(limit) => format!("SELECT * FROM ({}) AS limited_query LIMIT {}", sql, limit),
+            None => sql.to_string(),
+        };
+
+        match self {
+            DbPool::Postgres(pool) => {
+                let rows = sqlx::query(&sql).fetch_all(pool).await.map_err(db_error)?;
+                let specs = match rows.first() {
+                    Some(row) => column_specs(row.columns()),
+                    // 没有数据行时通过 describe 获取列名和类型（生成空表）
+                    None => column_specs(pool.describe(&sql).await.map_err(db_error)?.columns()),
+                };
+                rows_to_dataframe(&rows, &specs, postgres_column)
+            }
+            DbPool::Mysql(pool) => {
+                let rows = sqlx::query(&sql).fetch_all(pool).await.map_err(db_error)?;
+                let specs = match rows.first() {
+                    Some(row) => column_specs(row.columns()),
+                    None => column_specs(pool.describe(&sql).await.map_err(db_error)?.columns()),
+                };
+                rows_to_dataframe(&rows, &specs, mysql_column)
+            }
+        }
+    }
+}
+
+// ============================================================================
+// 类型转换
+// ============================================================================
+/// 列名和数据库类型名
+fn column_specs<C: sqlx::Column>(columns: &[C]) -> Vec<(String, String)> {
+    columns
+        .iter()
+        .map(|column| (column.name().to_string(), column.type_info().name().to_string()))
+        .collect()
+}
+
+/// 按列转换所有行
+fn rows_to_dataframe<R>(
+    rows: &[R],
+    specs: &[(String, String)],
+    build: fn(&[R], usize, &str, &str) -> Result<Column>,
+) -> Result<DataFrame> {
+    let columns = specs
+        .iter()
+        .enumerate()
+        .map(|(index, (name, type_name))| build(rows, index, name, type_name))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(DataFrame::new(columns)?)
+}
+
+/// 读取一列的所有值（NULL 为 None）
+fn decode_values<R, T>(rows: &[R], index: usize) -> Result<Vec<Option<T>>>
+where
+    R: Row,
+    usize: ColumnIndex<R>,
+    T: for<'r> Decode<'r, R::Database> + Type<R::Database>,
+{
+    rows.iter()
+        .map(|row| row.try_get::<Option<T>, _>(index).map_err(db_error))
+        .collect()
+}
+
+fn map_values<T, V>(values: Vec<Option<T>>, convert: impl Fn(T) -> V) -> Vec<Option<V>> {
+    values.into_iter().map(|value| value.map(&convert)).collect()
+}
+
+fn date_column(name: &str, values: Vec<Option<NaiveDate>>) -> Result<Column> {
+    let days = map_values(values, |date| date.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE);
+    Ok(Column::new(name.into(), days).cast(&DataType::Date)?)
+}
+
+fn datetime_column(name: &str, values: Vec<Option<NaiveDateTime>>) -> Result<Column> {
+    let micros = map_values(values, |datetime| datetime.and_utc().timestamp_micros());
+    Ok(Column::new(name.into(), micros).cast(&DataType::Datetime(TimeUnit::Microseconds, None))?)
+}
+
+fn text_column<T: ToString>(name: &str, values: Vec<Option<T>>) -> Column {
+    Column::new(name.into(), map_values(values, |value| value.to_string()))
+}
+
+/// 按每个值的整数尾数和小数位构建 Decimal 列（不经过浮点数，保持精确）
+///
+/// 小数位取整列最大的小数位，小数位较少的值按比例放大尾数；超出 Decimal 精度时返回错误
+fn decimal_column(name: &str, values: Vec<Option<Decimal>>) -> Result<Column> {
+    let scale = values
+        .iter()
+        .flatten()
+        .map(|decimal| decimal.scale())
+        .max()
+        .unwrap_or(0);
+
+    let mantissas = values
+        .into_iter()
+        .map(|value| {
+            value
+                .map(|decimal| {
+                    10i128
+                        .checked_pow(scale - decimal.scale())
+                        .and_then(|factor| decimal.mantissa().checked_mul(factor))
+                        .ok_or_else(|| {
+                            db_error(format!(
+                                "列 '{}' 的值 {} 按 {} 位小数表示时超出 Decimal 精度",
+                                name, decimal, scale
+                            ))
+                        })
+                })
+                .transpose()
+        })
+        .collect::<Result<Vec<Option<i128>>>>()?;
+
+    let decimals = Int128Chunked::from_iter_options(name.into(), mantissas.into_iter())
+        .into_decimal(DECIMAL_MAX_PRECISION, scale as usize)?;
+    Ok(decimals.into_series().into_column())
+}
+
+/// 其它类型按文本读取；无法按文本读取时提示在 SQL 中转换
+fn fallback_text_column<R>(rows: &[R], index: usize, name: &str, type_name: &str) -> Result<Column>
+where
+    R: Row,
+    usize: ColumnIndex<R>,
+    String: for<'r> Decode<'r, R::Database> + Type<R::Database>,
+{
+    let values = decode_values::<R, String>(rows, index).map_err(|_| {
+        db_error(format!(
+            "列 '{}' 的类型 {} 暂不支持，请在 SQL 中转换为文本（例如 CAST({} AS CHAR)）",
+            name, type_name, name
+        ))
+    })?;
+    Ok(Column::new(name.into(), values))
+}
+
+/// PostgreSQL 列转换
+fn postgres_column(rows: &[PgRow], index: usize, name: &str, type_name: &str) -> Result<Column> {
+    let column = match type_name {
+        "BOOL" => Column::new(name.into(), decode_values::<_, bool>(rows, index)?),
+        "INT2" => Column::new(
+            name.into(),
+            map_values(decode_values::<_, i16>(rows, index)?, i64::from),
+        ),
+        "INT4" => Column::new(
+            name.into(),
+            map_values(decode_values::<_, i32>(rows, index)?, i64::from),
+        ),
+        "INT8" => Column::new(name.into(), decode_values::<_, i64>(rows, index)?),
+        "FLOAT4" => Column::new(
+            name.into(),
+            map_values(decode_values::<_, f32>(rows, index)?, f64::from),
+        ),
+        "FLOAT8" => Column::new(name.into(), decode_values::<_, f64>(rows, index)?),
+        "NUMERIC" => decimal_column(name, decode_values(rows, index)?)?,
+        "DATE" => date_column(name, decode_values(rows, index)?)?,
+        "TIMESTAMP" => datetime_column(name, decode_values(rows, index)?)?,
+        "TIMESTAMPTZ" => {
+            let values = decode_values::<_, DateTime<Utc>>(rows, index)?;
+            datetime_column(name, map_values(values, |datetime| datetime.naive_utc()))?
+        }
+        "TIME" => text_column(name, decode_values::<_, NaiveTime>(rows, index)?),
+        "UUID" => text_column(name, decode_values::<_, Uuid>(rows, index)?),
+        "JSON" | "JSONB" => text_column(name, decode_values::<_, JsonValue>(rows, index)?),
+        _ => fallback_text_column(rows, index, name, type_name)?,
+    };
+    Ok(column)
+}
+
+/// MySQL 列转换
+fn mysql_column(rows: &[MySqlRow], index: usize, name: &str, type_name: &str) -> Result<Column> {
+    let column = match type_name {
+        "BOOLEAN" => Column::new(name.into(), decode_values::<_, bool>(rows, index)?),
+        "TINYINT" | "SMALLINT" | "MEDIUMINT" | "INT" | "BIGINT" => {
+            Column::new(name.into(), decode_values::<_, i64>(rows, index)?)
+        }
+        "TINYINT UNSIGNED" | "SMALLINT UNSIGNED" | "MEDIUMINT UNSIGNED" | "INT UNSIGNED" | "BIGINT UNSIGNED" => {
+            Column::new(name.into(), decode_values::<_, u64>(rows, index)?)
+        }
+        "FLOAT" => Column::new(
+            name.into(),
+            map_values(decode_values::<_, f32>(rows, index)?, f64::from),
+        ),
+        "DOUBLE" => Column::new(name.into(), decode_values::<_, f64>(rows, index)?),
+        "DECIMAL" => decimal_column(name, decode_values(rows, index)?)?,
+        "DATE" => date_column(name, decode_values(rows, index)?)?,
+        "DATETIME" => datetime_column(name, decode_values(rows, index)?)?,
+        "TIMESTAMP" => {
+            let values = decode_values::<_, DateTime<Utc>>(rows, index)?;
+            datetime_column(name, map_values(values, |datetime| datetime.naive_utc()))?
+        }
+        "TIME" => text_column(name, decode_values::<_, NaiveTime>(rows, index)?),
+        "JSON" => text_column(name, decode_values::<_, JsonValue>(rows, index)?),
+        _ => fallback_text_column(rows, index, name, type_name)?,
+    };
+    Ok(column)
+}
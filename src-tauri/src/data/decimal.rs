@@ -0,0 +1,108 @@
+// ============================================================================
+// data/decimal.rs - Decimal（定点小数）工具
+// ============================================================================
+// Decimal 列在内存中保存为 i128 整数 + 小数位数（scale），
+// 例如 scale = 2 时 12345 表示 123.45，适合金额等需要精确小数的数据
+//
+// 这个文件提供：
+// - 精确的文本表示（用于表格展示、导出）
+// - 转换为 f64（用于统计、图表、Excel 数字单元格）
+// - 解析类型名 "Decimal" / "Decimal(p, s)"，以及其他基础类型名（用于类型转换和导入时覆盖类型）
+
+use polars::prelude::*;
+
+/// Decimal 的最大精度（总位数）
+pub const DECIMAL_MAX_PRECISION: usize = 38;
+
+/// 只写 "Decimal" 时使用的小数位数
+pub const DECIMAL_DEFAULT_SCALE: usize = 2;
+
+/// 是否为数值类型（包括 Decimal）
+pub fn is_numeric_dtype(dtype: &DataType) -> bool {
+    dtype.is_numeric() || matches!(dtype, DataType::Decimal(..))
+}
+
+/// Decimal 值的精确文本表示
+///
+/// 例如 (12345, 2) → "123.45"，(-5, 3) → "-0.005"
+pub fn decimal_to_string(value: i128, scale: usize) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let sign = if value < 0 { "-" } else { "" };
+
+    if scale == 0 {
+        return format!("{}{}", sign, digits);
+    }
+
+    let padded = format!("{:0>width$}", digits, width = scale + 1);
+    let (integer, fraction) = padded.split_at(padded.len() - scale);
+    format!("{}{}.{}", sign, integer, fraction)
+}
+
+/// Decimal 值转换为 f64（可能损失精度，只用于统计和图表）
+pub fn decimal_to_f64(value: i128, scale: usize) -> f64 {
+    value as f64 / 10f64.powi(scale as i32)
+}
+
+/// 解析 Decimal 类型名
+///
+/// 支持：
+/// - "Decimal" → 精度 38，小数位 2
+/// - "Decimal(p, s)" → 精度 p（1 ~ 38），小数位 s（0 ~ p）
+///
+/// 不是 Decimal 类型名时返回 None，参数无效时返回错误信息
+pub fn parse_decimal_dtype(name: &str) -> Option<Result<DataType, String>> {
+    let name = name.trim();
+    let rest = name.strip_prefix("Decimal")?;
+
+    if rest.trim().is_empty() {
+        return Some(Ok(DataType::Decimal(DECIMAL_MAX_PRECISION, DECIMAL_DEFAULT_SCALE)));
+    }
+
+    let args = rest.trim().strip_prefix('(').and_then(|r| r.strip_suffix(')'));
+    let parsed = args.and_then(|args| {
+        let (precision, scale) = args.split_once(',')?;
+        Some((
+            precision.trim().parse::<usize>().ok()?,
+            scale.trim().parse::<usize>().ok()?,
+        ))
+    });
+
+    Some(match parsed {
+        Some((precision, scale)) if (1..=DECIMAL_MAX_PRECISION).contains(&precision) && scale <= precision => {
+            Ok(DataType::Decimal(precision, scale))
+        }
+        Some((precision, scale)) => Err(format!(
+            "无效的 Decimal 参数: 精度 {}（1 ~ {}），小数位 {}（不能超过精度）",
+            precision, DECIMAL_MAX_PRECISION, scale
+        )),
+        None => Err(format!("无效的 Decimal 类型: {}（格式为 Decimal(精度, 小数位)）", name)),
+    })
+}
+
+/// 解析类型名（cast_types 和 CSV 导入的类型覆盖共用）
+///
+/// 支持所有 Polars 基础类型名（"Int64"、"Float64"、"String"、"Boolean"、"Date"、
+/// "Datetime"、"Time"、"Duration" 等），以及 "Decimal"、"Decimal(精度, 小数位)"
+pub fn parse_dtype_name(name: &str) -> Result<DataType, String> {
+    let data_type = match name.trim() {
+        "Int8" => DataType::Int8,
+        "Int16" => DataType::Int16,
+        "Int32" => DataType::Int32,
+        "Int64" => DataType::Int64,
+        "UInt8" => DataType::UInt8,
+        "UInt16" => DataType::UInt16,
+        "UInt32" => DataType::UInt32,
+        "UInt64" => DataType::UInt64,
+        "Float32" => DataType::Float32,
+        "Float64" => DataType::Float64,
+        "String" => DataType::String,
+        "Boolean" => DataType::Boolean,
+        "Date" => DataType::Date,
+        "Datetime" => DataType::Datetime(TimeUnit::Microseconds, None),
+        "Time" => DataType::Time,
+        "Duration" => DataType::Duration(TimeUnit::Microseconds),
+        // Decimal / Decimal(精度, 小数位)
+        other => parse_decimal_dtype(other).ok_or_else(|| format!("不支持的类型: {}", name))??,
+    };
+    Ok(data_type)
+}
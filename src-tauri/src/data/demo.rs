@@ -0,0 +1,221 @@
+// ============================================================================
+// data/demo.rs - 演示数据生成
+// ============================================================================
+// 这个文件负责生成合成的演示数据集，让新用户不用找文件就能试用所有功能：
+// - sales: 销售订单（日期、分类、整数、小数、空值）
+// - timeseries: 按小时采样的传感器读数（日期时间、带趋势和周期的数值）
+// - iris: 鸢尾花风格的分类数据（按品种聚类的数值特征）
+//
+// 使用固定种子的伪随机数生成器，同样的预设和行数总是生成同样的数据
+
+use crate::error::{DataAnalystError, Result};
+use crate::models::DemoPreset;
+use polars::prelude::*;
+
+/// 导入记录中演示数据来源地址的前缀（例如 "demo://sales"）
+pub const DEMO_SOURCE_PREFIX: &str = "demo://";
+
+/// 未指定行数时生成的行数
+pub const DEFAULT_DEMO_ROWS: usize = 1_000;
+
+/// 最多生成的行数
+pub const MAX_DEMO_ROWS: usize = 1_000_000;
+
+/// 伪随机数种子
+const DEMO_SEED: u64 = 0x5EED_DA7A;
+
+/// 销售数据的起始日期 2024-01-01（距 1970-01-01 的天数）
+const SALES_START_DAY: i32 = 19_723;
+
+/// 时间序列的起始时间 2024-01-01T00:00:00Z（毫秒）
+const TIMESERIES_START_MS: i64 = 1_704_067_200_000;
+
+const MS_PER_HOUR: i64 = 3_600_000;
+
+const REGIONS: [&str; 5] = ["华东", "华南", "华北", "西南", "东北"];
+const PRODUCTS: [(&str, f64); 6] = [
+    ("笔记本电脑", 5999.0),
+    ("显示器", 1299.0),
+    ("键盘", 299.0),
+    ("鼠标", 129.0),
+    ("耳机", 499.0),
+    ("扩展坞", 399.0),
+];
+const SENSORS: [&str; 3] = ["sensor_a", "sensor_b", "sensor_c"];
+
+/// 鸢尾花品种及四个特征（花萼长、花萼宽、花瓣长、花瓣宽）的均值和标准差
+const IRIS_SPECIES: [(&str, [(f64, f64); 4]); 3] = [
+    ("setosa", [(5.0, 0.35), (3.4, 0.38), (1.5, 0.17), (0.25, 0.1)]),
+    ("versicolor", [(5.9, 0.52), (2.8, 0.31), (4.3, 0.47), (1.3, 0.2)]),
+    ("virginica", [(6.6, 0.64), (3.0, 0.32), (5.6, 0.55), (2.0, 0.27)]),
+];
+
+/// 生成演示数据集
+pub fn generate_demo_dataframe(preset: DemoPreset, rows: usize) -> Result<DataFrame> {
+    if rows == 0 || rows > MAX_DEMO_ROWS {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "行数必须在 1 到 {} 之间",
+            MAX_DEMO_ROWS
+        )));
+    }
+
+    let mut rng = DemoRng::new(DEMO_SEED);
+    match preset {
+        DemoPreset::Sales => sales(&mut rng, rows),
+        DemoPreset::Timeseries => timeseries(&mut rng, rows),
+        DemoPreset::Iris => iris(&mut rng, rows),
+    }
+}
+
+/// 演示数据的来源地址（记录在导入操作中）
+pub fn demo_source(preset: DemoPreset) -> String {
+    format!("{}{}", DEMO_SOURCE_PREFIX, preset.as_str())
+}
+
+fn sales(rng: &mut DemoRng, rows: usize) -> Result<DataFrame> {
+    let mut order_id = Vec::with_capacity(rows);
+    let mut order_date = Vec::with_capacity(rows);
+    let mut region = Vec::with_capacity(rows);
+    let mut product = Vec::with_capacity(rows);
+    let mut quantity = Vec::with_capacity(rows);
+    let mut unit_price = Vec::with_capacity(rows);
+    let mut discount = Vec::with_capacity(rows);
+    let mut revenue = Vec::with_capacity(rows);
+
+    for i in 0..rows {
+        let (name, base_price) = PRODUCTS[rng.below(PRODUCTS.len())];
+        // 价格在基准价上下浮动 10%，保留两位小数
+        let price = round2(base_price * (0.9 + 0.2 * rng.next_f64()));
+        let qty = 1 + rng.below(10) as i64;
+        // 约 30% 的订单有折扣，其余为空值
+        let rate = if rng.next_f64() < 0.3 {
+            Some([0.05, 0.1, 0.15, 0.2][rng.below(4)])
+        } else {
+            None
+        };
+
+        order_id.push(100_001 + i as i64);
+        // 订单日期大致按顺序分布在两年内
+        order_date.push(SALES_START_DAY + ((i * 730) / rows) as i32 + rng.below(3) as i32);
+        region.push(REGIONS[rng.below(REGIONS.len())]);
+        product.push(name);
+        quantity.push(qty);
+        unit_price.push(price);
+        discount.push(rate);
+        revenue.push(round2(price * qty as f64 * (1.0 - rate.unwrap_or(0.0))));
+    }
+
+    let order_date = Series::new("order_date".into(), order_date).cast(&DataType::Date)?;
+    let df = DataFrame::new(vec![
+        Column::new("order_id".into(), order_id),
+        order_date.into(),
+        Column::new("region".into(), region),
+        Column::new("product".into(), product),
+        Column::new("quantity".into(), quantity),
+        Column::new("unit_price".into(), unit_price),
+        Column::new("discount".into(), discount),
+        Column::new("revenue".into(), revenue),
+    ])?;
+    Ok(df)
+}
+
+fn timeseries(rng: &mut DemoRng, rows: usize) -> Result<DataFrame> {
+    let mut timestamp = Vec::with_capacity(rows);
+    let mut sensor = Vec::with_capacity(rows);
+    let mut temperature = Vec::with_capacity(rows);
+    let mut humidity = Vec::with_capacity(rows);
+
+    for i in 0..rows {
+        // 每个小时依次记录每个传感器的读数
+        let hour = (i / SENSORS.len()) as i64;
+        let sensor_index = i % SENSORS.len();
+        let hour_of_day = (hour % 24) as f64;
+        let daily = (2.0 * std::f64::consts::PI * (hour_of_day - 9.0) / 24.0).sin();
+        let trend = hour as f64 / (24.0 * 30.0) * 0.5;
+
+        timestamp.push(TIMESERIES_START_MS + hour * MS_PER_HOUR);
+        sensor.push(SENSORS[sensor_index]);
+        temperature.push(round2(
+            18.0 + sensor_index as f64 * 1.5 + 6.0 * daily + trend + rng.normal(0.0, 0.8),
+        ));
+        humidity.push(round2((60.0 - 15.0 * daily + rng.normal(0.0, 3.0)).clamp(0.0, 100.0)));
+    }
+
+    let timestamp =
+        Series::new("timestamp".into(), timestamp).cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?;
+    let df = DataFrame::new(vec![
+        timestamp.into(),
+        Column::new("sensor".into(), sensor),
+        Column::new("temperature".into(), temperature),
+        Column::new("humidity".into(), humidity),
+    ])?;
+    Ok(df)
+}
+
+fn iris(rng: &mut DemoRng, rows: usize) -> Result<DataFrame> {
+    let mut features: [Vec<f64>; 4] = std::array::from_fn(|_| Vec::with_capacity(rows));
+    let mut species = Vec::with_capacity(rows);
+
+    for i in 0..rows {
+        // 三个品种轮流出现，数量均衡
+        let (name, params) = IRIS_SPECIES[i % IRIS_SPECIES.len()];
+        for (values, (mean, std)) in features.iter_mut().zip(params) {
+            values.push(round1(rng.normal(mean, std).max(0.1)));
+        }
+        species.push(name);
+    }
+
+    let [sepal_length, sepal_width, petal_length, petal_width] = features;
+    let df = DataFrame::new(vec![
+        Column::new("sepal_length".into(), sepal_length),
+        Column::new("sepal_width".into(), sepal_width),
+        Column::new("petal_length".into(), petal_length),
+        Column::new("petal_width".into(), petal_width),
+        Column::new("species".into(), species),
+    ])?;
+    Ok(df)
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// SplitMix64 伪随机数生成器（足够用于演示数据，不用于任何安全相关场景）
+struct DemoRng {
+    state: u64,
+}
+
+impl DemoRng {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// [0, 1) 之间的均匀分布
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// [0, n) 之间的整数
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// 正态分布（Box-Muller 变换）
+    fn normal(&mut self, mean: f64, std: f64) -> f64 {
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        mean + std * (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+}
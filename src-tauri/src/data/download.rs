@@ -0,0 +1,175 @@
+// ============================================================================
+// data/download.rs - 远程文件下载
+// ============================================================================
+// 这个文件负责从 HTTP(S) 地址下载数据文件（开放数据平台、共享链接等）：
+// 1. 只允许 http / https 地址
+// 2. 超时和大小限制：响应头声明的大小超过限制时直接拒绝，
+//    下载过程中累计大小超过限制时中止（服务器没有声明大小时也能生效）
+// 3. 下载到系统临时目录，由调用方读取后删除
+// 4. 根据指定格式、URL 扩展名或 Content-Type 判断文件格式
+
+use crate::error::{DataAnalystError, Result};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// 默认超时时间（秒）
+pub const DEFAULT_DOWNLOAD_TIMEOUT_SECS: u64 = 60;
+
+/// 默认最大下载大小（500 MB）
+pub const DEFAULT_MAX_DOWNLOAD_BYTES: u64 = 500 * 1024 * 1024;
+
+fn download_error(message: impl std::fmt::Display) -> DataAnalystError {
+    DataAnalystError::InvalidOperation(format!("下载失败: {}", message))
+}
+
+// ============================================================================
+// 文件格式
+// ============================================================================
+/// RemoteFormat - 可以从 URL 导入的文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteFormat {
+    Csv,
+    Json,
+    Parquet,
+}
+
+impl RemoteFormat {
+    /// 解析用户指定的格式（"csv"、"json"、"parquet"，不区分大小写）
+    pub fn parse(label: &str) -> Result<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "csv" | "tsv" | "txt" => Ok(RemoteFormat::Csv),
+            "json" | "jsonl" | "ndjson" => Ok(RemoteFormat::Json),
+            "parquet" => Ok(RemoteFormat::Parquet),
+            _ => Err(DataAnalystError::InvalidOperation(format!(
+                "不支持的格式: {}（可选: csv、json、parquet）",
+                label
+            ))),
+        }
+    }
+
+    /// 根据文件名扩展名判断格式（.gz / .zst / .zip 压缩的 CSV 按 CSV 处理）
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let lower = file_name.to_ascii_lowercase();
+        let stem = [".gz", ".zst", ".zip"]
+            .iter()
+            .find_map(|suffix| lower.strip_suffix(suffix))
+            .unwrap_or(&lower);
+        let extension = stem.rsplit_once('.')?.1;
+        Self::parse(extension).ok()
+    }
+
+    /// 根据 Content-Type 判断格式
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next()?.trim().to_ascii_lowercase();
+        match mime.as_str() {
+            "text/csv" | "application/csv" | "text/tab-separated-values" => Some(RemoteFormat::Csv),
+            "application/json" | "application/x-ndjson" | "application/jsonl" | "application/x-jsonlines" => {
+                Some(RemoteFormat::Json)
+            }
+            "application/vnd.apache.parquet" | "application/x-parquet" => Some(RemoteFormat::Parquet),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// 下载
+// ============================================================================
+/// DownloadedFile - 下载到临时目录的文件
+///
+/// 被丢弃时自动删除临时文件
+pub struct DownloadedFile {
+    /// 临时文件路径
+    pub path: PathBuf,
+
+    /// URL 中的文件名（没有文件名时为主机名）
+    pub file_name: String,
+
+    /// 响应的 Content-Type
+    pub content_type: Option<String>,
+}
+
+impl Drop for DownloadedFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// 下载 URL 指向的文件到系统临时目录
+///
+/// 参数：
+/// - url: http / https 地址
+/// - timeout: 整个请求（连接 + 下载）的超时时间
+/// - max_bytes: 最大下载大小
+pub async fn download_to_temp(url: &str, timeout: Duration, max_bytes: u64) -> Result<DownloadedFile> {
+    let parsed = reqwest::Url::parse(url.trim()).map_err(|e| download_error(format!("无效的 URL '{}': {}", url, e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(download_error(format!(
+            "只支持 http / https 地址，当前为 {}",
+            parsed.scheme()
+        )));
+    }
+
+    let file_name = parsed
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_string())
+        .or_else(|| parsed.host_str().map(|host| host.to_string()))
+        .unwrap_or_else(|| "download".to_string());
+
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(download_error)?;
+    let mut response = client
+        .get(parsed)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(download_error)?;
+
+    if let Some(length) = response.content_length().filter(|length| *length > max_bytes) {
+        return Err(download_error(format!(
+            "文件大小 {} 字节超过限制 {} 字节",
+            length, max_bytes
+        )));
+    }
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+
+    // 临时文件保留原扩展名（load_json 根据 .jsonl / .ndjson 判断格式）
+    let safe_name: String = file_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let downloaded = DownloadedFile {
+        path: std::env::temp_dir().join(format!("datavis-{}-{}", uuid::Uuid::new_v4(), safe_name)),
+        file_name,
+        content_type,
+    };
+
+    let mut file = std::fs::File::create(&downloaded.path)?;
+    let mut written: u64 = 0;
+    while let Some(chunk) = response.chunk().await.map_err(download_error)? {
+        written += chunk.len() as u64;
+        if written > max_bytes {
+            return Err(download_error(format!("下载内容超过大小限制 {} 字节", max_bytes)));
+        }
+        file.write_all(&chunk)?;
+    }
+    file.sync_all()?;
+
+    Ok(downloaded)
+}
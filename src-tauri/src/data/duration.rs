@@ -0,0 +1,370 @@
+// ============================================================================
+// data/duration.rs - 时间间隔（Duration）与时刻（Time）工具
+// ============================================================================
+// 这个文件负责 Duration / Time 类型与文本之间的转换，以及时间间隔运算：
+// - 格式化：Duration → "2d 03:15:00"，Time → "08:30:00"（有小数秒时保留小数）
+// - 解析：文本 → Duration / Time（用于类型转换，与格式化结果可以互相转换）
+// - 运算：两个时间列相减、时间列加减间隔、间隔转换为数值
+//
+// 所有运算都在物理表示（i64）上进行，然后转换回原类型
+
+use crate::error::{DataAnalystError, Result};
+use crate::models::history::DurationUnit;
+use polars::prelude::*;
+
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// 每个时间单位对应的每秒刻度数
+fn ticks_per_second(unit: TimeUnit) -> i64 {
+    match unit {
+        TimeUnit::Nanoseconds => NANOS_PER_SECOND,
+        TimeUnit::Microseconds => MICROS_PER_SECOND,
+        TimeUnit::Milliseconds => 1_000,
+    }
+}
+
+/// 写出 "HH:MM:SS"，有小数秒时追加小数部分（去掉末尾的 0）
+fn format_clock(seconds_of_day: i64, fraction_ticks: i64, ticks: i64) -> String {
+    let mut text = format!(
+        "{:02}:{:02}:{:02}",
+        seconds_of_day / 3600,
+        (seconds_of_day % 3600) / 60,
+        seconds_of_day % 60
+    );
+
+    if fraction_ticks > 0 {
+        let width = ticks.ilog10() as usize;
+        let fraction = format!("{:0width$}", fraction_ticks, width = width);
+        text.push('.');
+        text.push_str(fraction.trim_end_matches('0'));
+    }
+    text
+}
+
+// ============================================================================
+// 格式化
+// ============================================================================
+/// 格式化时间间隔
+///
+/// 例如：
+/// - 2 天 3 小时 15 分 → "2d 03:15:00"
+/// - 90 秒 → "00:01:30"
+/// - 负 1.5 秒 → "-00:00:01.5"
+pub fn format_duration(value: i64, unit: TimeUnit) -> String {
+    let ticks = ticks_per_second(unit);
+    let sign = if value < 0 { "-" } else { "" };
+    let abs = value.unsigned_abs() as i128;
+
+    let total_seconds = (abs / ticks as i128) as i64;
+    let fraction = (abs % ticks as i128) as i64;
+    let days = total_seconds / SECONDS_PER_DAY;
+    let clock = format_clock(total_seconds % SECONDS_PER_DAY, fraction, ticks);
+
+    if days > 0 {
+        format!("{}{}d {}", sign, days, clock)
+    } else {
+        format!("{}{}", sign, clock)
+    }
+}
+
+/// 格式化一天中的时刻（纳秒）
+///
+/// 例如 08:30:00、23:59:59.25
+pub fn format_time(nanos: i64) -> String {
+    format_clock(nanos / NANOS_PER_SECOND, nanos % NANOS_PER_SECOND, NANOS_PER_SECOND)
+}
+
+// ============================================================================
+// 解析
+// ============================================================================
+/// 解析 "HH:MM[:SS[.fff]]" 为微秒（小时不限于 24）
+fn parse_clock_micros(text: &str) -> Option<i64> {
+    let parts: Vec<&str> = text.split(':').collect();
+    if !(2..=3).contains(&parts.len()) {
+        return None;
+    }
+
+    let hours: i64 = parts[0].parse().ok()?;
+    let minutes: i64 = parts[1].parse().ok()?;
+    let (seconds, fraction_micros) = match parts.get(2) {
+        Some(sec) => {
+            let (whole, fraction) = sec.split_once('.').unwrap_or((sec, ""));
+            if fraction.len() > 6 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            let fraction_micros = format!("{:0<6}", fraction).parse::<i64>().ok()?;
+            (whole.parse::<i64>().ok()?, fraction_micros)
+        }
+        None => (0, 0),
+    };
+
+    if minutes >= 60 || seconds >= 60 || hours < 0 || minutes < 0 || seconds < 0 {
+        return None;
+    }
+
+    hours
+        .checked_mul(3600)?
+        .checked_add(minutes * 60 + seconds)?
+        .checked_mul(MICROS_PER_SECOND)?
+        .checked_add(fraction_micros)
+}
+
+/// 解析时间间隔文本为微秒
+///
+/// 支持的格式（可以带前导 "-" 表示负数）：
+/// - format_duration 的输出："2d 03:15:00"、"03:15:00"、"00:00:01.5"
+/// - 单位组合："1d 2h 30m"、"90s"、"1.5h"（单位：d / h / m / s / ms）
+///
+/// 超出 i64 微秒范围时返回 None
+pub fn parse_duration_micros(text: &str) -> Option<i64> {
+    let text = text.trim();
+    let (negative, body) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, text),
+    };
+    if body.is_empty() {
+        return None;
+    }
+
+    let mut total: i64 = 0;
+    for token in body.split_whitespace() {
+        let micros = if token.contains(':') {
+            parse_clock_micros(token)?
+        } else {
+            let split = token.find(|c: char| c.is_ascii_alphabetic())?;
+            let (number, unit) = token.split_at(split);
+            let number: f64 = number.parse().ok()?;
+            let factor = match unit {
+                "d" => SECONDS_PER_DAY * MICROS_PER_SECOND,
+                "h" => 3600 * MICROS_PER_SECOND,
+                "m" => 60 * MICROS_PER_SECOND,
+                "s" => MICROS_PER_SECOND,
+                "ms" => 1_000,
+                _ => return None,
+            };
+            let micros = (number * factor as f64).round();
+            // i64::MAX as f64 等于 2^63，本身已超出范围
+            if !(i64::MIN as f64..i64::MAX as f64).contains(&micros) {
+                return None;
+            }
+            micros as i64
+        };
+        total = total.checked_add(micros)?;
+    }
+
+    if negative { total.checked_neg() } else { Some(total) }
+}
+
+/// 解析时刻文本 "HH:MM[:SS[.fff]]" 为纳秒（必须在一天之内）
+pub fn parse_time_nanos(text: &str) -> Option<i64> {
+    let micros = parse_clock_micros(text.trim())?;
+    (micros < SECONDS_PER_DAY * MICROS_PER_SECOND).then_some(micros * 1_000)
+}
+
+/// 将字符串列解析为 Duration(μs) 列（无法解析的值为空值）
+pub fn parse_duration_column(series: &Series) -> Result<Series> {
+    let parsed: Int64Chunked = series
+        .str()?
+        .into_iter()
+        .map(|value| value.and_then(parse_duration_micros))
+        .collect();
+
+    Ok(parsed
+        .into_series()
+        .cast(&DataType::Duration(TimeUnit::Microseconds))?
+        .with_name(series.name().clone()))
+}
+
+/// 将字符串列解析为 Time 列（无法解析的值为空值）
+pub fn parse_time_column(series: &Series) -> Result<Series> {
+    let parsed: Int64Chunked = series
+        .str()?
+        .into_iter()
+        .map(|value| value.and_then(parse_time_nanos))
+        .collect();
+
+    Ok(parsed
+        .into_series()
+        .cast(&DataType::Time)?
+        .with_name(series.name().clone()))
+}
+
+// ============================================================================
+// 运算
+// ============================================================================
+/// 读取时间列（Date / Datetime）并统一转换为 Datetime(μs) 的物理值
+fn datetime_micros(df: &DataFrame, column: &str) -> Result<Int64Chunked> {
+    let series = df
+        .column(column)
+        .map_err(|_| DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", column)))?;
+
+    let tz = match series.dtype() {
+        DataType::Date => None,
+        DataType::Datetime(_, tz) => tz.clone(),
+        other => {
+            return Err(DataAnalystError::InvalidOperation(format!(
+                "列 '{}' 不是日期或日期时间类型（当前类型: {:?}）",
+                column, other
+            )));
+        }
+    };
+
+    let micros = series
+        .as_materialized_series()
+        .cast(&DataType::Datetime(TimeUnit::Microseconds, tz))?
+        .to_physical_repr()
+        .into_owned();
+    Ok(micros.i64()?.clone())
+}
+
+/// 两个时间列之差（end - start），结果为 Duration(μs)
+pub fn duration_between(df: &DataFrame, start: &str, end: &str, name: &str) -> Result<Series> {
+    let start = datetime_micros(df, start)?;
+    let end = datetime_micros(df, end)?;
+    let diff = &end - &start;
+
+    Ok(diff
+        .into_series()
+        .cast(&DataType::Duration(TimeUnit::Microseconds))?
+        .with_name(name.into()))
+}
+
+/// 时间列（Date / Datetime / Duration）加上一个时间间隔（微秒，可以为负）
+///
+/// Date 列的结果为 Datetime(μs)，其他类型保持原类型；结果超出 i64 范围时返回错误
+pub fn shift_by_duration(df: &DataFrame, column: &str, offset_micros: i64, name: &str) -> Result<Series> {
+    let series = df
+        .column(column)
+        .map_err(|_| DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", column)))?;
+
+    let (target, unit) = match series.dtype() {
+        DataType::Date => (DataType::Datetime(TimeUnit::Microseconds, None), TimeUnit::Microseconds),
+        DataType::Datetime(unit, tz) => (DataType::Datetime(*unit, tz.clone()), *unit),
+        DataType::Duration(unit) => (DataType::Duration(*unit), *unit),
+        other => {
+            return Err(DataAnalystError::InvalidOperation(format!(
+                "列 '{}' 不是日期、日期时间或时间间隔类型（当前类型: {:?}）",
+                column, other
+            )));
+        }
+    };
+
+    let overflow = || {
+        DataAnalystError::InvalidOperation(format!(
+            "列 '{}' 加上时间间隔 {} 后超出可表示的时间范围",
+            column,
+            format_duration(offset_micros, TimeUnit::Microseconds)
+        ))
+    };
+
+    // 将偏移量换算为目标列的时间单位
+    let offset = match unit {
+        TimeUnit::Nanoseconds => offset_micros.checked_mul(1_000).ok_or_else(overflow)?,
+        TimeUnit::Microseconds => offset_micros,
+        TimeUnit::Milliseconds => offset_micros / 1_000,
+    };
+
+    let physical = series
+        .as_materialized_series()
+        .cast(&target)?
+        .to_physical_repr()
+        .into_owned();
+    let shifted = physical
+        .i64()?
+        .into_iter()
+        .map(|value| value.map(|v| v.checked_add(offset).ok_or_else(overflow)).transpose())
+        .collect::<Result<Int64Chunked>>()?;
+
+    Ok(shifted.into_series().cast(&target)?.with_name(name.into()))
+}
+
+/// 时间间隔列转换为数值（Float64，按指定单位）
+pub fn duration_to_number(df: &DataFrame, column: &str, unit: DurationUnit, name: &str) -> Result<Series> {
+    let series = df
+        .column(column)
+        .map_err(|_| DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", column)))?;
+
+    let DataType::Duration(time_unit) = series.dtype() else {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "列 '{}' 不是时间间隔类型（当前类型: {:?}）",
+            column,
+            series.dtype()
+        )));
+    };
+
+    let ticks_per_unit = (ticks_per_second(*time_unit) * unit.seconds()) as f64;
+    let physical = series.as_materialized_series().to_physical_repr().into_owned();
+    let values: Float64Chunked = physical
+        .i64()?
+        .into_iter()
+        .map(|v| v.map(|v| v as f64 / ticks_per_unit))
+        .collect();
+
+    Ok(values.with_name(name.into()).into_series())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formatted_durations_parse_back() {
+        let values = [
+            0,
+            1,
+            90 * MICROS_PER_SECOND,
+            (2 * SECONDS_PER_DAY + 3 * 3600 + 15 * 60) * MICROS_PER_SECOND,
+            -1_500_000,
+            123_456_789_012,
+        ];
+        for value in values {
+            let text = format_duration(value, TimeUnit::Microseconds);
+            assert_eq!(parse_duration_micros(&text), Some(value), "{}", text);
+        }
+    }
+
+    #[test]
+    fn formats_and_parses_known_texts() {
+        assert_eq!(
+            format_duration(90 * MICROS_PER_SECOND, TimeUnit::Microseconds),
+            "00:01:30"
+        );
+        assert_eq!(format_duration(-1_500_000, TimeUnit::Microseconds), "-00:00:01.5");
+        assert_eq!(
+            format_duration(2 * SECONDS_PER_DAY * NANOS_PER_SECOND, TimeUnit::Nanoseconds),
+            "2d 00:00:00"
+        );
+        assert_eq!(
+            parse_duration_micros("1d 2h 30m"),
+            Some((26 * 3600 + 30 * 60) * MICROS_PER_SECOND)
+        );
+        assert_eq!(parse_duration_micros("1.5h"), Some(5400 * MICROS_PER_SECOND));
+        assert_eq!(parse_duration_micros("250ms"), Some(250_000));
+        assert_eq!(parse_duration_micros("5x"), None);
+        assert_eq!(parse_duration_micros("00:60:00"), None);
+    }
+
+    #[test]
+    fn out_of_range_durations_are_rejected() {
+        assert_eq!(parse_duration_micros("1e300s"), None);
+        assert_eq!(parse_duration_micros("200000000d"), None);
+        assert_eq!(parse_duration_micros("9999999999999:00:00"), None);
+        assert_eq!(parse_duration_micros("100000000d 100000000d"), None);
+    }
+
+    #[test]
+    fn shifting_nanosecond_columns_reports_overflow() {
+        let df = DataFrame::new(vec![
+            Column::new("t".into(), [0i64])
+                .cast(&DataType::Datetime(TimeUnit::Nanoseconds, None))
+                .unwrap(),
+        ])
+        .unwrap();
+
+        let day = SECONDS_PER_DAY * MICROS_PER_SECOND;
+        assert!(shift_by_duration(&df, "t", day, "shifted").is_ok());
+        assert!(shift_by_duration(&df, "t", 106_752 * day, "shifted").is_err());
+    }
+}
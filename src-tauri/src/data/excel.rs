@@ -0,0 +1,251 @@
+// ============================================================================
+// data/excel.rs - Excel 工作簿写入
+// ============================================================================
+// 这个文件负责把多个 DataFrame 写入同一个 Excel 工作簿（每个 DataFrame 一个工作表）
+// - 数值写为数字，布尔值写为布尔，空值留空，列表和结构体写为 JSON 文本，其他类型写为文本
+// - 日期和不带时区的日期时间写为 Excel 日期（带日期格式，可以直接筛选和排序）
+// - 表头加粗
+// - 工作表名称按 Excel 规则清理（最长 31 个字符，不能包含 []:*?/\）
+// - 先写入临时文件，成功后再重命名为目标文件
+
+use crate::data::decimal::{decimal_to_f64, is_numeric_dtype};
+use crate::data::duration::{format_duration, format_time};
+use crate::data::export::write_atomic_checked;
+use crate::data::loader::any_value_to_json;
+use crate::error::{DataAnalystError, Result};
+use polars::prelude::*;
+use rust_xlsxwriter::{Format, Workbook, Worksheet, XlsxError};
+use std::collections::HashSet;
+
+/// Excel 单个工作表的最大行数（含表头）
+pub const EXCEL_MAX_ROWS: usize = 1_048_576;
+
+/// Excel 单个工作表的最大列数
+pub const EXCEL_MAX_COLUMNS: usize = 16_384;
+
+/// Excel 工作表名称的最大长度
+const SHEET_NAME_MAX_LEN: usize = 31;
+
+/// 1970-01-01 对应的 Excel 日期序号（Excel 日期从 1899-12-30 开始计数）
+const EXCEL_UNIX_EPOCH_SERIAL: f64 = 25_569.0;
+
+/// Excel 能正确显示的最早日期序号（1900-03-01，之前的日期受 1900 闰年问题影响）
+const EXCEL_MIN_DATE_SERIAL: f64 = 61.0;
+
+const MS_PER_DAY: f64 = 86_400_000.0;
+
+fn xlsx_error(err: XlsxError) -> DataAnalystError {
+    DataAnalystError::InvalidOperation(format!("写入 Excel 失败: {}", err))
+}
+
+// ============================================================================
+// 工作簿写入
+// ============================================================================
+/// 将多个 (工作表名称, DataFrame) 写入 Excel 文件
+///
+/// 工作表名称会被清理并去重；overwrite 为 false 时不覆盖已存在的文件
+pub fn write_workbook(path: &str, sheets: &[(String, DataFrame)], overwrite: bool) -> Result<()> {
+    let mut workbook = Workbook::new();
+    let formats = CellFormats::new();
+    let mut used_names = HashSet::new();
+
+    for (name, df) in sheets {
+        check_sheet_size(name, df)?;
+        let worksheet = workbook.add_worksheet();
+        worksheet
+            .set_name(unique_sheet_name(name, &mut used_names))
+            .map_err(xlsx_error)?;
+        write_dataframe(worksheet, df, &formats)?;
+    }
+
+    write_atomic_checked(path, overwrite, |file| {
+        workbook.save_to_writer(file).map_err(xlsx_error)?;
+        Ok(())
+    })
+}
+
+/// 将一个 DataFrame 写为只有一个工作表的 Excel 文件
+///
+/// 表头行添加自动筛选并冻结，滚动时表头保持可见；overwrite 为 false 时不覆盖已存在的文件
+pub fn write_table(path: &str, sheet_name: &str, df: &DataFrame, overwrite: bool) -> Result<()> {
+    check_sheet_size(sheet_name, df)?;
+
+    let mut workbook = Workbook::new();
+    let formats = CellFormats::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet
+        .set_name(unique_sheet_name(sheet_name, &mut HashSet::new()))
+        .map_err(xlsx_error)?;
+    write_dataframe(worksheet, df, &formats)?;
+
+    if df.width() > 0 {
+        worksheet
+            .autofilter(0, 0, df.height() as u32, (df.width() - 1) as u16)
+            .map_err(xlsx_error)?;
+        worksheet.set_freeze_panes(1, 0).map_err(xlsx_error)?;
+    }
+
+    write_atomic_checked(path, overwrite, |file| {
+        workbook.save_to_writer(file).map_err(xlsx_error)?;
+        Ok(())
+    })
+}
+
+/// 检查 DataFrame 是否超出 Excel 单个工作表的行列限制
+fn check_sheet_size(name: &str, df: &DataFrame) -> Result<()> {
+    if df.height() + 1 > EXCEL_MAX_ROWS || df.width() > EXCEL_MAX_COLUMNS {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "工作表 '{}' 超出 Excel 限制（{} 行 × {} 列）",
+            name,
+            df.height(),
+            df.width()
+        )));
+    }
+    Ok(())
+}
+
+/// 表头和日期单元格使用的格式
+struct CellFormats {
+    header: Format,
+    date: Format,
+    datetime: Format,
+}
+
+impl CellFormats {
+    fn new() -> Self {
+        Self {
+            header: Format::new().set_bold(),
+            date: Format::new().set_num_format("yyyy-mm-dd"),
+            datetime: Format::new().set_num_format("yyyy-mm-dd hh:mm:ss"),
+        }
+    }
+}
+
+/// 日期 / 不带时区的日期时间转换为 Excel 日期序号（其他类型和过早的日期返回 None）
+fn excel_date_serial(value: &AnyValue) -> Option<f64> {
+    let serial = match value {
+        AnyValue::Date(days) => *days as f64 + EXCEL_UNIX_EPOCH_SERIAL,
+        AnyValue::Datetime(v, time_unit, None) | AnyValue::DatetimeOwned(v, time_unit, None) => {
+            let ms = match time_unit {
+                TimeUnit::Nanoseconds => *v as f64 / 1_000_000.0,
+                TimeUnit::Microseconds => *v as f64 / 1_000.0,
+                TimeUnit::Milliseconds => *v as f64,
+            };
+            ms / MS_PER_DAY + EXCEL_UNIX_EPOCH_SERIAL
+        }
+        _ => return None,
+    };
+    (serial >= EXCEL_MIN_DATE_SERIAL).then_some(serial)
+}
+
+/// 将 DataFrame 写入工作表（第一行为表头）
+fn write_dataframe(worksheet: &mut Worksheet, df: &DataFrame, formats: &CellFormats) -> Result<()> {
+    for (col_idx, column) in df.get_columns().iter().enumerate() {
+        let col_num = col_idx as u16;
+        worksheet
+            .write_string_with_format(0, col_num, column.name().as_str(), &formats.header)
+            .map_err(xlsx_error)?;
+
+        let is_numeric = is_numeric_dtype(column.dtype());
+        for (row_idx, value) in column.as_materialized_series().iter().enumerate() {
+            let row_num = (row_idx + 1) as u32;
+            match value {
+                AnyValue::Null => {}
+                AnyValue::Boolean(b) => {
+                    worksheet.write_boolean(row_num, col_num, b).map_err(xlsx_error)?;
+                }
+                AnyValue::List(_) | AnyValue::Struct(..) | AnyValue::StructOwned(_) => {
+                    let text = any_value_to_json(value).to_string();
+                    worksheet.write_string(row_num, col_num, text).map_err(xlsx_error)?;
+                }
+                AnyValue::Duration(v, time_unit) => {
+                    worksheet
+                        .write_string(row_num, col_num, format_duration(v, time_unit))
+                        .map_err(xlsx_error)?;
+                }
+                AnyValue::Time(t) => {
+                    worksheet
+                        .write_string(row_num, col_num, format_time(t))
+                        .map_err(xlsx_error)?;
+                }
+                AnyValue::Date(_) | AnyValue::Datetime(..) | AnyValue::DatetimeOwned(..) => {
+                    match excel_date_serial(&value) {
+                        Some(serial) => {
+                            let format = if matches!(value, AnyValue::Date(_)) {
+                                &formats.date
+                            } else {
+                                &formats.datetime
+                            };
+                            worksheet
+                                .write_number_with_format(row_num, col_num, serial, format)
+                                .map_err(xlsx_error)?;
+                        }
+                        // 带时区的日期时间（保留时区信息）和 1900-03-01 之前的日期写为文本
+                        None => {
+                            worksheet
+                                .write_string(row_num, col_num, value.to_string())
+                                .map_err(xlsx_error)?;
+                        }
+                    }
+                }
+                // Excel 只有双精度数字，Decimal 按 f64 写入
+                AnyValue::Decimal(v, _, scale) => {
+                    worksheet
+                        .write_number(row_num, col_num, decimal_to_f64(v, scale))
+                        .map_err(xlsx_error)?;
+                }
+                ref v if is_numeric => {
+                    if let Some(number) = v.extract::<f64>() {
+                        worksheet.write_number(row_num, col_num, number).map_err(xlsx_error)?;
+                    }
+                }
+                ref v => {
+                    let text = match v.get_str() {
+                        Some(s) => s.to_string(),
+                        None => v.to_string(),
+                    };
+                    worksheet.write_string(row_num, col_num, text).map_err(xlsx_error)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// 工作表名称
+// ============================================================================
+/// 清理工作表名称并保证在工作簿内唯一
+///
+/// - 替换 Excel 不允许的字符 []:*?/\ 为 "_"
+/// - 截断到 31 个字符
+/// - 重名时追加 "(2)"、"(3)" ...
+fn unique_sheet_name(name: &str, used: &mut HashSet<String>) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if "[]:*?/\\".contains(c) { '_' } else { c })
+        .take(SHEET_NAME_MAX_LEN)
+        .collect();
+    let cleaned = if cleaned.trim().is_empty() {
+        "Sheet".to_string()
+    } else {
+        cleaned
+    };
+
+    let mut candidate = cleaned.clone();
+    let mut counter = 2;
+    // Excel 的工作表名称不区分大小写
+    while used.contains(&candidate.to_lowercase()) {
+        let suffix = format!("({})", counter);
+        let prefix: String = cleaned
+            .chars()
+            .take(SHEET_NAME_MAX_LEN - suffix.chars().count())
+            .collect();
+        candidate = format!("{}{}", prefix, suffix);
+        counter += 1;
+    }
+
+    used.insert(candidate.to_lowercase());
+    candidate
+}
@@ -0,0 +1,261 @@
+// ============================================================================
+// data/export.rs - 分批写出与原子替换
+// ============================================================================
+// 这个文件负责大数据集的文件导出：
+// - 先写入同目录下的临时文件，成功后再重命名为目标文件
+//   （导出失败、被取消或应用中途关闭时，目标文件保持原样，不会留下不完整的文件）
+// - 不覆盖模式下目标文件已存在时直接返回错误；写出期间目标文件被创建时，重命名前再检查一次，不会覆盖
+// - CSV / Parquet / Arrow IPC 按批次写出，每批之后更新任务进度并检查取消标记
+// - Arrow IPC 流写出到内存或命名管道，供本地 Python / R 进程直接读取
+//   （写出目标可以是文件，也可以是带密码 zip 中的条目）
+// - CSV 的分隔符、编码、引号、日期格式等由导出预设决定（见 export_profiles.rs）
+// - Parquet 可以指定压缩算法、压缩级别和每个 row group 的行数
+
+use crate::data::export_profiles::configure_csv_writer;
+use crate::data::tasks::TaskHandle;
+use crate::error::{DataAnalystError, Result};
+use crate::models::{CsvExportProfile, ParquetExportCompression};
+use polars::prelude::*;
+use polars_arrow::io::ipc::write::default_ipc_fields;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// 每批写出的行数
+pub const EXPORT_CHUNK_ROWS: usize = 50_000;
+
+// ============================================================================
+// 原子写入
+// ============================================================================
+/// 目标文件对应的临时文件路径：同目录下的 ".{文件名}.{uuid}.tmp"
+///
+/// 放在同一目录下，保证重命名不会跨文件系统
+pub fn temp_path_for(output_path: &Path) -> PathBuf {
+    let file_name = output_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "export".to_string());
+    output_path.with_file_name(format!(".{}.{}.tmp", file_name, uuid::Uuid::new_v4()))
+}
+
+/// 先写入临时文件，成功后重命名为目标文件
+///
+/// write 返回错误时删除临时文件，目标文件保持不变
+pub fn write_atomic<F>(output_path: &str, write: F) -> Result<()>
+where
+    F: FnOnce(&mut File) -> Result<()>,
+{
+    write_atomic_checked(output_path, true, write)
+}
+
+/// 同 write_atomic；overwrite 为 false 时不覆盖已存在的目标文件
+///
+/// 导出命令开始前的 check_overwrite 只能尽早报错，写出期间目标文件仍可能被其他程序创建，
+/// 因此写完后用硬链接把临时文件放到目标位置（目标已存在时失败，不会覆盖），再删除临时文件；
+/// 文件系统不支持硬链接（例如 FAT32 的 U 盘）时退回到检查后重命名
+pub fn write_atomic_checked<F>(output_path: &str, overwrite: bool, write: F) -> Result<()>
+where
+    F: FnOnce(&mut File) -> Result<()>,
+{
+    let target = Path::new(output_path);
+    let temp_path = temp_path_for(target);
+
+    let result = (|| -> Result<()> {
+        let mut file = File::create(&temp_path)?;
+        write(&mut file)?;
+        file.sync_all()?;
+        drop(file);
+        if overwrite {
+            std::fs::rename(&temp_path, target)?;
+            return Ok(());
+        }
+        match std::fs::hard_link(&temp_path, target) {
+            Ok(()) => {
+                let _ = std::fs::remove_file(&temp_path);
+                Ok(())
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Err(file_exists_error(output_path)),
+            Err(_) => {
+                check_overwrite(output_path, false)?;
+                std::fs::rename(&temp_path, target)?;
+                Ok(())
+            }
+        }
+    })();
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// 检查目标文件能否写入：overwrite 为 false 且目标文件已存在时返回错误
+pub fn check_overwrite(output_path: &str, overwrite: bool) -> Result<()> {
+    if !overwrite && Path::new(output_path).exists() {
+        return Err(file_exists_error(output_path));
+    }
+    Ok(())
+}
+
+/// 以不覆盖的方式占用目标路径：创建空文件，目标文件已存在时返回错误
+///
+/// 用于不经过临时文件、直接在目标位置写入的导出（例如 SQLite 数据库）
+pub fn create_new_output(output_path: &str) -> Result<()> {
+    match File::options().write(true).create_new(true).open(output_path) {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Err(file_exists_error(output_path)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 目标文件已存在的错误
+fn file_exists_error(output_path: &str) -> DataAnalystError {
+    DataAnalystError::InvalidOperation(format!(
+        "目标文件已存在: {}（如需覆盖请设置 overwrite = true）",
+        output_path
+    ))
+}
+
+// ============================================================================
+// 分批写出
+// ============================================================================
+/// 分批写出 CSV（只有第一批写 BOM 和表头）
+///
+/// 分隔符、引号、换行符、日期格式等按导出预设配置（未指定预设时使用 CsvExportProfile::default()）
+/// 每批之后更新已写行数并调用 on_progress；已请求取消时返回 Cancelled 错误
+pub fn write_csv_chunks<W: Write>(
+    file: &mut W,
+    df: &DataFrame,
+    profile: &CsvExportProfile,
+    task: &TaskHandle,
+    on_progress: impl Fn(&TaskHandle),
+) -> Result<()> {
+    // 空数据集只写表头
+    if df.height() == 0 {
+        configure_csv_writer(CsvWriter::new(&mut *file), profile, true).finish(&mut df.clone())?;
+        return Ok(());
+    }
+
+    let mut written = 0;
+    while written < df.height() {
+        task.check_cancelled()?;
+
+        let mut chunk = df.slice(written as i64, EXPORT_CHUNK_ROWS);
+        configure_csv_writer(CsvWriter::new(&mut *file), profile, written == 0).finish(&mut chunk)?;
+
+        written += chunk.height();
+        task.set_processed(written);
+        on_progress(task);
+    }
+
+    Ok(())
+}
+
+/// 将压缩选项转换为 Polars 的 Parquet 压缩方式（None 表示 Polars 默认值）
+///
+/// 级别超出范围时返回错误（gzip 0-9，zstd 1-22）
+pub fn parquet_compression(compression: Option<ParquetExportCompression>) -> Result<ParquetCompression> {
+    let invalid_level = |e: PolarsError| DataAnalystError::InvalidOperation(format!("压缩级别无效: {}", e));
+    Ok(match compression {
+        None => ParquetCompression::default(),
+        Some(ParquetExportCompression::Uncompressed) => ParquetCompression::Uncompressed,
+        Some(ParquetExportCompression::Snappy) => ParquetCompression::Snappy,
+        Some(ParquetExportCompression::Gzip { level }) => {
+            ParquetCompression::Gzip(level.map(GzipLevel::try_new).transpose().map_err(invalid_level)?)
+        }
+        Some(ParquetExportCompression::Zstd { level }) => {
+            ParquetCompression::Zstd(level.map(ZstdLevel::try_new).transpose().map_err(invalid_level)?)
+        }
+    })
+}
+
+/// 分批写出 Parquet（每批一个 row group）
+///
+/// 参数：
+/// - compression: 压缩方式（见 parquet_compression）
+/// - row_group_size: 每个 row group 的行数（None 表示 EXPORT_CHUNK_ROWS）
+///
+/// 每批之后更新已写行数并调用 on_progress；已请求取消时返回 Cancelled 错误
+pub fn write_parquet_chunks<W: Write>(
+    file: &mut W,
+    df: &DataFrame,
+    compression: ParquetCompression,
+    row_group_size: Option<usize>,
+    task: &TaskHandle,
+    on_progress: impl Fn(&TaskHandle),
+) -> Result<()> {
+    let chunk_rows = row_group_size.unwrap_or(EXPORT_CHUNK_ROWS);
+    if chunk_rows == 0 {
+        return Err(DataAnalystError::InvalidOperation(
+            "row group 行数必须大于 0".to_string(),
+        ));
+    }
+
+    let schema = df.schema().clone();
+    let mut writer = ParquetWriter::new(&mut *file)
+        .with_compression(compression)
+        .with_row_group_size(Some(chunk_rows))
+        .batched(&schema)?;
+
+    let mut written = 0;
+    while written < df.height() {
+        task.check_cancelled()?;
+
+        // 合并为单个 chunk，保证每批只写出一个 row group
+        let mut chunk = df.slice(written as i64, chunk_rows);
+        chunk.as_single_chunk_par();
+        writer.write_batch(&chunk)?;
+
+        written += chunk.height();
+        task.set_processed(written);
+        on_progress(task);
+    }
+
+    writer.finish()?;
+    Ok(())
+}
+
+/// 分批写出 Arrow IPC（Feather v2），每批一个 record batch
+///
+/// 使用最兼容的 Arrow 类型（普通 Utf8 / Binary，而不是 Utf8View），
+/// 保证旧版本的 pyarrow / R arrow 也能读取
+/// 每批之后更新已写行数并调用 on_progress；已请求取消时返回 Cancelled 错误
+pub fn write_ipc_chunks<W: Write>(
+    file: &mut W,
+    df: &DataFrame,
+    task: &TaskHandle,
+    on_progress: impl Fn(&TaskHandle),
+) -> Result<()> {
+    let schema = df.schema().clone();
+    let ipc_fields = default_ipc_fields(schema.to_arrow(CompatLevel::oldest()).iter_values());
+    let mut writer = IpcWriter::new(&mut *file)
+        .with_compat_level(CompatLevel::oldest())
+        .batched(&schema, ipc_fields)?;
+
+    let mut written = 0;
+    while written < df.height() {
+        task.check_cancelled()?;
+
+        let chunk = df.slice(written as i64, EXPORT_CHUNK_ROWS);
+        writer.write_batch(&chunk)?;
+
+        written += chunk.height();
+        task.set_processed(written);
+        on_progress(task);
+    }
+
+    writer.finish()?;
+    Ok(())
+}
+
+/// 写出 Arrow IPC 流（stream 格式，没有文件尾，可以边读边处理）
+///
+/// 与 write_ipc_chunks 相同使用最兼容的 Arrow 类型
+/// 可以直接被 pyarrow.ipc.open_stream、polars.read_ipc_stream、R arrow::read_ipc_stream 读取
+pub fn write_ipc_stream<W: Write>(writer: &mut W, df: &DataFrame) -> Result<()> {
+    IpcStreamWriter::new(&mut *writer)
+        .with_compat_level(CompatLevel::oldest())
+        .finish(&mut df.clone())?;
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,168 @@
+// ============================================================================
+// data/export_profiles.rs - CSV 导出预设
+// ============================================================================
+// 这个文件负责 CSV 导出预设的管理和应用：
+// - 内置预设：Excel-DE、RFC4180、Tab for BigQuery
+// - 用户预设：以 JSON 保存在应用配置目录下的 export_profiles.json
+// - 校验预设，并把预设应用到 Polars 的 CsvWriter
+
+use crate::data::export::write_atomic;
+use crate::data::format::validate_date_format;
+use crate::error::{DataAnalystError, Result};
+use crate::models::{CsvEncoding, CsvExportProfile, CsvQuoteStyle, LineEnding};
+use polars::prelude::*;
+use std::io::Write;
+use std::path::Path;
+
+/// 用户预设文件名（位于应用配置目录）
+pub const PROFILES_FILE_NAME: &str = "export_profiles.json";
+
+// ============================================================================
+// 内置预设
+// ============================================================================
+/// 内置预设列表
+///
+/// - Excel-DE: 德语地区 Excel（分号分隔、逗号小数点、UTF-8 BOM、日.月.年）
+/// - RFC4180: 标准 CSV（逗号分隔、CRLF 换行）
+/// - Tab for BigQuery: 制表符分隔，日期和时间使用 BigQuery 可以直接识别的格式
+pub fn builtin_profiles() -> Vec<CsvExportProfile> {
+    vec![
+        CsvExportProfile {
+            name: "Excel-DE".to_string(),
+            delimiter: ';',
+            encoding: CsvEncoding::Utf8Bom,
+            line_ending: LineEnding::Crlf,
+            decimal_comma: true,
+            date_format: Some("%d.%m.%Y".to_string()),
+            datetime_format: Some("%d.%m.%Y %H:%M:%S".to_string()),
+            builtin: true,
+            ..CsvExportProfile::default()
+        },
+        CsvExportProfile {
+            name: "RFC4180".to_string(),
+            line_ending: LineEnding::Crlf,
+            builtin: true,
+            ..CsvExportProfile::default()
+        },
+        CsvExportProfile {
+            name: "Tab for BigQuery".to_string(),
+            delimiter: '\t',
+            date_format: Some("%Y-%m-%d".to_string()),
+            datetime_format: Some("%Y-%m-%d %H:%M:%S%.f".to_string()),
+            builtin: true,
+            ..CsvExportProfile::default()
+        },
+    ]
+}
+
+/// 是否为内置预设名称
+pub fn is_builtin_profile(name: &str) -> bool {
+    builtin_profiles().iter().any(|profile| profile.name == name)
+}
+
+// ============================================================================
+// 校验
+// ============================================================================
+/// 检查预设选项是否有效
+pub fn validate_profile(profile: &CsvExportProfile) -> Result<()> {
+    let invalid = |message: String| -> Result<()> { Err(DataAnalystError::InvalidOperation(message)) };
+
+    if profile.name.trim().is_empty() {
+        return invalid("预设名称不能为空".to_string());
+    }
+
+    for (label, c) in [("分隔符", profile.delimiter), ("引号字符", profile.quote_char)] {
+        if !c.is_ascii() || c == '\n' || c == '\r' {
+            return invalid(format!("{}必须是单个 ASCII 字符（不能是换行），当前为 {:?}", label, c));
+        }
+    }
+    if profile.delimiter == profile.quote_char {
+        return invalid("分隔符和引号字符不能相同".to_string());
+    }
+    if profile.decimal_comma && profile.delimiter == ',' {
+        return invalid("使用逗号作为小数点时，分隔符不能是逗号".to_string());
+    }
+
+    for format in [&profile.date_format, &profile.datetime_format].into_iter().flatten() {
+        validate_date_format(format)?;
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// 用户预设存储
+// ============================================================================
+/// 读取用户预设（文件不存在时返回空列表）
+pub fn load_user_profiles(path: &Path) -> Result<Vec<CsvExportProfile>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| DataAnalystError::InvalidDataFormat(format!("导出预设文件格式错误: {}", e)))
+}
+
+/// 保存用户预设（先写临时文件再替换）
+pub fn save_user_profiles(path: &Path, profiles: &[CsvExportProfile]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let content = serde_json::to_string_pretty(profiles)?;
+    write_atomic(&path.to_string_lossy(), |file| {
+        file.write_all(content.as_bytes())?;
+        Ok(())
+    })
+}
+
+/// 所有预设：内置预设在前，用户预设在后
+pub fn all_profiles(path: &Path) -> Result<Vec<CsvExportProfile>> {
+    let mut profiles = builtin_profiles();
+    profiles.extend(load_user_profiles(path)?.into_iter().map(|profile| CsvExportProfile {
+        builtin: false,
+        ..profile
+    }));
+    Ok(profiles)
+}
+
+/// 按名称查找预设（内置预设优先）
+pub fn find_profile(path: &Path, name: &str) -> Result<CsvExportProfile> {
+    all_profiles(path)?
+        .into_iter()
+        .find(|profile| profile.name == name)
+        .ok_or_else(|| DataAnalystError::InvalidOperation(format!("导出预设 '{}' 不存在", name)))
+}
+
+// ============================================================================
+// 应用到 CsvWriter
+// ============================================================================
+/// 按预设配置 CsvWriter
+///
+/// 参数：
+/// - first_chunk: 是否为第一批（只有第一批写 BOM 和表头）
+pub fn configure_csv_writer<W: Write>(
+    writer: CsvWriter<W>,
+    profile: &CsvExportProfile,
+    first_chunk: bool,
+) -> CsvWriter<W> {
+    let quote_style = match profile.quote_style {
+        CsvQuoteStyle::Necessary => QuoteStyle::Necessary,
+        CsvQuoteStyle::Always => QuoteStyle::Always,
+        CsvQuoteStyle::NonNumeric => QuoteStyle::NonNumeric,
+        CsvQuoteStyle::Never => QuoteStyle::Never,
+    };
+
+    writer
+        .include_bom(first_chunk && profile.encoding == CsvEncoding::Utf8Bom)
+        .include_header(first_chunk && profile.include_header)
+        .with_separator(profile.delimiter as u8)
+        .with_quote_char(profile.quote_char as u8)
+        .with_quote_style(quote_style)
+        .with_line_terminator(profile.line_ending.as_str().to_string())
+        .with_decimal_comma(profile.decimal_comma)
+        .with_date_format(profile.date_format.clone())
+        .with_datetime_format(profile.datetime_format.clone())
+        .with_null_value(profile.null_value.clone())
+}
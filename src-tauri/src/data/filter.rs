@@ -0,0 +1,520 @@
+// ============================================================================
+// data/filter.rs - 筛选条件构建
+// ============================================================================
+// 这个文件负责把筛选条件转换为 Polars 表达式（Expr）：
+// 1. SQL WHERE 子句 → 谓词表达式（解析前检查常见的错误写法，给出明确提示）
+// 2. 结构化筛选条件 → 谓词表达式
+// 3. 按照 NullPolicy 处理谓词结果为 null 的行
+// 4. 按 ExportSubset 取出导出用的列和行
+//
+// SQL 条件中空值和集合相关的写法：
+// - email IS NULL / email IS NOT NULL（不能写成 = NULL，结果永远为 null）
+// - region IN ('EU', 'US') / region NOT IN ('EU')
+// - amount BETWEEN 10 AND 100（包含两端）
+// - name LIKE 'A%' / name ILIKE '%smith'（% 匹配任意字符，_ 匹配单个字符；ILIKE 不区分大小写）
+
+use crate::error::{DataAnalystError, Result};
+use crate::models::{ExportSubset, FilterCondition, FilterOperator, NullPolicy};
+use polars::prelude::*;
+
+// ============================================================================
+// SQL 谓词
+// ============================================================================
+/// 将 SQL WHERE 条件（不含 "WHERE" 关键字）解析为谓词表达式
+///
+/// 示例：
+/// ```rust
+/// let predicate = build_sql_predicate("region = 'EU' AND amount > 100")?;
+/// let predicate = build_sql_predicate("email IS NOT NULL AND region IN ('EU', 'US')")?;
+/// ```
+pub fn build_sql_predicate(expression: &str) -> Result<Expr> {
+    if expression.trim().is_empty() {
+        return Err(DataAnalystError::InvalidOperation("筛选条件不能为空".to_string()));
+    }
+
+    check_sql_condition(expression)?;
+
+    polars::sql::sql_expr(expression).map_err(|e| {
+        DataAnalystError::InvalidOperation(format!(
+            "SQL 条件解析失败: {}（支持的写法示例: {}）",
+            e, SQL_CONDITION_EXAMPLES
+        ))
+    })
+}
+
+/// 解析失败时附带的写法示例
+const SQL_CONDITION_EXAMPLES: &str =
+    "a IS NULL、a IS NOT NULL、a IN (1, 2)、a NOT IN ('x')、a BETWEEN 1 AND 10、a LIKE 'A%'、a ILIKE '%b%'";
+
+/// SQL 条件中的词法单元（只用于语法检查）
+#[derive(Debug)]
+enum SqlToken {
+    /// 关键字或标识符（统一转为大写）
+    Word(String),
+    /// 比较运算符（=、!=、<>、<= 等）
+    Operator(String),
+    /// 单引号字符串字面量
+    Literal,
+    /// 其他单个符号（括号、逗号等）
+    Symbol(char),
+}
+
+/// 将 SQL 条件拆分为词法单元
+fn tokenize_sql(expression: &str) -> Result<Vec<SqlToken>> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+
+        if c == '\'' || c == '"' {
+            // 单引号为字符串，双引号为带引号的标识符；连续两个引号表示转义
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some(q) if q == c && chars.peek() == Some(&c) => {
+                        chars.next();
+                        text.push(c);
+                    }
+                    Some(q) if q == c => break,
+                    Some(other) => text.push(other),
+                    None => {
+                        return Err(DataAnalystError::InvalidOperation(format!(
+                            "SQL 条件中的 {} 缺少结束引号",
+                            c
+                        )));
+                    }
+                }
+            }
+            tokens.push(if c == '\'' {
+                SqlToken::Literal
+            } else {
+                SqlToken::Word(text)
+            });
+        } else if c.is_alphanumeric() || c == '_' || c == '.' {
+            let mut word = c.to_string();
+            while let Some(&next) = chars.peek() {
+                if !(next.is_alphanumeric() || next == '_' || next == '.') {
+                    break;
+                }
+                word.push(next);
+                chars.next();
+            }
+            tokens.push(SqlToken::Word(word.to_uppercase()));
+        } else if "=!<>".contains(c) {
+            let mut op = c.to_string();
+            while let Some(&next) = chars.peek() {
+                if !"=!<>".contains(next) {
+                    break;
+                }
+                op.push(next);
+                chars.next();
+            }
+            tokens.push(SqlToken::Operator(op));
+        } else {
+            tokens.push(SqlToken::Symbol(c));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// 检查 SQL 条件中空值和集合运算符的常见错误写法
+///
+/// Polars SQL 对这些写法的报错不直观（或者不报错但筛选结果为空），这里提前给出明确提示：
+/// - `= NULL` / `!= NULL` / `<> NULL` → 应使用 IS NULL / IS NOT NULL
+/// - `IN` 后缺少括号列表，或列表为空
+/// - `BETWEEN` 缺少 `AND 上限`
+/// - `LIKE` / `ILIKE` 后不是单引号字符串模式
+fn check_sql_condition(expression: &str) -> Result<()> {
+    let tokens = tokenize_sql(expression)?;
+    let is_word = |token: Option<&SqlToken>, word: &str| matches!(token, Some(SqlToken::Word(w)) if w == word);
+    let invalid = |message: String| -> Result<()> { Err(DataAnalystError::InvalidOperation(message)) };
+
+    for (i, token) in tokens.iter().enumerate() {
+        let next = tokens.get(i + 1);
+
+        match token {
+            SqlToken::Operator(op) if is_word(next, "NULL") => {
+                let suggestion = if op == "=" || op == "==" {
+                    "IS NULL"
+                } else {
+                    "IS NOT NULL"
+                };
+                return invalid(format!(
+                    "不能使用 '{} NULL' 判断空值（结果永远为 null），请改用 {}",
+                    op, suggestion
+                ));
+            }
+            SqlToken::Word(w) if w == "IN" => match (next, tokens.get(i + 2)) {
+                (Some(SqlToken::Symbol('(')), Some(SqlToken::Symbol(')'))) => {
+                    return invalid("IN 列表不能为空，例如 region IN ('EU', 'US')".to_string());
+                }
+                (Some(SqlToken::Symbol('(')), _) => {}
+                _ => {
+                    return invalid("IN 后需要括号括起的值列表，例如 region IN ('EU', 'US')".to_string());
+                }
+            },
+            SqlToken::Word(w) if w == "BETWEEN" => {
+                // 下限之后、下一个 OR / BETWEEN 之前必须出现 AND
+                let has_and = tokens[i + 1..]
+                    .iter()
+                    .skip(1)
+                    .take_while(|t| !is_word(Some(*t), "OR") && !is_word(Some(*t), "BETWEEN"))
+                    .any(|t| is_word(Some(t), "AND"));
+                if !has_and {
+                    return invalid("BETWEEN 需要写成 '列 BETWEEN 下限 AND 上限'（包含两端）".to_string());
+                }
+            }
+            SqlToken::Word(w) if (w == "LIKE" || w == "ILIKE") && !matches!(next, Some(SqlToken::Literal)) => {
+                return invalid(format!(
+                    "{} 后需要单引号括起的模式，例如 name {} 'A%'（% 匹配任意字符，_ 匹配单个字符）",
+                    w, w
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// 结构化条件谓词
+// ============================================================================
+/// 将多个结构化筛选条件组合为一个谓词表达式
+///
+/// 参数：
+/// - conditions: 筛选条件列表（不能为空）
+/// - match_all: true 表示所有条件都满足（AND），false 表示任一条件满足（OR）
+pub fn build_condition_predicate(conditions: &[FilterCondition], match_all: bool) -> Result<Expr> {
+    let mut predicates = conditions.iter().map(build_single_condition);
+
+    let first = predicates
+        .next()
+        .ok_or_else(|| DataAnalystError::InvalidOperation("至少需要一个筛选条件".to_string()))??;
+
+    predicates.try_fold(first, |acc, predicate| {
+        let predicate = predicate?;
+        Ok(if match_all {
+            acc.and(predicate)
+        } else {
+            acc.or(predicate)
+        })
+    })
+}
+
+/// 构建单个结构化条件的谓词
+fn build_single_condition(condition: &FilterCondition) -> Result<Expr> {
+    let column = col(condition.column.as_str());
+
+    if !condition.operator.needs_value() {
+        return Ok(match condition.operator {
+            FilterOperator::IsNull => column.is_null(),
+            _ => column.is_not_null(),
+        });
+    }
+
+    let value = condition
+        .value
+        .as_ref()
+        .ok_or_else(|| DataAnalystError::InvalidOperation(format!("列 '{}' 的筛选条件缺少比较值", condition.column)))?;
+
+    match condition.operator {
+        FilterOperator::Contains => {
+            let pattern = value.as_str().ok_or_else(|| {
+                DataAnalystError::InvalidOperation(format!("列 '{}' 的包含条件需要字符串值", condition.column))
+            })?;
+            Ok(column.str().contains_literal(lit(pattern.to_string())))
+        }
+        FilterOperator::Like | FilterOperator::ILike => {
+            let pattern = value.as_str().ok_or_else(|| {
+                DataAnalystError::InvalidOperation(format!(
+                    "列 '{}' 的 LIKE 条件需要字符串模式，例如 \"A%\"",
+                    condition.column
+                ))
+            })?;
+            let regex = like_to_regex(pattern, condition.operator == FilterOperator::ILike);
+            Ok(column.str().contains(lit(regex), true))
+        }
+        FilterOperator::In | FilterOperator::NotIn => {
+            let values = value.as_array().map(Vec::as_slice).unwrap_or_default();
+
+            // 逐个比较后用 OR 组合：列值为 null 时结果仍为 null，交给 NullPolicy 处理
+            let matched = values
+                .iter()
+                .map(|v| Ok(column.clone().eq(json_to_literal(v)?)))
+                .collect::<Result<Vec<Expr>>>()?
+                .into_iter()
+                .reduce(|acc, predicate| acc.or(predicate))
+                .ok_or_else(|| {
+                    DataAnalystError::InvalidOperation(format!(
+                        "列 '{}' 的 IN 条件需要非空数组，例如 [\"EU\", \"US\"]",
+                        condition.column
+                    ))
+                })?;
+
+            Ok(if condition.operator == FilterOperator::In {
+                matched
+            } else {
+                matched.not()
+            })
+        }
+        FilterOperator::Between => {
+            let bounds = value.as_array().filter(|bounds| bounds.len() == 2).ok_or_else(|| {
+                DataAnalystError::InvalidOperation(format!(
+                    "列 '{}' 的 BETWEEN 条件需要 [下限, 上限] 两个值",
+                    condition.column
+                ))
+            })?;
+            let low = json_to_literal(&bounds[0])?;
+            let high = json_to_literal(&bounds[1])?;
+            Ok(column.clone().gt_eq(low).and(column.lt_eq(high)))
+        }
+        _ => {
+            let value = json_to_literal(value)?;
+            Ok(match condition.operator {
+                FilterOperator::Eq => column.eq(value),
+                FilterOperator::NotEq => column.neq(value),
+                FilterOperator::Gt => column.gt(value),
+                FilterOperator::GtEq => column.gt_eq(value),
+                FilterOperator::Lt => column.lt(value),
+                _ => column.lt_eq(value),
+            })
+        }
+    }
+}
+
+/// 将 LIKE 模式转换为正则表达式
+///
+/// % 匹配任意多个字符，_ 匹配单个字符，其他字符按字面匹配；ILIKE 不区分大小写
+fn like_to_regex(pattern: &str, case_insensitive: bool) -> String {
+    let mut regex = String::from(if case_insensitive { "(?is)^" } else { "(?s)^" });
+    for c in pattern.chars() {
+        match c {
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            c if r"\.+*?()|[]{}^$#&-~".contains(c) => {
+                regex.push('\\');
+                regex.push(c);
+            }
+            c => regex.push(c),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+/// 将 JSON 值转换为 Polars 字面量表达式
+pub fn json_to_literal(value: &serde_json::Value) -> Result<Expr> {
+    match value {
+        serde_json::Value::Bool(b) => Ok(lit(*b)),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Ok(lit(i)),
+            None => n
+                .as_f64()
+                .map(lit)
+                .ok_or_else(|| DataAnalystError::InvalidOperation(format!("无效的数值: {}", n))),
+        },
+        serde_json::Value::String(s) => Ok(lit(s.clone())),
+        _ => Err(DataAnalystError::InvalidOperation(format!("不支持的比较值: {}", value))),
+    }
+}
+
+// ============================================================================
+// 空值处理
+// ============================================================================
+/// 按照 NullPolicy 调整谓词，使谓词结果不再包含 null
+///
+/// - NullAsFalse: null → false
+/// - KeepNulls: null → true
+/// - OnlyNulls: 只有 null → true
+pub fn apply_null_policy(predicate: Expr, policy: NullPolicy) -> Expr {
+    match policy {
+        NullPolicy::NullAsFalse => predicate.fill_null(lit(false)),
+        NullPolicy::KeepNulls => predicate.fill_null(lit(true)),
+        NullPolicy::OnlyNulls => predicate.is_null(),
+    }
+}
+
+// ============================================================================
+// 被删除的行
+// ============================================================================
+/// 获取被筛选删除的行（keep 谓词不成立的行）
+///
+/// 参数：
+/// - keep: 已经应用 NullPolicy 的谓词（结果不含 null）
+pub fn removed_by_predicate(df: &DataFrame, keep: Expr) -> Result<DataFrame> {
+    Ok(df.clone().lazy().filter(keep.not()).collect()?)
+}
+
+/// 获取指定列中含有空值的行（即删除空值行时被删除的行）
+///
+/// 参数：
+/// - subset: 要检查的列（None 表示检查所有列）
+pub fn rows_with_nulls(df: &DataFrame, subset: Option<&[String]>) -> Result<DataFrame> {
+    let columns: Vec<&Column> = match subset {
+        Some(names) => names
+            .iter()
+            .map(|name| {
+                df.column(name)
+                    .map_err(|_| DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", name)))
+            })
+            .collect::<Result<_>>()?,
+        None => df.get_columns().iter().collect(),
+    };
+
+    match columns
+        .iter()
+        .map(|column| column.is_null())
+        .reduce(|acc, mask| &acc | &mask)
+    {
+        Some(mask) => Ok(df.filter(&mask)?),
+        None => Ok(df.clear()),
+    }
+}
+
+// ============================================================================
+// 导出子集
+// ============================================================================
+/// 按 ExportSubset 取出要导出的列和行（返回新的 DataFrame，不修改原数据）
+///
+/// 先按条件筛选行（条件可以引用未导出的列），再选择列
+pub fn select_export_subset(df: &DataFrame, subset: &ExportSubset) -> Result<DataFrame> {
+    let columns = subset.columns.as_deref().filter(|columns| !columns.is_empty());
+    if let Some(columns) = columns
+        && let Some(missing) = columns.iter().find(|name| df.get_column_index(name).is_none())
+    {
+        return Err(DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", missing)));
+    }
+
+    let mut lf = df.clone().lazy();
+    if let Some(filter) = subset
+        .filter
+        .as_deref()
+        .map(str::trim)
+        .filter(|filter| !filter.is_empty())
+    {
+        let predicate = build_sql_predicate(filter)?;
+        lf = lf.filter(apply_null_policy(predicate, subset.null_policy));
+    }
+    if let Some(columns) = columns {
+        lf = lf.select(columns.iter().map(|name| col(name.as_str())).collect::<Vec<_>>());
+    }
+    Ok(lf.collect()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> DataFrame {
+        DataFrame::new(vec![
+            Column::new("region".into(), ["EU", "US", "APAC", "EU"]),
+            Column::new("amount".into(), [5i64, 10, 15, 20]),
+            Column::new("code".into(), ["a.b", "axb", "A_1%", "ab"]),
+        ])
+        .unwrap()
+    }
+
+    fn filtered(predicate: Expr) -> DataFrame {
+        sample()
+            .lazy()
+            .filter(predicate.fill_null(lit(false)))
+            .collect()
+            .unwrap()
+    }
+
+    fn strings(df: &DataFrame, column: &str) -> Vec<String> {
+        df.column(column)
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .map(|v| v.unwrap().to_string())
+            .collect()
+    }
+
+    fn condition(column: &str, operator: FilterOperator, value: serde_json::Value) -> FilterCondition {
+        FilterCondition {
+            column: column.to_string(),
+            operator,
+            value: Some(value),
+        }
+    }
+
+    #[test]
+    fn like_patterns_translate_wildcards_and_escape_regex_characters() {
+        assert_eq!(like_to_regex("A%", false), "(?s)^A.*$");
+        assert_eq!(like_to_regex("a_c", false), "(?s)^a.c$");
+        assert_eq!(like_to_regex("a.b", false), r"(?s)^a\.b$");
+        assert_eq!(like_to_regex("(x)+[y]", true), r"(?is)^\(x\)\+\[y\]$");
+    }
+
+    #[test]
+    fn like_conditions_match_literal_dots() {
+        let like = condition("code", FilterOperator::Like, json!("a.b"));
+        let df = filtered(build_single_condition(&like).unwrap());
+        assert_eq!(strings(&df, "code"), vec!["a.b"]);
+
+        let ilike = condition("code", FilterOperator::ILike, json!("a_1%"));
+        let df = filtered(build_single_condition(&ilike).unwrap());
+        assert_eq!(strings(&df, "code"), vec!["A_1%"]);
+    }
+
+    #[test]
+    fn null_comparisons_are_rejected() {
+        assert!(check_sql_condition("email = NULL").is_err());
+        assert!(check_sql_condition("email != NULL").is_err());
+        assert!(check_sql_condition("email <> null").is_err());
+        assert!(check_sql_condition("email IS NULL").is_ok());
+        assert!(check_sql_condition("email = 'NULL'").is_ok());
+    }
+
+    #[test]
+    fn sql_in_between_and_like_syntax_is_checked() {
+        assert!(check_sql_condition("region IN ()").is_err());
+        assert!(check_sql_condition("region IN 'EU'").is_err());
+        assert!(check_sql_condition("region IN ('EU', 'US')").is_ok());
+        assert!(check_sql_condition("amount BETWEEN 1").is_err());
+        assert!(check_sql_condition("amount BETWEEN 1 OR amount > 2").is_err());
+        assert!(check_sql_condition("amount BETWEEN 1 AND 10").is_ok());
+        assert!(check_sql_condition("name LIKE A").is_err());
+        assert!(check_sql_condition("name LIKE 'A%'").is_ok());
+    }
+
+    #[test]
+    fn sql_in_and_between_filter_rows() {
+        let df = filtered(build_sql_predicate("region IN ('EU', 'APAC')").unwrap());
+        assert_eq!(strings(&df, "region"), vec!["EU", "APAC", "EU"]);
+
+        let df = filtered(build_sql_predicate("amount BETWEEN 10 AND 15").unwrap());
+        assert_eq!(strings(&df, "region"), vec!["US", "APAC"]);
+    }
+
+    #[test]
+    fn structured_in_and_between_filter_rows() {
+        let in_condition = condition("region", FilterOperator::In, json!(["EU", "US"]));
+        let df = filtered(build_single_condition(&in_condition).unwrap());
+        assert_eq!(strings(&df, "region"), vec!["EU", "US", "EU"]);
+
+        let not_in = condition("region", FilterOperator::NotIn, json!(["EU"]));
+        let df = filtered(build_single_condition(&not_in).unwrap());
+        assert_eq!(strings(&df, "region"), vec!["US", "APAC"]);
+
+        let between = condition("amount", FilterOperator::Between, json!([10, 15]));
+        let df = filtered(build_single_condition(&between).unwrap());
+        assert_eq!(strings(&df, "region"), vec!["US", "APAC"]);
+    }
+
+    #[test]
+    fn structured_in_and_between_require_valid_values() {
+        assert!(build_single_condition(&condition("region", FilterOperator::In, json!([]))).is_err());
+        assert!(build_single_condition(&condition("region", FilterOperator::In, json!("EU"))).is_err());
+        assert!(build_single_condition(&condition("amount", FilterOperator::Between, json!([1]))).is_err());
+        assert!(build_single_condition(&condition("amount", FilterOperator::Between, json!([1, 2, 3]))).is_err());
+    }
+}
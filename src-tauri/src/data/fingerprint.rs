@@ -0,0 +1,179 @@
+// ============================================================================
+// data/fingerprint.rs - 数据集内容指纹
+// ============================================================================
+// 这个文件负责计算 DataFrame 的内容哈希：
+// 1. 每列单独计算 SHA-256（列名 + 数据类型 + 所有值）
+// 2. 按列顺序把行数和各列哈希组合为整体摘要
+//
+// 使用固定算法（而不是 Polars 内部带随机种子的哈希），
+// 保证同样的数据在不同会话、不同机器上得到相同的指纹
+
+use crate::data::loader::any_value_to_json;
+use crate::error::Result;
+use crate::models::{ColumnFingerprint, DatasetFingerprint};
+use polars::prelude::*;
+use sha2::{Digest, Sha256};
+use std::io::Write;
+
+// ============================================================================
+// 指纹计算
+// ============================================================================
+/// 计算数据集指纹
+pub fn compute_fingerprint(df: &DataFrame) -> Result<DatasetFingerprint> {
+    let columns = df
+        .get_columns()
+        .iter()
+        .map(|column| hash_column(column.as_materialized_series()))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut hasher = Sha256::new();
+    hasher.update((df.height() as u64).to_le_bytes());
+    for column in &columns {
+        hasher.update(column.hash.as_bytes());
+    }
+
+    Ok(DatasetFingerprint {
+        digest: format!("{:x}", hasher.finalize()),
+        row_count: df.height(),
+        columns,
+    })
+}
+
+/// 计算单列指纹
+///
+/// 每个值的编码：
+/// - 空值：标记字节 0
+/// - 字符串：标记字节 1 + 长度 + UTF-8 字节（避免拼接歧义）
+/// - 列表 / 结构体：标记字节 3 + 长度 + JSON 文本（Display 会截断长列表）
+/// - 布尔 / 数值 / 日期时间：标记字节 4 + 原始值的字节（Display 会对浮点数四舍五入，并受显示设置影响）
+/// - 其他：标记字节 2 + 值的文本表示 + 分隔符
+fn hash_column(series: &Series) -> Result<ColumnFingerprint> {
+    let dtype = format!("{:?}", series.dtype());
+
+    let mut hasher = Sha256::new();
+    write_prefixed(&mut hasher, series.name().as_bytes());
+    write_prefixed(&mut hasher, dtype.as_bytes());
+
+    for value in series.iter() {
+        match value {
+            AnyValue::Null => hasher.update([0u8]),
+            AnyValue::String(s) => {
+                hasher.update([1u8]);
+                write_prefixed(&mut hasher, s.as_bytes());
+            }
+            AnyValue::StringOwned(s) => {
+                hasher.update([1u8]);
+                write_prefixed(&mut hasher, s.as_bytes());
+            }
+            AnyValue::List(_) | AnyValue::Struct(..) | AnyValue::StructOwned(_) => {
+                hasher.update([3u8]);
+                write_prefixed(&mut hasher, any_value_to_json(value).to_string().as_bytes());
+            }
+            AnyValue::Binary(bytes) => {
+                hasher.update([4u8]);
+                write_prefixed(&mut hasher, bytes);
+            }
+            AnyValue::BinaryOwned(bytes) => {
+                hasher.update([4u8]);
+                write_prefixed(&mut hasher, &bytes);
+            }
+            other if write_exact(&mut hasher, &other) => {}
+            other => {
+                hasher.update([2u8]);
+                write!(hasher, "{}", other)?;
+                hasher.update([0u8]);
+            }
+        }
+    }
+
+    Ok(ColumnFingerprint {
+        name: series.name().to_string(),
+        dtype,
+        hash: format!("{:x}", hasher.finalize()),
+    })
+}
+
+/// 写入布尔、数值和日期时间类型的原始值，其他类型返回 false
+///
+/// 浮点数按位写入：所有 NaN 视为同一个值，-0.0 视为 0.0；
+/// 日期、时间、时长和 Decimal 写入底层整数（单位、时区和小数位已包含在列类型中）
+fn write_exact(hasher: &mut Sha256, value: &AnyValue) -> bool {
+    let bytes = match *value {
+        AnyValue::Boolean(v) => vec![v as u8],
+        AnyValue::UInt8(v) => v.to_le_bytes().to_vec(),
+        AnyValue::UInt16(v) => v.to_le_bytes().to_vec(),
+        AnyValue::UInt32(v) => v.to_le_bytes().to_vec(),
+        AnyValue::UInt64(v) => v.to_le_bytes().to_vec(),
+        AnyValue::Int8(v) => v.to_le_bytes().to_vec(),
+        AnyValue::Int16(v) => v.to_le_bytes().to_vec(),
+        AnyValue::Int32(v) => v.to_le_bytes().to_vec(),
+        AnyValue::Int64(v) => v.to_le_bytes().to_vec(),
+        AnyValue::Float32(v) => canonical_f32_bits(v).to_le_bytes().to_vec(),
+        AnyValue::Float64(v) => canonical_f64_bits(v).to_le_bytes().to_vec(),
+        AnyValue::Date(v) => v.to_le_bytes().to_vec(),
+        AnyValue::Datetime(v, _, _) | AnyValue::DatetimeOwned(v, _, _) => v.to_le_bytes().to_vec(),
+        AnyValue::Duration(v, _) => v.to_le_bytes().to_vec(),
+        AnyValue::Time(v) => v.to_le_bytes().to_vec(),
+        AnyValue::Decimal(v, _, _) => v.to_le_bytes().to_vec(),
+        _ => return false,
+    };
+    hasher.update([4u8]);
+    hasher.update(bytes);
+    true
+}
+
+fn canonical_f64_bits(v: f64) -> u64 {
+    if v.is_nan() {
+        f64::NAN.to_bits()
+    } else if v == 0.0 {
+        0.0f64.to_bits()
+    } else {
+        v.to_bits()
+    }
+}
+
+fn canonical_f32_bits(v: f32) -> u32 {
+    if v.is_nan() {
+        f32::NAN.to_bits()
+    } else if v == 0.0 {
+        0.0f32.to_bits()
+    } else {
+        v.to_bits()
+    }
+}
+
+/// 写入带长度前缀的字节
+fn write_prefixed(hasher: &mut Sha256, bytes: &[u8]) {
+    hasher.update((bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compute_fingerprint;
+    use polars::prelude::*;
+
+    fn digest(values: &[f64]) -> String {
+        let df = DataFrame::new(vec![Column::new("x".into(), values)]).unwrap();
+        compute_fingerprint(&df).unwrap().digest
+    }
+
+    #[test]
+    fn near_equal_floats_have_different_fingerprints() {
+        assert_ne!(digest(&[0.1234561]), digest(&[0.1234562]));
+        assert_ne!(digest(&[1.0]), digest(&[1.0 + f64::EPSILON]));
+    }
+
+    #[test]
+    fn nan_and_signed_zero_are_canonical() {
+        assert_eq!(digest(&[0.0]), digest(&[-0.0]));
+        assert_eq!(digest(&[f64::NAN]), digest(&[-f64::NAN]));
+        assert_eq!(digest(&[f64::NAN]), digest(&[f64::from_bits(0x7ff8_0000_0000_0001)]));
+    }
+
+    #[test]
+    fn same_data_has_same_fingerprint() {
+        assert_eq!(digest(&[1.5, 2.25]), digest(&[1.5, 2.25]));
+        assert_ne!(digest(&[1.5, 2.25]), digest(&[2.25, 1.5]));
+    }
+}
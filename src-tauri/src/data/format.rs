@@ -0,0 +1,170 @@
+// ============================================================================
+// data/format.rs - 列显示格式化
+// ============================================================================
+// 这个文件负责按照 ColumnFormat 把单元格值格式化为字符串
+// 用于表格展示（dataframe_to_json_rows）和 CSV 导出
+// 格式化只影响输出结果，不修改 DataFrame 中的原始数据
+
+use crate::data::decimal::{decimal_to_f64, is_numeric_dtype};
+use crate::error::{DataAnalystError, Result};
+use crate::models::ColumnFormat;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use polars::prelude::*;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+// ============================================================================
+// 单值格式化
+// ============================================================================
+/// 按照显示格式格式化单个值
+///
+/// 返回：
+/// - Some(String): 格式适用于该值，返回格式化后的字符串
+/// - None: 空值，或者格式不适用于该类型（调用方应使用默认转换）
+pub fn format_any_value(value: &AnyValue, format: &ColumnFormat) -> Option<String> {
+    if value.is_null() {
+        return None;
+    }
+
+    // 日期 / 日期时间
+    if let Some(date_format) = &format.date_format
+        && let Some(datetime) = any_value_to_naive_datetime(value)
+    {
+        let mut text = String::new();
+        // 无效的格式字符串会返回错误而不是 panic
+        return write!(text, "{}", datetime.format(date_format)).ok().map(|_| text);
+    }
+
+    // 数值
+    if is_numeric_dtype(&value.dtype()) {
+        let number = match value {
+            AnyValue::Decimal(v, _, scale) => decimal_to_f64(*v, *scale),
+            _ => value.extract::<f64>()?,
+        };
+        if number.is_finite() && has_number_format(format) {
+            return Some(format_number(number, format));
+        }
+    }
+
+    None
+}
+
+/// 检查日期格式字符串是否有效
+pub fn validate_date_format(date_format: &str) -> Result<()> {
+    if StrftimeItems::new(date_format).any(|item| matches!(item, Item::Error)) {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "无效的日期格式: {}",
+            date_format
+        )));
+    }
+    Ok(())
+}
+
+/// 是否设置了任何数值格式
+fn has_number_format(format: &ColumnFormat) -> bool {
+    format.thousands_separator || format.decimal_places.is_some() || format.percentage
+}
+
+/// 格式化数值：百分比 → 小数位 → 千分位
+fn format_number(number: f64, format: &ColumnFormat) -> String {
+    let number = if format.percentage { number * 100.0 } else { number };
+
+    let mut text = match format.decimal_places {
+        Some(places) => format!("{:.*}", places, number),
+        None => number.to_string(),
+    };
+
+    if format.thousands_separator {
+        text = group_thousands(&text);
+    }
+
+    if format.percentage {
+        text.push('%');
+    }
+
+    text
+}
+
+/// 为整数部分添加千分位分隔符，例如 "-1234567.89" → "-1,234,567.89"
+fn group_thousands(text: &str) -> String {
+    let (sign, digits) = match text.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", text),
+    };
+
+    let (int_part, frac_part) = match digits.find('.') {
+        Some(pos) => digits.split_at(pos),
+        None => (digits, ""),
+    };
+
+    let mut grouped = String::with_capacity(int_part.len() + int_part.len() / 3);
+    for (i, ch) in int_part.chars().enumerate() {
+        if i > 0 && (int_part.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(ch);
+    }
+
+    format!("{}{}{}", sign, grouped, frac_part)
+}
+
+/// 将日期/日期时间值转换为 NaiveDateTime
+fn any_value_to_naive_datetime(value: &AnyValue) -> Option<NaiveDateTime> {
+    match value {
+        // Date 的物理值是距离 1970-01-01 的天数
+        AnyValue::Date(days) => NaiveDate::from_num_days_from_ce_opt(*days + 719_163)?.and_hms_opt(0, 0, 0),
+        AnyValue::Datetime(v, time_unit, _) => {
+            let datetime = match time_unit {
+                TimeUnit::Nanoseconds => DateTime::from_timestamp_nanos(*v),
+                TimeUnit::Microseconds => DateTime::from_timestamp_micros(*v)?,
+                TimeUnit::Milliseconds => DateTime::from_timestamp_millis(*v)?,
+            };
+            Some(datetime.naive_utc())
+        }
+        _ => None,
+    }
+}
+
+// ============================================================================
+// DataFrame 格式化（用于导出）
+// ============================================================================
+/// 将设置了显示格式的列转换为格式化后的字符串列
+///
+/// 未设置格式的列、以及格式不适用的值保持原样输出
+///
+/// 参数：
+/// - df: 原始 DataFrame
+/// - formats: 列名 -> 显示格式
+///
+/// 返回：
+/// - Result<DataFrame>: 格式化后的 DataFrame
+pub fn apply_column_formats(df: &DataFrame, formats: &HashMap<String, ColumnFormat>) -> Result<DataFrame> {
+    let mut result = df.clone();
+
+    for (name, format) in formats {
+        let Ok(column) = df.column(name) else {
+            continue;
+        };
+
+        let dtype = column.dtype();
+        if !(is_numeric_dtype(dtype) || dtype.is_temporal()) {
+            continue;
+        }
+
+        let series = column.as_materialized_series();
+        let values: Vec<Option<String>> = (0..series.len())
+            .map(|idx| {
+                let value = series.get(idx).ok()?;
+                if value.is_null() {
+                    return None;
+                }
+                Some(format_any_value(&value, format).unwrap_or_else(|| value.to_string()))
+            })
+            .collect();
+
+        result.with_column(Series::new(name.as_str().into(), values))?;
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,268 @@
+// ============================================================================
+// data/gaps.rs - 序列缺口检测
+// ============================================================================
+// 这个文件负责找出序列中缺失的值（例如缺失的发票号、缺失的日期）：
+// 1. 整数列按整数步长检查，日期 / 日期时间列按时间间隔步长检查
+// 2. 可以按分组列分别检查（例如每个门店的日期是否连续）
+// 3. 缺口报告可以写为 JSON 或 CSV 文件
+//
+// 检查前会去掉空值并排序，重复值不算缺口
+
+use crate::data::duration::parse_duration_micros;
+use crate::error::{DataAnalystError, Result};
+use crate::models::{GapReport, ReportFormat, SequenceGap};
+use chrono::DateTime;
+use polars::prelude::*;
+use std::path::Path;
+
+/// 最多返回的缺口明细数量
+pub const MAX_GAPS: usize = 10_000;
+
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+/// 序列值的类型（决定步长的解析方式和值的显示方式）
+#[derive(Clone, Copy)]
+enum SequenceKind {
+    /// 整数，物理值即数值
+    Integer,
+    /// 日期，物理值为 1970-01-01 以来的天数
+    Date,
+    /// 日期时间，物理值为微秒时间戳
+    Datetime,
+}
+
+impl SequenceKind {
+    /// 物理值的显示文本（日期时间按 UTC 显示）
+    fn format(self, value: i64) -> String {
+        match self {
+            SequenceKind::Integer => value.to_string(),
+            SequenceKind::Date => DateTime::from_timestamp(value * 86_400, 0)
+                .map(|d| d.date_naive().to_string())
+                .unwrap_or_else(|| value.to_string()),
+            SequenceKind::Datetime => DateTime::from_timestamp_micros(value)
+                .map(|d| d.naive_utc().format("%Y-%m-%d %H:%M:%S%.f").to_string())
+                .unwrap_or_else(|| value.to_string()),
+        }
+    }
+
+    /// 解析步长（返回物理值单位的步长）
+    ///
+    /// - 整数列：正整数，默认 1
+    /// - 日期列：整天数的时间间隔，默认 "1d"
+    /// - 日期时间列：时间间隔，默认 "1d"
+    fn parse_step(self, step: Option<&str>) -> Result<i64> {
+        let invalid = |message: String| DataAnalystError::InvalidOperation(message);
+
+        let value = match self {
+            SequenceKind::Integer => {
+                let text = step.unwrap_or("1");
+                text.trim()
+                    .parse::<i64>()
+                    .map_err(|_| invalid(format!("整数列的步长必须是整数: {}", text)))?
+            }
+            SequenceKind::Date | SequenceKind::Datetime => {
+                let text = step.unwrap_or("1d");
+                let micros = parse_duration_micros(text)
+                    .ok_or_else(|| invalid(format!("无法解析步长: {}（示例: \"1d\"、\"7d\"、\"1h\"）", text)))?;
+
+                match self {
+                    SequenceKind::Date if micros % MICROS_PER_DAY != 0 => {
+                        return Err(invalid(format!("日期列的步长必须是整天数: {}", text)));
+                    }
+                    SequenceKind::Date => micros / MICROS_PER_DAY,
+                    _ => micros,
+                }
+            }
+        };
+
+        if value <= 0 {
+            return Err(invalid("步长必须大于 0".to_string()));
+        }
+        Ok(value)
+    }
+}
+
+// ============================================================================
+// 缺口检测
+// ============================================================================
+/// 检测序列缺口
+///
+/// 参数：
+/// - column: 序列列（整数、日期或日期时间）
+/// - group_by: 分组列（为空表示整列作为一个序列）
+/// - step: 期望的步长（见 SequenceKind::parse_step）
+///
+/// 示例：
+/// ```rust
+/// // 每个门店每天都应该有一条记录
+/// let report = detect_sequence_gaps(&df, "date", &["store".to_string()], Some("1d"))?;
+/// ```
+pub fn detect_sequence_gaps(
+    df: &DataFrame,
+    column: &str,
+    group_by: &[String],
+    step: Option<&str>,
+) -> Result<GapReport> {
+    let series = df
+        .column(column)
+        .map_err(|_| DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", column)))?
+        .as_materialized_series();
+
+    let (kind, physical) = match series.dtype() {
+        dtype if dtype.is_integer() => (SequenceKind::Integer, series.cast(&DataType::Int64)?),
+        DataType::Date => (SequenceKind::Date, series.to_physical_repr().cast(&DataType::Int64)?),
+        DataType::Datetime(_, tz) => (
+            SequenceKind::Datetime,
+            series
+                .cast(&DataType::Datetime(TimeUnit::Microseconds, tz.clone()))?
+                .to_physical_repr()
+                .into_owned(),
+        ),
+        other => {
+            return Err(DataAnalystError::InvalidOperation(format!(
+                "列 '{}' 不是整数、日期或日期时间类型（当前类型: {:?}）",
+                column, other
+            )));
+        }
+    };
+    let step_value = kind.parse_step(step)?;
+
+    // 分组列转换为文本，和序列值一起去空值、排序
+    let mut columns = Vec::with_capacity(group_by.len() + 1);
+    for (i, name) in group_by.iter().enumerate() {
+        let group = df
+            .column(name)
+            .map_err(|_| DataAnalystError::InvalidOperation(format!("分组列 '{}' 不存在", name)))?
+            .cast(&DataType::String)?
+            .with_name(format!("__group_{}", i).into());
+        columns.push(group);
+    }
+    columns.push(physical.with_name("__value".into()).into_column());
+
+    let sort_by: Vec<String> = (0..group_by.len())
+        .map(|i| format!("__group_{}", i))
+        .chain(std::iter::once("__value".to_string()))
+        .collect();
+    let sorted = DataFrame::new(columns)?
+        .lazy()
+        .filter(col("__value").is_not_null())
+        .sort(sort_by, SortMultipleOptions::default())
+        .collect()?;
+
+    let values: Vec<i64> = sorted.column("__value")?.i64()?.into_no_null_iter().collect();
+    let groups: Vec<Vec<Option<String>>> = (0..group_by.len())
+        .map(|i| {
+            Ok(sorted
+                .column(&format!("__group_{}", i))?
+                .str()?
+                .into_iter()
+                .map(|v| v.map(str::to_string))
+                .collect())
+        })
+        .collect::<Result<_>>()?;
+    let group_at = |row: usize| -> Vec<Option<String>> { groups.iter().map(|g| g[row].clone()).collect() };
+    let same_group = |a: usize, b: usize| groups.iter().all(|g| g[a] == g[b]);
+
+    let mut gaps = Vec::new();
+    let mut gap_count = 0;
+    let mut missing_total: u64 = 0;
+
+    for row in 1..values.len() {
+        if !same_group(row - 1, row) {
+            continue;
+        }
+
+        let (after, before) = (values[row - 1], values[row]);
+        let diff = before as i128 - after as i128;
+        if diff <= step_value as i128 {
+            continue;
+        }
+
+        let missing = ((diff - 1) / step_value as i128) as u64;
+        gap_count += 1;
+        missing_total += missing;
+
+        if gaps.len() < MAX_GAPS {
+            gaps.push(SequenceGap {
+                group: group_at(row),
+                after: kind.format(after),
+                before: kind.format(before),
+                first_missing: kind.format(after + step_value),
+                last_missing: kind.format(after + step_value * missing as i64),
+                missing_count: missing,
+            });
+        }
+    }
+
+    Ok(GapReport {
+        column: column.to_string(),
+        group_by: group_by.to_vec(),
+        step: match kind {
+            SequenceKind::Integer => step_value.to_string(),
+            _ => step.unwrap_or("1d").trim().to_string(),
+        },
+        truncated: gap_count > gaps.len(),
+        gap_count,
+        missing_count: missing_total,
+        gaps,
+    })
+}
+
+// ============================================================================
+// 报告输出
+// ============================================================================
+/// 缺口明细转换为 DataFrame（分组列 + after, before, first_missing, last_missing, missing_count）
+pub fn gap_report_to_dataframe(report: &GapReport) -> Result<DataFrame> {
+    let mut columns: Vec<Column> = report
+        .group_by
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let values: Vec<Option<&str>> = report.gaps.iter().map(|gap| gap.group[i].as_deref()).collect();
+            Column::new(name.as_str().into(), values)
+        })
+        .collect();
+
+    let gaps = &report.gaps;
+    columns.extend([
+        Column::new(
+            "after".into(),
+            gaps.iter().map(|g| g.after.as_str()).collect::<Vec<_>>(),
+        ),
+        Column::new(
+            "before".into(),
+            gaps.iter().map(|g| g.before.as_str()).collect::<Vec<_>>(),
+        ),
+        Column::new(
+            "first_missing".into(),
+            gaps.iter().map(|g| g.first_missing.as_str()).collect::<Vec<_>>(),
+        ),
+        Column::new(
+            "last_missing".into(),
+            gaps.iter().map(|g| g.last_missing.as_str()).collect::<Vec<_>>(),
+        ),
+        Column::new(
+            "missing_count".into(),
+            gaps.iter().map(|g| g.missing_count).collect::<Vec<u64>>(),
+        ),
+    ]);
+
+    Ok(DataFrame::new(columns)?)
+}
+
+/// 将缺口报告写为 JSON（完整报告）或 CSV（缺口明细表）
+pub fn write_gap_report(report: &GapReport, path: &Path, format: ReportFormat) -> Result<()> {
+    match format {
+        ReportFormat::Json => {
+            let file = std::fs::File::create(path)?;
+            serde_json::to_writer_pretty(file, report)?;
+        }
+        ReportFormat::Csv => {
+            let mut df = gap_report_to_dataframe(report)?;
+            let mut file = std::fs::File::create(path)?;
+            CsvWriter::new(&mut file).include_header(true).finish(&mut df)?;
+        }
+    }
+
+    Ok(())
+}
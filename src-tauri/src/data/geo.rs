@@ -0,0 +1,175 @@
+// ============================================================================
+// data/geo.rs - 地理坐标工具
+// ============================================================================
+// 这个文件提供经纬度相关的计算：
+// - 坐标有效性检查与列校验
+// - Haversine 球面距离
+// - 网格 / Geohash 分桶（用于密度图等聚合）
+
+use crate::data::decimal::is_numeric_dtype;
+use crate::error::{DataAnalystError, Result};
+use crate::models::{CoordinateReport, GeoBucketMethod};
+use polars::prelude::*;
+
+/// 地球平均半径（千米）
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// Geohash 使用的 Base32 字符表
+const GEOHASH_BASE32: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// 校验报告中无效行号示例的最大数量
+const INVALID_SAMPLE_LIMIT: usize = 20;
+
+// ============================================================================
+// 坐标读取与校验
+// ============================================================================
+/// 读取坐标列并转换为 Float64
+pub fn coordinate_column(df: &DataFrame, column: &str) -> Result<Float64Chunked> {
+    let series = df
+        .column(column)
+        .map_err(|_| DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", column)))?;
+
+    if !is_numeric_dtype(series.dtype()) {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "列 '{}' 不是数值列，无法作为坐标",
+            column
+        )));
+    }
+
+    Ok(series.cast(&DataType::Float64)?.f64()?.clone())
+}
+
+/// 坐标是否有效（纬度 [-90, 90]，经度 [-180, 180]）
+pub fn is_valid_coordinate(lat: f64, lon: f64) -> bool {
+    (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon)
+}
+
+/// 校验经纬度列
+pub fn validate_coordinates(df: &DataFrame, lat_column: &str, lon_column: &str) -> Result<CoordinateReport> {
+    let lat = coordinate_column(df, lat_column)?;
+    let lon = coordinate_column(df, lon_column)?;
+
+    let mut report = CoordinateReport {
+        total_rows: df.height(),
+        valid_count: 0,
+        null_count: 0,
+        out_of_range_count: 0,
+        swapped_suspect_count: 0,
+        invalid_rows_sample: Vec::new(),
+    };
+
+    for (row, (lat, lon)) in lat.into_iter().zip(&lon).enumerate() {
+        match (lat, lon) {
+            (Some(lat), Some(lon)) if is_valid_coordinate(lat, lon) => {
+                report.valid_count += 1;
+                continue;
+            }
+            (Some(lat), Some(lon)) => {
+                report.out_of_range_count += 1;
+                if is_valid_coordinate(lon, lat) {
+                    report.swapped_suspect_count += 1;
+                }
+            }
+            _ => report.null_count += 1,
+        }
+
+        if report.invalid_rows_sample.len() < INVALID_SAMPLE_LIMIT {
+            report.invalid_rows_sample.push(row);
+        }
+    }
+
+    Ok(report)
+}
+
+// ============================================================================
+// 距离计算
+// ============================================================================
+/// Haversine 球面距离（千米）
+pub fn haversine_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lon = (lon2 - lon1).to_radians();
+
+    let a =
+        (d_lat / 2.0).sin().powi(2) + lat1.to_radians().cos() * lat2.to_radians().cos() * (d_lon / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+}
+
+// ============================================================================
+// 分桶
+// ============================================================================
+/// 按分桶方式计算坐标所在的桶标识
+///
+/// 坐标无效时返回 None
+pub fn geo_bucket(lat: f64, lon: f64, method: &GeoBucketMethod) -> Option<String> {
+    if !is_valid_coordinate(lat, lon) {
+        return None;
+    }
+
+    match method {
+        GeoBucketMethod::Grid { cell_size } => Some(grid_cell(lat, lon, *cell_size)),
+        GeoBucketMethod::Geohash { precision } => Some(encode_geohash(lat, lon, *precision)),
+    }
+}
+
+/// 检查分桶参数
+pub fn validate_bucket_method(method: &GeoBucketMethod) -> Result<()> {
+    match method {
+        GeoBucketMethod::Grid { cell_size } if !(*cell_size > 0.0 && *cell_size <= 180.0) => Err(
+            DataAnalystError::InvalidOperation(format!("网格边长必须在 (0, 180] 之间: {}", cell_size)),
+        ),
+        GeoBucketMethod::Geohash { precision } if !(1..=12).contains(precision) => Err(
+            DataAnalystError::InvalidOperation(format!("Geohash 精度必须在 1 ~ 12 之间: {}", precision)),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// 网格桶标识：网格西南角坐标 "lat,lon"
+///
+/// 小数位数由网格边长决定，例如边长 0.1 → "39.9,116.4"
+fn grid_cell(lat: f64, lon: f64, cell_size: f64) -> String {
+    let cell_lat = (lat / cell_size).floor() * cell_size;
+    let cell_lon = (lon / cell_size).floor() * cell_size;
+    let decimals = (-cell_size.log10()).ceil().clamp(0.0, 10.0) as usize;
+
+    format!("{:.*},{:.*}", decimals, cell_lat, decimals, cell_lon)
+}
+
+/// Geohash 编码
+///
+/// 经度、纬度交替二分（从经度开始），每 5 位编码为一个 Base32 字符
+pub fn encode_geohash(lat: f64, lon: f64, precision: usize) -> String {
+    let (mut lat_range, mut lon_range) = ((-90.0, 90.0), (-180.0, 180.0));
+    let mut hash = String::with_capacity(precision);
+    let mut bits = 0u8;
+    let mut bit_count = 0;
+    let mut even = true;
+
+    while hash.len() < precision {
+        let (range, value) = if even {
+            (&mut lon_range, lon)
+        } else {
+            (&mut lat_range, lat)
+        };
+
+        let mid = (range.0 + range.1) / 2.0;
+        bits <<= 1;
+        if value >= mid {
+            bits |= 1;
+            range.0 = mid;
+        } else {
+            range.1 = mid;
+        }
+
+        even = !even;
+        bit_count += 1;
+        if bit_count == 5 {
+            hash.push(GEOHASH_BASE32[bits as usize] as char);
+            bits = 0;
+            bit_count = 0;
+        }
+    }
+
+    hash
+}
@@ -0,0 +1,72 @@
+// ============================================================================
+// data/guardrails.rs - 行数保护设置
+// ============================================================================
+// 这个文件负责行数保护设置的读写、校验，以及判断一次操作是否需要确认
+// 设置以 JSON 保存在应用配置目录下的 row_guardrails.json，文件不存在时使用内置默认值
+
+use crate::data::export::write_atomic;
+use crate::error::{DataAnalystError, Result};
+use crate::models::{RowGuardrails, RowImpact};
+use std::io::Write;
+use std::path::Path;
+
+/// 行数保护设置文件名（位于应用配置目录）
+pub const ROW_GUARDRAILS_FILE_NAME: &str = "row_guardrails.json";
+
+/// 检查设置是否有效（百分比必须在 0 到 100 之间）
+pub fn validate_row_guardrails(guardrails: &RowGuardrails) -> Result<()> {
+    let percent = guardrails.max_removed_percent;
+    if !percent.is_finite() || !(0.0..=100.0).contains(&percent) {
+        return Err(DataAnalystError::InvalidOperation(
+            "删除行数占比阈值必须在 0 到 100 之间".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// 读取行数保护设置（文件不存在时返回内置默认值，缺少的字段使用内置默认值）
+pub fn load_row_guardrails(path: &Path) -> Result<RowGuardrails> {
+    if !path.exists() {
+        return Ok(RowGuardrails::default());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| DataAnalystError::InvalidDataFormat(format!("行数保护设置文件格式错误: {}", e)))
+}
+
+/// 保存行数保护设置（先写临时文件再替换）
+pub fn save_row_guardrails(path: &Path, guardrails: &RowGuardrails) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let content = serde_json::to_string_pretty(guardrails)?;
+    write_atomic(&path.to_string_lossy(), |file| {
+        file.write_all(content.as_bytes())?;
+        Ok(())
+    })
+}
+
+/// 判断操作是否需要确认
+///
+/// 返回 Some(预计影响) 表示删除的行数超过了阈值；未启用、删除行数过少或未超过阈值时返回 None
+pub fn exceeds_row_guardrails(guardrails: &RowGuardrails, rows_before: usize, rows_after: usize) -> Option<RowImpact> {
+    let removed_rows = rows_before.saturating_sub(rows_after);
+    if !guardrails.enabled || removed_rows == 0 || removed_rows < guardrails.min_removed_rows {
+        return None;
+    }
+
+    let removed_percent = removed_rows as f64 / rows_before as f64 * 100.0;
+    if removed_percent <= guardrails.max_removed_percent {
+        return None;
+    }
+
+    Some(RowImpact {
+        rows_before,
+        rows_after,
+        removed_rows,
+        removed_percent,
+        threshold_percent: guardrails.max_removed_percent,
+    })
+}
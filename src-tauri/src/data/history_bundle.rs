@@ -0,0 +1,96 @@
+// ============================================================================
+// data/history_bundle.rs - 历史归档目录
+// ============================================================================
+// 这个文件负责把完整的操作历史写入一个目录，用于归档和审计：
+// - entry_000.parquet, entry_001.parquet, ...: 每个历史条目操作后的数据
+// - entry_002_removed.parquet: 该操作删除的行（只有记录了被删除行的操作才有）
+// - manifest.json: 各条目的操作、时间、描述、行列数和显示设置（HistoryBundleManifest）
+//
+// 每个文件都先写临时文件再替换；manifest.json 最后写出，目录中有 manifest.json 说明归档完整
+
+use crate::data::export::write_atomic;
+use crate::error::{DataAnalystError, Result};
+use crate::models::{DisplaySettings, HistoryBundleEntry, HistoryBundleManifest, HistoryEntryInfo};
+use polars::prelude::*;
+use std::io::Write;
+use std::path::Path;
+
+/// 当前历史归档格式版本
+pub const HISTORY_BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// 归档清单文件名
+pub const HISTORY_MANIFEST_FILE: &str = "manifest.json";
+
+/// BundleEntry - 要写入归档的一个历史条目（从数据存储中复制出来，写出期间不持有锁）
+pub struct BundleEntry {
+    pub info: HistoryEntryInfo,
+    pub dataframe: DataFrame,
+    pub removed_rows: Option<DataFrame>,
+    pub display: DisplaySettings,
+}
+
+/// 将全部历史条目写入归档目录，返回写出的清单
+///
+/// 目录不存在时自动创建；目录已存在且不为空时返回错误（避免和旧归档的文件混在一起）
+pub fn write_history_bundle(
+    output_dir: &Path,
+    dataset_name: &str,
+    current_index: Option<usize>,
+    entries: Vec<BundleEntry>,
+) -> Result<HistoryBundleManifest> {
+    if output_dir.exists() && std::fs::read_dir(output_dir)?.next().is_some() {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "输出目录 {} 不为空，请选择一个空目录",
+            output_dir.display()
+        )));
+    }
+    std::fs::create_dir_all(output_dir)?;
+
+    let mut manifest_entries = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.into_iter().enumerate() {
+        let data_file = format!("entry_{:03}.parquet", index);
+        write_parquet_file(&output_dir.join(&data_file), &entry.dataframe)?;
+
+        let removed_rows_file = match &entry.removed_rows {
+            Some(removed) => {
+                let file_name = format!("entry_{:03}_removed.parquet", index);
+                write_parquet_file(&output_dir.join(&file_name), removed)?;
+                Some(file_name)
+            }
+            None => None,
+        };
+
+        manifest_entries.push(HistoryBundleEntry {
+            index,
+            data_file,
+            removed_rows_file,
+            rows: entry.dataframe.height(),
+            columns: entry.dataframe.width(),
+            entry: entry.info,
+            display: entry.display,
+        });
+    }
+
+    let manifest = HistoryBundleManifest {
+        format_version: HISTORY_BUNDLE_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        dataset_name: dataset_name.to_string(),
+        current_index,
+        entries: manifest_entries,
+    };
+    let content = serde_json::to_string_pretty(&manifest)?;
+    write_atomic(&output_dir.join(HISTORY_MANIFEST_FILE).to_string_lossy(), |file| {
+        file.write_all(content.as_bytes())?;
+        Ok(())
+    })?;
+
+    Ok(manifest)
+}
+
+fn write_parquet_file(path: &Path, df: &DataFrame) -> Result<()> {
+    write_atomic(&path.to_string_lossy(), |file| {
+        ParquetWriter::new(file).finish(&mut df.clone())?;
+        Ok(())
+    })
+}
@@ -0,0 +1,55 @@
+// ============================================================================
+// data/import_gate.rs - 导入互斥
+// ============================================================================
+// 导入会替换整个数据集和历史，同一时间只允许一个导入在进行：
+// - 导入开始时调用 ImportGate::begin 取得 ImportSession
+// - 另一个导入正在进行时返回 ImportInProgress 错误（包含正在导入的来源）
+// - ImportSession 被丢弃时（成功、失败或取消）自动释放
+//
+// 新数据在锁外完整构建好之后，再用 DataStore::replace_with_import 一次性替换，
+// 读取失败或被取消时当前数据集保持不变
+
+use crate::error::{DataAnalystError, Result};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// SharedImportGate - 在命令之间共享的导入互斥
+pub type SharedImportGate = Arc<ImportGate>;
+
+/// ImportGate - 记录正在进行的导入（None 表示空闲）
+#[derive(Default)]
+pub struct ImportGate {
+    active: Mutex<Option<String>>,
+}
+
+impl ImportGate {
+    /// 创建导入互斥（空闲）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 开始一次导入
+    ///
+    /// 参数：
+    /// - source: 导入来源（文件路径、URL 等，用于错误提示）
+    ///
+    /// 已有导入正在进行时返回 ImportInProgress 错误
+    pub fn begin(&self, source: &str) -> Result<ImportSession<'_>> {
+        let mut active = self.active.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(current) = active.as_ref() {
+            return Err(DataAnalystError::ImportInProgress(current.clone()));
+        }
+        *active = Some(source.to_string());
+        Ok(ImportSession { gate: self })
+    }
+}
+
+/// ImportSession - 一次正在进行的导入，被丢弃时释放导入互斥
+pub struct ImportSession<'a> {
+    gate: &'a ImportGate,
+}
+
+impl Drop for ImportSession<'_> {
+    fn drop(&mut self) {
+        *self.gate.active.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
@@ -0,0 +1,226 @@
+// ============================================================================
+// data/keys.rs - 匹配键规范化与去重
+// ============================================================================
+// 这个文件负责：
+// 1. 规范化字符串匹配键（忽略大小写 + Unicode NFKD 去重音），使 "José" 与 "jose" 匹配
+// 2. 按（可选规范化的）键删除重复行，输出中保留原始值
+// 3. 按（可选规范化的）键连接两个数据集，输出中保留当前数据集键列的原始值
+
+use crate::data::column_names::OutputColumns;
+use crate::data::text::map_string_column;
+use crate::error::{DataAnalystError, Result};
+use crate::models::history::{ColumnConflictPolicy, ColumnRename, DuplicateKeep, JoinHow};
+use polars::prelude::*;
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+/// 行号列的临时名称
+const ROW_INDEX_COLUMN: &str = "__row_index";
+
+/// 分组行数列的临时名称
+const GROUP_COUNT_COLUMN: &str = "__group_count";
+
+// ============================================================================
+// 键规范化
+// ============================================================================
+/// 规范化匹配键：NFKD 分解后去掉组合附加符号（重音），再转为小写
+///
+/// 例如："José" → "jose"，"ＡＢＣ"（全角）→ "abc"
+pub fn normalize_key(value: &str) -> String {
+    value
+        .nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// 为匹配准备键列
+///
+/// normalize 为 true 时，字符串键列会被复制为规范化后的临时列 "__key_{i}"，
+/// 原始列保持不变；非字符串列直接使用原列
+///
+/// 返回：(添加了临时键列的 DataFrame, 用于匹配的列名)
+pub fn prepare_key_columns(df: &DataFrame, keys: &[String], normalize: bool) -> Result<(DataFrame, Vec<String>)> {
+    let mut keyed = df.clone();
+    let mut key_names = Vec::with_capacity(keys.len());
+
+    for (idx, key) in keys.iter().enumerate() {
+        let column = df
+            .column(key)
+            .map_err(|_| DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", key)))?;
+
+        if normalize && column.dtype() == &DataType::String {
+            let name = format!("__key_{}", idx);
+            let series = map_string_column(df, key, &name, |value| Some(normalize_key(value)))?;
+            keyed.with_column(series)?;
+            key_names.push(name);
+        } else {
+            key_names.push(key.clone());
+        }
+    }
+
+    Ok((keyed, key_names))
+}
+
+// ============================================================================
+// 去重
+// ============================================================================
+/// 删除重复行（保持原有行顺序）
+///
+/// 参数：
+/// - df: 数据集
+/// - subset: 判断重复的列（空表示所有列）
+/// - keep: 保留策略
+/// - normalize_keys: 比较前是否规范化字符串键
+pub fn drop_duplicate_rows(
+    df: &DataFrame,
+    subset: &[String],
+    keep: DuplicateKeep,
+    normalize_keys: bool,
+) -> Result<DataFrame> {
+    let subset: Vec<String> = if subset.is_empty() {
+        df.get_column_names().iter().map(|s| s.to_string()).collect()
+    } else {
+        subset.to_vec()
+    };
+
+    let (keyed, key_names) = prepare_key_columns(df, &subset, normalize_keys)?;
+
+    let row = col(ROW_INDEX_COLUMN);
+    let selected = match keep {
+        DuplicateKeep::Last => row.last(),
+        _ => row.first(),
+    };
+
+    let mut grouped = keyed
+        .lazy()
+        .with_row_index(ROW_INDEX_COLUMN, None)
+        .group_by(key_names.iter().map(|name| col(name.as_str())).collect::<Vec<_>>())
+        .agg([selected.alias(ROW_INDEX_COLUMN), len().alias(GROUP_COUNT_COLUMN)]);
+
+    if keep == DuplicateKeep::None {
+        grouped = grouped.filter(col(GROUP_COUNT_COLUMN).eq(lit(1)));
+    }
+
+    let rows = grouped
+        .select([col(ROW_INDEX_COLUMN)])
+        .sort([ROW_INDEX_COLUMN], SortMultipleOptions::default())
+        .collect()?;
+
+    let indices = rows.column(ROW_INDEX_COLUMN)?.idx()?.clone();
+    Ok(df.take(&indices)?)
+}
+
+// ============================================================================
+// 连接
+// ============================================================================
+/// 按键连接两个数据集（保持左表的行顺序）
+///
+/// normalize_keys 为 true 时，两边的字符串键都规范化后再匹配（"José" 与 "jose" 匹配），
+/// 输出中保留左表键列的原始值，右表的键列不出现在输出中（与不规范化时一致）
+///
+/// 右表的其他列与左表的列同名时按冲突策略处理：suffix 时右表的列自动加数字后缀，
+/// overwrite 时右表的列替换左表的同名列（保持列的位置；不能覆盖左表的键列）
+///
+/// 参数：
+/// - left / right: 左表（当前数据集）和右表
+/// - left_on / right_on: 两边的键列（按位置对应，数量必须相同）
+/// - how: 连接方式
+/// - normalize_keys: 匹配前是否规范化字符串键
+/// - policy: 列名冲突策略
+///
+/// 返回：连接结果和被自动改名的右表列
+pub fn join_on_keys(
+    left: &DataFrame,
+    right: &DataFrame,
+    left_on: &[String],
+    right_on: &[String],
+    how: JoinHow,
+    normalize_keys: bool,
+    policy: ColumnConflictPolicy,
+) -> Result<(DataFrame, Vec<ColumnRename>)> {
+    if left_on.is_empty() {
+        return Err(DataAnalystError::InvalidOperation("请至少选择一个连接键".to_string()));
+    }
+    if left_on.len() != right_on.len() {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "两边的连接键数量不同（{} 个和 {} 个）",
+            left_on.len(),
+            right_on.len()
+        )));
+    }
+
+    let (left_keyed, left_keys) = prepare_key_columns(left, left_on, normalize_keys)?;
+    let (mut right_keyed, right_keys) = prepare_key_columns(right, right_on, normalize_keys)?;
+
+    // 规范化的键用临时列匹配，右表的原始键列与不规范化时一样不出现在输出中
+    let replaced: Vec<&str> = right_on
+        .iter()
+        .zip(&right_keys)
+        .filter(|(original, key)| original != key)
+        .map(|(original, _)| original.as_str())
+        .collect();
+    if !replaced.is_empty() {
+        right_keyed = right_keyed.drop_many(replaced);
+    }
+
+    // 右表的新列按冲突策略决定列名：加后缀的列在连接前改名，
+    // 覆盖的列先以临时列名连接，连接后替换左表的同名列
+    let mut outputs = OutputColumns::new(left, policy);
+    let mut overwritten: Vec<(String, String)> = Vec::new();
+    let value_columns: Vec<String> = right_keyed
+        .get_column_names()
+        .iter()
+        .map(|name| name.to_string())
+        .filter(|name| !right_keys.contains(name))
+        .collect();
+    for name in value_columns {
+        let actual = outputs.resolve(&name)?;
+        if actual != name {
+            right_keyed.rename(&name, actual.as_str().into())?;
+        } else if left.column(&name).is_ok() {
+            if left_on.contains(&name) {
+                return Err(DataAnalystError::InvalidOperation(format!(
+                    "右表的列 '{}' 与连接键同名，不能覆盖",
+                    name
+                )));
+            }
+            let temporary = format!("__overwrite_{}", overwritten.len());
+            right_keyed.rename(&name, temporary.as_str().into())?;
+            overwritten.push((temporary, name));
+        }
+    }
+
+    let join_type = match how {
+        JoinHow::Left => JoinType::Left,
+        JoinHow::Inner => JoinType::Inner,
+    };
+    let temporary: Vec<&str> = left_keys
+        .iter()
+        .zip(left_on)
+        .filter(|(key, original)| key != original)
+        .map(|(key, _)| key.as_str())
+        .collect();
+
+    let joined = left_keyed
+        .lazy()
+        .with_row_index(ROW_INDEX_COLUMN, None)
+        .join(
+            right_keyed.lazy(),
+            left_keys.iter().map(|name| col(name.as_str())).collect::<Vec<_>>(),
+            right_keys.iter().map(|name| col(name.as_str())).collect::<Vec<_>>(),
+            JoinArgs::new(join_type),
+        )
+        .sort(
+            [ROW_INDEX_COLUMN],
+            SortMultipleOptions::default().with_maintain_order(true),
+        )
+        .collect()?;
+
+    let mut joined = joined.drop_many(temporary.into_iter().chain([ROW_INDEX_COLUMN]));
+    for (temporary, name) in overwritten {
+        let column = joined.drop_in_place(&temporary)?.with_name(name.as_str().into());
+        joined.with_column(column)?;
+    }
+    Ok((joined, outputs.into_renamed()))
+}
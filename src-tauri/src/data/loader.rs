@@ -4,21 +4,32 @@
 // 这个文件负责：
 // 1. 读取 CSV 文件并转换为 Polars DataFrame
 // 2. 读取 Excel 文件并转换为 Polars DataFrame
+//    （以及 Parquet、Arrow IPC、JSON / NDJSON 文件）
 // 3. 从 DataFrame 提取元信息（DatasetInfo）
 // 4. 将 DataFrame 转换为 JSON 格式（用于传输给前端）
 
 // ============================================================================
 // 导入依赖库
 // ============================================================================
+use crate::data::decimal::{decimal_to_string, parse_dtype_name};
+use crate::data::duration::{format_duration, format_time};
+use crate::data::format::format_any_value;
+// 显示格式化
 use crate::error::{DataAnalystError, Result};
 // 自定义错误类型
-use crate::models::{ColumnInfo, DatasetInfo};
-use calamine::{DataType, Reader, Xlsx, open_workbook};
+use crate::models::{
+    ColumnFormat, ColumnInfo, CsvImportOptions, DatasetInfo, ExcelSheetInfo, FloatPrecision, FloatRounding,
+    NonFinitePolicy, SampleStrategy,
+};
+use calamine::{DataType, Reader, Sheet, SheetType, SheetVisible, Xlsx, open_workbook};
 // Calamine: Excel 解析库
 use chrono::{DateTime, Utc};
 // Chrono: 时间处理库
+use polars::io::avro::AvroReader;
 use polars::prelude::*;
 // Polars: 数据处理库
+use std::collections::HashMap;
+// HashMap: 列显示格式映射
 use std::path::Path;
 // Path: 文件路径处理 // 数据模型
 
@@ -35,77 +46,466 @@ use std::path::Path;
 /// - 分号 (;) - 欧洲常用格式
 /// - 竖线 (|) - 数据库导出常用
 ///
+/// 自动检测失败时，可以通过 options 覆盖分隔符、表头、引号、编码等选项
+/// 压缩文件（.csv.gz、.csv.zst、只包含一个文件的 .zip）会先自动解压
+///
 /// 参数：
 /// - file_path: CSV 文件的路径（字符串切片）
+/// - options: 导入选项（未指定的选项使用自动检测 / 默认值）
 ///
 /// 返回：
 /// - Result<DataFrame>: 成功返回 DataFrame，失败返回错误
 ///
 /// 示例：
 /// ```rust
-/// let df = load_csv("/path/to/data.csv")?;
+/// let df = load_csv("/path/to/data.csv", &CsvImportOptions::default())?;
 /// println!("行数: {}", df.height());
 /// println!("列数: {}", df.width());
+///
+/// // GBK 编码、分号分隔的导出文件
+/// let options = CsvImportOptions {
+///     separator: Some(';'),
+///     encoding: Some("gbk".to_string()),
+///     ..CsvImportOptions::default()
+/// };
+/// let df = load_csv("/path/to/export.csv", &options)?;
 /// ```
-pub fn load_csv(file_path: &str) -> Result<DataFrame> {
-    // 自动检测分隔符
-    let separator = detect_csv_separator(file_path)?;
+pub fn load_csv(file_path: &str, options: &CsvImportOptions) -> Result<DataFrame> {
+    load_csv_with_progress(file_path, options, &|_| Ok(()))
+}
+
+/// 超过这个大小（解压后）的 CSV 分块读取并报告进度（32 MB）
+///
+/// 取消导入只能在块之间生效，阈值较低时中等大小的文件也可以取消；
+/// 类型推断只看前 10000 行，分块读取（由第一块推断）与一次性读取得到的 schema 相同
+pub const STREAMING_CSV_THRESHOLD_BYTES: u64 = 32 * 1024 * 1024;
+
+/// 分块读取时每块的大致大小（16 MB，实际会延伸到下一条完整记录）
+const CSV_CHUNK_BYTES: u64 = 16 * 1024 * 1024;
+
+/// CsvProgress - CSV 读取进度
+#[derive(Debug, Clone, Copy)]
+pub struct CsvProgress {
+    /// 已读取的字节数
+    pub bytes_read: u64,
+
+    /// 总字节数
+    pub total_bytes: u64,
+
+    /// 已解析的行数
+    pub rows_parsed: usize,
+}
+
+/// 从 CSV 文件加载 DataFrame，并报告读取进度
+///
+/// 解压 / 转码完成、开始解析之前先报告一次进度（已读取 0 字节），之后：
+/// 小文件一次性读取，读取完成后报告一次进度；
+/// 超过 STREAMING_CSV_THRESHOLD_BYTES 的文件按记录边界切成约 16 MB 的块逐块解析，
+/// 每解析完一块报告一次进度（列类型由第一块推断，后续块沿用同一 schema）
+///
+/// 参数：
+/// - file_path: CSV 文件的路径
+/// - options: 导入选项
+/// - on_progress: 进度回调，返回错误时中止读取（用于取消导入）
+pub fn load_csv_with_progress(
+    file_path: &str,
+    options: &CsvImportOptions,
+    on_progress: &dyn Fn(CsvProgress) -> Result<()>,
+) -> Result<DataFrame> {
+    let PreparedCsv {
+        decoded,
+        read_options,
+        quote_char,
+        total_bytes,
+    } = prepare_csv(file_path, options)?;
+
+    // 解压和转码可能耗时较长，开始解析之前给回调一次中止的机会
+    on_progress(CsvProgress {
+        bytes_read: 0,
+        total_bytes,
+        rows_parsed: 0,
+    })?;
+
+    // 大文件：分块读取并报告进度
+    if total_bytes >= STREAMING_CSV_THRESHOLD_BYTES {
+        return match decoded {
+            Some(bytes) => read_csv_chunked(
+                std::io::Cursor::new(bytes),
+                total_bytes,
+                &read_options,
+                quote_char,
+                on_progress,
+            ),
+            None => read_csv_chunked(
+                std::fs::File::open(file_path)?,
+                total_bytes,
+                &read_options,
+                quote_char,
+                on_progress,
+            ),
+        };
+    }
+
+    let df = match decoded {
+        // 从内存中读取转换后的内容
+        Some(bytes) => read_options
+            .into_reader_with_file_handle(std::io::Cursor::new(bytes))
+            .finish()?,
+        // try_into_reader_with_file_path: 创建 CSV 读取器
+        // Some(file_path.into()): 将字符串转换为 PathBuf
+        None => read_options
+            .try_into_reader_with_file_path(Some(file_path.into()))?
+            .finish()?,
+    };
+
+    on_progress(CsvProgress {
+        bytes_read: total_bytes,
+        total_bytes,
+        rows_parsed: df.height(),
+    })?;
+
+    // 返回成功结果
+    Ok(df)
+}
+
+/// 解压 / 转码后的 CSV 内容和读取选项
+struct PreparedCsv {
+    /// 解压或转码后的内容（None 表示直接读取原文件）
+    decoded: Option<Vec<u8>>,
+
+    /// CSV 读取选项（分隔符、表头、类型覆盖等）
+    read_options: CsvReadOptions,
+
+    /// 引号字符（分块读取时用于判断记录边界）
+    quote_char: u8,
+
+    /// 内容总字节数（解压后）
+    total_bytes: u64,
+}
+
+/// 解压、转码 CSV 文件，并根据导入选项和自动检测结果生成读取选项
+fn prepare_csv(file_path: &str, options: &CsvImportOptions) -> Result<PreparedCsv> {
+    // 压缩文件（gzip / zstd / 单文件 zip）：先解压到内存
+    let decompressed = decompress_csv(file_path)?;
+
+    // 非 UTF-8 编码：先整体转换为 UTF-8，再从内存读取
+    let decoded = match options.encoding.as_deref() {
+        Some(label) => decode_to_utf8(file_path, decompressed, label)?,
+        None => decompressed,
+    };
+    let skip_rows = options.skip_rows.unwrap_or(0);
+
+    // 分隔符：优先使用指定的分隔符，否则自动检测（跳过表头之前的行）
+    let separator = match options.separator {
+        Some(c) => ascii_byte("分隔符", c)?,
+        None => {
+            let sample = match &decoded {
+                Some(bytes) => sample_csv_lines(std::io::Cursor::new(bytes), skip_rows),
+                None => sample_csv_lines(std::fs::File::open(file_path)?, skip_rows),
+            };
+            detect_csv_separator(&sample)
+        }
+    };
+
+    // 引号字符：默认双引号（分块读取时也用于判断记录边界）
+    let quote_char = match options.quote_char {
+        Some(c) => ascii_byte("引号字符", c)?,
+        None => b'"',
+    };
+
+    // CsvParseOptions: 设置分隔符、引号、空值文本和日期解析
+    let mut parse_options = CsvParseOptions::default()
+        .with_separator(separator) // 使用指定或检测到的分隔符
+        .with_quote_char(Some(quote_char))
+        .with_try_parse_dates(true); // 尝试解析日期时间
+    if let Some(values) = options.null_values.as_ref().filter(|values| !values.is_empty()) {
+        let values = values.iter().map(|v| v.as_str().into()).collect();
+        parse_options = parse_options.with_null_values(Some(NullValues::AllColumns(values)));
+    }
 
     // CsvReadOptions: CSV 读取选项配置
-    let df = CsvReadOptions::default()
+    let read_options = CsvReadOptions::default()
         // with_infer_schema_length: 设置类型推断的行数
         // Some(10000): 只读取前 10000 行来推断类型（平衡准确性和性能）
         // 对于大文件（25万行），这样可以大幅提升性能
         .with_infer_schema_length(Some(10000))
         // with_has_header: 指定是否有表头
-        .with_has_header(true)
-        // with_parse_options: 设置分隔符和日期解析
-        .with_parse_options(
-            CsvParseOptions::default()
-                .with_separator(separator) // 使用检测到的分隔符
-                .with_try_parse_dates(true), // 尝试解析日期时间
-        )
-        // try_into_reader_with_file_path: 创建 CSV 读取器
-        // Some(file_path.into()): 将字符串转换为 PathBuf
-        // ? 操作符: 如果失败，自动返回错误
-        .try_into_reader_with_file_path(Some(file_path.into()))?
-        // finish: 完成读取，返回 DataFrame
-        .finish()?;
+        .with_has_header(options.has_header.unwrap_or(true))
+        // with_skip_rows: 跳过表头之前的行
+        .with_skip_rows(skip_rows)
+        .with_parse_options(parse_options)
+        // with_schema_overwrite: 指定列的类型不再自动推断
+        .with_schema_overwrite(csv_schema_overrides(options)?);
+
+    let total_bytes = match &decoded {
+        Some(bytes) => bytes.len() as u64,
+        None => std::fs::metadata(file_path)?.len(),
+    };
 
-    // 返回成功结果
+    Ok(PreparedCsv {
+        decoded,
+        read_options,
+        quote_char,
+        total_bytes,
+    })
+}
+
+/// 将类型覆盖选项转换为 Schema（没有覆盖时为 None）
+fn csv_schema_overrides(options: &CsvImportOptions) -> Result<Option<SchemaRef>> {
+    let Some(overrides) = options.dtype_overrides.as_ref().filter(|o| !o.is_empty()) else {
+        return Ok(None);
+    };
+
+    let mut schema = Schema::with_capacity(overrides.len());
+    for (column, dtype_name) in overrides {
+        let dtype = parse_dtype_name(dtype_name)
+            .map_err(|e| DataAnalystError::InvalidOperation(format!("列 '{}' 的类型覆盖无效: {}", column, e)))?;
+        schema.with_column(column.as_str().into(), dtype);
+    }
+    Ok(Some(Arc::new(schema)))
+}
+
+/// 分块解析 CSV 并合并（第一块包含跳过的行和表头，后续块只有数据行）
+fn read_csv_chunked<R: std::io::Read>(
+    reader: R,
+    total_bytes: u64,
+    read_options: &CsvReadOptions,
+    quote_char: u8,
+    on_progress: &dyn Fn(CsvProgress) -> Result<()>,
+) -> Result<DataFrame> {
+    let mut result: Option<DataFrame> = None;
+
+    for_each_csv_chunk(reader, read_options, quote_char, &mut |df, bytes_read| {
+        let rows_parsed = match &mut result {
+            None => {
+                let rows = df.height();
+                result = Some(df);
+                rows
+            }
+            Some(acc) => {
+                acc.vstack_mut(&df)?;
+                acc.height()
+            }
+        };
+        on_progress(CsvProgress {
+            bytes_read,
+            total_bytes,
+            rows_parsed,
+        })
+    })?;
+
+    let mut df = result.unwrap_or_default();
+    df.rechunk_mut();
     Ok(df)
 }
 
+/// 逐块解析 CSV，每解析完一块调用一次 on_chunk（参数为该块的数据和累计读取的字节数）
+///
+/// 列类型由第一块推断，后续块沿用同一 schema
+fn for_each_csv_chunk<R: std::io::Read>(
+    reader: R,
+    read_options: &CsvReadOptions,
+    quote_char: u8,
+    on_chunk: &mut dyn FnMut(DataFrame, u64) -> Result<()>,
+) -> Result<()> {
+    let mut reader = std::io::BufReader::new(reader);
+    let mut bytes_read: u64 = 0;
+    let mut schema: Option<SchemaRef> = None;
+
+    loop {
+        let chunk = next_csv_chunk(&mut reader, quote_char, CSV_CHUNK_BYTES)?;
+        if chunk.is_empty() {
+            break;
+        }
+        bytes_read += chunk.len() as u64;
+
+        let chunk_options = match &schema {
+            None => read_options.clone(),
+            Some(schema) => read_options
+                .clone()
+                .with_has_header(false)
+                .with_skip_rows(0)
+                .with_schema(Some(schema.clone())),
+        };
+        let df = chunk_options
+            .into_reader_with_file_handle(std::io::Cursor::new(chunk))
+            .finish()?;
+
+        if schema.is_none() {
+            schema = Some(df.schema().clone());
+        }
+        on_chunk(df, bytes_read)?;
+    }
+
+    Ok(())
+}
+
+/// 读取下一块 CSV 内容（约 chunk_bytes 字节，延伸到下一条完整记录的结尾）
+///
+/// 引号内的换行不算记录结束：通过统计引号数量的奇偶判断是否在引号内
+/// （转义的引号 "" 成对出现，不影响奇偶）。返回空内容表示已读完
+fn next_csv_chunk<R: std::io::BufRead>(reader: &mut R, quote_char: u8, chunk_bytes: u64) -> Result<Vec<u8>> {
+    use std::io::Read;
+
+    let mut chunk = Vec::new();
+    reader.by_ref().take(chunk_bytes).read_to_end(&mut chunk)?;
+    let mut in_quotes = chunk.iter().filter(|&&b| b == quote_char).count() % 2 == 1;
+    if !in_quotes && chunk.last().is_none_or(|&b| b == b'\n') {
+        return Ok(chunk);
+    }
+
+    loop {
+        let start = chunk.len();
+        if reader.read_until(b'\n', &mut chunk)? == 0 {
+            break;
+        }
+        if chunk[start..].iter().filter(|&&b| b == quote_char).count() % 2 == 1 {
+            in_quotes = !in_quotes;
+        }
+        if !in_quotes {
+            break;
+        }
+    }
+    Ok(chunk)
+}
+
+/// 单个 ASCII 字符选项转换为字节
+fn ascii_byte(label: &str, c: char) -> Result<u8> {
+    if !c.is_ascii() || c == '\n' || c == '\r' {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "{}必须是单个 ASCII 字符（不能是换行），当前为 {:?}",
+            label, c
+        )));
+    }
+    Ok(c as u8)
+}
+
+/// 压缩文件的格式（按文件头的魔数识别，与扩展名无关）
+enum CsvCompression {
+    Gzip,
+    Zstd,
+    Zip,
+}
+
+impl CsvCompression {
+    fn detect(header: &[u8]) -> Option<Self> {
+        match header {
+            [0x1f, 0x8b, ..] => Some(CsvCompression::Gzip),
+            [0x28, 0xb5, 0x2f, 0xfd, ..] => Some(CsvCompression::Zstd),
+            [b'P', b'K', 0x03, 0x04, ..] => Some(CsvCompression::Zip),
+            _ => None,
+        }
+    }
+}
+
+/// 解压 .csv.gz、.csv.zst 和只包含一个文件的 .zip
+///
+/// 返回 None 表示文件没有压缩，可以直接按路径读取
+/// zip 中的目录和 macOS 生成的 __MACOSX/ 元数据会被忽略，剩余文件多于一个时报错
+fn decompress_csv(file_path: &str) -> Result<Option<Vec<u8>>> {
+    use std::io::Read;
+
+    let mut header = [0u8; 4];
+    let read = std::fs::File::open(file_path)?.read(&mut header)?;
+    let Some(compression) = CsvCompression::detect(&header[..read]) else {
+        return Ok(None);
+    };
+
+    let file = std::fs::File::open(file_path)?;
+    let mut bytes = Vec::new();
+    match compression {
+        // MultiGzDecoder: 支持多个 gzip 成员拼接的文件（例如按天追加的日志）
+        CsvCompression::Gzip => {
+            flate2::read::MultiGzDecoder::new(file).read_to_end(&mut bytes)?;
+        }
+        CsvCompression::Zstd => {
+            zstd::stream::read::Decoder::new(file)?.read_to_end(&mut bytes)?;
+        }
+        CsvCompression::Zip => {
+            let mut archive = ::zip::ZipArchive::new(file)
+                .map_err(|e| DataAnalystError::InvalidOperation(format!("无法读取 zip 文件: {}", e)))?;
+            let entries: Vec<String> = archive
+                .file_names()
+                .filter(|name| !name.ends_with('/') && !name.starts_with("__MACOSX/"))
+                .map(|name| name.to_string())
+                .collect();
+            let entry = match entries.as_slice() {
+                [entry] => entry,
+                [] => return Err(DataAnalystError::InvalidOperation("zip 文件中没有数据文件".to_string())),
+                _ => {
+                    return Err(DataAnalystError::InvalidOperation(format!(
+                        "zip 文件中包含 {} 个文件，只支持单个文件的压缩包: {}",
+                        entries.len(),
+                        entries.join(", ")
+                    )));
+                }
+            };
+            archive
+                .by_name(entry)
+                .map_err(|e| DataAnalystError::InvalidOperation(format!("无法解压 '{}': {}", entry, e)))?
+                .read_to_end(&mut bytes)?;
+        }
+    }
+
+    Ok(Some(bytes))
+}
+
+/// 将指定编码的文件内容转换为 UTF-8
+///
+/// content 为已解压的内容（None 表示从文件读取）
+/// 返回 None 表示文件本身就是 UTF-8，可以直接按路径读取
+/// 无法转换的字节会被替换为 U+FFFD
+fn decode_to_utf8(file_path: &str, content: Option<Vec<u8>>, label: &str) -> Result<Option<Vec<u8>>> {
+    let encoding = encoding_rs::Encoding::for_label(label.trim().as_bytes()).ok_or_else(|| {
+        DataAnalystError::InvalidOperation(format!(
+            "不支持的编码: {}（示例: \"utf-8\"、\"gbk\"、\"latin1\"、\"shift_jis\"）",
+            label
+        ))
+    })?;
+    if encoding == encoding_rs::UTF_8 {
+        return Ok(content);
+    }
+
+    let bytes = match content {
+        Some(bytes) => bytes,
+        None => std::fs::read(file_path)?,
+    };
+    let (text, _, _) = encoding.decode(&bytes);
+    Ok(Some(text.into_owned().into_bytes()))
+}
+
+/// 读取用于检测分隔符的样本行（跳过前 skip_rows 行，最多 10 行）
+fn sample_csv_lines<R: std::io::Read>(reader: R, skip_rows: usize) -> Vec<String> {
+    use std::io::{BufRead, BufReader};
+
+    BufReader::new(reader)
+        .lines()
+        .skip(skip_rows)
+        .take(10)
+        .filter_map(|line| line.ok())
+        .collect()
+}
+
 // ============================================================================
 // CSV 分隔符自动检测
 // ============================================================================
 /// 自动检测 CSV 文件的分隔符
 ///
 /// 算法：
-/// 1. 读取文件前 10 行作为样本
+/// 1. 使用文件前 10 行作为样本（由 sample_csv_lines 读取）
 /// 2. 尝试常见的分隔符（逗号、制表符、分号、竖线）
 /// 3. 计算每个分隔符产生的列数方差
 /// 4. 选择方差最小的分隔符（列数最一致）
 ///
 /// 参数：
-/// - file_path: CSV 文件路径
+/// - sample_lines: 样本行
 ///
 /// 返回：
-/// - Result<u8>: 检测到的分隔符（ASCII 字符）
-fn detect_csv_separator(file_path: &str) -> Result<u8> {
-    use std::fs::File;
-    use std::io::{BufRead, BufReader};
-
-    // 打开文件
-    let file = File::open(file_path)?;
-    let reader = BufReader::new(file);
-
-    // 读取前 10 行作为样本
-    let sample_lines: Vec<String> = reader.lines().take(10).filter_map(|line| line.ok()).collect();
-
+/// - u8: 检测到的分隔符（ASCII 字符）
+fn detect_csv_separator(sample_lines: &[String]) -> u8 {
     // 如果文件为空或只有一行，默认使用逗号
     if sample_lines.len() < 2 {
-        return Ok(b',');
+        return b',';
     }
 
     // 候选分隔符：逗号、制表符、分号、竖线
@@ -142,7 +542,187 @@ fn detect_csv_separator(file_path: &str) -> Result<u8> {
         }
     }
 
-    Ok(best_separator)
+    best_separator
+}
+
+// ============================================================================
+// 抽样加载
+// ============================================================================
+/// 随机抽样时 Parquet 每次读取的行数
+const PARQUET_SAMPLE_BATCH_ROWS: usize = 1_000_000;
+
+/// 随机抽样使用的临时列（随机键、原始行号）
+const SAMPLE_KEY_COLUMN: &str = "__sample_key";
+const SAMPLE_ROW_COLUMN: &str = "__sample_row";
+
+/// 只加载文件的一部分行，用于在超大文件上先探索结构、搭建处理流程
+///
+/// - Head: 只读取前 sample_rows 行（CSV、Parquet、Arrow IPC 读到足够的行就停止）
+/// - Random: 均匀随机抽取 sample_rows 行，保持文件中的行顺序。
+///   CSV 和 Parquet 逐块读取，内存中只保留抽样结果和当前块；其他格式先完整读取再抽样
+///
+/// 按扩展名选择读取方式，无法识别的扩展名按 CSV 读取（csv_options 只用于 CSV）
+///
+/// 示例：
+/// ```rust
+/// let df = load_sample("/path/to/huge.csv", 10_000, SampleStrategy::Random, &CsvImportOptions::default())?;
+/// ```
+pub fn load_sample(
+    file_path: &str,
+    sample_rows: usize,
+    strategy: SampleStrategy,
+    csv_options: &CsvImportOptions,
+) -> Result<DataFrame> {
+    if sample_rows == 0 {
+        return Err(DataAnalystError::InvalidOperation("抽样行数必须大于 0".to_string()));
+    }
+
+    let extension = Path::new(file_path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    match (extension.as_str(), strategy) {
+        ("parquet", SampleStrategy::Head) => load_parquet(file_path, None, Some(sample_rows)),
+        ("parquet", SampleStrategy::Random) => {
+            let mut sampler = RandomSampler::new(sample_rows);
+            let mut offset = 0;
+            loop {
+                let file = std::fs::File::open(file_path)?;
+                let batch = ParquetReader::new(file)
+                    .with_slice(Some((offset, PARQUET_SAMPLE_BATCH_ROWS)))
+                    .finish()?;
+                if batch.height() == 0 {
+                    break;
+                }
+                offset += batch.height();
+                sampler.add(batch)?;
+            }
+            sampler.finish()
+        }
+        ("arrow" | "feather" | "ipc", SampleStrategy::Head) => {
+            let file = std::fs::File::open(file_path)?;
+            Ok(IpcReader::new(file).with_n_rows(Some(sample_rows)).finish()?)
+        }
+        ("xlsx" | "xlsm" | "arrow" | "feather" | "ipc" | "json" | "jsonl" | "ndjson" | "avro" | "orc", _) => {
+            let df = match extension.as_str() {
+                "xlsx" | "xlsm" => load_excel(file_path, None, None, None, None)?,
+                "arrow" | "feather" | "ipc" => load_arrow(file_path)?,
+                "avro" => load_avro(file_path)?,
+                "orc" => load_orc(file_path)?,
+                _ => load_json(file_path)?,
+            };
+            match strategy {
+                SampleStrategy::Head => Ok(df.head(Some(sample_rows))),
+                SampleStrategy::Random => {
+                    let mut sampler = RandomSampler::new(sample_rows);
+                    sampler.add(df)?;
+                    sampler.finish()
+                }
+            }
+        }
+        _ => sample_csv(file_path, sample_rows, strategy, csv_options),
+    }
+}
+
+/// CSV 抽样：Head 读到足够的行就停止；Random 逐块读取整个文件
+fn sample_csv(
+    file_path: &str,
+    sample_rows: usize,
+    strategy: SampleStrategy,
+    options: &CsvImportOptions,
+) -> Result<DataFrame> {
+    let PreparedCsv {
+        decoded,
+        read_options,
+        quote_char,
+        ..
+    } = prepare_csv(file_path, options)?;
+
+    match strategy {
+        SampleStrategy::Head => {
+            let read_options = read_options.with_n_rows(Some(sample_rows));
+            let df = match decoded {
+                Some(bytes) => read_options
+                    .into_reader_with_file_handle(std::io::Cursor::new(bytes))
+                    .finish()?,
+                None => read_options
+                    .try_into_reader_with_file_path(Some(file_path.into()))?
+                    .finish()?,
+            };
+            Ok(df)
+        }
+        SampleStrategy::Random => {
+            let mut sampler = RandomSampler::new(sample_rows);
+            let mut add = |df: DataFrame, _: u64| sampler.add(df);
+            match decoded {
+                Some(bytes) => for_each_csv_chunk(std::io::Cursor::new(bytes), &read_options, quote_char, &mut add)?,
+                None => for_each_csv_chunk(std::fs::File::open(file_path)?, &read_options, quote_char, &mut add)?,
+            }
+            sampler.finish()
+        }
+    }
+}
+
+/// 逐块均匀随机抽样（bottom-k）
+///
+/// 每行分配一个 [0, 1) 的随机键，始终只保留键最小的 rows 行：
+/// 所有行的键独立同分布，因此结果是整个文件的均匀随机抽样，与分块方式无关
+struct RandomSampler {
+    /// 抽样行数
+    rows: usize,
+
+    /// 已读取的行数（用作下一块的起始行号）
+    seen: usize,
+
+    /// 当前的抽样结果（包含随机键和原始行号两个临时列）
+    sample: Option<DataFrame>,
+}
+
+impl RandomSampler {
+    fn new(rows: usize) -> Self {
+        Self {
+            rows,
+            seen: 0,
+            sample: None,
+        }
+    }
+
+    fn add(&mut self, df: DataFrame) -> Result<()> {
+        let height = df.height();
+        let mut df = df.with_row_index(SAMPLE_ROW_COLUMN.into(), Some(self.seen as IdxSize))?;
+        df.with_column(Float64Chunked::rand_uniform(SAMPLE_KEY_COLUMN.into(), height, 0.0, 1.0).into_series())?;
+        self.seen += height;
+
+        let combined = match self.sample.take() {
+            Some(mut sample) => {
+                sample.vstack_mut(&df)?;
+                sample
+            }
+            None => df,
+        };
+        let sample = if combined.height() > self.rows {
+            combined
+                .sort([SAMPLE_KEY_COLUMN], SortMultipleOptions::default())?
+                .head(Some(self.rows))
+        } else {
+            combined
+        };
+        self.sample = Some(sample);
+        Ok(())
+    }
+
+    /// 按原始行号排序并去掉临时列
+    fn finish(self) -> Result<DataFrame> {
+        let Some(sample) = self.sample else {
+            return Ok(DataFrame::default());
+        };
+        let mut df = sample
+            .sort([SAMPLE_ROW_COLUMN], SortMultipleOptions::default())?
+            .drop_many([SAMPLE_ROW_COLUMN, SAMPLE_KEY_COLUMN]);
+        df.rechunk_mut();
+        Ok(df)
+    }
 }
 
 // ============================================================================
@@ -154,19 +734,321 @@ fn detect_csv_separator(file_path: &str) -> Result<u8> {
 ///
 /// 参数：
 /// - file_path: Parquet 文件的路径
+/// - columns: 只读取这些列（None 表示所有列），其他列的数据不会被解码
+/// - n_rows: 最多读取的行数（None 表示全部），只解码需要的行组
 ///
 /// 返回：
 /// - Result<DataFrame>: 成功返回 DataFrame，失败返回错误
 ///
 /// 示例：
 /// ```rust
-/// let df = load_parquet("/path/to/data.parquet")?;
+/// let df = load_parquet("/path/to/data.parquet", None, None)?;
 /// println!("行数: {}", df.height());
+///
+/// // 大文件只读取两列的前 10 万行
+/// let columns = vec!["date".to_string(), "amount".to_string()];
+/// let df = load_parquet("/path/to/huge.parquet", Some(&columns), Some(100_000))?;
+/// ```
+pub fn load_parquet(file_path: &str, columns: Option<&[String]>, n_rows: Option<usize>) -> Result<DataFrame> {
+    if columns.is_some_and(|columns| columns.is_empty()) {
+        return Err(DataAnalystError::InvalidOperation("至少需要选择一列".to_string()));
+    }
+
+    // 使用 Polars 的 ParquetReader 读取 Parquet 文件（列投影和行数限制在解码前生效）
+    let file = std::fs::File::open(file_path)?;
+    let df = ParquetReader::new(file)
+        .with_columns(columns.map(|columns| columns.to_vec()))
+        .with_slice(n_rows.map(|n_rows| (0, n_rows)))
+        .finish()?;
+    Ok(df)
+}
+
+// ============================================================================
+// 分区 Parquet 目录加载
+// ============================================================================
+/// Hive 表示空分区值的目录名
+const HIVE_NULL_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// 从 Hive 分区的 Parquet 目录加载 DataFrame
+///
+/// 目录结构示例：`data/year=2024/month=01/part-0.parquet`，
+/// 读取目录下（含子目录）所有 .parquet 文件并纵向合并，路径中的 `键=值` 作为列追加在末尾
+///
+/// - 分区值全部是整数时为整数列，全部是 YYYY-MM-DD 日期时为日期列，否则为文本列
+/// - `__HIVE_DEFAULT_PARTITION__` 表示空值，值中的 %XX 转义会被还原
+/// - 以 `.` 或 `_` 开头的文件和目录（例如 `_SUCCESS`、`.crc`）会被忽略
+/// - 各文件的列按列名对齐，缺少的列填空值
+///
+/// 示例：
+/// ```rust
+/// let df = load_parquet_dir("/path/to/data")?;
+/// ```
+pub fn load_parquet_dir(dir_path: &str) -> Result<DataFrame> {
+    let root = Path::new(dir_path);
+    if !root.is_dir() {
+        return Err(DataAnalystError::FileReadError(format!("'{}' 不是目录", dir_path)));
+    }
+
+    let mut files = Vec::new();
+    collect_parquet_files(root, &mut files)?;
+    files.sort();
+    if files.is_empty() {
+        return Err(DataAnalystError::FileReadError(format!(
+            "目录 '{}' 中没有 Parquet 文件",
+            dir_path
+        )));
+    }
+
+    // 分区键按第一次出现的顺序排列
+    let mut keys: Vec<String> = Vec::new();
+    let mut frames = Vec::with_capacity(files.len());
+    for file in &files {
+        let partitions = hive_partitions(file.strip_prefix(root).unwrap_or(file));
+        let mut df = ParquetReader::new(std::fs::File::open(file)?).finish()?;
+        for (key, value) in partitions {
+            if df.get_column_names().iter().any(|name| name.as_str() == key) {
+                return Err(DataAnalystError::InvalidDataFormat(format!(
+                    "分区键 '{}' 与文件 '{}' 中的列重名",
+                    key,
+                    file.display()
+                )));
+            }
+            let values = vec![value; df.height()];
+            df.with_column(Series::new(key.as_str().into(), values))?;
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        frames.push(df.lazy());
+    }
+
+    let mut df = concat_lf_diagonal(
+        frames,
+        UnionArgs {
+            to_supertypes: true,
+            ..Default::default()
+        },
+    )?
+    .collect()?;
+
+    // 分区列推断类型
+    for key in &keys {
+        let column = df.column(key)?.as_materialized_series().clone();
+        let values = column.str()?;
+        let all_match = |parse: &dyn Fn(&str) -> bool| values.into_iter().flatten().all(parse);
+        let dtype = if all_match(&|v| v.parse::<i64>().is_ok()) {
+            datatypes::DataType::Int64
+        } else if all_match(&|v| chrono::NaiveDate::parse_from_str(v, "%Y-%m-%d").is_ok()) {
+            datatypes::DataType::Date
+        } else {
+            continue;
+        };
+        df.with_column(column.cast(&dtype)?)?;
+    }
+
+    Ok(df)
+}
+
+/// 递归收集目录下的 .parquet 文件（忽略以 . 或 _ 开头的文件和目录）
+fn collect_parquet_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if name.starts_with('.') || name.starts_with('_') {
+            continue;
+        }
+        if path.is_dir() {
+            collect_parquet_files(&path, files)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.to_string_lossy().eq_ignore_ascii_case("parquet"))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// 从相对路径的目录部分解析 `键=值` 分区（空分区值为 None）
+fn hive_partitions(relative: &Path) -> Vec<(String, Option<String>)> {
+    relative
+        .parent()
+        .into_iter()
+        .flat_map(|parent| parent.components())
+        .filter_map(|component| {
+            let segment = component.as_os_str().to_string_lossy();
+            let (key, value) = segment.split_once('=')?;
+            if key.is_empty() {
+                return None;
+            }
+            let value = match value {
+                "" | HIVE_NULL_PARTITION => None,
+                _ => Some(percent_decode(value)),
+            };
+            Some((percent_decode(key), value))
+        })
+        .collect()
+}
+
+/// 还原路径中的 %XX 转义（无效的转义保持原样）
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+// ============================================================================
+// Arrow IPC 文件加载
+// ============================================================================
+/// 从 Arrow IPC 文件（.arrow / .feather）加载 DataFrame
+///
+/// Arrow IPC（即 Feather v2）是 pandas / pyarrow / R arrow 通用的列式格式，
+/// 数据类型完整保留，读取几乎不需要解析
+///
+/// 注意：不支持旧的 Feather v1 格式（pyarrow 默认写出的已经是 v2）
+///
+/// 示例：
+/// ```rust
+/// let df = load_arrow("/path/to/data.feather")?;
 /// ```
-pub fn load_parquet(file_path: &str) -> Result<DataFrame> {
-    // 使用 Polars 的 ParquetReader 读取 Parquet 文件
+pub fn load_arrow(file_path: &str) -> Result<DataFrame> {
     let file = std::fs::File::open(file_path)?;
-    let df = ParquetReader::new(file).finish()?;
+    let df = IpcReader::new(file).finish()?;
+    Ok(df)
+}
+
+// ============================================================================
+// Avro 文件加载
+// ============================================================================
+/// 从 Avro 容器文件（.avro）加载 DataFrame
+///
+/// Avro 是 Kafka / Hadoop 生态常用的行式格式，文件自带 schema，
+/// 嵌套的 record 读取为结构体列，array 读取为列表列
+///
+/// 示例：
+/// ```rust
+/// let df = load_avro("/path/to/events.avro")?;
+/// ```
+pub fn load_avro(file_path: &str) -> Result<DataFrame> {
+    let file = std::fs::File::open(file_path)?;
+    let df = AvroReader::new(file).finish()?;
+    Ok(df)
+}
+
+// ============================================================================
+// ORC 文件加载
+// ============================================================================
+/// 从 ORC 文件（.orc）加载 DataFrame
+///
+/// ORC 是 Hive / Spark 数据湖常用的列式格式，Polars 没有原生的 ORC 读取器：
+/// 先用 orc-rust 读取为 Arrow 记录批次，再通过内存中的 Arrow IPC 流转换为 DataFrame
+///
+/// 示例：
+/// ```rust
+/// let df = load_orc("/path/to/part-00000.orc")?;
+/// ```
+pub fn load_orc(file_path: &str) -> Result<DataFrame> {
+    use arrow::ipc::writer::StreamWriter;
+    use arrow::record_batch::RecordBatchReader;
+
+    let orc_error = |e: &dyn std::fmt::Display| DataAnalystError::FileReadError(format!("ORC 文件读取失败: {}", e));
+
+    let file = std::fs::File::open(file_path)?;
+    let reader = orc_rust::ArrowReaderBuilder::try_new(file)
+        .map_err(|e| orc_error(&e))?
+        .build();
+    let schema = reader.schema();
+
+    // 把所有批次写入内存中的 Arrow IPC 流
+    let mut buffer = Vec::new();
+    {
+        let mut writer = StreamWriter::try_new(&mut buffer, &schema).map_err(|e| orc_error(&e))?;
+        for batch in reader {
+            let batch = batch.map_err(|e| orc_error(&e))?;
+            writer.write(&batch).map_err(|e| orc_error(&e))?;
+        }
+        writer.finish().map_err(|e| orc_error(&e))?;
+    }
+
+    let df = IpcStreamReader::new(std::io::Cursor::new(buffer)).finish()?;
+    Ok(df)
+}
+
+// ============================================================================
+// JSON / NDJSON 文件加载
+// ============================================================================
+/// 推断 JSON 类型时读取的最大记录数（API 导出的记录字段经常不一致）
+const JSON_INFER_SCHEMA_RECORDS: usize = 10_000;
+
+/// 从 JSON 文件加载 DataFrame
+///
+/// 支持两种格式：
+/// - 标准 JSON：对象数组，例如 `[{"a": 1}, {"a": 2}]`
+/// - NDJSON / JSONL：每行一个对象，例如 `{"a": 1}\n{"a": 2}`
+///
+/// 扩展名为 .jsonl / .ndjson 时按 NDJSON 读取；否则根据第一个非空白字符判断
+/// （'[' 为标准 JSON，'{' 为 NDJSON）。嵌套对象读取为结构体列，数组读取为列表列
+///
+/// 示例：
+/// ```rust
+/// let df = load_json("/path/to/events.jsonl")?;
+/// ```
+pub fn load_json(file_path: &str) -> Result<DataFrame> {
+    use std::io::Read;
+
+    // 读取文件开头，判断格式
+    let mut head = Vec::with_capacity(4096);
+    std::fs::File::open(file_path)?.take(4096).read_to_end(&mut head)?;
+    let first = head
+        .strip_prefix(b"\xEF\xBB\xBF".as_slice())
+        .unwrap_or(&head)
+        .iter()
+        .find(|b| !b.is_ascii_whitespace())
+        .copied();
+
+    let extension = Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase());
+
+    let format = match (extension.as_deref(), first) {
+        (_, None) => {
+            return Err(DataAnalystError::InvalidDataFormat("JSON 文件为空".to_string()));
+        }
+        (Some("jsonl") | Some("ndjson"), _) | (_, Some(b'{')) => JsonFormat::JsonLines,
+        (_, Some(b'[')) => JsonFormat::Json,
+        (_, Some(_)) => {
+            return Err(DataAnalystError::InvalidDataFormat(
+                "JSON 文件必须是对象数组（以 '[' 开头）或每行一个对象的 NDJSON（以 '{' 开头）".to_string(),
+            ));
+        }
+    };
+
+    let file = std::fs::File::open(file_path)?;
+    let df = JsonReader::new(file)
+        .with_json_format(format)
+        .infer_schema_len(std::num::NonZeroUsize::new(JSON_INFER_SCHEMA_RECORDS))
+        .finish()?;
     Ok(df)
 }
 
@@ -183,6 +1065,11 @@ pub fn load_parquet(file_path: &str) -> Result<DataFrame> {
 /// - sheet_name: 可选的工作表名称
 ///   - Some("Sheet1"): 读取指定的工作表
 ///   - None: 读取第一个工作表
+/// - header_row: 表头所在行（从 0 开始，相对于读取范围的第一行），默认 0
+///   - 表头之前的行（例如报表标题）会被忽略
+/// - skip_rows: 表头之后、数据之前要跳过的行数（例如单位说明行），默认 0
+/// - cell_range: A1 格式的读取范围（例如 "B3:H200"），用于去掉报表的标题和页脚
+///   - None: 读取工作表中所有有内容的单元格
 ///
 /// 返回：
 /// - Result<DataFrame>: 成功返回 DataFrame，失败返回错误
@@ -190,12 +1077,21 @@ pub fn load_parquet(file_path: &str) -> Result<DataFrame> {
 /// 示例：
 /// ```rust
 /// // 读取第一个工作表
-/// let df = load_excel("/path/to/data.xlsx", None)?;
+/// let df = load_excel("/path/to/data.xlsx", None, None, None, None)?;
 ///
 /// // 读取指定工作表
-/// let df = load_excel("/path/to/data.xlsx", Some("销售数据".to_string()))?;
+/// let df = load_excel("/path/to/data.xlsx", Some("销售数据".to_string()), None, None, None)?;
+///
+/// // 报表：前两行是标题，表头在第 3 行，只读取 B3:H200
+/// let df = load_excel("/path/to/report.xlsx", None, None, None, Some("B3:H200"))?;
 /// ```
-pub fn load_excel(file_path: &str, sheet_name: Option<String>) -> Result<DataFrame> {
+pub fn load_excel(
+    file_path: &str,
+    sheet_name: Option<String>,
+    header_row: Option<usize>,
+    skip_rows: Option<usize>,
+    cell_range: Option<&str>,
+) -> Result<DataFrame> {
     // open_workbook: 打开 Excel 文件
     // Xlsx<_>: 类型注解，表示这是一个 .xlsx 文件
     // ? 操作符: 如果打开失败，返回错误
@@ -216,10 +1112,143 @@ pub fn load_excel(file_path: &str, sheet_name: Option<String>) -> Result<DataFra
 
     // worksheet_range: 读取指定工作表的数据范围
     // 返回一个 Range 对象，包含所有单元格的数据
-    let range = workbook.worksheet_range(&sheet)?;
+    let mut range = workbook.worksheet_range(&sheet)?;
+
+    // 只保留指定的单元格范围（范围超出有内容的区域时，多出的单元格为空）
+    if let Some(text) = cell_range {
+        let (start, end) = parse_a1_range(text)?;
+        range = range.range(start, end);
+    }
 
     // 将 Excel Range 转换为 Polars DataFrame
-    excel_range_to_dataframe(range)
+    excel_range_to_dataframe(range, header_row.unwrap_or(0), skip_rows.unwrap_or(0))
+}
+
+/// 读取工作簿中的所有工作表并纵向合并为一个 DataFrame
+///
+/// 第一列 sheet_column 记录每行来自哪个工作表；各工作表的列按列名对齐，
+/// 某个工作表没有的列填空值，同名列类型不同时转换为公共类型
+///
+/// 只读取普通工作表，空工作表会被跳过；include_hidden 为 false 时跳过隐藏的工作表
+///
+/// 示例：
+/// ```rust
+/// // 每月一个工作表的报表合并为一张表
+/// let df = load_excel_all_sheets("/path/to/monthly.xlsx", "sheet", false)?;
+/// ```
+pub fn load_excel_all_sheets(file_path: &str, sheet_column: &str, include_hidden: bool) -> Result<DataFrame> {
+    let mut workbook: Xlsx<_> = open_workbook(file_path)?;
+
+    let sheets: Vec<Sheet> = workbook
+        .sheets_metadata()
+        .iter()
+        .filter(|sheet| sheet.typ == SheetType::WorkSheet)
+        .filter(|sheet| include_hidden || sheet.visible == SheetVisible::Visible)
+        .cloned()
+        .collect();
+
+    let mut frames = Vec::with_capacity(sheets.len());
+    for sheet in sheets {
+        let range = workbook.worksheet_range(&sheet.name)?;
+        if range.is_empty() {
+            continue;
+        }
+
+        let mut df = excel_range_to_dataframe(range, 0, 0)?;
+        if df.get_column_names().iter().any(|name| name.as_str() == sheet_column) {
+            return Err(DataAnalystError::InvalidOperation(format!(
+                "工作表 '{}' 已有列 '{}'，请指定其他的工作表列名",
+                sheet.name, sheet_column
+            )));
+        }
+        let names = Series::new(sheet_column.into(), vec![sheet.name.as_str(); df.height()]);
+        df.insert_column(0, names)?;
+        frames.push(df.lazy());
+    }
+
+    if frames.is_empty() {
+        return Err(DataAnalystError::ExcelParseError(
+            "工作簿中没有可导入的工作表".to_string(),
+        ));
+    }
+
+    let df = concat_lf_diagonal(
+        frames,
+        UnionArgs {
+            to_supertypes: true,
+            ..Default::default()
+        },
+    )?
+    .collect()?;
+    Ok(df)
+}
+
+/// 解析 A1 格式的单元格范围，返回从 0 开始的（起始单元格, 结束单元格），格式为 (行, 列)
+///
+/// 例如 "B3:H200" → ((2, 1), (199, 7))
+fn parse_a1_range(text: &str) -> Result<((u32, u32), (u32, u32))> {
+    let invalid = || DataAnalystError::InvalidOperation(format!("无效的单元格范围: {}（示例: \"B3:H200\"）", text));
+
+    let parse_cell = |cell: &str| -> Option<(u32, u32)> {
+        let cell = cell.trim().to_ascii_uppercase();
+        let split = cell.find(|c: char| !c.is_ascii_alphabetic())?;
+        let (letters, digits) = cell.split_at(split);
+        if letters.is_empty() || letters.len() > 3 {
+            return None;
+        }
+
+        let column = letters.bytes().fold(0u32, |acc, b| acc * 26 + (b - b'A' + 1) as u32);
+        let row: u32 = digits.parse().ok()?;
+        if row == 0 {
+            return None;
+        }
+        Some((row - 1, column - 1))
+    };
+
+    let (start, end) = text.split_once(':').ok_or_else(invalid)?;
+    let start = parse_cell(start).ok_or_else(invalid)?;
+    let end = parse_cell(end).ok_or_else(invalid)?;
+    if start.0 > end.0 || start.1 > end.1 {
+        return Err(invalid());
+    }
+
+    Ok((start, end))
+}
+
+// ============================================================================
+// Excel 工作表列表
+// ============================================================================
+/// 列出 Excel 工作簿中的工作表（名称、行数、列数、是否隐藏）
+///
+/// 只包含普通工作表（图表工作表等没有单元格数据，无法导入）
+/// 行数不含表头，与 load_excel 读取后的行数一致
+///
+/// 示例：
+/// ```rust
+/// let sheets = list_excel_sheets("/path/to/data.xlsx")?;
+/// ```
+pub fn list_excel_sheets(file_path: &str) -> Result<Vec<ExcelSheetInfo>> {
+    let mut workbook: Xlsx<_> = open_workbook(file_path)?;
+
+    let sheets: Vec<Sheet> = workbook
+        .sheets_metadata()
+        .iter()
+        .filter(|sheet| sheet.typ == SheetType::WorkSheet)
+        .cloned()
+        .collect();
+
+    let mut result = Vec::with_capacity(sheets.len());
+    for sheet in sheets {
+        let (height, width) = workbook.worksheet_range(&sheet.name)?.get_size();
+        result.push(ExcelSheetInfo {
+            name: sheet.name,
+            rows: height.saturating_sub(1),
+            columns: width,
+            hidden: sheet.visible != SheetVisible::Visible,
+        });
+    }
+
+    Ok(result)
 }
 
 // ============================================================================
@@ -230,16 +1259,23 @@ pub fn load_excel(file_path: &str, sheet_name: Option<String>) -> Result<DataFra
 /// 这是一个内部函数，处理 Excel 数据到 DataFrame 的转换
 ///
 /// 转换逻辑：
-/// 1. 第一行作为列名（表头）
-/// 2. 其余行作为数据
-/// 3. 所有数据先转换为字符串（简化处理）
+/// 1. 第 header_row 行作为列名（表头），空的列名使用 Column_N，重复的列名加后缀
+/// 2. 跳过表头之后的 skip_rows 行，其余行作为数据
+/// 3. 按单元格的原生类型构建列：整数、小数、布尔值、日期、日期时间、时间间隔
+///    直接生成对应类型的列（不经过文本转换），类型混杂的列保留为文本
 ///
 /// 参数：
 /// - range: Calamine 的 Range 对象，包含 Excel 单元格数据
+/// - header_row: 表头所在行（相对于 range 的第一行）
+/// - skip_rows: 表头之后要跳过的行数
 ///
 /// 返回：
 /// - Result<DataFrame>: 转换后的 DataFrame
-fn excel_range_to_dataframe(range: calamine::Range<calamine::Data>) -> Result<DataFrame> {
+fn excel_range_to_dataframe(
+    range: calamine::Range<calamine::Data>,
+    header_row: usize,
+    skip_rows: usize,
+) -> Result<DataFrame> {
     // 检查 Range 是否为空
     if range.is_empty() {
         return Err(DataAnalystError::InvalidDataFormat("Empty Excel sheet".to_string()));
@@ -254,61 +1290,49 @@ fn excel_range_to_dataframe(range: calamine::Range<calamine::Data>) -> Result<Da
         return Err(DataAnalystError::InvalidDataFormat("Empty Excel sheet".to_string()));
     }
 
+    if header_row >= height {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "表头行 {} 超出了读取范围（共 {} 行）",
+            header_row, height
+        )));
+    }
+
     // ------------------------------------------------------------------------
-    // 1. 提取列名（第一行）
+    // 1. 提取列名（表头行）
     // ------------------------------------------------------------------------
-    let headers: Vec<String> = (0..width)
-        .map(|col| {
-            // get_value: 获取指定单元格的值
-            // (0, col as u32): 第 0 行（表头），第 col 列
-            range
-                .get_value((0, col as u32))
-                .and_then(|v| v.as_string()) // 尝试转换为字符串
-                .unwrap_or_else(|| format!("Column_{}", col + 1)) // 如果失败，使用默认名称
-        })
-        .collect();
+    let mut headers: Vec<String> = Vec::with_capacity(width);
+    for col in 0..width {
+        // get: 获取相对于 range 左上角的单元格的值
+        // (header_row, col): 表头行，第 col 列
+        let name = range
+            .get((header_row, col))
+            .and_then(|v| v.as_string()) // 尝试转换为字符串
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| format!("Column_{}", col + 1)); // 如果失败，使用默认名称
+
+        // 重复的列名加后缀（name_2、name_3 ...），否则无法创建 DataFrame
+        let mut unique = name.clone();
+        let mut suffix = 2;
+        while headers.contains(&unique) {
+            unique = format!("{}_{}", name, suffix);
+            suffix += 1;
+        }
+        headers.push(unique);
+    }
 
     // ------------------------------------------------------------------------
-    // 2. 读取数据行（跳过第一行）
+    // 2. 按单元格类型构建每一列（跳过表头和 skip_rows 行）
     // ------------------------------------------------------------------------
+    let first_data_row = (header_row + 1 + skip_rows).min(height);
     // Vec<Column>: Polars 的列集合
-    let mut columns: Vec<Column> = Vec::new();
+    let mut columns: Vec<Column> = Vec::with_capacity(width);
 
-    // 遍历每一列
     for (col_idx, header) in headers.iter().enumerate() {
-        // 为每一列创建一个值数组
-        let mut values: Vec<Option<String>> = Vec::new();
-
-        // 遍历每一行（从第 1 行开始，跳过表头）
-        for row_idx in 1..height {
-            // 获取单元格的值
-            let cell_value = range.get_value((row_idx as u32, col_idx as u32));
-
-            // 将单元格值转换为字符串
-            let value = cell_value.map(|v| match v {
-                // 根据不同的数据类型进行转换
-                calamine::Data::Int(i) => i.to_string(),               // 整数
-                calamine::Data::Float(f) => f.to_string(),             // 浮点数
-                calamine::Data::String(s) => s.clone(),                // 字符串
-                calamine::Data::Bool(b) => b.to_string(),              // 布尔值
-                calamine::Data::DateTime(dt) => dt.to_string(),        // 日期时间
-                calamine::Data::Error(e) => format!("Error: {:?}", e), // 错误
-                calamine::Data::Empty => String::new(),                // 空单元格
-                _ => String::new(),                                    // 其他类型
-            });
-
-            // 添加到值数组
-            values.push(value);
-        }
-
-        // 创建 Polars Series（列）
-        // Series::new: 创建一个新的列
-        // header.as_str().into(): 列名
-        // values: 列的值
-        let series = Series::new(header.as_str().into(), values);
-
-        // 将 Series 转换为 Column 并添加到列集合
-        columns.push(series.into());
+        let cells: Vec<Option<&calamine::Data>> = (first_data_row..height)
+            .map(|row_idx| range.get((row_idx, col_idx)))
+            .collect();
+        columns.push(excel_cells_to_series(header, &cells)?.into());
     }
 
     // ------------------------------------------------------------------------
@@ -316,48 +1340,190 @@ fn excel_range_to_dataframe(range: calamine::Range<calamine::Data>) -> Result<Da
     // ------------------------------------------------------------------------
     // DataFrame::new: 从列集合创建 DataFrame
     // map_err: 将 Polars 错误转换为我们的自定义错误
-    let df = DataFrame::new(columns)?;
-
-    // ------------------------------------------------------------------------
-    // 4. 类型推导：将字符串列转换为合适的类型
-    // ------------------------------------------------------------------------
-    // 遍历每一列，尝试将字符串转换为更具体的类型（数值、日期等）
-    let mut typed_columns: Vec<Column> = Vec::new();
+    DataFrame::new(columns).map_err(|e| e.into())
+}
 
-    for col in df.get_columns() {
-        let series = col.as_materialized_series();
+/// Excel 单元格（或整列）的类型
+///
+/// 整列的类型由所有非空单元格的类型合并得到：
+/// - 整数 + 小数 → 小数，日期 + 日期时间 → 日期时间
+/// - 其他不同类型混在一起 → 文本
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExcelCellKind {
+    /// 整数（Int 单元格，或没有小数部分的 Float 单元格）
+    Int,
+    /// 小数
+    Float,
+    /// 布尔值
+    Bool,
+    /// 日期（时间部分为 00:00:00 的日期单元格）
+    Date,
+    /// 日期时间
+    Datetime,
+    /// 时间间隔（[h]:mm:ss 格式的单元格）
+    Duration,
+    /// 文本（包括错误单元格）
+    Text,
+}
 
-        // 尝试转换为数值类型
-        if let Ok(numeric) = series.cast(&datatypes::DataType::Float64) {
-            // 检查是否所有非空值都能成功转换
-            if numeric.null_count() == series.null_count() {
-                typed_columns.push(numeric.into());
-                continue;
-            }
+impl ExcelCellKind {
+    /// 单元格的类型（空单元格和错误单元格返回 None，不参与列类型判断）
+    fn of(cell: &calamine::Data) -> Option<Self> {
+        match cell {
+            calamine::Data::Empty | calamine::Data::Error(_) => None,
+            calamine::Data::Int(_) => Some(ExcelCellKind::Int),
+            calamine::Data::Float(f) if is_integral_f64(*f) => Some(ExcelCellKind::Int),
+            calamine::Data::Float(_) => Some(ExcelCellKind::Float),
+            calamine::Data::Bool(_) => Some(ExcelCellKind::Bool),
+            calamine::Data::String(s) if s.trim().is_empty() => None,
+            calamine::Data::DateTime(dt) if dt.is_duration() => Some(ExcelCellKind::Duration),
+            _ => match excel_cell_datetime(cell) {
+                Some(datetime) if datetime.time() == chrono::NaiveTime::MIN => Some(ExcelCellKind::Date),
+                Some(_) => Some(ExcelCellKind::Datetime),
+                None => Some(ExcelCellKind::Text),
+            },
         }
+    }
 
-        // 尝试转换为整数类型
-        if let Ok(integer) = series.cast(&datatypes::DataType::Int64) {
-            if integer.null_count() == series.null_count() {
-                typed_columns.push(integer.into());
-                continue;
-            }
+    /// 合并两个类型（用于确定整列的类型）
+    fn merge(self, other: Self) -> Self {
+        use ExcelCellKind::*;
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Int, Float) | (Float, Int) => Float,
+            (Date, Datetime) | (Datetime, Date) => Datetime,
+            _ => Text,
         }
+    }
+}
 
-        // 尝试转换为布尔类型
-        if let Ok(boolean) = series.cast(&datatypes::DataType::Boolean) {
-            if boolean.null_count() == series.null_count() {
-                typed_columns.push(boolean.into());
-                continue;
-            }
-        }
+/// 小数是否可以无损表示为 i64（Excel 中的整数通常以浮点数保存）
+fn is_integral_f64(value: f64) -> bool {
+    value.fract() == 0.0 && value.abs() < 9.0e15
+}
 
-        // 如果都不行，保持原始字符串类型
-        typed_columns.push(col.clone());
+/// 日期单元格转换为日期时间（xlsx 的日期单元格，以及 ods 的 ISO 日期文本）
+fn excel_cell_datetime(cell: &calamine::Data) -> Option<chrono::NaiveDateTime> {
+    match cell {
+        calamine::Data::DateTime(dt) if !dt.is_duration() => dt.as_datetime(),
+        calamine::Data::DateTimeIso(text) => chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S%.f")
+            .ok()
+            .or_else(|| {
+                chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                    .ok()
+                    .map(|date| date.and_time(chrono::NaiveTime::MIN))
+            }),
+        _ => None,
     }
+}
 
-    // 创建类型推导后的 DataFrame
-    DataFrame::new(typed_columns).map_err(|e| e.into())
+/// 单元格转换为文本（文本列使用；日期显示为 ISO 格式，而不是序列号）
+fn excel_cell_text(cell: &calamine::Data) -> Option<String> {
+    match cell {
+        calamine::Data::Empty => None,
+        calamine::Data::String(s) => Some(s.clone()),
+        calamine::Data::Int(i) => Some(i.to_string()),
+        calamine::Data::Float(f) if is_integral_f64(*f) => Some((*f as i64).to_string()),
+        calamine::Data::Float(f) => Some(f.to_string()),
+        calamine::Data::Bool(b) => Some(b.to_string()),
+        calamine::Data::Error(e) => Some(e.to_string()),
+        calamine::Data::DateTime(dt) if dt.is_duration() => Some(
+            dt.as_duration()
+                .map(|d| format_duration(d.num_milliseconds(), TimeUnit::Milliseconds))
+                .unwrap_or_else(|| dt.as_f64().to_string()),
+        ),
+        _ => Some(match excel_cell_datetime(cell) {
+            Some(datetime) if datetime.time() == chrono::NaiveTime::MIN => datetime.date().to_string(),
+            Some(datetime) => datetime.format("%Y-%m-%d %H:%M:%S").to_string(),
+            None => cell.to_string(),
+        }),
+    }
+}
+
+/// 根据单元格的原生类型构建一列
+///
+/// 整列为数值 / 布尔 / 日期 / 日期时间 / 时间间隔时直接构建对应类型的列，
+/// 类型混杂时构建文本列；空单元格和（非文本列中的）错误单元格为空值
+fn excel_cells_to_series(name: &str, cells: &[Option<&calamine::Data>]) -> Result<Series> {
+    let kind = cells
+        .iter()
+        .filter_map(|cell| cell.and_then(ExcelCellKind::of))
+        .reduce(ExcelCellKind::merge);
+    let name: PlSmallStr = name.into();
+    let epoch = chrono::NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid date");
+
+    let series = match kind {
+        Some(ExcelCellKind::Int) => {
+            let values: Vec<Option<i64>> = cells
+                .iter()
+                .map(|cell| match cell {
+                    Some(calamine::Data::Int(i)) => Some(*i),
+                    Some(calamine::Data::Float(f)) => Some(*f as i64),
+                    _ => None,
+                })
+                .collect();
+            Series::new(name, values)
+        }
+        Some(ExcelCellKind::Float) => {
+            let values: Vec<Option<f64>> = cells
+                .iter()
+                .map(|cell| match cell {
+                    Some(calamine::Data::Int(i)) => Some(*i as f64),
+                    Some(calamine::Data::Float(f)) => Some(*f),
+                    _ => None,
+                })
+                .collect();
+            Series::new(name, values)
+        }
+        Some(ExcelCellKind::Bool) => {
+            let values: Vec<Option<bool>> = cells
+                .iter()
+                .map(|cell| match cell {
+                    Some(calamine::Data::Bool(b)) => Some(*b),
+                    _ => None,
+                })
+                .collect();
+            Series::new(name, values)
+        }
+        Some(ExcelCellKind::Date) => {
+            // Date: 1970-01-01 起的天数
+            let values: Vec<Option<i32>> = cells
+                .iter()
+                .map(|cell| {
+                    let datetime = excel_cell_datetime((*cell)?)?;
+                    Some((datetime.date() - epoch).num_days() as i32)
+                })
+                .collect();
+            Series::new(name, values).cast(&datatypes::DataType::Date)?
+        }
+        Some(ExcelCellKind::Datetime) => {
+            let values: Vec<Option<i64>> = cells
+                .iter()
+                .map(|cell| Some(excel_cell_datetime((*cell)?)?.and_utc().timestamp_millis()))
+                .collect();
+            Series::new(name, values).cast(&datatypes::DataType::Datetime(TimeUnit::Milliseconds, None))?
+        }
+        Some(ExcelCellKind::Duration) => {
+            let values: Vec<Option<i64>> = cells
+                .iter()
+                .map(|cell| match cell {
+                    Some(calamine::Data::DateTime(dt)) => dt.as_duration().map(|d| d.num_milliseconds()),
+                    _ => None,
+                })
+                .collect();
+            Series::new(name, values).cast(&datatypes::DataType::Duration(TimeUnit::Milliseconds))?
+        }
+        // 文本列，或整列为空
+        Some(ExcelCellKind::Text) | None => {
+            let values: Vec<Option<String>> = cells
+                .iter()
+                .map(|cell| cell.and_then(excel_cell_text).filter(|text| !text.is_empty()))
+                .collect();
+            Series::new(name, values)
+        }
+    };
+
+    Ok(series)
 }
 
 // ============================================================================
@@ -419,6 +1585,7 @@ pub fn create_dataset_info(id: &str, file_path: &str, df: &DataFrame) -> Dataset
                 name: col.name().to_string(), // 列名
                 dtype,                        // 数据类型
                 null_count,                   // 空值数量
+                summary: None,                // 列摘要（按设置另行计算）
             }
         })
         .collect();
@@ -451,8 +1618,12 @@ pub fn create_dataset_info(id: &str, file_path: &str, df: &DataFrame) -> Dataset
 ///
 /// 参数：
 /// - df: Polars DataFrame（通常是切片后的部分数据）
+/// - formats: 列显示格式（列名 -> ColumnFormat），设置了格式的列输出为格式化后的字符串
+/// - precision: 浮点列的显示精度，没有显示格式的浮点值按它舍入（仍然输出为数字）；
+///   NaN / 无穷大按其中的 non_finite 策略输出
 ///
 /// 返回：
+/// - Err: non_finite 策略为 error 且数据中有 NaN / 无穷大
 /// - Vec<Vec<serde_json::Value>>: 二维数组
 ///   - 外层 Vec: 多行数据
 ///   - 内层 Vec: 一行中的多个值
@@ -466,7 +1637,11 @@ pub fn create_dataset_info(id: &str, file_path: &str, df: &DataFrame) -> Dataset
 ///   ["王五", null, "广州"]
 /// ]
 /// ```
-pub fn dataframe_to_json_rows(df: &DataFrame) -> Vec<Vec<serde_json::Value>> {
+pub fn dataframe_to_json_rows(
+    df: &DataFrame,
+    formats: &HashMap<String, ColumnFormat>,
+    precision: &FloatPrecision,
+) -> Result<Vec<Vec<serde_json::Value>>> {
     // 创建结果数组
     let mut rows = Vec::new();
 
@@ -487,8 +1662,28 @@ pub fn dataframe_to_json_rows(df: &DataFrame) -> Vec<Vec<serde_json::Value>> {
             // （确保数据已经加载到内存中）
             let series = col.as_materialized_series();
 
-            // 将单元格值转换为 JSON
-            let value = series_value_to_json(series, row_idx);
+            // NaN / 无穷大按策略输出，不参与格式化和舍入
+            if let Some(value) = col.dtype().is_float().then(|| non_finite_at(series, row_idx)).flatten() {
+                row.push(non_finite_to_json(value, precision.non_finite, col.name())?);
+                continue;
+            }
+
+            // 有显示格式时优先输出格式化后的字符串
+            let formatted = formats.get(col.name().as_str()).and_then(|format| {
+                series
+                    .get(row_idx)
+                    .ok()
+                    .and_then(|value| format_any_value(&value, format))
+            });
+
+            // 将单元格值转换为 JSON（没有显示格式的浮点值按显示精度舍入）
+            let value = match formatted {
+                Some(text) => serde_json::Value::String(text),
+                None => match precision.rounding_for(col.name().as_str()) {
+                    Some(rounding) => rounded_float_to_json(series, row_idx, rounding),
+                    None => series_value_to_json(series, row_idx),
+                },
+            };
 
             // 添加到 行数据
             row.push(value);
@@ -498,7 +1693,35 @@ pub fn dataframe_to_json_rows(df: &DataFrame) -> Vec<Vec<serde_json::Value>> {
         rows.push(row);
     }
 
-    rows
+    Ok(rows)
+}
+
+/// 单元格是 NaN / 无穷大时返回该值
+fn non_finite_at(series: &Series, idx: usize) -> Option<f64> {
+    match series.get(idx).ok()? {
+        AnyValue::Float32(v) if !v.is_finite() => Some(v as f64),
+        AnyValue::Float64(v) if !v.is_finite() => Some(v),
+        _ => None,
+    }
+}
+
+/// 按策略输出 NaN / 无穷大："NaN"、"Inf"、"-Inf"
+fn non_finite_to_json(value: f64, policy: NonFinitePolicy, column: &str) -> Result<serde_json::Value> {
+    let label = if value.is_nan() {
+        "NaN"
+    } else if value > 0.0 {
+        "Inf"
+    } else {
+        "-Inf"
+    };
+    match policy {
+        NonFinitePolicy::Null => Ok(serde_json::Value::Null),
+        NonFinitePolicy::Text => Ok(serde_json::Value::String(label.to_string())),
+        NonFinitePolicy::Error => Err(DataAnalystError::InvalidDataFormat(format!(
+            "列 '{}' 包含 {}，请先用 replace_non_finite 替换",
+            column, label
+        ))),
+    }
 }
 
 // ============================================================================
@@ -515,13 +1738,25 @@ pub fn dataframe_to_json_rows(df: &DataFrame) -> Vec<Vec<serde_json::Value>> {
 /// 返回：
 /// - serde_json::Value: JSON 值
 fn series_value_to_json(series: &Series, idx: usize) -> serde_json::Value {
-    // 导入 AnyValue 类型（Polars 的通用值类型）
-    use datatypes::AnyValue;
-
     // get: 获取指定索引的值
     // unwrap: 假设索引有效（在实际使用中，索引总是有效的）
-    let any_value = series.get(idx).unwrap();
+    any_value_to_json(series.get(idx).unwrap())
+}
+
+/// 将 Series 中的单个值转换为 JSON Value，浮点值按舍入方式舍入（其他类型不受影响）
+fn rounded_float_to_json(series: &Series, idx: usize, rounding: FloatRounding) -> serde_json::Value {
+    match series.get(idx).unwrap() {
+        AnyValue::Float32(v) if v.is_finite() => serde_json::json!(rounding.apply(v as f64)),
+        AnyValue::Float64(v) if v.is_finite() => serde_json::json!(rounding.apply(v)),
+        value => any_value_to_json(value),
+    }
+}
 
+/// 将单个 AnyValue 转换为 JSON Value
+///
+/// List 类型递归转换为 JSON 数组（例如 [1, 2, 3]、["a", null]），
+/// Struct 类型递归转换为 JSON 对象（例如 {"city": "北京", "zip": "100000"}）
+pub fn any_value_to_json(any_value: AnyValue) -> serde_json::Value {
     // 根据值的类型进行转换
     match any_value {
         // Null 值 → JSON null
@@ -562,6 +1797,9 @@ fn series_value_to_json(series: &Series, idx: usize) -> serde_json::Value {
             }
         }
 
+        // 定点小数 → JSON string（精确文本，避免转换为浮点数损失精度）
+        AnyValue::Decimal(v, _, scale) => serde_json::Value::String(decimal_to_string(v, scale)),
+
         // 日期和时间类型 → JSON string (格式化为 yyyy-MM-dd HH:mm:ss)
         AnyValue::Date(d) => {
             // Date 类型：转换为 yyyy-MM-dd 格式
@@ -593,11 +1831,35 @@ fn series_value_to_json(series: &Series, idx: usize) -> serde_json::Value {
             }
         }
         AnyValue::Time(t) => {
-            // Time 类型：转换为 HH:mm:ss 格式
-            let hours = t / 3_600_000_000_000;
-            let minutes = (t % 3_600_000_000_000) / 60_000_000_000;
-            let seconds = (t % 60_000_000_000) / 1_000_000_000;
-            serde_json::Value::String(format!("{:02}:{:02}:{:02}", hours, minutes, seconds))
+            // Time 类型：转换为 HH:mm:ss 格式（有小数秒时保留小数）
+            serde_json::Value::String(format_time(t))
+        }
+
+        // 时间间隔 → "2d 03:15:00" 格式（可以通过类型转换解析回 Duration）
+        AnyValue::Duration(v, time_unit) => serde_json::Value::String(format_duration(v, time_unit)),
+
+        // 列表 → JSON 数组（逐个元素递归转换）
+        AnyValue::List(inner) => {
+            serde_json::Value::Array((0..inner.len()).map(|i| series_value_to_json(&inner, i)).collect())
+        }
+
+        // 结构体 → JSON 对象（字段名 → 递归转换的字段值）
+        AnyValue::Struct(_, _, fields) => serde_json::Value::Object(
+            fields
+                .iter()
+                .zip(any_value._iter_struct_av())
+                .map(|(field, value)| (field.name().to_string(), any_value_to_json(value)))
+                .collect(),
+        ),
+        AnyValue::StructOwned(payload) => {
+            let (values, fields) = *payload;
+            serde_json::Value::Object(
+                fields
+                    .iter()
+                    .zip(values)
+                    .map(|(field, value)| (field.name().to_string(), any_value_to_json(value)))
+                    .collect(),
+            )
         }
 
         // 其他类型 → 使用 Debug 格式转换为字符串
@@ -637,11 +1899,11 @@ fn series_value_to_json(series: &Series, idx: usize) -> serde_json::Value {
 //
 // ```rust
 // // 1. 加载 CSV 文件
-// let df = load_csv("/path/to/data.csv")?;
+// let df = load_csv("/path/to/data.csv", &CsvImportOptions::default())?;
 // println!("加载了 {} 行数据", df.height());
 //
 // // 2. 加载 Excel 文件
-// let df = load_excel("/path/to/data.xlsx", None)?;
+// let df = load_excel("/path/to/data.xlsx", None, None, None, None)?;
 //
 // // 3. 创建元信息
 // let info = create_dataset_info("uuid", "/path/to/data.csv", &df);
@@ -649,8 +1911,62 @@ fn series_value_to_json(series: &Series, idx: usize) -> serde_json::Value {
 // println!("列数: {}", info.columns.len());
 //
 // // 4. 转换为 JSON
-// let json_rows = dataframe_to_json_rows(&df);
+// let json_rows = dataframe_to_json_rows(&df, &HashMap::new(), &FloatPrecision::default());
 // println!("第一行: {:?}", json_rows[0]);
 // ```
 //
 // ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::next_csv_chunk;
+    use std::io::Cursor;
+
+    /// 按给定块大小读取全部内容，返回每一块
+    fn chunks(content: &str, chunk_bytes: u64) -> Vec<String> {
+        let mut reader = Cursor::new(content.as_bytes().to_vec());
+        let mut result = Vec::new();
+        loop {
+            let chunk = next_csv_chunk(&mut reader, b'"', chunk_bytes).unwrap();
+            if chunk.is_empty() {
+                return result;
+            }
+            result.push(String::from_utf8(chunk).unwrap());
+        }
+    }
+
+    #[test]
+    fn chunk_extends_to_end_of_record() {
+        let content = "a,b\n1,2\n3,4\n";
+        assert_eq!(chunks(content, 5), vec!["a,b\n1,2\n", "3,4\n"]);
+    }
+
+    #[test]
+    fn quoted_newline_across_chunk_boundary_stays_in_one_chunk() {
+        // 块边界落在引号内的换行之前：块要延伸到引号结束后的那条记录末尾
+        let content = "id,note\n1,\"line one\nline two\"\n2,plain\n";
+        assert_eq!(
+            chunks(content, 12),
+            vec!["id,note\n1,\"line one\nline two\"\n", "2,plain\n"]
+        );
+    }
+
+    #[test]
+    fn quoted_field_with_several_newlines_and_escaped_quotes() {
+        // 引号字段跨越多行，其中的 "" 是转义的引号，不改变是否在引号内
+        let content = "1,\"a\n\"\"b\"\"\nc\"\n2,d\n";
+        assert_eq!(chunks(content, 4), vec!["1,\"a\n\"\"b\"\"\nc\"\n", "2,d\n"]);
+    }
+
+    #[test]
+    fn chunk_ending_exactly_at_record_end_is_not_extended() {
+        let content = "1,\"x\ny\"\n2,z\n";
+        assert_eq!(chunks(content, 8), vec!["1,\"x\ny\"\n", "2,z\n"]);
+    }
+
+    #[test]
+    fn last_record_without_trailing_newline() {
+        let content = "1,\"open\n2,z";
+        assert_eq!(chunks(content, 4), vec!["1,\"open\n2,z"]);
+    }
+}
@@ -25,6 +25,176 @@ pub mod store;
 /// 使用 Polars 处理 CSV，使用 Calamine 处理 Excel
 pub mod loader;
 
+/// 导出加密模块
+/// 列级 AES-256-GCM 加密（口令派生密钥）和带密码的 zip
+pub mod crypto;
+
+/// 文件导出模块
+/// 分批写出 CSV/Parquet（更新任务进度、支持取消），先写临时文件再原子重命名
+pub mod export;
+
+/// 剪贴板文本模块
+/// 将 DataFrame 渲染为 TSV / CSV / Markdown 文本（由前端写入剪贴板）
+pub mod clipboard;
+
+/// Excel 写入模块
+/// 将多个 DataFrame 写入同一个 Excel 工作簿（用于报告导出）
+pub mod excel;
+
+/// 定点小数模块
+/// Decimal 值的精确文本表示、转换为 f64、解析 Decimal(p, s) 类型名
+pub mod decimal;
+
+/// 时间间隔模块
+/// Duration / Time 与文本之间的转换，时间差、时间偏移、间隔转数值
+pub mod duration;
+
+/// 显示格式化模块
+/// 按照列显示格式（千分位、小数位、日期格式、百分比）格式化数据
+/// 用于表格展示和 CSV 导出
+pub mod format;
+
+/// 条件格式模块
+/// 校验条件格式规则，并计算分页数据中每个单元格的样式标签
+pub mod conditional;
+
+/// 数据集指纹模块
+/// 计算稳定的列级和整体内容哈希（SHA-256），用于检测数据是否变化
+pub mod fingerprint;
+
+/// 筛选条件构建模块
+/// 将 SQL 条件和结构化条件转换为 Polars 谓词表达式，并处理空值策略
+pub mod filter;
+
+/// 序列缺口检测模块
+/// 找出整数 / 日期序列中缺失的值（可按分组），并写出缺口报告
+pub mod gaps;
+
+/// 同比 / 环比模块
+/// 逐行或按日 / 周 / 月 / 季度 / 年汇总后，与上一周期比较（上期值、差值、变化率）
+pub mod period;
+
+/// 日期部分提取模块
+/// 提取年、季度、月、星期、ISO 周和财年 / 财季（可配置财年起始月份）
+pub mod calendar;
+
+/// 同期群留存模块
+/// 按注册周期划分同期群，统计每个偏移周期的活跃用户数和留存率
+pub mod cohort;
+
+/// 地理坐标模块
+/// 提供坐标校验、Haversine 距离、网格 / Geohash 分桶
+pub mod geo;
+
+/// 匹配键模块
+/// 规范化字符串键（忽略大小写和重音），按键删除重复行
+pub mod keys;
+
+/// 嵌套列模块
+/// 列表长度和元素统计、列表拼接、结构体字段展开、导出前转换为 JSON 字符串
+pub mod nested;
+
+/// Parquet 元数据模块
+/// 只读取文件 footer，提供行组、压缩、编码和统计值信息
+pub mod parquet_meta;
+
+/// CSV 导出预设模块
+/// 内置 / 用户预设的读写和校验，并把预设应用到 CsvWriter
+pub mod export_profiles;
+
+/// 类型转换预设模块
+/// 转换预设的读写和校验，按列名模式匹配规则并转换单列类型
+pub mod cast_profiles;
+
+/// 列摘要模块
+/// 列摘要设置的读写，计算元信息中每一列的最小值、最大值和不同值数量
+pub mod column_summary;
+
+/// 数据质量报告模块
+/// 计算每列最常见的值，把数据质量报告写为 JSON 或单文件 HTML
+pub mod profile_report;
+
+/// 图表规格模块
+/// 把图表数据转换为内嵌数据的独立 ECharts option
+pub mod chart_spec;
+
+/// 图表默认设置模块
+/// 默认设置的读写和校验
+pub mod chart_defaults;
+
+/// 列名冲突模块
+/// 按冲突策略决定操作生成的新列使用的列名，并记录自动改名
+pub mod column_names;
+
+/// 行数保护模块
+/// 行数保护设置的读写和校验，判断删除行的操作是否需要确认
+pub mod guardrails;
+
+/// 行顺序模块
+/// 行顺序设置的读写，稳定顺序下保持原始行顺序的辅助函数
+pub mod ordering;
+
+/// 数据校验模块
+/// 按校验规则找出违规行，并写出 JSON/CSV 校验报告
+pub mod validation;
+
+/// 字符串相似度模块
+/// 提供归一化 Levenshtein 相似度和近似重复值聚类
+pub mod similarity;
+
+/// 文本清洗模块
+/// 提供邮箱、电话、URL 等常见语义类型的规范化函数
+pub mod text;
+
+/// 后台任务模块
+/// 管理长时间运行任务的进度和取消标记
+pub mod tasks;
+
+/// 快照分享包模块
+/// 读写包含数据、操作历史、描述统计、图表配置和显示设置的 zip
+pub mod snapshot;
+
+/// 拆分导出模块
+/// 按某一列的取值把数据集拆分为多个部分，并生成安全的文件名
+pub mod partition;
+
+/// 操作记录模块
+/// 对比操作记录导入时的列结构和当前数据集，找出缺失列、类型不一致和改名建议；
+/// 替换操作记录参数，在新数据上重新执行支持的步骤
+pub mod recipe;
+
+/// 计划任务模块
+/// 计划任务的读写和校验，计算下次运行时间，记录运行状态
+pub mod scheduler;
+
+/// 历史归档模块
+/// 把每个历史条目的数据写为 Parquet，连同操作清单一起写入归档目录
+pub mod history_bundle;
+
+/// 远程文件下载模块
+/// 从 HTTP(S) 地址下载数据文件（超时、大小限制），判断文件格式
+pub mod download;
+
+/// 数据库连接模块
+/// 连接 PostgreSQL / MySQL，列出表，将查询结果转换为 DataFrame
+pub mod db;
+
+/// SQLite 导出模块
+/// 在一个事务中把 DataFrame 写入 SQLite 数据库的表（创建、替换或追加）
+pub mod sqlite;
+
+/// 源文件监视模块
+/// 监视当前数据集的源文件，文件在磁盘上变化时通知调用方
+pub mod watch;
+
+/// 导入互斥模块
+/// 同一时间只允许一个导入在进行，导入结束（成功、失败或取消）时自动释放
+pub mod import_gate;
+
+/// 演示数据模块
+/// 用固定种子生成合成数据集（销售、时间序列、鸢尾花），方便不用文件直接试用
+pub mod demo;
+
 // ============================================================================
 // 重新导出核心类型和函数
 // ============================================================================
@@ -36,38 +206,150 @@ pub mod loader;
 /// - 提供 insert、get、remove、clear 等方法
 ///
 /// SharedDataStore: 线程安全的共享数据存储
-/// - 类型别名：Arc<Mutex<DataStore>>
+/// - 类型别名：Arc<StoreMutex>
 /// - Arc: 允许多个所有者共享数据
-/// - Mutex: 确保同一时间只有一个线程可以访问数据
+/// - StoreMutex: 确保同一时间只有一个线程可以访问数据（命令 panic 导致锁中毒后自动恢复）
 /// - 用于 Tauri 的 AppState，在多个命令之间共享数据
-pub use store::{DataStore, SharedDataStore};
+pub use store::{DataStore, SharedDataStore, StoreMutex};
+
+/// 重新导出后台任务相关类型
+///
+/// TaskRegistry: 所有后台任务（查询进度、请求取消）
+/// SharedTaskRegistry: Arc<TaskRegistry>，保存在 AppState 中
+/// TaskHandle: 单个任务，工作线程通过它更新进度、检查取消
+pub use tasks::{SharedTaskRegistry, TaskHandle, TaskRegistry};
+
+/// 重新导出源文件监视相关类型
+///
+/// FileWatcher: 源文件监视器（同一时间只监视一个文件）
+/// SharedFileWatcher: Arc<FileWatcher>，保存在 AppState 中
+pub use watch::{FileWatcher, SharedFileWatcher};
+
+/// 重新导出导入互斥相关类型
+///
+/// ImportGate: 导入互斥（begin 开始一次导入，另一个导入进行中时返回错误）
+/// SharedImportGate: Arc<ImportGate>，保存在 AppState 中
+pub use import_gate::{ImportGate, SharedImportGate};
+
+/// 重新导出计划任务调度状态相关类型
+///
+/// Scheduler: 计划任务的下次运行时间、正在运行的任务和上次运行结果
+/// SharedScheduler: Arc<Scheduler>，保存在 AppState 中
+pub use scheduler::{Scheduler, SharedScheduler};
 
 /// 重新导出数据加载相关函数
 ///
 /// load_csv: 从 CSV 文件加载 DataFrame
-/// - 参数：文件路径
+/// - 参数：文件路径、导入选项（分隔符、表头、引号、编码等，未指定时自动检测）
 /// - 返回：Result<DataFrame>
 /// - 使用 Polars 的 CsvReader
 ///
+/// load_csv_with_progress: 同 load_csv，并通过回调报告读取进度（大文件分块读取）
+///
 /// load_excel: 从 Excel 文件加载 DataFrame
 /// - 参数：文件路径、可选的工作表名称
 /// - 返回：Result<DataFrame>
 /// - 使用 Calamine 读取 Excel，然后转换为 DataFrame
 ///
 /// load_parquet: 从 Parquet 文件加载 DataFrame
-/// - 参数：文件路径
+/// - 参数：文件路径、可选的列名列表、可选的最大行数
 /// - 返回：Result<DataFrame>
 /// - 使用 Polars 的 ParquetReader
 ///
+/// load_parquet_dir: 从 Hive 分区的 Parquet 目录加载 DataFrame
+/// - 参数：目录路径
+/// - 返回：Result<DataFrame>
+/// - 路径中的 `键=值` 作为列追加在末尾
+///
+/// load_arrow: 从 Arrow IPC（.arrow / .feather）文件加载 DataFrame
+/// - 参数：文件路径
+/// - 返回：Result<DataFrame>
+/// - 使用 Polars 的 IpcReader
+///
+/// load_avro: 从 Avro 容器文件加载 DataFrame
+/// - 参数：文件路径
+/// - 返回：Result<DataFrame>
+/// - 使用 Polars 的 AvroReader
+///
+/// load_orc: 从 ORC 文件加载 DataFrame
+/// - 参数：文件路径
+/// - 返回：Result<DataFrame>
+/// - 使用 orc-rust 读取为 Arrow 批次，再经 Arrow IPC 流转换
+///
+/// load_json: 从 JSON 数组或 NDJSON 文件加载 DataFrame
+/// - 参数：文件路径
+/// - 返回：Result<DataFrame>
+/// - 使用 Polars 的 JsonReader（根据扩展名和文件开头判断格式）
+///
+/// load_excel_all_sheets: 读取工作簿中的所有工作表并纵向合并
+/// - 参数：文件路径、记录来源工作表的列名、是否包含隐藏工作表
+/// - 返回：Result<DataFrame>
+/// - 各工作表按列名对齐，缺少的列填空值
+///
+/// load_sample: 只加载文件的前 N 行或随机 N 行
+/// - 参数：文件路径、抽样行数、抽样方式、CSV 导入选项
+/// - 返回：Result<DataFrame>
+/// - CSV / Parquet 随机抽样时逐块读取，内存中只保留抽样结果
+///
+/// list_excel_sheets: 列出 Excel 工作簿中的工作表
+/// - 参数：文件路径
+/// - 返回：Result<Vec<ExcelSheetInfo>>（名称、行数、列数、是否隐藏）
+///
 /// create_dataset_info: 从 DataFrame 创建元信息
 /// - 参数：数据集 ID、文件路径、DataFrame
 /// - 返回：DatasetInfo（包含行数、列信息、导入时间等）
 ///
 /// dataframe_to_json_rows: 将 DataFrame 转换为 JSON 格式
-/// - 参数：DataFrame（通常是切片后的部分数据）
+/// - 参数：DataFrame（通常是切片后的部分数据）、列显示格式
 /// - 返回：Vec<Vec<serde_json::Value>>（二维数组）
 /// - 用于将数据传输给前端
-pub use loader::{create_dataset_info, dataframe_to_json_rows, load_csv, load_excel, load_parquet};
+pub use loader::{
+    CsvProgress, create_dataset_info, dataframe_to_json_rows, list_excel_sheets, load_arrow, load_avro, load_csv,
+    load_csv_with_progress, load_excel, load_excel_all_sheets, load_json, load_orc, load_parquet, load_parquet_dir,
+    load_sample,
+};
+
+/// 重新导出显示格式化相关函数
+///
+/// apply_column_formats: 将设置了格式的列转换为格式化后的字符串列（用于导出）
+/// validate_date_format: 检查日期格式字符串是否有效
+pub use format::{apply_column_formats, validate_date_format};
+
+/// 重新导出条件格式相关函数
+///
+/// validate_conditional_rules: 校验条件格式规则
+/// compute_cell_styles: 计算当前页每个单元格的样式标签
+/// OutlierMomentsCache: 离群值规则的列统计量缓存（按历史条目）
+pub use conditional::{OutlierMomentsCache, compute_cell_styles, validate_conditional_rules};
+
+/// 重新导出数据集指纹相关函数
+///
+/// compute_fingerprint: 计算数据集的列级哈希和整体摘要
+pub use fingerprint::compute_fingerprint;
+
+/// 重新导出筛选条件构建相关函数
+///
+/// build_sql_predicate: 将 SQL WHERE 条件解析为谓词表达式
+/// build_condition_predicate: 将结构化筛选条件组合为谓词表达式
+/// apply_null_policy: 按空值策略调整谓词
+/// removed_by_predicate / rows_with_nulls: 获取筛选 / 删除空值时被删除的行
+/// select_export_subset: 按 ExportSubset 取出导出用的列和行
+pub use filter::{
+    apply_null_policy, build_condition_predicate, build_sql_predicate, removed_by_predicate, rows_with_nulls,
+    select_export_subset,
+};
+
+/// 重新导出数据校验相关函数
+///
+/// validate_dataframe: 按校验规则检查数据集，生成校验报告
+/// report_path_for: 计算导出文件对应的校验报告路径
+/// write_validation_report: 将校验报告写为 JSON 或 CSV
+pub use validation::{report_path_for, validate_dataframe, write_validation_report};
+
+/// 重新导出字符串相似度相关函数
+///
+/// cluster_similar_values: 将近似重复的值聚类
+pub use similarity::cluster_similar_values;
 
 // ============================================================================
 // 使用示例
@@ -79,7 +361,7 @@ pub use loader::{create_dataset_info, dataframe_to_json_rows, load_csv, load_exc
 // use crate::data::{load_csv, create_dataset_info, DataStore};
 //
 // // 1. 加载 CSV 文件
-// let df = load_csv("/path/to/file.csv")?;
+// let df = load_csv("/path/to/file.csv", &CsvImportOptions::default())?;
 //
 // // 2. 创建元信息
 // let info = create_dataset_info("uuid", "/path/to/file.csv", &df);
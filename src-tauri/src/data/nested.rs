@@ -0,0 +1,181 @@
+// ============================================================================
+// data/nested.rs - 嵌套列工具（列表 / 结构体）
+// ============================================================================
+// 这个文件处理嵌套类型的列（例如 JSON 数组/对象导入、嵌套的 Parquet 列）：
+// - 读取列表列、列表长度和元素统计、列表元素拼接为字符串
+// - 将结构体列的字段展开为顶层列
+// - 导出 CSV 前将嵌套列转换为 JSON 字符串（CSV 不支持嵌套类型）
+
+use crate::data::column_names::OutputColumns;
+use crate::data::decimal::is_numeric_dtype;
+use crate::data::loader::any_value_to_json;
+use crate::error::{DataAnalystError, Result};
+use crate::models::ListStats;
+use polars::prelude::*;
+use std::collections::HashSet;
+
+/// 是否为列表类型
+pub fn is_list_dtype(dtype: &DataType) -> bool {
+    matches!(dtype, DataType::List(_))
+}
+
+/// 读取列表列
+pub fn list_column(df: &DataFrame, column: &str) -> Result<ListChunked> {
+    let series = df
+        .column(column)
+        .map_err(|_| DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", column)))?;
+
+    if !is_list_dtype(series.dtype()) {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "列 '{}' 不是列表类型（当前类型: {:?}）",
+            column,
+            series.dtype()
+        )));
+    }
+
+    Ok(series.list()?.clone())
+}
+
+/// 单个嵌套值的 JSON 文本（用于去重、导出）
+fn nested_value_json(value: AnyValue) -> Option<String> {
+    match value {
+        AnyValue::Null => None,
+        other => Some(any_value_to_json(other).to_string()),
+    }
+}
+
+// ============================================================================
+// 统计
+// ============================================================================
+/// 嵌套列的唯一值数量（按 JSON 文本比较，空值计为一个值）
+pub fn nested_unique_count(series: &Series) -> usize {
+    series.iter().map(nested_value_json).collect::<HashSet<_>>().len()
+}
+
+/// 计算列表长度和元素统计
+///
+/// 空值行不参与统计；列表的内存布局本身就是展开后的元素，
+/// 长度直接取自偏移量，元素一次展开取出（不逐行复制）
+pub fn compute_list_stats(ca: &ListChunked) -> Result<ListStats> {
+    let valid = ca.clone().into_series().drop_nulls();
+    let valid = valid.list()?;
+
+    let lengths: Vec<usize> = valid.offsets()?.lengths().collect();
+    // 空列表不产生元素
+    let elements = valid.explode(true)?;
+
+    let (element_min, element_max, element_mean) = if is_numeric_dtype(elements.dtype()) {
+        let values = elements.cast(&DataType::Float64)?;
+        let values = values.f64()?;
+        (values.min(), values.max(), values.mean())
+    } else {
+        (None, None, None)
+    };
+
+    Ok(ListStats {
+        min_length: lengths.iter().min().copied(),
+        max_length: lengths.iter().max().copied(),
+        mean_length: if lengths.is_empty() {
+            None
+        } else {
+            Some(lengths.iter().sum::<usize>() as f64 / lengths.len() as f64)
+        },
+        empty_count: lengths.iter().filter(|&&len| len == 0).count(),
+        element_count: elements.len(),
+        element_null_count: elements.null_count(),
+        element_unique_count: elements.drop_nulls().n_unique()?,
+        element_min,
+        element_max,
+        element_mean,
+    })
+}
+
+// ============================================================================
+// 转换
+// ============================================================================
+/// 将结构体列的字段展开为顶层列（新列插入在原列的位置，原列被移除）
+///
+/// separator 为 Some 时新列名为 "{列名}{分隔符}{字段名}"，否则直接使用字段名
+/// 新列名与其他列冲突时按 outputs 的冲突策略处理（覆盖时被覆盖的列被移除）
+pub fn unnest_struct_column(
+    df: &DataFrame,
+    column: &str,
+    separator: Option<&str>,
+    outputs: &mut OutputColumns,
+) -> Result<DataFrame> {
+    let position = df
+        .get_column_index(column)
+        .ok_or_else(|| DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", column)))?;
+    let series = df.get_columns()[position].as_materialized_series();
+
+    if !matches!(series.dtype(), DataType::Struct(_)) {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "列 '{}' 不是结构体类型（当前类型: {:?}）",
+            column,
+            series.dtype()
+        )));
+    }
+
+    // 原结构体列被字段替换，它的列名可以被字段使用
+    outputs.release(column);
+    let fields: Vec<Column> = series
+        .struct_()?
+        .fields_as_series()
+        .into_iter()
+        .map(|field| {
+            let name = match separator {
+                Some(sep) => format!("{}{}{}", column, sep, field.name()),
+                None => field.name().to_string(),
+            };
+            let name = outputs.resolve(&name)?;
+            Ok(Column::from(field.with_name(name.into())))
+        })
+        .collect::<Result<_>>()?;
+    let field_names: HashSet<&str> = fields.iter().map(|field| field.name().as_str()).collect();
+
+    let mut columns = Vec::with_capacity(df.width() + fields.len());
+    for (idx, existing) in df.get_columns().iter().enumerate() {
+        if idx == position {
+            columns.extend(fields.iter().cloned());
+        } else if !field_names.contains(existing.name().as_str()) {
+            columns.push(existing.clone());
+        }
+    }
+
+    Ok(DataFrame::new(columns)?)
+}
+
+/// 将每个列表的元素拼接为字符串
+///
+/// 空值元素被跳过；空列表得到空字符串；列表本身为空值时结果为空值
+pub fn join_list_values(ca: &ListChunked, separator: &str) -> Result<StringChunked> {
+    ca.into_iter()
+        .map(|list| {
+            list.map(|list| {
+                let values = list.cast(&DataType::String)?;
+                let joined = values.str()?.into_iter().flatten().collect::<Vec<_>>().join(separator);
+                Ok::<String, DataAnalystError>(joined)
+            })
+            .transpose()
+        })
+        .collect()
+}
+
+/// 将嵌套列转换为 JSON 字符串（列表为数组，结构体为对象；列名不变，空值保持为空）
+pub fn nested_to_json_strings(series: &Series) -> StringChunked {
+    let json: StringChunked = series.iter().map(nested_value_json).collect();
+    json.with_name(series.name().clone())
+}
+
+/// 将所有嵌套列转换为 JSON 字符串列（CSV 不支持嵌套类型）
+pub fn nested_columns_to_json(df: &DataFrame) -> Result<DataFrame> {
+    let mut result = df.clone();
+
+    for column in df.get_columns() {
+        if column.dtype().is_nested() {
+            result.with_column(nested_to_json_strings(column.as_materialized_series()).into_series())?;
+        }
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,79 @@
+// ============================================================================
+// data/ordering.rs - 行顺序设置
+// ============================================================================
+// 这个文件负责行顺序设置的读写，以及稳定顺序下保持原始行顺序的辅助函数
+// 设置以 JSON 保存在应用配置目录下的 row_ordering.json，文件不存在时使用内置默认值（不启用）
+//
+// 稳定顺序下：
+// - 透视：使用保持顺序的分组，行和新列按首次出现的顺序排列
+// - 排序：值相同的行保持原来的相对顺序
+// - 纵表转换：用内部行号恢复原始行顺序，同一原始行展开的行排在一起
+
+use crate::data::export::write_atomic;
+use crate::error::{DataAnalystError, Result};
+use crate::models::RowOrdering;
+use polars::prelude::*;
+use std::io::Write;
+use std::path::Path;
+
+/// 行顺序设置文件名（位于应用配置目录）
+pub const ROW_ORDERING_FILE_NAME: &str = "row_ordering.json";
+
+/// 记录原始行号的内部列名
+const ORIGINAL_ORDER_COLUMN: &str = "__original_order";
+
+/// 读取行顺序设置（文件不存在时返回内置默认值，缺少的字段使用内置默认值）
+pub fn load_row_ordering(path: &Path) -> Result<RowOrdering> {
+    if !path.exists() {
+        return Ok(RowOrdering::default());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| DataAnalystError::InvalidDataFormat(format!("行顺序设置文件格式错误: {}", e)))
+}
+
+/// 保存行顺序设置（先写临时文件再替换）
+pub fn save_row_ordering(path: &Path, ordering: &RowOrdering) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let content = serde_json::to_string_pretty(ordering)?;
+    write_atomic(&path.to_string_lossy(), |file| {
+        file.write_all(content.as_bytes())?;
+        Ok(())
+    })
+}
+
+/// 纵表转换（宽表转长表），结果按原始行顺序排列
+///
+/// 同一原始行的各个值列依次排在一起（Polars 默认按值列分块排列）
+pub fn unpivot_in_original_order(df: &DataFrame, value_vars: &[String], id_vars: &[String]) -> Result<DataFrame> {
+    if df.get_column_index(ORIGINAL_ORDER_COLUMN).is_some() {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "列名 '{}' 为内部保留列名，请先重命名该列",
+            ORIGINAL_ORDER_COLUMN
+        )));
+    }
+
+    // 未指定值列时使用除 ID 列之外的所有列（不包括内部行号列）
+    let value_vars: Vec<String> = if value_vars.is_empty() {
+        df.get_column_names()
+            .iter()
+            .map(|name| name.to_string())
+            .filter(|name| !id_vars.contains(name))
+            .collect()
+    } else {
+        value_vars.to_vec()
+    };
+    let mut index = vec![ORIGINAL_ORDER_COLUMN.to_string()];
+    index.extend(id_vars.iter().cloned());
+
+    let indexed = df.with_row_index(ORIGINAL_ORDER_COLUMN.into(), None)?;
+    let unpivoted = indexed.unpivot(&value_vars, &index)?.sort(
+        [ORIGINAL_ORDER_COLUMN],
+        SortMultipleOptions::default().with_maintain_order(true),
+    )?;
+    Ok(unpivoted.drop(ORIGINAL_ORDER_COLUMN)?)
+}
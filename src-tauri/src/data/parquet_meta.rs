@@ -0,0 +1,125 @@
+// ============================================================================
+// data/parquet_meta.rs - Parquet 文件元数据读取
+// ============================================================================
+// 这个文件只读取 Parquet 文件末尾的元数据（footer），不读取数据页，
+// 因此即使文件很大也能很快返回
+
+use crate::error::{DataAnalystError, Result};
+use crate::models::{ParquetColumnChunk, ParquetColumnSummary, ParquetMetadata, ParquetRowGroup};
+use parquet::file::metadata::ColumnChunkMetaData;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::file::statistics::Statistics;
+use std::fs::File;
+
+/// 读取 Parquet 文件的元数据
+///
+/// 示例：
+/// ```rust
+/// let metadata = read_parquet_metadata("/path/to/data.parquet")?;
+/// println!("{} 个行组", metadata.row_groups.len());
+/// ```
+pub fn read_parquet_metadata(file_path: &str) -> Result<ParquetMetadata> {
+    let file = File::open(file_path)?;
+    let file_size = file.metadata()?.len();
+
+    let reader = SerializedFileReader::new(file)
+        .map_err(|e| DataAnalystError::InvalidDataFormat(format!("无法读取 Parquet 元数据: {}", e)))?;
+    let metadata = reader.metadata();
+    let file_metadata = metadata.file_metadata();
+
+    let row_groups: Vec<ParquetRowGroup> = metadata
+        .row_groups()
+        .iter()
+        .enumerate()
+        .map(|(index, row_group)| ParquetRowGroup {
+            index,
+            num_rows: row_group.num_rows(),
+            total_byte_size: row_group.total_byte_size(),
+            compressed_size: row_group.compressed_size(),
+            columns: row_group.columns().iter().map(column_chunk_info).collect(),
+        })
+        .collect();
+
+    Ok(ParquetMetadata {
+        file_path: file_path.to_string(),
+        file_size,
+        num_rows: file_metadata.num_rows(),
+        version: file_metadata.version(),
+        created_by: file_metadata.created_by().map(str::to_string),
+        columns: summarize_columns(&row_groups),
+        row_groups,
+    })
+}
+
+/// 提取单个列块的存储信息
+fn column_chunk_info(column: &ColumnChunkMetaData) -> ParquetColumnChunk {
+    let (min, max) = column.statistics().map(statistics_bounds).unwrap_or_default();
+
+    ParquetColumnChunk {
+        column: column.column_path().string(),
+        physical_type: column.column_type().to_string(),
+        compression: column.compression().to_string(),
+        encodings: column.encodings().iter().map(|e| e.to_string()).collect(),
+        compressed_size: column.compressed_size(),
+        uncompressed_size: column.uncompressed_size(),
+        null_count: column.statistics().and_then(|s| s.null_count_opt()),
+        min,
+        max,
+    }
+}
+
+/// 统计信息中的最小值和最大值（文本形式）
+fn statistics_bounds(statistics: &Statistics) -> (Option<String>, Option<String>) {
+    fn display<T: ToString>(min: Option<&T>, max: Option<&T>) -> (Option<String>, Option<String>) {
+        (min.map(T::to_string), max.map(T::to_string))
+    }
+    fn bytes(value: &[u8]) -> String {
+        String::from_utf8_lossy(value).into_owned()
+    }
+
+    match statistics {
+        Statistics::Boolean(s) => display(s.min_opt(), s.max_opt()),
+        Statistics::Int32(s) => display(s.min_opt(), s.max_opt()),
+        Statistics::Int64(s) => display(s.min_opt(), s.max_opt()),
+        Statistics::Float(s) => display(s.min_opt(), s.max_opt()),
+        Statistics::Double(s) => display(s.min_opt(), s.max_opt()),
+        Statistics::Int96(s) => (
+            s.min_opt().map(|v| format!("{:?}", v)),
+            s.max_opt().map(|v| format!("{:?}", v)),
+        ),
+        Statistics::ByteArray(s) => (
+            s.min_opt().map(|v| bytes(v.data())),
+            s.max_opt().map(|v| bytes(v.data())),
+        ),
+        Statistics::FixedLenByteArray(s) => (
+            s.min_opt().map(|v| bytes(v.data())),
+            s.max_opt().map(|v| bytes(v.data())),
+        ),
+    }
+}
+
+/// 按列汇总所有行组的大小（保持文件中的列顺序）
+fn summarize_columns(row_groups: &[ParquetRowGroup]) -> Vec<ParquetColumnSummary> {
+    let mut summaries: Vec<ParquetColumnSummary> = Vec::new();
+
+    for chunk in row_groups.iter().flat_map(|row_group| &row_group.columns) {
+        match summaries.iter_mut().find(|s| s.column == chunk.column) {
+            Some(summary) => {
+                summary.compressed_size += chunk.compressed_size;
+                summary.uncompressed_size += chunk.uncompressed_size;
+            }
+            None => summaries.push(ParquetColumnSummary {
+                column: chunk.column.clone(),
+                compressed_size: chunk.compressed_size,
+                uncompressed_size: chunk.uncompressed_size,
+                compression_ratio: None,
+            }),
+        }
+    }
+
+    for summary in &mut summaries {
+        summary.compression_ratio =
+            (summary.compressed_size > 0).then(|| summary.uncompressed_size as f64 / summary.compressed_size as f64);
+    }
+    summaries
+}
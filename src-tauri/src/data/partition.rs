@@ -0,0 +1,104 @@
+// ============================================================================
+// data/partition.rs - 按列值拆分导出
+// ============================================================================
+// 这个文件负责把数据集按某一列的取值拆分为多个部分（每个取值一个文件）：
+// - 按取值首次出现的顺序拆分，空值单独成为一部分
+// - 取值转换为安全的文件名（替换路径分隔符等非法字符，避免重名）
+
+use crate::error::{DataAnalystError, Result};
+use polars::prelude::*;
+use std::collections::HashSet;
+
+/// 最多拆分的文件数（防止误选高基数列生成大量文件）
+pub const MAX_PARTITIONS: usize = 1000;
+
+/// 空值部分使用的文件名
+const NULL_PARTITION_NAME: &str = "__null__";
+
+/// Partition - 拆分出的一部分数据
+pub struct Partition {
+    /// 输出文件名（包含扩展名）
+    pub file_name: String,
+
+    /// 这一部分的数据
+    pub df: DataFrame,
+}
+
+/// 把分区取值转换为文件名（不含扩展名）
+///
+/// 路径分隔符、Windows 不允许的字符和控制字符替换为 "_"，去掉首尾的空格和点
+fn sanitize_file_stem(value: &str) -> String {
+    let stem: String = value
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let stem = stem.trim_matches(|c: char| c == ' ' || c == '.');
+    if stem.is_empty() {
+        "_".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+/// 按列值拆分数据集
+///
+/// 参数：
+/// - column: 分区列（任意类型，按文本形式命名文件）
+/// - extension: 输出文件扩展名（例如 "csv"）
+///
+/// 不同取值得到相同文件名时（例如 "a/b" 和 "a_b"，或只有大小写不同），后出现的追加 "_2"、"_3"
+pub fn partition_dataframe(df: &DataFrame, column: &str, extension: &str) -> Result<Vec<Partition>> {
+    let keys = df
+        .column(column)
+        .map_err(|_| DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", column)))?
+        .cast(&DataType::String)?;
+    let keys = keys.as_materialized_series().str()?.clone();
+
+    let values: Vec<Option<String>> = keys
+        .clone()
+        .into_series()
+        .unique_stable()?
+        .str()?
+        .into_iter()
+        .map(|value| value.map(|value| value.to_string()))
+        .collect();
+    if values.len() > MAX_PARTITIONS {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "列 '{}' 有 {} 个不同取值，超过最多 {} 个文件的限制",
+            column,
+            values.len(),
+            MAX_PARTITIONS
+        )));
+    }
+
+    let mut used_names = HashSet::new();
+    let mut partitions = Vec::with_capacity(values.len());
+    for value in values {
+        let mask = match &value {
+            Some(value) => keys.equal(value.as_str()),
+            None => keys.is_null(),
+        };
+
+        let stem = match &value {
+            Some(value) => sanitize_file_stem(value),
+            None => NULL_PARTITION_NAME.to_string(),
+        };
+        let mut file_name = format!("{}.{}", stem, extension);
+        let mut suffix = 2;
+        while !used_names.insert(file_name.to_lowercase()) {
+            file_name = format!("{}_{}.{}", stem, suffix, extension);
+            suffix += 1;
+        }
+
+        partitions.push(Partition {
+            file_name,
+            df: df.filter(&mask)?,
+        });
+    }
+
+    Ok(partitions)
+}
@@ -0,0 +1,212 @@
+// ============================================================================
+// data/period.rs - 同比 / 环比
+// ============================================================================
+// 这个文件负责与上一周期比较（周环比、月环比、同比）：
+// 1. 逐行比较：每行找日期恰好早一个周期的那一行（同一分组内）
+// 2. 汇总比较：先按粒度（日 / 周 / 月 / 季度 / 年）汇总，再比较相邻周期
+//
+// 结果追加三列：
+// - {value}_prev: 上一周期的值（找不到时为空值）
+// - {value}_delta: 差值（本期 - 上期）
+// - {value}_pct: 变化率（差值 / |上期|，上期为 0 或空值时为空值），可配合百分比显示格式
+
+use crate::data::column_names::OutputColumns;
+use crate::data::decimal::is_numeric_dtype;
+use crate::error::{DataAnalystError, Result};
+use crate::models::history::{ComparisonPeriod, PeriodBucket};
+use polars::prelude::*;
+
+/// 汇总比较时默认的汇总函数
+pub const DEFAULT_AGGREGATION: &str = "sum";
+
+/// 构建汇总表达式（同比 / 环比和图表按时间段汇总共用）
+pub(crate) fn aggregation_expr(value_column: &str, aggregation: &str) -> Result<Expr> {
+    let c = col(value_column);
+    let expr = match aggregation {
+        "sum" => c.sum(),
+        "mean" => c.mean(),
+        "median" => c.median(),
+        "min" => c.min(),
+        "max" => c.max(),
+        "count" => c.count(),
+        "first" => c.first(),
+        "last" => c.last(),
+        _ => {
+            return Err(DataAnalystError::InvalidOperation(format!(
+                "不支持的汇总函数: {}（支持 sum, mean, median, min, max, count, first, last）",
+                aggregation
+            )));
+        }
+    };
+    Ok(expr)
+}
+
+/// 比较结果的三个列名
+pub fn comparison_column_names(value_column: &str) -> [String; 3] {
+    [
+        format!("{}_prev", value_column),
+        format!("{}_delta", value_column),
+        format!("{}_pct", value_column),
+    ]
+}
+
+// ============================================================================
+// 同比 / 环比计算
+// ============================================================================
+/// 与上一周期比较
+///
+/// 参数：
+/// - date_column: 日期或日期时间列
+/// - value_column: 数值列
+/// - period: 比较周期
+/// - group_by: 分组列（每组分别比较，分组值为空的行找不到上一周期）
+/// - bucket: 汇总粒度（None 表示逐行比较，行数和行顺序不变）
+/// - aggregation: 汇总函数（仅在 bucket 不为空时使用）
+/// - outputs: 三个结果列的列名冲突处理（汇总比较时只与保留的分组、日期、数值列比较）
+///
+/// 逐行比较时，同一分组内的日期必须唯一，否则上一周期的值不确定
+///
+/// 示例：
+/// ```rust
+/// // 每个门店按月汇总销售额，再计算同比
+/// let df = period_comparison(&df, "date", "sales", ComparisonPeriod::Yoy,
+///     &["store".to_string()], Some(PeriodBucket::Month), "sum", &mut outputs)?;
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn period_comparison(
+    df: &DataFrame,
+    date_column: &str,
+    value_column: &str,
+    period: ComparisonPeriod,
+    group_by: &[String],
+    bucket: Option<PeriodBucket>,
+    aggregation: &str,
+    outputs: &mut OutputColumns,
+) -> Result<DataFrame> {
+    let invalid = |message: String| DataAnalystError::InvalidOperation(message);
+
+    let date_dtype = df
+        .column(date_column)
+        .map_err(|_| invalid(format!("列 '{}' 不存在", date_column)))?
+        .dtype();
+    if !matches!(date_dtype, DataType::Date | DataType::Datetime(_, _)) {
+        return Err(invalid(format!(
+            "列 '{}' 不是日期或日期时间类型（当前类型: {:?}）",
+            date_column, date_dtype
+        )));
+    }
+
+    let value_dtype = df
+        .column(value_column)
+        .map_err(|_| invalid(format!("列 '{}' 不存在", value_column)))?
+        .dtype();
+    if !is_numeric_dtype(value_dtype) {
+        return Err(invalid(format!(
+            "列 '{}' 不是数值类型（当前类型: {:?}）",
+            value_column, value_dtype
+        )));
+    }
+
+    for name in group_by {
+        if name == date_column || name == value_column {
+            return Err(invalid(format!("分组列不能是日期列或数值列: {}", name)));
+        }
+        if df.get_column_index(name).is_none() {
+            return Err(invalid(format!("分组列 '{}' 不存在", name)));
+        }
+    }
+
+    // 连接时使用的临时列不能与已有列重名（否则会覆盖已有列）
+    if let Some(name) = ["__row", "__key", "__prev"]
+        .into_iter()
+        .find(|name| df.get_column_index(name).is_some())
+    {
+        return Err(invalid(format!("列名 '{}' 为内部保留列名，请先重命名该列", name)));
+    }
+
+    let group_exprs: Vec<Expr> = group_by.iter().map(|name| col(name.as_str())).collect();
+    let mut key_exprs = group_exprs.clone();
+    key_exprs.push(col(date_column));
+
+    // 汇总比较：先按粒度汇总；逐行比较：检查日期是否唯一
+    let base = match bucket {
+        Some(bucket) => {
+            if !bucket.supports(period) {
+                return Err(invalid(format!(
+                    "{}汇总后不能计算{}（汇总粒度不能比比较周期更粗，也不能是周和月混用）",
+                    bucket.label(),
+                    period.label()
+                )));
+            }
+
+            // 汇总后只保留分组、日期和数值列，其他列名不再占用
+            for column in df.get_column_names() {
+                if column != date_column && column != value_column && !group_by.iter().any(|name| name == column) {
+                    outputs.release(column);
+                }
+            }
+
+            let mut keys = group_exprs.clone();
+            keys.push(col(date_column).dt().truncate(lit(bucket.every())).alias(date_column));
+            df.clone()
+                .lazy()
+                .group_by(keys)
+                .agg([aggregation_expr(value_column, aggregation)?.alias(value_column)])
+                .sort_by_exprs(key_exprs.clone(), SortMultipleOptions::default())
+        }
+        None => {
+            let duplicated = df
+                .clone()
+                .lazy()
+                .filter(col(date_column).is_not_null())
+                .group_by(key_exprs.clone())
+                .agg([len().alias("__count")])
+                .filter(col("__count").gt(lit(1)))
+                .collect()?
+                .height();
+            if duplicated > 0 {
+                return Err(invalid(format!(
+                    "{} 个日期在同一分组内出现了多次，无法逐行比较，请指定汇总粒度或分组列",
+                    duplicated
+                )));
+            }
+            df.clone().lazy()
+        }
+    };
+
+    // 上一周期的值：本期日期往前推一个周期作为连接键，连接到上一周期的（分组, 日期, 值）
+    // 日期加一个周期不是一一对应（1 月 28 ~ 31 日都对应 2 月 28 日），不能反过来连接；
+    // 右表的（分组, 日期）唯一（逐行比较时已检查，汇总比较时由分组保证），每行最多匹配一行，行数不变
+    let mut lookup_exprs = group_exprs.clone();
+    lookup_exprs.push(col(date_column).alias("__key"));
+    lookup_exprs.push(col(value_column).alias("__prev"));
+    let lookup = base.clone().select(lookup_exprs).filter(col("__key").is_not_null());
+
+    let mut join_on = group_exprs;
+    join_on.push(col("__key"));
+    let back = format!("-{}", period.offset());
+
+    let [prev_name, delta_name, pct_name] = comparison_column_names(value_column);
+    let prev_name = outputs.resolve(&prev_name)?;
+    let delta_name = outputs.resolve(&delta_name)?;
+    let pct_name = outputs.resolve(&pct_name)?;
+    let prev = col("__prev").cast(DataType::Float64);
+    let delta = col(value_column).cast(DataType::Float64) - prev.clone();
+
+    let result = base
+        .with_row_index("__row", None)
+        .with_column(col(date_column).dt().offset_by(lit(back)).alias("__key"))
+        .join(lookup, join_on.clone(), join_on, JoinArgs::new(JoinType::Left))
+        .sort(["__row"], SortMultipleOptions::default())
+        .with_columns([
+            col("__prev").alias(prev_name.as_str()),
+            delta.clone().alias(delta_name.as_str()),
+            when(prev.clone().eq(lit(0.0)))
+                .then(lit(NULL).cast(DataType::Float64))
+                .otherwise(delta / prev.abs())
+                .alias(pct_name.as_str()),
+        ])
+        .collect()?;
+
+    Ok(result.drop_many(["__row", "__key", "__prev"]))
+}
@@ -0,0 +1,231 @@
+// ============================================================================
+// data/profile_report.rs - 数据质量报告
+// ============================================================================
+// 这个文件负责生成和写出数据质量报告：
+// - 计算每列最常见的值（按次数降序）
+// - 把列统计信息和最常见的值组合成 ProfileReport
+// - 写为 JSON（完整报告）或单文件 HTML（内联样式，不依赖外部资源，方便分享）
+
+use crate::data::export::write_atomic_checked;
+use crate::error::Result;
+use crate::models::{ColumnProfile, ColumnStats, ProfileReport, ProfileReportFormat, TopValue};
+use polars::prelude::*;
+use std::fmt::Write as _;
+use std::io::Write;
+
+/// 每列默认保留的最常见值数量
+pub const DEFAULT_TOP_VALUES: usize = 10;
+
+/// 由列统计信息构建数据质量报告（stats 与 df 的列一一对应）
+pub fn build_profile_report(
+    dataset_name: &str,
+    df: &DataFrame,
+    stats: Vec<ColumnStats>,
+    top_n: usize,
+) -> Result<ProfileReport> {
+    let profiles = stats
+        .into_iter()
+        .map(|stats| {
+            let top_values = top_values(df, &stats.name, top_n)?;
+            Ok(ColumnProfile { stats, top_values })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(ProfileReport {
+        dataset_name: dataset_name.to_string(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        rows: df.height(),
+        columns: df.width(),
+        profiles,
+    })
+}
+
+/// 计算单列最常见的值（按次数降序，次数相同时按值排序）
+///
+/// 列表、结构体列以及无法转换为文本的列返回空列表
+pub fn top_values(df: &DataFrame, column: &str, limit: usize) -> Result<Vec<TopValue>> {
+    let series = df.column(column)?;
+    if limit == 0 || series.dtype().is_nested() {
+        return Ok(Vec::new());
+    }
+    let Ok(text) = series.cast(&DataType::String) else {
+        return Ok(Vec::new());
+    };
+
+    let counts = DataFrame::new(vec![text.with_name("value".into())])?
+        .lazy()
+        .group_by([col("value")])
+        .agg([len().alias("count")])
+        .sort(
+            ["count", "value"],
+            SortMultipleOptions::default()
+                .with_order_descending_multi([true, false])
+                .with_nulls_last(true),
+        )
+        .limit(limit as IdxSize)
+        .collect()?;
+
+    let values = counts.column("value")?.str()?.clone();
+    let counts = counts.column("count")?.cast(&DataType::UInt64)?;
+    let counts = counts.u64()?;
+
+    Ok(values
+        .into_iter()
+        .zip(counts)
+        .map(|(value, count)| TopValue {
+            value: value.map(str::to_string),
+            count: count.unwrap_or_default() as usize,
+        })
+        .collect())
+}
+
+/// 按文件扩展名推断报告格式（.html / .htm 为 HTML，其余为 JSON）
+pub fn infer_profile_format(output_path: &str) -> ProfileReportFormat {
+    let extension = std::path::Path::new(output_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+
+    match extension.as_deref() {
+        Some("html") | Some("htm") => ProfileReportFormat::Html,
+        _ => ProfileReportFormat::Json,
+    }
+}
+
+/// 将数据质量报告写入文件（先写临时文件再重命名；overwrite 为 false 时不覆盖已存在的文件）
+pub fn write_profile_report(
+    report: &ProfileReport,
+    output_path: &str,
+    format: ProfileReportFormat,
+    overwrite: bool,
+) -> Result<()> {
+    write_atomic_checked(output_path, overwrite, |file| {
+        match format {
+            ProfileReportFormat::Json => serde_json::to_writer_pretty(&mut *file, report)?,
+            ProfileReportFormat::Html => file.write_all(render_profile_html(report).as_bytes())?,
+        }
+        Ok(())
+    })
+}
+
+// ============================================================================
+// HTML 渲染
+// ============================================================================
+/// 内联样式（报告是单个文件，不引用外部资源）
+const HTML_STYLE: &str = "body{font-family:-apple-system,'Segoe UI','PingFang SC','Microsoft YaHei',sans-serif;\
+margin:24px;color:#303133}h1{font-size:22px}h2{font-size:17px;margin-top:32px}\
+table{border-collapse:collapse;margin:8px 0;font-size:13px}\
+th,td{border:1px solid #dcdfe6;padding:4px 10px;text-align:left}th{background:#f5f7fa}\
+td.num{text-align:right;font-variant-numeric:tabular-nums}.meta{color:#909399}\
+.bar{display:inline-block;height:10px;background:#409eff;vertical-align:middle}";
+
+/// 把数据质量报告渲染为单文件 HTML
+pub fn render_profile_html(report: &ProfileReport) -> String {
+    let mut html = String::new();
+    let title = format!("数据质量报告 - {}", report.dataset_name);
+
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        escape_html(&title),
+        HTML_STYLE,
+        escape_html(&title)
+    );
+    let _ = writeln!(
+        html,
+        "<p class=\"meta\">{} 行 × {} 列 · 生成时间 {} · 版本 {}</p>",
+        report.rows,
+        report.columns,
+        escape_html(&report.created_at),
+        escape_html(&report.app_version)
+    );
+
+    // 概览：每列一行
+    html.push_str("<h2>概览</h2>\n<table>\n<tr><th>列名</th><th>类型</th><th>缺失</th><th>缺失率</th>");
+    html.push_str("<th>唯一值</th><th>最小值</th><th>25%</th><th>50%</th><th>75%</th><th>最大值</th>");
+    html.push_str("<th>平均值</th><th>标准差</th></tr>\n");
+    for profile in &report.profiles {
+        let stats = &profile.stats;
+        let null_ratio = if stats.total_count == 0 {
+            0.0
+        } else {
+            stats.null_count as f64 / stats.total_count as f64
+        };
+        let min = stats.min_datetime.clone().unwrap_or_else(|| format_number(stats.min));
+        let max = stats.max_datetime.clone().unwrap_or_else(|| format_number(stats.max));
+
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{:.1}%</td>\
+             <td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td>\
+             <td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+            escape_html(&stats.name),
+            escape_html(&stats.dtype),
+            stats.null_count,
+            null_ratio * 100.0,
+            stats.unique_count,
+            escape_html(&min),
+            format_number(stats.q25),
+            format_number(stats.q50),
+            format_number(stats.q75),
+            escape_html(&max),
+            format_number(stats.mean),
+            format_number(stats.std)
+        );
+    }
+    html.push_str("</table>\n");
+
+    // 每列最常见的值
+    for profile in report.profiles.iter().filter(|p| !p.top_values.is_empty()) {
+        let total = profile.stats.total_count.max(1) as f64;
+        let _ = writeln!(html, "<h2>{}</h2>", escape_html(&profile.stats.name));
+        html.push_str("<table>\n<tr><th>值</th><th>次数</th><th>占比</th><th></th></tr>\n");
+        for top in &profile.top_values {
+            let ratio = top.count as f64 / total;
+            let value = match &top.value {
+                Some(value) => escape_html(value),
+                None => "<span class=\"meta\">(空值)</span>".to_string(),
+            };
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{:.1}%</td>\
+                 <td><span class=\"bar\" style=\"width:{:.0}px\"></span></td></tr>",
+                value,
+                top.count,
+                ratio * 100.0,
+                ratio * 200.0
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// 格式化数值（整数不显示小数，其余保留 4 位小数，空值显示为 "-"）
+fn format_number(value: Option<f64>) -> String {
+    match value {
+        Some(v) if v.fract() == 0.0 && v.abs() < 1e15 => format!("{}", v as i64),
+        Some(v) => format!("{:.4}", v),
+        None => "-".to_string(),
+    }
+}
+
+/// 转义 HTML 特殊字符
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,499 @@
+// ============================================================================
+// data/recipe.rs - 操作记录兼容性检查
+// ============================================================================
+// 这个文件负责在把操作记录（recipe）用于新文件之前检查列结构是否兼容：
+// - 以操作记录第一步（导入）时的列和类型为准，对比当前数据集
+// - 从每一步操作的参数中找出用到的列（参数中的字符串和映射的键与列名相同即视为用到）
+// - 为缺失的列在多出的列中查找名称相似的列作为改名建议
+//
+// 以及操作记录参数的校验和替换：
+// - 参数绑定到步骤的操作参数（JSON Pointer），替换后重新解析为 OperationType，保证类型正确
+// - 替换后的步骤描述按新的操作参数重新生成
+//
+// 以及在新数据上重新执行操作记录（apply_recipe 和计划任务使用）：
+// - 只支持列结构和行筛选类的操作（选择 / 删除 / 重命名列、类型转换、按模式转换类型、排序、筛选、
+//   删除空值行、删除重复行、替换 NaN / 无穷大）
+// - 只修改显示设置的步骤不影响数据，直接跳过
+// - 其余操作（填充空值、生成新列等）依赖交互式命令中的逻辑和设置，遇到时在执行前一次性报告，不会执行到一半才失败
+
+use crate::data::cast_profiles::{cast_column, resolve_cast_rules};
+use crate::data::decimal::parse_dtype_name;
+use crate::data::filter::{apply_null_policy, build_condition_predicate, build_sql_predicate};
+use crate::data::keys::drop_duplicate_rows;
+use crate::data::similarity::{normalize_for_compare, similarity};
+use crate::error::{DataAnalystError, Result};
+use crate::models::{
+    HistoryEntryInfo, OperationType, RecipeColumnIssue, RecipeCompatibilityReport, RecipeParameter,
+    RecipeParameterKind, RenameSuggestion,
+};
+use polars::prelude::*;
+use serde_json::Value as JsonValue;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+/// 可以重新执行的操作（用于错误信息和文档）
+pub const REPLAYABLE_OPERATIONS: &str =
+    "选择 / 删除 / 重命名列、类型转换、按模式转换类型、排序、筛选、删除空值行、删除重复行、替换 NaN / 无穷大";
+
+/// 改名建议的最低列名相似度
+const RENAME_SIMILARITY_THRESHOLD: f64 = 0.6;
+
+/// 检查操作记录与数据集的兼容性
+///
+/// 操作记录为空时返回错误
+pub fn check_recipe_compatibility(recipe: &[HistoryEntryInfo], df: &DataFrame) -> Result<RecipeCompatibilityReport> {
+    let (baseline, steps) = recipe
+        .split_first()
+        .ok_or_else(|| DataAnalystError::InvalidOperation("操作记录为空".to_string()))?;
+
+    let expected: Vec<(&str, &str)> = baseline
+        .metadata
+        .columns
+        .iter()
+        .map(|column| (column.name.as_str(), column.dtype.as_str()))
+        .collect();
+    let expected_names: HashSet<&str> = expected.iter().map(|(name, _)| *name).collect();
+
+    // 每一列被哪些步骤用到
+    let mut usage: HashMap<&str, Vec<String>> = HashMap::new();
+    for (index, step) in steps.iter().enumerate() {
+        let mut strings = Vec::new();
+        if let Ok(value) = serde_json::to_value(&step.operation) {
+            collect_strings(&value, &mut strings);
+        }
+        let referenced: HashSet<&str> = strings
+            .iter()
+            .filter_map(|s| expected_names.get(s.as_str()).copied())
+            .collect();
+        for name in referenced {
+            usage
+                .entry(name)
+                .or_default()
+                .push(format!("第 {} 步: {}", index + 1, step.description));
+        }
+    }
+
+    let mut missing_columns = Vec::new();
+    let mut type_mismatches = Vec::new();
+    for (name, dtype) in &expected {
+        let used_by_steps = usage.get(name).cloned().unwrap_or_default();
+        match df.column(name) {
+            Err(_) => missing_columns.push(RecipeColumnIssue {
+                column: name.to_string(),
+                expected_dtype: dtype.to_string(),
+                actual_dtype: None,
+                used_by_steps,
+            }),
+            Ok(column) => {
+                let actual = format!("{:?}", column.dtype());
+                if actual != *dtype {
+                    type_mismatches.push(RecipeColumnIssue {
+                        column: name.to_string(),
+                        expected_dtype: dtype.to_string(),
+                        actual_dtype: Some(actual),
+                        used_by_steps,
+                    });
+                }
+            }
+        }
+    }
+
+    let extra_columns: Vec<String> = df
+        .get_column_names()
+        .into_iter()
+        .filter(|name| !expected_names.contains(name.as_str()))
+        .map(|name| name.to_string())
+        .collect();
+
+    let suggested_renames = missing_columns
+        .iter()
+        .filter_map(|issue| suggest_rename(&issue.column, &extra_columns))
+        .collect();
+
+    let compatible = missing_columns
+        .iter()
+        .chain(&type_mismatches)
+        .all(|issue| issue.used_by_steps.is_empty());
+
+    Ok(RecipeCompatibilityReport {
+        compatible,
+        step_count: steps.len(),
+        missing_columns,
+        type_mismatches,
+        extra_columns,
+        suggested_renames,
+    })
+}
+
+/// 收集 JSON 中的所有字符串（包括对象的键）
+fn collect_strings(value: &JsonValue, out: &mut Vec<String>) {
+    match value {
+        JsonValue::String(s) => out.push(s.clone()),
+        JsonValue::Array(items) => items.iter().for_each(|item| collect_strings(item, out)),
+        JsonValue::Object(map) => {
+            for (key, item) in map {
+                out.push(key.clone());
+                collect_strings(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 在候选列中查找与缺失列名称最相似的列（规范化后比较，低于阈值时不建议）
+fn suggest_rename(missing: &str, candidates: &[String]) -> Option<RenameSuggestion> {
+    let target = normalize_for_compare(missing);
+    candidates
+        .iter()
+        .map(|candidate| (candidate, similarity(&target, &normalize_for_compare(candidate))))
+        .filter(|(_, score)| *score >= RENAME_SIMILARITY_THRESHOLD)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(candidate, score)| RenameSuggestion {
+            expected: missing.to_string(),
+            candidate: candidate.clone(),
+            similarity: score,
+        })
+}
+
+// ============================================================================
+// 操作记录参数
+// ============================================================================
+/// 校验参数声明：参数名唯一且非空、至少有一个绑定、绑定的步骤和位置存在、默认值符合参数类型
+pub fn validate_recipe_parameters(recipe: &[HistoryEntryInfo], parameters: &[RecipeParameter]) -> Result<()> {
+    let mut names = HashSet::new();
+    for parameter in parameters {
+        if parameter.name.trim().is_empty() {
+            return Err(parameter_error("参数名不能为空"));
+        }
+        if !names.insert(parameter.name.as_str()) {
+            return Err(parameter_error(format!("参数名 '{}' 重复", parameter.name)));
+        }
+        if parameter.bindings.is_empty() {
+            return Err(parameter_error(format!("参数 '{}' 没有绑定任何步骤", parameter.name)));
+        }
+        for binding in &parameter.bindings {
+            let params = step_params(recipe, binding.step)?;
+            if params.pointer(&binding.path).is_none() {
+                return Err(parameter_error(format!(
+                    "参数 '{}' 绑定的位置 {} 在第 {} 步中不存在",
+                    parameter.name, binding.path, binding.step
+                )));
+            }
+        }
+        if let Some(default) = &parameter.default {
+            check_parameter_value(parameter, default)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 用参数值替换操作记录中绑定的位置，返回替换后的操作记录
+///
+/// 参数值的优先级：传入的值 → 参数默认值 → 第一个绑定位置上记录的值
+/// 传入了未声明的参数时返回错误
+pub fn resolve_recipe_parameters(
+    recipe: &[HistoryEntryInfo],
+    parameters: &[RecipeParameter],
+    values: &HashMap<String, JsonValue>,
+) -> Result<Vec<HistoryEntryInfo>> {
+    validate_recipe_parameters(recipe, parameters)?;
+
+    if let Some(unknown) = values.keys().find(|name| !parameters.iter().any(|p| &p.name == *name)) {
+        return Err(parameter_error(format!("未声明的参数 '{}'", unknown)));
+    }
+
+    // 被绑定的步骤的操作（JSON），按绑定逐一替换
+    let mut step_values: HashMap<usize, JsonValue> = HashMap::new();
+    for binding in parameters.iter().flat_map(|p| &p.bindings) {
+        if let Entry::Vacant(entry) = step_values.entry(binding.step) {
+            entry.insert(serde_json::to_value(&recipe[binding.step].operation)?);
+        }
+    }
+
+    for parameter in parameters {
+        let value = match values.get(&parameter.name).or(parameter.default.as_ref()) {
+            Some(value) => value.clone(),
+            None => {
+                let first = &parameter.bindings[0];
+                step_params(recipe, first.step)?
+                    .pointer(&first.path)
+                    .cloned()
+                    .unwrap_or(JsonValue::Null)
+            }
+        };
+        check_parameter_value(parameter, &value)?;
+
+        for binding in &parameter.bindings {
+            if let Some(slot) = step_values
+                .get_mut(&binding.step)
+                .and_then(|operation| operation.get_mut("params"))
+                .and_then(|params| params.pointer_mut(&binding.path))
+            {
+                *slot = value.clone();
+            }
+        }
+    }
+
+    let mut resolved = recipe.to_vec();
+    for (step, value) in step_values {
+        let operation: OperationType = serde_json::from_value(value)
+            .map_err(|e| parameter_error(format!("替换参数后第 {} 步的操作参数无效: {}", step, e)))?;
+        resolved[step].description = operation.description();
+        resolved[step].operation = operation;
+    }
+
+    Ok(resolved)
+}
+
+/// 取出第 step 步的操作参数（JSON）；第 0 条为导入，不能绑定参数
+fn step_params(recipe: &[HistoryEntryInfo], step: usize) -> Result<JsonValue> {
+    if step == 0 || step >= recipe.len() {
+        return Err(parameter_error(format!(
+            "步骤序号 {} 超出范围（1 ~ {}）",
+            step,
+            recipe.len().saturating_sub(1)
+        )));
+    }
+
+    let operation = serde_json::to_value(&recipe[step].operation)?;
+    Ok(operation.get("params").cloned().unwrap_or(JsonValue::Null))
+}
+
+/// 检查参数值是否符合参数类型
+fn check_parameter_value(parameter: &RecipeParameter, value: &JsonValue) -> Result<()> {
+    let valid = match parameter.kind {
+        RecipeParameterKind::Text => value.is_string(),
+        RecipeParameterKind::Number => value.is_number(),
+        RecipeParameterKind::Boolean => value.is_boolean(),
+        RecipeParameterKind::Date => value
+            .as_str()
+            .is_some_and(|s| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok()),
+        RecipeParameterKind::Column => value.as_str().is_some_and(|s| !s.is_empty()),
+        RecipeParameterKind::Columns => value
+            .as_array()
+            .is_some_and(|items| items.iter().all(|item| item.as_str().is_some_and(|s| !s.is_empty()))),
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(parameter_error(format!(
+            "参数 '{}' 的值 {} 不符合类型 {:?}",
+            parameter.name, value, parameter.kind
+        )))
+    }
+}
+
+fn parameter_error(message: impl std::fmt::Display) -> DataAnalystError {
+    DataAnalystError::InvalidOperation(format!("操作记录参数无效: {}", message))
+}
+
+// ============================================================================
+// 重新执行操作记录
+// ============================================================================
+/// 找出不能重新执行的步骤（"第 N 步: 操作描述"；只修改显示设置的步骤不算在内）
+pub fn unsupported_recipe_steps(recipe: &[HistoryEntryInfo]) -> Vec<String> {
+    recipe
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, step)| !step.operation.is_display_only() && !is_replayable(&step.operation))
+        .map(|(index, step)| format!("第 {} 步: {}", index, step.description))
+        .collect()
+}
+
+/// 在数据上按顺序重新执行操作记录（跳过第一条导入记录和只修改显示设置的步骤）
+///
+/// 有不能重新执行的步骤时，在执行任何步骤之前返回错误
+pub fn replay_recipe(df: DataFrame, recipe: &[HistoryEntryInfo]) -> Result<DataFrame> {
+    let unsupported = unsupported_recipe_steps(recipe);
+    if !unsupported.is_empty() {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "以下步骤不支持重新执行: {}（只支持{}）",
+            unsupported.join("；"),
+            REPLAYABLE_OPERATIONS
+        )));
+    }
+
+    recipe
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, step)| !step.operation.is_display_only())
+        .try_fold(df, |df, (index, step)| replay_recipe_step(df, index, step))
+}
+
+/// 重新执行操作记录中的第 index 步（错误信息包含步骤序号和描述）
+pub fn replay_recipe_step(df: DataFrame, index: usize, step: &HistoryEntryInfo) -> Result<DataFrame> {
+    replay_operation(df, &step.operation).map_err(|e| {
+        DataAnalystError::InvalidOperation(format!("第 {} 步（{}）执行失败: {}", index, step.description, e))
+    })
+}
+
+fn is_replayable(operation: &OperationType) -> bool {
+    matches!(
+        operation,
+        OperationType::SelectColumns { .. }
+            | OperationType::DropColumns { .. }
+            | OperationType::RenameColumns { .. }
+            | OperationType::CastTypes { .. }
+            | OperationType::CastTypesByPattern { .. }
+            | OperationType::Sort { .. }
+            | OperationType::Filter { .. }
+            | OperationType::FilterRows { .. }
+            | OperationType::DropNulls { .. }
+            | OperationType::DropAllNulls
+            | OperationType::DropDuplicates { .. }
+            | OperationType::ReplaceNonFinite { .. }
+    )
+}
+
+/// 执行单个操作（与对应交互式命令的结果一致，排序总是保持相同值的原有顺序）
+fn replay_operation(df: DataFrame, operation: &OperationType) -> Result<DataFrame> {
+    let result = match operation {
+        OperationType::SelectColumns {
+            columns,
+            keep_remaining,
+        } => {
+            let mut order = columns.clone();
+            if *keep_remaining {
+                order.extend(
+                    df.get_column_names()
+                        .iter()
+                        .map(|name| name.to_string())
+                        .filter(|name| !columns.contains(name)),
+                );
+            }
+            df.select(order)?
+        }
+        OperationType::DropColumns { columns } => {
+            if let Some(missing) = columns.iter().find(|name| df.get_column_index(name).is_none()) {
+                return Err(DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", missing)));
+            }
+            df.drop_many(columns.iter().map(|s| s.as_str()))
+        }
+        OperationType::RenameColumns { mapping } => {
+            let mut df = df;
+            for (old_name, new_name) in mapping {
+                df.rename(old_name, new_name.as_str().into())?;
+            }
+            df
+        }
+        OperationType::CastTypes { mapping } => {
+            let mut df = df;
+            for (column, target_type) in mapping {
+                let data_type = parse_dtype_name(target_type).map_err(DataAnalystError::InvalidOperation)?;
+                let casted = cast_column(df.column(column)?, &data_type, None)?;
+                df.with_column(casted)?;
+            }
+            df
+        }
+        // 按规则重新匹配列（新文件中匹配到的列可以与记录时不同）
+        OperationType::CastTypesByPattern { rules, .. } => {
+            let resolved = resolve_cast_rules(&df, rules)?;
+            let mut df = df;
+            for (column, rule) in resolved {
+                let data_type = parse_dtype_name(&rule.target_type).map_err(DataAnalystError::InvalidOperation)?;
+                let casted = cast_column(df.column(&column)?, &data_type, rule.format.as_deref())?;
+                df.with_column(casted)?;
+            }
+            df
+        }
+        OperationType::Sort {
+            column,
+            descending,
+            nulls_last,
+        } => df.sort(
+            [column.as_str()],
+            SortMultipleOptions::new()
+                .with_order_descending(*descending)
+                .with_nulls_last(*nulls_last)
+                .with_maintain_order(true),
+        )?,
+        OperationType::Filter {
+            expression,
+            null_policy,
+        } => df
+            .lazy()
+            .filter(apply_null_policy(build_sql_predicate(expression)?, *null_policy))
+            .collect()?,
+        OperationType::FilterRows {
+            conditions,
+            match_all,
+            null_policy,
+        } => {
+            if let Some(missing) = conditions.iter().find(|c| df.get_column_index(&c.column).is_none()) {
+                return Err(DataAnalystError::InvalidOperation(format!(
+                    "列 '{}' 不存在",
+                    missing.column
+                )));
+            }
+            df.lazy()
+                .filter(apply_null_policy(
+                    build_condition_predicate(conditions, *match_all)?,
+                    *null_policy,
+                ))
+                .collect()?
+        }
+        OperationType::DropNulls { subset } => df.drop_nulls(subset.as_deref())?,
+        OperationType::DropAllNulls => {
+            let mask = df
+                .get_columns()
+                .iter()
+                .map(|column| column.is_not_null())
+                .reduce(|keep, not_null| &keep | &not_null);
+            match mask {
+                Some(mask) => df.filter(&mask)?,
+                None => df,
+            }
+        }
+        OperationType::DropDuplicates {
+            subset,
+            keep,
+            normalize_keys,
+        } => drop_duplicate_rows(&df, subset, *keep, *normalize_keys)?,
+        // 没有 NaN / 无穷大时不报错（交互式命令此时返回错误，新文件中没有这些值是正常情况）
+        OperationType::ReplaceNonFinite { columns, value } => {
+            let targets: Vec<String> = match columns {
+                Some(columns) => {
+                    for name in columns {
+                        if !df.column(name)?.dtype().is_float() {
+                            return Err(DataAnalystError::InvalidOperation(format!("列 '{}' 不是浮点列", name)));
+                        }
+                    }
+                    columns.clone()
+                }
+                None => df
+                    .get_columns()
+                    .iter()
+                    .filter(|c| c.dtype().is_float())
+                    .map(|c| c.name().to_string())
+                    .collect(),
+            };
+            let exprs: Vec<Expr> = targets
+                .iter()
+                .map(|name| {
+                    let dtype = df.column(name).map(|c| c.dtype().clone()).unwrap_or(DataType::Float64);
+                    let replacement = match value {
+                        Some(v) => lit(*v),
+                        None => lit(NULL),
+                    };
+                    when(col(name.as_str()).is_finite().not().fill_null(lit(false)))
+                        .then(replacement.cast(dtype))
+                        .otherwise(col(name.as_str()))
+                        .alias(name.as_str())
+                })
+                .collect();
+            df.lazy().with_columns(exprs).collect()?
+        }
+        _ => {
+            return Err(DataAnalystError::InvalidOperation(format!(
+                "不支持重新执行: {}",
+                operation.description()
+            )));
+        }
+    };
+
+    Ok(result)
+}
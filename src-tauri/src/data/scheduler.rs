@@ -0,0 +1,242 @@
+// ============================================================================
+// data/scheduler.rs - 计划任务
+// ============================================================================
+// 这个文件负责计划任务的存储和调度状态：
+// 1. 任务配置以 JSON 保存在应用配置目录下的 schedules.json
+// 2. 计算每个任务的下次运行时间（本地时间），每次检查时取出到期的任务
+// 3. 记录正在运行的任务（同一个任务不会同时运行两次）和每个任务的上次运行结果
+//
+// 定时检查和任务执行（重新导入 → 执行操作记录 → 导出）在 commands/schedule.rs 中
+
+use crate::data::export::write_atomic;
+use crate::data::recipe::{REPLAYABLE_OPERATIONS, unsupported_recipe_steps, validate_recipe_parameters};
+use crate::error::{DataAnalystError, Result};
+use crate::models::{JobSchedule, ScheduledJob, ScheduledRunResult};
+use chrono::{DateTime, Duration, Local, NaiveTime, TimeZone};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// 计划任务文件名（位于应用配置目录）
+pub const SCHEDULES_FILE_NAME: &str = "schedules.json";
+
+/// 检查到期任务的间隔
+pub const SCHEDULER_TICK: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// 按分钟间隔运行的任务允许的最大间隔（366 天）
+pub const MAX_INTERVAL_MINUTES: u64 = 366 * 24 * 60;
+
+/// SharedScheduler - 在命令之间共享的调度状态
+pub type SharedScheduler = Arc<Scheduler>;
+
+// ============================================================================
+// 调度状态
+// ============================================================================
+/// Scheduler - 计划任务的调度状态（只保存在内存中，应用重启后重新计算）
+#[derive(Default)]
+pub struct Scheduler {
+    /// 任务 ID -> （计算时使用的运行时间, 下次运行时间）
+    next_due: Mutex<HashMap<String, (JobSchedule, DateTime<Local>)>>,
+
+    /// 正在运行的任务 ID
+    running: Mutex<HashSet<String>>,
+
+    /// 任务 ID -> 上次运行结果
+    last_runs: Mutex<HashMap<String, ScheduledRunResult>>,
+}
+
+impl Scheduler {
+    /// 创建调度状态（还没有任何任务）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 取出到期的已启用任务，并安排它们的下次运行
+    ///
+    /// 第一次见到的任务（或运行时间被修改的任务）只计算下次运行时间，不会立即运行
+    pub fn take_due(&self, jobs: &[ScheduledJob], now: DateTime<Local>) -> Vec<ScheduledJob> {
+        let mut next_due = self.next_due.lock().unwrap_or_else(PoisonError::into_inner);
+        next_due.retain(|id, _| jobs.iter().any(|job| job.enabled && &job.id == id));
+
+        let mut due = Vec::new();
+        for job in jobs.iter().filter(|job| job.enabled) {
+            let scheduled = next_due
+                .get(&job.id)
+                .filter(|(schedule, _)| *schedule == job.schedule)
+                .map(|(_, at)| *at);
+            match scheduled {
+                Some(at) if at > now => continue,
+                Some(_) => due.push(job.clone()),
+                None => {}
+            }
+            if let Some(at) = next_run(&job.schedule, now) {
+                next_due.insert(job.id.clone(), (job.schedule.clone(), at));
+            }
+        }
+        due
+    }
+
+    /// 下次运行时间（任务未启用或还没有被检查过时为 None）
+    pub fn next_due(&self, job_id: &str) -> Option<DateTime<Local>> {
+        self.next_due
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(job_id)
+            .map(|(_, at)| *at)
+    }
+
+    /// 标记任务开始运行（任务已经在运行时返回 false）
+    pub fn begin_run(&self, job_id: &str) -> bool {
+        self.running
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(job_id.to_string())
+    }
+
+    /// 标记任务运行结束并记录结果
+    pub fn finish_run(&self, result: ScheduledRunResult) {
+        self.running
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&result.job_id);
+        self.last_runs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(result.job_id.clone(), result);
+    }
+
+    /// 任务是否正在运行
+    pub fn is_running(&self, job_id: &str) -> bool {
+        self.running
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(job_id)
+    }
+
+    /// 上次运行结果
+    pub fn last_run(&self, job_id: &str) -> Option<ScheduledRunResult> {
+        self.last_runs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(job_id)
+            .cloned()
+    }
+
+    /// 删除任务后清除它的调度状态
+    pub fn forget(&self, job_id: &str) {
+        self.next_due
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(job_id);
+        self.last_runs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(job_id);
+    }
+}
+
+/// 计算 after 之后的下一次运行时间（本地时间）
+///
+/// 每天固定时间的任务遇到夏令时跳过的时间时，顺延到下一天
+pub fn next_run(schedule: &JobSchedule, after: DateTime<Local>) -> Option<DateTime<Local>> {
+    match schedule {
+        JobSchedule::Daily { time } => {
+            let time = parse_daily_time(time).ok()?;
+            (0..=2).find_map(|offset| {
+                let date = after.date_naive() + Duration::days(offset);
+                Local
+                    .from_local_datetime(&date.and_time(time))
+                    .earliest()
+                    .filter(|at| *at > after)
+            })
+        }
+        JobSchedule::Interval { minutes } => {
+            let interval = Duration::try_minutes(i64::try_from(*minutes).ok()?)?;
+            after.checked_add_signed(interval)
+        }
+    }
+}
+
+fn parse_daily_time(time: &str) -> Result<NaiveTime> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M")
+        .map_err(|_| DataAnalystError::InvalidOperation(format!("运行时间 '{}' 格式错误，应为 HH:MM", time)))
+}
+
+// ============================================================================
+// 校验
+// ============================================================================
+/// 检查计划任务是否有效：名称、路径、运行时间、操作记录中的步骤都能重新执行、参数声明有效
+pub fn validate_job(job: &ScheduledJob) -> Result<()> {
+    if job.name.trim().is_empty() {
+        return Err(DataAnalystError::InvalidOperation("任务名称不能为空".to_string()));
+    }
+    if job.source_path.trim().is_empty() {
+        return Err(DataAnalystError::InvalidOperation("源文件路径不能为空".to_string()));
+    }
+    if job.output_path.trim().is_empty() {
+        return Err(DataAnalystError::InvalidOperation("导出路径不能为空".to_string()));
+    }
+
+    match &job.schedule {
+        JobSchedule::Daily { time } => {
+            parse_daily_time(time)?;
+        }
+        JobSchedule::Interval { minutes } => {
+            if *minutes == 0 {
+                return Err(DataAnalystError::InvalidOperation("运行间隔至少为 1 分钟".to_string()));
+            }
+            if *minutes > MAX_INTERVAL_MINUTES {
+                return Err(DataAnalystError::InvalidOperation(format!(
+                    "运行间隔不能超过 {} 分钟（366 天）",
+                    MAX_INTERVAL_MINUTES
+                )));
+            }
+        }
+    }
+
+    let unsupported = unsupported_recipe_steps(&job.recipe);
+    if !unsupported.is_empty() {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "计划任务不支持以下步骤: {}（只支持{}）",
+            unsupported.join("；"),
+            REPLAYABLE_OPERATIONS
+        )));
+    }
+    if !job.recipe.is_empty() {
+        validate_recipe_parameters(&job.recipe, &job.parameters)?;
+    } else if !job.parameters.is_empty() {
+        return Err(DataAnalystError::InvalidOperation(
+            "没有操作记录，不能声明参数".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// 任务存储
+// ============================================================================
+/// 读取计划任务（文件不存在时返回空列表）
+pub fn load_schedules(path: &Path) -> Result<Vec<ScheduledJob>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| DataAnalystError::InvalidDataFormat(format!("计划任务文件格式错误: {}", e)))
+}
+
+/// 保存计划任务（先写临时文件再替换）
+pub fn save_schedules(path: &Path, jobs: &[ScheduledJob]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let content = serde_json::to_string_pretty(jobs)?;
+    write_atomic(&path.to_string_lossy(), |file| {
+        file.write_all(content.as_bytes())?;
+        Ok(())
+    })
+}
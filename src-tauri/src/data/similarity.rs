@@ -0,0 +1,137 @@
+// ============================================================================
+// data/similarity.rs - 字符串相似度与近似重复值聚类
+// ============================================================================
+// 这个文件负责：
+// 1. 字符串规范化（忽略大小写、标点和多余空白）
+// 2. 归一化 Levenshtein 相似度计算
+// 3. 将近似重复的值聚类（例如 "Acme Inc" 和 "ACME, Inc."）
+
+use crate::models::{SimilarCluster, SimilarValue};
+
+// ============================================================================
+// 字符串规范化
+// ============================================================================
+/// 规范化字符串用于比较
+///
+/// - 转为小写
+/// - 标点符号视为空白
+/// - 合并连续空白并去除首尾空白
+///
+/// 例如："ACME, Inc." → "acme inc"
+pub fn normalize_for_compare(value: &str) -> String {
+    value
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// ============================================================================
+// 相似度计算
+// ============================================================================
+/// 计算两个字符串的 Levenshtein 编辑距离（按字符计算，支持中文）
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.is_empty() {
+        return b.len();
+    }
+    if b.is_empty() {
+        return a.len();
+    }
+
+    // 只保留上一行，空间复杂度 O(len(b))
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+/// 归一化相似度：1 - 编辑距离 / 较长字符串长度
+///
+/// 返回 0.0 ~ 1.0，1.0 表示完全相同
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let max_len = a.chars().count().max(b.chars().count());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / max_len as f64
+}
+
+// ============================================================================
+// 近似重复值聚类
+// ============================================================================
+/// 将近似重复的值聚类
+///
+/// 算法（贪心）：
+/// 1. 按出现次数降序排列所有唯一值
+/// 2. 依次与已有簇的代表值（簇中出现次数最多的值）比较规范化后的相似度
+/// 3. 相似度 >= threshold 则加入该簇，否则新建一个簇
+///
+/// 只返回包含两个及以上不同值的簇，按总出现次数降序排列
+///
+/// 参数：
+/// - value_counts: (值, 出现次数) 列表
+/// - threshold: 相似度阈值（0.0 ~ 1.0）
+pub fn cluster_similar_values(mut value_counts: Vec<(String, usize)>, threshold: f64) -> Vec<SimilarCluster> {
+    value_counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    // (代表值的规范化形式, 簇)
+    let mut clusters: Vec<(String, SimilarCluster)> = Vec::new();
+
+    for (value, count) in value_counts {
+        let normalized = normalize_for_compare(&value);
+
+        let best = clusters
+            .iter()
+            .enumerate()
+            .map(|(idx, (key, _))| (idx, similarity(key, &normalized)))
+            .filter(|(_, score)| *score >= threshold)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        match best {
+            Some((idx, score)) => {
+                let cluster = &mut clusters[idx].1;
+                cluster.total_count += count;
+                cluster.members.push(SimilarValue {
+                    value,
+                    count,
+                    similarity: score,
+                });
+            }
+            None => clusters.push((
+                normalized,
+                SimilarCluster {
+                    canonical: value.clone(),
+                    total_count: count,
+                    members: vec![SimilarValue {
+                        value,
+                        count,
+                        similarity: 1.0,
+                    }],
+                },
+            )),
+        }
+    }
+
+    let mut result: Vec<SimilarCluster> = clusters
+        .into_iter()
+        .map(|(_, cluster)| cluster)
+        .filter(|cluster| cluster.members.len() > 1)
+        .collect();
+    result.sort_by_key(|cluster| std::cmp::Reverse(cluster.total_count));
+    result
+}
@@ -0,0 +1,138 @@
+// ============================================================================
+// data/snapshot.rs - 快照分享包读写
+// ============================================================================
+// 这个文件负责快照分享包（.zip）的读写：
+// 1. 写出：当前数据（Parquet）+ 操作历史、参数声明、描述统计、图表配置、显示设置（JSON）
+// 2. 读取：检查格式版本，还原数据和各部分内容
+//
+// 快照包中各文件的说明见 models/snapshot.rs
+
+use crate::error::{DataAnalystError, Result};
+use crate::models::chart::ChartConfig;
+use crate::models::{ColumnStats, DisplaySettings, HistoryEntryInfo, RecipeParameter, SnapshotManifest};
+use ::zip::write::SimpleFileOptions;
+use ::zip::{CompressionMethod, ZipArchive, ZipWriter};
+use polars::prelude::*;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::io::{Cursor, Read, Seek, Write};
+
+/// 当前快照包格式版本
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// 快照包中的文件名
+pub const MANIFEST_ENTRY: &str = "manifest.json";
+pub const DATA_ENTRY: &str = "data.parquet";
+pub const RECIPE_ENTRY: &str = "recipe.json";
+pub const PARAMETERS_ENTRY: &str = "parameters.json";
+pub const PROFILE_ENTRY: &str = "profile.json";
+pub const CHARTS_ENTRY: &str = "charts.json";
+pub const DISPLAY_ENTRY: &str = "display.json";
+
+fn snapshot_error(message: impl std::fmt::Display) -> DataAnalystError {
+    DataAnalystError::InvalidOperation(format!("快照包读写失败: {}", message))
+}
+
+/// SnapshotParts - 快照包中除数据以外的各部分
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotParts {
+    pub recipe: Vec<HistoryEntryInfo>,
+    pub parameters: Vec<RecipeParameter>,
+    pub profile: Vec<ColumnStats>,
+    pub charts: Vec<ChartConfig>,
+    pub display: DisplaySettings,
+}
+
+// ============================================================================
+// 写出快照包
+// ============================================================================
+/// 将数据和各部分内容写入快照包
+///
+/// 数据以 Parquet 保存（类型完整保留），其余部分为格式化的 JSON
+pub fn write_snapshot_bundle<W: Write + Seek>(
+    file: W,
+    manifest: &SnapshotManifest,
+    df: &DataFrame,
+    parts: &SnapshotParts,
+) -> Result<()> {
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true);
+    let mut zip = ZipWriter::new(file);
+
+    write_json_entry(&mut zip, MANIFEST_ENTRY, manifest, options)?;
+
+    // Parquet 已经压缩，不再重复压缩
+    zip.start_file(DATA_ENTRY, options.compression_method(CompressionMethod::Stored))
+        .map_err(snapshot_error)?;
+    ParquetWriter::new(&mut zip).finish(&mut df.clone())?;
+
+    write_json_entry(&mut zip, RECIPE_ENTRY, &parts.recipe, options)?;
+    write_json_entry(&mut zip, PARAMETERS_ENTRY, &parts.parameters, options)?;
+    write_json_entry(&mut zip, PROFILE_ENTRY, &parts.profile, options)?;
+    write_json_entry(&mut zip, CHARTS_ENTRY, &parts.charts, options)?;
+    write_json_entry(&mut zip, DISPLAY_ENTRY, &parts.display, options)?;
+
+    zip.finish().map_err(snapshot_error)?;
+    Ok(())
+}
+
+fn write_json_entry<W: Write + Seek, T: Serialize + ?Sized>(
+    zip: &mut ZipWriter<W>,
+    name: &str,
+    value: &T,
+    options: SimpleFileOptions,
+) -> Result<()> {
+    zip.start_file(name, options).map_err(snapshot_error)?;
+    serde_json::to_writer_pretty(&mut *zip, value)?;
+    Ok(())
+}
+
+// ============================================================================
+// 读取快照包
+// ============================================================================
+/// 读取快照包，返回概要、数据和各部分内容
+///
+/// manifest.json 和 data.parquet 必须存在；其余部分缺失时使用空值
+/// 格式版本高于当前版本（由更新的应用创建）时报错
+pub fn read_snapshot_bundle(file_path: &str) -> Result<(SnapshotManifest, DataFrame, SnapshotParts)> {
+    let file = std::fs::File::open(file_path)?;
+    let mut archive = ZipArchive::new(file).map_err(|e| snapshot_error(format!("不是有效的快照包: {}", e)))?;
+
+    let manifest: SnapshotManifest = read_json_entry(&mut archive, MANIFEST_ENTRY)?
+        .ok_or_else(|| snapshot_error(format!("缺少 {}，不是快照包", MANIFEST_ENTRY)))?;
+    if manifest.format_version > SNAPSHOT_FORMAT_VERSION {
+        return Err(snapshot_error(format!(
+            "快照包格式版本 {} 高于当前支持的版本 {}，请升级应用后再导入",
+            manifest.format_version, SNAPSHOT_FORMAT_VERSION
+        )));
+    }
+
+    let mut bytes = Vec::new();
+    archive
+        .by_name(DATA_ENTRY)
+        .map_err(|_| snapshot_error(format!("缺少 {}", DATA_ENTRY)))?
+        .read_to_end(&mut bytes)?;
+    let df = ParquetReader::new(Cursor::new(bytes)).finish()?;
+
+    let parts = SnapshotParts {
+        recipe: read_json_entry(&mut archive, RECIPE_ENTRY)?.unwrap_or_default(),
+        parameters: read_json_entry(&mut archive, PARAMETERS_ENTRY)?.unwrap_or_default(),
+        profile: read_json_entry(&mut archive, PROFILE_ENTRY)?.unwrap_or_default(),
+        charts: read_json_entry(&mut archive, CHARTS_ENTRY)?.unwrap_or_default(),
+        display: read_json_entry(&mut archive, DISPLAY_ENTRY)?.unwrap_or_default(),
+    };
+
+    Ok((manifest, df, parts))
+}
+
+/// 读取 JSON 条目（条目不存在时返回 None）
+fn read_json_entry<R: Read + Seek, T: DeserializeOwned>(archive: &mut ZipArchive<R>, name: &str) -> Result<Option<T>> {
+    let entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(::zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(snapshot_error(e)),
+    };
+    let value = serde_json::from_reader(entry).map_err(|e| snapshot_error(format!("{} 格式错误: {}", name, e)))?;
+    Ok(Some(value))
+}
@@ -0,0 +1,212 @@
+// ============================================================================
+// data/sqlite.rs - 导出到 SQLite
+// ============================================================================
+// 这个文件负责把 DataFrame 写入 SQLite 数据库中的一张表：
+// 1. 数据库文件不存在时自动创建，库中的其他表保持不变
+// 2. 表已存在时按 SqliteIfExists 报错、替换或追加
+// 3. 所有写入在一个事务中完成，失败或取消时回滚，数据库保持原样
+//
+// 类型对应：
+// - 整数 → INTEGER；浮点数、Decimal → REAL；布尔 → INTEGER（0 / 1）
+// - 日期、日期时间 → TEXT（ISO 格式，SQLite 的日期函数可以直接使用）
+// - 列表、结构体 → TEXT（JSON）；其他类型 → TEXT
+
+use crate::data::TaskHandle;
+use crate::data::export::EXPORT_CHUNK_ROWS;
+use crate::data::nested::nested_columns_to_json;
+use crate::error::{DataAnalystError, Result};
+use crate::models::SqliteIfExists;
+use polars::prelude::*;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::{ConnectOptions, Connection};
+
+fn sqlite_error(message: impl std::fmt::Display) -> DataAnalystError {
+    DataAnalystError::InvalidOperation(format!("写入 SQLite 失败: {}", message))
+}
+
+/// 列在 SQLite 中的存储类型
+#[derive(Clone, Copy)]
+enum SqliteColumnKind {
+    Integer,
+    Real,
+    Boolean,
+    Text,
+}
+
+impl SqliteColumnKind {
+    fn declared_type(self) -> &'static str {
+        match self {
+            SqliteColumnKind::Integer | SqliteColumnKind::Boolean => "INTEGER",
+            SqliteColumnKind::Real => "REAL",
+            SqliteColumnKind::Text => "TEXT",
+        }
+    }
+}
+
+/// 将 DataFrame 写入 SQLite 数据库中的表
+///
+/// 参数：
+/// - path: 数据库文件路径（不存在时创建）
+/// - table: 表名
+/// - df: 要写入的数据
+/// - if_exists: 表已存在时的处理方式
+/// - task / on_progress: 每批之后更新已写行数；已请求取消时回滚并返回 Cancelled 错误
+pub async fn write_sqlite_table(
+    path: &str,
+    table: &str,
+    df: &DataFrame,
+    if_exists: SqliteIfExists,
+    task: &TaskHandle,
+    on_progress: impl Fn(&TaskHandle),
+) -> Result<()> {
+    validate_table_name(table)?;
+    let (prepared, kinds) = prepare_columns(df)?;
+
+    let mut conn: SqliteConnection = SqliteConnectOptions::new()
+        .filename(path)
+        .create_if_missing(true)
+        .connect()
+        .await
+        .map_err(|e| sqlite_error(format!("无法打开数据库 {}: {}", path, e)))?;
+
+    let mut tx = conn.begin().await.map_err(sqlite_error)?;
+    let quoted_table = quote_identifier(table);
+
+    let exists: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?")
+        .bind(table)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(sqlite_error)?;
+    let create = match (exists > 0, if_exists) {
+        (false, _) => true,
+        (true, SqliteIfExists::Fail) => {
+            return Err(DataAnalystError::InvalidOperation(format!(
+                "表 '{}' 已存在，请选择替换（replace）或追加（append）",
+                table
+            )));
+        }
+        (true, SqliteIfExists::Replace) => {
+            sqlx::query(&format!("DROP TABLE {}", quoted_table))
+                .execute(&mut *tx)
+                .await
+                .map_err(sqlite_error)?;
+            true
+        }
+        (true, SqliteIfExists::Append) => {
+            check_append_columns(&mut tx, table, df).await?;
+            false
+        }
+    };
+
+    if create {
+        let columns: Vec<String> = prepared
+            .get_columns()
+            .iter()
+            .zip(&kinds)
+            .map(|(column, kind)| format!("{} {}", quote_identifier(column.name()), kind.declared_type()))
+            .collect();
+        sqlx::query(&format!("CREATE TABLE {} ({})", quoted_table, columns.join(", ")))
+            .execute(&mut *tx)
+            .await
+            .map_err(sqlite_error)?;
+    }
+
+    let placeholders = vec!["?"; prepared.width()].join(", ");
+    let insert_sql = format!("INSERT INTO {} VALUES ({})", quoted_table, placeholders);
+
+    let mut written = 0;
+    while written < prepared.height() {
+        task.check_cancelled()?;
+
+        let chunk = prepared.slice(written as i64, EXPORT_CHUNK_ROWS);
+        let columns = chunk.get_columns();
+        for row in 0..chunk.height() {
+            let mut query = sqlx::query(&insert_sql);
+            for (column, kind) in columns.iter().zip(&kinds) {
+                query = match kind {
+                    SqliteColumnKind::Integer => query.bind(column.i64()?.get(row)),
+                    SqliteColumnKind::Real => query.bind(column.f64()?.get(row)),
+                    SqliteColumnKind::Boolean => query.bind(column.bool()?.get(row)),
+                    SqliteColumnKind::Text => query.bind(column.str()?.get(row)),
+                };
+            }
+            query.execute(&mut *tx).await.map_err(sqlite_error)?;
+        }
+
+        written += chunk.height();
+        task.set_processed(written);
+        on_progress(task);
+    }
+
+    tx.commit().await.map_err(sqlite_error)?;
+    conn.close().await.map_err(sqlite_error)?;
+    Ok(())
+}
+
+/// 把每列转换为 SQLite 能直接绑定的类型（Int64 / Float64 / Boolean / String）
+fn prepare_columns(df: &DataFrame) -> Result<(DataFrame, Vec<SqliteColumnKind>)> {
+    // 列表和结构体先转换为 JSON 文本
+    let df = nested_columns_to_json(df)?;
+    let mut columns = Vec::with_capacity(df.width());
+    let mut kinds = Vec::with_capacity(df.width());
+
+    for column in df.get_columns() {
+        let series = column.as_materialized_series();
+        let (converted, kind) = match series.dtype() {
+            DataType::Boolean => (series.clone(), SqliteColumnKind::Boolean),
+            dtype if dtype.is_integer() => {
+                let converted = series.strict_cast(&DataType::Int64).map_err(|_| {
+                    DataAnalystError::InvalidOperation(format!("列 '{}' 的值超出 SQLite 整数范围", column.name()))
+                })?;
+                (converted, SqliteColumnKind::Integer)
+            }
+            dtype if dtype.is_float() || dtype.is_decimal() => {
+                (series.cast(&DataType::Float64)?, SqliteColumnKind::Real)
+            }
+            _ => (series.cast(&DataType::String)?, SqliteColumnKind::Text),
+        };
+        columns.push(converted.into());
+        kinds.push(kind);
+    }
+
+    Ok((DataFrame::new(columns)?, kinds))
+}
+
+/// 追加时检查原表的列名（按顺序）是否与当前数据一致
+async fn check_append_columns(conn: &mut SqliteConnection, table: &str, df: &DataFrame) -> Result<()> {
+    let existing: Vec<String> = sqlx::query_scalar("SELECT name FROM pragma_table_info(?) ORDER BY cid")
+        .bind(table)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(sqlite_error)?;
+    let current: Vec<String> = df.get_column_names().iter().map(|name| name.to_string()).collect();
+
+    if existing != current {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "表 '{}' 的列（{}）与当前数据的列（{}）不一致，无法追加",
+            table,
+            existing.join(", "),
+            current.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// 检查表名（不能为空，不能使用 SQLite 保留的 sqlite_ 前缀）
+fn validate_table_name(table: &str) -> Result<()> {
+    if table.trim().is_empty() {
+        return Err(DataAnalystError::InvalidOperation("表名不能为空".to_string()));
+    }
+    if table.to_ascii_lowercase().starts_with("sqlite_") {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "表名 '{}' 以 sqlite_ 开头，这是 SQLite 保留的前缀",
+            table
+        )));
+    }
+    Ok(())
+}
+
+/// 用双引号包裹标识符（内部的双引号写两次）
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
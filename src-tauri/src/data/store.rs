@@ -4,10 +4,12 @@
 // 这个文件实现了基于操作历史的数据存储管理
 // 使用历史栈来存储每次操作后的完整状态，支持 undo/redo 功能
 
+use crate::data::OutlierMomentsCache;
 use crate::error::DataAnalystError;
-use crate::models::{DatasetInfo, HistoryEntry, HistoryEntryInfo};
+use crate::models::{DatasetInfo, DisplaySettings, ExportRecord, HistoryEntry, HistoryEntryInfo};
 use polars::prelude::*;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LockResult, Mutex, MutexGuard};
 
 // ============================================================================
 // 数据存储结构体
@@ -42,11 +44,25 @@ pub struct DataStore {
     /// - history.len() = 3, current_index = Some(0): 在最早状态
     current_index: Option<usize>,
 
+    /// 条件格式离群值规则的列统计量缓存（当前历史条目）
+    outlier_moments: OutlierMomentsCache,
+
     /// 历史栈最大深度
     ///
     /// 限制历史记录数量，防止内存溢出
     /// 默认值：50
     max_history: usize,
+
+    /// 只读锁定
+    ///
+    /// 锁定后，所有修改数据或历史的操作（新增操作、undo/redo、跳转、重置、清空）
+    /// 都会返回 DataAnalystError::DatasetLocked
+    locked: bool,
+
+    /// 本次会话中成功的导出记录（按时间顺序）
+    ///
+    /// 不属于历史：清空、导入新数据、撤销都不会影响已有的记录
+    exports: Vec<ExportRecord>,
 }
 
 // ============================================================================
@@ -60,7 +76,10 @@ impl DataStore {
         Self {
             history: Vec::new(),
             current_index: None,
+            outlier_moments: OutlierMomentsCache::default(),
             max_history: 50,
+            locked: false,
+            exports: Vec::new(),
         }
     }
 
@@ -69,7 +88,10 @@ impl DataStore {
         Self {
             history: Vec::new(),
             current_index: None,
+            outlier_moments: OutlierMomentsCache::default(),
             max_history,
+            locked: false,
+            exports: Vec::new(),
         }
     }
 
@@ -83,7 +105,19 @@ impl DataStore {
     ///
     /// 参数：
     /// - entry: 新的历史条目
-    pub fn push_operation(&mut self, entry: HistoryEntry) {
+    ///
+    /// 返回：
+    /// - Err(DatasetLocked): 数据集已锁定
+    pub fn push_operation(&mut self, mut entry: HistoryEntry) -> Result<(), DataAnalystError> {
+        self.ensure_unlocked()?;
+
+        // 数据操作继承当前状态的显示设置（只修改显示设置的操作自带完整设置）
+        if !entry.operation.is_display_only()
+            && let Some(current) = self.get_current_entry()
+        {
+            entry.display = current.display.carry_over(&entry.dataframe);
+        }
+
         // 线性历史：如果当前不在最新状态，丢弃后续历史
         if let Some(index) = self.current_index {
             // 保留 0..=index 的历史，丢弃 index+1.. 的历史
@@ -105,6 +139,8 @@ impl DataStore {
             // 更新 current_index 指向新状态
             self.current_index = Some(self.history.len() - 1);
         }
+
+        Ok(())
     }
 
     /// 获取当前 DataFrame
@@ -131,6 +167,17 @@ impl DataStore {
             .map(|entry| &entry.metadata)
     }
 
+    /// 获取当前显示设置
+    ///
+    /// 返回：
+    /// - Some(&DisplaySettings): 当前显示设置
+    /// - None: 没有数据
+    pub fn get_current_display(&self) -> Option<&DisplaySettings> {
+        self.current_index
+            .and_then(|index| self.history.get(index))
+            .map(|entry| &entry.display)
+    }
+
     /// 获取当前历史条目
     ///
     /// 返回：
@@ -140,6 +187,11 @@ impl DataStore {
         self.current_index.and_then(|index| self.history.get(index))
     }
 
+    /// 获取条件格式离群值规则的列统计量缓存
+    pub fn outlier_moments(&self) -> &OutlierMomentsCache {
+        &self.outlier_moments
+    }
+
     /// 撤销操作（Undo）
     ///
     /// 回退到上一个状态
@@ -148,6 +200,8 @@ impl DataStore {
     /// - Ok(()): 成功撤销
     /// - Err: 无法撤销（已经在最早状态或没有数据）
     pub fn undo(&mut self) -> Result<(), DataAnalystError> {
+        self.ensure_unlocked()?;
+
         match self.current_index {
             Some(index) if index > 0 => {
                 // 回退到上一个状态
@@ -175,6 +229,8 @@ impl DataStore {
     /// - Ok(()): 成功重做
     /// - Err: 无法重做（已经在最新状态或没有数据）
     pub fn redo(&mut self) -> Result<(), DataAnalystError> {
+        self.ensure_unlocked()?;
+
         match self.current_index {
             Some(index) if index < self.history.len() - 1 => {
                 // 前进到下一个状态
@@ -203,6 +259,8 @@ impl DataStore {
     /// - Ok(()): 成功跳转
     /// - Err: 找不到指定的历史节点
     pub fn jump_to(&mut self, entry_id: &str) -> Result<(), DataAnalystError> {
+        self.ensure_unlocked()?;
+
         // 查找指定 ID 的历史条目
         let index = self
             .history
@@ -215,6 +273,20 @@ impl DataStore {
         Ok(())
     }
 
+    /// 按 ID 获取历史条目
+    ///
+    /// 返回：
+    /// - Some(&HistoryEntry): 找到的历史条目
+    /// - None: 找不到指定的历史节点
+    pub fn get_entry(&self, entry_id: &str) -> Option<&HistoryEntry> {
+        self.history.iter().find(|entry| entry.id == entry_id)
+    }
+
+    /// 获取全部历史条目（按时间顺序，包含可以重做的条目）
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.history
+    }
+
     /// 获取操作历史列表
     ///
     /// 返回所有历史条目的元信息（不包含 DataFrame）
@@ -260,9 +332,30 @@ impl DataStore {
     /// 清空所有数据和历史
     ///
     /// 删除所有历史条目，重置状态
-    pub fn clear(&mut self) {
+    ///
+    /// 返回：
+    /// - Err(DatasetLocked): 数据集已锁定
+    pub fn clear(&mut self) -> Result<(), DataAnalystError> {
+        self.ensure_unlocked()?;
+
         self.history.clear();
         self.current_index = None;
+        Ok(())
+    }
+
+    /// 用导入的新条目替换全部历史
+    ///
+    /// 调用方在锁外完整构建好新条目后再调用，检查和替换在同一次调用中完成：
+    /// 失败时（数据集已锁定）当前数据和历史保持不变，不会留下空的数据集
+    ///
+    /// 返回：
+    /// - Err(DatasetLocked): 数据集已锁定
+    pub fn replace_with_import(&mut self, entry: HistoryEntry) -> Result<(), DataAnalystError> {
+        self.ensure_unlocked()?;
+
+        self.history = vec![entry];
+        self.current_index = Some(0);
+        Ok(())
     }
 
     /// 重置到初始状态
@@ -270,6 +363,8 @@ impl DataStore {
     /// 截断历史栈，只保留第一个节点（刚导入时的状态）
     /// 这会删除所有后续操作历史
     pub fn reset_to_initial(&mut self) -> Result<(), DataAnalystError> {
+        self.ensure_unlocked()?;
+
         if self.history.is_empty() {
             return Err(DataAnalystError::InvalidOperation("没有历史记录".to_string()));
         }
@@ -280,6 +375,31 @@ impl DataStore {
         Ok(())
     }
 
+    /// 更新指定历史条目的元信息（例如补充列摘要）
+    ///
+    /// 只是缓存计算结果，不修改数据和历史，锁定时也可以更新
+    ///
+    /// 返回：条目不存在（已被丢弃）时返回 false
+    pub fn set_entry_metadata(&mut self, entry_id: &str, metadata: DatasetInfo) -> bool {
+        match self.history.iter_mut().find(|entry| entry.id == entry_id) {
+            Some(entry) => {
+                entry.metadata = metadata;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 记录一次成功的导出
+    pub fn record_export(&mut self, record: ExportRecord) {
+        self.exports.push(record);
+    }
+
+    /// 本次会话中的全部导出记录（按时间顺序）
+    pub fn export_log(&self) -> &[ExportRecord] {
+        &self.exports
+    }
+
     /// 清理历史（保留最近的 N 条）
     ///
     /// 参数：
@@ -299,6 +419,59 @@ impl DataStore {
             }
         }
     }
+
+    /// 锁定数据集（只读）
+    ///
+    /// 返回：
+    /// - Err: 没有数据
+    pub fn lock(&mut self) -> Result<(), DataAnalystError> {
+        if self.current_index.is_none() {
+            return Err(DataAnalystError::InvalidOperation("没有数据".to_string()));
+        }
+        self.locked = true;
+        Ok(())
+    }
+
+    /// 解除只读锁定
+    pub fn unlock(&mut self) {
+        self.locked = false;
+    }
+
+    /// 是否处于只读锁定状态
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// 确认数据集未锁定，锁定时返回 DatasetLocked 错误
+    pub fn ensure_unlocked(&self) -> Result<(), DataAnalystError> {
+        if self.locked {
+            Err(DataAnalystError::DatasetLocked)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// 修复历史索引（命令在修改历史的过程中 panic 后使用）
+    ///
+    /// - 历史为空时索引重置为 None
+    /// - 索引超出历史范围时指向最新状态
+    ///
+    /// 返回：是否做了修复
+    pub fn repair(&mut self) -> bool {
+        let repaired = match (self.current_index, self.history.len()) {
+            (None, 0) => None,
+            (Some(_), 0) => Some(None),
+            (Some(index), len) if index < len => None,
+            (_, len) => Some(Some(len - 1)),
+        };
+        match repaired {
+            Some(index) => {
+                self.current_index = index;
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 // ============================================================================
@@ -310,16 +483,62 @@ impl Default for DataStore {
     }
 }
 
+// ============================================================================
+// 数据存储互斥锁
+// ============================================================================
+/// StoreMutex - 数据存储的互斥锁，可以从中毒状态恢复
+///
+/// 标准库的 Mutex 在持有锁的线程 panic 后会"中毒"，之后每次加锁都返回错误，
+/// 一次失败的操作就会让所有命令都无法使用，只能重启应用。
+/// StoreMutex 加锁时发现中毒会清除中毒标记、修复历史索引（DataStore::repair）后继续使用
+pub struct StoreMutex {
+    inner: Mutex<DataStore>,
+
+    /// 从中毒状态恢复的次数
+    recoveries: AtomicUsize,
+}
+
+impl StoreMutex {
+    /// 用数据存储创建互斥锁
+    pub fn new(store: DataStore) -> Self {
+        Self {
+            inner: Mutex::new(store),
+            recoveries: AtomicUsize::new(0),
+        }
+    }
+
+    /// 加锁
+    ///
+    /// 返回值与 Mutex::lock 相同，但中毒时会自动恢复，因此总是返回 Ok
+    pub fn lock(&self) -> LockResult<MutexGuard<'_, DataStore>> {
+        match self.inner.lock() {
+            Ok(guard) => Ok(guard),
+            Err(poisoned) => {
+                self.inner.clear_poison();
+                self.recoveries.fetch_add(1, Ordering::Relaxed);
+                let mut guard = poisoned.into_inner();
+                guard.repair();
+                Ok(guard)
+            }
+        }
+    }
+
+    /// 从中毒状态恢复的次数
+    pub fn recovery_count(&self) -> usize {
+        self.recoveries.load(Ordering::Relaxed)
+    }
+}
+
 // ============================================================================
 // 类型别名
 // ============================================================================
 /// SharedDataStore - 共享的数据存储类型
 ///
-/// Arc<Mutex<DataStore>> 的含义：
+/// Arc<StoreMutex> 的含义：
 /// - DataStore: 我们的数据存储结构体
-/// - Mutex<DataStore>: 互斥锁包装，确保同一时间只有一个线程可以修改
-/// - Arc<Mutex<DataStore>>: 原子引用计数，允许多个所有者共享数据
-pub type SharedDataStore = Arc<Mutex<DataStore>>;
+/// - StoreMutex: 互斥锁包装，确保同一时间只有一个线程可以修改，中毒后自动恢复
+/// - Arc<StoreMutex>: 原子引用计数，允许多个所有者共享数据
+pub type SharedDataStore = Arc<StoreMutex>;
 
 // ============================================================================
 // 使用说明
@@ -339,8 +558,11 @@ pub type SharedDataStore = Arc<Mutex<DataStore>>;
 //        metadata: info,
 //        timestamp: chrono::Utc::now().to_rfc3339(),
 //        description: "导入文件".to_string(),
+//        display: DisplaySettings::default(),
+//        removed_rows: None,
+//        renamed_columns: Vec::new(),
 //    };
-//    store.push_operation(entry);
+//    store.push_operation(entry)?;
 //    ```
 //
 // 3. 获取当前数据：
@@ -0,0 +1,175 @@
+// ============================================================================
+// data/tasks.rs - 后台任务注册表
+// ============================================================================
+// 这个文件管理长时间运行的任务（例如大文件导出）：
+// - 每个任务有一个 TaskHandle，工作线程通过它更新进度、检查是否被取消
+// - TaskRegistry 保存所有任务，供命令查询进度和请求取消
+// - 取消是协作式的：工作线程在每个批次之间检查取消标记
+
+use crate::error::{DataAnalystError, Result};
+use crate::models::{TaskInfo, TaskStatus};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// 注册表中最多保留的已结束任务数量（超出时丢弃最早的）
+const MAX_FINISHED_TASKS: usize = 50;
+
+/// SharedTaskRegistry - 在命令之间共享的任务注册表
+pub type SharedTaskRegistry = Arc<TaskRegistry>;
+
+// ============================================================================
+// 任务句柄
+// ============================================================================
+/// TaskHandle - 单个后台任务
+///
+/// 进度和取消标记使用原子类型，工作线程更新进度时不需要加锁
+pub struct TaskHandle {
+    id: String,
+    kind: String,
+    total_rows: Option<usize>,
+    processed_rows: AtomicUsize,
+    cancelled: AtomicBool,
+    /// (状态, 错误信息)
+    state: Mutex<(TaskStatus, Option<String>)>,
+}
+
+impl TaskHandle {
+    /// 任务 ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// 是否已请求取消
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// 已请求取消时返回 Cancelled 错误（工作线程在批次之间调用）
+    pub fn check_cancelled(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(DataAnalystError::Cancelled);
+        }
+        Ok(())
+    }
+
+    /// 更新已处理的行数
+    pub fn set_processed(&self, rows: usize) {
+        self.processed_rows.store(rows, Ordering::Relaxed);
+    }
+
+    /// 按任务结果设置最终状态
+    ///
+    /// - Ok: 已完成
+    /// - Err 且已请求取消: 已取消
+    /// - 其他 Err: 失败，记录错误信息
+    pub fn finish<T, E: std::fmt::Display>(&self, result: &std::result::Result<T, E>) {
+        let next = match result {
+            Ok(_) => (TaskStatus::Completed, None),
+            Err(_) if self.is_cancelled() => (TaskStatus::Cancelled, None),
+            Err(e) => (TaskStatus::Failed, Some(e.to_string())),
+        };
+        *self.state.lock().unwrap_or_else(PoisonError::into_inner) = next;
+    }
+
+    /// 当前状态
+    pub fn status(&self) -> TaskStatus {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner).0
+    }
+
+    /// 生成进度快照
+    pub fn info(&self) -> TaskInfo {
+        let (status, message) = self.state.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        TaskInfo {
+            id: self.id.clone(),
+            kind: self.kind.clone(),
+            status,
+            processed_rows: self.processed_rows.load(Ordering::Relaxed),
+            total_rows: self.total_rows,
+            message,
+        }
+    }
+}
+
+// ============================================================================
+// 任务注册表
+// ============================================================================
+/// TaskRegistry - 所有后台任务（按创建顺序保存）
+#[derive(Default)]
+pub struct TaskRegistry {
+    tasks: Mutex<Vec<Arc<TaskHandle>>>,
+}
+
+impl TaskRegistry {
+    /// 创建空的任务注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册一个新任务
+    ///
+    /// 参数：
+    /// - id: 任务 ID（前端传入以便在任务结束前取消；None 时自动生成 UUID）
+    /// - kind: 任务类型
+    /// - total_rows: 总行数（未知时为 None）
+    ///
+    /// 同一 ID 的任务仍在运行时返回错误
+    pub fn start(&self, id: Option<String>, kind: &str, total_rows: Option<usize>) -> Result<Arc<TaskHandle>> {
+        let id = id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let mut tasks = self.tasks.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if tasks.iter().any(|t| t.id == id && !t.status().is_finished()) {
+            return Err(DataAnalystError::InvalidOperation(format!("任务 '{}' 正在运行", id)));
+        }
+        tasks.retain(|t| t.id != id);
+
+        // 丢弃最早的已结束任务
+        let finished = tasks.iter().filter(|t| t.status().is_finished()).count();
+        let mut excess = (finished + 1).saturating_sub(MAX_FINISHED_TASKS);
+        tasks.retain(|t| {
+            if excess > 0 && t.status().is_finished() {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+
+        let handle = Arc::new(TaskHandle {
+            id,
+            kind: kind.to_string(),
+            total_rows,
+            processed_rows: AtomicUsize::new(0),
+            cancelled: AtomicBool::new(false),
+            state: Mutex::new((TaskStatus::Running, None)),
+        });
+        tasks.push(handle.clone());
+        Ok(handle)
+    }
+
+    /// 查询任务进度
+    pub fn get(&self, id: &str) -> Option<TaskInfo> {
+        let tasks = self.tasks.lock().unwrap_or_else(PoisonError::into_inner);
+        tasks.iter().find(|t| t.id == id).map(|t| t.info())
+    }
+
+    /// 列出所有任务（按创建顺序）
+    pub fn list(&self) -> Vec<TaskInfo> {
+        let tasks = self.tasks.lock().unwrap_or_else(PoisonError::into_inner);
+        tasks.iter().map(|t| t.info()).collect()
+    }
+
+    /// 请求取消任务
+    ///
+    /// 任务已结束时不做任何事；任务不存在时返回错误
+    pub fn cancel(&self, id: &str) -> Result<()> {
+        let tasks = self.tasks.lock().unwrap_or_else(PoisonError::into_inner);
+        let task = tasks
+            .iter()
+            .find(|t| t.id == id)
+            .ok_or_else(|| DataAnalystError::InvalidOperation(format!("任务 '{}' 不存在", id)))?;
+
+        if !task.status().is_finished() {
+            task.cancelled.store(true, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,508 @@
+// ============================================================================
+// data/text.rs - 文本清洗工具
+// ============================================================================
+// 这个文件提供常见语义类型的字符串规范化函数：
+// - 空白：去除首尾空白、合并连续空白、清除零宽字符和不换行空格
+// - 邮箱：去除首尾空白并转为小写
+// - 电话：规范化为 E.164 格式（+8613800138000）
+// - URL：拆分出域名和路径
+// - 金额：去除货币符号和千分位，解析为数值
+// - 百分比："12.5%" 与 0.125 互相转换
+// - 布尔值：把 "Y/N"、"是/否" 等标记转换为真正的布尔值
+// - IP 地址：识别 v4 / v6
+// - User-Agent：识别浏览器和操作系统
+// 以及一个把逐值转换函数应用到整列的辅助函数
+
+use crate::error::{DataAnalystError, Result};
+use crate::models::UnmatchedValuesReport;
+use polars::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+// ============================================================================
+// 整列映射
+// ============================================================================
+/// 对字符串列逐值应用转换函数，生成新的字符串列
+///
+/// 非字符串列会先转换为字符串；空值保持为空；转换函数返回 None 时结果为空
+///
+/// 参数：
+/// - df: 数据集
+/// - column: 源列名
+/// - output_name: 新列名
+/// - f: 转换函数
+pub fn map_string_column<F>(df: &DataFrame, column: &str, output_name: &str, f: F) -> Result<Series>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let source = df
+        .column(column)
+        .map_err(|_| DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", column)))?
+        .cast(&DataType::String)?;
+
+    let mapped: StringChunked = source.str()?.into_iter().map(|value| value.and_then(&f)).collect();
+
+    Ok(mapped.with_name(output_name.into()).into_series())
+}
+
+/// 对字符串列逐值应用解析函数，生成新的 Float64 列
+///
+/// 非字符串列会先转换为字符串；空值保持为空；解析函数返回 None 时结果为空
+pub fn map_string_to_f64<F>(df: &DataFrame, column: &str, output_name: &str, f: F) -> Result<Series>
+where
+    F: Fn(&str) -> Option<f64>,
+{
+    let source = df
+        .column(column)
+        .map_err(|_| DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", column)))?
+        .cast(&DataType::String)?;
+
+    let mapped: Float64Chunked = source.str()?.into_iter().map(|value| value.and_then(&f)).collect();
+
+    Ok(mapped.with_name(output_name.into()).into_series())
+}
+
+// ============================================================================
+// 空白与不可见字符
+// ============================================================================
+/// 清理空白和不可见字符
+///
+/// - 删除零宽字符：U+200B ~ U+200D、U+2060、U+FEFF（BOM）、U+00AD（软连字符）
+/// - 不换行空格（U+00A0、U+2007、U+202F）、制表符、换行等所有 Unicode 空白视为普通空格
+/// - 合并连续空白为一个空格，并去除首尾空白
+///
+/// 例如："\u{FEFF} Acme\u{00A0}\u{00A0}Inc\u{200B} " → "Acme Inc"
+pub fn clean_whitespace_value(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !matches!(c, '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}'))
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// ============================================================================
+// 邮箱
+// ============================================================================
+/// 规范化邮箱：去除首尾空白、转为小写；空字符串视为空值
+pub fn normalize_email(value: &str) -> Option<String> {
+    let email = value.trim().to_lowercase();
+    if email.is_empty() { None } else { Some(email) }
+}
+
+// ============================================================================
+// 电话
+// ============================================================================
+/// 规范化电话号码为 E.164 格式
+///
+/// 规则：
+/// - 以 "+" 开头：视为已包含国家码
+/// - 以 "00" 开头：国际冠码，替换为 "+"
+/// - 其他：去掉开头的长途冠码 "0" 后加上默认国家码
+/// - E.164 要求总位数为 8 ~ 15 位，否则视为无效（返回 None）
+///
+/// 例如（默认国家码 86）：
+/// - "138 0013 8000" → "+8613800138000"
+/// - "+1 (555) 123-4567" → "+15551234567"
+pub fn normalize_phone_e164(value: &str, default_country_code: Option<&str>) -> Option<String> {
+    let trimmed = value.trim();
+    let digits: String = trimmed.chars().filter(|c| c.is_ascii_digit()).collect();
+    if digits.is_empty() {
+        return None;
+    }
+
+    let full = if trimmed.starts_with('+') {
+        digits
+    } else if let Some(rest) = digits.strip_prefix("00") {
+        rest.to_string()
+    } else {
+        let country_code = default_country_code?;
+        format!("{}{}", country_code, digits.trim_start_matches('0'))
+    };
+
+    if (8..=15).contains(&full.len()) {
+        Some(format!("+{}", full))
+    } else {
+        None
+    }
+}
+
+// ============================================================================
+// URL
+// ============================================================================
+/// 解析 URL，返回 (域名, 路径)
+///
+/// - 域名转为小写，去掉用户信息和端口
+/// - 路径不包含查询参数和锚点；没有路径时为 "/"
+///
+/// 例如："https://User@Example.com:8080/a/b?q=1#top" → ("example.com", "/a/b")
+pub fn parse_url(value: &str) -> Option<(String, String)> {
+    let trimmed = value.trim();
+    let without_scheme = match trimmed.find("://") {
+        Some(pos) => &trimmed[pos + 3..],
+        None => trimmed,
+    };
+
+    let authority_end = without_scheme.find(['/', '?', '#']).unwrap_or(without_scheme.len());
+    let (authority, rest) = without_scheme.split_at(authority_end);
+
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+    let host = match host.rfind(':') {
+        Some(pos) if host[pos + 1..].chars().all(|c| c.is_ascii_digit()) => &host[..pos],
+        _ => host,
+    };
+    if host.is_empty() {
+        return None;
+    }
+
+    let path_end = rest.find(['?', '#']).unwrap_or(rest.len());
+    let path = if rest[..path_end].is_empty() {
+        "/"
+    } else {
+        &rest[..path_end]
+    };
+
+    Some((host.to_lowercase(), path.to_string()))
+}
+
+// ============================================================================
+// 金额
+// ============================================================================
+/// 可以出现在金额前后的货币符号（多字符的排在前面，优先匹配；货币代码先于符号匹配）
+const CURRENCY_SYMBOLS: &[&str] = &[
+    "US$", "HK$", "NT$", "R$", "A$", "C$", "S$", "zł", "kr", "$", "€", "£", "¥", "￥", "₹", "₩", "₽", "₺", "₫", "₪",
+    "₱", "฿", "₴", "₦", "¢", "元", "円",
+];
+
+/// 可以出现在金额前后的 ISO 4217 货币代码（不区分大小写）
+const CURRENCY_CODES: &[&str] = &[
+    "USD", "EUR", "GBP", "JPY", "CNY", "RMB", "HKD", "TWD", "KRW", "INR", "RUB", "CHF", "CAD", "AUD", "NZD", "SGD",
+    "SEK", "NOK", "DKK", "PLN", "CZK", "HUF", "TRY", "BRL", "MXN", "ZAR", "THB", "IDR", "MYR", "PHP", "VND", "AED",
+    "SAR", "ILS",
+];
+
+/// 解析带货币格式的金额字符串
+///
+/// 规则：
+/// - 货币符号或货币代码只能出现在数字前后："$1,234.56"、"1.234,56 €"、"USD 100"
+/// - 负号只能出现在开头或末尾，或用括号包住整个金额："($1,234.56)" → -1234.56，"-¥50" → -50.0，"50-" → -50.0
+/// - 数字部分只能包含数字、一个小数点和千分位分隔符（另一种标点、空格或 "'"）
+/// - decimal_separator 为小数点字符（"." 或 ","），欧式格式 "1.234,56 €" 使用 ","
+/// - 其他内容（字母、科学计数法、数字中间的负号、多个小数点等）返回 None
+pub fn parse_currency_value(value: &str, decimal_separator: char) -> Option<f64> {
+    let mut rest = value.trim();
+
+    let mut negative = false;
+    if let Some(inner) = rest.strip_prefix('(').and_then(|r| r.strip_suffix(')')) {
+        negative = true;
+        rest = inner.trim();
+    }
+
+    // 符号可以在货币符号之前或之后："-$50"、"$-50"
+    let mut signed = false;
+    for _ in 0..2 {
+        if let Some((after_sign, is_negative)) = strip_sign_prefix(rest) {
+            if signed || negative {
+                return None;
+            }
+            signed = true;
+            negative = is_negative;
+            rest = after_sign.trim_start();
+        }
+        if let Some(after_currency) = strip_currency_prefix(rest) {
+            rest = after_currency.trim_start();
+        }
+    }
+
+    if let Some(before_currency) = strip_currency_suffix(rest) {
+        rest = before_currency.trim_end();
+    }
+    if let Some(before_sign) = rest.strip_suffix(['-', '−']) {
+        if signed || negative {
+            return None;
+        }
+        negative = true;
+        rest = before_sign.trim_end();
+    }
+
+    let amount = parse_grouped_number(rest, decimal_separator)?;
+    Some(if negative { -amount } else { amount })
+}
+
+/// 去掉开头的正负号，返回剩余部分和是否为负
+fn strip_sign_prefix(text: &str) -> Option<(&str, bool)> {
+    if let Some(rest) = text.strip_prefix(['-', '−']) {
+        Some((rest, true))
+    } else {
+        text.strip_prefix('+').map(|rest| (rest, false))
+    }
+}
+
+/// 去掉开头的货币符号或货币代码
+fn strip_currency_prefix(text: &str) -> Option<&str> {
+    CURRENCY_CODES
+        .iter()
+        .find_map(|code| {
+            let head = text.get(..code.len())?;
+            head.eq_ignore_ascii_case(code).then_some(&text[code.len()..])
+        })
+        .or_else(|| CURRENCY_SYMBOLS.iter().find_map(|symbol| text.strip_prefix(symbol)))
+}
+
+/// 去掉末尾的货币符号或货币代码
+fn strip_currency_suffix(text: &str) -> Option<&str> {
+    CURRENCY_CODES
+        .iter()
+        .find_map(|code| {
+            let split = text.len().checked_sub(code.len())?;
+            let tail = text.get(split..)?;
+            tail.eq_ignore_ascii_case(code).then_some(&text[..split])
+        })
+        .or_else(|| CURRENCY_SYMBOLS.iter().find_map(|symbol| text.strip_suffix(symbol)))
+}
+
+/// 解析带千分位分隔符的数字
+///
+/// 有分隔符时，第一组 1~3 位、中间每组 2~3 位（兼容印度格式 "1,00,000"）、最后一组 3 位；小数部分只能是数字
+fn parse_grouped_number(text: &str, decimal_separator: char) -> Option<f64> {
+    let (integer, fraction) = text.split_once(decimal_separator).unwrap_or((text, ""));
+
+    let groups: Vec<&str> = integer
+        .split([',', '.', ' ', '\'', '\u{a0}', '\u{202f}'])
+        .collect();
+    if let [first, middle @ .., last] = groups.as_slice() {
+        let valid = (1..=3).contains(&first.len())
+            && middle.iter().all(|group| (2..=3).contains(&group.len()))
+            && last.len() == 3;
+        if !valid {
+            return None;
+        }
+    }
+
+    let integer = groups.concat();
+    if !integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    if integer.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    format!("{}.{}", integer, fraction).parse().ok()
+}
+
+// ============================================================================
+// 百分比
+// ============================================================================
+/// 解析百分比字符串为小数："12.5%" → 0.125
+///
+/// 百分号可以省略（整列都按百分数解释："12.5" → 0.125），支持全角 "％" 和千分位
+pub fn parse_percentage_value(value: &str) -> Option<f64> {
+    let number: String = value
+        .trim()
+        .trim_end_matches(['%', '％'])
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .collect();
+
+    if number.is_empty() {
+        return None;
+    }
+    number.parse::<f64>().ok().map(|v| v / 100.0)
+}
+
+/// 将小数格式化为百分比字符串：0.125 → "12.5%"
+///
+/// - decimal_places: 保留的小数位数；None 表示最多 6 位并去掉末尾的 0
+pub fn format_percentage_value(value: f64, decimal_places: Option<usize>) -> String {
+    let percent = value * 100.0;
+    match decimal_places {
+        Some(places) => format!("{:.*}%", places, percent),
+        None => {
+            let text = format!("{:.6}", percent);
+            let text = text.trim_end_matches('0').trim_end_matches('.');
+            format!("{}%", text)
+        }
+    }
+}
+
+// ============================================================================
+// 布尔值
+// ============================================================================
+/// 默认的真值标记（比较时忽略大小写和首尾空白）
+pub const DEFAULT_TRUE_TOKENS: &[&str] = &["true", "t", "yes", "y", "1", "on", "是", "对", "真"];
+
+/// 默认的假值标记（比较时忽略大小写和首尾空白）
+pub const DEFAULT_FALSE_TOKENS: &[&str] = &["false", "f", "no", "n", "0", "off", "否", "错", "假"];
+
+/// 未匹配值报告中保留的不同值数量上限
+const UNMATCHED_SAMPLE_LIMIT: usize = 50;
+
+/// 按真值/假值标记将列转换为布尔列
+///
+/// 无法识别的值转换为空值，并记录在未匹配值报告中
+///
+/// 参数：
+/// - df: 数据集
+/// - column: 源列名
+/// - output_name: 新列名
+/// - true_tokens / false_tokens: 真值/假值标记（不能有重叠）
+pub fn map_boolean_column(
+    df: &DataFrame,
+    column: &str,
+    output_name: &str,
+    true_tokens: &[String],
+    false_tokens: &[String],
+) -> Result<(Series, UnmatchedValuesReport)> {
+    let normalize = |token: &str| token.trim().to_lowercase();
+    let truthy: HashSet<String> = true_tokens.iter().map(|t| normalize(t)).collect();
+    let falsy: HashSet<String> = false_tokens.iter().map(|t| normalize(t)).collect();
+
+    if let Some(overlap) = truthy.intersection(&falsy).next() {
+        return Err(DataAnalystError::InvalidOperation(format!(
+            "标记 '{}' 同时出现在真值和假值中",
+            overlap
+        )));
+    }
+
+    let source = df
+        .column(column)
+        .map_err(|_| DataAnalystError::InvalidOperation(format!("列 '{}' 不存在", column)))?
+        .cast(&DataType::String)?;
+
+    let mut unmatched: HashMap<String, usize> = HashMap::new();
+    let mapped: BooleanChunked = source
+        .str()?
+        .into_iter()
+        .map(|value| {
+            let value = value?;
+            let token = normalize(value);
+            if truthy.contains(&token) {
+                Some(true)
+            } else if falsy.contains(&token) {
+                Some(false)
+            } else {
+                *unmatched.entry(value.to_string()).or_insert(0) += 1;
+                None
+            }
+        })
+        .collect();
+
+    let unmatched_count = unmatched.values().sum();
+    let mut values: Vec<(String, usize)> = unmatched.into_iter().collect();
+    values.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    values.truncate(UNMATCHED_SAMPLE_LIMIT);
+
+    let report = UnmatchedValuesReport {
+        column: column.to_string(),
+        unmatched_count,
+        values,
+    };
+
+    Ok((mapped.with_name(output_name.into()).into_series(), report))
+}
+
+// ============================================================================
+// IP 地址
+// ============================================================================
+/// 解析 IP 地址，返回版本（"v4" / "v6"）
+///
+/// 无法解析时返回 None
+pub fn ip_version(value: &str) -> Option<String> {
+    match value.trim().parse::<std::net::IpAddr>().ok()? {
+        std::net::IpAddr::V4(_) => Some("v4".to_string()),
+        std::net::IpAddr::V6(_) => Some("v6".to_string()),
+    }
+}
+
+// ============================================================================
+// User-Agent
+// ============================================================================
+/// 从 User-Agent 字符串识别浏览器
+///
+/// 按特征从具体到通用依次匹配（例如 Edge 的 UA 同时包含 "Chrome" 和 "Safari"）
+pub fn user_agent_browser(value: &str) -> Option<String> {
+    let ua = value.to_lowercase();
+    let browser = if ua.contains("bot") || ua.contains("spider") || ua.contains("crawler") {
+        "Bot"
+    } else if ua.contains("edg/") || ua.contains("edge/") || ua.contains("edga/") || ua.contains("edgios/") {
+        "Edge"
+    } else if ua.contains("opr/") || ua.contains("opera") {
+        "Opera"
+    } else if ua.contains("samsungbrowser") {
+        "Samsung Internet"
+    } else if ua.contains("micromessenger") {
+        "WeChat"
+    } else if ua.contains("firefox/") || ua.contains("fxios/") {
+        "Firefox"
+    } else if ua.contains("chrome/") || ua.contains("crios/") || ua.contains("chromium/") {
+        "Chrome"
+    } else if ua.contains("safari/") {
+        "Safari"
+    } else if ua.contains("msie ") || ua.contains("trident/") {
+        "Internet Explorer"
+    } else {
+        return None;
+    };
+    Some(browser.to_string())
+}
+
+/// 从 User-Agent 字符串识别操作系统
+///
+/// iOS 的 UA 包含 "like Mac OS X"，Android 的 UA 包含 "Linux"，因此需要先匹配
+pub fn user_agent_os(value: &str) -> Option<String> {
+    let ua = value.to_lowercase();
+    let os = if ua.contains("iphone") || ua.contains("ipad") || ua.contains("ipod") {
+        "iOS"
+    } else if ua.contains("android") {
+        "Android"
+    } else if ua.contains("windows") {
+        "Windows"
+    } else if ua.contains("cros ") {
+        "ChromeOS"
+    } else if ua.contains("mac os x") || ua.contains("macintosh") {
+        "macOS"
+    } else if ua.contains("linux") {
+        "Linux"
+    } else {
+        return None;
+    };
+    Some(os.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_currency_value;
+
+    #[test]
+    fn parses_symbols_codes_and_grouping() {
+        assert_eq!(parse_currency_value("$1,234.56", '.'), Some(1234.56));
+        assert_eq!(parse_currency_value("1.234,56 €", ','), Some(1234.56));
+        assert_eq!(parse_currency_value("USD 1 000", '.'), Some(1000.0));
+        assert_eq!(parse_currency_value("100 cny", '.'), Some(100.0));
+        assert_eq!(parse_currency_value("¥50", '.'), Some(50.0));
+        assert_eq!(parse_currency_value("₹1,00,000", '.'), Some(100000.0));
+        assert_eq!(parse_currency_value(".5", '.'), Some(0.5));
+    }
+
+    #[test]
+    fn parses_leading_trailing_and_parenthesized_signs() {
+        assert_eq!(parse_currency_value("($1,234.56)", '.'), Some(-1234.56));
+        assert_eq!(parse_currency_value("-¥50", '.'), Some(-50.0));
+        assert_eq!(parse_currency_value("$-50", '.'), Some(-50.0));
+        assert_eq!(parse_currency_value("50-", '.'), Some(-50.0));
+        assert_eq!(parse_currency_value("+50", '.'), Some(50.0));
+    }
+
+    #[test]
+    fn rejects_stray_characters() {
+        assert_eq!(parse_currency_value("abc1x2", '.'), None);
+        assert_eq!(parse_currency_value("1e5", '.'), None);
+        assert_eq!(parse_currency_value("12-34", '.'), None);
+        assert_eq!(parse_currency_value("--5", '.'), None);
+        assert_eq!(parse_currency_value("(-5)", '.'), None);
+        assert_eq!(parse_currency_value("1.2.3", '.'), None);
+        assert_eq!(parse_currency_value("1.5,3", ','), None);
+        assert_eq!(parse_currency_value(",100", '.'), None);
+        assert_eq!(parse_currency_value("1,5", '.'), None);
+        assert_eq!(parse_currency_value("$", '.'), None);
+    }
+}
@@ -0,0 +1,103 @@
+// ============================================================================
+// data/validation.rs - 数据校验
+// ============================================================================
+// 这个文件负责：
+// 1. 按校验规则（SQL 条件）找出违规的行
+// 2. 将校验报告写为 JSON 或 CSV 文件（放在导出文件旁边）
+
+use crate::data::filter::build_sql_predicate;
+use crate::error::{DataAnalystError, Result};
+use crate::models::{ReportFormat, RuleSummary, ValidationReport, ValidationRule, ValidationViolation};
+use polars::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// 行号列的临时名称
+const ROW_INDEX_COLUMN: &str = "__row_index";
+
+// ============================================================================
+// 校验
+// ============================================================================
+/// 按校验规则检查数据集
+///
+/// 条件结果为 false 或 null 的行都视为违规
+pub fn validate_dataframe(df: &DataFrame, rules: &[ValidationRule]) -> Result<ValidationReport> {
+    let mut summaries = Vec::with_capacity(rules.len());
+    let mut violations = Vec::new();
+
+    for rule in rules {
+        let predicate = build_sql_predicate(&rule.expression)?;
+
+        let violated = df
+            .clone()
+            .lazy()
+            .with_row_index(ROW_INDEX_COLUMN, None)
+            .filter(predicate.fill_null(lit(false)).not())
+            .select([col(ROW_INDEX_COLUMN)])
+            .collect()
+            .map_err(|e| DataAnalystError::InvalidOperation(format!("校验规则 '{}' 执行失败: {}", rule.name, e)))?;
+
+        let rows = violated.column(ROW_INDEX_COLUMN)?.idx()?;
+        summaries.push(RuleSummary {
+            name: rule.name.clone(),
+            expression: rule.expression.clone(),
+            violation_count: rows.len(),
+        });
+        violations.extend(rows.into_no_null_iter().map(|row| ValidationViolation {
+            rule: rule.name.clone(),
+            row: row as usize,
+        }));
+    }
+
+    Ok(ValidationReport {
+        total_rows: df.height(),
+        violation_count: violations.len(),
+        rules: summaries,
+        violations,
+    })
+}
+
+// ============================================================================
+// 报告输出
+// ============================================================================
+/// 校验报告的路径：与导出文件同目录，文件名为 "{导出文件名}.validation.{ext}"
+///
+/// 例如："/data/sales.csv" → "/data/sales.csv.validation.json"
+pub fn report_path_for(output_path: &str, format: ReportFormat) -> PathBuf {
+    PathBuf::from(format!("{}.validation.{}", output_path, format.extension()))
+}
+
+/// 将校验报告写入文件
+pub fn write_validation_report(report: &ValidationReport, path: &Path, format: ReportFormat) -> Result<()> {
+    match format {
+        ReportFormat::Json => {
+            let file = std::fs::File::create(path)?;
+            serde_json::to_writer_pretty(file, report)?;
+        }
+        ReportFormat::Csv => {
+            let expressions: std::collections::HashMap<&str, &str> = report
+                .rules
+                .iter()
+                .map(|rule| (rule.name.as_str(), rule.expression.as_str()))
+                .collect();
+
+            let rule: Vec<&str> = report.violations.iter().map(|v| v.rule.as_str()).collect();
+            let expression: Vec<&str> = report
+                .violations
+                .iter()
+                .map(|v| expressions.get(v.rule.as_str()).copied().unwrap_or_default())
+                .collect();
+            let row: Vec<u64> = report.violations.iter().map(|v| v.row as u64).collect();
+
+            let mut df = DataFrame::new(vec![
+                Column::new("rule".into(), rule),
+                Column::new("expression".into(), expression),
+                Column::new("row".into(), row),
+            ])?;
+
+            let mut file = std::fs::File::create(path)?;
+            CsvWriter::new(&mut file).include_header(true).finish(&mut df)?;
+        }
+    }
+
+    Ok(())
+}
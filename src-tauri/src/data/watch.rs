@@ -0,0 +1,120 @@
+// ============================================================================
+// data/watch.rs - 源文件监视
+// ============================================================================
+// 这个文件负责监视当前数据集的源文件，文件在磁盘上变化时通知调用方：
+// 1. 同一时间只监视一个文件，重新开始监视时替换之前的监视
+// 2. 监视文件所在的目录（不递归），按文件名过滤事件：
+//    很多编辑器和导出工具保存时先写临时文件再重命名覆盖，直接监视文件会在第一次保存后失效
+// 3. 一次保存通常会触发多个文件系统事件，SOURCE_CHANGE_THROTTLE 内只通知一次
+
+use crate::error::{DataAnalystError, Result};
+use crate::models::SourceChangeKind;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// 同一个文件两次通知之间的最短间隔
+const SOURCE_CHANGE_THROTTLE: Duration = Duration::from_millis(1000);
+
+/// SharedFileWatcher - 在命令之间共享的源文件监视器
+pub type SharedFileWatcher = Arc<FileWatcher>;
+
+/// 正在进行的监视（watcher 被丢弃时停止监视）
+struct ActiveWatch {
+    path: PathBuf,
+    _watcher: RecommendedWatcher,
+}
+
+/// FileWatcher - 源文件监视器
+#[derive(Default)]
+pub struct FileWatcher {
+    active: Mutex<Option<ActiveWatch>>,
+}
+
+impl FileWatcher {
+    /// 创建监视器（尚未监视任何文件）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 开始监视文件（替换之前的监视）
+    ///
+    /// 参数：
+    /// - path: 要监视的文件
+    /// - on_change: 文件变化时调用（在监视线程中调用）
+    pub fn watch<F>(&self, path: &Path, on_change: F) -> Result<()>
+    where
+        F: Fn(SourceChangeKind) + Send + 'static,
+    {
+        let watch_error = |e: notify::Error| DataAnalystError::InvalidOperation(format!("无法监视文件: {}", e));
+
+        let path = path.canonicalize()?;
+        let directory = path
+            .parent()
+            .ok_or_else(|| DataAnalystError::InvalidOperation(format!("无效的文件路径: {}", path.display())))?
+            .to_path_buf();
+        let file_name = path.file_name().map(|name| name.to_os_string());
+
+        let last_notified: Mutex<Option<Instant>> = Mutex::new(None);
+        let target = path.clone();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            if !event
+                .paths
+                .iter()
+                .any(|p| p.file_name().map(|n| n.to_os_string()) == file_name)
+            {
+                return;
+            }
+
+            // 重命名覆盖时目标文件仍然存在，按修改处理
+            let kind = match event.kind {
+                EventKind::Create(_) | EventKind::Modify(_) => SourceChangeKind::Modified,
+                EventKind::Remove(_) if target.exists() => SourceChangeKind::Modified,
+                EventKind::Remove(_) => SourceChangeKind::Removed,
+                _ => return,
+            };
+
+            let mut last = last_notified.lock().unwrap_or_else(PoisonError::into_inner);
+            let now = Instant::now();
+            if last.is_some_and(|at| now.duration_since(at) < SOURCE_CHANGE_THROTTLE) {
+                return;
+            }
+            *last = Some(now);
+            drop(last);
+
+            on_change(kind);
+        })
+        .map_err(watch_error)?;
+        watcher
+            .watch(&directory, RecursiveMode::NonRecursive)
+            .map_err(watch_error)?;
+
+        *self.active.lock().unwrap_or_else(PoisonError::into_inner) = Some(ActiveWatch {
+            path,
+            _watcher: watcher,
+        });
+        Ok(())
+    }
+
+    /// 停止监视，返回之前监视的文件（没有监视时为 None）
+    pub fn unwatch(&self) -> Option<PathBuf> {
+        self.active
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+            .map(|active| active.path)
+    }
+
+    /// 当前监视的文件
+    pub fn watched_path(&self) -> Option<PathBuf> {
+        self.active
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .map(|active| active.path.clone())
+    }
+}
@@ -62,6 +62,30 @@ pub enum DataAnalystError {
     /// 无效操作错误
     #[error("Invalid operation: {0}")]
     InvalidOperation(String),
+
+    /// 数据集已锁定错误
+    ///
+    /// 数据集处于只读锁定状态时，任何修改数据或历史的操作都会返回这个错误
+    #[error("Dataset is locked: 数据集已锁定为只读，请先解锁")]
+    DatasetLocked,
+
+    /// 操作已取消错误
+    ///
+    /// 后台任务（例如导出）被用户取消时返回这个错误
+    #[error("Operation cancelled: 操作已取消")]
+    Cancelled,
+
+    /// 导入进行中错误
+    ///
+    /// 另一个导入尚未结束时开始新的导入会返回这个错误，{0} 是正在导入的来源
+    #[error("Import in progress: 正在导入 {0}，请等待完成或取消后再导入")]
+    ImportInProgress(String),
+
+    /// 列名冲突错误
+    ///
+    /// 操作生成的新列与已有列同名、且冲突策略为 error 时返回这个错误，{0} 是冲突的列名
+    #[error("Column conflict: 列 '{0}' 已存在，请换一个列名，或选择自动加后缀（suffix）/ 覆盖（overwrite）")]
+    ColumnConflict(String),
 }
 
 // ============================================================================
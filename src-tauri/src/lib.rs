@@ -12,8 +12,7 @@
 // ============================================================================
 // std::sync 是 Rust 标准库中的同步原语模块
 // Arc = Atomic Reference Counted（原子引用计数）：允许多个所有者共享数据
-// Mutex = Mutual Exclusion（互斥锁）：确保同一时间只有一个线程可以访问数据
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 #[cfg(target_os = "macos")]
 use tauri::menu::{HELP_SUBMENU_ID, Menu, MenuItem, PredefinedMenuItem, Submenu, WINDOW_SUBMENU_ID};
@@ -35,43 +34,138 @@ mod models; // 数据模型模块（models/ 文件夹）
 // use 关键字用于将模块中的内容引入当前作用域，类似于 import
 
 // 从 data 模块导入数据存储相关类型
-use data::{DataStore, SharedDataStore};
+use data::{
+    DataStore, FileWatcher, ImportGate, Scheduler, SharedDataStore, SharedFileWatcher, SharedImportGate,
+    SharedScheduler, SharedTaskRegistry, StoreMutex, TaskRegistry,
+};
 
 // 从 commands 模块导入所有 Tauri 命令函数
 // 这些函数可以被前端 JavaScript/TypeScript 代码调用
 use commands::{
+    add_business_days,
+    aggregate_scalar,
+    apply_recipe,
+    business_days_between,
     can_redo,
     can_undo,
+    cancel_import,
+    cancel_task,
     cast_types,
+    cast_types_by_pattern,
+    check_recipe_compatibility,
+    clean_whitespace,
     clear_data,
+    cohort_table,
+    compute_age,
+    connect_database,
+    delete_cast_profile,
+    delete_export_profile,
+    delete_schedule,
+    describe_dataset,
+    detect_gaps,
+    diff_from_initial,
+    disable_file_watch,
     drop_all_nulls,
     drop_columns,
+    drop_duplicates,
     // 数据操作
     drop_nulls,
+    duration_between,
+    duration_to_number,
+    enable_file_watch,
+    explode_list,
     // 数据导出
+    export_aggregated,
+    export_arrow,
+    export_arrow_stream,
+    export_chart_spec,
+    export_clipboard,
     export_csv,
+    export_excel,
+    export_excel_report,
+    export_history_bundle,
     export_parquet,
+    export_partitioned,
+    export_profile_report,
+    export_snapshot_bundle,
+    export_sqlite,
+    extract_date_parts,
     fill_null,
     filter_data,
+    filter_rows,
+    find_similar_values,
+    format_percentages,
     // 图表数据生成
     generate_chart_data,
+    generate_demo_data,
+    geo_bucket,
+    get_chart_defaults,
+    get_column_formats,
     get_column_stats,
+    get_column_summaries,
+    get_column_totals,
     // 数据查询（新架构）
     get_current_data,
     get_current_index,
     get_current_info,
+    get_dataset_fingerprint,
+    get_excel_sheets,
+    get_export_log,
     // 历史管理
     get_history,
+    get_kpi_summary,
+    get_metric_history,
+    get_parquet_metadata,
+    get_removed_rows,
+    get_row_guardrails,
+    get_row_ordering,
+    get_task,
+    get_view_state,
+    haversine_distance,
     // 文件导入
+    import_arrow,
+    import_avro,
     import_csv,
     import_excel,
+    import_excel_all_sheets,
+    import_json,
+    import_orc,
     import_parquet,
+    import_parquet_dir,
+    import_query,
+    import_sample,
+    import_snapshot_bundle,
+    import_url,
+    is_dataset_locked,
+    join_file,
+    join_list,
     jump_to_history,
+    list_cast_profiles,
+    list_export_profiles,
+    list_schedules,
+    list_tables,
+    list_tasks,
+    lock_dataset,
+    materialize_chart_data,
+    normalize_boolean,
+    normalize_emails,
+    normalize_phones,
+    parse_currency,
+    parse_ip_addresses,
+    parse_percentages,
+    parse_urls,
+    parse_user_agents,
+    period_comparison,
     pivot_data,
+    recover_state,
     redo_operation,
+    reload_from_source,
     rename_columns,
+    replace_non_finite,
     reset_to_initial,
+    resolve_recipe,
     rolling_average,
+    rolling_batch,
     rolling_max,
     rolling_median,
     rolling_min,
@@ -79,10 +173,28 @@ use commands::{
     rolling_std,
     rolling_sum,
     rolling_var,
+    run_schedule_now,
+    save_cast_profile,
+    save_chart_defaults,
+    save_column_summaries,
+    save_export_profile,
+    save_row_guardrails,
+    save_row_ordering,
+    save_schedule,
     select_columns,
+    set_column_format,
+    set_conditional_formats,
+    set_float_precision,
+    set_view_state,
+    shift_by_duration,
     sort_data,
+    suggest_charts,
     undo_operation,
+    unlock_dataset,
+    unnest_struct,
     unpivot_data,
+    validate_chart_config,
+    validate_coordinates,
 };
 
 // ============================================================================
@@ -97,12 +209,36 @@ use commands::{
 pub struct AppState {
     /// data_store: 共享的数据存储
     ///
-    /// SharedDataStore 是 Arc<Mutex<DataStore>> 的类型别名
+    /// SharedDataStore 是 Arc<StoreMutex> 的类型别名
     /// - Arc: 允许多个 Tauri 命令同时持有数据存储的引用
-    /// - Mutex: 确保同一时间只有一个命令可以修改数据
+    /// - StoreMutex: 确保同一时间只有一个命令可以修改数据；
+    ///   某个命令持有锁时 panic 不会让之后的命令都失败（见 recover_state）
     ///
     /// 这种模式在 Rust 中很常见，用于在多线程环境中安全地共享可变数据
     pub data_store: SharedDataStore,
+
+    /// tasks: 后台任务注册表
+    ///
+    /// 长时间运行的命令（例如导出）在这里登记任务，
+    /// 其他命令可以查询进度或请求取消
+    pub tasks: SharedTaskRegistry,
+
+    /// file_watcher: 源文件监视器
+    ///
+    /// enable_file_watch 开始监视当前数据集的源文件，文件变化时推送事件
+    pub file_watcher: SharedFileWatcher,
+
+    /// imports: 导入互斥
+    ///
+    /// 导入会替换整个数据集，同一时间只允许一个导入在进行，
+    /// 另一个导入进行中时新的导入返回 ImportInProgress 错误
+    pub imports: SharedImportGate,
+
+    /// scheduler: 计划任务的调度状态
+    ///
+    /// 后台调度循环按 schedules.json 运行到期的任务，
+    /// 这里记录下次运行时间、正在运行的任务和上次运行结果
+    pub scheduler: SharedScheduler,
 }
 
 #[cfg(target_os = "macos")]
@@ -133,14 +269,26 @@ pub fn run() {
     // 1. 创建共享数据存储
     // ------------------------------------------------------------------------
     // DataStore::new() 创建一个新的数据存储实例
-    // Mutex::new() 将数据存储包装在互斥锁中
+    // StoreMutex::new() 将数据存储包装在互斥锁中（锁中毒后自动恢复）
     // Arc::new() 将互斥锁包装在原子引用计数中
     //
     // 这样做的好处：
     // - 多个命令可以同时持有数据存储的引用（Arc）
-    // - 但同一时间只有一个命令可以修改数据（Mutex）
+    // - 但同一时间只有一个命令可以修改数据（StoreMutex）
     // - 当最后一个引用被释放时，数据会自动清理（Arc 的引用计数）
-    let data_store = Arc::new(Mutex::new(DataStore::new()));
+    let data_store = Arc::new(StoreMutex::new(DataStore::new()));
+
+    // 后台任务注册表（内部自行加锁，只需要 Arc 共享）
+    let tasks = Arc::new(TaskRegistry::new());
+
+    // 源文件监视器（内部自行加锁，只需要 Arc 共享）
+    let file_watcher = Arc::new(FileWatcher::new());
+
+    // 导入互斥（内部自行加锁，只需要 Arc 共享）
+    let imports = Arc::new(ImportGate::new());
+
+    // 计划任务调度状态（内部自行加锁，只需要 Arc 共享）
+    let scheduler = Arc::new(Scheduler::new());
 
     // ------------------------------------------------------------------------
     // 2. 构建并配置 Tauri 应用
@@ -156,39 +304,85 @@ pub fn run() {
         .setup(|app| {
             #[cfg(desktop)]
             app.handle().plugin(tauri_plugin_updater::Builder::new().build())?;
+            // 启动计划任务的后台调度循环
+            tauri::async_runtime::spawn(commands::schedule::run_scheduler(app.handle().clone()));
             Ok(())
         })
         // 管理应用状态：将 AppState 注册到 Tauri
         // 这样所有的命令都可以通过 State 参数访问这个状态
-        .manage(AppState { data_store })
+        .manage(AppState {
+            data_store,
+            tasks,
+            file_watcher,
+            imports,
+            scheduler,
+        })
         // 注册命令处理器：告诉 Tauri 哪些函数可以被前端调用
         .invoke_handler(tauri::generate_handler![
             // 文件导入命令
             import_csv,
+            cancel_import,
             import_excel,
+            import_excel_all_sheets,
+            get_excel_sheets,
             import_parquet,
+            import_parquet_dir,
+            generate_demo_data,
+            import_json,
+            import_arrow,
+            import_avro,
+            import_orc,
+            import_sample,
+            import_url,
+            reload_from_source,
+            get_parquet_metadata,
             // 数据查询命令（新架构）
             get_current_data,
             get_current_info,
             get_column_stats,
+            describe_dataset,
+            find_similar_values,
+            detect_gaps,
+            get_column_formats,
+            aggregate_scalar,
+            get_kpi_summary,
+            get_column_totals,
+            get_dataset_fingerprint,
+            get_view_state,
             // 历史管理命令
             get_history,
             get_current_index,
+            get_export_log,
+            diff_from_initial,
             undo_operation,
             redo_operation,
             jump_to_history,
+            get_removed_rows,
+            get_metric_history,
             can_undo,
             can_redo,
             reset_to_initial,
+            // 只读锁定命令
+            lock_dataset,
+            unlock_dataset,
+            is_dataset_locked,
+            recover_state,
             // 数据操作命令
             drop_nulls,
             drop_all_nulls,
+            drop_duplicates,
+            join_file,
+            explode_list,
+            join_list,
+            unnest_struct,
             select_columns,
             drop_columns,
             rename_columns,
             cast_types,
+            cast_types_by_pattern,
             sort_data,
             filter_data,
+            filter_rows,
             fill_null,
             unpivot_data,
             pivot_data,
@@ -200,12 +394,94 @@ pub fn run() {
             rolling_std,
             rolling_var,
             rolling_quantile,
+            rolling_batch,
+            set_column_format,
+            set_conditional_formats,
+            set_view_state,
+            set_float_precision,
+            // 数据清洗命令
+            normalize_emails,
+            normalize_phones,
+            parse_urls,
+            parse_ip_addresses,
+            parse_user_agents,
+            parse_currency,
+            parse_percentages,
+            format_percentages,
+            normalize_boolean,
+            clean_whitespace,
+            replace_non_finite,
+            // 地理坐标命令
+            validate_coordinates,
+            haversine_distance,
+            geo_bucket,
+            // 时间间隔命令
+            duration_between,
+            shift_by_duration,
+            duration_to_number,
+            period_comparison,
+            cohort_table,
+            extract_date_parts,
+            business_days_between,
+            add_business_days,
+            compute_age,
             // 数据导出命令
             export_csv,
+            export_sqlite,
+            export_excel,
+            export_excel_report,
             export_parquet,
+            export_arrow,
+            export_arrow_stream,
+            export_clipboard,
+            export_aggregated,
+            export_partitioned,
+            export_profile_report,
             clear_data,
+            // 数据库导入命令
+            connect_database,
+            list_tables,
+            import_query,
+            // 源文件监视命令
+            enable_file_watch,
+            disable_file_watch,
+            // 计划任务命令
+            list_schedules,
+            save_schedule,
+            delete_schedule,
+            run_schedule_now,
+            // 快照分享包命令
+            export_snapshot_bundle,
+            export_history_bundle,
+            import_snapshot_bundle,
+            check_recipe_compatibility,
+            resolve_recipe,
+            apply_recipe,
+            // 导出预设命令
+            list_export_profiles,
+            save_export_profile,
+            get_chart_defaults,
+            save_chart_defaults,
+            get_row_guardrails,
+            save_row_guardrails,
+            get_row_ordering,
+            save_row_ordering,
+            delete_export_profile,
+            list_cast_profiles,
+            save_cast_profile,
+            delete_cast_profile,
+            get_column_summaries,
+            save_column_summaries,
+            // 后台任务命令
+            get_task,
+            list_tasks,
+            cancel_task,
             // 图表数据生成命令
             generate_chart_data,
+            export_chart_spec,
+            materialize_chart_data,
+            suggest_charts,
+            validate_chart_config,
         ]);
 
     #[cfg(target_os = "macos")]
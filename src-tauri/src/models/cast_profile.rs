@@ -0,0 +1,59 @@
+// ============================================================================
+// models/cast_profile.rs - 按列名模式批量转换类型的数据模型
+// ============================================================================
+// 这个文件定义了类型转换规则和转换预设：
+// - CastRule: 列名模式（例如 "*_date"）→ 目标类型（可选解析格式）
+// - CastProfile: 一组命名的规则，保存在应用配置目录中，可以重复用于同一来源的新文件
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// 转换规则
+// ============================================================================
+/// CastRule - 一条类型转换规则
+///
+/// 列名模式支持通配符：* 匹配任意多个字符，? 匹配单个字符（不区分大小写）
+///
+/// 序列化示例：
+/// ```json
+/// { "pattern": "*_date", "target_type": "Date", "format": "%d.%m.%Y" }
+/// { "pattern": "*_id", "target_type": "String" }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CastRule {
+    /// 列名模式
+    pub pattern: String,
+
+    /// 目标类型（与 cast_types 相同，例如 "Int64"、"Date"、"Decimal(18, 2)"）
+    pub target_type: String,
+
+    /// 字符串列转为 Date / Datetime 时使用的解析格式（chrono 格式字符串，None 表示自动识别）
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+// ============================================================================
+// 转换预设
+// ============================================================================
+/// CastProfile - 一组命名的类型转换规则
+///
+/// 规则按顺序匹配，一列只使用第一条匹配的规则
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "name": "Vendor A",
+///   "rules": [
+///     { "pattern": "*_date", "target_type": "Date", "format": "%d.%m.%Y" },
+///     { "pattern": "*_id", "target_type": "String" }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CastProfile {
+    /// 预设名称
+    pub name: String,
+
+    /// 转换规则（按顺序匹配）
+    pub rules: Vec<CastRule>,
+}
@@ -2,6 +2,7 @@
 // models/chart.rs - 图表相关数据模型
 // ============================================================================
 
+use crate::models::history::{PeriodBucket, RollingFunction};
 use serde::{Deserialize, Serialize};
 
 /// 图表类型
@@ -18,6 +19,22 @@ pub enum ChartType {
     Pie,
     /// 直方图
     Histogram,
+    /// 热力图（两个分类列交叉计数或求和）
+    Heatmap,
+}
+
+impl ChartType {
+    /// 图表类型的中文名称（用于操作描述）
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChartType::Line => "折线图",
+            ChartType::Bar => "柱状图",
+            ChartType::Scatter => "散点图",
+            ChartType::Pie => "饼图",
+            ChartType::Histogram => "直方图",
+            ChartType::Heatmap => "热力图",
+        }
+    }
 }
 
 /// 图表配置请求
@@ -26,16 +43,16 @@ pub struct ChartConfig {
     /// 图表类型
     pub chart_type: ChartType,
 
-    /// X 轴列名（折线图、柱状图、散点图使用）
+    /// X 轴列名（折线图、柱状图、散点图、热力图使用）
     pub x_column: Option<String>,
 
-    /// Y 轴列名列表（折线图、柱状图、散点图使用，支持多系列）
+    /// Y 轴列名列表（折线图、柱状图、散点图使用，支持多系列；热力图使用第一列作为 Y 轴分类）
     pub y_columns: Option<Vec<String>>,
 
     /// 分类列名（饼图使用）
     pub category_column: Option<String>,
 
-    /// 值列名（饼图使用）
+    /// 值列名（饼图使用；热力图可选，指定时求和，否则计数）
     pub value_column: Option<String>,
 
     /// 图表标题（可选）
@@ -47,11 +64,413 @@ pub struct ChartConfig {
     /// 双 Y 轴右侧列（折线图使用）
     pub y_axis_right_columns: Option<Vec<String>>,
 
+    /// Y 轴系列标准化（折线图、柱状图、散点图使用，None 表示原始值）
+    ///
+    /// 各系列数量级相差很大时（例如双 Y 轴），标准化后可以放在同一坐标轴上比较
+    #[serde(default)]
+    pub normalize: Option<SeriesNormalization>,
+
+    /// 对数变换（折线图、柱状图、散点图的 Y 轴系列，或直方图的数值列；None 表示不变换）
+    ///
+    /// 只影响返回的图表数据，不会在数据集中添加计算列
+    #[serde(default)]
+    pub log_transform: Option<LogTransform>,
+
+    /// Y 轴系列平滑（折线图、柱状图、散点图使用，None 表示不平滑）
+    ///
+    /// 只影响返回的图表数据，不会在数据集中添加移动窗口列
+    #[serde(default)]
+    pub smoothing: Option<ChartSmoothing>,
+
+    /// 分位带（折线图使用，None 表示不计算）
+    ///
+    /// 在 dataset 的 Y 轴列之后追加 {列名}_p10、{列名}_p50、{列名}_p90 等列，
+    /// 用于绘制"中位数 + 区间"图。设置 x_bucket 时按时间段计算，否则按移动窗口计算
+    #[serde(default)]
+    pub quantile_bands: Option<QuantileBands>,
+
+    /// 建议的 Y 轴范围（折线图、柱状图、散点图使用，None 表示不计算）
+    ///
+    /// 按分位数（默认 1% ~ 99%）给出坐标轴的最小 / 最大值，
+    /// 避免个别极端值把其余数据压成一条线；结果在 ChartData.axis_ranges 中返回
+    #[serde(default)]
+    pub axis_range: Option<AxisRangeOptions>,
+
+    /// X 轴时间段汇总粒度（折线图、柱状图使用，X 轴须为日期 / 日期时间列，None 表示不汇总）
+    #[serde(default)]
+    pub x_bucket: Option<PeriodBucket>,
+
+    /// 时间段汇总函数（sum, mean, median, min, max, count, first, last，None 表示 sum）
+    #[serde(default)]
+    pub bucket_aggregation: Option<String>,
+
+    /// 没有数据的时间段如何补齐（仅在指定 x_bucket 时使用，None 表示不补齐）
+    ///
+    /// 不补齐时缺失的月份会被折线直接跨过，容易误读为连续数据
+    #[serde(default)]
+    pub fill_empty_buckets: Option<EmptyBucketFill>,
+
+    /// X 值重复时如何合并 Y 值（折线图、柱状图使用，None 表示逐行绘制）
+    ///
+    /// 例如每天有多行时，逐行绘制会让折线来回折返；合并后每个 X 值只保留一个点，
+    /// 按 X 值首次出现的顺序排列。指定 x_bucket 时已按时间段汇总，此选项不生效
+    #[serde(default)]
+    pub x_duplicates: Option<DuplicateXAggregation>,
+
     /// 直方图列名（直方图使用）
     pub histogram_column: Option<String>,
 
-    /// 直方图分箱数量（直方图使用）
+    /// 直方图分箱数量（直方图使用，仅数值列）
     pub histogram_bins: Option<usize>,
+
+    /// 直方图时间分箱粒度（直方图使用，仅日期 / 日期时间列，None 表示按时间跨度自动选择）
+    pub histogram_interval: Option<PeriodBucket>,
+
+    /// 分面列名（可选，指定时按该列的每个值分别生成数据，用于小多图）
+    #[serde(default)]
+    pub facet_column: Option<String>,
+
+    /// 最多返回的分面数量（按行数取前 N 个，None 表示 12 个）
+    #[serde(default)]
+    pub facet_limit: Option<usize>,
+}
+
+/// Y 轴系列标准化方式（每个系列单独计算）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeriesNormalization {
+    /// 指数化：以第一个非空且非零的值为基准 100
+    Index,
+    /// 最小-最大缩放到 0 ~ 1（所有值相同时为 0）
+    MinMax,
+}
+
+/// 对数的底
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogBase {
+    /// 以 10 为底
+    Log10,
+    /// 自然对数
+    Ln,
+}
+
+impl LogBase {
+    /// 计算对数
+    pub fn apply(&self, value: f64) -> f64 {
+        match self {
+            LogBase::Log10 => value.log10(),
+            LogBase::Ln => value.ln(),
+        }
+    }
+}
+
+/// 对数变换时小于等于 0 的值的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NonPositivePolicy {
+    /// 变为空值（默认）
+    #[default]
+    Null,
+    /// 整列平移，使最小值变为 1 后再取对数（log(x - min + 1)）
+    Shift,
+    /// 返回错误
+    Error,
+}
+
+/// 对数变换选项
+///
+/// 序列化示例：
+/// ```json
+/// { "base": "log10", "columns": ["revenue"], "non_positive": "null" }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogTransform {
+    /// 对数的底
+    pub base: LogBase,
+
+    /// 需要变换的 Y 轴列（None 表示所有 Y 轴列；直方图忽略）
+    #[serde(default)]
+    pub columns: Option<Vec<String>>,
+
+    /// 小于等于 0 的值的处理方式
+    #[serde(default)]
+    pub non_positive: NonPositivePolicy,
+}
+
+/// 图表坐标轴
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChartAxis {
+    /// X 轴（直方图的分箱轴）
+    X,
+    /// 左侧 Y 轴
+    Y,
+    /// 右侧 Y 轴（y_axis_right_columns）
+    YRight,
+}
+
+/// 坐标轴提示：告诉前端哪个坐标轴上的值已经取过对数
+///
+/// 前端可以据此把刻度显示为原始值（例如 log10 轴上的 3 显示为 1,000）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AxisHint {
+    /// 坐标轴
+    pub axis: ChartAxis,
+
+    /// 对数的底
+    pub log_base: LogBase,
+
+    /// 该坐标轴上取过对数的列
+    pub columns: Vec<String>,
+}
+
+/// 坐标轴范围建议选项（分位数，0 ~ 1）
+///
+/// 序列化示例：
+/// ```json
+/// { "lower": 0.01, "upper": 0.99 }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AxisRangeOptions {
+    /// 下限分位数
+    pub lower: f64,
+
+    /// 上限分位数
+    pub upper: f64,
+}
+
+impl Default for AxisRangeOptions {
+    fn default() -> Self {
+        Self {
+            lower: 0.01,
+            upper: 0.99,
+        }
+    }
+}
+
+/// 建议的坐标轴范围
+///
+/// min / max 是按分位数计算的建议范围，data_min / data_max 是实际的最小 / 最大值；
+/// clipped_count 为 0 时说明没有极端值，可以直接使用默认范围
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AxisRange {
+    /// 坐标轴（左侧 Y 轴或右侧 Y 轴）
+    pub axis: ChartAxis,
+
+    /// 建议的最小值
+    pub min: f64,
+
+    /// 建议的最大值
+    pub max: f64,
+
+    /// 实际的最小值
+    pub data_min: f64,
+
+    /// 实际的最大值
+    pub data_max: f64,
+
+    /// 落在建议范围之外的值的数量
+    pub clipped_count: usize,
+}
+
+/// 空时间段的补齐方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmptyBucketFill {
+    /// 补 0（适合求和、计数）
+    Zero,
+    /// 补空值（适合平均值等，折线图显示为断点）
+    Null,
+}
+
+/// 重复 X 值的合并方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateXAggregation {
+    /// 保留第一行
+    First,
+    /// 求和
+    Sum,
+    /// 平均值
+    Mean,
+}
+
+impl DuplicateXAggregation {
+    /// 对应的汇总函数名
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DuplicateXAggregation::First => "first",
+            DuplicateXAggregation::Sum => "sum",
+            DuplicateXAggregation::Mean => "mean",
+        }
+    }
+}
+
+/// 图表平滑选项（按行顺序计算移动窗口，先平滑再标准化）
+///
+/// 序列化示例：
+/// ```json
+/// { "function": "mean", "window_size": 7 }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartSmoothing {
+    /// 移动窗口函数（不支持 quantile）
+    pub function: RollingFunction,
+
+    /// 窗口大小（行数）
+    pub window_size: usize,
+
+    /// 是否使用居中窗口（默认 false，即当前行及之前的行）
+    #[serde(default)]
+    pub center: bool,
+}
+
+/// 默认的分位带分位数（p10 / p50 / p90）
+pub const DEFAULT_BAND_QUANTILES: [f64; 3] = [0.1, 0.5, 0.9];
+
+/// 分位带选项
+///
+/// 序列化示例：
+/// ```json
+/// { "column": "cpu_usage", "window_size": 24 }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantileBands {
+    /// 计算分位带的列（必须是 Y 轴列之一）
+    pub column: String,
+
+    /// 分位数（0 ~ 1，None 表示 p10 / p50 / p90）
+    #[serde(default)]
+    pub quantiles: Option<Vec<f64>>,
+
+    /// 移动窗口大小（行数，未设置 x_bucket 时必须指定；设置 x_bucket 时按时间段计算，忽略此项）
+    #[serde(default)]
+    pub window_size: Option<usize>,
+}
+
+impl QuantileBands {
+    /// 要计算的分位数
+    pub fn quantiles(&self) -> Vec<f64> {
+        self.quantiles
+            .clone()
+            .unwrap_or_else(|| DEFAULT_BAND_QUANTILES.to_vec())
+    }
+
+    /// 分位数对应的输出列名，例如 0.1 → "{column}_p10"，0.025 → "{column}_p2.5"
+    pub fn column_name(&self, quantile: f64) -> String {
+        let percent = quantile * 100.0;
+        if (percent - percent.round()).abs() < 1e-9 {
+            format!("{}_p{}", self.column, percent.round() as i64)
+        } else {
+            format!("{}_p{}", self.column, percent)
+        }
+    }
+}
+
+/// 图表推荐（suggest_charts 返回，按 score 降序）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartSuggestion {
+    /// 推荐的图表配置（可以直接传给 generate_chart_data）
+    pub config: ChartConfig,
+
+    /// 推荐分数（0 ~ 1，越高越推荐）
+    pub score: f64,
+
+    /// 推荐理由（用于界面提示）
+    pub reason: String,
+}
+
+/// 图表默认设置（用户可修改，保存在应用配置目录）
+///
+/// 图表配置中未指定的选项使用这里的值，推荐图表时也按这里的上限选择列
+///
+/// 序列化示例：
+/// ```json
+/// { "histogram_bins": 20, "date_bucket": "month", "max_points": 100000 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChartDefaults {
+    /// 直方图默认分箱数量（数值列）
+    pub histogram_bins: usize,
+
+    /// 默认时间粒度（日期直方图的分箱粒度、推荐折线图的 x_bucket，None 表示按时间跨度自动选择 / 不汇总）
+    pub date_bucket: Option<PeriodBucket>,
+
+    /// 未汇总的折线图 / 散点图超过该行数时提示渲染缓慢
+    pub max_points: usize,
+
+    /// 默认最多返回的分面数量
+    pub facet_limit: usize,
+
+    /// 推荐折线图时最多的 Y 轴系列数量（每个系列一种颜色）
+    pub max_series: usize,
+
+    /// 推荐饼图时最多的扇区数量
+    pub max_pie_slices: usize,
+
+    /// 推荐图表时分类列最多的不同值数量（超过时不作为分类轴）
+    pub max_categories: usize,
+}
+
+impl Default for ChartDefaults {
+    fn default() -> Self {
+        Self {
+            histogram_bins: 10,
+            date_bucket: None,
+            max_points: 50_000,
+            facet_limit: 12,
+            max_series: 3,
+            max_pie_slices: 8,
+            max_categories: 30,
+        }
+    }
+}
+
+impl ChartDefaults {
+    /// 用默认值补齐图表配置中未指定的选项
+    pub fn apply(&self, config: &mut ChartConfig) {
+        config.histogram_bins.get_or_insert(self.histogram_bins);
+        if config.histogram_interval.is_none() {
+            config.histogram_interval = self.date_bucket;
+        }
+        config.facet_limit.get_or_insert(self.facet_limit);
+    }
+}
+
+/// 图表配置检查问题的级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartIssueLevel {
+    /// 无法生成图表（generate_chart_data 会返回错误）
+    Error,
+    /// 可以生成，但图表可能难以阅读或渲染缓慢
+    Warning,
+}
+
+/// 图表配置检查发现的单个问题
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartIssue {
+    /// 问题级别
+    pub level: ChartIssueLevel,
+
+    /// 相关的配置字段（例如 "y_columns"，None 表示整体问题）
+    pub field: Option<String>,
+
+    /// 问题说明（包含修改建议）
+    pub message: String,
+}
+
+/// 图表配置检查结果（validate_chart_config 返回）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartValidation {
+    /// 是否没有 Error 级别的问题
+    pub valid: bool,
+
+    /// 发现的问题（先错误后警告）
+    pub issues: Vec<ChartIssue>,
 }
 
 /// 图表数据响应（使用 ECharts dataset 格式）
@@ -65,6 +484,87 @@ pub struct ChartData {
     /// 例如：[["date", "sales", "profit"], ["2024-01", 100, 50], ...]
     pub dataset: Vec<Vec<serde_json::Value>>,
 
+    /// 数据行数（不包括表头；分面时为所有分面的行数之和）
+    pub data_count: usize,
+
+    /// 分面数据（指定 facet_column 时返回，此时 dataset 为空）
+    #[serde(default)]
+    pub facets: Option<Vec<ChartFacet>>,
+
+    /// 因超过 facet_limit 被省略的分面数量
+    #[serde(default)]
+    pub omitted_facets: usize,
+
+    /// 坐标轴提示（指定 log_transform 时返回）
+    #[serde(default)]
+    pub axis_hints: Vec<AxisHint>,
+
+    /// 建议的 Y 轴范围（指定 axis_range 时返回，值为变换后的值；分面时在每个分面中返回）
+    #[serde(default)]
+    pub axis_ranges: Vec<AxisRange>,
+}
+
+/// 单个分面的图表数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartFacet {
+    /// 分面值（显示用文本）
+    pub value: String,
+
+    /// 该分面在原数据中的行数
+    pub row_count: usize,
+
+    /// ECharts dataset 格式的数据（与 ChartData.dataset 相同）
+    pub dataset: Vec<Vec<serde_json::Value>>,
+
     /// 数据行数（不包括表头）
     pub data_count: usize,
+
+    /// 该分面建议的 Y 轴范围（指定 axis_range 时返回）
+    #[serde(default)]
+    pub axis_ranges: Vec<AxisRange>,
+}
+
+// ============================================================================
+// 独立图表规格
+// ============================================================================
+/// ChartSpec - 独立的图表规格文件（export_chart_spec 写出）
+///
+/// option 是完整的 ECharts option（数据以 dataset.source 内嵌），
+/// 可以直接交给其他工具中的 echarts.setOption，或在无界面环境中渲染
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartSpec {
+    /// 规格格式版本
+    pub format_version: u32,
+
+    /// 生成规格的应用版本
+    pub app_version: String,
+
+    /// 生成时间（ISO 8601 格式）
+    pub created_at: String,
+
+    /// 生成图表使用的配置（已应用图表默认设置）
+    pub config: ChartConfig,
+
+    /// 数据行数（不包括表头；分面时为所有分面的行数之和）
+    pub data_count: usize,
+
+    /// ECharts option（分面图表为 None，见 facets）
+    pub option: Option<serde_json::Value>,
+
+    /// 分面图表的每个分面（指定 facet_column 时才有）
+    #[serde(default)]
+    pub facets: Vec<ChartFacetSpec>,
+}
+
+/// ChartFacetSpec - 分面图表中单个分面的规格
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartFacetSpec {
+    /// 分面值（显示用文本）
+    pub value: String,
+
+    /// 该分面在原数据中的行数
+    pub row_count: usize,
+
+    /// 该分面的 ECharts option
+    pub option: serde_json::Value,
 }
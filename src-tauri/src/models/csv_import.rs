@@ -0,0 +1,89 @@
+// ============================================================================
+// models/csv_import.rs - CSV 导入选项数据模型
+// ============================================================================
+// 这个文件定义了 CSV 导入时可以覆盖的选项，以及大文件导入时推送的进度
+// 所有选项都是可选的，未指定的选项沿用自动检测 / 默认行为
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// CsvImportOptions - CSV 导入选项
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "separator": ";",
+///   "has_header": true,
+///   "quote_char": "\"",
+///   "encoding": "gbk",
+///   "skip_rows": 2,
+///   "null_values": ["NA", "-"],
+///   "dtype_overrides": { "zip_code": "String", "order_id": "String" }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CsvImportOptions {
+    /// 分隔符（单个 ASCII 字符），None 表示自动检测
+    #[serde(default)]
+    pub separator: Option<char>,
+
+    /// 第一行是否为表头，None 表示有表头
+    #[serde(default)]
+    pub has_header: Option<bool>,
+
+    /// 引号字符（单个 ASCII 字符），None 表示双引号
+    #[serde(default)]
+    pub quote_char: Option<char>,
+
+    /// 文件编码（WHATWG 编码标签，例如 "gbk"、"latin1"、"shift_jis"），None 表示 UTF-8
+    #[serde(default)]
+    pub encoding: Option<String>,
+
+    /// 表头之前要跳过的行数（例如导出工具写在开头的说明行）
+    #[serde(default)]
+    pub skip_rows: Option<usize>,
+
+    /// 视为空值的文本（例如 "NA"、"-"、"null"）
+    #[serde(default)]
+    pub null_values: Option<Vec<String>>,
+
+    /// 列类型覆盖：列名 -> 类型名（与 cast_types 相同，例如 "String"、"Int64"、"Date"）
+    ///
+    /// 指定的列不再自动推断类型，例如邮编、带前导零的编号强制读取为 String，避免被推断为整数后丢失前导零
+    #[serde(default)]
+    pub dtype_overrides: Option<HashMap<String, String>>,
+}
+
+/// ImportProgress - 大文件导入进度（通过 "import://progress" 事件推送）
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "job_id": "import-1",
+///   "file_path": "/data/events.csv",
+///   "bytes_read": 134217728,
+///   "total_bytes": 2147483648,
+///   "rows_parsed": 1048576,
+///   "done": false
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportProgress {
+    /// 导入任务 ID（可以传给 cancel_import 取消导入）
+    pub job_id: String,
+
+    /// 正在导入的文件路径
+    pub file_path: String,
+
+    /// 已读取的字节数（压缩文件为解压后的字节数）
+    pub bytes_read: u64,
+
+    /// 总字节数（压缩文件为解压后的字节数）
+    pub total_bytes: u64,
+
+    /// 已解析的行数
+    pub rows_parsed: usize,
+
+    /// 是否已读取完成
+    pub done: bool,
+}
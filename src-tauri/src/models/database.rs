@@ -0,0 +1,138 @@
+// ============================================================================
+// models/database.rs - 数据库连接相关数据模型
+// ============================================================================
+// 这个文件定义了从 PostgreSQL / MySQL 导入数据使用的数据结构：
+// - DatabaseKind: 数据库类型
+// - DatabaseConnection: 连接参数（每次调用时传入，不会保存到磁盘）
+// - DatabaseTable: 数据库中的表或视图
+// - SqliteIfExists: 导出到 SQLite 时表已存在的处理方式
+
+use serde::{Deserialize, Serialize};
+
+/// DatabaseKind - 数据库类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseKind {
+    Postgres,
+    Mysql,
+}
+
+impl DatabaseKind {
+    /// 默认端口
+    pub fn default_port(&self) -> u16 {
+        match self {
+            DatabaseKind::Postgres => 5432,
+            DatabaseKind::Mysql => 3306,
+        }
+    }
+
+    /// URL 协议名（用于历史记录中的数据来源，不包含密码）
+    pub fn scheme(&self) -> &'static str {
+        match self {
+            DatabaseKind::Postgres => "postgres",
+            DatabaseKind::Mysql => "mysql",
+        }
+    }
+}
+
+/// DatabaseConnection - 数据库连接参数
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "kind": "postgres",
+///   "host": "db.example.com",
+///   "port": 5432,
+///   "database": "analytics",
+///   "username": "reader",
+///   "password": "secret"
+/// }
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DatabaseConnection {
+    /// 数据库类型
+    pub kind: DatabaseKind,
+
+    /// 主机名或 IP 地址
+    pub host: String,
+
+    /// 端口（None 表示默认端口：PostgreSQL 5432，MySQL 3306）
+    #[serde(default)]
+    pub port: Option<u16>,
+
+    /// 数据库名称
+    pub database: String,
+
+    /// 用户名
+    pub username: String,
+
+    /// 密码（可选）
+    #[serde(default)]
+    pub password: Option<String>,
+
+    /// 连接超时（秒，None 表示 10 秒）
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+impl DatabaseConnection {
+    /// 端口（未指定时使用默认端口）
+    pub fn port(&self) -> u16 {
+        self.port.unwrap_or_else(|| self.kind.default_port())
+    }
+
+    /// 数据来源描述，例如 "postgres://reader@db.example.com:5432/analytics"（不包含密码）
+    pub fn source(&self) -> String {
+        format!(
+            "{}://{}@{}:{}/{}",
+            self.kind.scheme(),
+            self.username,
+            self.host,
+            self.port(),
+            self.database
+        )
+    }
+}
+
+/// 手动实现 Debug，避免密码出现在日志中
+impl std::fmt::Debug for DatabaseConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DatabaseConnection")
+            .field("kind", &self.kind)
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("database", &self.database)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "***"))
+            .field("timeout_secs", &self.timeout_secs)
+            .finish()
+    }
+}
+
+/// DatabaseTable - 数据库中的表或视图
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseTable {
+    /// 模式名（PostgreSQL 的 schema；MySQL 为数据库名）
+    pub schema: String,
+
+    /// 表名
+    pub name: String,
+
+    /// 是否为视图
+    pub is_view: bool,
+}
+
+/// SqliteIfExists - 导出到 SQLite 时目标表已存在的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SqliteIfExists {
+    /// 返回错误，不修改数据库
+    #[default]
+    Fail,
+
+    /// 删除原表后按当前数据重新创建
+    Replace,
+
+    /// 追加到原表（列名和列数需要与原表一致）
+    Append,
+}
@@ -7,6 +7,7 @@
 // 导入 serde 库，用于序列化和反序列化
 // 序列化：将 Rust 数据结构转换为 JSON（发送给前端）
 // 反序列化：将 JSON 转换为 Rust 数据结构（接收前端数据）
+use crate::models::CellStyle;
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -92,6 +93,42 @@ pub struct ColumnInfo {
     /// 这一列中有多少个空值（null/missing values）
     /// 用于数据质量评估
     pub null_count: usize,
+
+    /// 列摘要（最小值、最大值、不同值数量）
+    ///
+    /// 只有开启列摘要设置时才计算，供表头提示使用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<ColumnSummary>,
+}
+
+/// ColumnSummary - 列摘要
+///
+/// 最小值、最大值按列类型比较（数值、日期时间、字符串按字典序），以文本形式保存
+/// 布尔、列表、结构体等没有大小顺序的列不计算最小值和最大值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnSummary {
+    /// 最小值（没有非空值或类型不支持时为 None）
+    pub min: Option<String>,
+
+    /// 最大值（没有非空值或类型不支持时为 None）
+    pub max: Option<String>,
+
+    /// 不同值数量（包括空值）
+    pub distinct_count: usize,
+
+    /// 不同值数量是否精确（大数据集按抽样估计时为 false）
+    pub distinct_exact: bool,
+}
+
+/// ColumnSummarySettings - 列摘要设置
+///
+/// 开启后，数据集元信息中的每一列附带最小值、最大值和不同值数量，
+/// 表头提示不需要为每一列单独调用 get_column_stats
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColumnSummarySettings {
+    /// 是否计算列摘要
+    pub enabled: bool,
 }
 
 // ============================================================================
@@ -133,6 +170,13 @@ pub struct DatasetData {
     /// 例如：如果数据集有 10000 行，但只返回前 100 行
     /// 那么 rows.len() = 100，但 total_rows = 10000
     pub total_rows: usize,
+
+    /// 单元格样式标签（条件格式的计算结果）
+    ///
+    /// 只有请求时才返回；只包含至少有一个标签的单元格
+    /// 例如：[{ "row": 0, "column": "利润", "tags": ["negative"] }]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cell_styles: Option<Vec<CellStyle>>,
 }
 
 // ============================================================================
@@ -255,4 +299,187 @@ pub struct ColumnStats {
 
     /// False 数量（仅布尔类型）
     pub false_count: Option<usize>,
+
+    /// 列表长度和元素统计（仅列表类型）
+    pub list_stats: Option<ListStats>,
+}
+
+/// ListStats - 列表列的统计信息
+///
+/// 长度统计只计算非空列表；元素统计把所有列表展开后计算
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListStats {
+    /// 最短列表长度
+    pub min_length: Option<usize>,
+
+    /// 最长列表长度
+    pub max_length: Option<usize>,
+
+    /// 平均列表长度
+    pub mean_length: Option<f64>,
+
+    /// 空列表数量
+    pub empty_count: usize,
+
+    /// 元素总数（含空值元素）
+    pub element_count: usize,
+
+    /// 空值元素数量
+    pub element_null_count: usize,
+
+    /// 不同元素的数量
+    pub element_unique_count: usize,
+
+    /// 元素最小值（仅数值元素）
+    pub element_min: Option<f64>,
+
+    /// 元素最大值（仅数值元素）
+    pub element_max: Option<f64>,
+
+    /// 元素平均值（仅数值元素）
+    pub element_mean: Option<f64>,
+}
+
+// ============================================================================
+// 近似重复值聚类结果
+// ============================================================================
+/// SimilarCluster - 一组近似重复的值
+///
+/// 用于快速构建"替换值"映射，例如把 "ACME, Inc."、"Acme Inc" 统一为 "Acme Inc"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarCluster {
+    /// 代表值（簇中出现次数最多的值）
+    pub canonical: String,
+
+    /// 簇内所有值的总出现次数
+    pub total_count: usize,
+
+    /// 簇内的值（第一个为代表值）
+    pub members: Vec<SimilarValue>,
+}
+
+/// SimilarValue - 簇中的单个值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarValue {
+    /// 原始值
+    pub value: String,
+
+    /// 出现次数
+    pub count: usize,
+
+    /// 与代表值的相似度（0.0 ~ 1.0，规范化后计算）
+    pub similarity: f64,
+}
+
+// ============================================================================
+// 数据集指纹
+// ============================================================================
+/// DatasetFingerprint - 数据集内容指纹
+///
+/// 用于判断数据在两次会话之间是否真正发生了变化
+/// 哈希只依赖列名、数据类型和值，与导入时间、历史记录无关
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetFingerprint {
+    /// 整个数据集的摘要（SHA-256，十六进制）
+    pub digest: String,
+
+    /// 行数
+    pub row_count: usize,
+
+    /// 每列的指纹（按列顺序）
+    pub columns: Vec<ColumnFingerprint>,
+}
+
+/// ColumnFingerprint - 单列内容指纹
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnFingerprint {
+    /// 列名
+    pub name: String,
+
+    /// 数据类型
+    pub dtype: String,
+
+    /// 列内容的摘要（SHA-256，十六进制）
+    pub hash: String,
+}
+
+// ============================================================================
+// 未匹配值报告
+// ============================================================================
+/// UnmatchedValuesReport - 转换操作中无法识别的值
+///
+/// 无法识别的值会被转换为空值，报告用于提示用户补充映射规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnmatchedValuesReport {
+    /// 处理的列
+    pub column: String,
+
+    /// 无法识别的行数（不含原本就为空的行）
+    pub unmatched_count: usize,
+
+    /// 无法识别的不同值及其出现次数（按次数降序，最多 50 个）
+    pub values: Vec<(String, usize)>,
+}
+
+// ============================================================================
+// Excel 工作表信息
+// ============================================================================
+/// ExcelSheetInfo - 工作簿中一个工作表的概况（用于导入前选择工作表）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExcelSheetInfo {
+    /// 工作表名称（传给 import_excel 的 sheet_name）
+    pub name: String,
+
+    /// 数据行数（不含表头）
+    pub rows: usize,
+
+    /// 列数
+    pub columns: usize,
+
+    /// 是否为隐藏的工作表
+    pub hidden: bool,
+}
+
+// ============================================================================
+// 抽样导入
+// ============================================================================
+/// SampleStrategy - 抽样导入的方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SampleStrategy {
+    /// 前 N 行（最快，只读取文件开头）
+    #[default]
+    Head,
+
+    /// 随机 N 行（需要读完整个文件，但内存中只保留 N 行），保持文件中的行顺序
+    Random,
+}
+
+// ============================================================================
+// 演示数据
+// ============================================================================
+/// DemoPreset - 演示数据集的结构预设
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DemoPreset {
+    /// 销售订单：日期、地区、产品、数量、单价、折扣（含空值）、金额
+    #[default]
+    Sales,
+
+    /// 时间序列：按小时采样的多个传感器读数（温度、湿度）
+    Timeseries,
+
+    /// 鸢尾花风格的分类数据：四个数值特征 + 品种
+    Iris,
+}
+
+impl DemoPreset {
+    /// 预设名称（用于导入记录中的来源地址）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DemoPreset::Sales => "sales",
+            DemoPreset::Timeseries => "timeseries",
+            DemoPreset::Iris => "iris",
+        }
+    }
 }
@@ -0,0 +1,352 @@
+// ============================================================================
+// models/display.rs - 列显示设置数据模型
+// ============================================================================
+// 这个文件定义了列的显示格式等"只影响展示、不影响数据"的设置
+// 显示设置随历史条目一起保存，因此同样支持 undo/redo
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// ============================================================================
+// 列显示格式
+// ============================================================================
+/// ColumnFormat - 单列的显示格式
+///
+/// 只改变值在表格和 CSV 导出中的呈现方式，不改变底层数据类型
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "thousands_separator": true,
+///   "decimal_places": 2,
+///   "date_format": null,
+///   "percentage": false
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ColumnFormat {
+    /// 是否使用千分位分隔符（仅数值列），例如 1234567 → 1,234,567
+    #[serde(default)]
+    pub thousands_separator: bool,
+
+    /// 保留的小数位数（仅数值列），None 表示保持原样
+    #[serde(default)]
+    pub decimal_places: Option<usize>,
+
+    /// 日期格式（仅日期/日期时间列），使用 chrono 格式字符串，例如 "%Y/%m/%d"
+    #[serde(default)]
+    pub date_format: Option<String>,
+
+    /// 是否以百分比显示（仅数值列），例如 0.125 → 12.5%
+    #[serde(default)]
+    pub percentage: bool,
+}
+
+// ============================================================================
+// 条件格式
+// ============================================================================
+/// ConditionalCondition - 条件格式的判断条件
+///
+/// 序列化示例：
+/// ```json
+/// { "type": "above_threshold", "value": 1000 }
+/// { "type": "outlier", "z_score": 3.0 }
+/// { "type": "expression", "sql": "amount > budget" }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConditionalCondition {
+    /// 负数（仅数值列）
+    Negative,
+
+    /// 正数（仅数值列）
+    Positive,
+
+    /// 空值
+    IsNull,
+
+    /// 大于阈值（仅数值列）
+    AboveThreshold { value: f64 },
+
+    /// 小于阈值（仅数值列）
+    BelowThreshold { value: f64 },
+
+    /// 在区间 [min, max] 内（仅数值列）
+    Between { min: f64, max: f64 },
+
+    /// 离群值：与整列均值的距离超过 z_score 个标准差（仅数值列）
+    Outlier { z_score: f64 },
+
+    /// 任意 SQL 条件（可以引用其他列），结果为 true 时标记本列单元格
+    Expression { sql: String },
+}
+
+impl ConditionalCondition {
+    /// 条件是否只适用于数值列
+    pub fn requires_numeric(&self) -> bool {
+        !matches!(
+            self,
+            ConditionalCondition::IsNull | ConditionalCondition::Expression { .. }
+        )
+    }
+}
+
+/// ConditionalFormatRule - 单条条件格式规则
+///
+/// 满足条件的单元格会被打上 tag（例如 "negative"、"outlier"），由前端决定具体样式
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConditionalFormatRule {
+    /// 应用规则的列
+    pub column: String,
+
+    /// 判断条件
+    pub condition: ConditionalCondition,
+
+    /// 样式标签
+    pub tag: String,
+}
+
+impl ConditionalFormatRule {
+    /// 规则用到的所有列：应用规则的列，以及 SQL 条件中引用的其他列
+    ///
+    /// SQL 条件无法解析时返回 None
+    pub fn referenced_columns(&self) -> Option<Vec<String>> {
+        let mut columns = vec![self.column.clone()];
+        if let ConditionalCondition::Expression { sql } = &self.condition {
+            let predicate = polars::sql::sql_expr(sql).ok()?;
+            for name in predicate.meta().root_names() {
+                if !columns.iter().any(|column| column == name.as_str()) {
+                    columns.push(name.to_string());
+                }
+            }
+        }
+        Some(columns)
+    }
+}
+
+/// CellStyle - 单元格样式标签（稀疏表示，只返回有标签的单元格）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CellStyle {
+    /// 行号（相对于当前页的第一行，从 0 开始）
+    pub row: usize,
+
+    /// 列名
+    pub column: String,
+
+    /// 样式标签（按规则顺序）
+    pub tags: Vec<String>,
+}
+
+// ============================================================================
+// 表格布局
+// ============================================================================
+/// ViewState - 表格布局（隐藏列、固定列、列宽）
+///
+/// 与数据一起保存在历史中，而不是保存在前端 localStorage 中
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "hidden_columns": ["internal_id"],
+///   "pinned_columns": ["name"],
+///   "column_widths": { "name": 180, "amount": 120 }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ViewState {
+    /// 隐藏的列
+    #[serde(default)]
+    pub hidden_columns: Vec<String>,
+
+    /// 固定在左侧的列（按固定顺序）
+    #[serde(default)]
+    pub pinned_columns: Vec<String>,
+
+    /// 列名 -> 列宽（像素）
+    #[serde(default)]
+    pub column_widths: HashMap<String, u32>,
+}
+
+impl ViewState {
+    /// 布局中引用的所有列名
+    pub fn referenced_columns(&self) -> impl Iterator<Item = &String> {
+        self.hidden_columns
+            .iter()
+            .chain(self.pinned_columns.iter())
+            .chain(self.column_widths.keys())
+    }
+
+    /// 只保留在 DataFrame 中仍然存在的列
+    pub fn retain_existing(&self, df: &DataFrame) -> Self {
+        let exists = |name: &String| df.get_column_index(name).is_some();
+
+        Self {
+            hidden_columns: self.hidden_columns.iter().filter(|n| exists(n)).cloned().collect(),
+            pinned_columns: self.pinned_columns.iter().filter(|n| exists(n)).cloned().collect(),
+            column_widths: self
+                .column_widths
+                .iter()
+                .filter(|(name, _)| exists(name))
+                .map(|(name, width)| (name.clone(), *width))
+                .collect(),
+        }
+    }
+}
+
+// ============================================================================
+// 浮点数精度
+// ============================================================================
+/// FloatRounding - 浮点数传给前端前的舍入方式
+///
+/// 序列化示例：
+/// ```json
+/// { "type": "significant_digits", "digits": 12 }
+/// { "type": "decimal_places", "places": 2 }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FloatRounding {
+    /// 保留有效数字位数（1-17），例如 12 位时 0.30000000000000004 → 0.3
+    SignificantDigits { digits: u8 },
+
+    /// 保留小数位数，例如 2 位时 3.14159 → 3.14
+    DecimalPlaces { places: u8 },
+}
+
+impl FloatRounding {
+    /// 舍入一个浮点数（通过十进制文本舍入，结果不会再引入二进制误差）
+    pub fn apply(&self, value: f64) -> f64 {
+        let text = match *self {
+            FloatRounding::SignificantDigits { digits } => {
+                format!("{:.*e}", digits.clamp(1, 17) as usize - 1, value)
+            }
+            FloatRounding::DecimalPlaces { places } => format!("{:.*}", places as usize, value),
+        };
+        text.parse().unwrap_or(value)
+    }
+}
+
+/// NonFinitePolicy - NaN / 无穷大传给前端时的处理方式
+///
+/// JSON 没有 NaN / Infinity，默认输出为 null（与空值无法区分）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NonFinitePolicy {
+    /// 输出为 null
+    #[default]
+    Null,
+
+    /// 输出为字符串 "NaN" / "Inf" / "-Inf"
+    Text,
+
+    /// 返回错误（提示先用 replace_non_finite 替换）
+    Error,
+}
+
+/// FloatPrecision - 浮点列在表格中的显示精度
+///
+/// 只影响传给前端的数值（仍然是数字，不是字符串），不改变底层数据
+/// 设置了列显示格式（ColumnFormat）的列按显示格式输出，不使用这里的舍入设置
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "default": { "type": "significant_digits", "digits": 12 },
+///   "columns": { "price": { "type": "decimal_places", "places": 2 } },
+///   "non_finite": "text"
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FloatPrecision {
+    /// 所有浮点列的舍入方式（None 表示完整精度）
+    #[serde(default)]
+    pub default: Option<FloatRounding>,
+
+    /// 列名 -> 舍入方式（优先于 default）
+    #[serde(default)]
+    pub columns: HashMap<String, FloatRounding>,
+
+    /// NaN / 无穷大的处理方式（所有浮点列，包括设置了显示格式的列）
+    #[serde(default)]
+    pub non_finite: NonFinitePolicy,
+}
+
+impl FloatPrecision {
+    /// 指定列使用的舍入方式（None 表示完整精度）
+    pub fn rounding_for(&self, column: &str) -> Option<FloatRounding> {
+        self.columns.get(column).copied().or(self.default)
+    }
+
+    /// 只保留在 DataFrame 中仍然存在的列
+    pub fn retain_existing(&self, df: &DataFrame) -> Self {
+        Self {
+            default: self.default,
+            columns: self
+                .columns
+                .iter()
+                .filter(|(name, _)| df.get_column_index(name).is_some())
+                .map(|(name, rounding)| (name.clone(), *rounding))
+                .collect(),
+            non_finite: self.non_finite,
+        }
+    }
+}
+
+// ============================================================================
+// 显示设置
+// ============================================================================
+/// DisplaySettings - 一个历史状态上的全部显示设置
+///
+/// 存储在 HistoryEntry 中：
+/// - 修改显示设置会产生新的历史条目（可撤销）
+/// - 数据操作会继承上一个状态的显示设置（自动丢弃已不存在的列）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DisplaySettings {
+    /// 列名 -> 显示格式
+    #[serde(default)]
+    pub column_formats: HashMap<String, ColumnFormat>,
+
+    /// 条件格式规则（按顺序计算）
+    #[serde(default)]
+    pub conditional_formats: Vec<ConditionalFormatRule>,
+
+    /// 表格布局（隐藏列、固定列、列宽）
+    #[serde(default)]
+    pub view_state: ViewState,
+
+    /// 浮点列的显示精度
+    #[serde(default)]
+    pub float_precision: FloatPrecision,
+}
+
+impl DisplaySettings {
+    /// 为新的 DataFrame 继承显示设置
+    ///
+    /// 只保留在新 DataFrame 中仍然存在的列；条件格式规则用到的列（包括 SQL 条件引用的列）都存在时才保留
+    pub fn carry_over(&self, df: &DataFrame) -> Self {
+        let column_formats = self
+            .column_formats
+            .iter()
+            .filter(|(name, _)| df.get_column_index(name).is_some())
+            .map(|(name, format)| (name.clone(), format.clone()))
+            .collect();
+
+        let conditional_formats = self
+            .conditional_formats
+            .iter()
+            .filter(|rule| {
+                rule.referenced_columns()
+                    .is_some_and(|columns| columns.iter().all(|name| df.get_column_index(name).is_some()))
+            })
+            .cloned()
+            .collect();
+
+        Self {
+            column_formats,
+            conditional_formats,
+            view_state: self.view_state.retain_existing(df),
+            float_precision: self.float_precision.retain_existing(df),
+        }
+    }
+}
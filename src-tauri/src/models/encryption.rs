@@ -0,0 +1,55 @@
+// ============================================================================
+// models/encryption.rs - 导出加密相关数据模型
+// ============================================================================
+// 这个文件定义了导出时的加密方式：
+// - Columns: 只加密指定的敏感列（AES-256-GCM，密钥由口令派生）
+// - Zip: 将导出文件打包为带密码的 zip（AES-256）
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// 导出加密方式
+// ============================================================================
+/// ExportEncryption - 导出加密方式
+///
+/// 序列化示例：
+/// ```json
+/// { "type": "columns", "columns": ["salary", "id_card"], "passphrase": "..." }
+/// { "type": "zip", "password": "..." }
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExportEncryption {
+    /// 加密指定列：每个非空单元格替换为 "enc:v1:{base64}" 密文字符串
+    ///
+    /// 密文 = salt(16 字节) + nonce(12 字节) + AES-256-GCM 密文，
+    /// 密钥 = PBKDF2-HMAC-SHA256(passphrase, salt, 600000 次)
+    Columns { columns: Vec<String>, passphrase: String },
+
+    /// 将导出文件打包为带密码的 zip（WinZip AES-256，常见解压工具均可打开）
+    Zip { password: String },
+}
+
+impl ExportEncryption {
+    /// 口令或密码
+    pub fn secret(&self) -> &str {
+        match self {
+            ExportEncryption::Columns { passphrase, .. } => passphrase,
+            ExportEncryption::Zip { password } => password,
+        }
+    }
+}
+
+/// 手动实现 Debug，避免口令出现在日志中
+impl std::fmt::Debug for ExportEncryption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportEncryption::Columns { columns, .. } => f
+                .debug_struct("Columns")
+                .field("columns", columns)
+                .field("passphrase", &"***")
+                .finish(),
+            ExportEncryption::Zip { .. } => f.debug_struct("Zip").field("password", &"***").finish(),
+        }
+    }
+}
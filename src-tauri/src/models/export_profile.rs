@@ -0,0 +1,226 @@
+// ============================================================================
+// models/export_profile.rs - 导出选项数据模型
+// ============================================================================
+// 这个文件定义了 CSV 导出预设：
+// - CsvExportProfile: 一组命名的导出选项（分隔符、编码、引号、日期格式等）
+// - CsvEncoding / CsvQuoteStyle / LineEnding: 各项选项的取值
+//
+// 内置预设（Excel-DE、RFC4180、Tab for BigQuery）不能修改或删除，
+// 用户预设保存在应用配置目录中
+//
+// 以及 Parquet 导出的压缩方式和复制到剪贴板的文本格式：
+// - ParquetExportCompression: 压缩算法和压缩级别
+// - ClipboardFormat: TSV / CSV / Markdown
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// 选项取值
+// ============================================================================
+/// CsvEncoding - 文件编码
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvEncoding {
+    /// UTF-8
+    #[default]
+    Utf8,
+
+    /// UTF-8 带 BOM（Excel 打开时可以正确识别中文等非 ASCII 字符）
+    Utf8Bom,
+}
+
+/// CsvQuoteStyle - 何时给字段加引号
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvQuoteStyle {
+    /// 只在字段包含分隔符、引号或换行时加引号
+    #[default]
+    Necessary,
+
+    /// 所有字段都加引号
+    Always,
+
+    /// 非数值字段加引号
+    NonNumeric,
+
+    /// 从不加引号
+    Never,
+}
+
+/// LineEnding - 换行符
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineEnding {
+    /// \n
+    #[default]
+    Lf,
+
+    /// \r\n（RFC 4180、Windows）
+    Crlf,
+}
+
+impl LineEnding {
+    /// 换行符文本
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        }
+    }
+}
+
+// ============================================================================
+// 导出预设
+// ============================================================================
+/// CsvExportProfile - CSV 导出预设
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "name": "Excel-DE",
+///   "delimiter": ";",
+///   "encoding": "utf8_bom",
+///   "quote_char": "\"",
+///   "quote_style": "necessary",
+///   "line_ending": "crlf",
+///   "include_header": true,
+///   "decimal_comma": true,
+///   "date_format": "%d.%m.%Y",
+///   "datetime_format": "%d.%m.%Y %H:%M:%S",
+///   "null_value": "",
+///   "builtin": true
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CsvExportProfile {
+    /// 预设名称（唯一）
+    pub name: String,
+
+    /// 字段分隔符（单个 ASCII 字符）
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+
+    /// 文件编码
+    #[serde(default)]
+    pub encoding: CsvEncoding,
+
+    /// 引号字符（单个 ASCII 字符）
+    #[serde(default = "default_quote_char")]
+    pub quote_char: char,
+
+    /// 引号策略
+    #[serde(default)]
+    pub quote_style: CsvQuoteStyle,
+
+    /// 换行符
+    #[serde(default)]
+    pub line_ending: LineEnding,
+
+    /// 是否写表头
+    #[serde(default = "default_true")]
+    pub include_header: bool,
+
+    /// 是否使用逗号作为小数点（例如德语地区的 Excel）
+    #[serde(default)]
+    pub decimal_comma: bool,
+
+    /// 日期列格式（chrono 格式字符串，None 表示 ISO 8601）
+    #[serde(default)]
+    pub date_format: Option<String>,
+
+    /// 日期时间列格式（chrono 格式字符串，None 表示 ISO 8601）
+    #[serde(default)]
+    pub datetime_format: Option<String>,
+
+    /// 空值的输出文本
+    #[serde(default)]
+    pub null_value: String,
+
+    /// 是否为内置预设（只由后端设置）
+    #[serde(default)]
+    pub builtin: bool,
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+fn default_quote_char() -> char {
+    '"'
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for CsvExportProfile {
+    /// 默认选项（与未指定预设时的导出行为一致）
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            delimiter: default_delimiter(),
+            encoding: CsvEncoding::Utf8,
+            quote_char: default_quote_char(),
+            quote_style: CsvQuoteStyle::Necessary,
+            line_ending: LineEnding::Lf,
+            include_header: true,
+            decimal_comma: false,
+            date_format: None,
+            datetime_format: None,
+            null_value: String::new(),
+            builtin: false,
+        }
+    }
+}
+
+// ============================================================================
+// Parquet 压缩方式
+// ============================================================================
+/// ParquetExportCompression - Parquet 导出的压缩算法
+///
+/// 序列化示例：
+/// ```json
+/// { "type": "snappy" }
+/// { "type": "zstd", "level": 9 }
+/// { "type": "gzip", "level": 6 }
+/// { "type": "uncompressed" }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ParquetExportCompression {
+    /// 不压缩
+    Uncompressed,
+
+    /// Snappy（压缩和解压都很快，压缩率一般）
+    Snappy,
+
+    /// Gzip，级别 0-9（不指定时使用默认级别）
+    Gzip {
+        #[serde(default)]
+        level: Option<u8>,
+    },
+
+    /// Zstd，级别 1-22（不指定时使用默认级别）
+    Zstd {
+        #[serde(default)]
+        level: Option<i32>,
+    },
+}
+
+// ============================================================================
+// 剪贴板文本格式
+// ============================================================================
+/// ClipboardFormat - 复制到剪贴板时的文本格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClipboardFormat {
+    /// 制表符分隔（粘贴到 Excel 时自动分列）
+    #[default]
+    Tsv,
+
+    /// 逗号分隔
+    Csv,
+
+    /// Markdown 表格（粘贴到邮件、文档、聊天工具）
+    Markdown,
+}
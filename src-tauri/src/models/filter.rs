@@ -0,0 +1,132 @@
+// ============================================================================
+// models/filter.rs - 筛选相关数据模型
+// ============================================================================
+// 这个文件定义了筛选操作使用的数据结构：
+// - NullPolicy: 条件结果为 null 时的处理策略
+// - FilterOperator / FilterCondition: 结构化筛选条件
+// - ExportSubset: 导出时只导出部分列 / 部分行
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// 空值处理策略
+// ============================================================================
+/// NullPolicy - 筛选条件结果为 null 时如何处理该行
+///
+/// SQL 使用三值逻辑：`amount > 100` 在 amount 为 null 时结果是 null（不是 false）
+/// 默认行为与 SQL 一致（null 视为 false，行被删除），这里允许显式选择
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NullPolicy {
+    /// null 视为 false（删除该行，SQL 默认行为）
+    #[default]
+    NullAsFalse,
+
+    /// 保留条件结果为 null 的行
+    KeepNulls,
+
+    /// 只保留条件结果为 null 的行
+    OnlyNulls,
+}
+
+impl NullPolicy {
+    /// 策略的中文名称（用于操作描述）
+    pub fn label(&self) -> &'static str {
+        match self {
+            NullPolicy::NullAsFalse => "空值视为不满足",
+            NullPolicy::KeepNulls => "保留空值",
+            NullPolicy::OnlyNulls => "仅空值",
+        }
+    }
+}
+
+// ============================================================================
+// 结构化筛选条件
+// ============================================================================
+/// FilterOperator - 结构化筛选的比较运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterOperator {
+    /// 等于
+    Eq,
+    /// 不等于
+    NotEq,
+    /// 大于
+    Gt,
+    /// 大于等于
+    GtEq,
+    /// 小于
+    Lt,
+    /// 小于等于
+    LtEq,
+    /// 包含子串（仅字符串列）
+    Contains,
+    /// 为空
+    IsNull,
+    /// 不为空
+    IsNotNull,
+    /// 等于列表中任一值（value 为非空数组）
+    In,
+    /// 不等于列表中任何值（value 为非空数组）
+    NotIn,
+    /// 在区间内，包含两端（value 为 [下限, 上限]）
+    Between,
+    /// LIKE 模式匹配（% 匹配任意字符，_ 匹配单个字符，仅字符串列）
+    Like,
+    /// 不区分大小写的 LIKE 模式匹配
+    #[serde(rename = "ilike")]
+    ILike,
+}
+
+impl FilterOperator {
+    /// 运算符是否需要比较值
+    pub fn needs_value(&self) -> bool {
+        !matches!(self, FilterOperator::IsNull | FilterOperator::IsNotNull)
+    }
+}
+
+/// FilterCondition - 单个结构化筛选条件
+///
+/// 序列化示例：
+/// ```json
+/// { "column": "region", "operator": "eq", "value": "EU" }
+/// { "column": "email", "operator": "is_null" }
+/// { "column": "region", "operator": "in", "value": ["EU", "US"] }
+/// { "column": "amount", "operator": "between", "value": [10, 100] }
+/// { "column": "name", "operator": "ilike", "value": "%smith" }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterCondition {
+    /// 列名
+    pub column: String,
+
+    /// 运算符
+    pub operator: FilterOperator,
+
+    /// 比较值（is_null / is_not_null 不需要；in / not_in / between 为数组）
+    #[serde(default)]
+    pub value: Option<serde_json::Value>,
+}
+
+// ============================================================================
+// 导出子集
+// ============================================================================
+/// ExportSubset - 导出时只导出当前数据的一部分（不修改数据集，不记录历史）
+///
+/// 序列化示例：
+/// ```json
+/// { "columns": ["order_id", "amount"], "filter": "region = 'EU' AND amount > 100" }
+/// { "filter": "email IS NULL", "null_policy": "keep_nulls" }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportSubset {
+    /// 要导出的列，按给定顺序输出（None 或空列表表示全部列）
+    pub columns: Option<Vec<String>>,
+
+    /// SQL WHERE 条件，不含 "WHERE" 关键字（None 表示全部行）
+    pub filter: Option<String>,
+
+    /// 条件结果为 null 时的处理策略
+    pub null_policy: NullPolicy,
+}
@@ -0,0 +1,64 @@
+// ============================================================================
+// models/gaps.rs - 序列缺口检测数据模型
+// ============================================================================
+// 这个文件定义了序列缺口检测的结果：
+// - SequenceGap: 一个缺口（相邻两个已有值之间缺少的连续值）
+// - GapReport: 整列（或按分组）的缺口汇总
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// 缺口
+// ============================================================================
+/// SequenceGap - 序列中的一个缺口
+///
+/// 例如发票号 1001、1002、1005 之间的缺口：
+/// after = "1002"，before = "1005"，first_missing = "1003"，last_missing = "1004"，missing_count = 2
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequenceGap {
+    /// 分组值（与 GapReport.group_by 一一对应；空值为 null）
+    pub group: Vec<Option<String>>,
+
+    /// 缺口前的最后一个已有值
+    pub after: String,
+
+    /// 缺口后的第一个已有值
+    pub before: String,
+
+    /// 第一个缺失值
+    pub first_missing: String,
+
+    /// 最后一个缺失值
+    pub last_missing: String,
+
+    /// 缺失值数量
+    pub missing_count: u64,
+}
+
+// ============================================================================
+// 缺口报告
+// ============================================================================
+/// GapReport - 序列缺口检测结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GapReport {
+    /// 检测的列
+    pub column: String,
+
+    /// 分组列
+    pub group_by: Vec<String>,
+
+    /// 步长（整数列为数值，日期 / 日期时间列为时间间隔文本，例如 "1d"）
+    pub step: String,
+
+    /// 缺口数量
+    pub gap_count: usize,
+
+    /// 缺失值总数
+    pub missing_count: u64,
+
+    /// 缺口明细（按分组和值排序，最多返回 MAX_GAPS 个）
+    pub gaps: Vec<SequenceGap>,
+
+    /// 缺口明细是否被截断
+    pub truncated: bool,
+}
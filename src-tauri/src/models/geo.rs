@@ -0,0 +1,67 @@
+// ============================================================================
+// models/geo.rs - 地理坐标相关数据模型
+// ============================================================================
+// 这个文件定义了经纬度处理使用的数据结构：
+// - CoordinateReport: 经纬度列校验结果
+// - GeoBucketMethod: 坐标分桶方式（网格 / Geohash）
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// 坐标校验报告
+// ============================================================================
+/// CoordinateReport - 经纬度列校验结果
+///
+/// 有效坐标：纬度在 [-90, 90]，经度在 [-180, 180]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoordinateReport {
+    /// 总行数
+    pub total_rows: usize,
+
+    /// 有效坐标数量
+    pub valid_count: usize,
+
+    /// 纬度或经度为空的数量
+    pub null_count: usize,
+
+    /// 超出范围的数量
+    pub out_of_range_count: usize,
+
+    /// 疑似经纬度颠倒的数量（交换后即为有效坐标）
+    pub swapped_suspect_count: usize,
+
+    /// 无效坐标的行号示例（最多 20 个，从 0 开始）
+    pub invalid_rows_sample: Vec<usize>,
+}
+
+// ============================================================================
+// 坐标分桶方式
+// ============================================================================
+/// GeoBucketMethod - 坐标分桶方式
+///
+/// 序列化示例：
+/// ```json
+/// { "type": "grid", "cell_size": 0.1 }
+/// { "type": "geohash", "precision": 6 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GeoBucketMethod {
+    /// 等经纬度网格，cell_size 为网格边长（度）
+    ///
+    /// 桶标识为网格西南角坐标，例如 "39.9,116.4"
+    Grid { cell_size: f64 },
+
+    /// Geohash 编码，precision 为字符数（1 ~ 12）
+    Geohash { precision: usize },
+}
+
+impl GeoBucketMethod {
+    /// 分桶方式的中文描述（用于操作描述）
+    pub fn label(&self) -> String {
+        match self {
+            GeoBucketMethod::Grid { cell_size } => format!("网格 {}°", cell_size),
+            GeoBucketMethod::Geohash { precision } => format!("Geohash 精度 {}", precision),
+        }
+    }
+}
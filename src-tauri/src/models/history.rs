@@ -4,7 +4,11 @@
 // 这个文件定义了操作历史相关的数据结构
 // 用于实现 undo/redo 功能和操作历史管理
 
-use crate::models::DatasetInfo;
+use crate::models::chart::{ChartConfig, ChartType};
+use crate::models::{
+    CastRule, ColumnFormat, ConditionalFormatRule, CsvImportOptions, DatasetInfo, DisplaySettings, FilterCondition,
+    FloatPrecision, GeoBucketMethod, NullPolicy, ViewState,
+};
 use polars::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -35,7 +39,15 @@ use std::collections::HashMap;
 #[serde(tag = "type", content = "params")]
 pub enum OperationType {
     /// 导入文件操作
-    Import { file_path: String },
+    ///
+    /// 参数：
+    /// - file_path: 文件路径或 URL
+    /// - options: 导入时使用的选项（从源文件刷新时沿用；旧版本的记录中没有）
+    Import {
+        file_path: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        options: Option<ImportOptions>,
+    },
 
     /// 横表转纵表（Unpivot/Melt）
     /// 将宽格式转为长格式
@@ -77,8 +89,13 @@ pub enum OperationType {
     /// 选择列
     ///
     /// 参数：
-    /// - columns: 要保留的列名列表
-    SelectColumns { columns: Vec<String> },
+    /// - columns: 要保留的列名列表（结果按列表顺序排列）
+    /// - keep_remaining: 是否保留其余列（排在列表之后，保持原顺序），即只调整列顺序
+    SelectColumns {
+        columns: Vec<String>,
+        #[serde(default)]
+        keep_remaining: bool,
+    },
 
     /// 删除列
     ///
@@ -99,6 +116,16 @@ pub enum OperationType {
     ///   目标类型: "Int64", "Float64", "String", "Boolean", "Date"
     CastTypes { mapping: HashMap<String, String> },
 
+    /// 按列名模式批量转换列类型
+    CastTypesByPattern {
+        /// 转换规则
+        rules: Vec<CastRule>,
+        /// 使用的转换预设名称
+        profile: Option<String>,
+        /// 实际转换的列
+        columns: Vec<String>,
+    },
+
     /// 排序
     ///
     /// 参数：
@@ -114,8 +141,26 @@ pub enum OperationType {
     /// 筛选过滤
     ///
     /// 参数：
-    /// - expression: Polars 表达式字符串
-    Filter { expression: String },
+    /// - expression: SQL WHERE 条件字符串
+    /// - null_policy: 条件结果为 null 时的处理策略
+    Filter {
+        expression: String,
+        #[serde(default)]
+        null_policy: NullPolicy,
+    },
+
+    /// 结构化筛选
+    ///
+    /// 参数：
+    /// - conditions: 筛选条件列表
+    /// - match_all: true 为 AND 组合，false 为 OR 组合
+    /// - null_policy: 条件结果为 null 时的处理策略
+    FilterRows {
+        conditions: Vec<FilterCondition>,
+        match_all: bool,
+        #[serde(default)]
+        null_policy: NullPolicy,
+    },
 
     /// 空值填充
     ///
@@ -187,6 +232,392 @@ pub enum OperationType {
         center: bool,
         min_periods: Option<usize>,
     },
+
+    /// 批量移动窗口计算
+    ///
+    /// 对多个列同时应用一个或多个移动窗口函数，只产生一条历史记录
+    ///
+    /// 参数：
+    /// - columns: 要计算的列
+    /// - functions: 移动窗口函数列表
+    /// - quantile: 分位数（仅 Quantile 函数使用）
+    RollingBatch {
+        columns: Vec<String>,
+        functions: Vec<RollingFunction>,
+        window_size: usize,
+        quantile: Option<f64>,
+        center: bool,
+        min_periods: Option<usize>,
+    },
+
+    /// 邮箱规范化（去除空白、转小写）
+    ///
+    /// 参数：
+    /// - columns: 处理的列
+    NormalizeEmails { columns: Vec<String> },
+
+    /// 电话号码规范化为 E.164（写入新列 "{column}_e164"）
+    ///
+    /// 参数：
+    /// - column: 电话号码列
+    /// - default_country_code: 默认国家码
+    NormalizePhones {
+        column: String,
+        default_country_code: Option<String>,
+    },
+
+    /// URL 拆分为域名和路径（写入新列 "{column}_domain"、"{column}_path"）
+    ///
+    /// 参数：
+    /// - column: URL 列
+    ParseUrls { column: String },
+
+    /// IP 地址解析（写入新列 "{column}_ip_version"、"{column}_ip_valid"，可选 "{column}_country"）
+    ///
+    /// 参数：
+    /// - column: IP 地址列
+    /// - mmdb_path: GeoIP 数据库路径（可选）
+    ParseIpAddresses { column: String, mmdb_path: Option<String> },
+
+    /// User-Agent 解析（写入新列 "{column}_browser"、"{column}_os"）
+    ///
+    /// 参数：
+    /// - column: User-Agent 列
+    ParseUserAgents { column: String },
+
+    /// 金额字符串解析为数值
+    ///
+    /// 参数：
+    /// - column: 金额列
+    /// - decimal_separator: 小数点字符
+    /// - output_column: 新列名（None 表示原地替换）
+    ParseCurrency {
+        column: String,
+        decimal_separator: String,
+        output_column: Option<String>,
+    },
+
+    /// 百分比字符串转换为小数（"12.5%" → 0.125）
+    ///
+    /// 参数：
+    /// - columns: 处理的列
+    ParsePercentages { columns: Vec<String> },
+
+    /// 小数转换为百分比字符串（0.125 → "12.5%"）
+    ///
+    /// 参数：
+    /// - columns: 处理的列
+    /// - decimal_places: 保留的小数位数
+    FormatPercentages {
+        columns: Vec<String>,
+        decimal_places: Option<usize>,
+    },
+
+    /// 真值/假值标记转换为布尔列
+    ///
+    /// 参数：
+    /// - column: 源列
+    /// - true_values / false_values: 真值/假值标记
+    /// - output_column: 新列名（None 表示原地替换）
+    NormalizeBoolean {
+        column: String,
+        true_values: Vec<String>,
+        false_values: Vec<String>,
+        output_column: Option<String>,
+    },
+
+    /// 清理空白和不可见字符
+    ///
+    /// 参数：
+    /// - columns: 处理的列（None 表示所有字符串列）
+    CleanWhitespace { columns: Option<Vec<String>> },
+
+    /// 替换 NaN / 无穷大
+    ///
+    /// 参数：
+    /// - columns: 要处理的浮点列（None 表示所有浮点列）
+    /// - value: 替换值（None 表示替换为空值）
+    ReplaceNonFinite {
+        columns: Option<Vec<String>>,
+        value: Option<f64>,
+    },
+
+    /// 删除重复行
+    ///
+    /// 参数：
+    /// - subset: 判断重复的列（空表示所有列）
+    /// - keep: 保留策略
+    /// - normalize_keys: 是否在比较前规范化字符串键（忽略大小写和重音）
+    DropDuplicates {
+        subset: Vec<String>,
+        keep: DuplicateKeep,
+        normalize_keys: bool,
+    },
+
+    /// 按键连接另一个文件
+    ///
+    /// 参数：
+    /// - file_path: 右表文件路径
+    /// - left_on / right_on: 当前数据集和右表的键列（按位置对应）
+    /// - how: 连接方式
+    /// - normalize_keys: 是否在匹配前规范化字符串键（忽略大小写和重音）
+    ///
+    /// 右表中与当前数据集重名的列按冲突策略处理，自动改名的列记录在历史条目中
+    JoinFile {
+        file_path: String,
+        left_on: Vec<String>,
+        right_on: Vec<String>,
+        how: JoinHow,
+        normalize_keys: bool,
+    },
+
+    /// 两组坐标之间的 Haversine 球面距离（千米，写入新列）
+    ///
+    /// 参数：
+    /// - lat1 / lon1: 起点纬度、经度列
+    /// - lat2 / lon2: 终点纬度、经度列
+    /// - output_column: 新列名
+    HaversineDistance {
+        lat1: String,
+        lon1: String,
+        lat2: String,
+        lon2: String,
+        output_column: String,
+    },
+
+    /// 坐标分桶（网格 / Geohash，写入新列）
+    ///
+    /// 参数：
+    /// - lat_column: 纬度列
+    /// - lon_column: 经度列
+    /// - method: 分桶方式
+    /// - output_column: 新列名
+    GeoBucket {
+        lat_column: String,
+        lon_column: String,
+        method: GeoBucketMethod,
+        output_column: String,
+    },
+
+    /// 展开列表列（每个元素一行，其他列的值随之复制）
+    ///
+    /// 参数：
+    /// - columns: 要展开的列表列（多列时各行的列表长度必须相同）
+    ExplodeList { columns: Vec<String> },
+
+    /// 将列表列的元素拼接为字符串
+    ///
+    /// 参数：
+    /// - column: 列表列
+    /// - separator: 分隔符
+    /// - output_column: 输出列（None 表示覆盖原列）
+    JoinList {
+        column: String,
+        separator: String,
+        output_column: Option<String>,
+    },
+
+    /// 将结构体列的字段展开为顶层列
+    ///
+    /// 参数：
+    /// - column: 结构体列
+    /// - separator: 新列名前缀分隔符（None 表示直接使用字段名）
+    UnnestStruct { column: String, separator: Option<String> },
+
+    /// 两个时间列之差（end - start，写入时间间隔列）
+    ///
+    /// 参数：
+    /// - start_column: 开始时间列
+    /// - end_column: 结束时间列
+    /// - output_column: 新列名
+    DurationBetween {
+        start_column: String,
+        end_column: String,
+        output_column: String,
+    },
+
+    /// 时间列加上一个时间间隔
+    ///
+    /// 参数：
+    /// - column: 日期、日期时间或时间间隔列
+    /// - duration: 时间间隔文本（例如 "1d 2h"、"-00:30:00"）
+    /// - output_column: 输出列（None 表示覆盖原列）
+    ShiftByDuration {
+        column: String,
+        duration: String,
+        output_column: Option<String>,
+    },
+
+    /// 时间间隔转换为数值
+    ///
+    /// 参数：
+    /// - column: 时间间隔列
+    /// - unit: 数值单位
+    /// - output_column: 输出列（None 表示覆盖原列）
+    DurationToNumber {
+        column: String,
+        unit: DurationUnit,
+        output_column: Option<String>,
+    },
+
+    /// 两个日期列之间的工作日数（不含结束日期）
+    ///
+    /// 参数：
+    /// - start_column: 开始日期列
+    /// - end_column: 结束日期列
+    /// - output_column: 新列名
+    /// - holidays: 节假日（YYYY-MM-DD）
+    BusinessDaysBetween {
+        start_column: String,
+        end_column: String,
+        output_column: String,
+        holidays: Vec<String>,
+    },
+
+    /// 日期列加上 N 个工作日
+    ///
+    /// 参数：
+    /// - column: 日期或日期时间列
+    /// - days: 工作日数（可以为负）
+    /// - output_column: 输出列（None 表示覆盖原列）
+    /// - holidays: 节假日（YYYY-MM-DD）
+    AddBusinessDays {
+        column: String,
+        days: i64,
+        output_column: Option<String>,
+        holidays: Vec<String>,
+    },
+
+    /// 计算年龄 / 司龄（整年或整月数）
+    ///
+    /// 参数：
+    /// - column: 出生日期或开始日期列
+    /// - reference_column: 参照日期列（None 表示使用 reference_date）
+    /// - reference_date: 参照日期（YYYY-MM-DD，执行时的"今天"也会记录为具体日期）
+    /// - unit: 年或月
+    /// - output_column: 新列名
+    ComputeAge {
+        column: String,
+        reference_column: Option<String>,
+        reference_date: Option<String>,
+        unit: AgeUnit,
+        output_column: String,
+    },
+
+    /// 提取日期部分（年、季度、月、ISO 周、财年、财季等），每个部分写入一个新列
+    ///
+    /// 参数：
+    /// - column: 日期或日期时间列
+    /// - parts: 要提取的部分
+    /// - fiscal_year_start: 财年起始月份（1-12）
+    /// - fiscal_year_label: 财年的命名方式
+    ExtractDateParts {
+        column: String,
+        parts: Vec<DatePart>,
+        fiscal_year_start: u32,
+        fiscal_year_label: FiscalYearLabel,
+    },
+
+    /// 同比 / 环比（与上一周期比较）
+    ///
+    /// 参数：
+    /// - date_column: 日期或日期时间列
+    /// - value_column: 数值列
+    /// - period: 比较周期（周环比 / 月环比 / 同比）
+    /// - group_by: 分组列（每组分别比较）
+    /// - bucket: 汇总粒度（None 表示逐行比较，不汇总）
+    /// - aggregation: 汇总函数（仅在 bucket 不为空时使用）
+    PeriodComparison {
+        date_column: String,
+        value_column: String,
+        period: ComparisonPeriod,
+        group_by: Vec<String>,
+        bucket: Option<PeriodBucket>,
+        aggregation: String,
+    },
+
+    /// 生成同期群留存表（结果替换当前数据集）
+    ///
+    /// 参数：
+    /// - user_column: 用户 ID 列
+    /// - signup_column: 注册日期列
+    /// - activity_column: 活跃日期列
+    /// - period: 同期群和偏移的周期粒度
+    /// - layout: 结果布局（长表 / 矩阵）
+    /// - max_offset: 最大偏移周期数（None 表示不限制）
+    CohortTable {
+        user_column: String,
+        signup_column: String,
+        activity_column: String,
+        period: PeriodBucket,
+        layout: CohortLayout,
+        max_offset: Option<u32>,
+    },
+
+    /// 图表汇总结果转为数据集（结果替换当前数据集）
+    ///
+    /// 参数：
+    /// - config: 生成汇总结果的图表配置（已补齐默认设置）
+    MaterializeChart { config: Box<ChartConfig> },
+
+    /// 设置列显示格式
+    ///
+    /// 参数：
+    /// - column: 列名
+    /// - format: 显示格式（None 表示清除格式）
+    SetColumnFormat {
+        column: String,
+        format: Option<ColumnFormat>,
+    },
+
+    /// 设置条件格式规则（整体替换）
+    ///
+    /// 参数：
+    /// - rules: 条件格式规则列表（为空表示清除所有条件格式）
+    SetConditionalFormats { rules: Vec<ConditionalFormatRule> },
+
+    /// 设置表格布局（整体替换）
+    ///
+    /// 参数：
+    /// - view: 隐藏列、固定列、列宽
+    SetViewState { view: ViewState },
+
+    /// 设置浮点列的显示精度（整体替换）
+    ///
+    /// 参数：
+    /// - precision: 全局和按列的舍入方式（默认值表示完整精度）
+    SetFloatPrecision { precision: FloatPrecision },
+}
+
+// ============================================================================
+// 导入选项
+// ============================================================================
+/// ImportOptions - 导入文件时使用的选项（记录在导入操作中，从源文件刷新时沿用）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ImportOptions {
+    /// CSV 导入选项
+    Csv { options: CsvImportOptions },
+
+    /// 读取 Excel 的一个工作表
+    Excel {
+        sheet_name: Option<String>,
+        header_row: Option<usize>,
+        skip_rows: Option<usize>,
+        cell_range: Option<String>,
+    },
+
+    /// 合并 Excel 的所有工作表
+    ExcelAllSheets { sheet_column: String, include_hidden: bool },
+
+    /// 只读取 Parquet 的部分列和前 N 行
+    Parquet {
+        columns: Option<Vec<String>>,
+        n_rows: Option<usize>,
+    },
+
+    /// 快照分享包（.zip），刷新时重新读取包中的数据
+    Snapshot,
 }
 
 // ============================================================================
@@ -226,6 +657,379 @@ pub enum FillStrategy {
     One,
 }
 
+// ============================================================================
+// 移动窗口函数
+// ============================================================================
+/// RollingFunction - 移动窗口函数
+///
+/// 用于批量移动窗口计算，每个函数对应一个单列 rolling_* 命令
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RollingFunction {
+    /// 移动平均
+    Mean,
+    /// 移动中位数
+    Median,
+    /// 移动求和
+    Sum,
+    /// 移动最小值
+    Min,
+    /// 移动最大值
+    Max,
+    /// 移动标准差
+    Std,
+    /// 移动方差
+    Var,
+    /// 移动分位数
+    Quantile,
+}
+
+impl RollingFunction {
+    /// 新列名中使用的函数后缀（与单列 rolling_* 命令保持一致）
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            RollingFunction::Mean => "avg",
+            RollingFunction::Median => "median",
+            RollingFunction::Sum => "sum",
+            RollingFunction::Min => "min",
+            RollingFunction::Max => "max",
+            RollingFunction::Std => "std",
+            RollingFunction::Var => "var",
+            RollingFunction::Quantile => "quantile",
+        }
+    }
+
+    /// 函数的中文名称（用于操作描述）
+    pub fn label(&self) -> &'static str {
+        match self {
+            RollingFunction::Mean => "平均",
+            RollingFunction::Median => "中位数",
+            RollingFunction::Sum => "求和",
+            RollingFunction::Min => "最小值",
+            RollingFunction::Max => "最大值",
+            RollingFunction::Std => "标准差",
+            RollingFunction::Var => "方差",
+            RollingFunction::Quantile => "分位数",
+        }
+    }
+}
+
+// ============================================================================
+// 列名冲突策略
+// ============================================================================
+/// ColumnConflictPolicy - 操作生成的新列与已有列同名时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnConflictPolicy {
+    /// 返回错误，数据不变
+    #[default]
+    Error,
+
+    /// 自动加数字后缀（"revenue" → "revenue_1"）
+    Suffix,
+
+    /// 覆盖已有列
+    Overwrite,
+}
+
+/// ColumnRename - 因列名冲突被自动改名的新列
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnRename {
+    /// 操作原本要生成的列名
+    pub requested: String,
+
+    /// 实际使用的列名
+    pub actual: String,
+}
+
+// ============================================================================
+// 去重保留策略
+// ============================================================================
+/// DuplicateKeep - 删除重复行时保留哪一行
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateKeep {
+    /// 保留第一次出现的行
+    #[default]
+    First,
+
+    /// 保留最后一次出现的行
+    Last,
+
+    /// 重复的行全部删除
+    None,
+}
+
+impl DuplicateKeep {
+    /// 策略的中文名称（用于操作描述）
+    pub fn label(&self) -> &'static str {
+        match self {
+            DuplicateKeep::First => "保留首行",
+            DuplicateKeep::Last => "保留末行",
+            DuplicateKeep::None => "全部删除",
+        }
+    }
+}
+
+// ============================================================================
+// 连接方式
+// ============================================================================
+/// JoinHow - 连接另一个文件时的连接方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JoinHow {
+    /// 保留当前数据集的所有行，没有匹配的行右表列为空
+    #[default]
+    Left,
+
+    /// 只保留两边都匹配的行
+    Inner,
+}
+
+impl JoinHow {
+    /// 连接方式的中文名称（用于操作描述）
+    pub fn label(&self) -> &'static str {
+        match self {
+            JoinHow::Left => "左连接",
+            JoinHow::Inner => "内连接",
+        }
+    }
+}
+
+// ============================================================================
+// 时间间隔单位
+// ============================================================================
+/// DurationUnit - 时间间隔转换为数值时使用的单位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DurationUnit {
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+}
+
+impl DurationUnit {
+    /// 每个单位包含的秒数
+    pub fn seconds(&self) -> i64 {
+        match self {
+            DurationUnit::Seconds => 1,
+            DurationUnit::Minutes => 60,
+            DurationUnit::Hours => 3_600,
+            DurationUnit::Days => 86_400,
+        }
+    }
+
+    /// 单位的中文名称（用于操作描述）
+    pub fn label(&self) -> &'static str {
+        match self {
+            DurationUnit::Seconds => "秒",
+            DurationUnit::Minutes => "分钟",
+            DurationUnit::Hours => "小时",
+            DurationUnit::Days => "天",
+        }
+    }
+}
+
+// ============================================================================
+// 年龄单位
+// ============================================================================
+/// AgeUnit - 年龄 / 司龄的计量单位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AgeUnit {
+    Years,
+    Months,
+}
+
+impl AgeUnit {
+    /// 每个单位包含的月数
+    pub fn months(&self) -> u32 {
+        match self {
+            AgeUnit::Years => 12,
+            AgeUnit::Months => 1,
+        }
+    }
+
+    /// 单位的中文名称（用于操作描述）
+    pub fn label(&self) -> &'static str {
+        match self {
+            AgeUnit::Years => "年",
+            AgeUnit::Months => "月",
+        }
+    }
+}
+
+// ============================================================================
+// 日期部分
+// ============================================================================
+/// DatePart - 可以从日期中提取的部分
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DatePart {
+    Year,
+    Quarter,
+    Month,
+    Day,
+    /// 星期几（1 = 周一，7 = 周日）
+    Weekday,
+    /// ISO 8601 周数（1-53）
+    IsoWeek,
+    /// ISO 周所属的年份（年初 / 年末的几天可能与日历年不同）
+    IsoYear,
+    /// 财年（按财年起始月份计算）
+    FiscalYear,
+    /// 财季（1-4，按财年起始月份计算）
+    FiscalQuarter,
+}
+
+impl DatePart {
+    /// 输出列名的后缀，例如 order_date_iso_week
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            DatePart::Year => "year",
+            DatePart::Quarter => "quarter",
+            DatePart::Month => "month",
+            DatePart::Day => "day",
+            DatePart::Weekday => "weekday",
+            DatePart::IsoWeek => "iso_week",
+            DatePart::IsoYear => "iso_year",
+            DatePart::FiscalYear => "fiscal_year",
+            DatePart::FiscalQuarter => "fiscal_quarter",
+        }
+    }
+
+    /// 部分的中文名称（用于操作描述）
+    pub fn label(&self) -> &'static str {
+        match self {
+            DatePart::Year => "年",
+            DatePart::Quarter => "季度",
+            DatePart::Month => "月",
+            DatePart::Day => "日",
+            DatePart::Weekday => "星期",
+            DatePart::IsoWeek => "ISO 周",
+            DatePart::IsoYear => "ISO 年",
+            DatePart::FiscalYear => "财年",
+            DatePart::FiscalQuarter => "财季",
+        }
+    }
+}
+
+/// FiscalYearLabel - 跨自然年的财年用哪一年命名
+///
+/// 例如财年从 2024 年 4 月到 2025 年 3 月：
+/// - EndYear: FY2025（美国联邦政府、英国等）
+/// - StartYear: FY2024（日本等）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FiscalYearLabel {
+    #[default]
+    EndYear,
+    StartYear,
+}
+
+// ============================================================================
+// 同比 / 环比
+// ============================================================================
+/// ComparisonPeriod - 与哪个上一周期比较
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComparisonPeriod {
+    /// 周环比（与 7 天前比较）
+    Wow,
+    /// 月环比（与上个月同一天比较）
+    Mom,
+    /// 同比（与去年同一天比较）
+    Yoy,
+}
+
+impl ComparisonPeriod {
+    /// 时间偏移（Polars 时间间隔字符串）
+    pub fn offset(&self) -> &'static str {
+        match self {
+            ComparisonPeriod::Wow => "1w",
+            ComparisonPeriod::Mom => "1mo",
+            ComparisonPeriod::Yoy => "1y",
+        }
+    }
+
+    /// 往前推一个周期（月末对齐，例如 3 月 31 日的上个月为 2 月 28 日 / 29 日）
+    pub fn shift_back(&self, date: chrono::NaiveDate) -> Option<chrono::NaiveDate> {
+        match self {
+            ComparisonPeriod::Wow => date.checked_sub_days(chrono::Days::new(7)),
+            ComparisonPeriod::Mom => date.checked_sub_months(chrono::Months::new(1)),
+            ComparisonPeriod::Yoy => date.checked_sub_months(chrono::Months::new(12)),
+        }
+    }
+
+    /// 周期的中文名称（用于操作描述）
+    pub fn label(&self) -> &'static str {
+        match self {
+            ComparisonPeriod::Wow => "周环比",
+            ComparisonPeriod::Mom => "月环比",
+            ComparisonPeriod::Yoy => "同比",
+        }
+    }
+}
+
+/// PeriodBucket - 比较前的汇总粒度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PeriodBucket {
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl PeriodBucket {
+    /// 截断间隔（Polars 时间间隔字符串）
+    pub fn every(&self) -> &'static str {
+        match self {
+            PeriodBucket::Day => "1d",
+            PeriodBucket::Week => "1w",
+            PeriodBucket::Month => "1mo",
+            PeriodBucket::Quarter => "1q",
+            PeriodBucket::Year => "1y",
+        }
+    }
+
+    /// 粒度的中文名称（用于操作描述）
+    pub fn label(&self) -> &'static str {
+        match self {
+            PeriodBucket::Day => "按日",
+            PeriodBucket::Week => "按周",
+            PeriodBucket::Month => "按月",
+            PeriodBucket::Quarter => "按季度",
+            PeriodBucket::Year => "按年",
+        }
+    }
+
+    /// 汇总后的周期起点偏移一个比较周期后，是否仍然是周期起点
+    ///
+    /// 例如按周汇总后做月环比，周一加一个月通常不是周一，永远找不到上一周期
+    pub fn supports(&self, period: ComparisonPeriod) -> bool {
+        match self {
+            PeriodBucket::Day => true,
+            PeriodBucket::Week => period == ComparisonPeriod::Wow,
+            PeriodBucket::Month => period != ComparisonPeriod::Wow,
+            PeriodBucket::Quarter | PeriodBucket::Year => period == ComparisonPeriod::Yoy,
+        }
+    }
+}
+
+/// CohortLayout - 同期群留存表的布局
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CohortLayout {
+    /// 长表：每个（同期群, 偏移）一行，可直接用于热力图
+    #[default]
+    Long,
+    /// 矩阵：每个同期群一行，每个偏移一列（值为留存率）
+    Wide,
+}
+
 // ============================================================================
 // 历史条目结构体（内部使用，包含 DataFrame）
 // ============================================================================
@@ -252,6 +1056,15 @@ pub struct HistoryEntry {
 
     /// 操作描述（用于显示）
     pub description: String,
+
+    /// 显示设置（列格式等，不影响数据本身）
+    pub display: DisplaySettings,
+
+    /// 被该操作删除的行（筛选、删除空值时按需记录，用于审计）
+    pub removed_rows: Option<DataFrame>,
+
+    /// 因列名冲突被自动改名的新列（冲突策略为 suffix 时记录）
+    pub renamed_columns: Vec<ColumnRename>,
 }
 
 // ============================================================================
@@ -277,6 +1090,14 @@ pub struct HistoryEntryInfo {
 
     /// 操作描述
     pub description: String,
+
+    /// 记录的被删除行数（None 表示该操作没有记录被删除的行）
+    #[serde(default)]
+    pub removed_row_count: Option<usize>,
+
+    /// 因列名冲突被自动改名的新列
+    #[serde(default)]
+    pub renamed_columns: Vec<ColumnRename>,
 }
 
 // ============================================================================
@@ -290,10 +1111,238 @@ impl From<&HistoryEntry> for HistoryEntryInfo {
             metadata: entry.metadata.clone(),
             timestamp: entry.timestamp.clone(),
             description: entry.description.clone(),
+            removed_row_count: entry.removed_rows.as_ref().map(|df| df.height()),
+            renamed_columns: entry.renamed_columns.clone(),
+        }
+    }
+}
+
+// ============================================================================
+// 状态恢复
+// ============================================================================
+/// StateRecoveryReport - recover_state 的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateRecoveryReport {
+    /// 应用启动以来数据存储锁从中毒状态恢复的次数（命令持有锁时 panic 会让锁中毒）
+    pub lock_recoveries: usize,
+
+    /// 本次是否修复了历史索引
+    pub repaired: bool,
+
+    /// 历史条目数量
+    pub history_len: usize,
+
+    /// 当前历史索引（None 表示没有数据）
+    pub current_index: Option<usize>,
+}
+
+// ============================================================================
+// 行数保护
+// ============================================================================
+/// RowGuardrails - 删除行的操作（删除空值行、筛选、去重）的确认阈值
+///
+/// 预计删除的行数超过阈值时，操作不会执行，而是返回预计影响等待确认
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RowGuardrails {
+    /// 是否启用
+    pub enabled: bool,
+
+    /// 删除行数占比超过该百分比（0 - 100）时需要确认
+    pub max_removed_percent: f64,
+
+    /// 删除行数少于该数量时不需要确认（避免小数据集频繁提示）
+    pub min_removed_rows: usize,
+}
+
+impl Default for RowGuardrails {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_removed_percent: 50.0,
+            min_removed_rows: 10,
+        }
+    }
+}
+
+/// RowOrdering - 行顺序设置
+///
+/// 开启稳定顺序后，可能改变行顺序的操作（透视、排序、纵表转换）保持原始行的相对顺序，
+/// 同样的数据和操作总是得到同样顺序的结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RowOrdering {
+    /// 是否启用稳定顺序
+    pub stable: bool,
+}
+
+/// RowImpact - 操作预计对行数的影响
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RowImpact {
+    /// 操作前的行数
+    pub rows_before: usize,
+
+    /// 操作后的行数
+    pub rows_after: usize,
+
+    /// 删除的行数
+    pub removed_rows: usize,
+
+    /// 删除行数占比（0 - 100）
+    pub removed_percent: f64,
+
+    /// 触发确认的阈值（RowGuardrails::max_removed_percent）
+    pub threshold_percent: f64,
+}
+
+/// OperationOutcome - 受行数保护的操作的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationOutcome {
+    /// 操作是否已执行（false 表示需要确认，数据和历史都没有变化）
+    pub applied: bool,
+
+    /// 需要确认时的预计影响（带 force = true 重新调用即可执行）
+    pub needs_confirmation: Option<RowImpact>,
+}
+
+impl OperationOutcome {
+    /// 操作已执行
+    pub fn applied() -> Self {
+        Self {
+            applied: true,
+            needs_confirmation: None,
+        }
+    }
+
+    /// 操作未执行，等待确认
+    pub fn needs_confirmation(impact: RowImpact) -> Self {
+        Self {
+            applied: false,
+            needs_confirmation: Some(impact),
         }
     }
 }
 
+// ============================================================================
+// 指标历史
+// ============================================================================
+/// MetricSnapshot - 某个历史节点上的数据指标
+///
+/// 用于查看每一步清洗操作对数据分布的影响（行数、空值比例、均值的变化）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricSnapshot {
+    /// 历史节点 ID
+    pub entry_id: String,
+
+    /// 操作描述
+    pub description: String,
+
+    /// 时间戳
+    pub timestamp: String,
+
+    /// 是否为当前所在的历史节点
+    pub is_current: bool,
+
+    /// 行数
+    pub row_count: usize,
+
+    /// 列数
+    pub column_count: usize,
+
+    /// 所选列的指标（顺序与请求的列一致）
+    pub columns: Vec<ColumnMetric>,
+}
+
+/// ColumnMetric - 单列在某个历史节点上的指标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnMetric {
+    /// 列名
+    pub column: String,
+
+    /// 该节点上是否存在这一列（被删除或尚未创建时为 false，其余字段为空）
+    pub present: bool,
+
+    /// 数据类型
+    pub dtype: Option<String>,
+
+    /// 空值数量
+    pub null_count: Option<usize>,
+
+    /// 空值比例（0 ~ 1，没有行时为空）
+    pub null_ratio: Option<f64>,
+
+    /// 平均值（仅数值类型）
+    pub mean: Option<f64>,
+}
+
+// ============================================================================
+// 与初始导入的差异
+// ============================================================================
+/// DatasetDiff - 当前状态相对初始导入的累计变化
+///
+/// 用于向数据提供方说明"对文件做了什么"：行数变化、新增 / 删除 / 重命名的列、类型变化的列
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetDiff {
+    /// 初始导入的历史节点 ID
+    pub baseline_entry_id: String,
+
+    /// 初始导入的描述
+    pub baseline_description: String,
+
+    /// 当前历史节点 ID
+    pub current_entry_id: String,
+
+    /// 从初始导入到当前状态的数据操作描述（按时间顺序，不包括只修改显示设置的操作）
+    pub operations: Vec<String>,
+
+    /// 初始导入的行数
+    pub rows_before: usize,
+
+    /// 当前行数
+    pub rows_after: usize,
+
+    /// 减少的行数（行数增加时为 0）
+    pub rows_removed: usize,
+
+    /// 增加的行数（行数减少时为 0）
+    pub rows_added: usize,
+
+    /// 新增的列（按当前列顺序）
+    pub columns_added: Vec<String>,
+
+    /// 删除的列（按初始列顺序）
+    pub columns_dropped: Vec<String>,
+
+    /// 重命名的列（多次重命名合并为 初始列名 → 当前列名）
+    pub columns_renamed: Vec<RenamedColumn>,
+
+    /// 类型变化的列（按当前列名）
+    pub dtype_changes: Vec<DtypeChange>,
+}
+
+/// RenamedColumn - 被重命名的列
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenamedColumn {
+    /// 初始导入时的列名
+    pub from: String,
+
+    /// 当前列名
+    pub to: String,
+}
+
+/// DtypeChange - 类型变化的列
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DtypeChange {
+    /// 当前列名
+    pub column: String,
+
+    /// 初始导入时的类型
+    pub from: String,
+
+    /// 当前类型
+    pub to: String,
+}
+
 // ============================================================================
 // 操作描述生成
 // ============================================================================
@@ -301,7 +1350,7 @@ impl OperationType {
     /// 生成操作的人类可读描述
     pub fn description(&self) -> String {
         match self {
-            OperationType::Import { file_path } => {
+            OperationType::Import { file_path, .. } => {
                 let file_name = std::path::Path::new(file_path)
                     .file_name()
                     .and_then(|n| n.to_str())
@@ -337,8 +1386,15 @@ impl OperationType {
                 }
             }
             OperationType::DropAllNulls => "删除全空行".to_string(),
-            OperationType::SelectColumns { columns } => {
-                format!("选择列 ({} 列)", columns.len())
+            OperationType::SelectColumns {
+                columns,
+                keep_remaining,
+            } => {
+                if *keep_remaining {
+                    format!("调整列顺序 (前 {} 列)", columns.len())
+                } else {
+                    format!("选择列 ({} 列)", columns.len())
+                }
             }
             OperationType::DropColumns { columns } => {
                 format!("删除列 ({} 列)", columns.len())
@@ -349,6 +1405,10 @@ impl OperationType {
             OperationType::CastTypes { mapping } => {
                 format!("转换列类型 ({} 列)", mapping.len())
             }
+            OperationType::CastTypesByPattern { profile, columns, .. } => match profile {
+                Some(name) => format!("按预设 {} 转换列类型 ({} 列)", name, columns.len()),
+                None => format!("按规则转换列类型 ({} 列)", columns.len()),
+            },
             OperationType::Sort {
                 column,
                 descending,
@@ -358,7 +1418,23 @@ impl OperationType {
                 let nulls = if *nulls_last { "空值置后" } else { "空值置前" };
                 format!("排序 ({}，{}，{})", column, order, nulls)
             }
-            OperationType::Filter { .. } => "筛选过滤".to_string(),
+            OperationType::Filter { null_policy, .. } => match null_policy {
+                NullPolicy::NullAsFalse => "筛选过滤".to_string(),
+                _ => format!("筛选过滤 ({})", null_policy.label()),
+            },
+            OperationType::FilterRows {
+                conditions,
+                match_all,
+                null_policy,
+            } => {
+                let combine = if *match_all { "全部满足" } else { "任一满足" };
+                format!(
+                    "条件筛选 ({} 个条件，{}，{})",
+                    conditions.len(),
+                    combine,
+                    null_policy.label()
+                )
+            }
             OperationType::FillNull { strategy } => {
                 let strategy_name = match strategy {
                     FillStrategy::Constant { .. } => "常量",
@@ -479,8 +1555,354 @@ impl OperationType {
                     column, window_size, quantile, center_str, min_p
                 )
             }
+            OperationType::RollingBatch {
+                columns,
+                functions,
+                window_size,
+                center,
+                min_periods,
+                ..
+            } => {
+                let center_str = if *center { "是" } else { "否" };
+                let min_p = min_periods.unwrap_or(1);
+                let function_names: Vec<&str> = functions.iter().map(|f| f.label()).collect();
+                format!(
+                    "批量移动计算 ({} 列, 函数: {}, 窗口: {}, 居中: {}, 最小样本: {})",
+                    columns.len(),
+                    function_names.join("/"),
+                    window_size,
+                    center_str,
+                    min_p
+                )
+            }
+            OperationType::NormalizeEmails { columns } => {
+                format!("邮箱规范化 ({} 列)", columns.len())
+            }
+            OperationType::NormalizePhones {
+                column,
+                default_country_code,
+            } => match default_country_code {
+                Some(code) => format!("电话规范化 (列: {}, 默认国家码: +{})", column, code),
+                None => format!("电话规范化 (列: {})", column),
+            },
+            OperationType::ParseUrls { column } => format!("URL 解析 (列: {})", column),
+            OperationType::ParseIpAddresses { column, mmdb_path } => {
+                if mmdb_path.is_some() {
+                    format!("IP 地址解析 (列: {}, 含 GeoIP 国家)", column)
+                } else {
+                    format!("IP 地址解析 (列: {})", column)
+                }
+            }
+            OperationType::ParseUserAgents { column } => format!("User-Agent 解析 (列: {})", column),
+            OperationType::ParseCurrency {
+                column, output_column, ..
+            } => match output_column {
+                Some(output) => format!("金额解析 ({} → {})", column, output),
+                None => format!("金额解析 (列: {})", column),
+            },
+            OperationType::ParsePercentages { columns } => {
+                format!("百分比转小数 ({} 列)", columns.len())
+            }
+            OperationType::FormatPercentages { columns, .. } => {
+                format!("小数转百分比 ({} 列)", columns.len())
+            }
+            OperationType::NormalizeBoolean {
+                column, output_column, ..
+            } => match output_column {
+                Some(output) => format!("布尔值规范化 ({} → {})", column, output),
+                None => format!("布尔值规范化 (列: {})", column),
+            },
+            OperationType::CleanWhitespace { columns } => match columns {
+                Some(cols) => format!("清理空白字符 ({} 列)", cols.len()),
+                None => "清理空白字符 (所有字符串列)".to_string(),
+            },
+            OperationType::ReplaceNonFinite { columns, value } => {
+                let target = match columns {
+                    Some(cols) => format!("{} 列", cols.len()),
+                    None => "所有浮点列".to_string(),
+                };
+                match value {
+                    Some(v) => format!("替换 NaN / 无穷大为 {} ({})", v, target),
+                    None => format!("替换 NaN / 无穷大为空值 ({})", target),
+                }
+            }
+            OperationType::DropDuplicates {
+                subset,
+                keep,
+                normalize_keys,
+            } => {
+                let scope = if subset.is_empty() {
+                    "所有列".to_string()
+                } else {
+                    subset.join(", ")
+                };
+                let normalize = if *normalize_keys {
+                    ", 忽略大小写和重音"
+                } else {
+                    ""
+                };
+                format!("删除重复行 ({}, {}{})", scope, keep.label(), normalize)
+            }
+            OperationType::JoinFile {
+                file_path,
+                left_on,
+                right_on,
+                how,
+                normalize_keys,
+                ..
+            } => {
+                let file_name = std::path::Path::new(file_path)
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("未知文件");
+                let keys: Vec<String> = left_on
+                    .iter()
+                    .zip(right_on)
+                    .map(|(left, right)| {
+                        if left == right {
+                            left.clone()
+                        } else {
+                            format!("{} = {}", left, right)
+                        }
+                    })
+                    .collect();
+                let normalize = if *normalize_keys {
+                    ", 忽略大小写和重音"
+                } else {
+                    ""
+                };
+                format!(
+                    "连接文件: {} ({}, 键: {}{})",
+                    file_name,
+                    how.label(),
+                    keys.join(", "),
+                    normalize
+                )
+            }
+            OperationType::HaversineDistance {
+                lat1,
+                lon1,
+                lat2,
+                lon2,
+                output_column,
+            } => format!(
+                "球面距离 ({}, {}) → ({}, {}) → {}",
+                lat1, lon1, lat2, lon2, output_column
+            ),
+            OperationType::GeoBucket {
+                lat_column,
+                lon_column,
+                method,
+                output_column,
+            } => format!(
+                "坐标分桶 ({}, {}, {}) → {}",
+                lat_column,
+                lon_column,
+                method.label(),
+                output_column
+            ),
+            OperationType::ExplodeList { columns } => format!("展开列表 ({})", columns.join(", ")),
+            OperationType::JoinList {
+                column,
+                separator,
+                output_column,
+            } => match output_column {
+                Some(output) => format!("拼接列表 ({} → {}, 分隔符 \"{}\")", column, output, separator),
+                None => format!("拼接列表 (列: {}, 分隔符 \"{}\")", column, separator),
+            },
+            OperationType::UnnestStruct { column, .. } => format!("展开结构体 (列: {})", column),
+            OperationType::DurationBetween {
+                start_column,
+                end_column,
+                output_column,
+            } => format!("时间差 ({} - {} → {})", end_column, start_column, output_column),
+            OperationType::ShiftByDuration {
+                column,
+                duration,
+                output_column,
+            } => match output_column {
+                Some(output) => format!("时间偏移 ({} + {} → {})", column, duration, output),
+                None => format!("时间偏移 ({} + {})", column, duration),
+            },
+            OperationType::DurationToNumber {
+                column,
+                unit,
+                output_column,
+            } => match output_column {
+                Some(output) => format!("时间间隔转数值 ({} → {}, 单位: {})", column, output, unit.label()),
+                None => format!("时间间隔转数值 ({}, 单位: {})", column, unit.label()),
+            },
+            OperationType::BusinessDaysBetween {
+                start_column,
+                end_column,
+                output_column,
+                holidays,
+            } => format!(
+                "工作日差 ({} - {} → {}, {} 个节假日)",
+                end_column,
+                start_column,
+                output_column,
+                holidays.len()
+            ),
+            OperationType::AddBusinessDays {
+                column,
+                days,
+                output_column,
+                ..
+            } => match output_column {
+                Some(output) => format!("加工作日 ({} + {} 个工作日 → {})", column, days, output),
+                None => format!("加工作日 ({} + {} 个工作日)", column, days),
+            },
+            OperationType::ComputeAge {
+                column,
+                reference_column,
+                reference_date,
+                unit,
+                output_column,
+            } => {
+                let reference = reference_column
+                    .as_deref()
+                    .or(reference_date.as_deref())
+                    .unwrap_or("今天");
+                format!(
+                    "计算年龄 ({} → {}, 参照: {}, 单位: {})",
+                    column,
+                    output_column,
+                    reference,
+                    unit.label()
+                )
+            }
+            OperationType::ExtractDateParts {
+                column,
+                parts,
+                fiscal_year_start,
+                ..
+            } => {
+                let labels: Vec<&str> = parts.iter().map(|part| part.label()).collect();
+                let fiscal = parts
+                    .iter()
+                    .any(|part| matches!(part, DatePart::FiscalYear | DatePart::FiscalQuarter));
+                if fiscal {
+                    format!(
+                        "提取日期部分 ({}: {}, 财年起始月: {})",
+                        column,
+                        labels.join(", "),
+                        fiscal_year_start
+                    )
+                } else {
+                    format!("提取日期部分 ({}: {})", column, labels.join(", "))
+                }
+            }
+            OperationType::PeriodComparison {
+                date_column,
+                value_column,
+                period,
+                bucket,
+                aggregation,
+                ..
+            } => match bucket {
+                Some(bucket) => format!(
+                    "{} ({} {} {}, 日期列: {})",
+                    period.label(),
+                    bucket.label(),
+                    aggregation,
+                    value_column,
+                    date_column
+                ),
+                None => format!("{} ({}, 日期列: {})", period.label(), value_column, date_column),
+            },
+            OperationType::CohortTable {
+                user_column,
+                activity_column,
+                period,
+                ..
+            } => format!(
+                "同期群留存表 ({}, 用户列: {}, 活跃日期列: {})",
+                period.label(),
+                user_column,
+                activity_column
+            ),
+            OperationType::MaterializeChart { config } => {
+                let source = match config.chart_type {
+                    ChartType::Histogram => config.histogram_column.clone(),
+                    _ => config.x_column.clone().or_else(|| config.category_column.clone()),
+                };
+                match source {
+                    Some(column) => format!("图表汇总转为数据集 ({}, {})", config.chart_type.label(), column),
+                    None => format!("图表汇总转为数据集 ({})", config.chart_type.label()),
+                }
+            }
+            OperationType::SetColumnFormat { column, format } => {
+                if format.is_some() {
+                    format!("设置列格式 ({})", column)
+                } else {
+                    format!("清除列格式 ({})", column)
+                }
+            }
+            OperationType::SetConditionalFormats { rules } => {
+                if rules.is_empty() {
+                    "清除条件格式".to_string()
+                } else {
+                    format!("设置条件格式 ({} 条规则)", rules.len())
+                }
+            }
+            OperationType::SetViewState { view } => format!(
+                "调整表格布局 (隐藏 {} 列，固定 {} 列)",
+                view.hidden_columns.len(),
+                view.pinned_columns.len()
+            ),
+            OperationType::SetFloatPrecision { precision } => {
+                if *precision == FloatPrecision::default() {
+                    "恢复默认数值显示".to_string()
+                } else {
+                    format!("设置数值精度 ({} 列单独设置)", precision.columns.len())
+                }
+            }
         }
     }
+
+    /// 是否为只修改显示设置的操作
+    ///
+    /// 这类操作自己负责生成完整的显示设置，不从上一个状态继承
+    pub fn is_display_only(&self) -> bool {
+        matches!(
+            self,
+            OperationType::SetColumnFormat { .. }
+                | OperationType::SetConditionalFormats { .. }
+                | OperationType::SetViewState { .. }
+                | OperationType::SetFloatPrecision { .. }
+        )
+    }
+}
+
+// ============================================================================
+// 导出记录
+// ============================================================================
+/// ExportRecord - 一次成功的导出（用于追溯已交付的文件来自哪个数据状态）
+///
+/// 历史条目可能之后被撤销、丢弃或裁剪，这里同时保存条目 ID 和操作描述
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportRecord {
+    /// 输出文件路径
+    pub path: String,
+
+    /// 导出命令（例如 "export_csv"、"export_sqlite"）
+    pub kind: String,
+
+    /// 导出完成的时间（ISO 8601 格式）
+    pub timestamp: String,
+
+    /// 导出数据所在的历史条目 ID
+    pub history_entry_id: Option<String>,
+
+    /// 该历史条目的操作描述
+    pub history_description: Option<String>,
+
+    /// 导出的行数
+    pub rows: usize,
+
+    /// 导出的列数
+    pub columns: usize,
 }
 
 // ============================================================================
@@ -498,6 +1920,9 @@ impl OperationType {
 //        metadata: info,
 //        timestamp: chrono::Utc::now().to_rfc3339(),
 //        description: operation.description(),
+//        display: DisplaySettings::default(),
+//        removed_rows: None,
+//        renamed_columns: Vec::new(),
 //    };
 //    ```
 //
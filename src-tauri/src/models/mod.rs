@@ -30,6 +30,71 @@ pub mod chart;
 /// 定义了 OperationType、HistoryEntry、HistoryEntryInfo 等结构体
 /// 用于实现 undo/redo 功能和操作历史管理
 pub mod history;
+
+/// 显示设置相关的数据模型
+/// 定义了 ColumnFormat、DisplaySettings 等结构体
+/// 只影响数据的展示方式，随历史条目一起保存
+pub mod display;
+
+/// 导出加密相关的数据模型
+/// 定义了 ExportEncryption
+pub mod encryption;
+
+/// CSV 导入选项数据模型
+/// 定义了 CsvImportOptions
+pub mod csv_import;
+
+/// 数据库连接相关的数据模型
+/// 定义了 DatabaseKind、DatabaseConnection、DatabaseTable、SqliteIfExists
+pub mod database;
+
+/// 导出选项数据模型
+/// 定义了 CsvExportProfile、CsvEncoding、CsvQuoteStyle、LineEnding、ParquetExportCompression、ClipboardFormat
+pub mod export_profile;
+
+/// 类型转换预设数据模型
+/// 定义了 CastRule、CastProfile
+pub mod cast_profile;
+
+/// 筛选相关的数据模型
+/// 定义了 NullPolicy、FilterOperator、FilterCondition、ExportSubset 等结构体
+pub mod filter;
+
+/// 序列缺口检测相关的数据模型
+/// 定义了 GapReport、SequenceGap 等结构体
+pub mod gaps;
+
+/// 地理坐标相关的数据模型
+/// 定义了 CoordinateReport、GeoBucketMethod 等结构体
+pub mod geo;
+
+/// Parquet 文件元数据模型
+/// 定义了 ParquetMetadata、ParquetRowGroup、ParquetColumnChunk 等结构体
+pub mod parquet_meta;
+
+/// 报告导出相关的数据模型
+/// 定义了 ExcelReportOptions、PivotSpec、AggregationSpec、ProfileReport 等结构体
+pub mod report;
+
+/// 快照分享包相关的数据模型
+/// 定义了 SnapshotManifest、SnapshotContents、HistoryBundleManifest 等结构体
+pub mod snapshot;
+
+/// 计划任务相关的数据模型
+/// 定义了 ScheduledJob、JobSchedule、ScheduledRunResult 等结构体
+pub mod schedule;
+
+/// 后台任务相关的数据模型
+/// 定义了 TaskStatus、TaskInfo 等结构体
+pub mod task;
+
+/// 数据校验相关的数据模型
+/// 定义了 ValidationRule、ValidationReport、ValidationExportOptions 等结构体
+pub mod validation;
+
+/// 源文件监视相关的数据模型
+/// 定义了 SourceChangeKind、SourceChangedEvent
+pub mod watch;
 // ============================================================================
 // 重新导出核心数据结构
 // ============================================================================
@@ -40,6 +105,7 @@ pub mod history;
 /// - name: String - 列名
 /// - dtype: String - 数据类型（"Int64", "Float64", "String" 等）
 /// - null_count: usize - 空值数量
+/// - summary: Option<ColumnSummary> - 列摘要（开启列摘要设置时才有）
 ///
 /// DatasetInfo: 数据集元信息
 /// - id: String - 数据集的唯一标识符（UUID）
@@ -62,7 +128,20 @@ pub mod history;
 /// - unique_count: usize - 唯一值数量
 /// - max/min/mean/std: Option<f64> - 基础统计量（仅数值类型）
 /// - q25/q50/q75: Option<f64> - 分位数（仅数值类型）
-pub use dataset::{ColumnInfo, ColumnStats, DatasetData, DatasetInfo};
+///
+/// SimilarCluster / SimilarValue: 近似重复值聚类结果
+/// DatasetFingerprint / ColumnFingerprint: 数据集内容指纹
+/// UnmatchedValuesReport: 转换操作中无法识别的值
+/// ListStats: 列表列的长度和元素统计
+/// ExcelSheetInfo: Excel 工作表名称、行数和列数
+/// SampleStrategy: 抽样导入的方式（前 N 行 / 随机 N 行）
+/// DemoPreset: 演示数据集的结构预设（销售 / 时间序列 / 鸢尾花）
+/// ColumnSummary / ColumnSummarySettings: 元信息中的列摘要和是否计算列摘要的设置
+pub use dataset::{
+    ColumnFingerprint, ColumnInfo, ColumnStats, ColumnSummary, ColumnSummarySettings, DatasetData, DatasetFingerprint,
+    DatasetInfo, DemoPreset, ExcelSheetInfo, ListStats, SampleStrategy, SimilarCluster, SimilarValue,
+    UnmatchedValuesReport,
+};
 
 /// 重新导出历史相关的数据结构
 ///
@@ -91,7 +170,149 @@ pub use dataset::{ColumnInfo, ColumnStats, DatasetData, DatasetInfo};
 ///
 /// HistoryEntry: 历史条目（包含 DataFrame，不可序列化）
 /// HistoryEntryInfo: 历史条目信息（可序列化，用于传输给前端）
-pub use history::{HistoryEntry, HistoryEntryInfo, OperationType};
+/// MetricSnapshot / ColumnMetric: 历史节点上的行数、空值比例、均值（用于指标历史）
+/// StateRecoveryReport: recover_state 的结果（锁恢复次数、是否修复了历史索引）
+/// RowGuardrails / RowImpact / OperationOutcome: 删除大量行时的确认阈值、预计影响和操作结果
+/// RowOrdering: 行顺序设置（稳定顺序）
+/// ExportRecord: 一次成功的导出（文件路径、时间、数据所在的历史条目）
+/// ImportOptions: 导入文件时使用的选项（CSV 选项、Excel 工作表、Parquet 列和行数），从源文件刷新时沿用
+/// DatasetDiff / RenamedColumn / DtypeChange: 当前状态相对初始导入的累计变化
+pub use history::{
+    ColumnMetric, DatasetDiff, DtypeChange, ExportRecord, HistoryEntry, HistoryEntryInfo, ImportOptions,
+    MetricSnapshot, OperationOutcome, OperationType, RenamedColumn, RowGuardrails, RowImpact, RowOrdering,
+    StateRecoveryReport,
+};
+
+/// 重新导出显示设置相关的数据结构
+///
+/// ColumnFormat: 单列显示格式（千分位、小数位、日期格式、百分比）
+/// ConditionalFormatRule / ConditionalCondition: 条件格式规则
+/// CellStyle: 单元格样式标签（随分页数据返回）
+/// ViewState: 表格布局（隐藏列、固定列、列宽）
+/// FloatPrecision / FloatRounding: 浮点列的显示精度（全局和按列的舍入方式）
+/// NonFinitePolicy: NaN / 无穷大传给前端时的处理方式（null / 字符串 / 报错）
+/// DisplaySettings: 一个历史状态上的全部显示设置
+pub use display::{
+    CellStyle, ColumnFormat, ConditionalCondition, ConditionalFormatRule, DisplaySettings, FloatPrecision,
+    FloatRounding, NonFinitePolicy, ViewState,
+};
+
+/// 重新导出导出加密相关的数据结构
+///
+/// ExportEncryption: 导出加密方式（加密指定列 / 带密码的 zip）
+pub use encryption::ExportEncryption;
+
+/// 重新导出数据库连接相关的数据结构
+///
+/// DatabaseKind: 数据库类型（PostgreSQL / MySQL）
+/// DatabaseConnection: 连接参数（每次调用时传入，不保存）
+/// DatabaseTable: 表或视图
+/// SqliteIfExists: 导出到 SQLite 时表已存在的处理方式（报错 / 替换 / 追加）
+pub use database::{DatabaseConnection, DatabaseKind, DatabaseTable, SqliteIfExists};
+
+/// 重新导出 CSV 导入选项
+///
+/// CsvImportOptions: 分隔符、表头、引号、编码、跳过行数、空值文本、列类型覆盖
+/// ImportProgress: 大文件导入进度
+pub use csv_import::{CsvImportOptions, ImportProgress};
+
+/// 重新导出 CSV 导出预设相关的数据结构
+///
+/// CsvExportProfile: 一组命名的 CSV 导出选项
+/// CsvEncoding / CsvQuoteStyle / LineEnding: 编码、引号策略、换行符
+/// ParquetExportCompression: Parquet 导出的压缩算法和级别
+/// ClipboardFormat: 复制到剪贴板的文本格式（TSV / CSV / Markdown）
+pub use export_profile::{
+    ClipboardFormat, CsvEncoding, CsvExportProfile, CsvQuoteStyle, LineEnding, ParquetExportCompression,
+};
+
+/// 重新导出类型转换预设相关的数据结构
+///
+/// CastRule: 列名模式 → 目标类型（可选解析格式）
+/// CastProfile: 一组命名的类型转换规则
+pub use cast_profile::{CastProfile, CastRule};
+
+/// 重新导出筛选相关的数据结构
+///
+/// NullPolicy: 条件结果为 null 时的处理策略（视为 false / 保留 / 仅保留）
+/// FilterOperator: 结构化筛选运算符（比较、包含、为空、不为空、IN、BETWEEN、LIKE）
+/// FilterCondition: 单个结构化筛选条件
+/// ExportSubset: 导出时只导出的列和行
+pub use filter::{ExportSubset, FilterCondition, FilterOperator, NullPolicy};
+
+/// 重新导出序列缺口检测相关的数据结构
+///
+/// GapReport: 缺口汇总和明细
+/// SequenceGap: 单个缺口（前后已有值、缺失范围、缺失数量）
+pub use gaps::{GapReport, SequenceGap};
+
+/// 重新导出地理坐标相关的数据结构
+///
+/// CoordinateReport: 经纬度列校验结果
+/// GeoBucketMethod: 坐标分桶方式（网格 / Geohash）
+pub use geo::{CoordinateReport, GeoBucketMethod};
+
+/// 重新导出 Parquet 元数据相关的数据结构
+///
+/// ParquetMetadata: 文件概要（行数、写入程序、行组、列汇总）
+/// ParquetRowGroup / ParquetColumnChunk: 行组及列块的大小、压缩、编码和统计值
+/// ParquetColumnSummary: 单列在所有行组中的大小汇总
+pub use parquet_meta::{ParquetColumnChunk, ParquetColumnSummary, ParquetMetadata, ParquetRowGroup};
+
+/// 重新导出报告导出相关的数据结构
+///
+/// ExcelReportOptions: Excel 报告包含的汇总工作表
+/// AggregationSpec: 分组汇总导出中的单个聚合
+/// KpiSummarySpec / KpiMetric: KPI 卡片的指标定义
+/// KpiSummary / KpiValue: KPI 汇总结果
+/// ProfileReport / ColumnProfile / TopValue / ProfileReportFormat: 数据质量报告
+pub use report::{
+    AggregationSpec, ColumnProfile, ExcelReportOptions, KpiMetric, KpiSummary, KpiSummarySpec, KpiValue, ProfileReport,
+    ProfileReportFormat, TopValue,
+};
+
+/// 重新导出快照分享包相关的数据结构
+///
+/// SnapshotManifest: 快照包概要（格式版本、创建时间、数据集名称、行列数）
+/// SnapshotContents: 导入快照包后返回的内容
+/// HistoryBundleManifest / HistoryBundleEntry: 历史归档目录的清单
+/// RecipeCompatibilityReport / RecipeColumnIssue / RenameSuggestion: 操作记录与当前数据集的兼容性检查结果
+/// RecipeParameter / RecipeParameterKind: 操作记录的参数声明
+pub use snapshot::{
+    HistoryBundleEntry, HistoryBundleManifest, RecipeColumnIssue, RecipeCompatibilityReport, RecipeParameter,
+    RecipeParameterKind, RenameSuggestion, SnapshotContents, SnapshotManifest,
+};
+
+/// 重新导出后台任务相关的数据结构
+///
+/// TaskStatus: 任务状态（运行中 / 已完成 / 失败 / 已取消）
+/// TaskInfo: 任务进度快照
+pub use task::{TaskInfo, TaskStatus};
+
+/// 重新导出数据校验相关的数据结构
+///
+/// ValidationRule: 每一行都必须满足的 SQL 条件
+/// ValidationReport / RuleSummary / ValidationViolation: 校验报告
+/// ReportFormat: 报告文件格式（JSON / CSV）
+/// ValidationExportOptions: 导出时附带校验报告的选项
+pub use validation::{
+    ReportFormat, RuleSummary, ValidationExportOptions, ValidationReport, ValidationRule, ValidationViolation,
+};
+
+/// 重新导出源文件监视相关的数据结构
+///
+/// SourceChangeKind: 变化类型（修改 / 删除）
+/// SourceChangedEvent: "source://changed" 事件载荷
+pub use watch::{SourceChangeKind, SourceChangedEvent};
+
+/// 重新导出计划任务相关的数据结构
+///
+/// ScheduledJob: 计划任务配置（源文件、操作记录、导出路径、运行时间）
+/// JobSchedule: 运行时间（每天固定时间 / 固定间隔）
+/// ScheduledExportFormat: 导出格式（Parquet / CSV）
+/// ScheduledRunResult: 单次运行结果（"schedule://run" 事件载荷）
+/// ScheduledJobInfo: 任务配置和运行状态
+pub use schedule::{JobSchedule, ScheduledExportFormat, ScheduledJob, ScheduledJobInfo, ScheduledRunResult};
 
 // ============================================================================
 // 使用示例
@@ -148,6 +369,7 @@ pub use history::{HistoryEntry, HistoryEntryInfo, OperationType};
 //   name: string;
 //   dtype: string;
 //   null_count: number;
+//   summary?: { min: string | null; max: string | null; distinct_count: number; distinct_exact: boolean };
 // }
 //
 // interface DatasetInfo {
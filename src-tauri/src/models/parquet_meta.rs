@@ -0,0 +1,114 @@
+// ============================================================================
+// models/parquet_meta.rs - Parquet 文件元数据模型
+// ============================================================================
+// 这个文件定义了 Parquet 文件级元数据的数据结构：
+// - ParquetMetadata: 文件概要（行数、写入程序、行组列表、列汇总）
+// - ParquetRowGroup / ParquetColumnChunk: 行组及其中每一列的存储信息
+// - ParquetColumnSummary: 单列在所有行组中的大小汇总
+//
+// 用于排查文件为什么读取慢或体积大（行组过多、未压缩、编码不合适等）
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// 文件元数据
+// ============================================================================
+/// ParquetMetadata - Parquet 文件概要
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParquetMetadata {
+    /// 文件路径
+    pub file_path: String,
+
+    /// 文件大小（字节）
+    pub file_size: u64,
+
+    /// 总行数
+    pub num_rows: i64,
+
+    /// Parquet 格式版本
+    pub version: i32,
+
+    /// 写入程序（例如 "parquet-cpp-arrow version 14.0.0"）
+    pub created_by: Option<String>,
+
+    /// 行组列表
+    pub row_groups: Vec<ParquetRowGroup>,
+
+    /// 每列在所有行组中的汇总（按文件中的列顺序）
+    pub columns: Vec<ParquetColumnSummary>,
+}
+
+// ============================================================================
+// 行组
+// ============================================================================
+/// ParquetRowGroup - 单个行组
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParquetRowGroup {
+    /// 行组序号（从 0 开始）
+    pub index: usize,
+
+    /// 行数
+    pub num_rows: i64,
+
+    /// 未压缩大小（字节）
+    pub total_byte_size: i64,
+
+    /// 压缩后大小（字节）
+    pub compressed_size: i64,
+
+    /// 各列的存储信息
+    pub columns: Vec<ParquetColumnChunk>,
+}
+
+/// ParquetColumnChunk - 行组中单列的存储信息
+///
+/// min / max 为文件中记录的统计值（物理值的文本形式，例如日期列为天数），
+/// 写入程序没有记录统计信息时为 null
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParquetColumnChunk {
+    /// 列路径（嵌套列用 "." 连接）
+    pub column: String,
+
+    /// 物理类型（例如 INT64、BYTE_ARRAY）
+    pub physical_type: String,
+
+    /// 压缩方式（例如 SNAPPY、ZSTD(ZstdLevel(1))、UNCOMPRESSED）
+    pub compression: String,
+
+    /// 使用的编码（例如 PLAIN、RLE_DICTIONARY）
+    pub encodings: Vec<String>,
+
+    /// 压缩后大小（字节）
+    pub compressed_size: i64,
+
+    /// 未压缩大小（字节）
+    pub uncompressed_size: i64,
+
+    /// 空值数量
+    pub null_count: Option<u64>,
+
+    /// 最小值
+    pub min: Option<String>,
+
+    /// 最大值
+    pub max: Option<String>,
+}
+
+// ============================================================================
+// 列汇总
+// ============================================================================
+/// ParquetColumnSummary - 单列在所有行组中的汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParquetColumnSummary {
+    /// 列路径
+    pub column: String,
+
+    /// 压缩后大小合计（字节）
+    pub compressed_size: i64,
+
+    /// 未压缩大小合计（字节）
+    pub uncompressed_size: i64,
+
+    /// 压缩比（未压缩 / 压缩后，压缩后大小为 0 时为 null）
+    pub compression_ratio: Option<f64>,
+}
@@ -0,0 +1,274 @@
+// ============================================================================
+// models/report.rs - 报告导出相关数据模型
+// ============================================================================
+// 这个文件定义了 Excel 报告导出使用的数据结构：
+// - ExcelReportOptions: 报告包含哪些汇总工作表
+// - PivotSpec: 透视表汇总的配置
+// - AggregationSpec: 分组汇总导出中的单个聚合
+// - KpiSummarySpec / KpiSummary: 仪表盘 KPI 卡片的指标定义和结果
+// - ProfileReport: 数据质量报告（每列的统计信息和最常见的值）
+
+use crate::models::dataset::ColumnStats;
+use crate::models::history::ComparisonPeriod;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Excel 报告选项
+// ============================================================================
+/// ExcelReportOptions - Excel 报告包含的工作表
+///
+/// 工作表顺序：数据 → 描述统计 → 各列值计数 → 透视表
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "include_summary": true,
+///   "value_count_columns": ["region", "category"],
+///   "value_count_limit": 100,
+///   "pivot": { "index": ["region"], "columns": "month", "values": "sales", "aggregate": "sum" }
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExcelReportOptions {
+    /// 是否包含描述统计工作表（默认 true）
+    #[serde(default = "default_true")]
+    pub include_summary: bool,
+
+    /// 需要生成值计数工作表的分类列
+    #[serde(default)]
+    pub value_count_columns: Vec<String>,
+
+    /// 每个值计数工作表最多保留的行数（按次数降序，None 表示全部）
+    #[serde(default)]
+    pub value_count_limit: Option<usize>,
+
+    /// 透视表汇总（可选）
+    #[serde(default)]
+    pub pivot: Option<PivotSpec>,
+}
+
+impl Default for ExcelReportOptions {
+    fn default() -> Self {
+        Self {
+            include_summary: true,
+            value_count_columns: Vec::new(),
+            value_count_limit: None,
+            pivot: None,
+        }
+    }
+}
+
+/// PivotSpec - 透视表配置（与 pivot_data 命令参数一致）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PivotSpec {
+    /// 行索引列
+    pub index: Vec<String>,
+
+    /// 展开为列头的列
+    pub columns: String,
+
+    /// 值列
+    pub values: String,
+
+    /// 聚合函数（默认 "first"）
+    #[serde(default)]
+    pub aggregate: Option<String>,
+}
+
+/// AggregationSpec - 分组汇总导出中的单个聚合
+///
+/// 聚合函数与 aggregate_scalar 一致：
+/// sum, mean, median, min, max, std, var, first, last, count, null_count, n_unique, weighted_mean
+///
+/// 序列化示例：
+/// ```json
+/// { "column": "sales", "function": "sum", "alias": "total_sales" }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregationSpec {
+    /// 聚合的列
+    pub column: String,
+
+    /// 聚合函数
+    pub function: String,
+
+    /// 权重列（仅 weighted_mean 使用）
+    #[serde(default)]
+    pub weight_column: Option<String>,
+
+    /// 结果列名（默认 "{column}_{function}"）
+    #[serde(default)]
+    pub alias: Option<String>,
+}
+
+impl AggregationSpec {
+    /// 结果列名
+    pub fn output_name(&self) -> String {
+        self.alias
+            .clone()
+            .unwrap_or_else(|| format!("{}_{}", self.column, self.function))
+    }
+}
+
+// ============================================================================
+// KPI 汇总
+// ============================================================================
+/// KpiSummarySpec - 一组 KPI 指标（一次调用全部计算）
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "metrics": [
+///     { "name": "销售额", "column": "amount", "function": "sum" },
+///     { "name": "客单价", "expression": "SUM(amount) / COUNT(DISTINCT order_id)" }
+///   ],
+///   "comparison": { "date_column": "order_date", "period": "mom" }
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KpiSummarySpec {
+    /// 指标列表
+    pub metrics: Vec<KpiMetric>,
+
+    /// 与上一周期比较（可选）
+    #[serde(default)]
+    pub comparison: Option<KpiComparison>,
+}
+
+/// KpiMetric - 单个 KPI 指标（与 aggregate_scalar 参数一致）
+///
+/// expression 与 column + function 二选一
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KpiMetric {
+    /// 指标名称（显示在卡片上）
+    pub name: String,
+
+    /// 聚合的列
+    #[serde(default)]
+    pub column: Option<String>,
+
+    /// 聚合函数（sum, mean, median, min, max, count, n_unique, weighted_mean 等）
+    #[serde(default)]
+    pub function: Option<String>,
+
+    /// SQL 聚合表达式，例如 "SUM(amount) / COUNT(*)"
+    #[serde(default)]
+    pub expression: Option<String>,
+
+    /// 权重列（仅 weighted_mean 使用）
+    #[serde(default)]
+    pub weight_column: Option<String>,
+}
+
+/// KpiComparison - 与上一周期比较
+///
+/// 当前周期为截至参照日期（含）往前一个周期，上一周期为再往前一个周期，
+/// 例如参照日期 3 月 31 日、月环比：当前周期 (2 月 29 日, 3 月 31 日]，上一周期 (1 月 29 日, 2 月 29 日]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KpiComparison {
+    /// 日期列（Date / Datetime，日期时间按日期部分计算）
+    pub date_column: String,
+
+    /// 比较周期（wow / mom / yoy）
+    pub period: ComparisonPeriod,
+
+    /// 参照日期（YYYY-MM-DD，None 表示日期列中的最晚日期）
+    #[serde(default)]
+    pub reference_date: Option<String>,
+}
+
+/// KpiSummary - KPI 汇总结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KpiSummary {
+    /// 各指标的值（顺序与请求一致）
+    pub metrics: Vec<KpiValue>,
+
+    /// 当前周期（开始日期不含，结束日期含；没有比较时为 None）
+    pub current_period: Option<(String, String)>,
+
+    /// 上一周期（开始日期不含，结束日期含；没有比较时为 None）
+    pub previous_period: Option<(String, String)>,
+}
+
+/// KpiValue - 单个 KPI 的值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KpiValue {
+    /// 指标名称
+    pub name: String,
+
+    /// 当前值（有比较时为当前周期的值，否则为全部数据的值）
+    pub value: serde_json::Value,
+
+    /// 上一周期的值
+    pub previous: Option<serde_json::Value>,
+
+    /// 变化量（当前 - 上一周期，仅数值）
+    pub delta: Option<f64>,
+
+    /// 变化率（变化量 / |上一周期|，上一周期为 0 时为空）
+    pub change_ratio: Option<f64>,
+}
+
+// ============================================================================
+// 数据质量报告
+// ============================================================================
+/// ProfileReportFormat - 数据质量报告文件格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileReportFormat {
+    /// JSON（完整报告，便于程序读取和比较）
+    #[default]
+    Json,
+
+    /// HTML（单文件网页，可直接在浏览器中打开或分享）
+    Html,
+}
+
+/// ProfileReport - 数据质量报告
+///
+/// 包含每一列的统计信息（缺失、唯一值、分位数等，与 describe_dataset 相同）和最常见的值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileReport {
+    /// 数据集名称
+    pub dataset_name: String,
+
+    /// 生成报告的应用版本
+    pub app_version: String,
+
+    /// 生成时间（RFC 3339）
+    pub created_at: String,
+
+    /// 总行数
+    pub rows: usize,
+
+    /// 总列数
+    pub columns: usize,
+
+    /// 各列的统计信息（按列顺序）
+    pub profiles: Vec<ColumnProfile>,
+}
+
+/// ColumnProfile - 单列的统计信息和最常见的值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnProfile {
+    /// 列统计信息（展开到同一层）
+    #[serde(flatten)]
+    pub stats: ColumnStats,
+
+    /// 最常见的值（按次数降序，列表、结构体列为空）
+    pub top_values: Vec<TopValue>,
+}
+
+/// TopValue - 最常见的值及其次数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopValue {
+    /// 值的文本表示（空值为 None）
+    pub value: Option<String>,
+
+    /// 出现次数
+    pub count: usize,
+}
+
+fn default_true() -> bool {
+    true
+}
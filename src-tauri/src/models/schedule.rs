@@ -0,0 +1,145 @@
+// ============================================================================
+// models/schedule.rs - 计划任务相关数据模型
+// ============================================================================
+// 这个文件定义了计划任务（定时重新导入 → 执行操作记录 → 导出）使用的数据结构：
+// - ScheduledJob: 任务配置（保存在设置目录的 schedules.json 中）
+// - JobSchedule: 运行时间（每天固定时间 / 固定间隔）
+// - ScheduledExportFormat: 导出格式
+// - ScheduledRunResult: 单次运行结果，也是 "schedule://run" 事件载荷
+// - ScheduledJobInfo: 返回给前端的任务状态（配置 + 下次运行时间 + 上次运行结果）
+
+use crate::models::{CsvImportOptions, HistoryEntryInfo, RecipeParameter};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+
+/// ScheduledJob - 计划任务
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "id": "daily-sales",
+///   "name": "每日销售汇总",
+///   "enabled": true,
+///   "source_path": "/data/daily_sales.csv",
+///   "recipe": [ ... ],
+///   "parameters": [],
+///   "values": {},
+///   "output_path": "/data/out/daily_sales.parquet",
+///   "format": "parquet",
+///   "schedule": { "kind": "daily", "time": "07:00" }
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    /// 任务 ID（保存时为空则自动生成）
+    #[serde(default)]
+    pub id: String,
+
+    /// 任务名称
+    pub name: String,
+
+    /// 是否启用（默认 true）
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// 每次运行时重新读取的源文件（按扩展名选择读取方式，与 reload_from_source 相同）
+    pub source_path: String,
+
+    /// CSV 导入选项（可选，只对 CSV 源文件生效；没有时沿用操作记录中导入步骤的选项）
+    #[serde(default)]
+    pub csv_options: Option<CsvImportOptions>,
+
+    /// 操作记录（第一条为导入，之后的步骤按顺序执行；为空表示只转换格式）
+    #[serde(default)]
+    pub recipe: Vec<HistoryEntryInfo>,
+
+    /// 操作记录的参数声明
+    #[serde(default)]
+    pub parameters: Vec<RecipeParameter>,
+
+    /// 参数值（按参数名，未给出的参数使用默认值）
+    #[serde(default)]
+    pub values: HashMap<String, JsonValue>,
+
+    /// 导出文件路径（每次运行覆盖）
+    pub output_path: String,
+
+    /// 导出格式（默认 Parquet）
+    #[serde(default)]
+    pub format: ScheduledExportFormat,
+
+    /// 运行时间
+    pub schedule: JobSchedule,
+}
+
+/// JobSchedule - 计划任务的运行时间（本地时间）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JobSchedule {
+    /// 每天固定时间运行（"HH:MM"）
+    Daily { time: String },
+
+    /// 每隔固定分钟数运行（从应用启动或保存任务时开始计算）
+    Interval { minutes: u64 },
+}
+
+/// ScheduledExportFormat - 计划任务的导出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduledExportFormat {
+    /// Parquet（默认）
+    #[default]
+    Parquet,
+
+    /// CSV（UTF-8，带表头）
+    Csv,
+}
+
+/// ScheduledRunResult - 计划任务的一次运行结果（"schedule://run" 事件载荷）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledRunResult {
+    /// 任务 ID
+    pub job_id: String,
+
+    /// 任务名称
+    pub job_name: String,
+
+    /// 是否成功
+    pub success: bool,
+
+    /// 开始时间（ISO 8601 格式）
+    pub started_at: String,
+
+    /// 结束时间（ISO 8601 格式）
+    pub finished_at: String,
+
+    /// 导出的行数（失败时为 None）
+    pub rows: Option<usize>,
+
+    /// 导出文件路径
+    pub output_path: String,
+
+    /// 失败原因
+    pub error: Option<String>,
+}
+
+/// ScheduledJobInfo - 计划任务及其运行状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledJobInfo {
+    /// 任务配置
+    pub job: ScheduledJob,
+
+    /// 下次运行时间（ISO 8601 格式，未启用时为 None）
+    pub next_run: Option<String>,
+
+    /// 是否正在运行
+    pub running: bool,
+
+    /// 上次运行结果（本次启动后还没有运行过时为 None）
+    pub last_run: Option<ScheduledRunResult>,
+}
+
+fn default_true() -> bool {
+    true
+}
@@ -0,0 +1,291 @@
+// ============================================================================
+// models/snapshot.rs - 快照分享包数据模型
+// ============================================================================
+// 这个文件定义了快照分享包（.zip）中各部分的数据结构：
+// - SnapshotManifest: 包的概要（格式版本、创建时间、数据集名称、行列数）
+// - SnapshotContents: 导入快照包后返回给前端的内容
+//
+// 快照包中的文件：
+// - manifest.json: SnapshotManifest
+// - data.parquet: 当前数据
+// - recipe.json: 从导入到当前状态的操作历史（HistoryEntryInfo 列表）
+// - parameters.json: 操作记录的参数声明（RecipeParameter 列表）
+// - profile.json: 各列的描述统计（ColumnStats 列表）
+// - charts.json: 图表配置（ChartConfig 列表）
+// - display.json: 显示设置（列格式、条件格式、视图状态）
+//
+// 以及历史归档目录（export_history_bundle）的清单：
+// - HistoryBundleManifest / HistoryBundleEntry: 每个历史条目的操作、时间和对应的 Parquet 文件
+//
+// 以及操作记录兼容性检查（check_recipe_compatibility）的结果：
+// - RecipeCompatibilityReport: 缺失的列、类型不一致的列、多出的列和改名建议
+//
+// 以及操作记录的参数（RecipeParameter）：把某些步骤的参数声明为可替换的值，
+// 同一份操作记录可以用不同的阈值、日期范围、列名用于相似的文件
+
+use crate::models::chart::ChartConfig;
+use crate::models::{ColumnStats, DatasetInfo, DisplaySettings, HistoryEntryInfo};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+// ============================================================================
+// 快照包概要
+// ============================================================================
+/// SnapshotManifest - 快照包概要
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "format_version": 1,
+///   "app_version": "0.1.1",
+///   "created_at": "2024-01-01T12:00:00+00:00",
+///   "dataset_name": "sales_data.csv",
+///   "rows": 1000,
+///   "columns": 12
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// 快照包格式版本（读取时检查，高于当前版本的包无法导入）
+    pub format_version: u32,
+
+    /// 创建快照包的应用版本
+    pub app_version: String,
+
+    /// 创建时间（ISO 8601 格式）
+    pub created_at: String,
+
+    /// 数据集名称（原始文件名）
+    pub dataset_name: String,
+
+    /// 行数
+    pub rows: usize,
+
+    /// 列数
+    pub columns: usize,
+}
+
+// ============================================================================
+// 快照包内容
+// ============================================================================
+/// SnapshotContents - 导入快照包后返回的内容
+///
+/// 数据和显示设置已经恢复到数据存储中，
+/// 其余部分（操作历史、描述统计、图表配置）交给前端展示或恢复
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotContents {
+    /// 快照包概要
+    pub manifest: SnapshotManifest,
+
+    /// 导入后的数据集元信息
+    pub dataset: DatasetInfo,
+
+    /// 创建快照时的操作历史（只读记录，不会重新执行）
+    #[serde(default)]
+    pub recipe: Vec<HistoryEntryInfo>,
+
+    /// 操作记录的参数声明
+    #[serde(default)]
+    pub parameters: Vec<RecipeParameter>,
+
+    /// 创建快照时的描述统计
+    #[serde(default)]
+    pub profile: Vec<ColumnStats>,
+
+    /// 图表配置
+    #[serde(default)]
+    pub charts: Vec<ChartConfig>,
+}
+
+// ============================================================================
+// 历史归档清单
+// ============================================================================
+/// HistoryBundleEntry - 历史归档中的一个历史条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryBundleEntry {
+    /// 在历史中的位置（从 0 开始，0 为导入时的状态）
+    pub index: usize,
+
+    /// 该条目数据的 Parquet 文件名（相对于归档目录）
+    pub data_file: String,
+
+    /// 该操作删除的行的 Parquet 文件名（None 表示没有记录被删除的行）
+    #[serde(default)]
+    pub removed_rows_file: Option<String>,
+
+    /// 行数
+    pub rows: usize,
+
+    /// 列数
+    pub columns: usize,
+
+    /// 历史条目信息（操作、时间、描述等）
+    pub entry: HistoryEntryInfo,
+
+    /// 该条目上的显示设置
+    #[serde(default)]
+    pub display: DisplaySettings,
+}
+
+/// HistoryBundleManifest - 历史归档目录中的 manifest.json
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "format_version": 1,
+///   "app_version": "0.1.1",
+///   "created_at": "2024-01-01T12:00:00+00:00",
+///   "dataset_name": "sales_data.csv",
+///   "current_index": 3,
+///   "entries": [
+///     { "index": 0, "data_file": "entry_000.parquet", "rows": 1000, "columns": 12, "entry": { ... } }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryBundleManifest {
+    /// 归档格式版本
+    pub format_version: u32,
+
+    /// 创建归档的应用版本
+    pub app_version: String,
+
+    /// 创建时间（ISO 8601 格式）
+    pub created_at: String,
+
+    /// 数据集名称（原始文件名）
+    pub dataset_name: String,
+
+    /// 归档时的当前历史位置（之后的条目是已撤销、可以重做的操作）
+    pub current_index: Option<usize>,
+
+    /// 全部历史条目（按时间顺序）
+    pub entries: Vec<HistoryBundleEntry>,
+}
+
+// ============================================================================
+// 操作记录兼容性
+// ============================================================================
+/// RecipeCompatibilityReport - 操作记录（recipe）与当前数据集的兼容性检查结果
+///
+/// 把快照包中的操作记录用于新文件之前，先对比操作记录导入时的列和当前数据集的列，
+/// 一次性列出所有问题，而不是在执行到中途某一步时才失败
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecipeCompatibilityReport {
+    /// 是否兼容（没有被操作用到的缺失列，也没有被操作用到的类型不一致的列）
+    pub compatible: bool,
+
+    /// 操作记录中的步骤数（不包括导入）
+    pub step_count: usize,
+
+    /// 缺失的列（操作记录导入时有，当前数据集没有）
+    pub missing_columns: Vec<RecipeColumnIssue>,
+
+    /// 类型不一致的列
+    pub type_mismatches: Vec<RecipeColumnIssue>,
+
+    /// 当前数据集中多出的列（操作记录导入时没有）
+    pub extra_columns: Vec<String>,
+
+    /// 缺失列的改名建议（按列名相似度在多出的列中查找）
+    pub suggested_renames: Vec<RenameSuggestion>,
+}
+
+/// RecipeColumnIssue - 缺失或类型不一致的列
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecipeColumnIssue {
+    /// 列名
+    pub column: String,
+
+    /// 操作记录导入时的类型
+    pub expected_dtype: String,
+
+    /// 当前数据集中的类型（缺失时为 None）
+    pub actual_dtype: Option<String>,
+
+    /// 用到这一列的步骤（"第 N 步: 操作描述"，为空表示没有步骤直接用到，不影响执行）
+    pub used_by_steps: Vec<String>,
+}
+
+/// RenameSuggestion - 缺失列的改名建议
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameSuggestion {
+    /// 操作记录中的列名
+    pub expected: String,
+
+    /// 当前数据集中最相似的列名
+    pub candidate: String,
+
+    /// 列名相似度（0 ~ 1）
+    pub similarity: f64,
+}
+
+// ============================================================================
+// 操作记录参数
+// ============================================================================
+/// RecipeParameter - 操作记录的参数声明
+///
+/// 参数绑定到一个或多个步骤的操作参数上，替换时按 JSON Pointer 写入新值
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "name": "threshold",
+///   "description": "最低销售额",
+///   "kind": "number",
+///   "default": 100,
+///   "bindings": [{ "step": 3, "path": "/conditions/0/value" }]
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecipeParameter {
+    /// 参数名（在同一份操作记录中唯一）
+    pub name: String,
+
+    /// 参数说明（显示给用户）
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// 参数类型
+    pub kind: RecipeParameterKind,
+
+    /// 默认值（None 表示使用第一个绑定位置上记录的值）
+    #[serde(default)]
+    pub default: Option<JsonValue>,
+
+    /// 参数绑定的位置
+    pub bindings: Vec<RecipeParameterBinding>,
+}
+
+/// RecipeParameterKind - 参数类型（替换前检查传入的值）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecipeParameterKind {
+    /// 文本
+    Text,
+
+    /// 数值
+    Number,
+
+    /// 布尔值
+    Boolean,
+
+    /// 日期（YYYY-MM-DD）
+    Date,
+
+    /// 单个列名
+    Column,
+
+    /// 多个列名
+    Columns,
+}
+
+/// RecipeParameterBinding - 参数绑定的位置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecipeParameterBinding {
+    /// 步骤序号（从 1 开始，与兼容性检查中的"第 N 步"一致；第 0 条为导入）
+    pub step: usize,
+
+    /// 操作参数中的 JSON Pointer（例如 "/threshold"、"/columns/0"）
+    pub path: String,
+}
@@ -0,0 +1,72 @@
+// ============================================================================
+// models/task.rs - 后台任务相关数据模型
+// ============================================================================
+// 这个文件定义了长时间运行任务（导出等）的状态信息：
+// - TaskStatus: 任务状态
+// - TaskInfo: 任务进度快照（通过事件推送给前端，也可以主动查询）
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// 任务状态
+// ============================================================================
+/// TaskStatus - 后台任务状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskStatus {
+    /// 运行中
+    Running,
+
+    /// 已完成
+    Completed,
+
+    /// 失败（message 中包含错误信息）
+    Failed,
+
+    /// 已取消
+    Cancelled,
+}
+
+impl TaskStatus {
+    /// 任务是否已经结束（完成、失败或取消）
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, TaskStatus::Running)
+    }
+}
+
+// ============================================================================
+// 任务进度
+// ============================================================================
+/// TaskInfo - 后台任务进度快照
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "id": "export-1",
+///   "kind": "export_csv",
+///   "status": "running",
+///   "processed_rows": 150000,
+///   "total_rows": 1000000,
+///   "message": null
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskInfo {
+    /// 任务 ID（前端传入或自动生成的 UUID）
+    pub id: String,
+
+    /// 任务类型（例如 "export_csv"、"export_parquet"）
+    pub kind: String,
+
+    /// 当前状态
+    pub status: TaskStatus,
+
+    /// 已处理的行数
+    pub processed_rows: usize,
+
+    /// 总行数（未知时为 None）
+    pub total_rows: Option<usize>,
+
+    /// 失败时的错误信息
+    pub message: Option<String>,
+}
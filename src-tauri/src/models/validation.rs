@@ -0,0 +1,129 @@
+// ============================================================================
+// models/validation.rs - 数据校验相关数据模型
+// ============================================================================
+// 这个文件定义了数据校验规则和校验报告：
+// - ValidationRule: 每一行都必须满足的 SQL 条件
+// - ValidationReport: 校验结果（每条规则的违规数量和违规行）
+// - ValidationExportOptions: 导出时附带校验报告的选项
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// 校验规则
+// ============================================================================
+/// ValidationRule - 校验规则
+///
+/// expression 是每一行都必须满足的 SQL 条件（不含 "WHERE"）
+/// 条件结果为 null 时视为违规，需要允许空值时可以写成 "amount IS NULL OR amount > 0"
+///
+/// 序列化示例：
+/// ```json
+/// { "name": "金额为正", "expression": "amount > 0" }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationRule {
+    /// 规则名称（出现在报告中）
+    pub name: String,
+
+    /// 每一行都必须满足的 SQL 条件
+    pub expression: String,
+}
+
+// ============================================================================
+// 校验报告
+// ============================================================================
+/// ValidationReport - 校验报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationReport {
+    /// 校验的总行数
+    pub total_rows: usize,
+
+    /// 违规总数（同一行违反多条规则时分别计数）
+    pub violation_count: usize,
+
+    /// 每条规则的汇总
+    pub rules: Vec<RuleSummary>,
+
+    /// 违规明细（按规则顺序，同一规则内按行号排列）
+    pub violations: Vec<ValidationViolation>,
+}
+
+impl ValidationReport {
+    /// 是否没有任何违规
+    pub fn is_clean(&self) -> bool {
+        self.violation_count == 0
+    }
+}
+
+/// RuleSummary - 单条规则的校验汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleSummary {
+    /// 规则名称
+    pub name: String,
+
+    /// SQL 条件
+    pub expression: String,
+
+    /// 违规行数
+    pub violation_count: usize,
+}
+
+/// ValidationViolation - 单条违规记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationViolation {
+    /// 违反的规则名称
+    pub rule: String,
+
+    /// 违规行号（从 0 开始）
+    pub row: usize,
+}
+
+// ============================================================================
+// 导出选项
+// ============================================================================
+/// ReportFormat - 校验报告文件格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    /// JSON（完整报告，包含汇总和明细）
+    #[default]
+    Json,
+
+    /// CSV（只包含违规明细：rule, expression, row）
+    Csv,
+}
+
+impl ReportFormat {
+    /// 文件扩展名
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Json => "json",
+            ReportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// ValidationExportOptions - 导出时的校验选项
+///
+/// 报告写在导出文件旁边，文件名为 "{导出文件名}.validation.{json|csv}"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationExportOptions {
+    /// 校验规则
+    pub rules: Vec<ValidationRule>,
+
+    /// 是否写出校验报告（默认 true）
+    #[serde(default = "default_true")]
+    pub write_report: bool,
+
+    /// 报告格式（默认 JSON）
+    #[serde(default)]
+    pub report_format: ReportFormat,
+
+    /// 存在违规时是否拒绝导出（默认 false）
+    #[serde(default)]
+    pub refuse_on_violations: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
@@ -0,0 +1,41 @@
+// ============================================================================
+// models/watch.rs - 源文件监视相关数据模型
+// ============================================================================
+// 这个文件定义了源文件变化时推送给前端的事件载荷：
+// - SourceChangeKind: 变化类型
+// - SourceChangedEvent: "source://changed" 事件载荷
+
+use serde::{Deserialize, Serialize};
+
+/// SourceChangeKind - 源文件的变化类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceChangeKind {
+    /// 文件内容被修改（包括编辑器先写临时文件再重命名覆盖）
+    Modified,
+
+    /// 文件被删除或移走
+    Removed,
+}
+
+/// SourceChangedEvent - 源文件变化事件
+///
+/// 序列化示例：
+/// ```json
+/// {
+///   "path": "/data/daily_sales.csv",
+///   "kind": "modified",
+///   "detected_at": "2024-03-05T08:30:00+00:00"
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceChangedEvent {
+    /// 被监视的源文件路径
+    pub path: String,
+
+    /// 变化类型
+    pub kind: SourceChangeKind,
+
+    /// 检测到变化的时间（ISO 8601 格式）
+    pub detected_at: String,
+}